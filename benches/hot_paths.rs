@@ -0,0 +1,162 @@
+//! Benchmarks for the refresh and query hot paths over a 2k-model fixture.
+//!
+//! Run with `cargo bench`. Compare against a saved baseline with
+//! `cargo bench -- --save-baseline main` / `--baseline main`.
+
+use criterion::{black_box, criterion_group, criterion_main, BatchSize, Criterion};
+use serde_json::json;
+use which_llm::commands::cost::CostRow;
+use which_llm::config::ParquetConfig;
+use which_llm::output::{format_output, OutputFormat};
+use which_llm::parquet::{write_benchmarks_parquet, ParquetSession};
+use which_llm::query::QueryExecutor;
+use which_llm::sources::artificial_analysis::models::{AaExtraRow, AaLlmModel, AaLlmRow};
+use which_llm::sources::models_dev::models::{flatten_response, ModelsDevResponse};
+
+/// Number of models in each fixture.
+const MODELS: usize = 2_000;
+
+/// AA API models with evaluations, pricing and one untyped field each.
+fn aa_models() -> Vec<AaLlmModel> {
+    (0..MODELS)
+        .map(|i| {
+            serde_json::from_value(json!({
+                "id": format!("id-{i}"),
+                "name": format!("Model {i}"),
+                "slug": format!("model-{i}"),
+                "release_date": "2025-01-15",
+                "model_creator": {
+                    "id": format!("creator-{}", i % 40),
+                    "name": format!("Creator {}", i % 40),
+                    "slug": format!("creator-{}", i % 40),
+                },
+                "evaluations": {
+                    "artificial_analysis_intelligence_index": (i % 70) as f64,
+                    "artificial_analysis_coding_index": (i % 60) as f64,
+                    "mmlu_pro": 0.7,
+                    "gpqa": 0.6,
+                },
+                "pricing": {
+                    "price_1m_input_tokens": 0.5 + (i % 10) as f64,
+                    "price_1m_output_tokens": 2.0 + (i % 10) as f64,
+                    "price_1m_blended_3_to_1": 1.0 + (i % 10) as f64,
+                },
+                "median_output_tokens_per_second": 120.0,
+                "median_time_to_first_token_seconds": 0.4,
+                "context_window": 128_000,
+            }))
+            .expect("valid AA fixture")
+        })
+        .collect()
+}
+
+/// models.dev response spread over 20 providers.
+fn models_dev_response() -> ModelsDevResponse {
+    let mut providers = serde_json::Map::new();
+    for p in 0..20 {
+        let models: serde_json::Map<_, _> = (0..MODELS / 20)
+            .map(|i| {
+                let id = format!("model-{}", p * (MODELS / 20) + i);
+                let model = json!({
+                    "id": id,
+                    "name": id,
+                    "tool_call": true,
+                    "reasoning": i % 2 == 0,
+                    "release_date": "2025-01",
+                    "limit": { "context": 128_000, "output": 8_192 },
+                    "cost": { "input": 1.0, "output": 4.0 },
+                });
+                (id, model)
+            })
+            .collect();
+        providers.insert(
+            format!("provider-{p}"),
+            json!({
+                "id": format!("provider-{p}"),
+                "name": format!("Provider {p}"),
+                "models": models,
+            }),
+        );
+    }
+    serde_json::from_value(serde_json::Value::Object(providers)).expect("valid models.dev fixture")
+}
+
+fn session() -> ParquetSession {
+    ParquetSession::new(&ParquetConfig::default()).expect("parquet session")
+}
+
+fn bench_merge(c: &mut Criterion) {
+    let aa = aa_models();
+    let response = models_dev_response();
+
+    c.bench_function("merge/aa_rows", |b| {
+        b.iter(|| {
+            let rows: Vec<AaLlmRow> = aa.iter().map(AaLlmRow::from).collect();
+            let extra: Vec<AaExtraRow> = aa.iter().flat_map(AaExtraRow::from_model).collect();
+            black_box((rows, extra))
+        })
+    });
+    c.bench_function("merge/models_dev_flatten", |b| {
+        b.iter(|| black_box(flatten_response(&response)))
+    });
+}
+
+fn bench_views(c: &mut Criterion) {
+    let dir = tempfile::tempdir().unwrap();
+    let rows: Vec<AaLlmRow> = aa_models().iter().map(AaLlmRow::from).collect();
+    write_benchmarks_parquet(&rows, &dir.path().join("benchmarks.parquet"), &session()).unwrap();
+    let executor = QueryExecutor::new(dir.path().to_path_buf());
+    let sql = "SELECT b.name, b.intelligence FROM benchmarks b \
+               WHERE b.intelligence > 40 AND b.creator IN \
+               (SELECT creator FROM benchmarks GROUP BY creator HAVING COUNT(*) > 10) \
+               ORDER BY b.intelligence DESC LIMIT 20";
+
+    c.bench_function("query/view_statements", |b| {
+        b.iter(|| black_box(executor.view_statements(black_box(sql))))
+    });
+}
+
+fn bench_parquet(c: &mut Criterion) {
+    let rows: Vec<AaLlmRow> = aa_models().iter().map(AaLlmRow::from).collect();
+    let session = session();
+
+    c.bench_function("parquet/write_benchmarks", |b| {
+        b.iter_batched(
+            || tempfile::tempdir().unwrap(),
+            |dir| {
+                write_benchmarks_parquet(&rows, &dir.path().join("benchmarks.parquet"), &session)
+                    .unwrap()
+            },
+            BatchSize::PerIteration,
+        )
+    });
+}
+
+fn bench_format(c: &mut Criterion) {
+    let rows: Vec<CostRow> = (0..MODELS)
+        .map(|i| CostRow {
+            name: format!("Model {i}"),
+            input_cost: format!("${:.4}", i as f64 * 0.001),
+            output_cost: format!("${:.4}", i as f64 * 0.004),
+            total_cost: format!("${:.4}", i as f64 * 0.005),
+        })
+        .collect();
+
+    for (name, format) in [
+        ("format/markdown", OutputFormat::Markdown),
+        ("format/table", OutputFormat::Table),
+        ("format/csv", OutputFormat::Csv),
+        ("format/json", OutputFormat::Json),
+    ] {
+        c.bench_function(name, |b| b.iter(|| black_box(format_output(&rows, format))));
+    }
+}
+
+criterion_group!(
+    benches,
+    bench_merge,
+    bench_views,
+    bench_parquet,
+    bench_format
+);
+criterion_main!(benches);
@@ -0,0 +1,1231 @@
+//! Entry point shared by the `which-llm` and `aa` binaries.
+
+use crate::{
+    cache::{self, Cache},
+    cli::{
+        model_args, parse_when, AuditCommands, CacheCommands, Cli, Commands, FormatArgs,
+        MediaCommands, NoteCommands, PolicyCommands, ProfileCommands, SchemaCommands,
+        SkillCommands, TagCommands,
+    },
+    client::{Client, HostedDataClient},
+    commands,
+    config::{self, Config},
+    error::{AppError, Result},
+    logging,
+    models::LlmModel,
+    notes::Notes,
+    output,
+    parquet::ParquetSession,
+    policy::{Policy, PolicyCheck},
+    remote::{DataChannel, RemoteDataClient},
+    schema::{self, BENCHMARKS},
+    snapshot::{self, Snapshot},
+    sources::{
+        artificial_analysis::{
+            index_version, percentiles::attach_percentiles, uncertainty::attach_intelligence_errors,
+        },
+        lifecycle,
+        models_dev::{
+            integration::{attach_integrations, load_integrations},
+            pricing::load_price_tiers,
+        },
+    },
+    strict,
+    utils::{sort_field, ModelSearch, ScoreWeights, SortField},
+};
+use chrono::Utc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+
+/// Parse the command line, run the command and exit with its error code
+/// on failure.
+pub fn run() {
+    let mut cli = Cli::parse_invoked();
+    if let Some(home) = &cli.home {
+        config::set_home(home.clone());
+    }
+    if let Some(channel) = &cli.channel {
+        DataChannel::select(channel.clone());
+    }
+    if let Some(key) = &cli.api_key {
+        // Masked before the arguments are written to the debug log
+        logging::register_secret(key);
+        config::set_api_key(key.clone());
+    }
+    cli.quiet |= cli.porcelain;
+    if cli.agent {
+        cli.quiet = true;
+        cli.errors_json = true;
+        output::json::enable_envelope();
+    }
+    if cli.attribution {
+        output::export::enable(export_meta());
+    }
+    if cli.ascii {
+        output::glyphs::force_ascii();
+    }
+
+    if let Err(e) = run_command(&cli) {
+        report(&cli, &e);
+        std::process::exit(e.exit_code());
+    }
+}
+
+/// Print an error on stderr, as JSON with `--errors-json`.
+fn report(cli: &Cli, e: &AppError) {
+    logging::debug("which-llm", format_args!("exit {}: {}", e.exit_code(), e));
+    if cli.errors_json {
+        eprintln!("{}", e.to_json());
+    } else {
+        eprintln!("Error: {}", e);
+    }
+}
+
+/// Attribution metadata with the timestamps of the cached hosted data.
+fn export_meta() -> output::export::ExportMeta {
+    let meta = Cache::new()
+        .and_then(|cache| RemoteDataClient::new(cache.base_dir().to_path_buf()))
+        .ok()
+        .and_then(|remote| remote.local_meta());
+    match meta {
+        Some(meta) => output::export::ExportMeta::new(meta.generated_at, Some(meta.fetched_at)),
+        None => output::export::ExportMeta::new(None, None),
+    }
+}
+
+/// A command, or one form of it, with its name for error messages.
+type CommandForm = (&'static str, fn(&Commands) -> bool);
+
+/// Commands that read benchmarks, and so honor `--as-of`, `--use-api` and
+/// `--profile`.
+const BENCHMARK_COMMANDS: &[CommandForm] = &[
+    ("compare", |c| matches!(c, Commands::Compare { .. })),
+    ("cost (tokens)", |c| {
+        matches!(
+            c,
+            Commands::Cost {
+                characters: None,
+                media: None,
+                ..
+            }
+        )
+    }),
+    ("get", |c| matches!(c, Commands::Get { .. })),
+    ("migrate-cost", |c| {
+        matches!(c, Commands::MigrateCost { .. })
+    }),
+    ("policy", |c| matches!(c, Commands::Policy { .. })),
+    ("report", |c| matches!(c, Commands::Report { .. })),
+    ("route", |c| matches!(c, Commands::Route { .. })),
+    ("recommend", |c| matches!(c, Commands::Recommend { .. })),
+    ("llms", |c| matches!(c, Commands::Llms { .. })),
+    ("shortlist", |c| matches!(c, Commands::Shortlist { .. })),
+];
+
+/// Commands besides [`BENCHMARK_COMMANDS`] that fetch benchmarks.
+const FETCH_COMMANDS: &[CommandForm] = &[
+    ("refresh", |c| matches!(c, Commands::Refresh { .. })),
+    ("changelog", |c| matches!(c, Commands::Changelog { .. })),
+];
+
+/// Commands besides those that fetch benchmarks that use the API key.
+const KEY_COMMANDS: &[CommandForm] = &[
+    ("quota", |c| matches!(c, Commands::Quota { .. })),
+    ("batch", |c| matches!(c, Commands::Batch { .. })),
+    ("profile create", |c| {
+        matches!(
+            c,
+            Commands::Profile {
+                command: ProfileCommands::Create { .. }
+            }
+        )
+    }),
+    ("profile show --effective", |c| {
+        matches!(
+            c,
+            Commands::Profile {
+                command: ProfileCommands::Show {
+                    effective: true,
+                    ..
+                }
+            }
+        )
+    }),
+];
+
+/// Commands that honor `--as-of`: those reading benchmarks, and `query`.
+fn as_of_commands() -> Vec<CommandForm> {
+    let mut commands: Vec<CommandForm> = Vec::new();
+    #[cfg(feature = "sql")]
+    commands.push(("query", |c| matches!(c, Commands::Query { .. })));
+    commands.extend_from_slice(BENCHMARK_COMMANDS);
+    commands
+}
+
+/// Commands that fetch benchmarks and so honor `--use-api` and `--profile`.
+fn api_commands() -> Vec<CommandForm> {
+    [FETCH_COMMANDS, BENCHMARK_COMMANDS].concat()
+}
+
+/// Whether `command` is one of `forms`.
+fn is_any(forms: &[CommandForm], command: &Commands) -> bool {
+    forms.iter().any(|(_, is)| is(command))
+}
+
+/// Names of `forms` for an error message.
+fn form_names(forms: &[CommandForm]) -> String {
+    forms
+        .iter()
+        .map(|(name, _)| *name)
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Whether a command reads benchmarks and so honors `--as-of`.
+fn supports_as_of(command: &Commands) -> bool {
+    is_any(&as_of_commands(), command)
+}
+
+/// Whether a command fetches benchmarks and so honors `--use-api` and `--profile`.
+fn supports_api(command: &Commands) -> bool {
+    is_any(&api_commands(), command)
+}
+
+/// Whether a command that loads benchmarks then reads the models.dev table
+/// (capabilities, context windows, modalities, integrations), so
+/// [`load_llm_models`] downloads both together.
+///
+/// Commands reading it through the model selection validate the selection
+/// before loading, so a typo fails before anything is fetched, and resolve
+/// it after loading, so they see the downloaded table.
+fn reads_models_dev(command: &Commands) -> bool {
+    match command {
+        Commands::Llms {
+            tool_call,
+            reasoning,
+            open_weights,
+            sort,
+            selection,
+            ..
+        } => {
+            *tool_call
+                || *reasoning
+                || *open_weights
+                || sort
+                    .as_deref()
+                    .and_then(sort_field)
+                    .is_some_and(SortField::reads_models)
+                || selection.reads_models()
+        }
+        Commands::Recommend {
+            needs,
+            tool_call,
+            selection,
+            ..
+        } => !needs.is_empty() || *tool_call || selection.reads_models(),
+        Commands::Shortlist {
+            needs, selection, ..
+        } => !needs.is_empty() || selection.reads_models(),
+        Commands::Compare { selection, .. }
+        | Commands::Report { selection, .. }
+        | Commands::Route { selection, .. } => selection.reads_models(),
+        Commands::Get { integration, .. } => *integration,
+        _ => false,
+    }
+}
+
+/// Whether a command reads cached tables, and so bootstraps the cache on the
+/// first run. A query only does when it names a table or opens the shell.
+fn reads_data(command: &Commands) -> bool {
+    match command {
+        #[cfg(feature = "sql")]
+        Commands::Query {
+            sql, interactive, ..
+        } => {
+            *interactive
+                || sql
+                    .as_deref()
+                    .is_some_and(|sql| !crate::query::referenced_tables(sql).is_empty())
+        }
+        Commands::Compare { .. }
+        | Commands::Cost { .. }
+        | Commands::Get { .. }
+        | Commands::MigrateCost { .. }
+        | Commands::Policy { .. }
+        | Commands::Report { .. }
+        | Commands::Route { .. }
+        | Commands::Recommend { .. }
+        | Commands::Llms { .. }
+        | Commands::Shortlist { .. }
+        | Commands::Open { .. }
+        | Commands::Unmatched { .. }
+        | Commands::Audit { .. }
+        | Commands::Media { .. }
+        | Commands::Stats { .. }
+        | Commands::Tokens { model: Some(_), .. } => true,
+        Commands::Note { command } => !matches!(command, NoteCommands::List { .. }),
+        Commands::Tag { command } => !matches!(command, TagCommands::List { .. }),
+        _ => false,
+    }
+}
+
+/// Download every table before the first command that reads data, unless
+/// `--no-bootstrap` or the cache is read-only. A pinned `--as-of` snapshot is never fetched.
+fn bootstrap(cli: &Cli) -> Result<()> {
+    if cli.no_bootstrap || cli.as_of.is_some() || cache::is_read_only() || !reads_data(&cli.command)
+    {
+        return Ok(());
+    }
+    let cache = Cache::new()?;
+    if !schema::cache_is_empty(cache.base_dir()) {
+        return Ok(());
+    }
+    tokio::runtime::Builder::new_multi_thread()
+        .enable_all()
+        .build()?
+        .block_on(commands::refresh::bootstrap(
+            cli.quiet,
+            cli.use_api,
+            cli.profile.as_deref(),
+        ));
+    Ok(())
+}
+
+/// Download the hosted tables a query reads that are not cached yet
+/// (`query --fetch`). The pinned benchmarks snapshot replaces that table.
+#[cfg(feature = "sql")]
+fn fetch_queried_tables(cli: &Cli, sql: &str, pinned: bool) -> Result<()> {
+    let cache = Cache::new()?;
+    let mut tables = commands::query::uncached_hosted_tables(sql, cache.base_dir());
+    if pinned {
+        tables.retain(|table| table.name != BENCHMARKS.name);
+    }
+    if tables.is_empty() || cache::is_read_only() {
+        return Ok(());
+    }
+    let remote = RemoteDataClient::new(cache.base_dir().to_path_buf())?;
+    let runtime = tokio::runtime::Builder::new_multi_thread()
+        .enable_all()
+        .build()?;
+    for table in tables {
+        if !cli.quiet {
+            eprintln!("Fetching {}...", table.name);
+        }
+        runtime.block_on(remote.ensure_parquet(table.name, false))?;
+    }
+    Ok(())
+}
+
+/// Resolve `--as-of` to a stored benchmarks snapshot.
+fn pinned_snapshot(cli: &Cli) -> Result<Option<Snapshot>> {
+    let Some(as_of) = cli.as_of.as_deref() else {
+        return Ok(None);
+    };
+    let date = parse_when("as-of", as_of)?;
+    let cache = Cache::new()?;
+    let snapshot = snapshot::resolve(cache.base_dir(), date)?;
+    if !cli.quiet {
+        eprintln!("Using benchmarks snapshot from {}", snapshot.date);
+        let current = index_version::load_index_version(cache.base_dir())
+            .ok()
+            .flatten();
+        if let Some(warning) = snapshot::version_mismatch_warning(&snapshot, current.as_deref()) {
+            eprintln!("{}", warning);
+        }
+    }
+    Ok(Some(snapshot))
+}
+
+/// Whether loaded benchmarks are kept for later commands (`batch`).
+static SHARE_MODELS: AtomicBool = AtomicBool::new(false);
+
+/// Benchmarks loaded so far in a batch, by `--use-api` and `--profile`.
+type ModelSource = (bool, Option<String>);
+static SHARED_MODELS: Mutex<Vec<(ModelSource, Vec<LlmModel>)>> = Mutex::new(Vec::new());
+
+/// Load LLM models from the `--as-of` snapshot, or the latest data (checked
+/// for freshness with `--strict`). With `columns`, only those benchmark
+/// columns are read.
+///
+/// Like `refresh`, data missing from the cache comes from the API with
+/// `--use-api`, and the API is the fallback when hosted data cannot be
+/// fetched and the profile has a key. Deprecated and retired models get
+/// their `status` set. Hosted benchmarks are fetched concurrently with the
+/// models.dev table when the command reads it (see [`reads_models_dev`]).
+async fn load_llm_models(cli: &Cli, columns: Option<&[&str]>) -> Result<Vec<LlmModel>> {
+    let client = HostedDataClient::new()?;
+    let cache_dir = client.cache().base_dir();
+    if let Some(snapshot) = pinned_snapshot(cli)? {
+        let mut llm_models = client.get_llm_models_at(&snapshot, columns)?;
+        lifecycle::attach_status(&mut llm_models, cache_dir, Some(&snapshot))?;
+        return Ok(llm_models);
+    }
+
+    // In a batch, every line shares one full load per data source
+    let share = SHARE_MODELS.load(Ordering::Relaxed);
+    let source = (cli.use_api, cli.profile.clone());
+    let columns = if share { None } else { columns };
+    if share {
+        let shared = SHARED_MODELS.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some((_, llm_models)) = shared.iter().find(|(key, _)| *key == source) {
+            if cli.strict {
+                strict::ensure_fresh(&[&BENCHMARKS], cache_dir)?;
+            }
+            return Ok(llm_models.clone());
+        }
+    }
+    let mut llm_models = if cli.use_api {
+        api_client(cli)?.get_llm_models(false).await?
+    } else {
+        // A missing or stale models.dev table is downloaded alongside, for
+        // commands that read it; the command reports it if still missing
+        let models_dev = async {
+            if reads_models_dev(&cli.command) {
+                client.ensure_models().await
+            } else {
+                Ok(())
+            }
+        };
+        let (llm_models, models_dev) =
+            tokio::join!(client.get_llm_models_projected(false, columns), models_dev);
+        if let Err(e) = models_dev {
+            logging::debug("cache", format_args!("models.parquet: {}", e));
+        }
+        match llm_models {
+            Ok(llm_models) => llm_models,
+            Err(e) => match api_client(cli) {
+                Ok(api) => {
+                    if !cli.quiet {
+                        eprintln!(
+                            "Warning: Could not fetch hosted data ({}). Falling back to API.",
+                            e
+                        );
+                    }
+                    api.get_llm_models(false).await?
+                }
+                Err(_) => return Err(e),
+            },
+        }
+    };
+    if cli.strict {
+        strict::ensure_fresh(&[&BENCHMARKS], cache_dir)?;
+    }
+    lifecycle::attach_status(&mut llm_models, cache_dir, None)?;
+    if share {
+        SHARED_MODELS
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .push((source, llm_models.clone()));
+    }
+    Ok(llm_models)
+}
+
+/// API client for the `--profile` (or default) API key.
+fn api_client(cli: &Cli) -> Result<Client> {
+    let config = Config::load()?;
+    let session = ParquetSession::new(&config.parquet)?;
+    Client::for_profile(&config, cli.profile.as_deref(), session)
+}
+
+/// Print what the command would fetch, read and write (`--plan`).
+fn run_plan(cli: &Cli) -> Result<()> {
+    let cache = Cache::new()?;
+    let remote = RemoteDataClient::new(cache.base_dir().to_path_buf())?;
+    let snapshot = pinned_snapshot(cli)?;
+    let ctx = commands::plan::PlanContext {
+        cache_dir: cache.base_dir(),
+        data_fresh: remote.is_data_fresh(),
+        channel: remote.channel(),
+        use_api: cli.use_api,
+        snapshot: snapshot.as_ref(),
+        today: Utc::now().date_naive(),
+    };
+    commands::plan::run(&cli.command, &ctx)
+}
+
+/// Run every command in a batch file, stopping at the first failure unless
+/// `keep_going`.
+///
+/// The batch's global flags (`--quiet`, `--strict`, `--plan`, ...) apply to
+/// every line, and benchmarks are loaded once for all of them.
+///
+/// # Errors
+///
+/// Returns the failing line's error, or with `keep_going`, once every line
+/// has run, `AppError::Batch` with the first failure's exit code.
+fn run_batch(cli: &Cli, file: &str, keep_going: bool) -> Result<()> {
+    let lines = commands::batch::read(file)?;
+    SHARE_MODELS.store(true, Ordering::Relaxed);
+
+    let mut failed = 0;
+    let mut first_failure = None;
+    for line in lines {
+        let mut line_cli = line.cli;
+        line_cli.quiet |= cli.quiet || line_cli.porcelain;
+        line_cli.porcelain |= cli.porcelain;
+        line_cli.errors_json |= cli.errors_json;
+        line_cli.strict |= cli.strict;
+        line_cli.fail_on_empty |= cli.fail_on_empty;
+        line_cli.plan |= cli.plan;
+        line_cli.agent |= cli.agent;
+        line_cli.no_bootstrap |= cli.no_bootstrap;
+        if line_cli.agent {
+            line_cli.quiet = true;
+            line_cli.errors_json = true;
+        }
+
+        if !line_cli.quiet {
+            eprintln!("==> {}", line.text);
+        }
+        if let Err(e) = execute(&line_cli) {
+            if !keep_going {
+                if !cli.errors_json {
+                    eprintln!("Line {} failed: {}", line.number, line.text);
+                }
+                return Err(e);
+            }
+            if !line_cli.errors_json {
+                eprintln!("Line {} failed: {}", line.number, line.text);
+            }
+            report(&line_cli, &e);
+            failed += 1;
+            first_failure.get_or_insert(e.exit_code());
+        }
+    }
+    match first_failure {
+        Some(code) => Err(AppError::Batch { failed, code }),
+        None => Ok(()),
+    }
+}
+
+/// Start the debug trace log from `--log-file` or the config's `log_file`.
+fn init_logging(cli: &Cli) -> Result<()> {
+    // A broken config is reported by the command that needs it, not here
+    let path = match &cli.log_file {
+        Some(path) => Some(path.clone()),
+        None => Config::load().ok().and_then(|config| config.log_file),
+    };
+    match path {
+        Some(path) => logging::init(&path),
+        None => Ok(()),
+    }
+}
+
+/// Run the command, starting the async runtime only for commands that need it.
+fn run_command(cli: &Cli) -> Result<()> {
+    init_logging(cli)?;
+    cache::recover()?;
+    if let Some(format) = &cli.date_format {
+        output::dates::set_format(format.parse()?);
+    }
+    execute(cli)
+}
+
+/// Run one command; `batch` runs each of its lines through here.
+fn execute(cli: &Cli) -> Result<()> {
+    if cli.as_of.is_some() && !supports_as_of(&cli.command) {
+        return Err(AppError::Config(format!(
+            "--as-of only applies to commands that read benchmarks: {}",
+            form_names(&as_of_commands())
+        )));
+    }
+    let selects_profile = matches!(
+        cli.command,
+        Commands::Quota { .. }
+            | Commands::Profile {
+                command: ProfileCommands::Show {
+                    effective: true,
+                    ..
+                }
+            }
+    ) && !cli.use_api;
+    if (cli.use_api || cli.profile.is_some()) && !supports_api(&cli.command) && !selects_profile {
+        return Err(AppError::Config(format!(
+            "--use-api and --profile only apply to commands that fetch benchmarks: {}",
+            form_names(&api_commands())
+        )));
+    }
+    if cli.api_key.is_some() && !supports_api(&cli.command) && !is_any(KEY_COMMANDS, &cli.command) {
+        return Err(AppError::Config(format!(
+            "--api-key only applies to commands that call the API: {}",
+            form_names(&[api_commands().as_slice(), KEY_COMMANDS].concat())
+        )));
+    }
+    if let Commands::Batch { file, keep_going } = &cli.command {
+        return run_batch(cli, file, *keep_going);
+    }
+    if cli.plan {
+        return run_plan(cli);
+    }
+    bootstrap(cli)?;
+
+    // Commands that only read the local cache run without starting the async runtime
+    match &cli.command {
+        // Query command - primary interface
+        #[cfg(feature = "sql")]
+        Commands::Query {
+            interactive: true,
+            format,
+            ..
+        } => {
+            let format = cli.output_format(format);
+            let snapshot = pinned_snapshot(cli)?;
+            commands::repl::run(format, snapshot.as_ref())
+        }
+        #[cfg(feature = "sql")]
+        Commands::Query {
+            sql, fetch, format, ..
+        } => {
+            let format = cli.output_format(format);
+            let snapshot = pinned_snapshot(cli)?;
+            if let (Some(sql), true) = (sql, *fetch) {
+                fetch_queried_tables(cli, sql, snapshot.is_some())?;
+            }
+            commands::query::run(
+                sql.as_deref(),
+                false,
+                format,
+                cli.strict,
+                cli.fail_on_empty,
+                cli.quiet,
+                snapshot.as_ref(),
+            )
+        }
+
+        // Tables command - list available tables
+        Commands::Tables { table, verbose } => commands::tables::run(table.as_deref(), *verbose),
+
+        // Schema command - dump or diff table schemas
+        Commands::Schema {
+            command: SchemaCommands::Dump,
+        } => commands::schema::run_dump(),
+        Commands::Schema {
+            command: SchemaCommands::Diff { old, format },
+        } => {
+            let format = cli.output_format(format);
+            commands::schema::run_diff(old, format, cli.fail_on_empty)
+        }
+
+        // Open command - open-weight models preset
+        Commands::Open {
+            limit,
+            all,
+            sort,
+            asc,
+            top_per,
+            new_only,
+            age,
+            format,
+        } => {
+            let format = cli.output_format(format);
+            let released_after = new_only
+                .as_deref()
+                .map(|s| parse_when("new-only", s))
+                .transpose()?;
+            let options = commands::open::OpenOptions {
+                limit: (!*all).then_some(*limit),
+                sort: commands::open::OpenSort {
+                    column: sort.clone(),
+                    ascending: *asc,
+                },
+                top_per: top_per.as_deref().map(str::parse).transpose()?,
+                released_after,
+                age: *age || released_after.is_some(),
+            };
+            commands::open::run(&options, format, cli.fail_on_empty, cli.quiet)
+        }
+
+        // Unmatched command - models.dev models missing from benchmarks
+        Commands::Unmatched { limit, all, format } => {
+            let format = cli.output_format(format);
+            let limit = (!*all).then_some(*limit);
+            commands::unmatched::run(limit, format, cli.fail_on_empty, cli.quiet)
+        }
+
+        // Audit command - cross-check AA against models.dev
+        Commands::Audit {
+            command:
+                AuditCommands::Pricing {
+                    threshold,
+                    limit,
+                    all,
+                    format,
+                },
+        } => {
+            let format = cli.output_format(format);
+            let limit = (!*all).then_some(*limit);
+            commands::audit::run_pricing(*threshold, limit, format, cli.fail_on_empty, cli.quiet)
+        }
+
+        // Media command - media leaderboards
+        Commands::Media {
+            command: Some(MediaCommands::Show { model, kind, json }),
+            ..
+        } => {
+            let format = cli.output_format(&FormatArgs::json(*json));
+            let kind = kind
+                .as_deref()
+                .map(commands::media::media_kind)
+                .transpose()?;
+            commands::media::show(model, kind, format)
+        }
+        Commands::Media {
+            command:
+                Some(MediaCommands::Compare {
+                    first,
+                    second,
+                    kind,
+                    json,
+                }),
+            ..
+        } => {
+            let format = cli.output_format(&FormatArgs::json(*json));
+            let kind = kind
+                .as_deref()
+                .map(commands::media::media_kind)
+                .transpose()?;
+            commands::media::compare(first, second, kind, format)
+        }
+        Commands::Media {
+            command: None,
+            kind,
+            creator,
+            min_elo,
+            category,
+            style,
+            sort,
+            asc,
+            top,
+            all,
+            confidence,
+            format,
+        } => {
+            let format = cli.output_format(format);
+            let options = commands::media::MediaOptions {
+                kind: commands::media::media_kind(kind)?,
+                creator: creator.clone(),
+                min_elo: *min_elo,
+                category: match (category, style) {
+                    (Some(name), _) => Some(commands::media::CategoryFilter {
+                        name: name.clone(),
+                        category_type: None,
+                    }),
+                    (None, Some(name)) => Some(commands::media::CategoryFilter {
+                        name: name.clone(),
+                        category_type: Some("style"),
+                    }),
+                    (None, None) => None,
+                },
+                sort: sort.clone(),
+                ascending: *asc,
+                top: (!*all).then_some(*top),
+                confidence: *confidence,
+            };
+            commands::media::run(&options, format, cli.fail_on_empty, cli.quiet)
+        }
+
+        // Stats command - dataset-wide aggregates
+        Commands::Stats { format } => {
+            let format = cli.output_format(format);
+            commands::stats::run(format)
+        }
+
+        Commands::Trend {
+            metric,
+            window,
+            limit,
+            all,
+            format,
+        } => {
+            let format = cli.output_format(format);
+            let since = parse_when("window", window)?;
+            let limit = (!*all).then_some(*limit);
+            commands::trend::run(metric, since, limit, format, cli.quiet, cli.fail_on_empty)
+        }
+
+        // Info command
+        Commands::Info { json } => {
+            let format = cli.output_format(&FormatArgs::json(*json));
+            commands::info::run(format)
+        }
+
+        // Tokens command - conversions and estimates without model data
+        Commands::Tokens {
+            counts,
+            chars,
+            model: None,
+            format,
+        } => {
+            let format = cli.output_format(format);
+            match chars {
+                Some(path) => commands::tokens::run_estimate(path, None, format),
+                None => commands::tokens::run(counts, format),
+            }
+        }
+
+        // Examples command - curated invocations
+        Commands::Examples { command, json } => {
+            let format = cli.output_format(&FormatArgs::json(*json));
+            commands::examples::run(command.as_deref(), format)
+        }
+
+        // Cache management
+        Commands::Cache { command } => match command {
+            CacheCommands::Clear {
+                table,
+                json_only,
+                parquet_only,
+            } => commands::cache::clear(table.as_deref(), *json_only, *parquet_only),
+            CacheCommands::Status => commands::cache::status(),
+        },
+
+        // Profile management
+        Commands::Quota {
+            all_profiles,
+            format,
+        } => {
+            let format = cli.output_format(format);
+            commands::quota::run(cli.profile.as_deref(), *all_profiles, format)
+        }
+
+        Commands::Note {
+            command: NoteCommands::List { model },
+        } => commands::notes::list_notes(model.as_deref()),
+        Commands::Tag {
+            command: TagCommands::List { tag },
+        } => commands::notes::list_tags(tag.as_deref()),
+
+        Commands::Profile { command } => match command {
+            ProfileCommands::Create { name } => {
+                commands::profile::create(name, cli.api_key.as_deref(), !cli.agent)
+            }
+            ProfileCommands::List => commands::profile::list(),
+            ProfileCommands::Default { name } => commands::profile::set_default(name),
+            ProfileCommands::Delete { name } => commands::profile::delete(name, !cli.agent),
+            ProfileCommands::Show {
+                name,
+                effective: true,
+            } => commands::profile::show_effective(name.as_deref().or(cli.profile.as_deref())),
+            ProfileCommands::Show { name, .. } => commands::profile::show(name.as_deref()),
+        },
+
+        // Everything else fetches data (or may, when the cache is stale)
+        _ => tokio::runtime::Builder::new_multi_thread()
+            .enable_all()
+            .build()?
+            .block_on(run_async(cli)),
+    }
+}
+
+/// Run a command that needs the async runtime for network access.
+async fn run_async(cli: &Cli) -> Result<()> {
+    match &cli.command {
+        // Refresh command - fetch fresh data
+        Commands::Refresh { snapshot } => {
+            commands::refresh::run(
+                cli.quiet,
+                cli.use_api,
+                cli.profile.as_deref(),
+                *snapshot,
+                cli.strict,
+            )
+            .await
+        }
+
+        // Compare command - side-by-side model comparison
+        Commands::Compare {
+            models,
+            regex,
+            verbose,
+            fields,
+            score_profile,
+            explain,
+            uncertainty,
+            collapse_variants,
+            with_reasoning_pair,
+            percentile,
+            selection,
+            format,
+        } => {
+            let format = cli.output_format(format);
+            let search = ModelSearch::new(model_args(models)?, *regex)?;
+            let score = score_profile
+                .as_deref()
+                .map(|name| ScoreWeights::from_profile(&Config::load()?, name))
+                .transpose()?;
+            let percentile = percentile.as_deref().map(str::parse).transpose()?;
+            selection.validate()?;
+            let mut llm_models = load_llm_models(cli, None).await?;
+            let selection = selection.to_selection()?;
+            if *uncertainty {
+                attach_intelligence_errors(&mut llm_models, Cache::new()?.base_dir())?;
+            }
+            if percentile.is_some() {
+                attach_percentiles(&mut llm_models, Cache::new()?.base_dir())?;
+            }
+            let llm_models = selection.apply(llm_models);
+            let options = commands::compare::CompareOptions {
+                verbose: *verbose,
+                fields: fields.as_deref(),
+                score: score.as_ref(),
+                explain: *explain,
+                collapse_variants: *collapse_variants,
+                reasoning_pair: *with_reasoning_pair,
+                percentile,
+            };
+            commands::compare::run(&llm_models, &search, &options, format)
+        }
+
+        // Cost command - token cost calculator
+        Commands::Cost {
+            models,
+            regex,
+            input,
+            output,
+            characters,
+            media,
+            seconds,
+            clips,
+            requests,
+            period,
+            selection,
+            format,
+        } => {
+            let format = cli.output_format(format);
+            let search = ModelSearch::new(model_args(models)?, *regex)?;
+            if let Some(kind) = media {
+                let table = commands::cost::video_kind(kind)?;
+                let seconds = seconds.unwrap_or_default();
+                return commands::cost::run_video(&search, table, seconds, *clips, period, format);
+            }
+            if let Some(characters) = characters {
+                return commands::cost::run_speech(&search, characters, *requests, period, format);
+            }
+            let selection = selection.to_selection()?;
+            let columns = selection.projection(commands::cost::COLUMNS);
+            let llm_models = load_llm_models(cli, columns.as_deref()).await?;
+            let llm_models = selection.apply(llm_models);
+            commands::cost::run(
+                &llm_models,
+                &search,
+                input.as_deref().unwrap_or_default(),
+                output.as_deref().unwrap_or_default(),
+                *requests,
+                period,
+                format,
+            )
+        }
+
+        // Report command - monthly cost of team workloads
+        Commands::Report {
+            workloads,
+            selection,
+            format,
+        } => {
+            let format = cli.output_format(format);
+            let workloads = commands::report::load_workloads(workloads)?;
+            selection.validate()?;
+            let llm_models = load_llm_models(cli, None).await?;
+            let selection = selection.to_selection()?;
+            let candidates = selection.apply(llm_models.clone());
+            let tiers = load_price_tiers(Cache::new()?.base_dir()).unwrap_or_default();
+            commands::report::run(&workloads, &llm_models, &candidates, &tiers, format)
+        }
+
+        // Migrate-cost command - what switching models would change
+        Commands::MigrateCost {
+            from,
+            to,
+            usage,
+            format,
+        } => {
+            let format = cli.output_format(format);
+            let usage = commands::migrate::load_usage(usage)?;
+            let llm_models = load_llm_models(cli, None).await?;
+            let cache = Cache::new()?;
+            let tiers = load_price_tiers(cache.base_dir()).unwrap_or_default();
+            commands::migrate::run(
+                &llm_models,
+                from,
+                to,
+                &usage,
+                &tiers,
+                cache.base_dir(),
+                format,
+            )
+        }
+
+        // Route command - primary/fallback pair for a task mix
+        Commands::Route {
+            mix,
+            max_price,
+            selection,
+            format,
+        } => {
+            let format = cli.output_format(format);
+            let mix: commands::route::TaskMix = mix.parse()?;
+            selection.validate()?;
+            let llm_models = load_llm_models(cli, None).await?;
+            let selection = selection.to_selection()?;
+            let llm_models = selection.apply(llm_models);
+            commands::route::run(&llm_models, &mix, *max_price, format)
+        }
+
+        // Changelog command - what changed since a snapshot
+        Commands::Changelog { since, limit, json } => {
+            let format = cli.output_format(&FormatArgs::json(*json));
+            // Resolve the snapshot first, so a missing one fails before fetching
+            let since = parse_when("since", since)?;
+            let baseline = snapshot::resolve(Cache::new()?.base_dir(), since)?;
+            let llm_models = load_llm_models(cli, None).await?;
+            commands::changelog::run(&baseline, &llm_models, *limit, format, cli.quiet)
+        }
+
+        // Shortlist command - diversified bake-off candidates
+        Commands::Shortlist {
+            budget_per_1k,
+            needs,
+            count,
+            selection,
+            format,
+        } => {
+            let format = cli.output_format(format);
+            selection.validate()?;
+            let llm_models = load_llm_models(cli, None).await?;
+            let selection = selection.to_selection()?;
+            let llm_models = selection.apply(llm_models);
+            let options = commands::shortlist::ShortlistOptions {
+                budget_per_1k: *budget_per_1k,
+                needs: needs.clone(),
+                count: *count,
+            };
+            commands::shortlist::run(&llm_models, Cache::new()?.base_dir(), &options, format)
+        }
+
+        // Recommend command - best models under hard constraints
+        Commands::Recommend {
+            max_input_price,
+            max_output_price,
+            min_intelligence,
+            tool_call,
+            needs,
+            optimize,
+            score_profile,
+            explain,
+            count,
+            all,
+            selection,
+            format,
+        } => {
+            let format = cli.output_format(format);
+            let mut needs = needs.clone();
+            if *tool_call && !needs.iter().any(|n| n == "tool_call") {
+                needs.push("tool_call".to_string());
+            }
+            let options = commands::recommend::RecommendOptions {
+                max_input_price: *max_input_price,
+                max_output_price: *max_output_price,
+                min_intelligence: *min_intelligence,
+                needs,
+                optimize: optimize.parse()?,
+                score: score_profile
+                    .as_deref()
+                    .map(|name| ScoreWeights::from_profile(&Config::load()?, name))
+                    .transpose()?,
+                explain: *explain,
+                count: (!*all).then_some(*count),
+            };
+            selection.validate()?;
+            let llm_models = load_llm_models(cli, None).await?;
+            let selection = selection.to_selection()?;
+            let llm_models = selection.apply(llm_models);
+            commands::recommend::run(
+                &llm_models,
+                Cache::new()?.base_dir(),
+                &options,
+                format,
+                cli.fail_on_empty,
+                cli.quiet,
+            )
+        }
+
+        // LLMs command - list models matching filter flags
+        Commands::Llms {
+            min_intelligence,
+            max_price,
+            min_tps,
+            max_latency,
+            tool_call,
+            reasoning,
+            open_weights,
+            sort,
+            asc,
+            score_profile,
+            fields,
+            collapse_variants,
+            top_per,
+            new_only,
+            age,
+            percentile,
+            limit,
+            all,
+            selection,
+            format,
+        } => {
+            let format = cli.output_format(format);
+            let released_after = new_only
+                .as_deref()
+                .map(|s| parse_when("new-only", s))
+                .transpose()?;
+            let percentile = percentile.as_deref().map(str::parse).transpose()?;
+            let needs = [
+                (*tool_call, "tool_call"),
+                (*reasoning, "reasoning"),
+                (*open_weights, "open_weights"),
+            ]
+            .into_iter()
+            .filter(|(set, _)| *set)
+            .map(|(_, need)| need.to_string())
+            .collect();
+            let filters = commands::llms::LlmFilters {
+                min_intelligence: *min_intelligence,
+                max_price: *max_price,
+                min_tps: *min_tps,
+                max_latency: *max_latency,
+                released_after,
+                needs,
+            };
+            let view = commands::llms::LlmView {
+                sort: sort.clone(),
+                ascending: *asc,
+                score: score_profile
+                    .as_deref()
+                    .map(|name| ScoreWeights::from_profile(&Config::load()?, name))
+                    .transpose()?,
+                fields: fields.clone(),
+                collapse_variants: *collapse_variants,
+                top_per: top_per.as_deref().map(str::parse).transpose()?,
+                percentile,
+                age: *age || released_after.is_some(),
+                limit: (!*all).then_some(*limit),
+            };
+            selection.validate()?;
+            let mut llm_models = load_llm_models(cli, None).await?;
+            let selection = selection.to_selection()?;
+            if percentile.is_some() {
+                attach_percentiles(&mut llm_models, Cache::new()?.base_dir())?;
+            }
+            commands::llms::run(
+                selection.apply(llm_models),
+                Cache::new()?.base_dir(),
+                &filters,
+                &view,
+                format,
+                cli.fail_on_empty,
+                cli.quiet,
+            )
+        }
+
+        // Tokens command - estimate with a model's tokenizer ratio
+        Commands::Tokens {
+            chars: Some(path),
+            model: Some(model),
+            format,
+            ..
+        } => {
+            let format = cli.output_format(format);
+            let llm_models = load_llm_models(cli, None).await?;
+            let model = commands::get::resolve_model(&llm_models, model)?;
+            commands::tokens::run_estimate(path, Some(model), format)
+        }
+
+        // Get command - raw value for scripts
+        Commands::Get {
+            model,
+            field,
+            family,
+            integration,
+        } => {
+            let mut llm_models = load_llm_models(cli, None).await?;
+            let cache = Cache::new()?;
+            attach_integrations(&mut llm_models, cache.base_dir())?;
+            let integrations = integration
+                .then(|| load_integrations(cache.base_dir()))
+                .transpose()?;
+            let notes = Notes::load()?;
+            commands::get::run(
+                &llm_models,
+                &notes,
+                model,
+                field.as_deref(),
+                *family,
+                integrations.as_ref(),
+            )
+        }
+
+        // Policy command - check one model against a policy file
+        Commands::Policy {
+            command:
+                PolicyCommands::Check {
+                    model,
+                    policy,
+                    json,
+                },
+        } => {
+            let format = cli.output_format(&FormatArgs::json(*json));
+            let path = match policy {
+                Some(path) => path.clone(),
+                None => Policy::default_path()?,
+            };
+            let check = PolicyCheck::new(Policy::load(&path)?, Cache::new()?.base_dir())?;
+            let llm_models = load_llm_models(cli, None).await?;
+            commands::policy::run_check(&llm_models, model, &check, &path, format)
+        }
+
+        // Notes and tags - resolve the model against the benchmarks
+        Commands::Note { command } => {
+            let llm_models = load_llm_models(cli, None).await?;
+            match command {
+                NoteCommands::Add { model, text } => {
+                    commands::notes::add_note(&llm_models, model, text, cli.quiet)
+                }
+                NoteCommands::Remove { model, index } => {
+                    commands::notes::remove_note(&llm_models, model, *index, cli.quiet)
+                }
+                NoteCommands::List { .. } => unreachable!("listed by run"),
+            }
+        }
+        Commands::Tag { command } => {
+            let llm_models = load_llm_models(cli, None).await?;
+            match command {
+                TagCommands::Add { model, tags } => {
+                    commands::notes::add_tags(&llm_models, model, tags, cli.quiet)
+                }
+                TagCommands::Remove { model, tags } => {
+                    commands::notes::remove_tags(&llm_models, model, tags, cli.quiet)
+                }
+                TagCommands::List { .. } => unreachable!("listed by run"),
+            }
+        }
+
+        // Doctor command - diagnose the local setup
+        Commands::Doctor { format } => {
+            let format = cli.output_format(format);
+            commands::doctor::run(format).await
+        }
+
+        // Skill management
+        Commands::Skill { command } => match command {
+            SkillCommands::Install {
+                tool,
+                global,
+                force,
+                dry_run,
+            } => commands::skill::install(tool, *global, *force, *dry_run).await,
+            SkillCommands::Uninstall { tool, global } => commands::skill::uninstall(tool, *global),
+            SkillCommands::List => commands::skill::list(),
+        },
+
+        _ => unreachable!("local commands are handled by run"),
+    }
+}
@@ -0,0 +1,7 @@
+//! aa - short alias of `which-llm`, sharing its entry point.
+//!
+//! Help, hints and error messages name whichever binary was invoked.
+
+fn main() {
+    which_llm::run();
+}
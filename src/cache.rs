@@ -1,15 +1,59 @@
 //! File-based caching for API responses.
+//!
+//! A refresh writes its tables to a [`Staging`] directory inside the cache
+//! and moves them into place together, so readers never see a mix of new
+//! and old tables from one refresh.
 
+use crate::config::home_dir;
 use crate::error::{AppError, Result};
+use crate::logging;
+use crate::schema::{TableDef, TableLayout};
 use chrono::{DateTime, Utc};
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use sha2::{Digest, Sha256};
+use std::collections::HashMap;
 use std::path::{Path, PathBuf};
-use std::time::Duration;
+use std::time::{Duration, SystemTime};
 
 /// Default cache TTL (1 hour).
 const DEFAULT_TTL_SECS: u64 = 3600;
 
+/// Whether the cache is read-only (`WHICH_LLM_READONLY=1`), e.g. a shared
+/// directory mounted into containers. Cached data is then used however old
+/// it is and nothing is written; only an explicit refresh or clear fails.
+pub fn is_read_only() -> bool {
+    std::env::var("WHICH_LLM_READONLY").is_ok_and(|value| {
+        matches!(
+            value.trim().to_lowercase().as_str(),
+            "1" | "true" | "yes" | "on"
+        )
+    })
+}
+
+/// Error for an explicit write to a read-only cache.
+pub fn read_only_error(action: &str) -> AppError {
+    AppError::Config(format!(
+        "Cannot {}: the cache is read-only (WHICH_LLM_READONLY is set). Update it from a writable copy.",
+        action
+    ))
+}
+
+/// Which cached files [`Cache::clear_scope`] removes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ClearScope {
+    /// API responses, download records and Parquet tables.
+    All,
+    /// API responses only. Parquet tables and the records of where they
+    /// were downloaded from are kept.
+    Json,
+    /// Parquet tables only.
+    Parquet,
+}
+
+/// Download records, kept by a JSON-only clear since they describe the
+/// Parquet files.
+const DOWNLOAD_RECORDS: &[&str] = &["remote_meta.json", "manifest.json"];
+
 /// Cached entry wrapper.
 #[derive(Debug, Serialize, Deserialize)]
 struct CacheEntry<T> {
@@ -25,22 +69,36 @@ pub struct Cache {
 
 impl Cache {
     /// Create a new cache with default settings.
-    /// Respects WHICH_LLM_CACHE_DIR environment variable for testing/portability.
+    /// Respects WHICH_LLM_CACHE_DIR, then `--home` (WHICH_LLM_HOME), for
+    /// testing/portability.
     pub fn new() -> Result<Self> {
-        let base_dir = if let Ok(cache_dir) = std::env::var("WHICH_LLM_CACHE_DIR") {
-            PathBuf::from(cache_dir)
+        let base_dir = Self::default_dir()?;
+        if !is_read_only() {
+            std::fs::create_dir_all(&base_dir)?;
+        }
+
+        Ok(Self::at(base_dir))
+    }
+
+    /// The cache directory, without creating it.
+    fn default_dir() -> Result<PathBuf> {
+        if let Ok(cache_dir) = std::env::var("WHICH_LLM_CACHE_DIR") {
+            Ok(PathBuf::from(cache_dir))
+        } else if let Some(home) = home_dir() {
+            Ok(home.join("cache"))
         } else {
             dirs::cache_dir()
                 .map(|p| p.join("which-llm"))
-                .ok_or_else(|| AppError::Cache("Could not determine cache directory".into()))?
-        };
-
-        std::fs::create_dir_all(&base_dir)?;
+                .ok_or_else(|| AppError::Cache("Could not determine cache directory".into()))
+        }
+    }
 
-        Ok(Self {
+    /// A cache rooted at `base_dir`, e.g. a refresh's staging directory.
+    pub fn at(base_dir: PathBuf) -> Self {
+        Self {
             base_dir,
             ttl: Duration::from_secs(DEFAULT_TTL_SECS),
-        })
+        }
     }
 
     /// Get the cache base directory.
@@ -66,7 +124,15 @@ impl Cache {
     }
 
     /// Get cached data if valid.
+    ///
+    /// A read-only cache is never updated, so its entries are used however
+    /// old they are rather than expiring.
     pub fn get<T: DeserializeOwned>(&self, key: &str) -> Option<T> {
+        self.read(key, is_read_only())
+    }
+
+    /// [`Cache::get`], with the read-only mode passed in.
+    fn read<T: DeserializeOwned>(&self, key: &str, read_only: bool) -> Option<T> {
         let path = self.base_dir.join(format!("{key}.json"));
         if !path.exists() {
             return None;
@@ -77,8 +143,12 @@ impl Cache {
 
         // Check TTL
         let age = Utc::now().signed_duration_since(entry.cached_at);
-        if age.num_seconds() > self.ttl.as_secs() as i64 {
+        if !read_only && age.num_seconds() > self.ttl.as_secs() as i64 {
             // Expired, remove the file
+            logging::debug(
+                "cache",
+                format_args!("{}: expired ({}s old)", key, age.num_seconds()),
+            );
             let _ = std::fs::remove_file(&path);
             return None;
         }
@@ -86,8 +156,11 @@ impl Cache {
         Some(entry.data)
     }
 
-    /// Store data in cache.
+    /// Store data in cache; a no-op when the cache is read-only.
     pub fn set<T: Serialize>(&self, key: &str, data: &T) -> Result<()> {
+        if is_read_only() {
+            return Ok(());
+        }
         let entry = CacheEntry {
             data,
             cached_at: Utc::now(),
@@ -99,17 +172,55 @@ impl Cache {
         Ok(())
     }
 
-    /// Clear all cached data.
-    pub fn clear(&self) -> Result<()> {
+    /// Remove the cached files in `scope`. Returns how many were removed.
+    pub fn clear_scope(&self, scope: ClearScope) -> Result<usize> {
+        if is_read_only() {
+            return Err(read_only_error("clear the cache"));
+        }
+        let mut removed = 0;
         for entry in std::fs::read_dir(&self.base_dir)? {
             let entry = entry?;
             let path = entry.path();
-            let ext = path.extension().and_then(|e| e.to_str());
-            if ext == Some("json") || ext == Some("parquet") {
+            let is_record = entry
+                .file_name()
+                .to_str()
+                .is_some_and(|name| DOWNLOAD_RECORDS.contains(&name));
+            let remove = match path.extension().and_then(|e| e.to_str()) {
+                Some("json") => {
+                    scope == ClearScope::All || (scope == ClearScope::Json && !is_record)
+                }
+                Some("parquet") => scope != ClearScope::Json,
+                _ => false,
+            };
+            if remove {
                 std::fs::remove_file(path)?;
+                removed += 1;
             }
         }
-        Ok(())
+        Ok(removed)
+    }
+
+    /// Remove one table's cached Parquet data, so the next command that
+    /// reads it fetches it again. Returns how many files or directories
+    /// were removed.
+    pub fn clear_table(&self, table: &TableDef) -> Result<usize> {
+        if is_read_only() {
+            return Err(read_only_error(&format!("clear table '{}'", table.name)));
+        }
+        let path = table.cache_path(&self.base_dir);
+        match table.layout {
+            TableLayout::File if path.exists() => std::fs::remove_file(path)?,
+            TableLayout::Partitioned if path.exists() => std::fs::remove_dir_all(path)?,
+            TableLayout::File | TableLayout::Partitioned => return Ok(0),
+            TableLayout::Glob => {
+                let files = table.glob_files(&self.base_dir);
+                for file in &files {
+                    std::fs::remove_file(file)?;
+                }
+                return Ok(files.len());
+            }
+        }
+        Ok(1)
     }
 
     /// Get cache statistics.
@@ -117,7 +228,13 @@ impl Cache {
         let mut count = 0;
         let mut size = 0;
 
-        for entry in std::fs::read_dir(&self.base_dir)? {
+        // A read-only cache may not exist yet
+        let entries: Vec<std::io::Result<std::fs::DirEntry>> = if self.base_dir.exists() {
+            std::fs::read_dir(&self.base_dir)?.collect()
+        } else {
+            Vec::new()
+        };
+        for entry in entries {
             let entry = entry?;
             let path = entry.path();
             let ext = path.extension().and_then(|e| e.to_str());
@@ -135,6 +252,253 @@ impl Cache {
     }
 }
 
+/// Directory holding one subdirectory per refresh in progress, inside the
+/// cache directory.
+const STAGING_DIR: &str = ".staging";
+
+/// Extension of a refresh's commit journal, next to its staging directory:
+/// the files the commit is moving into place, one name per line. While the
+/// journal exists, the staging directory holds a complete refresh.
+const JOURNAL_EXT: &str = "commit";
+
+/// Extension of a journal still being written. It is renamed to
+/// [`JOURNAL_EXT`] once synced, so a journal is never read half-written.
+const PARTIAL_JOURNAL_EXT: &str = "commit.tmp";
+
+/// Records a refresh reads and rewrites alongside the tables, seeded into
+/// its staging directory.
+const REFRESH_RECORDS: &[&str] = &["remote_meta.json", "manifest.json", "derived_meta.json"];
+
+/// Age after which an uncommitted staging directory (or a journal never
+/// finished) is taken to be left by a refresh that was killed, and removed.
+const ABANDONED_STAGING: Duration = Duration::from_secs(24 * 3600);
+
+/// Whether `path` is a cache file (JSON or Parquet) rather than a directory
+/// or a partial download.
+fn is_cache_file(path: &Path) -> bool {
+    path.is_file()
+        && matches!(
+            path.extension().and_then(|e| e.to_str()),
+            Some("json" | "parquet")
+        )
+}
+
+/// Whether `path` is a file a refresh rewrites: a table's Parquet file or
+/// one of the [`REFRESH_RECORDS`]. API responses and the history partitions
+/// are not.
+fn is_refreshed_file(path: &Path) -> bool {
+    is_cache_file(path)
+        && (path.extension().and_then(|e| e.to_str()) == Some("parquet")
+            || REFRESH_RECORDS.contains(&entry_name(path).as_str()))
+}
+
+/// Paths of the files staged in `dir`, after `prefix` and joined with `/`:
+/// cache files at the top and everything in subdirectories, such as a
+/// history partition.
+fn staged_files(dir: &Path, prefix: &str, out: &mut Vec<String>) -> Result<()> {
+    for entry in std::fs::read_dir(dir)? {
+        let path = entry?.path();
+        let name = format!("{}{}", prefix, entry_name(&path));
+        if path.is_dir() {
+            staged_files(&path, &format!("{}/", name), out)?;
+        } else if !prefix.is_empty() || is_cache_file(&path) {
+            out.push(name);
+        }
+    }
+    Ok(())
+}
+
+/// Write the journal of the files a commit moves, durably: to a partial
+/// journal first, synced, then renamed into place.
+fn write_journal(journal: &Path, names: &[String]) -> Result<()> {
+    use std::io::Write;
+
+    let partial = journal.with_extension(PARTIAL_JOURNAL_EXT);
+    let mut file = std::fs::File::create(&partial)?;
+    file.write_all(names.join("\n").as_bytes())?;
+    file.sync_all()?;
+    drop(file);
+    std::fs::rename(&partial, journal)?;
+    Ok(())
+}
+
+/// SHA-256 of a file's contents, to tell whether a staged file was rewritten.
+fn content_hash(path: &Path) -> Result<String> {
+    Ok(crate::remote::sha256_hex(&std::fs::read(path)?))
+}
+
+/// Finish the commit journaled at `journal`: move the files still staged in
+/// `staging` into `cache_dir`, then drop the journal and staging directory.
+fn finish_commit(cache_dir: &Path, staging: &Path, journal: &Path) -> Result<()> {
+    let Ok(names) = std::fs::read_to_string(journal) else {
+        return Ok(());
+    };
+    for name in names.lines().filter(|name| !name.is_empty()) {
+        let target = cache_dir.join(name);
+        if let Some(parent) = target.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        match std::fs::rename(staging.join(name), target) {
+            Ok(()) => {}
+            // Already moved, by this commit or a concurrent recovery
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+            Err(e) => return Err(e.into()),
+        }
+    }
+    let _ = std::fs::remove_file(journal);
+    let _ = std::fs::remove_dir_all(staging);
+    Ok(())
+}
+
+/// Finish every commit that was cut short in `cache_dir`, and remove the
+/// staging directories of refreshes abandoned long ago.
+///
+/// Staging directories of refreshes still running are left alone.
+fn recover_staging(cache_dir: &Path) -> Result<()> {
+    let root = cache_dir.join(STAGING_DIR);
+    let Ok(entries) = std::fs::read_dir(&root) else {
+        return Ok(());
+    };
+    for entry in entries {
+        let path = entry?.path();
+        if path.extension().and_then(|e| e.to_str()) == Some(JOURNAL_EXT) {
+            finish_commit(cache_dir, &path.with_extension(""), &path)?;
+            continue;
+        }
+        let abandoned = std::fs::metadata(&path)
+            .and_then(|meta| meta.modified())
+            .ok()
+            .and_then(|modified| modified.elapsed().ok())
+            .is_some_and(|age| age > ABANDONED_STAGING);
+        if !abandoned {
+            continue;
+        }
+        if path.is_dir() && !path.with_extension(JOURNAL_EXT).exists() {
+            logging::debug(
+                "cache",
+                format_args!("removing abandoned staging {}", path.display()),
+            );
+            let _ = std::fs::remove_dir_all(&path);
+        } else if entry_name(&path).ends_with(PARTIAL_JOURNAL_EXT) {
+            let _ = std::fs::remove_file(&path);
+        }
+    }
+    Ok(())
+}
+
+/// Finish a refresh commit that an earlier run was killed in the middle of.
+///
+/// Called once at startup, so a cut-short commit is never left half-applied
+/// for later commands. A cache that does not exist yet or is read-only has
+/// nothing to recover.
+pub fn recover() -> Result<()> {
+    if is_read_only() {
+        return Ok(());
+    }
+    match Cache::default_dir() {
+        Ok(dir) => recover_staging(&dir),
+        // Reported by the commands that use the cache
+        Err(_) => Ok(()),
+    }
+}
+
+/// A refresh in progress.
+///
+/// Tables are written to a staging directory of this refresh's own, seeded
+/// with copies of the cached tables and download records so unchanged
+/// tables are not downloaded again. Files written to its subdirectories,
+/// such as a new `llms_history` partition, are committed along with them. [`Staging::commit`] moves the rewritten files into the cache;
+/// dropping it uncommitted discards them and leaves the cache as it was.
+/// Concurrent refreshes each stage separately, and the last to commit wins.
+///
+/// The file list is journaled before the first move, so a commit cut short
+/// is finished at the next startup (see [`recover`]), never left
+/// half-applied.
+pub struct Staging {
+    cache_dir: PathBuf,
+    dir: PathBuf,
+    /// Content hashes of the seeded copies; files whose hash changed were
+    /// rewritten.
+    seeded: HashMap<String, String>,
+    committed: bool,
+}
+
+impl Staging {
+    /// Start staging a refresh of the cache in `cache_dir`.
+    ///
+    /// Commits an earlier refresh was killed in the middle of are finished
+    /// first.
+    pub fn begin(cache_dir: &Path) -> Result<Self> {
+        recover_staging(cache_dir)?;
+        let nanos = SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .map_or(0, |d| d.as_nanos());
+        let dir = cache_dir
+            .join(STAGING_DIR)
+            .join(format!("{}-{}", std::process::id(), nanos));
+        std::fs::create_dir_all(&dir)?;
+
+        let mut seeded = HashMap::new();
+        for entry in std::fs::read_dir(cache_dir)? {
+            let path = entry?.path();
+            if !is_refreshed_file(&path) {
+                continue;
+            }
+            let name = entry_name(&path);
+            let data = std::fs::read(&path)?;
+            std::fs::write(dir.join(&name), &data)?;
+            seeded.insert(name, crate::remote::sha256_hex(&data));
+        }
+
+        Ok(Self {
+            cache_dir: cache_dir.to_path_buf(),
+            dir,
+            seeded,
+            committed: false,
+        })
+    }
+
+    /// Directory to write the refreshed tables to.
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Move every new or rewritten file into the cache.
+    pub fn commit(mut self) -> Result<()> {
+        let mut staged = Vec::new();
+        staged_files(&self.dir, "", &mut staged)?;
+        let mut changed = Vec::new();
+        for name in staged {
+            if self.seeded.get(&name) != Some(&content_hash(&self.dir.join(&name))?) {
+                changed.push(name);
+            }
+        }
+        logging::debug(
+            "cache",
+            format_args!("committing refresh: {}", changed.join(", ")),
+        );
+
+        let journal = self.dir.with_extension(JOURNAL_EXT);
+        write_journal(&journal, &changed)?;
+        self.committed = true;
+        finish_commit(&self.cache_dir, &self.dir, &journal)
+    }
+}
+
+impl Drop for Staging {
+    fn drop(&mut self) {
+        if !self.committed {
+            let _ = std::fs::remove_dir_all(&self.dir);
+        }
+    }
+}
+
+fn entry_name(path: &Path) -> String {
+    path.file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default()
+}
+
 /// Cache statistics.
 #[derive(Debug)]
 pub struct CacheStats {
@@ -169,4 +533,168 @@ mod tests {
         assert_ne!(key1, key2);
         assert_eq!(key1, key3);
     }
+
+    #[test]
+    fn test_read_only_ignores_ttl() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let cache = Cache::at(temp_dir.path().to_path_buf());
+        let entry = CacheEntry {
+            data: vec![1, 2, 3],
+            cached_at: Utc::now() - chrono::Duration::days(30),
+        };
+        let path = temp_dir.path().join("llms.json");
+        std::fs::write(&path, serde_json::to_string(&entry).unwrap()).unwrap();
+
+        assert_eq!(cache.read::<Vec<i32>>("llms", true), Some(vec![1, 2, 3]));
+        assert!(path.exists());
+        assert_eq!(cache.read::<Vec<i32>>("llms", false), None);
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn test_clear_scope_and_table() {
+        use crate::schema::{BENCHMARKS, LLMS_HISTORY, MEDIA_CATEGORIES, TEXT_TO_IMAGE};
+
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let cache = Cache::at(temp_dir.path().to_path_buf());
+        let dir = temp_dir.path();
+        for name in [
+            "benchmarks.parquet",
+            "text_to_image.parquet",
+            "text_to_image_categories.parquet",
+            "remote_meta.json",
+            "llms-0123456789abcdef.json",
+        ] {
+            std::fs::write(dir.join(name), "data").unwrap();
+        }
+        std::fs::create_dir_all(dir.join("llms_history/snapshot_date=2025-01-01")).unwrap();
+
+        // JSON-only keeps the download record that describes the tables
+        assert_eq!(cache.clear_scope(ClearScope::Json).unwrap(), 1);
+        assert!(dir.join("remote_meta.json").exists());
+        assert!(!dir.join("llms-0123456789abcdef.json").exists());
+
+        assert_eq!(cache.clear_table(&BENCHMARKS).unwrap(), 1);
+        assert_eq!(cache.clear_table(&BENCHMARKS).unwrap(), 0);
+        assert!(TEXT_TO_IMAGE.is_cached(dir));
+        assert_eq!(cache.clear_table(&MEDIA_CATEGORIES).unwrap(), 1);
+        assert!(TEXT_TO_IMAGE.is_cached(dir));
+        assert_eq!(cache.clear_table(&LLMS_HISTORY).unwrap(), 1);
+        assert!(!dir.join("llms_history").exists());
+
+        assert_eq!(cache.clear_scope(ClearScope::Parquet).unwrap(), 1);
+        assert!(!TEXT_TO_IMAGE.is_cached(dir));
+        assert!(dir.join("remote_meta.json").exists());
+    }
+
+    #[test]
+    fn test_staging_commits_all_or_nothing() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let cache_dir = temp_dir.path();
+        std::fs::write(cache_dir.join("benchmarks.parquet"), "old").unwrap();
+        std::fs::write(cache_dir.join("models.parquet"), "old").unwrap();
+
+        // Dropped without a commit: the cache is untouched
+        let staging = Staging::begin(cache_dir).unwrap();
+        std::fs::write(staging.dir().join("benchmarks.parquet"), "new").unwrap();
+        drop(staging);
+        let read = |name: &str| std::fs::read_to_string(cache_dir.join(name)).unwrap();
+        assert_eq!(read("benchmarks.parquet"), "old");
+        assert_eq!(
+            std::fs::read_dir(cache_dir.join(STAGING_DIR))
+                .unwrap()
+                .count(),
+            0
+        );
+
+        // A rewrite of the same size is still a change
+        let staging = Staging::begin(cache_dir).unwrap();
+        std::fs::write(staging.dir().join("benchmarks.parquet"), "new").unwrap();
+        std::fs::write(staging.dir().join("llms_extra.parquet"), "new").unwrap();
+        staging.commit().unwrap();
+        assert_eq!(read("benchmarks.parquet"), "new");
+        assert_eq!(read("llms_extra.parquet"), "new");
+        assert_eq!(read("models.parquet"), "old");
+        assert_eq!(
+            std::fs::read_dir(cache_dir.join(STAGING_DIR))
+                .unwrap()
+                .count(),
+            0
+        );
+    }
+
+    #[test]
+    fn test_staging_seeds_tables_and_commits_partitions() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let cache_dir = temp_dir.path();
+        std::fs::write(cache_dir.join("benchmarks.parquet"), "old").unwrap();
+        std::fs::write(cache_dir.join("remote_meta.json"), "{}").unwrap();
+        std::fs::write(cache_dir.join("llms-0123456789abcdef.json"), "{}").unwrap();
+
+        let staging = Staging::begin(cache_dir).unwrap();
+        assert!(staging.dir().join("benchmarks.parquet").exists());
+        assert!(staging.dir().join("remote_meta.json").exists());
+        assert!(!staging.dir().join("llms-0123456789abcdef.json").exists());
+
+        let partition = staging.dir().join("llms_history/date=2025-01-15");
+        std::fs::create_dir_all(&partition).unwrap();
+        std::fs::write(partition.join("benchmarks.parquet"), "snapshot").unwrap();
+        std::fs::write(partition.join("index_version"), "4").unwrap();
+        staging.commit().unwrap();
+        let read = |name: &str| std::fs::read_to_string(cache_dir.join(name)).unwrap();
+        assert_eq!(
+            read("llms_history/date=2025-01-15/benchmarks.parquet"),
+            "snapshot"
+        );
+        assert_eq!(read("llms_history/date=2025-01-15/index_version"), "4");
+        assert_eq!(read("llms-0123456789abcdef.json"), "{}");
+    }
+
+    #[test]
+    fn test_concurrent_refreshes_stage_separately() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let cache_dir = temp_dir.path();
+        std::fs::write(cache_dir.join("models.parquet"), "old").unwrap();
+
+        let first = Staging::begin(cache_dir).unwrap();
+        std::fs::write(first.dir().join("models.parquet"), "first").unwrap();
+        let second = Staging::begin(cache_dir).unwrap();
+        assert_ne!(first.dir(), second.dir());
+        std::fs::write(second.dir().join("benchmarks.parquet"), "second").unwrap();
+
+        second.commit().unwrap();
+        first.commit().unwrap();
+        let read = |name: &str| std::fs::read_to_string(cache_dir.join(name)).unwrap();
+        assert_eq!(read("models.parquet"), "first");
+        assert_eq!(read("benchmarks.parquet"), "second");
+    }
+
+    #[test]
+    fn test_interrupted_commit_is_finished() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let cache_dir = temp_dir.path();
+        std::fs::write(cache_dir.join("benchmarks.parquet"), "new").unwrap();
+        std::fs::write(cache_dir.join("models.parquet"), "old").unwrap();
+        // Killed after moving benchmarks but before models
+        let staging = cache_dir.join(STAGING_DIR).join("42-1");
+        std::fs::create_dir_all(&staging).unwrap();
+        std::fs::write(staging.join("models.parquet"), "new").unwrap();
+        let journal = staging.with_extension(JOURNAL_EXT);
+        std::fs::write(&journal, "benchmarks.parquet\nmodels.parquet").unwrap();
+        // Another refresh still staging is left alone
+        let running = cache_dir.join(STAGING_DIR).join("43-1");
+        std::fs::create_dir_all(&running).unwrap();
+        // So is one killed while writing its journal, which is not replayed
+        let partial = running.with_extension(PARTIAL_JOURNAL_EXT);
+        std::fs::write(&partial, "models.parquet").unwrap();
+
+        recover_staging(cache_dir).unwrap();
+        let read = |name: &str| std::fs::read_to_string(cache_dir.join(name)).unwrap();
+        assert_eq!(read("benchmarks.parquet"), "new");
+        assert_eq!(read("models.parquet"), "new");
+        assert!(!journal.exists());
+        assert!(!staging.exists());
+        assert!(running.exists());
+        assert!(partial.exists());
+    }
 }
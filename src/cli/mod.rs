@@ -1,6 +1,47 @@
 //! CLI argument definitions.
 
-use clap::{Parser, Subcommand};
+use crate::cache::Cache;
+use crate::commands::examples;
+use crate::error::{AppError, Result};
+use crate::models::BENCHMARK_COLUMNS;
+use crate::notes::Notes;
+use crate::policy::{Policy, PolicyCheck};
+use crate::sources::models_dev::capabilities::{
+    load_token_limits, modal_models, validate_modalities,
+};
+use crate::utils::{parse_relative_date, parse_tokens, Filter, ModelSelection};
+use chrono::{NaiveDate, Utc};
+use clap::{Args, CommandFactory, FromArgMatches, Parser, Subcommand};
+use once_cell::sync::Lazy;
+use std::collections::HashMap;
+use std::io::BufRead;
+use std::path::{Path, PathBuf};
+
+/// Names the CLI is installed under; the first is the primary one.
+pub const BIN_NAMES: [&str; 2] = ["which-llm", "aa"];
+
+/// Name the CLI was invoked as, for help, hints and error messages.
+///
+/// Falls back to `which-llm` when run under any other name (e.g. tests).
+pub fn bin_name() -> &'static str {
+    static NAME: Lazy<&'static str> = Lazy::new(|| {
+        let invoked = std::env::args_os().next().and_then(|arg0| {
+            Path::new(&arg0)
+                .file_stem()
+                .map(|stem| stem.to_string_lossy().into_owned())
+        });
+        BIN_NAMES
+            .into_iter()
+            .find(|name| invoked.as_deref() == Some(*name))
+            .unwrap_or(BIN_NAMES[0])
+    });
+    *NAME
+}
+
+/// `args` as a command line for the invoked binary, e.g. `aa refresh`.
+pub fn invocation(args: &str) -> String {
+    format!("{} {}", bin_name(), args)
+}
 
 /// which-llm - Query AI model benchmarks from the terminal.
 #[derive(Parser, Debug)]
@@ -13,6 +54,22 @@ pub struct Cli {
     #[arg(long, short = 'p', global = true)]
     pub profile: Option<String>,
 
+    /// API key for this run, taking precedence over
+    /// $ARTIFICIAL_ANALYSIS_API_KEY and profiles. With `profile create`, the
+    /// key to store (prompted for if not given)
+    #[arg(long, global = true, value_name = "KEY")]
+    pub api_key: Option<String>,
+
+    /// Keep config and cache under this one directory (`config/` and
+    /// `cache/`), e.g. a volume mounted into a container
+    #[arg(long, global = true, value_name = "DIR", env = "WHICH_LLM_HOME")]
+    pub home: Option<PathBuf>,
+
+    /// Hosted data channel: stable (default, updated daily) or nightly,
+    /// updated every six hours. Also `channel` in the config file
+    #[arg(long, global = true, value_name = "CHANNEL", env = "WHICH_LLM_CHANNEL")]
+    pub channel: Option<String>,
+
     /// Use direct API access instead of hosted data (requires API key)
     #[arg(long, global = true)]
     pub use_api: bool,
@@ -20,81 +77,308 @@ pub struct Cli {
     /// Suppress output messages (for scripting)
     #[arg(long, short = 'q', global = true)]
     pub quiet: bool,
+
+    /// Stable, parse-friendly output: a header line then tab-separated rows
+    /// in a fixed column order, with no tips or notes. Implies --quiet and
+    /// overrides the format flags
+    #[arg(long, global = true)]
+    pub porcelain: bool,
+
+    /// Show booleans in tables as yes/no instead of ✓/✗ (automatic when
+    /// the locale is not UTF-8)
+    #[arg(long, global = true)]
+    pub ascii: bool,
+
+    /// Date display in tables and reports: iso (default), dmy ("13 May
+    /// 2024"), mdy ("May 13, 2024"), auto (from the locale), or a strftime
+    /// pattern. Data, JSON and CSV always use ISO-8601
+    #[arg(
+        long,
+        global = true,
+        value_name = "FORMAT",
+        env = "WHICH_LLM_DATE_FORMAT"
+    )]
+    pub date_format: Option<String>,
+
+    /// Print errors as JSON ({code, message, hint}) on stderr
+    #[arg(long, global = true)]
+    pub errors_json: bool,
+
+    /// Fail on stale or partial data instead of warning (for CI)
+    #[arg(long, global = true)]
+    pub strict: bool,
+
+    /// Exit with code 6 when a query, open or media listing returns no rows,
+    /// so filters work as shell conditionals
+    #[arg(long, global = true)]
+    pub fail_on_empty: bool,
+
+    /// Read benchmarks from the newest stored snapshot on or before this date
+    /// (2025-01-01, 90d, 2024-Q3) for reproducible results
+    #[arg(long, global = true, value_name = "WHEN")]
+    pub as_of: Option<String>,
+
+    /// Append debug traces (HTTP calls, cache decisions, SQL) to this file
+    /// for bug reports; API keys are redacted
+    #[arg(long, global = true, value_name = "PATH")]
+    pub log_file: Option<PathBuf>,
+
+    /// Skip downloading every table on the first run; commands then fetch
+    /// only what they need
+    #[arg(
+        long,
+        global = true,
+        env = "WHICH_LLM_NO_BOOTSTRAP",
+        value_parser = clap::builder::FalseyValueParser::new()
+    )]
+    pub no_bootstrap: bool,
+
+    /// Print the data sources, cache files and writes a command would use,
+    /// without contacting or writing anything
+    #[arg(long, global = true)]
+    pub plan: bool,
+
+    /// Agent mode for tools that shell out to the CLI: JSON output wrapped
+    /// with version and attribution metadata, errors as JSON, and no
+    /// progress messages or prompts
+    #[arg(
+        long,
+        global = true,
+        env = "WHICH_LLM_AGENT",
+        value_parser = clap::builder::FalseyValueParser::new()
+    )]
+    pub agent: bool,
+
+    /// Embed data attribution and source timestamps in JSON (as `_meta`)
+    /// and CSV (as `#` comment lines) output, for extracts you share
+    #[arg(long, global = true)]
+    pub attribution: bool,
+
+    /// Print example invocations of the command instead of running it
+    #[arg(long, global = true, exclusive = true)]
+    pub examples: bool,
 }
 
 #[derive(Subcommand, Debug)]
 pub enum Commands {
     /// Execute SQL queries against cached data
+    #[cfg(feature = "sql")]
     Query {
         /// SQL query to execute (e.g., "SELECT * FROM benchmarks WHERE intelligence > 40")
         sql: Option<String>,
 
-        /// Output as JSON
-        #[arg(long, conflicts_with_all = ["csv", "table", "plain"])]
-        json: bool,
-
-        /// Output as CSV
-        #[arg(long, conflicts_with_all = ["json", "table", "plain"])]
-        csv: bool,
+        /// Open an interactive SQL shell with history and tab completion
+        #[arg(long, short = 'i', conflicts_with = "sql")]
+        interactive: bool,
 
-        /// Output as ASCII table
-        #[arg(long, conflicts_with_all = ["json", "csv", "plain"])]
-        table: bool,
+        /// Download hosted tables the query reads that are not cached yet
+        /// (benchmarks, models, media tables) before running it
+        #[arg(long, conflicts_with = "interactive")]
+        fetch: bool,
 
-        /// Output as plain text (tab-separated)
-        #[arg(long, conflicts_with_all = ["json", "csv", "table"])]
-        plain: bool,
+        #[command(flatten)]
+        format: FormatArgs,
     },
 
     /// Refresh all cached data from sources
-    Refresh,
+    Refresh {
+        /// Also append today's benchmarks to the llms_history table
+        #[arg(long)]
+        snapshot: bool,
+    },
 
     /// List available tables and their schemas
     Tables {
-        /// Show details for a specific table
+        /// Show details for a specific table, including column descriptions
         table: Option<String>,
+
+        /// Describe every column (units, source, higher/lower is better)
+        #[arg(long, short = 'v')]
+        verbose: bool,
+    },
+
+    /// Dump the table schemas as JSON, or diff them against a saved dump
+    Schema {
+        #[command(subcommand)]
+        command: SchemaCommands,
+    },
+
+    /// List LLMs with their key metrics, most intelligent first
+    Llms {
+        /// Minimum intelligence index
+        #[arg(long, value_name = "SCORE")]
+        min_intelligence: Option<f64>,
+
+        /// Maximum blended price in USD per million tokens
+        #[arg(long, value_name = "USD")]
+        max_price: Option<f64>,
+
+        /// Minimum output speed in tokens per second
+        #[arg(long, value_name = "TPS")]
+        min_tps: Option<f64>,
+
+        /// Maximum time to first token in seconds
+        #[arg(long, value_name = "SECONDS")]
+        max_latency: Option<f64>,
+
+        /// Only models with tool calling (from the models table)
+        #[arg(long)]
+        tool_call: bool,
+
+        /// Only reasoning models (from the models table)
+        #[arg(long)]
+        reasoning: bool,
+
+        /// Only models with open weights (from the models table)
+        #[arg(long)]
+        open_weights: bool,
+
+        /// Field to sort by (descending): a numeric field such as tps, price
+        /// or coding, release_date, or context_window (from the models table)
+        #[arg(long, short = 's')]
+        sort: Option<String>,
+
+        /// Sort ascending instead of descending
+        #[arg(long, requires = "sort")]
+        asc: bool,
+
+        /// Add a score column using a [score_profiles] entry from config.toml, ranking by it unless --sort is set
+        #[arg(long, value_name = "NAME")]
+        score_profile: Option<String>,
+
+        /// Columns to show, in order, by `compare --fields` key (e.g. intelligence,input_price,tps)
+        #[arg(long, value_delimiter = ',')]
+        fields: Option<Vec<String>>,
+
+        /// One row per family (e.g. one GPT-5 instead of its
+        /// minimal/low/medium/high variants): its best variant, with a
+        /// variant summary column
+        #[arg(long)]
+        collapse_variants: bool,
+
+        /// Keep the first N models per group in list order, e.g. creator=2
+        /// (groups: name, slug, creator, creator_slug, family)
+        #[arg(long, value_name = "COLUMN=N")]
+        top_per: Option<String>,
+
+        /// Only models released within this window or since a date (60d, 2025-01-31); adds the age column
+        #[arg(long, value_name = "WHEN")]
+        new_only: Option<String>,
+
+        /// Add an age column computed from the release date (e.g. "3 mo")
+        #[arg(long)]
+        age: bool,
+
+        /// Filter, sort and show speed and latency at this percentile (p5,
+        /// p25, p75, p95) instead of the median, where AA publishes it
+        #[arg(long, value_name = "P")]
+        percentile: Option<String>,
+
+        /// Maximum number of models to show
+        #[arg(long, short = 'n', default_value = "20")]
+        limit: usize,
+
+        /// Show every matching model
+        #[arg(long, conflicts_with = "limit")]
+        all: bool,
+
+        #[command(flatten)]
+        selection: ModelSelectionArgs,
+
+        #[command(flatten)]
+        format: FormatArgs,
     },
 
     /// Compare multiple models side by side
     Compare {
-        /// Model names to compare (fuzzy matched)
+        /// Model names to compare (fuzzy matched); `-` reads one name per line from stdin
         #[arg(required = true)]
         models: Vec<String>,
 
+        /// Treat model names as case-insensitive regular expressions
+        #[arg(long, short = 'e')]
+        regex: bool,
+
         /// Show all available fields
-        #[arg(long, short = 'v')]
+        #[arg(long, short = 'v', conflicts_with = "fields")]
         verbose: bool,
 
-        /// Output as JSON
-        #[arg(long, conflicts_with_all = ["csv", "table", "plain"])]
-        json: bool,
+        /// Fields to show, in order (e.g. intelligence,input_price,tps)
+        #[arg(long, value_delimiter = ',')]
+        fields: Option<Vec<String>>,
+
+        /// Add a composite score row using a [score_profiles] entry from config.toml
+        #[arg(long, value_name = "NAME")]
+        score_profile: Option<String>,
+
+        /// Break the score down into each weighted metric's points
+        #[arg(long, requires = "score_profile")]
+        explain: bool,
+
+        /// Show intelligence as "score ± error" where AA publishes an error;
+        /// scores within each other's error bars all count as winners
+        #[arg(long)]
+        uncertainty: bool,
+
+        /// Compare each family's best variant (e.g. one GPT-5 instead of its
+        /// minimal/low/medium/high variants) with a variant summary row
+        #[arg(long, conflicts_with = "with_reasoning_pair")]
+        collapse_variants: bool,
 
-        /// Output as CSV
-        #[arg(long, conflicts_with_all = ["json", "table", "plain"])]
-        csv: bool,
+        /// Pair each model with its reasoning or non-reasoning variant and show
+        /// the price per intelligence point and the pair's deltas
+        #[arg(long)]
+        with_reasoning_pair: bool,
 
-        /// Output as ASCII table
-        #[arg(long, conflicts_with_all = ["json", "csv", "plain"])]
-        table: bool,
+        /// Show speed and latency at this percentile (p5, p25, p75, p95)
+        /// instead of the median, where AA publishes it
+        #[arg(long, value_name = "P")]
+        percentile: Option<String>,
 
-        /// Output as plain text (tab-separated)
-        #[arg(long, conflicts_with_all = ["json", "csv", "table"])]
-        plain: bool,
+        #[command(flatten)]
+        selection: ModelSelectionArgs,
+
+        #[command(flatten)]
+        format: FormatArgs,
     },
 
     /// Calculate token costs for models
     Cost {
-        /// Model names to calculate costs for (fuzzy matched)
+        /// Model names to calculate costs for (fuzzy matched); `-` reads one name per line from stdin
         #[arg(required = true)]
         models: Vec<String>,
 
+        /// Treat model names as case-insensitive regular expressions
+        #[arg(long, short = 'e')]
+        regex: bool,
+
         /// Input tokens per request (supports units: 10k, 1M)
-        #[arg(long, short = 'i')]
-        input: String,
+        #[arg(long, short = 'i', required_unless_present_any = ["characters", "media"])]
+        input: Option<String>,
 
         /// Output tokens per request (supports units: 10k, 1M)
-        #[arg(long, short = 'o')]
-        output: String,
+        #[arg(long, short = 'o', required_unless_present_any = ["characters", "media"])]
+        output: Option<String>,
+
+        /// Price text-to-speech models instead: characters per request (supports units: 500k, 1M)
+        #[arg(long, conflicts_with_all = ["input", "output", "media", "ModelSelectionArgs"])]
+        characters: Option<String>,
+
+        /// Price video models instead: text-to-video or image-to-video (use with --seconds)
+        #[arg(
+            long,
+            requires = "seconds",
+            conflicts_with_all = ["input", "output", "ModelSelectionArgs"]
+        )]
+        media: Option<String>,
+
+        /// Seconds of video per clip (with --media)
+        #[arg(long, requires = "media")]
+        seconds: Option<f64>,
+
+        /// Number of clips per period (with --media)
+        #[arg(long, default_value = "1", requires = "media")]
+        clips: u64,
 
         /// Number of requests per period (used with --period for projections)
         #[arg(long, short = 'r', default_value = "1")]
@@ -104,25 +388,360 @@ pub enum Commands {
         #[arg(long, default_value = "once")]
         period: String,
 
+        #[command(flatten)]
+        selection: ModelSelectionArgs,
+
+        #[command(flatten)]
+        format: FormatArgs,
+    },
+
+    /// Monthly cost report of team workloads, with cheaper alternatives
+    Report {
+        /// YAML file listing workloads (name, team, model, input_tokens,
+        /// output_tokens, requests_per_day)
+        #[arg(long, value_name = "FILE")]
+        workloads: PathBuf,
+
+        /// Options narrowing the suggested alternatives
+        #[command(flatten)]
+        selection: ModelSelectionArgs,
+
+        #[command(flatten)]
+        format: FormatArgs,
+    },
+
+    /// Cost, capability and speed changes of switching models for observed usage
+    MigrateCost {
+        /// Model used today (must resolve to a single model)
+        #[arg(long)]
+        from: String,
+
+        /// Model to switch to (must resolve to a single model)
+        #[arg(long)]
+        to: String,
+
+        /// CSV of observed usage with input_tokens and output_tokens columns,
+        /// and optionally requests
+        #[arg(long, value_name = "FILE")]
+        usage: PathBuf,
+
+        #[command(flatten)]
+        format: FormatArgs,
+    },
+
+    /// Recommend a primary/fallback model pair for a weighted task mix
+    Route {
+        /// Task weights, e.g. coding=0.5,chat=0.3,math=0.2 (tasks: coding, math, chat, science, knowledge)
+        #[arg(long)]
+        mix: String,
+
+        /// Maximum blended price per million tokens for either model
+        #[arg(long)]
+        max_price: Option<f64>,
+
+        #[command(flatten)]
+        selection: ModelSelectionArgs,
+
+        #[command(flatten)]
+        format: FormatArgs,
+    },
+
+    /// Build a diversified candidate shortlist for an evaluation bake-off
+    Shortlist {
+        /// Maximum blended price in USD per 1K tokens (e.g. 0.01 = $10/M)
+        #[arg(long)]
+        budget_per_1k: Option<f64>,
+
+        /// Required capability from the models table (repeatable or comma-separated):
+        /// tool_call, reasoning, structured_output, attachment, temperature, open_weights
+        #[arg(long, value_delimiter = ',')]
+        needs: Vec<String>,
+
+        /// Number of models to shortlist
+        #[arg(long, short = 'n', default_value = "3")]
+        count: usize,
+
+        #[command(flatten)]
+        selection: ModelSelectionArgs,
+
+        #[command(flatten)]
+        format: FormatArgs,
+    },
+
+    /// Rank the best models that meet price, intelligence, capability and
+    /// context constraints
+    Recommend {
+        /// Maximum input price in USD per million tokens
+        #[arg(long, value_name = "USD")]
+        max_input_price: Option<f64>,
+
+        /// Maximum output price in USD per million tokens
+        #[arg(long, value_name = "USD")]
+        max_output_price: Option<f64>,
+
+        /// Minimum intelligence index
+        #[arg(long, value_name = "SCORE")]
+        min_intelligence: Option<f64>,
+
+        /// Require tool calling (same as --needs tool_call)
+        #[arg(long)]
+        tool_call: bool,
+
+        /// Required capability from the models table (repeatable or comma-separated):
+        /// tool_call, reasoning, structured_output, attachment, temperature, open_weights
+        #[arg(long, value_delimiter = ',')]
+        needs: Vec<String>,
+
+        /// What to rank by: balanced, quality, cost or speed
+        #[arg(long, default_value = "balanced")]
+        optimize: String,
+
+        /// Rank by a [score_profiles] entry from config.toml instead of --optimize
+        #[arg(long, value_name = "NAME", conflicts_with = "optimize")]
+        score_profile: Option<String>,
+
+        /// Break each score down into each weighted metric's points
+        #[arg(long)]
+        explain: bool,
+
+        /// Number of models to return
+        #[arg(long, short = 'n', default_value = "5")]
+        count: usize,
+
+        /// Show every matching model
+        #[arg(long, conflicts_with = "count")]
+        all: bool,
+
+        #[command(flatten)]
+        selection: ModelSelectionArgs,
+
+        #[command(flatten)]
+        format: FormatArgs,
+    },
+
+    /// Print one model's value (or a plain summary) for scripts
+    Get {
+        /// Model slug or name (must resolve to a single model)
+        model: String,
+
+        /// Field to print, e.g. input_price or intelligence
+        #[arg(long, short = 'f')]
+        field: Option<String>,
+
+        /// List every variant in the model's family as slug<TAB>name, best first
+        #[arg(long, conflicts_with = "field")]
+        family: bool,
+
+        /// Print env setup and AI SDK install commands for each provider
+        /// serving the model (from models.dev), the suggested one first
+        #[arg(long, conflicts_with_all = ["field", "family"])]
+        integration: bool,
+    },
+
+    /// List open-weight models with their best price and context window
+    Open {
+        /// Maximum number of models to show
+        #[arg(long, short = 'n', default_value = "20")]
+        limit: usize,
+
+        /// Show every matching model
+        #[arg(long, conflicts_with = "limit")]
+        all: bool,
+
+        /// Column to sort by (descending), e.g. context_window, release_date, input_price
+        #[arg(long, short = 's')]
+        sort: Option<String>,
+
+        /// Sort ascending instead of descending
+        #[arg(long, requires = "sort")]
+        asc: bool,
+
+        /// Keep the best N models per group by the sort order, e.g. creator=2
+        #[arg(long, value_name = "COLUMN=N")]
+        top_per: Option<String>,
+
+        /// Only models released within this window or since a date (60d, 2025-01-31); adds the age column
+        #[arg(long, value_name = "WHEN")]
+        new_only: Option<String>,
+
+        /// Add an age column computed from the release date (e.g. "3 mo")
+        #[arg(long)]
+        age: bool,
+
+        #[command(flatten)]
+        format: FormatArgs,
+    },
+
+    /// List models.dev models that Artificial Analysis does not benchmark, newest first
+    Unmatched {
+        /// Maximum number of models to show
+        #[arg(long, short = 'n', default_value = "20")]
+        limit: usize,
+
+        /// Show every matching model
+        #[arg(long, conflicts_with = "limit")]
+        all: bool,
+
+        #[command(flatten)]
+        format: FormatArgs,
+    },
+
+    /// Cross-check Artificial Analysis data against models.dev
+    Audit {
+        #[command(subcommand)]
+        command: AuditCommands,
+    },
+
+    /// Media model leaderboards (image, video, and speech)
+    #[command(args_conflicts_with_subcommands = true)]
+    Media {
+        #[command(subcommand)]
+        command: Option<MediaCommands>,
+
+        /// Media kind: text-to-image, image-editing, text-to-speech, text-to-video, image-to-video
+        #[arg(long, short = 'k', default_value = "text-to-image")]
+        kind: String,
+
+        /// Only models whose creator contains this text
+        #[arg(long, short = 'c')]
+        creator: Option<String>,
+
+        /// Only models with at least this ELO
+        #[arg(long)]
+        min_elo: Option<f64>,
+
+        /// Rank by ELO in a category of any type (e.g. anime, people, portrait)
+        #[arg(long, conflicts_with = "style")]
+        category: Option<String>,
+
+        /// Rank by ELO in a style category (e.g. photorealistic)
+        #[arg(long)]
+        style: Option<String>,
+
+        /// Column to sort by (descending), e.g. elo, release_date, name
+        #[arg(long, short = 's')]
+        sort: Option<String>,
+
+        /// Sort ascending instead of descending
+        #[arg(long, requires = "sort")]
+        asc: bool,
+
+        /// Maximum number of models to show
+        #[arg(long, short = 'n', default_value = "20")]
+        top: usize,
+
+        /// Show every matching model
+        #[arg(long, conflicts_with = "top")]
+        all: bool,
+
+        /// Show the 95% confidence interval and arena appearances for each model
+        #[arg(long, conflicts_with_all = ["category", "style"])]
+        confidence: bool,
+
+        #[command(flatten)]
+        format: FormatArgs,
+    },
+
+    /// Show dataset-wide statistics (models per creator, median prices, score percentiles).
+    /// CSV, plain and porcelain output list the per-creator rows only
+    Stats {
+        #[command(flatten)]
+        format: FormatArgs,
+    },
+
+    /// Show how a metric moved per model across stored snapshots: the
+    /// fastest price-droppers or score-climbers
+    Trend {
+        /// Metric to fit, e.g. input_price, price, intelligence or tps
+        #[arg(long, short = 'm')]
+        metric: String,
+
+        /// Snapshots to include: those since a date, age or quarter
+        /// (2025-01-01, 90d, 2024-Q3)
+        #[arg(long, default_value = "90d", value_name = "WHEN")]
+        window: String,
+
+        /// Maximum number of models to show
+        #[arg(long, short = 'n', default_value = "20")]
+        limit: usize,
+
+        /// Show every matching model
+        #[arg(long, conflicts_with = "limit")]
+        all: bool,
+
+        #[command(flatten)]
+        format: FormatArgs,
+    },
+
+    /// Summarize what changed since a snapshot as Markdown for a team update:
+    /// new and retired models, price changes and ranking movements
+    Changelog {
+        /// Compare against the newest snapshot on or before this date, age or
+        /// quarter (2025-01-01, 14d, 2024-Q3)
+        #[arg(long, default_value = "14d", value_name = "WHEN")]
+        since: String,
+
+        /// Maximum number of ranking movements to list
+        #[arg(long, short = 'n', default_value = "10")]
+        limit: usize,
+
         /// Output as JSON
-        #[arg(long, conflicts_with_all = ["csv", "table", "plain"])]
+        #[arg(long)]
         json: bool,
+    },
 
-        /// Output as CSV
-        #[arg(long, conflicts_with_all = ["json", "table", "plain"])]
-        csv: bool,
+    /// Show data source information and attribution
+    Info {
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
 
-        /// Output as ASCII table
-        #[arg(long, conflicts_with_all = ["json", "csv", "plain"])]
-        table: bool,
+    /// Check config, credentials, cache, DuckDB, network access and installed skills
+    Doctor {
+        #[command(flatten)]
+        format: FormatArgs,
+    },
+
+    /// Convert token counts (1.5M <-> 1500000) or estimate tokens from characters
+    Tokens {
+        /// Token counts to convert, e.g. 1.5M, 250k or 4096
+        #[arg(required_unless_present = "chars", conflicts_with = "chars")]
+        counts: Vec<String>,
+
+        /// Estimate the tokens in a file from its character count (`-` reads stdin)
+        #[arg(long, value_name = "FILE")]
+        chars: Option<String>,
 
-        /// Output as plain text (tab-separated)
-        #[arg(long, conflicts_with_all = ["json", "csv", "table"])]
-        plain: bool,
+        /// Use this model's tokenizer ratio for --chars (about 4 characters
+        /// per token otherwise)
+        #[arg(long, requires = "chars")]
+        model: Option<String>,
+
+        #[command(flatten)]
+        format: FormatArgs,
     },
 
-    /// Show data source information and attribution
-    Info,
+    /// Show example invocations for every command, or for one
+    Examples {
+        /// Command to show examples for (e.g. query, compare, cost)
+        command: Option<String>,
+
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Run the commands listed in a file (one per line) in one process,
+    /// loading the data once
+    Batch {
+        /// File with one command per line, `#` comments allowed (`-` reads stdin)
+        file: String,
+
+        /// Run the remaining lines after a command fails instead of stopping
+        #[arg(long)]
+        keep_going: bool,
+    },
 
     /// Manage response cache
     Cache {
@@ -130,6 +749,35 @@ pub enum Commands {
         command: CacheCommands,
     },
 
+    /// Show the API quota last reported for the profile's API key
+    Quota {
+        /// Show every profile (and $ARTIFICIAL_ANALYSIS_API_KEY); profiles
+        /// with the same key hash share one quota
+        #[arg(long)]
+        all_profiles: bool,
+
+        #[command(flatten)]
+        format: FormatArgs,
+    },
+
+    /// Attach local notes to models, shown by `get`
+    Note {
+        #[command(subcommand)]
+        command: NoteCommands,
+    },
+
+    /// Tag models locally, to select them with `--tag`
+    Tag {
+        #[command(subcommand)]
+        command: TagCommands,
+    },
+
+    /// Check models against an organization policy file
+    Policy {
+        #[command(subcommand)]
+        command: PolicyCommands,
+    },
+
     /// Manage configuration profiles
     Profile {
         #[command(subcommand)]
@@ -143,43 +791,358 @@ pub enum Commands {
     },
 }
 
-/// Get output format from command-specific flags.
-pub fn get_output_format(
-    json: bool,
-    csv: bool,
-    table: bool,
-    plain: bool,
-) -> crate::output::OutputFormat {
-    if json {
-        crate::output::OutputFormat::Json
-    } else if csv {
-        crate::output::OutputFormat::Csv
-    } else if table {
-        crate::output::OutputFormat::Table
-    } else if plain {
-        crate::output::OutputFormat::Plain
-    } else {
-        crate::output::OutputFormat::Markdown
+impl Cli {
+    /// Parse the process arguments, with help and usage naming the binary
+    /// the CLI was invoked as and each command's `--help` ending in its
+    /// examples.
+    ///
+    /// `<command> --examples` prints the examples and exits, like `--help`.
+    pub fn parse_invoked() -> Self {
+        let mut command = Self::command().name(bin_name()).bin_name(bin_name());
+        for name in examples::commands() {
+            if let Some(help) = examples::help_text(name) {
+                if command.find_subcommand(name).is_some() {
+                    command = command.mut_subcommand(name, |sub| sub.after_long_help(help));
+                }
+            }
+        }
+        let matches = command.get_matches();
+
+        // Checked before building `Cli`, which would fail on missing arguments
+        if matches.get_flag("examples") {
+            let name = matches.subcommand_name().unwrap_or_default();
+            match examples::run(Some(name), crate::output::OutputFormat::Markdown) {
+                Ok(()) => std::process::exit(0),
+                Err(e) => {
+                    eprintln!("Error: {}", e);
+                    std::process::exit(e.exit_code());
+                }
+            }
+        }
+        Self::from_arg_matches(&matches).unwrap_or_else(|e| e.exit())
+    }
+
+    /// Output format from the command's format flags; `--porcelain` overrides
+    /// them, and agent mode defaults to JSON.
+    pub fn output_format(&self, format: &FormatArgs) -> crate::output::OutputFormat {
+        if self.porcelain {
+            crate::output::OutputFormat::Porcelain
+        } else if self.agent && !format.is_set() {
+            crate::output::OutputFormat::Json
+        } else {
+            format.output_format()
+        }
+    }
+}
+
+/// Options that narrow the set of models a command works on.
+#[derive(Args, Debug, Clone, Default)]
+pub struct ModelSelectionArgs {
+    /// Only models that accept this input modality (repeatable or
+    /// comma-separated): text, image, audio, video, pdf
+    #[arg(long, value_name = "MODALITY", value_delimiter = ',')]
+    pub input_modality: Vec<String>,
+
+    /// Only models that produce this output modality (repeatable or
+    /// comma-separated): text, image, audio, video, pdf
+    #[arg(long, value_name = "MODALITY", value_delimiter = ',')]
+    pub output_modality: Vec<String>,
+
+    /// Filter expression, e.g. "intelligence>50 && price<3 && tool_call"
+    #[arg(long)]
+    pub filter: Option<String>,
+
+    /// Only models released on or after a date, age, or quarter (2025-01-31, 90d, 2024-Q3)
+    #[arg(long, value_name = "WHEN")]
+    pub released_after: Option<String>,
+
+    /// Drop models whose name or slug contains this text (repeatable)
+    #[arg(long, value_name = "PATTERN")]
+    pub exclude: Vec<String>,
+
+    /// Only models with scores for at least N of the 10 benchmarks, so
+    /// barely-evaluated models do not look artificially cheap or fast
+    #[arg(long, value_name = "N")]
+    pub min_coverage: Option<usize>,
+
+    /// Include models that models.dev lists as deprecated, or that are gone
+    /// from the current data (with --as-of)
+    #[arg(long)]
+    pub include_retired: bool,
+
+    /// Only models you tagged with this (repeatable or comma-separated; see
+    /// `tag add`)
+    #[arg(long, value_name = "TAG", value_delimiter = ',')]
+    pub tag: Vec<String>,
+
+    /// Only models allowed by this policy file (see `policy check`)
+    #[arg(long, value_name = "FILE")]
+    pub policy: Option<PathBuf>,
+
+    /// Only models with a context window of at least this many tokens
+    /// (e.g. 128k, 1M; from the models table)
+    #[arg(long, value_name = "TOKENS", alias = "context-min")]
+    pub min_context: Option<String>,
+
+    /// Only models that can output at least this many tokens per response
+    /// (e.g. 32k; from the models table)
+    #[arg(long, value_name = "TOKENS")]
+    pub min_output: Option<String>,
+}
+
+impl ModelSelectionArgs {
+    /// Whether a selection option reads the models table.
+    pub fn reads_models(&self) -> bool {
+        self.min_context.is_some()
+            || self.min_output.is_some()
+            || !self.input_modality.is_empty()
+            || !self.output_modality.is_empty()
+    }
+
+    /// Check the options that need no data, so a typo fails before anything
+    /// is fetched. [`Self::to_selection`] runs the same checks.
+    pub fn validate(&self) -> Result<()> {
+        validate_modalities(&self.input_modality)?;
+        validate_modalities(&self.output_modality)?;
+        if let Some(filter) = &self.filter {
+            Filter::parse(filter)?;
+        }
+        if let Some(released_after) = &self.released_after {
+            parse_when("released-after", released_after)?;
+        }
+
+        let total = BENCHMARK_COLUMNS.len();
+        if let Some(min) = self.min_coverage.filter(|min| *min > total) {
+            return Err(AppError::Config(format!(
+                "Invalid --min-coverage {}. Models have at most {} benchmarks.",
+                min, total
+            )));
+        }
+
+        for tokens in [&self.min_context, &self.min_output].into_iter().flatten() {
+            parse_tokens(tokens)?;
+        }
+        Ok(())
+    }
+
+    /// Parse the options into a [`ModelSelection`], reading the tables
+    /// the options need.
+    pub fn to_selection(&self) -> Result<ModelSelection> {
+        self.validate()?;
+
+        let inputs = validate_modalities(&self.input_modality)?;
+        let outputs = validate_modalities(&self.output_modality)?;
+        let modal = if inputs.is_empty() && outputs.is_empty() {
+            None
+        } else {
+            Some(modal_models(Cache::new()?.base_dir(), &inputs, &outputs)?)
+        };
+
+        let filter = match &self.filter {
+            Some(filter) => {
+                Some(Filter::parse(filter)?.load_capabilities(Cache::new()?.base_dir())?)
+            }
+            None => None,
+        };
+        let released_after = self
+            .released_after
+            .as_deref()
+            .map(|s| parse_when("released-after", s))
+            .transpose()?;
+
+        let tagged = if self.tag.is_empty() {
+            None
+        } else {
+            Some(Notes::load()?.tagged(&self.tag)?)
+        };
+
+        let policy = match &self.policy {
+            Some(path) => Some(PolicyCheck::new(
+                Policy::load(path)?,
+                Cache::new()?.base_dir(),
+            )?),
+            None => None,
+        };
+
+        let min_context = self.min_context.as_deref().map(parse_tokens).transpose()?;
+        let min_output = self.min_output.as_deref().map(parse_tokens).transpose()?;
+        let token_limits = if min_context.is_some() || min_output.is_some() {
+            load_token_limits(Cache::new()?.base_dir())?
+        } else {
+            HashMap::new()
+        };
+
+        Ok(ModelSelection {
+            modal,
+            filter,
+            released_after,
+            exclude: self.exclude.clone(),
+            min_coverage: self.min_coverage,
+            include_retired: self.include_retired,
+            tagged,
+            policy,
+            min_context,
+            min_output,
+            token_limits,
+        })
+    }
+}
+
+/// Model name arguments with `-` replaced by the names piped on stdin.
+pub fn model_args(models: &[String]) -> Result<Vec<String>> {
+    if !models.iter().any(|m| m == "-") {
+        return Ok(models.to_vec());
+    }
+    let piped = read_model_list(std::io::stdin().lock())?;
+    if piped.is_empty() {
+        return Err(AppError::Config("No model names on stdin".into()));
+    }
+    let mut expanded = Vec::new();
+    for model in models {
+        if model == "-" {
+            expanded.extend(piped.iter().cloned());
+        } else {
+            expanded.push(model.clone());
+        }
+    }
+    Ok(expanded)
+}
+
+/// Read newline-separated model names, skipping blank lines.
+///
+/// Only the first tab-separated field of each line is used, so `--plain`
+/// query output can be piped in directly.
+fn read_model_list(reader: impl BufRead) -> Result<Vec<String>> {
+    let mut models = Vec::new();
+    for line in reader.lines() {
+        let line = line?;
+        let name = line.split('\t').next().unwrap_or_default().trim();
+        if !name.is_empty() {
+            models.push(name.to_string());
+        }
+    }
+    Ok(models)
+}
+
+/// Parse a date option value given as a date, age, or quarter relative to today.
+pub fn parse_when(flag: &str, value: &str) -> Result<NaiveDate> {
+    parse_relative_date(value, Utc::now().date_naive()).ok_or_else(|| {
+        AppError::Config(format!(
+            "Invalid --{} '{}'. Use a date (2025-01-31), age (90d, 6m), or quarter (2024-Q3).",
+            flag, value
+        ))
+    })
+}
+
+/// Output format flags shared by every command that prints a table.
+#[derive(Args, Debug, Clone, Copy, Default)]
+pub struct FormatArgs {
+    /// Output as JSON
+    #[arg(long, conflicts_with_all = ["csv", "table", "plain"])]
+    pub json: bool,
+
+    /// Output as CSV
+    #[arg(long, conflicts_with_all = ["json", "table", "plain"])]
+    pub csv: bool,
+
+    /// Output as ASCII table
+    #[arg(long, conflicts_with_all = ["json", "csv", "plain"])]
+    pub table: bool,
+
+    /// Output as plain text (tab-separated)
+    #[arg(long, conflicts_with_all = ["json", "csv", "table"])]
+    pub plain: bool,
+}
+
+impl FormatArgs {
+    /// Only `--json`, for commands with no tabular output.
+    pub fn json(json: bool) -> Self {
+        Self {
+            json,
+            ..Self::default()
+        }
+    }
+
+    /// Whether any format flag was given.
+    pub fn is_set(&self) -> bool {
+        self.json || self.csv || self.table || self.plain
+    }
+
+    /// Selected output format; Markdown when no flag is given.
+    pub fn output_format(&self) -> crate::output::OutputFormat {
+        if self.json {
+            crate::output::OutputFormat::Json
+        } else if self.csv {
+            crate::output::OutputFormat::Csv
+        } else if self.table {
+            crate::output::OutputFormat::Table
+        } else if self.plain {
+            crate::output::OutputFormat::Plain
+        } else {
+            crate::output::OutputFormat::Markdown
+        }
     }
 }
 
 #[derive(Subcommand, Debug)]
 pub enum CacheCommands {
-    /// Clear all cached data
-    Clear,
+    /// Clear all cached data, or one table or kind of file
+    Clear {
+        /// Only remove this table's Parquet data, e.g. benchmarks (see `tables`)
+        #[arg(long, value_name = "TABLE", conflicts_with_all = ["json_only", "parquet_only"])]
+        table: Option<String>,
+
+        /// Only remove cached API responses, keeping the Parquet tables
+        #[arg(long, conflicts_with = "parquet_only")]
+        json_only: bool,
+
+        /// Only remove the Parquet tables, keeping cached API responses
+        #[arg(long)]
+        parquet_only: bool,
+    },
     /// Show cache status
     Status,
 }
 
+#[derive(Subcommand, Debug)]
+pub enum MediaCommands {
+    /// Show one media model with its full category breakdown
+    Show {
+        /// Model slug or name
+        model: String,
+
+        /// Only search this media kind (searches all kinds by default)
+        #[arg(long, short = 'k')]
+        kind: Option<String>,
+
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Compare two media models, including their expected head-to-head win rate
+    Compare {
+        /// First model slug or name
+        first: String,
+
+        /// Second model slug or name
+        second: String,
+
+        /// Only search this media kind (searches all kinds by default)
+        #[arg(long, short = 'k')]
+        kind: Option<String>,
+
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+}
+
 #[derive(Subcommand, Debug)]
 pub enum ProfileCommands {
     /// Create a new profile
     Create {
-        /// Profile name
+        /// Profile name; the key comes from --api-key, or is prompted for
         name: String,
-        /// API key (will prompt if not provided)
-        #[arg(long)]
-        api_key: Option<String>,
     },
     /// List all profiles
     List,
@@ -197,6 +1160,114 @@ pub enum ProfileCommands {
     Show {
         /// Profile name (uses default if not specified)
         name: Option<String>,
+        /// Show the key API requests would use and where it comes from:
+        /// --api-key, then $ARTIFICIAL_ANALYSIS_API_KEY, then the named (or
+        /// --profile) profile, then the default profile
+        #[arg(long)]
+        effective: bool,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum NoteCommands {
+    /// Add a note to a model
+    Add {
+        /// Model slug or name (must resolve to a single model)
+        model: String,
+        /// Note text
+        text: String,
+    },
+    /// Remove a model's notes
+    Remove {
+        /// Model slug or name
+        model: String,
+        /// Remove only this note (numbered as in `note list`)
+        #[arg(long)]
+        index: Option<usize>,
+    },
+    /// List notes
+    List {
+        /// Only this model's notes (slug)
+        model: Option<String>,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum TagCommands {
+    /// Tag a model
+    Add {
+        /// Model slug or name (must resolve to a single model)
+        model: String,
+        /// Tags to add
+        #[arg(required = true)]
+        tags: Vec<String>,
+    },
+    /// Remove tags from a model
+    Remove {
+        /// Model slug or name
+        model: String,
+        /// Tags to remove
+        #[arg(required = true)]
+        tags: Vec<String>,
+    },
+    /// List tagged models as slug<TAB>tags
+    List {
+        /// Only models with this tag
+        #[arg(long)]
+        tag: Option<String>,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum AuditCommands {
+    /// List models whose AA and models.dev prices differ beyond a threshold
+    Pricing {
+        /// Minimum difference to report, in percent of the AA price
+        #[arg(long, value_name = "PCT", default_value = "10")]
+        threshold: f64,
+
+        /// Maximum number of models to show
+        #[arg(long, short = 'n', default_value = "20")]
+        limit: usize,
+
+        /// Show every matching model
+        #[arg(long, conflicts_with = "limit")]
+        all: bool,
+
+        #[command(flatten)]
+        format: FormatArgs,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum SchemaCommands {
+    /// Print every table's columns and types as JSON
+    Dump,
+
+    /// List tables and columns added, removed or retyped since a saved dump
+    Diff {
+        /// Schema JSON saved earlier with `schema dump`
+        old: PathBuf,
+
+        #[command(flatten)]
+        format: FormatArgs,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum PolicyCommands {
+    /// Explain whether a model is allowed; exits non-zero when it is not
+    Check {
+        /// Model slug or name (must resolve to a single model)
+        model: String,
+
+        /// Policy file [default: policy.toml next to config.toml]
+        #[arg(long, value_name = "FILE")]
+        policy: Option<PathBuf>,
+
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
     },
 }
 
@@ -231,3 +1302,23 @@ pub enum SkillCommands {
     /// List supported tools and their paths
     List,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_model_list() {
+        let input = "gpt-5\t70.1\n\n  claude 4.5 sonnet  \ngemini-2.5-pro\n";
+        assert_eq!(
+            read_model_list(input.as_bytes()).unwrap(),
+            vec!["gpt-5", "claude 4.5 sonnet", "gemini-2.5-pro"]
+        );
+    }
+
+    #[test]
+    fn test_model_args_without_dash() {
+        let models = vec!["gpt-5".to_string(), "claude".to_string()];
+        assert_eq!(model_args(&models).unwrap(), models);
+    }
+}
@@ -8,31 +8,100 @@
 //! - `benchmarks`: Pure Artificial Analysis data (benchmarks, performance, pricing)
 //! - `models`: Pure models.dev data (capabilities, limits, provider info)
 
-use crate::cache::Cache;
+use crate::cache::{is_read_only, read_only_error, Cache};
+use crate::config::Config;
 use crate::error::{AppError, Result};
+use crate::logging;
 use crate::models::{LlmModel, MediaModel};
-use crate::parquet;
+use crate::parquet::{self, ParquetSession};
 use crate::remote::RemoteDataClient;
-use crate::sources::artificial_analysis::models::{AaLlmModel, AaLlmRow};
+use crate::schema::MEDIA_TABLES;
+use crate::snapshot::Snapshot;
+use crate::sources::artificial_analysis::models::{AaExtraRow, AaLlmModel, AaLlmRow};
+use crate::sources::artificial_analysis::validation::validate_llm_models;
 use crate::sources::artificial_analysis::AaClient;
 use crate::sources::models_dev::models::{flatten_response, ModelsDevRow};
 use crate::sources::models_dev::ModelsDevClient;
 use duckdb::Connection;
 use std::path::Path;
+use std::sync::Mutex;
+
+/// Columns read into `LlmModel`, in field order.
+///
+/// Identity columns (up to `creator_slug`) are always read; the rest can be
+/// left out of a projected read.
+const LLM_COLUMNS: &[&str] = &[
+    "id",
+    "name",
+    "slug",
+    "creator",
+    "creator_slug",
+    "release_date",
+    "intelligence",
+    "coding",
+    "math",
+    "mmlu_pro",
+    "gpqa",
+    "hle",
+    "livecodebench",
+    "scicode",
+    "math_500",
+    "aime",
+    "input_price",
+    "output_price",
+    "price",
+    "tps",
+    "latency",
+];
+
+/// Number of identity columns at the start of [`LLM_COLUMNS`].
+const LLM_IDENTITY_COLUMNS: usize = 5;
+
+/// SELECT list for [`LLM_COLUMNS`], reading only `columns` when given.
+///
+/// Columns left out are selected as NULL, so every row still has one value
+/// per `LlmModel` field and DuckDB skips reading them from the file.
+fn llm_select_list(columns: Option<&[&str]>) -> String {
+    LLM_COLUMNS
+        .iter()
+        .enumerate()
+        .map(|(i, column)| {
+            let wanted = i < LLM_IDENTITY_COLUMNS
+                || columns.map_or(true, |columns| columns.contains(column));
+            match (wanted, *column) {
+                (false, column) => format!("NULL AS {}", column),
+                (true, "release_date") => "TRY_CAST(release_date AS DATE) AS release_date".into(),
+                (true, column) => column.to_string(),
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
 
 /// Client for fetching data using hosted GitHub Releases.
 /// This is the default mode that requires no API key.
 pub struct HostedDataClient {
     remote: RemoteDataClient,
     cache: Cache,
+    /// Parsed benchmarks (all columns), reused by later calls so chained
+    /// operations in one invocation read the Parquet file only once.
+    llm_models: Mutex<Option<Vec<LlmModel>>>,
 }
 
 impl HostedDataClient {
     /// Create a new hosted data client.
     pub fn new() -> Result<Self> {
-        let cache = Cache::new()?;
+        Self::with_cache(Cache::new()?)
+    }
+
+    /// Create a hosted data client that reads and writes `cache`.
+    pub fn with_cache(cache: Cache) -> Result<Self> {
         let remote = RemoteDataClient::new(cache.base_dir().to_path_buf())?;
-        Ok(Self { remote, cache })
+        Ok(Self {
+            remote,
+            cache,
+            llm_models: Mutex::new(None),
+        })
     }
 
     /// Get the cache instance.
@@ -47,26 +116,78 @@ impl HostedDataClient {
 
     /// Fetch LLM models from hosted data.
     pub async fn get_llm_models(&self, refresh: bool) -> Result<Vec<LlmModel>> {
+        self.get_llm_models_projected(refresh, None).await
+    }
+
+    /// Fetch LLM models from hosted data, reading only `columns` (plus the
+    /// identity columns) when given. Other fields are left as `None`.
+    ///
+    /// Full loads are memoized: later calls without `refresh` return the
+    /// same models (with every column) without touching the cache file.
+    pub async fn get_llm_models_projected(
+        &self,
+        refresh: bool,
+        columns: Option<&[&str]>,
+    ) -> Result<Vec<LlmModel>> {
+        if !refresh {
+            if let Some(models) = self.memoized_llm_models() {
+                logging::debug(
+                    "cache",
+                    format_args!("benchmarks: reusing {} parsed models", models.len()),
+                );
+                return Ok(models);
+            }
+        }
+
         // Ensure we have the data
         let parquet_path = self.remote.ensure_parquet("benchmarks", refresh).await?;
 
         // Load from parquet
-        self.load_llms_from_parquet(&parquet_path)
+        let models = self.load_llms_from_parquet(&parquet_path, columns)?;
+        logging::debug(
+            "cache",
+            format_args!("benchmarks: loaded {} models", models.len()),
+        );
+        let mut memo = self.llm_models.lock().unwrap_or_else(|e| e.into_inner());
+        if columns.is_none() {
+            *memo = Some(models.clone());
+        } else if refresh {
+            // A projected refresh must not leave older data behind
+            *memo = None;
+        }
+        Ok(models)
+    }
+
+    /// Models parsed by an earlier full load, if any.
+    fn memoized_llm_models(&self) -> Option<Vec<LlmModel>> {
+        self.llm_models
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+    }
+
+    /// Load LLM models from a stored benchmarks snapshot (`--as-of`).
+    pub fn get_llm_models_at(
+        &self,
+        snapshot: &Snapshot,
+        columns: Option<&[&str]>,
+    ) -> Result<Vec<LlmModel>> {
+        self.load_llms_from_parquet(&snapshot.path, columns)
     }
 
     /// Load LLM models from a parquet file.
-    fn load_llms_from_parquet(&self, path: &Path) -> Result<Vec<LlmModel>> {
+    fn load_llms_from_parquet(
+        &self,
+        path: &Path,
+        columns: Option<&[&str]>,
+    ) -> Result<Vec<LlmModel>> {
         let conn = Connection::open_in_memory()
             .map_err(|e| AppError::Cache(format!("DuckDB error: {}", e)))?;
 
         let path_str = path.to_string_lossy();
         let sql = format!(
-            r#"SELECT
-                id, name, slug, creator, creator_slug, release_date,
-                intelligence, coding, math, mmlu_pro, gpqa, hle,
-                livecodebench, scicode, math_500, aime,
-                input_price, output_price, price, tps, latency
-            FROM read_parquet('{}')"#,
+            "SELECT {} FROM read_parquet('{}')",
+            llm_select_list(columns),
             path_str
         );
         let mut stmt = conn
@@ -81,8 +202,10 @@ impl HostedDataClient {
                     slug: row.get(2)?,
                     creator: row.get(3)?,
                     creator_slug: row.get(4)?,
+                    status: None,
                     release_date: row.get(5)?,
                     intelligence: row.get(6)?,
+                    intelligence_error: None,
                     coding: row.get(7)?,
                     math: row.get(8)?,
                     mmlu_pro: row.get(9)?,
@@ -97,6 +220,10 @@ impl HostedDataClient {
                     price: row.get(18)?,
                     tps: row.get(19)?,
                     latency: row.get(20)?,
+                    tps_percentiles: None,
+                    latency_percentiles: None,
+                    npm_package: None,
+                    env_vars: None,
                 })
             })
             .map_err(|e| AppError::Cache(format!("DuckDB error: {}", e)))?
@@ -117,7 +244,7 @@ impl HostedDataClient {
         let sql = format!(
             r#"SELECT
                 id, name, slug, creator,
-                elo, rank, release_date
+                elo, rank, TRY_CAST(release_date AS DATE) AS release_date
             FROM read_parquet('{}')"#,
             path_str
         );
@@ -142,6 +269,9 @@ impl HostedDataClient {
                     rank: row.get::<_, Option<i32>>(5)?.map(|r| r as u32),
                     ci95: None,
                     appearances: None,
+                    price_per_1m_characters: None,
+                    price_per_minute: None,
+                    price_per_second: None,
                     release_date: row.get(6)?,
                     categories: None,
                     extra: serde_json::Value::Null,
@@ -190,11 +320,53 @@ impl HostedDataClient {
         self.load_media_from_parquet(&parquet_path)
     }
 
+    /// Fetch models.dev data if it is missing or older than its TTL.
+    pub async fn ensure_models(&self) -> Result<()> {
+        self.remote
+            .ensure_parquet("models", false)
+            .await
+            .map(|_| ())
+    }
+
     /// Refresh models.dev data.
     pub async fn refresh_models(&self) -> Result<()> {
         let _ = self.remote.ensure_parquet("models", true).await?;
         Ok(())
     }
+
+    /// Refresh the per-table media category files.
+    ///
+    /// Returns the number of files fetched.
+    pub async fn refresh_media_categories(&self) -> Result<usize> {
+        let names: Vec<String> = MEDIA_TABLES
+            .iter()
+            .map(|table| format!("{}_categories", table.name))
+            .collect();
+        self.refresh_published(&names).await
+    }
+
+    /// Refresh the `llms_extra` table of untyped AA fields.
+    ///
+    /// Returns the number of files fetched.
+    pub async fn refresh_llms_extra(&self) -> Result<usize> {
+        self.refresh_published(&["llms_extra".to_string()]).await
+    }
+
+    /// Fetch the given files if the data release publishes them.
+    ///
+    /// Older data releases do not include every table, so only files listed
+    /// in the manifest are fetched.
+    async fn refresh_published(&self, names: &[String]) -> Result<usize> {
+        let manifest = self.remote.fetch_manifest().await?;
+        let mut fetched = 0;
+        for name in names {
+            if manifest.files.contains_key(&format!("{}.parquet", name)) {
+                self.remote.ensure_parquet(name, true).await?;
+                fetched += 1;
+            }
+        }
+        Ok(fetched)
+    }
 }
 
 /// Unified client that fetches from both AA and models.dev APIs.
@@ -209,11 +381,15 @@ pub struct Client {
     aa_client: AaClient,
     md_client: ModelsDevClient,
     cache: Cache,
+    parquet: ParquetSession,
 }
 
 impl Client {
     /// Create a new unified client.
-    pub fn new(api_key: String, profile_name: String) -> Result<Self> {
+    ///
+    /// Parquet cache files are written through `parquet`, which carries the
+    /// compression and row group settings.
+    pub fn new(api_key: String, profile_name: String, parquet: ParquetSession) -> Result<Self> {
         let aa_client = AaClient::new(api_key, profile_name)?;
         let md_client = ModelsDevClient::new()?;
         let cache = Cache::new()?;
@@ -222,9 +398,32 @@ impl Client {
             aa_client,
             md_client,
             cache,
+            parquet,
         })
     }
 
+    /// Create a client with the API key of `profile`, or of the default
+    /// profile (or environment) when `None`.
+    pub fn for_profile(
+        config: &Config,
+        profile: Option<&str>,
+        parquet: ParquetSession,
+    ) -> Result<Self> {
+        let api_key = config.get_api_key(profile)?;
+        let profile_name = profile
+            .map(String::from)
+            .or(config.default_profile.clone())
+            .unwrap_or_else(|| "default".into());
+        Self::new(api_key, profile_name, parquet)
+    }
+
+    /// Write Parquet tables to `cache` instead of the default cache, e.g. a
+    /// refresh's staging directory.
+    pub fn with_cache(mut self, cache: Cache) -> Self {
+        self.cache = cache;
+        self
+    }
+
     /// Get the cache instance.
     pub fn cache(&self) -> &Cache {
         &self.cache
@@ -243,20 +442,56 @@ impl Client {
 
         // Quick path: if not refreshing and cache exists, load from cache
         if !refresh && benchmarks_path.exists() {
-            if let Ok(models) = self.load_llms_from_parquet(&benchmarks_path) {
-                return Ok(models);
+            match self.load_llms_from_parquet(&benchmarks_path) {
+                Ok(models) => {
+                    logging::debug(
+                        "cache",
+                        format_args!("benchmarks: loaded {} cached models", models.len()),
+                    );
+                    return Ok(models);
+                }
+                Err(e) => logging::debug(
+                    "cache",
+                    format_args!("benchmarks: unreadable cache, refetching: {}", e),
+                ),
             }
         }
 
         // Fetch AA data
         let aa_models = self.aa_client.fetch_llm_models(refresh).await?;
 
+        // Warn when the API response no longer matches the expected schema
+        for warning in validate_llm_models(&aa_models).warnings() {
+            eprintln!("Warning: {}", warning);
+        }
+
+        // A read-only cache keeps whatever it has
+        if is_read_only() {
+            return Ok(aa_models.iter().map(aa_to_llm_model).collect());
+        }
+
         // Convert to rows and write to parquet
         let rows: Vec<AaLlmRow> = aa_models.iter().map(AaLlmRow::from).collect();
-        if let Err(e) = parquet::write_benchmarks_parquet(&rows, &benchmarks_path) {
+        if let Err(e) = parquet::write_benchmarks_parquet(&rows, &benchmarks_path, &self.parquet) {
             eprintln!("Warning: Failed to write benchmarks Parquet cache: {}", e);
         }
 
+        // Capture fields without typed support in the llms_extra table
+        let extra_rows: Vec<AaExtraRow> =
+            aa_models.iter().flat_map(AaExtraRow::from_model).collect();
+        logging::debug(
+            "merge",
+            format_args!(
+                "benchmarks: {} models, {} llms_extra rows",
+                rows.len(),
+                extra_rows.len()
+            ),
+        );
+        let extra_path = self.cache.parquet_path("llms_extra");
+        if let Err(e) = parquet::write_llms_extra_parquet(&extra_rows, &extra_path, &self.parquet) {
+            eprintln!("Warning: Failed to write llms_extra Parquet cache: {}", e);
+        }
+
         // Also refresh models.dev data when refreshing
         if refresh {
             if let Err(e) = self.refresh_models().await {
@@ -278,7 +513,8 @@ impl Client {
         let path_str = path.to_string_lossy();
         let sql = format!(
             r#"SELECT
-                id, name, slug, creator, creator_slug, release_date,
+                id, name, slug, creator, creator_slug,
+                TRY_CAST(release_date AS DATE) AS release_date,
                 intelligence, coding, math, mmlu_pro, gpqa, hle,
                 livecodebench, scicode, math_500, aime,
                 input_price, output_price, price, tps, latency
@@ -297,8 +533,10 @@ impl Client {
                     slug: row.get(2)?,
                     creator: row.get(3)?,
                     creator_slug: row.get(4)?,
+                    status: None,
                     release_date: row.get(5)?,
                     intelligence: row.get(6)?,
+                    intelligence_error: None,
                     coding: row.get(7)?,
                     math: row.get(8)?,
                     mmlu_pro: row.get(9)?,
@@ -313,6 +551,10 @@ impl Client {
                     price: row.get(18)?,
                     tps: row.get(19)?,
                     latency: row.get(20)?,
+                    tps_percentiles: None,
+                    latency_percentiles: None,
+                    npm_package: None,
+                    env_vars: None,
                 })
             })
             .map_err(|e| crate::error::AppError::Cache(format!("DuckDB error: {}", e)))?
@@ -328,12 +570,23 @@ impl Client {
     ///
     /// This is called during `update` to refresh the models table.
     pub async fn refresh_models(&self) -> Result<()> {
+        if is_read_only() {
+            return Err(read_only_error("refresh models"));
+        }
         let models_path = self.cache.parquet_path("models");
 
         match self.md_client.fetch().await {
             Ok(response) => {
                 let rows: Vec<ModelsDevRow> = flatten_response(&response);
-                if let Err(e) = parquet::write_models_parquet(&rows, &models_path) {
+                logging::debug(
+                    "merge",
+                    format_args!(
+                        "models.dev: {} providers flattened to {} rows",
+                        response.len(),
+                        rows.len()
+                    ),
+                );
+                if let Err(e) = parquet::write_models_parquet(&rows, &models_path, &self.parquet) {
                     eprintln!("Warning: Failed to write models Parquet cache: {}", e);
                 }
                 Ok(())
@@ -347,53 +600,56 @@ impl Client {
 
     // ========== Media Models (AA-only) ==========
 
+    /// Write a media table and its category scores to the Parquet cache.
+    fn write_media_cache(&self, table: &str, models: &[MediaModel]) {
+        if is_read_only() {
+            return;
+        }
+        let parquet_path = self.cache.parquet_path(table);
+        if let Err(e) = parquet::write_media_parquet(models, &parquet_path, &self.parquet) {
+            eprintln!("Warning: Failed to write Parquet cache: {}", e);
+        }
+
+        let categories_path = self.cache.parquet_path(&format!("{}_categories", table));
+        if let Err(e) =
+            parquet::write_media_categories_parquet(table, models, &categories_path, &self.parquet)
+        {
+            eprintln!("Warning: Failed to write {} categories cache: {}", table, e);
+        }
+    }
+
     /// Fetch text-to-image models.
     pub async fn get_text_to_image(&self, refresh: bool) -> Result<Vec<MediaModel>> {
         let models = self.aa_client.fetch_text_to_image(refresh).await?;
-        let parquet_path = self.cache.parquet_path("text_to_image");
-        if let Err(e) = parquet::write_media_parquet(&models, &parquet_path) {
-            eprintln!("Warning: Failed to write Parquet cache: {}", e);
-        }
+        self.write_media_cache("text_to_image", &models);
         Ok(models)
     }
 
     /// Fetch image-editing models.
     pub async fn get_image_editing(&self, refresh: bool) -> Result<Vec<MediaModel>> {
         let models = self.aa_client.fetch_image_editing(refresh).await?;
-        let parquet_path = self.cache.parquet_path("image_editing");
-        if let Err(e) = parquet::write_media_parquet(&models, &parquet_path) {
-            eprintln!("Warning: Failed to write Parquet cache: {}", e);
-        }
+        self.write_media_cache("image_editing", &models);
         Ok(models)
     }
 
     /// Fetch text-to-speech models.
     pub async fn get_text_to_speech(&self, refresh: bool) -> Result<Vec<MediaModel>> {
         let models = self.aa_client.fetch_text_to_speech(refresh).await?;
-        let parquet_path = self.cache.parquet_path("text_to_speech");
-        if let Err(e) = parquet::write_media_parquet(&models, &parquet_path) {
-            eprintln!("Warning: Failed to write Parquet cache: {}", e);
-        }
+        self.write_media_cache("text_to_speech", &models);
         Ok(models)
     }
 
     /// Fetch text-to-video models.
     pub async fn get_text_to_video(&self, refresh: bool) -> Result<Vec<MediaModel>> {
         let models = self.aa_client.fetch_text_to_video(refresh).await?;
-        let parquet_path = self.cache.parquet_path("text_to_video");
-        if let Err(e) = parquet::write_media_parquet(&models, &parquet_path) {
-            eprintln!("Warning: Failed to write Parquet cache: {}", e);
-        }
+        self.write_media_cache("text_to_video", &models);
         Ok(models)
     }
 
     /// Fetch image-to-video models.
     pub async fn get_image_to_video(&self, refresh: bool) -> Result<Vec<MediaModel>> {
         let models = self.aa_client.fetch_image_to_video(refresh).await?;
-        let parquet_path = self.cache.parquet_path("image_to_video");
-        if let Err(e) = parquet::write_media_parquet(&models, &parquet_path) {
-            eprintln!("Warning: Failed to write Parquet cache: {}", e);
-        }
+        self.write_media_cache("image_to_video", &models);
         Ok(models)
     }
 }
@@ -407,10 +663,12 @@ fn aa_to_llm_model(aa: &AaLlmModel) -> LlmModel {
         id: aa.id.clone(),
         name: aa.name.clone(),
         slug: aa.slug.clone(),
-        release_date: aa.release_date.clone(),
+        release_date: aa.release_date,
         creator: aa.model_creator.name.clone(),
         creator_slug: aa.model_creator.slug.clone(),
+        status: None,
         intelligence: evaluations.and_then(|e| e.artificial_analysis_intelligence_index),
+        intelligence_error: None,
         coding: evaluations.and_then(|e| e.artificial_analysis_coding_index),
         math: evaluations.and_then(|e| e.artificial_analysis_math_index),
         mmlu_pro: evaluations.and_then(|e| e.mmlu_pro),
@@ -425,5 +683,28 @@ fn aa_to_llm_model(aa: &AaLlmModel) -> LlmModel {
         price: pricing.and_then(|p| p.price_1m_blended_3_to_1),
         tps: aa.median_output_tokens_per_second,
         latency: aa.median_time_to_first_token_seconds,
+        tps_percentiles: None,
+        latency_percentiles: None,
+        npm_package: None,
+        env_vars: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_llm_select_list_projection() {
+        let all = llm_select_list(None);
+        assert!(all.starts_with("id, name, slug, creator, creator_slug, TRY_CAST(release_date"));
+        assert!(all.ends_with("price, tps, latency"));
+        assert!(!all.contains("NULL"));
+
+        let projected = llm_select_list(Some(&["price", "release_date"]));
+        assert!(projected.starts_with("id, name, slug, creator, creator_slug, TRY_CAST("));
+        assert!(projected.contains("NULL AS intelligence"));
+        assert!(projected.contains(", price, NULL AS tps"));
+        assert_eq!(projected.split(", ").count(), LLM_COLUMNS.len());
     }
 }
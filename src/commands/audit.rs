@@ -0,0 +1,198 @@
+//! Audit command - cross-check Artificial Analysis against models.dev.
+//!
+//! `audit pricing` lists benchmarked models whose AA price and models.dev
+//! price differ by more than a threshold, with both values and the percent
+//! difference, to catch stale data in either source. Slugs are matched as
+//! in the open view (provider prefix stripped, lowercased).
+
+use crate::cache::Cache;
+use crate::cli::invocation;
+use crate::error::{AppError, Result};
+use crate::output::{note_hidden, OutputFormat};
+use crate::query::{format_query_result, QueryExecutor, QueryResult};
+use crate::schema::BENCHMARKS;
+use crate::sources::MODELS;
+use std::path::Path;
+
+/// Build the SQL for models whose input or output price diverges by more
+/// than `threshold` percent.
+///
+/// The models.dev price is the creator's own provider's when it serves the
+/// model (the `basis` column names it), otherwise the median across
+/// providers. The difference is relative to the AA price; a zero AA price
+/// only matches a zero models.dev price.
+fn pricing_divergence_sql(threshold: f64, limit: Option<usize>) -> String {
+    let limit = limit
+        .map(|limit| format!("\nLIMIT {}", limit))
+        .unwrap_or_default();
+    format!(
+        "WITH prices AS (
+    SELECT
+        b.slug AS model,
+        any_value(b.name) AS name,
+        any_value(b.input_price) AS aa_input,
+        any_value(b.output_price) AS aa_output,
+        coalesce(
+            max(m.cost_input) FILTER (WHERE m.provider_id = lower(b.creator_slug)),
+            median(m.cost_input)
+        ) AS dev_input,
+        coalesce(
+            max(m.cost_output) FILTER (WHERE m.provider_id = lower(b.creator_slug)),
+            median(m.cost_output)
+        ) AS dev_output,
+        CASE WHEN bool_or(m.provider_id = lower(b.creator_slug))
+            THEN any_value(lower(b.creator_slug))
+            ELSE 'median of ' || CAST(count(DISTINCT m.provider_id) AS VARCHAR)
+        END AS basis
+    FROM benchmarks b
+    JOIN models m ON lower(regexp_replace(m.model_id, '^.*/', '')) = lower(b.slug)
+    GROUP BY b.slug
+),
+diffs AS (
+    SELECT *,
+        CASE WHEN aa_input = 0 AND dev_input = 0 THEN 0
+            ELSE 100 * (dev_input - aa_input) / nullif(aa_input, 0) END AS input_diff_pct,
+        CASE WHEN aa_output = 0 AND dev_output = 0 THEN 0
+            ELSE 100 * (dev_output - aa_output) / nullif(aa_output, 0) END AS output_diff_pct
+    FROM prices
+)
+SELECT
+    model,
+    name,
+    basis,
+    aa_input,
+    dev_input AS models_dev_input,
+    round(input_diff_pct, 1) AS input_diff_pct,
+    aa_output,
+    dev_output AS models_dev_output,
+    round(output_diff_pct, 1) AS output_diff_pct
+FROM diffs
+WHERE abs(input_diff_pct) > {threshold} OR abs(output_diff_pct) > {threshold}
+ORDER BY greatest(abs(coalesce(input_diff_pct, 0)), abs(coalesce(output_diff_pct, 0))) DESC, model{limit}"
+    )
+}
+
+/// Query diverging prices from the cache in `cache_dir`.
+///
+/// Both tables are needed for a comparison, so a missing one is an error.
+fn query_pricing_divergence(
+    cache_dir: &Path,
+    threshold: f64,
+    limit: Option<usize>,
+) -> Result<QueryResult> {
+    if !threshold.is_finite() || threshold < 0.0 {
+        return Err(AppError::Config(format!(
+            "Invalid --threshold {}. Use a percentage of 0 or more, e.g. 10.",
+            threshold
+        )));
+    }
+    for table in [&BENCHMARKS, &MODELS] {
+        if !table.is_cached(cache_dir) {
+            return Err(AppError::Config(format!(
+                "The {} table is not cached. Run '{}' first.",
+                table.name,
+                invocation("refresh")
+            )));
+        }
+    }
+    let executor = QueryExecutor::new(cache_dir.to_path_buf());
+    executor.execute(&pricing_divergence_sql(threshold, limit))
+}
+
+/// Run `audit pricing`.
+///
+/// When `limit` hides rows, a note on stderr says how many there are,
+/// unless `quiet`.
+pub fn run_pricing(
+    threshold: f64,
+    limit: Option<usize>,
+    format: OutputFormat,
+    fail_on_empty: bool,
+    quiet: bool,
+) -> Result<()> {
+    let cache = Cache::new()?;
+    let result = query_pricing_divergence(cache.base_dir(), threshold, limit)?;
+    println!("{}", format_query_result(&result, format));
+    if !quiet && limit.is_some_and(|limit| result.len() >= limit) {
+        let executor = QueryExecutor::new(cache.base_dir().to_path_buf());
+        let total = executor.count(&pricing_divergence_sql(threshold, None))?;
+        note_hidden(result.len(), total, "--limit");
+    }
+    if fail_on_empty && result.is_empty() {
+        return Err(AppError::Empty(format!(
+            "no prices diverge by more than {}%",
+            threshold
+        )));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use duckdb::Connection;
+    use tempfile::TempDir;
+
+    fn write_parquet(path: &Path, select: &str) {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute(
+            &format!(
+                "COPY ({}) TO '{}' (FORMAT PARQUET)",
+                select,
+                path.to_string_lossy()
+            ),
+            [],
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_pricing_divergence() {
+        let temp_dir = TempDir::new().unwrap();
+        write_parquet(
+            &temp_dir.path().join("benchmarks.parquet"),
+            "SELECT * FROM (VALUES
+                ('gpt-4o', 'GPT-4o', 'openai', 2.5::DOUBLE, 10.0::DOUBLE),
+                ('llama-4', 'Llama 4', 'meta', 0.2, 0.6),
+                ('free-1', 'Free 1', 'acme', 0.0, 0.0),
+                ('solo', 'Solo', 'acme', 1.0, 1.0)
+            ) t(slug, name, creator_slug, input_price, output_price)",
+        );
+        write_parquet(
+            &temp_dir.path().join("models.parquet"),
+            "SELECT * FROM (VALUES
+                ('openai', 'gpt-4o', 5.0::DOUBLE, 10.0::DOUBLE),
+                ('azure', 'gpt-4o', 2.5, 10.0),
+                ('groq', 'meta/llama-4', 0.2, 0.6),
+                ('together', 'llama-4', 0.3, 0.9),
+                ('fireworks', 'llama-4', 0.21, 0.63),
+                ('acme', 'free-1', 0.0, 0.0)
+            ) t(provider_id, model_id, cost_input, cost_output)",
+        );
+
+        let result = query_pricing_divergence(temp_dir.path(), 10.0, Some(10)).unwrap();
+        let models: Vec<&str> = result.rows.iter().map(|r| r[0].as_str()).collect();
+        // The creator's own price wins over the cheaper reseller; llama-4's
+        // median (0.21/0.63) is within 10%
+        assert_eq!(models, vec!["gpt-4o"]);
+        assert_eq!(result.rows[0][2], "openai");
+        assert_eq!(result.rows[0][5], "100.00");
+
+        let result = query_pricing_divergence(temp_dir.path(), 4.0, Some(10)).unwrap();
+        let models: Vec<&str> = result.rows.iter().map(|r| r[0].as_str()).collect();
+        assert_eq!(models, vec!["gpt-4o", "llama-4"]);
+        assert_eq!(result.rows[1][2], "median of 3");
+
+        let err = query_pricing_divergence(temp_dir.path(), -1.0, Some(10)).unwrap_err();
+        assert_eq!(err.exit_code(), 2);
+    }
+
+    #[test]
+    fn test_pricing_divergence_needs_both_tables() {
+        let temp_dir = TempDir::new().unwrap();
+        let err = query_pricing_divergence(temp_dir.path(), 10.0, Some(10))
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("benchmarks table is not cached"));
+    }
+}
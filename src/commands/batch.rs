@@ -0,0 +1,206 @@
+//! Batch command - run many commands from a file in one process.
+//!
+//! Each non-blank line that does not start with `#` is one invocation,
+//! quoted as for a POSIX shell, with or without the binary name in front:
+//!
+//! ```text
+//! compare gpt-5 "claude 4.5 sonnet" --json
+//! which-llm cost gpt-5 --input 10k --output 2k
+//! ```
+//!
+//! Every line is parsed before any runs, so a typo on line 40 does not leave
+//! the first 39 commands half-applied. Benchmarks are loaded once and shared
+//! by all lines (see `crate::run`).
+
+use crate::cli::{bin_name, Cli, Commands, BIN_NAMES};
+use crate::error::{AppError, Result};
+use clap::parser::ValueSource;
+use clap::{ArgMatches, CommandFactory, FromArgMatches};
+use std::io::Read;
+
+/// One parsed line of a batch file.
+#[derive(Debug)]
+pub struct BatchLine {
+    /// 1-based line number in the file.
+    pub number: usize,
+    /// The line as written, for progress and error messages.
+    pub text: String,
+    pub cli: Cli,
+}
+
+/// Split a command line into arguments like a POSIX shell, for single and
+/// double quotes (no escapes or expansions).
+///
+/// # Errors
+///
+/// Returns `AppError::Config` for an unterminated quote.
+pub fn split_args(line: &str) -> Result<Vec<String>> {
+    let mut words = Vec::new();
+    let mut word = String::new();
+    let mut quote = None;
+    let mut in_word = false;
+    for ch in line.chars() {
+        match (quote, ch) {
+            (Some(q), c) if c == q => quote = None,
+            (Some(_), c) => word.push(c),
+            (None, '"' | '\'') => {
+                quote = Some(ch);
+                in_word = true;
+            }
+            (None, c) if c.is_whitespace() => {
+                if in_word {
+                    words.push(std::mem::take(&mut word));
+                    in_word = false;
+                }
+            }
+            (None, c) => {
+                word.push(c);
+                in_word = true;
+            }
+        }
+    }
+    if let Some(q) = quote {
+        return Err(AppError::Config(format!("Unterminated {} quote", q)));
+    }
+    if in_word {
+        words.push(word);
+    }
+    Ok(words)
+}
+
+/// The first global flag given on a line's command line that is applied
+/// once per process (in `crate::run`), so a batch line cannot change it.
+///
+/// Values from the environment (`WHICH_LLM_HOME` and friends) are inherited
+/// from the batch process itself, so only the command line counts.
+fn process_flag(matches: &ArgMatches) -> Option<&'static str> {
+    [
+        ("api_key", "--api-key"),
+        ("home", "--home"),
+        ("channel", "--channel"),
+        ("date_format", "--date-format"),
+        ("log_file", "--log-file"),
+        ("ascii", "--ascii"),
+        ("attribution", "--attribution"),
+    ]
+    .into_iter()
+    .find_map(|(id, flag)| {
+        (matches.value_source(id) == Some(ValueSource::CommandLine)).then_some(flag)
+    })
+}
+
+/// Parse batch file contents into commands.
+///
+/// # Errors
+///
+/// Returns `AppError::Config` naming the line for a line that does not
+/// parse, is itself a `batch` command, or sets a global flag that applies
+/// to the whole process (such as `--home` or `--api-key`).
+pub fn parse(contents: &str) -> Result<Vec<BatchLine>> {
+    let mut lines = Vec::new();
+    for (i, text) in contents.lines().enumerate() {
+        let number = i + 1;
+        let text = text.trim();
+        if text.is_empty() || text.starts_with('#') {
+            continue;
+        }
+        let mut args =
+            split_args(text).map_err(|e| AppError::Config(format!("Line {}: {}", number, e)))?;
+        if args
+            .first()
+            .is_some_and(|a| BIN_NAMES.contains(&a.as_str()))
+        {
+            args.remove(0);
+        }
+        let argv = std::iter::once(bin_name().to_string()).chain(args);
+        let invalid = |e: clap::Error| {
+            AppError::Config(format!(
+                "Line {}: {}",
+                number,
+                e.render().to_string().trim_end()
+            ))
+        };
+        let matches = Cli::command().try_get_matches_from(argv).map_err(invalid)?;
+        let cli = Cli::from_arg_matches(&matches).map_err(invalid)?;
+        if matches!(cli.command, Commands::Batch { .. }) {
+            return Err(AppError::Config(format!(
+                "Line {}: batch files cannot run batch",
+                number
+            )));
+        }
+        if let Some(flag) = process_flag(&matches) {
+            return Err(AppError::Config(format!(
+                "Line {}: {} applies to the whole batch; pass it to 'batch' instead",
+                number, flag
+            )));
+        }
+        lines.push(BatchLine {
+            number,
+            text: text.to_string(),
+            cli,
+        });
+    }
+    Ok(lines)
+}
+
+/// Read and parse a batch file; `-` reads stdin.
+pub fn read(path: &str) -> Result<Vec<BatchLine>> {
+    let contents = if path == "-" {
+        let mut contents = String::new();
+        std::io::stdin().read_to_string(&mut contents)?;
+        contents
+    } else {
+        std::fs::read_to_string(path)
+            .map_err(|e| AppError::Config(format!("Could not read batch file '{}': {}", path, e)))?
+    };
+    parse(&contents)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_args() {
+        assert_eq!(
+            split_args(r#"compare "gpt-5 (high)" gpt-4o	--filter 'a>1'"#).unwrap(),
+            vec!["compare", "gpt-5 (high)", "gpt-4o", "--filter", "a>1"]
+        );
+        assert_eq!(split_args(r#"get """#).unwrap(), vec!["get", ""]);
+        assert!(split_args(r#"compare "gpt-5"#).is_err());
+    }
+
+    #[test]
+    fn test_parse() {
+        let lines = parse(
+            "# shortlist for the bake-off\n\
+             \n\
+             compare gpt-5 claude --json\n\
+             which-llm tokens 1.5M\n",
+        )
+        .unwrap();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0].number, 3);
+        assert!(matches!(lines[0].cli.command, Commands::Compare { .. }));
+        assert_eq!(lines[1].text, "which-llm tokens 1.5M");
+        assert!(matches!(lines[1].cli.command, Commands::Tokens { .. }));
+    }
+
+    #[test]
+    fn test_parse_errors_name_the_line() {
+        let err = parse("tokens 1k\ncomapre gpt-5\n").unwrap_err().to_string();
+        assert!(err.contains("Line 2"), "{}", err);
+        let err = parse("batch other.txt\n").unwrap_err().to_string();
+        assert!(err.contains("Line 1: batch files cannot run batch"));
+        for flag in ["--home /tmp", "--ascii", "--date-format iso", "--api-key k"] {
+            let err = parse(&format!("tokens 1k\ntokens 2k {}\n", flag))
+                .unwrap_err()
+                .to_string();
+            assert!(
+                err.contains("Line 2") && err.contains("whole batch"),
+                "{}",
+                err
+            );
+        }
+    }
+}
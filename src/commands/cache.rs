@@ -1,13 +1,46 @@
 //! Cache management command.
 
-use crate::cache::Cache;
-use crate::error::Result;
+use crate::cache::{Cache, ClearScope};
+use crate::cli::invocation;
+use crate::error::{AppError, Result};
+use crate::schema::{get_table_def, suggest_table};
 
-/// Clear the cache.
-pub fn clear() -> Result<()> {
+/// Clear the cache: everything, one `table`, or only the JSON responses or
+/// Parquet tables.
+pub fn clear(table: Option<&str>, json_only: bool, parquet_only: bool) -> Result<()> {
     let cache = Cache::new()?;
-    cache.clear()?;
-    println!("Cache cleared.");
+
+    if let Some(name) = table {
+        let def = get_table_def(&name.to_lowercase()).ok_or_else(|| {
+            let hint = suggest_table(name)
+                .map(|t| format!(" Did you mean '{}'?", t.name))
+                .unwrap_or_default();
+            AppError::Config(format!(
+                "Unknown table '{}'.{} Run '{}' to list tables.",
+                name,
+                hint,
+                invocation("tables")
+            ))
+        })?;
+        if cache.clear_table(def)? == 0 {
+            println!("Table '{}' is not cached.", def.name);
+        } else {
+            println!(
+                "Cleared table '{}'. Run '{}' to fetch it again.",
+                def.name,
+                def.command_line()
+            );
+        }
+        return Ok(());
+    }
+
+    let (scope, what) = match (json_only, parquet_only) {
+        (true, _) => (ClearScope::Json, "Cached API responses"),
+        (_, true) => (ClearScope::Parquet, "Cached tables"),
+        _ => (ClearScope::All, "Cache"),
+    };
+    cache.clear_scope(scope)?;
+    println!("{} cleared.", what);
     Ok(())
 }
 
@@ -21,6 +54,9 @@ pub fn status() -> Result<()> {
     println!("Location: {}", stats.location.display());
     println!("Entries:  {}", stats.entry_count);
     println!("Size:     {}", stats.size_human());
+    if crate::cache::is_read_only() {
+        println!("Mode:     read-only (WHICH_LLM_READONLY)");
+    }
 
     Ok(())
 }
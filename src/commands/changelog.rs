@@ -0,0 +1,427 @@
+//! Changelog command - what changed in the benchmarks since a snapshot.
+//!
+//! Compares the current benchmarks with the newest `llms_history` snapshot
+//! taken on or before `--since` and renders the differences as Markdown,
+//! ready to paste into a team update: new and retired models, input and
+//! output price changes, and intelligence ranking movements. Rankings are
+//! computed among the models present in both, so a new model entering the
+//! top ten does not show up as every model below it sliding down.
+
+use crate::cache::Cache;
+use crate::client::HostedDataClient;
+use crate::error::Result;
+use crate::models::LlmModel;
+use crate::output::json::format_json;
+use crate::output::{dates, OutputFormat, ATTRIBUTION};
+use crate::snapshot::{self, Snapshot};
+use crate::sources::artificial_analysis::index_version;
+use chrono::{NaiveDate, Utc};
+use serde::Serialize;
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::fmt::Write;
+
+/// Prices closer than this are treated as unchanged.
+const PRICE_EPSILON: f64 = 1e-9;
+
+/// A model that appeared or disappeared.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ModelEntry {
+    pub slug: String,
+    pub name: String,
+    pub creator: String,
+    pub intelligence: Option<f64>,
+    /// Blended price per million tokens.
+    pub price: Option<f64>,
+}
+
+impl ModelEntry {
+    fn new(model: &LlmModel) -> Self {
+        Self {
+            slug: model.slug.clone(),
+            name: model.name.clone(),
+            creator: model.creator.clone(),
+            intelligence: model.intelligence,
+            price: model.price,
+        }
+    }
+}
+
+/// A change in one of a model's per-token prices.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PriceChange {
+    pub slug: String,
+    pub name: String,
+    pub creator: String,
+    /// `input_price` or `output_price`.
+    pub field: &'static str,
+    pub from: f64,
+    pub to: f64,
+    /// Relative change in percent; `None` when the old price was zero.
+    pub change_pct: Option<f64>,
+}
+
+/// A model that moved in the intelligence ranking.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RankMove {
+    pub slug: String,
+    pub name: String,
+    pub creator: String,
+    pub from_rank: usize,
+    pub to_rank: usize,
+}
+
+/// Differences between a snapshot and the current benchmarks.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Changelog {
+    /// Date of the snapshot compared against.
+    pub from: NaiveDate,
+    /// Date of the current data.
+    pub to: NaiveDate,
+    pub previous_models: usize,
+    pub current_models: usize,
+    pub new_models: Vec<ModelEntry>,
+    pub retired_models: Vec<ModelEntry>,
+    pub price_changes: Vec<PriceChange>,
+    pub rank_moves: Vec<RankMove>,
+}
+
+/// Models keyed by lowercased slug.
+fn by_slug(models: &[LlmModel]) -> HashMap<String, &LlmModel> {
+    models.iter().map(|m| (m.slug.to_lowercase(), m)).collect()
+}
+
+/// Intelligence rank (1 = best) of each slug in `keys` among `models`.
+fn ranks(models: &HashMap<String, &LlmModel>, keys: &[&String]) -> HashMap<String, usize> {
+    let mut scored: Vec<(&String, f64)> = keys
+        .iter()
+        .filter_map(|key| Some((*key, models[*key].intelligence?)))
+        .collect();
+    scored.sort_by(|a, b| b.1.total_cmp(&a.1).then_with(|| a.0.cmp(b.0)));
+    scored
+        .into_iter()
+        .enumerate()
+        .map(|(i, (key, _))| (key.clone(), i + 1))
+        .collect()
+}
+
+/// Sort entries best first: by intelligence (missing last), then name.
+fn sort_entries(entries: &mut [ModelEntry]) {
+    entries.sort_by(|a, b| {
+        b.intelligence
+            .unwrap_or(f64::NEG_INFINITY)
+            .total_cmp(&a.intelligence.unwrap_or(f64::NEG_INFINITY))
+            .then_with(|| a.name.cmp(&b.name))
+    });
+}
+
+impl Changelog {
+    /// Compare `previous` (from the `from` snapshot) with `current`, keeping
+    /// the `limit` largest ranking movements.
+    pub fn build(
+        previous: &[LlmModel],
+        current: &[LlmModel],
+        from: NaiveDate,
+        to: NaiveDate,
+        limit: usize,
+    ) -> Self {
+        let old = by_slug(previous);
+        let new = by_slug(current);
+
+        let mut new_models: Vec<ModelEntry> = new
+            .iter()
+            .filter(|(key, _)| !old.contains_key(*key))
+            .map(|(_, m)| ModelEntry::new(m))
+            .collect();
+        sort_entries(&mut new_models);
+        let mut retired_models: Vec<ModelEntry> = old
+            .iter()
+            .filter(|(key, _)| !new.contains_key(*key))
+            .map(|(_, m)| ModelEntry::new(m))
+            .collect();
+        sort_entries(&mut retired_models);
+
+        let mut common: Vec<&String> = new.keys().filter(|key| old.contains_key(*key)).collect();
+        common.sort();
+
+        let mut price_changes = Vec::new();
+        for key in &common {
+            let (before, after) = (old[*key], new[*key]);
+            for (field, from, to) in [
+                ("input_price", before.input_price, after.input_price),
+                ("output_price", before.output_price, after.output_price),
+            ] {
+                let (Some(from), Some(to)) = (from, to) else {
+                    continue;
+                };
+                if (to - from).abs() < PRICE_EPSILON {
+                    continue;
+                }
+                price_changes.push(PriceChange {
+                    slug: after.slug.clone(),
+                    name: after.name.clone(),
+                    creator: after.creator.clone(),
+                    field,
+                    from,
+                    to,
+                    change_pct: (from > 0.0).then(|| 100.0 * (to - from) / from),
+                });
+            }
+        }
+        // Biggest relative changes first; from-zero changes after those
+        price_changes.sort_by(|a, b| {
+            let size = |c: &PriceChange| c.change_pct.map_or(f64::NEG_INFINITY, f64::abs);
+            size(b)
+                .total_cmp(&size(a))
+                .then_with(|| a.name.cmp(&b.name))
+        });
+
+        let (old_ranks, new_ranks) = (ranks(&old, &common), ranks(&new, &common));
+        let mut rank_moves: Vec<RankMove> = common
+            .iter()
+            .filter_map(|key| {
+                let (from_rank, to_rank) = (*old_ranks.get(*key)?, *new_ranks.get(*key)?);
+                (from_rank != to_rank).then(|| RankMove {
+                    slug: new[*key].slug.clone(),
+                    name: new[*key].name.clone(),
+                    creator: new[*key].creator.clone(),
+                    from_rank,
+                    to_rank,
+                })
+            })
+            .collect();
+        rank_moves.sort_by(|a, b| {
+            let size = |m: &RankMove| m.from_rank.abs_diff(m.to_rank);
+            size(b)
+                .cmp(&size(a))
+                .then_with(|| a.to_rank.cmp(&b.to_rank))
+        });
+        rank_moves.truncate(limit);
+
+        Self {
+            from,
+            to,
+            previous_models: old.len(),
+            current_models: new.len(),
+            new_models,
+            retired_models,
+            price_changes,
+            rank_moves,
+        }
+    }
+
+    /// Render the changelog as Markdown.
+    pub fn to_markdown(&self) -> String {
+        let mut out = String::new();
+        let (from, to) = (dates::display(self.from), dates::display(self.to));
+        let _ = writeln!(out, "# Model changes from {} to {}", from, to);
+        let _ = writeln!(out);
+        let count = match self.current_models.cmp(&self.previous_models) {
+            Ordering::Greater => format!("up from {} on {}", self.previous_models, from),
+            Ordering::Less => format!("down from {} on {}", self.previous_models, from),
+            Ordering::Equal => format!("as on {}", from),
+        };
+        let _ = writeln!(
+            out,
+            "The benchmarks list {} models, {} ({} new, {} retired).",
+            self.current_models,
+            count,
+            self.new_models.len(),
+            self.retired_models.len()
+        );
+
+        section(&mut out, "New models", &self.new_models, |e| {
+            format!("**{}** ({}){}", e.name, e.creator, entry_details(e))
+        });
+        section(&mut out, "Retired models", &self.retired_models, |e| {
+            format!("**{}** ({})", e.name, e.creator)
+        });
+        section(&mut out, "Price changes", &self.price_changes, |c| {
+            let field = if c.field == "input_price" {
+                "input"
+            } else {
+                "output"
+            };
+            let change = c
+                .change_pct
+                .map(|pct| format!(" ({:+.0}%)", pct))
+                .unwrap_or_default();
+            format!(
+                "**{}** ({}): {} ${:.2} → ${:.2} per 1M tokens{}",
+                c.name, c.creator, field, c.from, c.to, change
+            )
+        });
+        section(&mut out, "Ranking movements", &self.rank_moves, |m| {
+            let direction = if m.to_rank < m.from_rank {
+                "up"
+            } else {
+                "down"
+            };
+            format!(
+                "**{}** ({}): #{} → #{} by intelligence ({} {})",
+                m.name,
+                m.creator,
+                m.from_rank,
+                m.to_rank,
+                direction,
+                m.from_rank.abs_diff(m.to_rank)
+            )
+        });
+
+        let _ = writeln!(out);
+        let _ = write!(out, "_{}._", ATTRIBUTION[0]);
+        out
+    }
+}
+
+/// Intelligence and price of a new model, when known.
+fn entry_details(entry: &ModelEntry) -> String {
+    let mut details = Vec::new();
+    if let Some(intelligence) = entry.intelligence {
+        details.push(format!("intelligence {:.1}", intelligence));
+    }
+    if let Some(price) = entry.price {
+        details.push(format!("${:.2}/M blended", price));
+    }
+    if details.is_empty() {
+        String::new()
+    } else {
+        format!(": {}", details.join(", "))
+    }
+}
+
+/// Append a `## title` section listing `items`, or saying there were none.
+fn section<T>(out: &mut String, title: &str, items: &[T], line: impl Fn(&T) -> String) {
+    let _ = writeln!(out);
+    let _ = writeln!(out, "## {}", title);
+    let _ = writeln!(out);
+    if items.is_empty() {
+        let _ = writeln!(out, "None.");
+    }
+    for item in items {
+        let _ = writeln!(out, "- {}", line(item));
+    }
+}
+
+/// Run the changelog command, comparing `current` with `baseline`.
+///
+/// Unless `quiet`, warns when the snapshot uses a different intelligence
+/// index version than the current data, since rankings then compare scores
+/// on different scales.
+pub fn run(
+    baseline: &Snapshot,
+    current: &[LlmModel],
+    limit: usize,
+    format: OutputFormat,
+    quiet: bool,
+) -> Result<()> {
+    if !quiet {
+        let version = index_version::load_index_version(Cache::new()?.base_dir())
+            .ok()
+            .flatten();
+        if let Some(warning) = snapshot::version_mismatch_warning(baseline, version.as_deref()) {
+            eprintln!("{}", warning);
+        }
+    }
+    let previous = HostedDataClient::new()?.get_llm_models_at(baseline, None)?;
+    let changelog = Changelog::build(
+        &previous,
+        current,
+        baseline.date,
+        Utc::now().date_naive(),
+        limit,
+    );
+
+    if format == OutputFormat::Json {
+        println!("{}", format_json(&changelog));
+    } else {
+        println!("{}", changelog.to_markdown());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn model(slug: &str, intelligence: f64, input: f64, output: f64) -> LlmModel {
+        LlmModel {
+            input_price: Some(input),
+            output_price: Some(output),
+            ..LlmModel::fixture(slug, intelligence, (3.0 * input + output) / 4.0)
+        }
+    }
+
+    fn date(s: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap()
+    }
+
+    fn changelog(limit: usize) -> Changelog {
+        let previous = vec![
+            model("alpha", 60.0, 2.0, 8.0),
+            model("beta", 50.0, 1.0, 4.0),
+            model("gamma", 40.0, 0.5, 1.0),
+            model("old", 30.0, 0.1, 0.2),
+        ];
+        let current = vec![
+            model("alpha", 60.0, 1.5, 8.0),
+            model("beta", 50.0, 1.0, 4.0),
+            model("gamma", 55.0, 0.5, 1.2),
+            model("fresh", 70.0, 3.0, 12.0),
+        ];
+        Changelog::build(
+            &previous,
+            &current,
+            date("2025-01-01"),
+            date("2025-01-15"),
+            limit,
+        )
+    }
+
+    #[test]
+    fn test_changelog_build() {
+        let log = changelog(10);
+        assert_eq!(log.new_models.len(), 1);
+        assert_eq!(log.new_models[0].slug, "fresh");
+        assert_eq!(log.retired_models[0].slug, "old");
+
+        // Biggest relative change first
+        let changes: Vec<(&str, &str)> = log
+            .price_changes
+            .iter()
+            .map(|c| (c.slug.as_str(), c.field))
+            .collect();
+        assert_eq!(
+            changes,
+            vec![("alpha", "input_price"), ("gamma", "output_price")]
+        );
+        assert_eq!(log.price_changes[0].change_pct, Some(-25.0));
+
+        // Ranked among models in both: the new model does not push others down
+        let moves: Vec<(&str, usize, usize)> = log
+            .rank_moves
+            .iter()
+            .map(|m| (m.slug.as_str(), m.from_rank, m.to_rank))
+            .collect();
+        assert_eq!(moves, vec![("gamma", 3, 2), ("beta", 2, 3)]);
+        assert_eq!(changelog(1).rank_moves.len(), 1);
+    }
+
+    #[test]
+    fn test_changelog_markdown() {
+        let markdown = changelog(10).to_markdown();
+        assert!(markdown.starts_with("# Model changes from 2025-01-01 to 2025-01-15"));
+        assert!(
+            markdown.contains("The benchmarks list 4 models, as on 2025-01-01 (1 new, 1 retired).")
+        );
+        assert!(markdown.contains("- **FRESH** (Acme): intelligence 70.0, $5.25/M blended"));
+        assert!(markdown.contains("## Retired models\n\n- **OLD** (Acme)"));
+        assert!(markdown.contains("- **ALPHA** (Acme): input $2.00 → $1.50 per 1M tokens (-25%)"));
+        assert!(markdown.contains("- **GAMMA** (Acme): #3 → #2 by intelligence (up 1)"));
+        assert!(markdown
+            .ends_with("_Data provided by Artificial Analysis (https://artificialanalysis.ai)._"));
+
+        let empty = Changelog::build(&[], &[], date("2025-01-01"), date("2025-01-15"), 10);
+        assert!(empty.to_markdown().contains("## New models\n\nNone."));
+    }
+}
@@ -3,10 +3,13 @@
 //! Side-by-side comparison of multiple LLM models.
 
 use crate::error::{AppError, Result};
-use crate::models::LlmModel;
+use crate::models::{LlmModel, Percentile, ReasoningMode};
+use crate::output::json::format_json;
+use crate::output::porcelain::format_porcelain;
 use crate::output::OutputFormat;
-use crate::utils::find_models_by_names;
+use crate::utils::{ModelSearch, ScoreWeights};
 use serde::Serialize;
+use serde_json::{Map, Value};
 
 /// Comparison field with value and formatting info.
 #[derive(Debug, Clone, Serialize)]
@@ -40,14 +43,78 @@ type FieldExtractor = Box<dyn Fn(&LlmModel) -> Option<FieldValue>>;
 /// Field definition for comparison.
 struct FieldDef {
     name: &'static str,
+    /// Column name accepted by `--fields` (matches the `benchmarks` table).
+    key: &'static str,
     field_type: FieldType,
     extractor: FieldExtractor,
 }
 
+impl FieldDef {
+    /// Format one of this field's values for display.
+    fn display(&self, value: &FieldValue) -> String {
+        match value {
+            // Special formatting for price fields
+            FieldValue::Float(f) if self.name.contains("$/M") => format_price(*f),
+            _ => value.to_display(),
+        }
+    }
+}
+
+/// Columns picked by key with `--fields`, for commands listing one model
+/// per row (`llms`).
+pub(crate) struct FieldColumns(Vec<FieldDef>);
+
+impl FieldColumns {
+    /// Resolve `keys` as `compare --fields` does.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AppError::Config` for an unknown key, listing the valid ones.
+    pub(crate) fn select(keys: &[String]) -> Result<Self> {
+        select_field_defs(keys).map(Self)
+    }
+
+    /// Show speed and latency at `percentile` instead of the median.
+    pub(crate) fn at_percentile(self, percentile: Percentile) -> Self {
+        Self(at_percentile(self.0, percentile))
+    }
+
+    /// Column headers, in the order requested.
+    pub(crate) fn headers(&self) -> impl Iterator<Item = &'static str> + '_ {
+        self.0.iter().map(|def| def.name)
+    }
+
+    /// `model`'s values for each column, `-` when missing.
+    pub(crate) fn values(&self, model: &LlmModel) -> Vec<String> {
+        self.0
+            .iter()
+            .map(|def| (def.extractor)(model).map_or("-".to_string(), |v| def.display(&v)))
+            .collect()
+    }
+
+    /// `model`'s values keyed by column key, as JSON numbers and strings
+    /// (`null` when missing) rather than display text.
+    pub(crate) fn json_values(&self, model: &LlmModel) -> Map<String, Value> {
+        self.0
+            .iter()
+            .map(|def| {
+                let value = match (def.extractor)(model) {
+                    Some(FieldValue::Float(v) | FieldValue::Estimate(v, _)) => Value::from(v),
+                    Some(FieldValue::String(s)) => Value::from(s),
+                    None => Value::Null,
+                };
+                (def.key.to_string(), value)
+            })
+            .collect()
+    }
+}
+
 /// Field value union type.
 #[derive(Debug, Clone)]
 enum FieldValue {
     Float(f64),
+    /// A score with its 95% confidence half-width.
+    Estimate(f64, f64),
     String(String),
 }
 
@@ -55,11 +122,23 @@ impl FieldValue {
     fn to_display(&self) -> String {
         match self {
             FieldValue::Float(v) => format!("{:.1}", v),
+            FieldValue::Estimate(v, err) => format!("{:.1} ± {:.1}", v, err),
             FieldValue::String(s) => s.clone(),
         }
     }
 }
 
+/// Show a 0–1 accuracy on the 0–100 scale used by the AA indices.
+///
+/// Values already above 1 are assumed to be percentages.
+fn percent_scale(value: f64) -> FieldValue {
+    if value <= 1.0 {
+        FieldValue::Float(value * 100.0)
+    } else {
+        FieldValue::Float(value)
+    }
+}
+
 /// Format price for display.
 fn format_price(value: f64) -> String {
     format!("${:.2}", value)
@@ -71,47 +150,73 @@ fn get_field_defs(verbose: bool) -> Vec<FieldDef> {
         // Core identity
         FieldDef {
             name: "Creator",
+            key: "creator",
             field_type: FieldType::NoWinner,
             extractor: Box::new(|m| Some(FieldValue::String(m.creator.clone()))),
         },
         // Benchmarks
         FieldDef {
             name: "Intelligence",
+            key: "intelligence",
             field_type: FieldType::HigherBetter,
-            extractor: Box::new(|m| m.intelligence.map(FieldValue::Float)),
+            extractor: Box::new(|m| {
+                m.intelligence.map(|v| match m.intelligence_error {
+                    Some(err) => FieldValue::Estimate(v, err),
+                    None => FieldValue::Float(v),
+                })
+            }),
         },
         FieldDef {
             name: "Coding",
+            key: "coding",
             field_type: FieldType::HigherBetter,
             extractor: Box::new(|m| m.coding.map(FieldValue::Float)),
         },
+        FieldDef {
+            name: "Coverage",
+            key: "coverage",
+            field_type: FieldType::NoWinner,
+            extractor: Box::new(|m| Some(FieldValue::String(m.coverage_label()))),
+        },
         // Pricing
         FieldDef {
             name: "Input $/M",
+            key: "input_price",
             field_type: FieldType::LowerBetter,
             extractor: Box::new(|m| m.input_price.map(FieldValue::Float)),
         },
         FieldDef {
             name: "Output $/M",
+            key: "output_price",
             field_type: FieldType::LowerBetter,
             extractor: Box::new(|m| m.output_price.map(FieldValue::Float)),
         },
         FieldDef {
             name: "Blended $/M",
+            key: "price",
             field_type: FieldType::LowerBetter,
             extractor: Box::new(|m| m.price.map(FieldValue::Float)),
         },
         // Performance
         FieldDef {
             name: "TPS",
+            key: "tps",
             field_type: FieldType::HigherBetter,
             extractor: Box::new(|m| m.tps.map(FieldValue::Float)),
         },
         FieldDef {
             name: "Latency (s)",
+            key: "latency",
             field_type: FieldType::LowerBetter,
             extractor: Box::new(|m| m.latency.map(FieldValue::Float)),
         },
+        // Value for money
+        FieldDef {
+            name: "Intelligence/$",
+            key: "intelligence_per_dollar",
+            field_type: FieldType::HigherBetter,
+            extractor: Box::new(|m| m.intelligence_per_dollar().map(FieldValue::Float)),
+        },
     ];
 
     // Verbose-only fields (additional AA benchmarks)
@@ -119,43 +224,57 @@ fn get_field_defs(verbose: bool) -> Vec<FieldDef> {
         fields.extend(vec![
             FieldDef {
                 name: "Math",
+                key: "math",
                 field_type: FieldType::HigherBetter,
                 extractor: Box::new(|m| m.math.map(FieldValue::Float)),
             },
             FieldDef {
                 name: "MMLU-Pro",
+                key: "mmlu_pro",
                 field_type: FieldType::HigherBetter,
-                extractor: Box::new(|m| m.mmlu_pro.map(FieldValue::Float)),
+                extractor: Box::new(|m| m.mmlu_pro.map(percent_scale)),
             },
             FieldDef {
                 name: "GPQA",
+                key: "gpqa",
                 field_type: FieldType::HigherBetter,
-                extractor: Box::new(|m| m.gpqa.map(FieldValue::Float)),
+                extractor: Box::new(|m| m.gpqa.map(percent_scale)),
             },
             FieldDef {
                 name: "HLE",
+                key: "hle",
                 field_type: FieldType::HigherBetter,
-                extractor: Box::new(|m| m.hle.map(FieldValue::Float)),
+                extractor: Box::new(|m| m.hle.map(percent_scale)),
             },
             FieldDef {
                 name: "LiveCodeBench",
+                key: "livecodebench",
                 field_type: FieldType::HigherBetter,
-                extractor: Box::new(|m| m.livecodebench.map(FieldValue::Float)),
+                extractor: Box::new(|m| m.livecodebench.map(percent_scale)),
             },
             FieldDef {
                 name: "SciCode",
+                key: "scicode",
                 field_type: FieldType::HigherBetter,
-                extractor: Box::new(|m| m.scicode.map(FieldValue::Float)),
+                extractor: Box::new(|m| m.scicode.map(percent_scale)),
             },
             FieldDef {
                 name: "Math 500",
+                key: "math_500",
                 field_type: FieldType::HigherBetter,
-                extractor: Box::new(|m| m.math_500.map(FieldValue::Float)),
+                extractor: Box::new(|m| m.math_500.map(percent_scale)),
             },
             FieldDef {
                 name: "AIME",
+                key: "aime",
                 field_type: FieldType::HigherBetter,
-                extractor: Box::new(|m| m.aime.map(FieldValue::Float)),
+                extractor: Box::new(|m| m.aime.map(percent_scale)),
+            },
+            FieldDef {
+                name: "Coding/$",
+                key: "coding_per_dollar",
+                field_type: FieldType::HigherBetter,
+                extractor: Box::new(|m| m.coding_per_dollar().map(FieldValue::Float)),
             },
         ]);
     }
@@ -163,92 +282,414 @@ fn get_field_defs(verbose: bool) -> Vec<FieldDef> {
     fields
 }
 
+/// Replace the median speed and latency fields with their `percentile`.
+///
+/// Models without a published percentile show `-` rather than the median.
+fn at_percentile(defs: Vec<FieldDef>, percentile: Percentile) -> Vec<FieldDef> {
+    defs.into_iter()
+        .map(|def| match def.key {
+            "tps" => FieldDef {
+                name: match percentile {
+                    Percentile::P5 => "TPS (p5)",
+                    Percentile::P25 => "TPS (p25)",
+                    Percentile::P75 => "TPS (p75)",
+                    Percentile::P95 => "TPS (p95)",
+                },
+                extractor: Box::new(move |m| {
+                    m.tps_percentiles?.get(percentile).map(FieldValue::Float)
+                }),
+                ..def
+            },
+            "latency" => FieldDef {
+                name: match percentile {
+                    Percentile::P5 => "Latency (s, p5)",
+                    Percentile::P25 => "Latency (s, p25)",
+                    Percentile::P75 => "Latency (s, p75)",
+                    Percentile::P95 => "Latency (s, p95)",
+                },
+                extractor: Box::new(move |m| {
+                    m.latency_percentiles?
+                        .get(percentile)
+                        .map(FieldValue::Float)
+                }),
+                ..def
+            },
+            _ => def,
+        })
+        .collect()
+}
+
+/// Select field definitions by key, in the order requested.
+///
+/// Keys are matched case-insensitively against every field (including the
+/// verbose ones); an unknown key fails with the list of valid keys.
+fn select_field_defs(keys: &[String]) -> Result<Vec<FieldDef>> {
+    let mut available = get_field_defs(true);
+    let mut selected = Vec::with_capacity(keys.len());
+
+    for key in keys {
+        let key = key.trim().to_lowercase();
+        if key.is_empty() || selected.iter().any(|d: &FieldDef| d.key == key) {
+            continue;
+        }
+        match available.iter().position(|d| d.key == key) {
+            Some(idx) => selected.push(available.remove(idx)),
+            None => {
+                let valid: Vec<&str> = get_field_defs(true).iter().map(|d| d.key).collect();
+                return Err(AppError::Config(format!(
+                    "Unknown field '{}'. Available fields: {}",
+                    key,
+                    valid.join(", ")
+                )));
+            }
+        }
+    }
+
+    if selected.is_empty() {
+        return Err(AppError::Config(
+            "--fields requires at least one field".into(),
+        ));
+    }
+
+    Ok(selected)
+}
+
 /// Find winners for a set of values.
+///
+/// Estimates whose confidence interval overlaps the best one's are marked as
+/// winners too, so a difference within the error bars is not called a win.
 fn find_winners(values: &[Option<FieldValue>], field_type: FieldType) -> Vec<bool> {
-    match field_type {
-        FieldType::NoWinner => vec![false; values.len()],
-        FieldType::HigherBetter => {
-            let floats: Vec<Option<f64>> = values
-                .iter()
-                .map(|v| match v {
-                    Some(FieldValue::Float(f)) => Some(*f),
-                    _ => None,
-                })
-                .collect();
+    let sign = match field_type {
+        FieldType::NoWinner => return vec![false; values.len()],
+        FieldType::HigherBetter => 1.0,
+        FieldType::LowerBetter => -1.0,
+    };
 
-            let max = floats
-                .iter()
-                .filter_map(|f| *f)
-                .fold(f64::NEG_INFINITY, f64::max);
+    // Flip lower-is-better values so the best is always the largest
+    let estimates: Vec<Option<(f64, f64)>> = values
+        .iter()
+        .map(|v| match v {
+            Some(FieldValue::Float(f)) => Some((sign * f, 0.0)),
+            Some(FieldValue::Estimate(f, err)) => Some((sign * f, *err)),
+            _ => None,
+        })
+        .collect();
 
-            if max == f64::NEG_INFINITY {
-                vec![false; values.len()]
-            } else {
-                floats
-                    .iter()
-                    .map(|f| f.map(|v| (v - max).abs() < 0.001).unwrap_or(false))
-                    .collect()
-            }
+    let Some((best, best_err)) = estimates
+        .iter()
+        .flatten()
+        .copied()
+        .max_by(|a, b| a.0.total_cmp(&b.0))
+    else {
+        return vec![false; values.len()];
+    };
+
+    estimates
+        .iter()
+        .map(|e| e.is_some_and(|(v, err)| (v - best).abs() < 0.001 || v + err >= best - best_err))
+        .collect()
+}
+
+/// Options for the compare command.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CompareOptions<'a> {
+    /// Show every field in table-style output.
+    pub verbose: bool,
+    /// Fields to show, in order, replacing the defaults.
+    pub fields: Option<&'a [String]>,
+    /// Composite score shown as the leading row.
+    pub score: Option<&'a ScoreWeights>,
+    /// Add one row per weighted metric of the score.
+    pub explain: bool,
+    /// Compare one variant per family instead of every matched variant.
+    pub collapse_variants: bool,
+    /// Add each model's reasoning/non-reasoning counterpart, with deltas.
+    pub reasoning_pair: bool,
+    /// Show speed and latency at this percentile instead of the median.
+    pub percentile: Option<Percentile>,
+}
+
+/// The variant of `model`'s family in the other reasoning mode.
+///
+/// The family's plain slug counts as reasoning when the family also has an
+/// explicit non-reasoning variant (e.g. `gpt-5` vs `gpt-5-minimal`), and as
+/// non-reasoning otherwise (e.g. `claude-4-5-sonnet` vs `-thinking`). Among
+/// several candidates the highest intelligence wins.
+fn reasoning_counterpart<'a>(model: &LlmModel, models: &'a [LlmModel]) -> Option<&'a LlmModel> {
+    let wanted = match model.reasoning_mode() {
+        ReasoningMode::Reasoning => [ReasoningMode::NonReasoning, ReasoningMode::Default],
+        ReasoningMode::NonReasoning => [ReasoningMode::Default, ReasoningMode::Reasoning],
+        ReasoningMode::Default => [ReasoningMode::NonReasoning, ReasoningMode::Reasoning],
+    };
+    let rank = |m: &&LlmModel| m.intelligence.unwrap_or(f64::NEG_INFINITY);
+    wanted.iter().find_map(|mode| {
+        models
+            .iter()
+            .filter(|m| m.id != model.id && m.family() == model.family())
+            .filter(|m| m.reasoning_mode() == *mode)
+            .max_by(|a, b| rank(a).total_cmp(&rank(b)))
+    })
+}
+
+/// Insert each model's reasoning counterpart right after it.
+///
+/// Returns the models and, for each, the index of its pair. Counterparts
+/// that were already requested are paired in place rather than repeated.
+fn pair_reasoning<'a>(
+    matched: &[&'a LlmModel],
+    models: &'a [LlmModel],
+) -> (Vec<&'a LlmModel>, Vec<Option<usize>>) {
+    let mut paired: Vec<&'a LlmModel> = Vec::new();
+    let mut pairs: Vec<Option<usize>> = Vec::new();
+    for &model in matched {
+        if paired.iter().any(|m| m.id == model.id) {
+            continue;
         }
-        FieldType::LowerBetter => {
-            let floats: Vec<Option<f64>> = values
-                .iter()
-                .map(|v| match v {
-                    Some(FieldValue::Float(f)) => Some(*f),
-                    _ => None,
-                })
-                .collect();
+        paired.push(model);
+        pairs.push(None);
+        let Some(counterpart) = reasoning_counterpart(model, models) else {
+            continue;
+        };
+        let i = paired.len() - 1;
+        let j = match paired.iter().position(|m| m.id == counterpart.id) {
+            Some(j) if pairs[j].is_none() => j,
+            Some(_) => continue,
+            None => match matched.iter().position(|m| m.id == counterpart.id) {
+                // Requested later; it is paired when its turn comes
+                Some(_) => continue,
+                None => {
+                    paired.push(counterpart);
+                    pairs.push(None);
+                    paired.len() - 1
+                }
+            },
+        };
+        pairs[i] = Some(j);
+        pairs[j] = Some(i);
+    }
+    (paired, pairs)
+}
 
-            let min = floats
-                .iter()
-                .filter_map(|f| *f)
-                .fold(f64::INFINITY, f64::min);
+/// Format a signed price difference, e.g. `+$1.20` or `-$0.50`.
+fn format_price_delta(value: f64) -> String {
+    let sign = if value < 0.0 { '-' } else { '+' };
+    format!("{}{}", sign, format_price(value.abs()))
+}
 
-            if min == f64::INFINITY {
-                vec![false; values.len()]
-            } else {
-                floats
-                    .iter()
-                    .map(|f| f.map(|v| (v - min).abs() < 0.001).unwrap_or(false))
-                    .collect()
+/// Rows for `--with-reasoning-pair`: blended price per intelligence point,
+/// and the intelligence and price difference to each model's pair with the
+/// marginal cost of each extra point.
+fn reasoning_pair_fields(models: &[&LlmModel], pairs: &[Option<usize>]) -> Vec<CompareField> {
+    let per_point: Vec<Option<FieldValue>> = models
+        .iter()
+        .map(|m| match (m.price, m.intelligence) {
+            (Some(price), Some(intelligence)) if intelligence > 0.0 => {
+                Some(FieldValue::Float(price / intelligence))
             }
+            _ => None,
+        })
+        .collect();
+    let winners = find_winners(&per_point, FieldType::LowerBetter);
+    let values = per_point
+        .iter()
+        .zip(&winners)
+        .map(|(v, &is_winner)| match v {
+            Some(FieldValue::Float(f)) if is_winner => format!("{} *", format_price(*f)),
+            Some(FieldValue::Float(f)) => format_price(*f),
+            _ => "-".to_string(),
+        })
+        .collect();
+
+    let delta = |i: usize| -> Option<(f64, f64)> {
+        let (a, b) = (models[i], models[pairs[i]?]);
+        Some((a.intelligence? - b.intelligence?, a.price? - b.price?))
+    };
+    let deltas: Vec<String> = (0..models.len())
+        .map(|i| match delta(i) {
+            Some((points, price)) => format!(
+                "{:+.1} pts, {} vs {}",
+                points,
+                format_price_delta(price),
+                models[pairs[i].unwrap_or(i)].display_name()
+            ),
+            None => "-".to_string(),
+        })
+        .collect();
+    let marginal: Vec<String> = (0..models.len())
+        .map(|i| match delta(i) {
+            Some((points, price)) if points > 0.0 => format_price(price / points),
+            _ => "-".to_string(),
+        })
+        .collect();
+
+    vec![
+        CompareField {
+            name: "$/M per Point".to_string(),
+            values,
+            winners,
+        },
+        CompareField {
+            name: "Pair Δ".to_string(),
+            values: deltas,
+            winners: vec![false; models.len()],
+        },
+        CompareField {
+            name: "$/M per Extra Point".to_string(),
+            values: marginal,
+            winners: vec![false; models.len()],
+        },
+    ]
+}
+
+/// A family collapsed to its best variant.
+pub(crate) struct CollapsedFamily<'a> {
+    pub best: &'a LlmModel,
+    /// Number of the family's variants that matched.
+    pub variants: usize,
+    /// The cheapest variant by blended price, with its price.
+    pub cheapest: Option<(&'a LlmModel, f64)>,
+}
+
+impl CollapsedFamily<'_> {
+    /// The variant count, naming the cheapest variant when there are several,
+    /// e.g. `3 (cheapest: GPT-5 mini $0.69)`.
+    pub(crate) fn summary(&self) -> String {
+        match self.cheapest {
+            Some((m, price)) if self.variants > 1 => format!(
+                "{} (cheapest: {} {})",
+                self.variants,
+                m.display_name(),
+                format_price(price)
+            ),
+            _ => self.variants.to_string(),
+        }
+    }
+}
+
+/// Keep the best variant (highest intelligence, first on ties) of each
+/// family, in order of first appearance.
+pub(crate) fn collapse_variants<'a>(models: &[&'a LlmModel]) -> Vec<CollapsedFamily<'a>> {
+    let mut families: Vec<Vec<&'a LlmModel>> = Vec::new();
+    for &model in models {
+        match families
+            .iter_mut()
+            .find(|f| f[0].family() == model.family())
+        {
+            Some(family) => family.push(model),
+            None => families.push(vec![model]),
         }
     }
+
+    families
+        .into_iter()
+        .map(|variants| {
+            let rank = |m: &&LlmModel| m.intelligence.unwrap_or(f64::NEG_INFINITY);
+            let best = variants
+                .iter()
+                .copied()
+                .reduce(|best, m| if rank(&m) > rank(&best) { m } else { best })
+                .expect("families are never empty");
+            let cheapest = variants
+                .iter()
+                .filter_map(|m| Some((*m, m.price?)))
+                .min_by(|a, b| a.1.total_cmp(&b.1));
+            CollapsedFamily {
+                best,
+                variants: variants.len(),
+                cheapest,
+            }
+        })
+        .collect()
 }
 
 /// Run the compare command.
+///
+/// `fields` selects exactly which rows are shown. Without it, table-style
+/// output shows the core fields (all of them with `verbose`), while JSON, CSV
+/// and porcelain always include every field since they are meant for further
+/// processing.
+/// A `score` row leads the table; `explain` adds one row per weighted metric.
+/// With `collapse_variants`, each family's best variant is compared and a
+/// `Variants` row summarizes the rest. With a `percentile`, speed and latency
+/// rows show that percentile instead of the median.
 pub fn run(
     models: &[LlmModel],
-    model_searches: &[String],
-    verbose: bool,
+    search: &ModelSearch,
+    options: &CompareOptions,
     format: OutputFormat,
 ) -> Result<()> {
+    // Resolve fields first so a typo fails before any matching work
+    let field_defs = match options.fields {
+        Some(keys) => select_field_defs(keys)?,
+        None => get_field_defs(
+            options.verbose
+                || matches!(
+                    format,
+                    OutputFormat::Json | OutputFormat::Csv | OutputFormat::Porcelain
+                ),
+        ),
+    };
+    let field_defs = match options.percentile {
+        Some(percentile) => at_percentile(field_defs, percentile),
+        None => field_defs,
+    };
+
     // Find matching models
-    let matched_models = find_models_by_names(models, model_searches);
+    let matched_models = search.find(models);
 
     if matched_models.is_empty() {
-        return Err(AppError::NotFound(format!(
-            "No models found matching: {}",
-            model_searches.join(", ")
-        )));
+        return Err(search.not_found_error(models));
     }
 
+    let mut pair_fields = None;
+    let matched_models = if options.reasoning_pair {
+        let (paired, pairs) = pair_reasoning(&matched_models, models);
+        if pairs.iter().all(Option::is_none) {
+            return Err(AppError::NotFound(
+                "No reasoning or non-reasoning counterpart found for the matched models.".into(),
+            ));
+        }
+        pair_fields = Some(reasoning_pair_fields(&paired, &pairs));
+        paired
+    } else {
+        matched_models
+    };
+
+    let mut variants_field = None;
+    let matched_models = if options.collapse_variants {
+        let collapsed = collapse_variants(&matched_models);
+        variants_field = Some(CompareField {
+            name: "Variants".to_string(),
+            values: collapsed.iter().map(CollapsedFamily::summary).collect(),
+            winners: vec![false; collapsed.len()],
+        });
+        collapsed.into_iter().map(|family| family.best).collect()
+    } else {
+        matched_models
+    };
+
     if matched_models.len() < 2 {
-        return Err(AppError::Config(
-            "Need at least 2 models to compare. Try broader search terms.".into(),
-        ));
+        let hint = if options.collapse_variants {
+            "All matches are variants of one family. Drop --collapse-variants or broaden the search."
+        } else {
+            "Try broader search terms."
+        };
+        return Err(AppError::Config(format!(
+            "Need at least 2 models to compare. {}",
+            hint
+        )));
     }
 
-    // Get field definitions
-    let field_defs = get_field_defs(verbose);
-
-    // Build comparison data
+    // Build comparison data, flagging models on their way out
     let model_names: Vec<String> = matched_models
         .iter()
-        .map(|m| m.display_name().to_string())
+        .map(|m| match &m.status {
+            Some(status) => format!("{} ({})", m.display_name(), status),
+            None => m.display_name().to_string(),
+        })
         .collect();
 
-    let compare_fields: Vec<CompareField> = field_defs
+    let mut compare_fields: Vec<CompareField> = field_defs
         .iter()
         .map(|def| {
             let values: Vec<Option<FieldValue>> =
@@ -260,21 +701,7 @@ pub fn run(
                 .iter()
                 .zip(winners.iter())
                 .map(|(v, &is_winner)| {
-                    let base = match v {
-                        Some(fv) => {
-                            // Special formatting for price fields
-                            if def.name.contains("$/M") {
-                                if let FieldValue::Float(f) = fv {
-                                    format_price(*f)
-                                } else {
-                                    fv.to_display()
-                                }
-                            } else {
-                                fv.to_display()
-                            }
-                        }
-                        None => "-".to_string(),
-                    };
+                    let base = v.as_ref().map_or("-".to_string(), |fv| def.display(fv));
                     if is_winner {
                         format!("{} *", base)
                     } else {
@@ -291,6 +718,61 @@ pub fn run(
         })
         .collect();
 
+    if let Some(field) = variants_field {
+        compare_fields.insert(0, field);
+    }
+    if let Some(fields) = pair_fields {
+        compare_fields.extend(fields);
+    }
+
+    // The composite score leads, normalized over every model, not just the compared ones
+    if let Some(weights) = options.score {
+        let scorer = weights.scorer(models);
+        let values: Vec<Option<FieldValue>> = matched_models
+            .iter()
+            .map(|m| Some(FieldValue::Float(scorer.score(m))))
+            .collect();
+        let winners = find_winners(&values, FieldType::HigherBetter);
+        let display_values = values
+            .iter()
+            .zip(&winners)
+            .map(|(v, &is_winner)| {
+                let base = v.as_ref().map(FieldValue::to_display).unwrap_or_default();
+                if is_winner {
+                    format!("{} *", base)
+                } else {
+                    base
+                }
+            })
+            .collect();
+        let mut score_fields = vec![CompareField {
+            name: format!("Score ({})", weights.label),
+            values: display_values,
+            winners,
+        }];
+
+        // Explain the score: each metric's points, so the winner's edge is visible
+        if options.explain {
+            let parts: Vec<Vec<(&str, f64, f64)>> = matched_models
+                .iter()
+                .map(|m| scorer.contributions(m))
+                .collect();
+            for (i, (metric, weight, _)) in parts[0].iter().enumerate() {
+                let values: Vec<Option<FieldValue>> = parts
+                    .iter()
+                    .map(|p| Some(FieldValue::Float(p[i].2)))
+                    .collect();
+                let winners = find_winners(&values, FieldType::HigherBetter);
+                score_fields.push(CompareField {
+                    name: format!("  {} ({:+})", metric, weight),
+                    values: parts.iter().map(|p| format!("{:+.1}", p[i].2)).collect(),
+                    winners,
+                });
+            }
+        }
+        compare_fields.splice(0..0, score_fields);
+    }
+
     let result = CompareResult {
         models: model_names.clone(),
         fields: compare_fields,
@@ -299,9 +781,7 @@ pub fn run(
     // Output based on format
     match format {
         OutputFormat::Json => {
-            let json =
-                serde_json::to_string_pretty(&result).map_err(crate::error::AppError::from)?;
-            println!("{}", json);
+            println!("{}", format_json(&result));
         }
         OutputFormat::Csv => {
             // CSV: header row is "Field", then model names; each row is a field and its values
@@ -341,6 +821,18 @@ pub fn run(
                 println!("{}", row_cells.join(","));
             }
         }
+        OutputFormat::Porcelain => {
+            // Porcelain: "Field" and model names, then one line per field; no legend
+            let headers: Vec<&str> = std::iter::once("Field")
+                .chain(model_names.iter().map(String::as_str))
+                .collect();
+            let rows = result.fields.iter().map(|field| {
+                std::iter::once(field.name.clone())
+                    .chain(field.values.iter().cloned())
+                    .collect::<Vec<String>>()
+            });
+            println!("{}", format_porcelain(&headers, rows));
+        }
         OutputFormat::Plain => {
             // Plain: tab-separated values
             // Header
@@ -414,3 +906,222 @@ pub fn run(
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::Percentiles;
+
+    fn keys(defs: &[FieldDef]) -> Vec<&'static str> {
+        defs.iter().map(|d| d.key).collect()
+    }
+
+    #[test]
+    fn test_select_field_defs_keeps_requested_order() {
+        let fields = vec!["tps".to_string(), "Intelligence".into(), "aime".into()];
+        let defs = select_field_defs(&fields).unwrap();
+        assert_eq!(keys(&defs), vec!["tps", "intelligence", "aime"]);
+    }
+
+    #[test]
+    fn test_select_field_defs_ignores_duplicates() {
+        let fields = vec!["price".to_string(), "price".into()];
+        let defs = select_field_defs(&fields).unwrap();
+        assert_eq!(keys(&defs), vec!["price"]);
+    }
+
+    #[test]
+    fn test_accuracy_fields_use_percent_scale() {
+        let model = LlmModel {
+            gpqa: Some(0.705),
+            aime: Some(87.0),
+            ..Default::default()
+        };
+        let display = |key: &str| {
+            let defs = select_field_defs(&[key.to_string()]).unwrap();
+            (defs[0].extractor)(&model).unwrap().to_display()
+        };
+        assert_eq!(display("gpqa"), "70.5");
+        assert_eq!(display("aime"), "87.0");
+    }
+
+    #[test]
+    fn test_find_winners() {
+        let floats = [
+            Some(FieldValue::Float(2.0)),
+            Some(FieldValue::Float(1.0)),
+            None,
+        ];
+        assert_eq!(
+            find_winners(&floats, FieldType::HigherBetter),
+            vec![true, false, false]
+        );
+        assert_eq!(
+            find_winners(&floats, FieldType::LowerBetter),
+            vec![false, true, false]
+        );
+        assert_eq!(
+            find_winners(&[None, None], FieldType::HigherBetter),
+            vec![false, false]
+        );
+    }
+
+    #[test]
+    fn test_find_winners_within_error_bars() {
+        let values = [
+            Some(FieldValue::Estimate(68.0, 1.5)),
+            Some(FieldValue::Estimate(66.0, 1.0)),
+            Some(FieldValue::Estimate(60.0, 2.0)),
+            Some(FieldValue::Float(67.0)),
+        ];
+        assert_eq!(
+            find_winners(&values, FieldType::HigherBetter),
+            vec![true, true, false, true]
+        );
+    }
+
+    #[test]
+    fn test_intelligence_shows_error() {
+        let mut model = LlmModel {
+            intelligence: Some(68.04),
+            ..Default::default()
+        };
+        let defs = select_field_defs(&["intelligence".to_string()]).unwrap();
+        assert_eq!((defs[0].extractor)(&model).unwrap().to_display(), "68.0");
+
+        model.intelligence_error = Some(1.26);
+        assert_eq!(
+            (defs[0].extractor)(&model).unwrap().to_display(),
+            "68.0 ± 1.3"
+        );
+    }
+
+    #[test]
+    fn test_at_percentile() {
+        let mut model = LlmModel {
+            tps: Some(120.0),
+            latency: Some(0.8),
+            ..Default::default()
+        };
+        let keys = ["tps".to_string(), "latency".into(), "intelligence".into()];
+        let defs = at_percentile(select_field_defs(&keys).unwrap(), Percentile::P95);
+        let names: Vec<&str> = defs.iter().map(|d| d.name).collect();
+        assert_eq!(names, vec!["TPS (p95)", "Latency (s, p95)", "Intelligence"]);
+        assert_eq!(defs[1].field_type, FieldType::LowerBetter);
+
+        // No fallback to the median when the percentile is not published
+        assert!((defs[0].extractor)(&model).is_none());
+
+        let mut latency = Percentiles::default();
+        latency.set(Percentile::P95, 3.2);
+        model.latency_percentiles = Some(latency);
+        assert_eq!((defs[1].extractor)(&model).unwrap().to_display(), "3.2");
+    }
+
+    #[test]
+    fn test_collapse_variants() {
+        let variant = |slug: &str, intelligence: f64, price: f64| LlmModel {
+            name: slug.to_string(),
+            slug: slug.to_string(),
+            intelligence: Some(intelligence),
+            price: Some(price),
+            ..Default::default()
+        };
+        let models = [
+            variant("gpt-5-low", 60.0, 2.0),
+            variant("claude-4-5-sonnet", 63.0, 6.0),
+            variant("gpt-5", 68.0, 3.4),
+            variant("gpt-5-minimal", 44.0, 1.0),
+        ];
+        let refs: Vec<&LlmModel> = models.iter().collect();
+        let collapsed: Vec<(&str, String)> = collapse_variants(&refs)
+            .into_iter()
+            .map(|family| (family.best.slug.as_str(), family.summary()))
+            .collect();
+        assert_eq!(
+            collapsed,
+            vec![
+                ("gpt-5", "3 (cheapest: gpt-5-minimal $1.00)".to_string()),
+                ("claude-4-5-sonnet", "1".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_reasoning_pairs() {
+        let variant = |slug: &str, intelligence: f64, price: f64| LlmModel {
+            id: slug.to_string(),
+            name: slug.to_string(),
+            slug: slug.to_string(),
+            intelligence: Some(intelligence),
+            price: Some(price),
+            ..Default::default()
+        };
+        let models = vec![
+            variant("gpt-5", 68.0, 3.4),
+            variant("gpt-5-low", 62.0, 2.0),
+            variant("gpt-5-minimal", 44.0, 1.0),
+            variant("claude-4-5-sonnet", 50.0, 6.0),
+            variant("claude-4-5-sonnet-thinking", 63.0, 6.0),
+            variant("grok-4", 65.0, 6.0),
+        ];
+        let counterpart =
+            |i: usize| reasoning_counterpart(&models[i], &models).map(|m| m.slug.as_str());
+        assert_eq!(counterpart(0), Some("gpt-5-minimal"));
+        assert_eq!(counterpart(1), Some("gpt-5-minimal"));
+        assert_eq!(counterpart(2), Some("gpt-5"));
+        assert_eq!(counterpart(3), Some("claude-4-5-sonnet-thinking"));
+        assert_eq!(counterpart(5), None);
+
+        let matched = vec![&models[0], &models[4], &models[5]];
+        let (paired, pairs) = pair_reasoning(&matched, &models);
+        let slugs: Vec<&str> = paired.iter().map(|m| m.slug.as_str()).collect();
+        assert_eq!(
+            slugs,
+            vec![
+                "gpt-5",
+                "gpt-5-minimal",
+                "claude-4-5-sonnet-thinking",
+                "claude-4-5-sonnet",
+                "grok-4"
+            ]
+        );
+        assert_eq!(pairs, vec![Some(1), Some(0), Some(3), Some(2), None]);
+
+        let fields = reasoning_pair_fields(&paired, &pairs);
+        assert_eq!(fields[0].values[0], "$0.05");
+        assert_eq!(fields[1].values[0], "+24.0 pts, +$2.40 vs gpt-5-minimal");
+        assert_eq!(fields[1].values[1], "-24.0 pts, -$2.40 vs gpt-5");
+        assert_eq!(fields[2].values[0], "$0.10");
+        assert_eq!(fields[2].values[1], "-");
+        assert_eq!(fields[2].values[4], "-");
+    }
+
+    #[test]
+    fn test_select_field_defs_rejects_unknown() {
+        let err = select_field_defs(&["context_window".to_string()])
+            .err()
+            .unwrap()
+            .to_string();
+        assert!(err.contains("Unknown field 'context_window'"));
+        assert!(err.contains("input_price"));
+    }
+
+    #[test]
+    fn test_field_keys_are_benchmark_columns() {
+        for def in get_field_defs(true) {
+            // Computed from the benchmark columns
+            if def.key.ends_with("_per_dollar") || def.key == "coverage" {
+                continue;
+            }
+            assert!(
+                crate::schema::BENCHMARKS
+                    .columns
+                    .iter()
+                    .any(|c| c.name == def.key),
+                "{} is not a benchmarks column",
+                def.key
+            );
+        }
+    }
+}
@@ -1,12 +1,19 @@
 //! Cost calculator command.
 //!
-//! Calculate token costs for one or more LLM models.
+//! Calculate token costs for one or more LLM models, character costs for
+//! text-to-speech models with `--characters`, or clip costs for video models
+//! with `--media`.
 
+use crate::cache::Cache;
 use crate::error::{AppError, Result};
 use crate::models::LlmModel;
 use crate::output::{format_output, Formattable, OutputFormat};
-use crate::utils::{find_models_by_names, parse_tokens};
+use crate::query::QueryExecutor;
+use crate::schema::{TableDef, IMAGE_TO_VIDEO, TEXT_TO_VIDEO};
+use crate::sources::models_dev::pricing::{load_price_tiers, PriceTier};
+use crate::utils::{format_tokens, parse_tokens, ModelSearch};
 use serde::Serialize;
+use std::path::Path;
 use tabled::Tabled;
 
 /// Time period for cost projection.
@@ -44,6 +51,9 @@ pub struct CostResult {
     pub period: String,
     pub period_cost: Option<f64>,
     pub monthly_cost: Option<f64>,
+    /// Prompt size above which long-context prices were applied, if they were.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub long_context_threshold: Option<u64>,
 }
 
 /// Cost row for table output.
@@ -75,7 +85,7 @@ impl Formattable for CostRow {
 }
 
 /// Format a cost value for display.
-fn format_cost(cost: Option<f64>) -> String {
+pub(crate) fn format_cost(cost: Option<f64>) -> String {
     match cost {
         Some(c) if c < 0.01 => format!("${:.4}", c),
         Some(c) if c < 1.0 => format!("${:.3}", c),
@@ -94,36 +104,57 @@ fn format_cost_with_winner(cost: Option<f64>, is_winner: bool) -> String {
     }
 }
 
-/// Format token count for display.
-fn format_token_count(tokens: u64) -> String {
-    if tokens >= 1_000_000 {
-        format!("{:.1}M", tokens as f64 / 1_000_000.0)
-    } else if tokens >= 1_000 {
-        if tokens % 1_000 == 0 {
-            format!("{}K", tokens / 1_000)
-        } else {
-            format!("{:.1}K", tokens as f64 / 1_000.0)
+/// Project a per-request cost over `requests` requests per period.
+///
+/// Returns the period name, the cost for the period, and the 30-day cost.
+fn project_cost(
+    cost: Option<f64>,
+    requests: u64,
+    period: Period,
+) -> (String, Option<f64>, Option<f64>) {
+    // Apply requests multiplier
+    let request_cost = cost.map(|c| c * requests as f64);
+
+    match period {
+        Period::Once => ("once".to_string(), request_cost, None),
+        Period::Daily => {
+            let daily = request_cost;
+            let monthly = daily.map(|d| d * 30.0);
+            ("daily".to_string(), daily, monthly)
+        }
+        Period::Monthly => {
+            let monthly = request_cost.map(|c| c * 30.0);
+            ("monthly".to_string(), monthly, monthly)
         }
-    } else {
-        tokens.to_string()
     }
 }
 
+/// Benchmark columns the token cost calculator reads besides model identity.
+pub const COLUMNS: &[&str] = &["input_price", "output_price"];
+
 /// Calculate cost for a model.
-fn calculate_cost(
+///
+/// When the prompt exceeds the model's long-context `tier` threshold, the
+/// tier's prices apply to the whole request.
+pub(crate) fn calculate_cost(
     model: &LlmModel,
+    tier: Option<&PriceTier>,
     input_tokens: u64,
     output_tokens: u64,
     requests: u64,
     period: Period,
 ) -> CostResult {
+    let tier = tier.filter(|t| t.applies(input_tokens));
+    let (input_multiplier, output_multiplier) =
+        tier.map_or((1.0, 1.0), |t| (t.input_multiplier, t.output_multiplier));
+
     // Prices are per million tokens
     let input_cost = model
         .input_price
-        .map(|p| (input_tokens as f64 / 1_000_000.0) * p);
+        .map(|p| (input_tokens as f64 / 1_000_000.0) * p * input_multiplier);
     let output_cost = model
         .output_price
-        .map(|p| (output_tokens as f64 / 1_000_000.0) * p);
+        .map(|p| (output_tokens as f64 / 1_000_000.0) * p * output_multiplier);
 
     let total_cost = match (input_cost, output_cost) {
         (Some(i), Some(o)) => Some(i + o),
@@ -132,22 +163,7 @@ fn calculate_cost(
         (None, None) => None,
     };
 
-    // Apply requests multiplier
-    let request_cost = total_cost.map(|c| c * requests as f64);
-
-    // Calculate period costs
-    let (period_str, period_cost, monthly_cost) = match period {
-        Period::Once => ("once".to_string(), request_cost, None),
-        Period::Daily => {
-            let daily = request_cost;
-            let monthly = daily.map(|d| d * 30.0);
-            ("daily".to_string(), daily, monthly)
-        }
-        Period::Monthly => {
-            let monthly = request_cost.map(|c| c * 30.0);
-            ("monthly".to_string(), monthly, monthly)
-        }
-    };
+    let (period_str, period_cost, monthly_cost) = project_cost(total_cost, requests, period);
 
     CostResult {
         name: model.display_name().to_string(),
@@ -160,21 +176,22 @@ fn calculate_cost(
         period: period_str,
         period_cost,
         monthly_cost,
+        long_context_threshold: tier.map(|t| t.threshold),
     }
 }
 
 /// Find the minimum cost among results (for winner highlighting).
-fn find_min_cost(results: &[CostResult]) -> Option<f64> {
-    results
-        .iter()
-        .filter_map(|r| r.total_cost)
+fn find_min_cost(costs: impl IntoIterator<Item = Option<f64>>) -> Option<f64> {
+    costs
+        .into_iter()
+        .flatten()
         .min_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal))
 }
 
 /// Run the cost command.
 pub fn run(
     models: &[LlmModel],
-    model_searches: &[String],
+    search: &ModelSearch,
     input_tokens_str: &str,
     output_tokens_str: &str,
     requests: u64,
@@ -187,23 +204,30 @@ pub fn run(
     let period = Period::parse(period_str)?;
 
     // Find matching models
-    let matched_models = find_models_by_names(models, model_searches);
+    let matched_models = search.find(models);
 
     if matched_models.is_empty() {
-        return Err(AppError::NotFound(format!(
-            "No models found matching: {}",
-            model_searches.join(", ")
-        )));
+        return Err(search.not_found_error(models));
     }
 
+    // Long-context tiers come from models.dev; without them (or with a cache
+    // written before they were recorded) the base prices apply
+    let tiers = Cache::new()
+        .ok()
+        .and_then(|cache| load_price_tiers(cache.base_dir()).ok())
+        .unwrap_or_default();
+
     // Calculate costs
     let results: Vec<CostResult> = matched_models
         .iter()
-        .map(|m| calculate_cost(m, input_tokens, output_tokens, requests, period))
+        .map(|m| {
+            let tier = tiers.get(&m.slug.to_lowercase());
+            calculate_cost(m, tier, input_tokens, output_tokens, requests, period)
+        })
         .collect();
 
     // Find winner (lowest cost)
-    let min_cost = find_min_cost(&results);
+    let min_cost = find_min_cost(results.iter().map(|r| r.total_cost));
 
     // Output based on format
     if format == OutputFormat::Json {
@@ -227,7 +251,7 @@ pub fn run(
 
     match format {
         OutputFormat::Json => unreachable!(), // Handled above
-        OutputFormat::Csv | OutputFormat::Plain => {
+        OutputFormat::Csv | OutputFormat::Plain | OutputFormat::Porcelain => {
             // For CSV and Plain, just output the table data without any preamble
             println!("{}", format_output(&rows, format));
         }
@@ -241,18 +265,24 @@ pub fn run(
                 println!("Cost per request:");
                 println!(
                     "  Input ({} tokens):    {}",
-                    format_token_count(result.input_tokens),
+                    format_tokens(result.input_tokens),
                     format_cost(result.input_cost)
                 );
                 println!(
                     "  Output ({} tokens):   {}",
-                    format_token_count(result.output_tokens),
+                    format_tokens(result.output_tokens),
                     format_cost(result.output_cost)
                 );
                 println!(
                     "  Total:                  {}",
                     format_cost(result.total_cost)
                 );
+                if let Some(threshold) = result.long_context_threshold {
+                    println!(
+                        "  Long-context pricing applies (prompt over {} tokens)",
+                        format_tokens(threshold)
+                    );
+                }
 
                 if requests > 1 || period != Period::Once {
                     println!();
@@ -284,8 +314,8 @@ pub fn run(
                 // Multiple models: comparison table with preamble
                 println!(
                     "Cost Comparison ({} input / {} output tokens per request)",
-                    format_token_count(input_tokens),
-                    format_token_count(output_tokens)
+                    format_tokens(input_tokens),
+                    format_tokens(output_tokens)
                 );
 
                 if requests > 1 {
@@ -299,6 +329,15 @@ pub fn run(
                     println!();
                     println!("* = lowest cost");
                 }
+                for r in &results {
+                    if let Some(threshold) = r.long_context_threshold {
+                        println!(
+                            "{}: long-context pricing applies (prompt over {} tokens)",
+                            r.name,
+                            format_tokens(threshold)
+                        );
+                    }
+                }
 
                 // Show projections if requested
                 if period != Period::Once {
@@ -326,3 +365,522 @@ pub fn run(
 
     Ok(())
 }
+
+/// Published prices of a text-to-speech model.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpeechPrice {
+    pub name: String,
+    pub slug: String,
+    pub price_per_1m_characters: Option<f64>,
+    pub price_per_minute: Option<f64>,
+}
+
+/// Cost calculation result for a text-to-speech model.
+#[derive(Debug, Clone, Serialize)]
+pub struct SpeechCostResult {
+    pub name: String,
+    pub characters: u64,
+    pub price_per_1m_characters: Option<f64>,
+    pub price_per_minute: Option<f64>,
+    pub request_cost: Option<f64>,
+    pub requests: u64,
+    pub period: String,
+    pub period_cost: Option<f64>,
+    pub monthly_cost: Option<f64>,
+}
+
+/// Speech cost row for table output.
+#[derive(Debug, Clone, Serialize, Tabled)]
+pub struct SpeechCostRow {
+    #[tabled(rename = "Model")]
+    pub name: String,
+    #[tabled(rename = "Price/1M Chars")]
+    pub price_per_1m_characters: String,
+    #[tabled(rename = "Price/Min")]
+    pub price_per_minute: String,
+    #[tabled(rename = "Total")]
+    pub total_cost: String,
+}
+
+impl Formattable for SpeechCostRow {
+    fn headers() -> &'static [&'static str] {
+        &["Model", "Price/1M Chars", "Price/Min", "Total"]
+    }
+
+    fn to_row(&self) -> Vec<String> {
+        vec![
+            self.name.clone(),
+            self.price_per_1m_characters.clone(),
+            self.price_per_minute.clone(),
+            self.total_cost.clone(),
+        ]
+    }
+}
+
+/// Load text-to-speech prices from the cached `text_to_speech` table.
+///
+/// Prices are read as text so small per-character prices keep their full
+/// precision.
+pub fn load_speech_prices(cache_dir: &Path) -> Result<Vec<SpeechPrice>> {
+    let executor = QueryExecutor::new(cache_dir.to_path_buf());
+    let result = executor.execute(
+        "SELECT name, slug, CAST(price_per_1m_characters AS VARCHAR), \
+         CAST(price_per_minute AS VARCHAR) FROM text_to_speech ORDER BY elo DESC NULLS LAST, name",
+    )?;
+
+    Ok(result
+        .rows
+        .into_iter()
+        .map(|row| SpeechPrice {
+            name: row[0].clone(),
+            slug: row[1].clone(),
+            price_per_1m_characters: row[2].parse().ok(),
+            price_per_minute: row[3].parse().ok(),
+        })
+        .collect())
+}
+
+/// Calculate character costs for the speech models matching `search`.
+///
+/// Models that only publish per-minute pricing get no total, since the
+/// audio length of a given text depends on the voice.
+fn calculate_speech_costs(
+    prices: &[SpeechPrice],
+    search: &ModelSearch,
+    characters: u64,
+    requests: u64,
+    period: Period,
+) -> Result<Vec<SpeechCostResult>> {
+    let results: Vec<SpeechCostResult> = prices
+        .iter()
+        .filter(|p| search.matches(&p.slug, &p.name))
+        .map(|p| {
+            let request_cost = p
+                .price_per_1m_characters
+                .map(|price| (characters as f64 / 1_000_000.0) * price);
+            let (period_str, period_cost, monthly_cost) =
+                project_cost(request_cost, requests, period);
+            SpeechCostResult {
+                name: p.name.clone(),
+                characters,
+                price_per_1m_characters: p.price_per_1m_characters,
+                price_per_minute: p.price_per_minute,
+                request_cost,
+                requests,
+                period: period_str,
+                period_cost,
+                monthly_cost,
+            }
+        })
+        .collect();
+
+    if results.is_empty() {
+        return Err(AppError::NotFound(format!(
+            "No text-to-speech models found matching: {}",
+            search.terms().join(", ")
+        )));
+    }
+    Ok(results)
+}
+
+/// Run the cost command for text-to-speech models (`--characters`).
+pub fn run_speech(
+    search: &ModelSearch,
+    characters_str: &str,
+    requests: u64,
+    period_str: &str,
+    format: OutputFormat,
+) -> Result<()> {
+    let characters = parse_tokens(characters_str)?;
+    let period = Period::parse(period_str)?;
+
+    let cache = Cache::new()?;
+    let prices = load_speech_prices(cache.base_dir())?;
+    let results = calculate_speech_costs(&prices, search, characters, requests, period)?;
+
+    if format == OutputFormat::Json {
+        println!("{}", crate::output::json::format_json(&results));
+        return Ok(());
+    }
+
+    let min_cost = find_min_cost(results.iter().map(|r| r.period_cost));
+    let rows: Vec<SpeechCostRow> = results
+        .iter()
+        .map(|r| {
+            let is_winner =
+                results.len() > 1 && r.period_cost.is_some() && r.period_cost == min_cost;
+            SpeechCostRow {
+                name: r.name.clone(),
+                price_per_1m_characters: format_cost(r.price_per_1m_characters),
+                price_per_minute: format_cost(r.price_per_minute),
+                total_cost: format_cost_with_winner(r.period_cost, is_winner),
+            }
+        })
+        .collect();
+
+    match format {
+        OutputFormat::Json => unreachable!(), // Handled above
+        OutputFormat::Csv | OutputFormat::Plain | OutputFormat::Porcelain => {
+            println!("{}", format_output(&rows, format));
+        }
+        OutputFormat::Markdown | OutputFormat::Table => {
+            println!(
+                "Speech Cost ({} characters per request)",
+                format_tokens(characters)
+            );
+            if requests > 1 {
+                println!("Requests: {} ({})", requests, results[0].period);
+            }
+            println!();
+            println!("{}", format_output(&rows, format));
+
+            if results.len() > 1 && min_cost.is_some() {
+                println!();
+                println!("* = lowest cost");
+            }
+            if results.iter().any(|r| r.request_cost.is_none()) {
+                println!("N/A = no per-character price published");
+            }
+            if period == Period::Daily {
+                println!();
+                println!("Monthly estimates (30 days):");
+                for r in &results {
+                    println!("  {}: {}", r.name, format_cost(r.monthly_cost));
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Published per-second price of a video model.
+#[derive(Debug, Clone, PartialEq)]
+pub struct VideoPrice {
+    pub name: String,
+    pub slug: String,
+    pub price_per_second: Option<f64>,
+}
+
+/// Cost calculation result for a video model.
+#[derive(Debug, Clone, Serialize)]
+pub struct VideoCostResult {
+    pub name: String,
+    pub seconds: f64,
+    pub price_per_second: Option<f64>,
+    pub clip_cost: Option<f64>,
+    pub clips: u64,
+    pub period: String,
+    pub period_cost: Option<f64>,
+    pub monthly_cost: Option<f64>,
+}
+
+/// Video cost row for table output.
+#[derive(Debug, Clone, Serialize, Tabled)]
+pub struct VideoCostRow {
+    #[tabled(rename = "Model")]
+    pub name: String,
+    #[tabled(rename = "Price/Sec")]
+    pub price_per_second: String,
+    #[tabled(rename = "Per Clip")]
+    pub clip_cost: String,
+    #[tabled(rename = "Total")]
+    pub total_cost: String,
+}
+
+impl Formattable for VideoCostRow {
+    fn headers() -> &'static [&'static str] {
+        &["Model", "Price/Sec", "Per Clip", "Total"]
+    }
+
+    fn to_row(&self) -> Vec<String> {
+        vec![
+            self.name.clone(),
+            self.price_per_second.clone(),
+            self.clip_cost.clone(),
+            self.total_cost.clone(),
+        ]
+    }
+}
+
+/// Resolve the `--media` kind for video pricing.
+///
+/// # Errors
+///
+/// Returns `AppError::Config` for unknown kinds and non-video kinds.
+pub fn video_kind(kind: &str) -> Result<&'static TableDef> {
+    let table = crate::commands::media::media_kind(kind)?;
+    if table.name != TEXT_TO_VIDEO.name && table.name != IMAGE_TO_VIDEO.name {
+        return Err(AppError::Config(format!(
+            "Per-second pricing only applies to video kinds (text-to-video, image-to-video), not '{}'. \
+             Use --characters for text-to-speech.",
+            kind
+        )));
+    }
+    Ok(table)
+}
+
+/// Load per-second prices from a cached video table.
+pub fn load_video_prices(cache_dir: &Path, table: &TableDef) -> Result<Vec<VideoPrice>> {
+    let executor = QueryExecutor::new(cache_dir.to_path_buf());
+    let result = executor.execute(&format!(
+        "SELECT name, slug, CAST(price_per_second AS VARCHAR) FROM {} \
+         ORDER BY elo DESC NULLS LAST, name",
+        table.name
+    ))?;
+
+    Ok(result
+        .rows
+        .into_iter()
+        .map(|row| VideoPrice {
+            name: row[0].clone(),
+            slug: row[1].clone(),
+            price_per_second: row[2].parse().ok(),
+        })
+        .collect())
+}
+
+/// Calculate clip costs for the video models matching `search`.
+fn calculate_video_costs(
+    prices: &[VideoPrice],
+    search: &ModelSearch,
+    seconds: f64,
+    clips: u64,
+    period: Period,
+) -> Result<Vec<VideoCostResult>> {
+    let results: Vec<VideoCostResult> = prices
+        .iter()
+        .filter(|p| search.matches(&p.slug, &p.name))
+        .map(|p| {
+            let clip_cost = p.price_per_second.map(|price| seconds * price);
+            let (period_str, period_cost, monthly_cost) = project_cost(clip_cost, clips, period);
+            VideoCostResult {
+                name: p.name.clone(),
+                seconds,
+                price_per_second: p.price_per_second,
+                clip_cost,
+                clips,
+                period: period_str,
+                period_cost,
+                monthly_cost,
+            }
+        })
+        .collect();
+
+    if results.is_empty() {
+        return Err(AppError::NotFound(format!(
+            "No video models found matching: {}",
+            search.terms().join(", ")
+        )));
+    }
+    Ok(results)
+}
+
+/// Run the cost command for video models (`--media <kind> --seconds`).
+pub fn run_video(
+    search: &ModelSearch,
+    table: &TableDef,
+    seconds: f64,
+    clips: u64,
+    period_str: &str,
+    format: OutputFormat,
+) -> Result<()> {
+    if !(seconds.is_finite() && seconds > 0.0) {
+        return Err(AppError::Config(format!(
+            "Invalid --seconds '{}'. Use a positive clip length.",
+            seconds
+        )));
+    }
+    let period = Period::parse(period_str)?;
+
+    let cache = Cache::new()?;
+    let prices = load_video_prices(cache.base_dir(), table)?;
+    let results = calculate_video_costs(&prices, search, seconds, clips, period)?;
+
+    if format == OutputFormat::Json {
+        println!("{}", crate::output::json::format_json(&results));
+        return Ok(());
+    }
+
+    let min_cost = find_min_cost(results.iter().map(|r| r.period_cost));
+    let rows: Vec<VideoCostRow> = results
+        .iter()
+        .map(|r| {
+            let is_winner =
+                results.len() > 1 && r.period_cost.is_some() && r.period_cost == min_cost;
+            VideoCostRow {
+                name: r.name.clone(),
+                price_per_second: format_cost(r.price_per_second),
+                clip_cost: format_cost(r.clip_cost),
+                total_cost: format_cost_with_winner(r.period_cost, is_winner),
+            }
+        })
+        .collect();
+
+    match format {
+        OutputFormat::Json => unreachable!(), // Handled above
+        OutputFormat::Csv | OutputFormat::Plain | OutputFormat::Porcelain => {
+            println!("{}", format_output(&rows, format));
+        }
+        OutputFormat::Markdown | OutputFormat::Table => {
+            println!(
+                "Video Cost ({}s per clip, {} clips {})",
+                seconds,
+                clips,
+                match period {
+                    Period::Once => "total",
+                    Period::Daily => "per day",
+                    Period::Monthly => "per day, over 30 days",
+                }
+            );
+            println!();
+            println!("{}", format_output(&rows, format));
+
+            if results.len() > 1 && min_cost.is_some() {
+                println!();
+                println!("* = lowest cost");
+            }
+            if results.iter().any(|r| r.clip_cost.is_none()) {
+                println!("N/A = no per-second price published");
+            }
+            if period == Period::Daily {
+                println!();
+                println!("Monthly estimates (30 days):");
+                for r in &results {
+                    println!("  {}: {}", r.name, format_cost(r.monthly_cost));
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn speech_prices() -> Vec<SpeechPrice> {
+        vec![
+            SpeechPrice {
+                name: "Eleven v3".into(),
+                slug: "eleven-v3".into(),
+                price_per_1m_characters: Some(100.0),
+                price_per_minute: None,
+            },
+            SpeechPrice {
+                name: "Voice Pro".into(),
+                slug: "voice-pro".into(),
+                price_per_1m_characters: None,
+                price_per_minute: Some(0.015),
+            },
+        ]
+    }
+
+    #[test]
+    fn test_video_costs_per_clip() {
+        let prices = vec![
+            VideoPrice {
+                name: "Kling 2.5".into(),
+                slug: "kling-2-5".into(),
+                price_per_second: Some(0.07),
+            },
+            VideoPrice {
+                name: "Sora 2".into(),
+                slug: "sora-2".into(),
+                price_per_second: Some(0.1),
+            },
+            VideoPrice {
+                name: "Veo 3".into(),
+                slug: "veo-3".into(),
+                price_per_second: None,
+            },
+        ];
+        let search = ModelSearch::new(vec!["kling".into(), "sora".into()], false).unwrap();
+        let results = calculate_video_costs(&prices, &search, 30.0, 100, Period::Once).unwrap();
+
+        assert_eq!(results.len(), 2);
+        assert!((results[0].clip_cost.unwrap() - 2.1).abs() < 1e-9);
+        assert!((results[0].period_cost.unwrap() - 210.0).abs() < 1e-9);
+        assert!((results[1].period_cost.unwrap() - 300.0).abs() < 1e-9);
+        assert_eq!(
+            find_min_cost(results.iter().map(|r| r.period_cost)),
+            results[0].period_cost
+        );
+    }
+
+    #[test]
+    fn test_video_kind() {
+        assert_eq!(video_kind("text-to-video").unwrap().name, "text_to_video");
+        assert_eq!(video_kind("image_to_video").unwrap().name, "image_to_video");
+        let err = video_kind("text-to-image").unwrap_err();
+        assert_eq!(err.exit_code(), 2);
+    }
+
+    #[test]
+    fn test_calculate_cost_long_context_tier() {
+        let model = LlmModel {
+            name: "Gemini 2.5 Pro".into(),
+            input_price: Some(1.25),
+            output_price: Some(10.0),
+            ..Default::default()
+        };
+        let close = |a: Option<f64>, b: f64| (a.unwrap() - b).abs() < 1e-9;
+        let tier = PriceTier {
+            threshold: 200_000,
+            input_multiplier: 2.0,
+            output_multiplier: 1.5,
+        };
+
+        let base = calculate_cost(&model, Some(&tier), 100_000, 1_000_000, 1, Period::Once);
+        assert!(close(base.input_cost, 0.125));
+        assert!(close(base.output_cost, 10.0));
+        assert_eq!(base.long_context_threshold, None);
+
+        // The tier prices the whole request, not just the tokens past 200K
+        let long = calculate_cost(&model, Some(&tier), 300_000, 1_000_000, 1, Period::Once);
+        assert!(close(long.input_cost, 0.75));
+        assert!(close(long.output_cost, 15.0));
+        assert!(close(long.total_cost, 15.75));
+        assert_eq!(long.long_context_threshold, Some(200_000));
+
+        let untiered = calculate_cost(&model, None, 300_000, 1_000_000, 1, Period::Once);
+        assert!(close(untiered.input_cost, 0.375));
+    }
+
+    #[test]
+    fn test_project_cost() {
+        assert_eq!(
+            project_cost(Some(2.0), 10, Period::Once),
+            ("once".to_string(), Some(20.0), None)
+        );
+        assert_eq!(
+            project_cost(Some(2.0), 10, Period::Daily),
+            ("daily".to_string(), Some(20.0), Some(600.0))
+        );
+        assert_eq!(project_cost(None, 10, Period::Monthly).1, None);
+    }
+
+    #[test]
+    fn test_speech_costs_per_character() {
+        let search = ModelSearch::new(vec!["eleven".into(), "voice".into()], false).unwrap();
+        let results =
+            calculate_speech_costs(&speech_prices(), &search, 500_000, 4, Period::Once).unwrap();
+
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].request_cost, Some(50.0));
+        assert_eq!(results[0].period_cost, Some(200.0));
+        // Per-minute pricing alone cannot price a character count
+        assert_eq!(results[1].request_cost, None);
+        assert_eq!(results[1].price_per_minute, Some(0.015));
+    }
+
+    #[test]
+    fn test_speech_costs_not_found() {
+        let search = ModelSearch::new(vec!["gpt".into()], false).unwrap();
+        let err =
+            calculate_speech_costs(&speech_prices(), &search, 1_000, 1, Period::Once).unwrap_err();
+        assert_eq!(err.exit_code(), 5);
+        assert!(err.to_string().contains("text-to-speech"));
+    }
+}
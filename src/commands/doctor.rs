@@ -0,0 +1,387 @@
+//! Doctor command - diagnose the local setup.
+//!
+//! Checks the config file, stored credentials, cache directory, DuckDB,
+//! reachability of every data source, and installed skills, and prints a
+//! fix for each check that does not pass.
+
+use crate::cache::Cache;
+use crate::cli::invocation;
+use crate::commands::skill::Tool;
+use crate::config::Config;
+use crate::error::{AppError, Result};
+use crate::output::{format_output, Formattable, OutputFormat};
+use crate::remote::{release_file_url, DataChannel};
+use crate::sources::artificial_analysis::API_BASE;
+use crate::sources::models_dev::MODELS_DEV_API;
+use duckdb::Connection;
+use serde::Serialize;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tabled::Tabled;
+
+/// Timeout for each network reachability probe.
+const NETWORK_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Outcome of a check.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Status {
+    Pass,
+    /// Works, but something is worth fixing.
+    Warn,
+    Fail,
+}
+
+impl fmt::Display for Status {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Status::Pass => "PASS",
+            Status::Warn => "WARN",
+            Status::Fail => "FAIL",
+        })
+    }
+}
+
+/// Result of one diagnostic check.
+#[derive(Debug, Clone, Serialize, Tabled)]
+pub struct Check {
+    #[tabled(rename = "Check")]
+    pub name: String,
+    #[tabled(rename = "Status")]
+    pub status: Status,
+    #[tabled(rename = "Detail")]
+    pub detail: String,
+    /// How to fix a failing or warning check; empty when it passed.
+    #[tabled(rename = "Fix")]
+    pub fix: String,
+}
+
+impl Formattable for Check {
+    fn headers() -> &'static [&'static str] {
+        &["Check", "Status", "Detail", "Fix"]
+    }
+
+    fn to_row(&self) -> Vec<String> {
+        vec![
+            self.name.clone(),
+            self.status.to_string(),
+            self.detail.clone(),
+            self.fix.clone(),
+        ]
+    }
+}
+
+fn pass(name: impl Into<String>, detail: impl Into<String>) -> Check {
+    Check {
+        name: name.into(),
+        status: Status::Pass,
+        detail: detail.into(),
+        fix: String::new(),
+    }
+}
+
+fn problem(
+    status: Status,
+    name: impl Into<String>,
+    detail: impl Into<String>,
+    fix: impl Into<String>,
+) -> Check {
+    Check {
+        name: name.into(),
+        status,
+        detail: detail.into(),
+        fix: fix.into(),
+    }
+}
+
+/// The config file parses and its settings are valid.
+fn check_config(path: &Path) -> Check {
+    let config = match Config::load() {
+        Ok(config) => config,
+        Err(e) => {
+            return problem(
+                Status::Fail,
+                "config",
+                e.to_string(),
+                format!("Fix or remove {}", path.display()),
+            )
+        }
+    };
+    if let Err(e) = config.parquet.copy_options() {
+        return problem(
+            Status::Fail,
+            "config",
+            e.to_string(),
+            format!("Edit [parquet] in {}", path.display()),
+        );
+    }
+    if let Some(name) = config
+        .default_profile
+        .as_deref()
+        .filter(|name| config.get_profile(name).is_none())
+    {
+        return problem(
+            Status::Fail,
+            "config",
+            format!("default profile '{}' does not exist", name),
+            format!(
+                "Run '{}' with an existing profile",
+                invocation("profile default <name>")
+            ),
+        );
+    }
+    if path.exists() {
+        pass("config", path.display().to_string())
+    } else {
+        pass("config", "no config file (defaults in use)")
+    }
+}
+
+/// API keys are stored in the config file; it should be private to the user.
+fn check_credentials(path: &Path) -> Check {
+    let profiles = Config::load().map(|c| c.profiles.len()).unwrap_or_default();
+    if profiles == 0 {
+        return pass("credentials", "no API keys stored (hosted data needs none)");
+    }
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        if let Ok(meta) = std::fs::metadata(path) {
+            if meta.permissions().mode() & 0o077 != 0 {
+                return problem(
+                    Status::Warn,
+                    "credentials",
+                    format!(
+                        "{} holds {} API key(s) and is readable by other users",
+                        path.display(),
+                        profiles
+                    ),
+                    format!("chmod 600 {}", path.display()),
+                );
+            }
+        }
+    }
+    pass(
+        "credentials",
+        format!("{} API key(s) in {}", profiles, path.display()),
+    )
+}
+
+/// The cache directory exists and accepts writes, unless it is read-only.
+fn check_cache() -> Check {
+    let cache = match Cache::new() {
+        Ok(cache) => cache,
+        Err(e) => {
+            return problem(
+                Status::Fail,
+                "cache",
+                e.to_string(),
+                "Set WHICH_LLM_CACHE_DIR to a writable directory",
+            )
+        }
+    };
+    if crate::cache::is_read_only() {
+        return if cache.base_dir().is_dir() {
+            pass(
+                "cache",
+                format!(
+                    "{} is read-only (WHICH_LLM_READONLY)",
+                    cache.base_dir().display()
+                ),
+            )
+        } else {
+            problem(
+                Status::Warn,
+                "cache",
+                format!(
+                    "read-only cache {} does not exist",
+                    cache.base_dir().display()
+                ),
+                "Mount the shared cache there or set WHICH_LLM_CACHE_DIR",
+            )
+        };
+    }
+    let probe = cache.base_dir().join(".doctor-probe");
+    let written = std::fs::write(&probe, b"ok").and_then(|_| std::fs::remove_file(&probe));
+    match written {
+        Ok(()) => pass(
+            "cache",
+            format!("{} is writable", cache.base_dir().display()),
+        ),
+        Err(e) => problem(
+            Status::Fail,
+            "cache",
+            format!("cannot write to {}: {}", cache.base_dir().display(), e),
+            "Fix the directory permissions or set WHICH_LLM_CACHE_DIR",
+        ),
+    }
+}
+
+/// DuckDB runs queries and round-trips a Parquet file.
+fn check_duckdb() -> Check {
+    let path =
+        std::env::temp_dir().join(format!("which-llm-doctor-{}.parquet", std::process::id()));
+    let result = (|| -> duckdb::Result<i64> {
+        let conn = Connection::open_in_memory()?;
+        let file = path.to_string_lossy().replace('\\', "/");
+        conn.execute(
+            &format!("COPY (SELECT 42 AS answer) TO '{}' (FORMAT PARQUET)", file),
+            [],
+        )?;
+        conn.query_row(
+            &format!("SELECT answer FROM read_parquet('{}')", file),
+            [],
+            |row| row.get(0),
+        )
+    })();
+    let _ = std::fs::remove_file(&path);
+
+    match result {
+        Ok(42) => pass("duckdb", "queries and Parquet I/O work"),
+        Ok(other) => problem(
+            Status::Fail,
+            "duckdb",
+            format!("Parquet round trip returned {}", other),
+            "Reinstall which-llm",
+        ),
+        Err(e) => problem(
+            Status::Fail,
+            "duckdb",
+            e.to_string(),
+            "Reinstall which-llm; check that the temp directory is writable",
+        ),
+    }
+}
+
+/// Each data source answers HTTP requests. Any response counts, since the
+/// API rejects requests without a key.
+async fn check_network() -> Vec<Check> {
+    let client = match reqwest::Client::builder()
+        .user_agent(format!("which-llm/{}", env!("CARGO_PKG_VERSION")))
+        .timeout(NETWORK_TIMEOUT)
+        .build()
+    {
+        Ok(client) => client,
+        Err(e) => {
+            return vec![problem(
+                Status::Fail,
+                "network",
+                e.to_string(),
+                "Check TLS and proxy settings",
+            )]
+        }
+    };
+
+    let sources = [
+        (
+            "network: hosted data",
+            // An invalid channel is reported by every data command
+            release_file_url(DataChannel::current().unwrap_or_default(), "manifest.json"),
+        ),
+        ("network: Artificial Analysis", API_BASE.to_string()),
+        ("network: models.dev", MODELS_DEV_API.to_string()),
+    ];
+    let mut checks = Vec::new();
+    for (name, url) in sources {
+        checks.push(match client.get(&url).send().await {
+            Ok(response) => pass(name, format!("{} ({})", url, response.status())),
+            Err(e) => problem(
+                Status::Fail,
+                name,
+                format!("{}: {}", url, e),
+                "Check your connection, proxy (HTTPS_PROXY) and firewall",
+            ),
+        });
+    }
+    checks
+}
+
+/// Installed skills contain their `SKILL.md`.
+fn check_skills() -> Vec<Check> {
+    let cwd = std::env::current_dir().unwrap_or_default();
+    let mut checks = Vec::new();
+    for tool in Tool::all() {
+        let installs: [(PathBuf, &str); 2] = [
+            (cwd.join(tool.project_path()), ""),
+            (tool.global_path().unwrap_or_default(), " --global"),
+        ];
+        for (dir, flag) in installs {
+            if dir.as_os_str().is_empty() || !dir.exists() {
+                continue;
+            }
+            let name = format!("skill: {}", tool.name());
+            checks.push(if dir.join("SKILL.md").is_file() {
+                pass(name, dir.display().to_string())
+            } else {
+                problem(
+                    Status::Fail,
+                    name,
+                    format!("{} is missing SKILL.md", dir.display()),
+                    invocation(&format!("skill install {}{} --force", tool.name(), flag)),
+                )
+            });
+        }
+    }
+    if checks.is_empty() {
+        checks.push(pass("skills", "none installed"));
+    }
+    checks
+}
+
+/// Run every check.
+pub async fn diagnose() -> Vec<Check> {
+    let config_path = Config::config_path().unwrap_or_default();
+    let mut checks = vec![
+        check_config(&config_path),
+        check_credentials(&config_path),
+        check_cache(),
+        check_duckdb(),
+    ];
+    checks.extend(check_network().await);
+    checks.extend(check_skills());
+    checks
+}
+
+/// Run the doctor command.
+///
+/// Fails (exit code 2) when any check fails; warnings alone do not.
+pub async fn run(format: OutputFormat) -> Result<()> {
+    let checks = diagnose().await;
+    println!("{}", format_output(&checks, format));
+
+    let failed = checks.iter().filter(|c| c.status == Status::Fail).count();
+    if failed > 0 {
+        return Err(AppError::Config(format!(
+            "{} of {} checks failed",
+            failed,
+            checks.len()
+        )));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_duckdb() {
+        let check = check_duckdb();
+        assert_eq!(check.status, Status::Pass, "{}", check.detail);
+    }
+
+    #[test]
+    fn test_check_row() {
+        let check = problem(Status::Warn, "credentials", "readable", "chmod 600 x");
+        assert_eq!(
+            check.to_row(),
+            vec!["credentials", "WARN", "readable", "chmod 600 x"]
+        );
+        assert_eq!(
+            serde_json::to_value(&check).unwrap()["status"],
+            serde_json::json!("warn")
+        );
+    }
+}
@@ -0,0 +1,400 @@
+//! Examples command - curated real-world invocations.
+//!
+//! The same registry feeds `examples [COMMAND]`, `<command> --examples` and
+//! the "Examples:" section of each command's `--help`.
+
+use crate::cli::invocation;
+use crate::error::{AppError, Result};
+use crate::output::json::format_json;
+use crate::output::OutputFormat;
+use serde_json::json;
+
+/// One curated invocation.
+#[derive(Debug, Clone, Copy)]
+pub struct Example {
+    /// Subcommand the example belongs to.
+    pub command: &'static str,
+    pub description: &'static str,
+    /// Arguments after the binary name, quoted as for a POSIX shell.
+    pub args: &'static str,
+}
+
+const fn example(command: &'static str, description: &'static str, args: &'static str) -> Example {
+    Example {
+        command,
+        description,
+        args,
+    }
+}
+
+/// Every example, grouped by command in help order.
+pub const EXAMPLES: &[Example] = &[
+    example(
+        "query",
+        "Best coding models under $5/M output tokens",
+        r#"query "SELECT name, creator, coding, output_price FROM benchmarks WHERE coding > 40 AND output_price < 5 ORDER BY coding DESC""#,
+    ),
+    example(
+        "query",
+        "Cache pricing and API key env vars per provider, fetching uncached tables first",
+        r#"query --fetch "SELECT model_name, provider_name, cost_cache_read, provider_env FROM models WHERE cost_cache_read IS NOT NULL""#,
+    ),
+    example(
+        "query",
+        "Models with tool calling and a context window over 100k",
+        r#"query "SELECT model_name, provider_name, context_window FROM models WHERE tool_call = true AND context_window > 100000""#,
+    ),
+    example(
+        "query",
+        "Explore the cached tables in an interactive SQL shell",
+        r#"query --interactive"#,
+    ),
+    example(
+        "query",
+        "Average intelligence by creator",
+        r#"query "SELECT creator, COUNT(*) AS models, ROUND(AVG(intelligence), 1) AS avg FROM benchmarks GROUP BY creator ORDER BY avg DESC LIMIT 10""#,
+    ),
+    example(
+        "query",
+        "Use a filter as a shell conditional (exit code 6 when nothing matches)",
+        r#"query "SELECT name FROM benchmarks WHERE price < 1 AND intelligence > 55" --fail-on-empty"#,
+    ),
+    example(
+        "compare",
+        "Compare three models side by side",
+        r#"compare "gpt-5 (high)" "claude 4.5 sonnet" "gemini 2.5 pro""#,
+    ),
+    example(
+        "compare",
+        "Pick the fields to show, in order",
+        r#"compare gpt-5 claude-4.5 --fields intelligence,input_price,tps"#,
+    ),
+    example(
+        "compare",
+        "Only recent models that pass a filter",
+        r#"compare gpt claude gemini --filter "intelligence>50 && price<5" --released-after 90d"#,
+    ),
+    example(
+        "compare",
+        "One column per model family, using each family's best variant",
+        r#"compare gpt-5 claude gemini --collapse-variants"#,
+    ),
+    example(
+        "compare",
+        "What reasoning costs: each model next to its non-reasoning variant",
+        r#"compare --with-reasoning-pair gpt-5 "claude 4.5 sonnet""#,
+    ),
+    example(
+        "compare",
+        "Tail latency and speed instead of medians",
+        r#"compare gpt-5 claude gemini --percentile p95"#,
+    ),
+    example(
+        "cost",
+        "Cost of one request",
+        r#"cost "gpt-5 (high)" --input 10k --output 5k"#,
+    ),
+    example(
+        "cost",
+        "Daily and monthly cost at 1,000 requests a day",
+        r#"cost gpt-5 "claude 4.5" --input 2k --output 1k --requests 1000 --period daily"#,
+    ),
+    example(
+        "cost",
+        "Text-to-speech cost by characters",
+        r#"cost eleven voice --characters 500k --requests 100 --period daily"#,
+    ),
+    example(
+        "cost",
+        "Video generation cost per clip",
+        r#"cost --media text-to-video kling sora --seconds 30 --clips 100"#,
+    ),
+    example(
+        "report",
+        "Monthly cost per team, with cheaper alternatives",
+        r#"report --workloads workloads.yaml"#,
+    ),
+    example(
+        "report",
+        "Only suggest alternatives the policy allows",
+        r#"report --workloads workloads.yaml --policy policy.toml --json"#,
+    ),
+    example(
+        "migrate-cost",
+        "What switching models would change for last month's traffic",
+        r#"migrate-cost --from gpt-4o --to claude-4-5-sonnet --usage usage.csv"#,
+    ),
+    example(
+        "route",
+        "Primary and fallback model for a task mix under a price cap",
+        r#"route --mix coding=0.5,chat=0.3,math=0.2 --max-price 5"#,
+    ),
+    example(
+        "shortlist",
+        "Three cheap candidates with tool calling for a bake-off",
+        r#"shortlist --budget-per-1k 0.01 --needs tool_call --count 3"#,
+    ),
+    example(
+        "llms",
+        "Capable, affordable models without writing SQL",
+        r#"llms --min-intelligence 50 --max-price 2 --tool-call"#,
+    ),
+    example(
+        "llms",
+        "Fast open-weight models released this year",
+        r#"llms --open-weights --min-tps 100 --released-after 2025-01-01"#,
+    ),
+    example(
+        "llms",
+        "Every model with tool calling, not just the top 20",
+        r#"llms --tool-call --all"#,
+    ),
+    example(
+        "llms",
+        "Tool-calling models, fastest first",
+        r#"llms --tool-call --sort tps"#,
+    ),
+    example(
+        "llms",
+        "The best variant of each family, two per creator",
+        r#"llms --collapse-variants --top-per creator=2"#,
+    ),
+    example(
+        "recommend",
+        "Best affordable models with tool calling and a long context",
+        r#"recommend --max-input-price 2 --min-intelligence 50 --tool-call --context-min 128k"#,
+    ),
+    example(
+        "recommend",
+        "Cheapest capable models first",
+        r#"recommend --min-intelligence 40 --optimize cost -n 3"#,
+    ),
+    example(
+        "get",
+        "A single raw value for scripts",
+        r#"get gpt-4o --field input_price"#,
+    ),
+    example(
+        "get",
+        "Every reasoning-effort variant of a model",
+        r#"get gpt-5 --family"#,
+    ),
+    example(
+        "get",
+        "Env setup and SDK install commands for each provider",
+        r#"get claude-4-5-sonnet --integration"#,
+    ),
+    example(
+        "open",
+        "Newest open-weight models, two per creator",
+        r#"open --sort release_date --top-per creator=2"#,
+    ),
+    example(
+        "unmatched",
+        "Newest models on models.dev that AA has not benchmarked yet",
+        r#"unmatched --limit 10"#,
+    ),
+    example(
+        "audit",
+        "Models whose AA and models.dev prices differ by more than 25%",
+        r#"audit pricing --threshold 25"#,
+    ),
+    example(
+        "media",
+        "Top text-to-video models from one creator",
+        r#"media --kind text-to-video --creator google --min-elo 1100 --top 5"#,
+    ),
+    example(
+        "media",
+        "Text-to-image leaderboard for one style",
+        r#"media --kind text-to-image --style photorealistic"#,
+    ),
+    example(
+        "media",
+        "Every text-to-speech model, not just the top 20",
+        r#"media --kind text-to-speech --all"#,
+    ),
+    example(
+        "stats",
+        "Dataset-wide aggregates as JSON",
+        r#"stats --json"#,
+    ),
+    example(
+        "schema",
+        "Every table's columns and types as JSON, to save before an upgrade",
+        r#"schema dump"#,
+    ),
+    example(
+        "schema",
+        "Columns added, removed or retyped since a saved dump",
+        r#"schema diff schema-0.7.json"#,
+    ),
+    example(
+        "trend",
+        "Models whose input price fell fastest over the last 90 days",
+        r#"trend --metric input_price --window 90d"#,
+    ),
+    example(
+        "trend",
+        "Fastest intelligence climbers over six months, as JSON",
+        r#"trend --metric intelligence --window 6m --json"#,
+    ),
+    example(
+        "changelog",
+        "Markdown summary of the last two weeks for a team update",
+        r#"changelog --since 14d"#,
+    ),
+    example(
+        "changelog",
+        "Changes since the start of a quarter, as JSON",
+        r#"changelog --since 2025-Q3 --json"#,
+    ),
+    example(
+        "note",
+        "Record a decision against a model",
+        r#"note add gpt-4o "approved for prod""#,
+    ),
+    example("note", "Show every note", r#"note list"#),
+    example(
+        "tag",
+        "Tag a model for later selection",
+        r#"tag add claude-3-5-haiku cheap-tier"#,
+    ),
+    example(
+        "llms",
+        "List only the models tagged cheap-tier",
+        r#"llms --tag cheap-tier"#,
+    ),
+    example(
+        "policy",
+        "Explain whether a model meets policy.toml",
+        r#"policy check gpt-4o"#,
+    ),
+    example(
+        "shortlist",
+        "Shortlist only models a governance policy allows",
+        r#"shortlist --policy team-policy.toml -n 5"#,
+    ),
+    example(
+        "tokens",
+        "Convert between short and exact token counts",
+        r#"tokens 1.5M 250k"#,
+    ),
+    example(
+        "tokens",
+        "Rough token count of a prompt file for Claude's tokenizer",
+        r#"tokens --chars prompt.txt --model claude-4-5-sonnet"#,
+    ),
+    example(
+        "batch",
+        "Run a file of commands in one process, loading the data once",
+        r#"batch commands.txt --keep-going"#,
+    ),
+    example(
+        "refresh",
+        "Refresh and keep today's benchmarks as a snapshot",
+        r#"refresh --snapshot"#,
+    ),
+];
+
+/// Examples for one command.
+pub fn for_command(command: &str) -> Vec<&'static Example> {
+    EXAMPLES.iter().filter(|e| e.command == command).collect()
+}
+
+/// Commands that have examples, in help order.
+pub fn commands() -> Vec<&'static str> {
+    let mut commands: Vec<&'static str> = Vec::new();
+    for example in EXAMPLES {
+        if !commands.contains(&example.command) {
+            commands.push(example.command);
+        }
+    }
+    commands
+}
+
+/// Render examples as indented `# description` / command line pairs.
+fn render(examples: &[&Example]) -> String {
+    examples
+        .iter()
+        .map(|e| format!("  # {}\n  {}", e.description, invocation(e.args)))
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
+/// "Examples:" section for a command's `--help`, if it has any.
+pub fn help_text(command: &str) -> Option<String> {
+    let examples = for_command(command);
+    (!examples.is_empty()).then(|| format!("Examples:\n{}", render(&examples)))
+}
+
+/// Run the examples command, for one command or all of them.
+pub fn run(command: Option<&str>, format: OutputFormat) -> Result<()> {
+    let examples: Vec<&Example> = match command {
+        Some(command) => {
+            let examples = for_command(command);
+            if examples.is_empty() {
+                return Err(AppError::NotFound(format!(
+                    "No examples for '{}'. Commands with examples: {}",
+                    command,
+                    commands().join(", ")
+                )));
+            }
+            examples
+        }
+        None => EXAMPLES.iter().collect(),
+    };
+
+    if format == OutputFormat::Json {
+        let json: Vec<_> = examples
+            .iter()
+            .map(|e| {
+                json!({
+                    "command": e.command,
+                    "description": e.description,
+                    "invocation": invocation(e.args),
+                })
+            })
+            .collect();
+        println!("{}", format_json(&json));
+        return Ok(());
+    }
+
+    let sections: Vec<String> = commands()
+        .into_iter()
+        .filter(|c| command.map_or(true, |command| command == *c))
+        .map(|c| format!("{}:\n{}", c, render(&for_command(c))))
+        .collect();
+    println!("{}", sections.join("\n\n"));
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::Cli;
+    use crate::commands::batch::split_args;
+    use clap::Parser;
+
+    #[test]
+    fn test_examples_parse() {
+        for example in EXAMPLES {
+            let args = split_args(example.args).unwrap();
+            assert_eq!(args[0], example.command, "{}", example.args);
+            if cfg!(not(feature = "sql")) && example.command == "query" {
+                continue;
+            }
+            let argv = std::iter::once("which-llm".to_string()).chain(args);
+            if let Err(e) = Cli::try_parse_from(argv) {
+                panic!("example '{}' does not parse: {}", example.args, e);
+            }
+        }
+    }
+
+    #[test]
+    fn test_help_text() {
+        let help = help_text("cost").unwrap();
+        assert!(help.starts_with("Examples:\n  # Cost of one request\n  which-llm cost"));
+        assert!(help_text("profile").is_none());
+    }
+}
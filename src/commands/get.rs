@@ -0,0 +1,328 @@
+//! Get command - print a single model value for scripts.
+//!
+//! Resolves one model and prints either one raw field value or a plain
+//! `field<TAB>value` summary, with no table formatting or attribution.
+//! Local tags and notes (see `note` and `tag`) are included as the `tags`
+//! and `notes` fields. `--integration` prints provider SDK setup instead.
+
+use crate::error::{AppError, Result};
+use crate::models::LlmModel;
+use crate::notes::{ModelNotes, Notes};
+use crate::sources::models_dev::integration::{preferred, Integration};
+use crate::utils::{filter_models_by_name, no_models_found_error};
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+
+/// Maximum number of candidates listed for an ambiguous search.
+const MAX_CANDIDATES: usize = 5;
+
+/// Resolve a search term to exactly one model.
+///
+/// An exact slug or name match (case-insensitive) wins; otherwise the
+/// substring search must match a single model.
+pub fn resolve_model<'a>(models: &'a [LlmModel], search: &str) -> Result<&'a LlmModel> {
+    let exact = models.iter().find(|m| m.slug.eq_ignore_ascii_case(search));
+    let exact = exact.or_else(|| models.iter().find(|m| m.name.eq_ignore_ascii_case(search)));
+    if let Some(model) = exact {
+        return Ok(model);
+    }
+
+    let matches = filter_models_by_name(models, search);
+    match matches.as_slice() {
+        [] => Err(no_models_found_error(models, &[search.to_string()])),
+        [model] => Ok(model),
+        _ => {
+            let mut message = format!(
+                "'{}' matches {} models. Use an exact slug:",
+                search,
+                matches.len()
+            );
+            for model in matches.iter().take(MAX_CANDIDATES) {
+                message.push_str(&format!("\n  {} ({})", model.name, model.slug));
+            }
+            Err(AppError::Config(message))
+        }
+    }
+}
+
+/// Model fields keyed by their JSON (and `benchmarks` column) names, plus
+/// the computed value-for-money metrics, benchmark coverage, family,
+/// provider SDK hints, and the local tags (comma-separated) and notes
+/// (`; `-separated).
+fn model_fields(model: &LlmModel, notes: Option<&ModelNotes>) -> Result<Map<String, Value>> {
+    let Value::Object(mut map) = serde_json::to_value(model)? else {
+        unreachable!("LlmModel serializes to an object");
+    };
+    map.insert(
+        "intelligence_per_dollar".into(),
+        model.intelligence_per_dollar().into(),
+    );
+    map.insert("coding_per_dollar".into(), model.coding_per_dollar().into());
+    map.insert("coverage".into(), model.coverage_label().into());
+    map.insert("family".into(), model.family().into());
+    // Joined from an optional table; always listed so a missing value is NotFound
+    map.insert("npm_package".into(), model.npm_package.clone().into());
+    map.insert("env_vars".into(), model.env_vars.clone().into());
+    let tags = notes.filter(|n| !n.tags.is_empty()).map(|n| {
+        n.tags
+            .iter()
+            .map(String::as_str)
+            .collect::<Vec<_>>()
+            .join(",")
+    });
+    map.insert("tags".into(), tags.into());
+    let notes = notes
+        .filter(|n| !n.notes.is_empty())
+        .map(|n| n.notes.join("; "));
+    map.insert("notes".into(), notes.into());
+    Ok(map)
+}
+
+/// Render a JSON value without quotes around strings.
+fn raw_value(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+/// Look up one field of a model as a raw string.
+///
+/// Unknown fields are a usage error; a known field with no value is
+/// `AppError::NotFound` so scripts can tell it apart by exit code.
+fn field_value(model: &LlmModel, notes: Option<&ModelNotes>, field: &str) -> Result<String> {
+    let fields = model_fields(model, notes)?;
+    let key = field.trim().to_lowercase();
+    match fields.get(&key) {
+        Some(Value::Null) => Err(AppError::NotFound(format!(
+            "{} has no value for '{}'",
+            model.slug, key
+        ))),
+        Some(value) => Ok(raw_value(value)),
+        None => {
+            let valid: Vec<&str> = fields.keys().map(String::as_str).collect();
+            Err(AppError::Config(format!(
+                "Unknown field '{}'. Available fields: {}",
+                key,
+                valid.join(", ")
+            )))
+        }
+    }
+}
+
+/// Render every non-empty field as `field<TAB>value` lines.
+fn summary(model: &LlmModel, notes: Option<&ModelNotes>) -> Result<String> {
+    let lines: Vec<String> = model_fields(model, notes)?
+        .iter()
+        .filter(|(_, value)| !value.is_null())
+        .map(|(key, value)| format!("{}\t{}", key, raw_value(value)))
+        .collect();
+    Ok(lines.join("\n"))
+}
+
+/// Render every variant in the model's family as `slug<TAB>name` lines,
+/// highest intelligence first (unscored variants last).
+fn family_variants(models: &[LlmModel], model: &LlmModel) -> String {
+    let mut variants: Vec<&LlmModel> = models
+        .iter()
+        .filter(|m| m.family() == model.family())
+        .collect();
+    variants.sort_by(|a, b| {
+        let rank = |m: &LlmModel| m.intelligence.unwrap_or(f64::NEG_INFINITY);
+        rank(b).total_cmp(&rank(a))
+    });
+    variants
+        .iter()
+        .map(|m| format!("{}\t{}", m.slug, m.name))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Render ready-to-copy env setup and SDK install commands for every
+/// provider of `model`, the suggested provider first.
+fn integration_section(model: &LlmModel, providers: &[Integration]) -> Result<String> {
+    let Some(suggested) = preferred(model, providers) else {
+        return Err(AppError::NotFound(format!(
+            "No provider SDK or env var hints for {} in the models table",
+            model.slug
+        )));
+    };
+    let ordered = std::iter::once(suggested).chain(
+        providers
+            .iter()
+            .filter(|p| p.provider_id != suggested.provider_id),
+    );
+    let sections: Vec<String> = ordered
+        .map(|p| {
+            let mut lines = vec![format!("# {} ({})", p.provider_name, p.provider_id)];
+            lines.extend(p.setup_commands());
+            lines.join("\n")
+        })
+        .collect();
+    Ok(sections.join("\n\n"))
+}
+
+/// Run the get command.
+///
+/// With `family`, lists the variants of the model's family instead of its
+/// fields. With `integrations` (provider hints by lowercased slug), prints
+/// the model's integration section instead.
+pub fn run(
+    models: &[LlmModel],
+    notes: &Notes,
+    search: &str,
+    field: Option<&str>,
+    family: bool,
+    integrations: Option<&HashMap<String, Vec<Integration>>>,
+) -> Result<()> {
+    let model = resolve_model(models, search)?;
+    if let Some(integrations) = integrations {
+        let providers = integrations
+            .get(&model.slug.to_lowercase())
+            .map(Vec::as_slice)
+            .unwrap_or_default();
+        println!("{}", integration_section(model, providers)?);
+        return Ok(());
+    }
+    let notes = notes.get(&model.slug);
+    match field {
+        _ if family => println!("{}", family_variants(models, model)),
+        Some(field) => println!("{}", field_value(model, notes, field)?),
+        None => println!("{}", summary(model, notes)?),
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn models() -> Vec<LlmModel> {
+        vec![
+            LlmModel {
+                id: "1".into(),
+                name: "GPT-4o".into(),
+                slug: "gpt-4o".into(),
+                creator: "OpenAI".into(),
+                input_price: Some(2.5),
+                ..Default::default()
+            },
+            LlmModel {
+                id: "2".into(),
+                name: "GPT-4o mini".into(),
+                slug: "gpt-4o-mini".into(),
+                creator: "OpenAI".into(),
+                ..Default::default()
+            },
+        ]
+    }
+
+    #[test]
+    fn test_resolve_prefers_exact_slug() {
+        let models = models();
+        assert_eq!(resolve_model(&models, "GPT-4o").unwrap().id, "1");
+        assert_eq!(resolve_model(&models, "mini").unwrap().id, "2");
+    }
+
+    #[test]
+    fn test_resolve_errors() {
+        let models = models();
+        let err = resolve_model(&models, "gpt").unwrap_err();
+        assert_eq!(err.exit_code(), 2);
+        assert!(err.to_string().contains("matches 2 models"));
+
+        let err = resolve_model(&models, "claude").unwrap_err();
+        assert_eq!(err.exit_code(), 5);
+    }
+
+    #[test]
+    fn test_field_value() {
+        let models = models();
+        assert_eq!(field_value(&models[0], None, "input_price").unwrap(), "2.5");
+        assert_eq!(field_value(&models[0], None, "Creator").unwrap(), "OpenAI");
+        assert!(field_value(&models[0], None, "intelligence_per_dollar").is_err());
+
+        let err = field_value(&models[1], None, "input_price").unwrap_err();
+        assert_eq!(err.exit_code(), 5);
+
+        let err = field_value(&models[0], None, "context_window").unwrap_err();
+        assert_eq!(err.exit_code(), 2);
+        assert!(err.to_string().contains("Available fields:"));
+    }
+
+    #[test]
+    fn test_family_variants() {
+        let variant = |slug: &str, intelligence: Option<f64>| LlmModel {
+            name: slug.to_uppercase(),
+            slug: slug.into(),
+            intelligence,
+            ..Default::default()
+        };
+        let models = vec![
+            variant("gpt-5-low", Some(60.0)),
+            variant("gpt-5-minimal", None),
+            variant("gpt-5", Some(68.0)),
+            variant("gpt-5-mini", Some(61.0)),
+        ];
+        assert_eq!(
+            family_variants(&models, &models[1]),
+            "gpt-5\tGPT-5\ngpt-5-low\tGPT-5-LOW\ngpt-5-minimal\tGPT-5-MINIMAL"
+        );
+    }
+
+    #[test]
+    fn test_integration_section() {
+        let provider = |id: &str, npm: Option<&str>, env: &[&str]| Integration {
+            provider_id: id.into(),
+            provider_name: id.to_uppercase(),
+            npm_package: npm.map(String::from),
+            env_vars: env.iter().map(|v| v.to_string()).collect(),
+        };
+        let providers = vec![
+            provider("azure", Some("@ai-sdk/azure"), &["AZURE_API_KEY"]),
+            provider("openai", Some("@ai-sdk/openai"), &["OPENAI_API_KEY"]),
+        ];
+        let model = LlmModel {
+            slug: "gpt-4o".into(),
+            creator: "OpenAI".into(),
+            ..Default::default()
+        };
+        assert_eq!(
+            integration_section(&model, &providers).unwrap(),
+            "# OPENAI (openai)\nexport OPENAI_API_KEY=...\nnpm install ai @ai-sdk/openai\n\n\
+             # AZURE (azure)\nexport AZURE_API_KEY=...\nnpm install ai @ai-sdk/azure"
+        );
+
+        let err = integration_section(&model, &[]).unwrap_err();
+        assert_eq!(err.exit_code(), 5);
+    }
+
+    #[test]
+    fn test_summary_skips_missing_values() {
+        let text = summary(&models()[0], None).unwrap();
+        assert!(text.contains("slug\tgpt-4o"));
+        assert!(text.contains("input_price\t2.5"));
+        assert!(!text.contains("tps"));
+    }
+
+    #[test]
+    fn test_notes_fields() {
+        let models = models();
+        let err = field_value(&models[0], None, "tags").unwrap_err();
+        assert_eq!(err.exit_code(), 5);
+
+        let mut notes = Notes::default();
+        notes.add_note("gpt-4o", "approved for prod");
+        notes.add_note("gpt-4o", "EU region only");
+        notes
+            .add_tags("gpt-4o", &["prod".into(), "cheap-tier".into()])
+            .unwrap();
+        let entry = notes.get("gpt-4o");
+        assert_eq!(
+            field_value(&models[0], entry, "tags").unwrap(),
+            "cheap-tier,prod"
+        );
+        let text = summary(&models[0], entry).unwrap();
+        assert!(text.contains("notes\tapproved for prod; EU region only"));
+    }
+}
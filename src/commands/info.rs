@@ -1,25 +1,133 @@
 //! Info command - shows data source and attribution information.
 
 use crate::cache::Cache;
+use crate::cli::{bin_name, invocation};
+use crate::commands::stats::{self, DataSummary};
 use crate::error::Result;
-use crate::remote::RemoteDataClient;
+use crate::output::json::format_json;
+use crate::output::{OutputFormat, ATTRIBUTION};
+use crate::remote::{FileStatus, RemoteDataClient};
+use serde_json::{json, Value};
 
-/// Attribution text.
-const ATTRIBUTION: &str = "Data provided by Artificial Analysis (https://artificialanalysis.ai)";
-const MODELS_DEV_ATTRIBUTION: &str = "Capability data from models.dev (https://models.dev)";
 const METHODOLOGY_URL: &str = "https://artificialanalysis.ai/methodology";
 
+/// Label for a downloaded file's integrity.
+fn status_label(status: FileStatus) -> &'static str {
+    match status {
+        FileStatus::Ok => "verified",
+        FileStatus::Modified => "CHECKSUM MISMATCH",
+        FileStatus::Missing => "missing",
+    }
+}
+
+/// Render the info output as one JSON document.
+fn info_json(
+    cache: &Cache,
+    remote: &RemoteDataClient,
+    summary: Option<&DataSummary>,
+) -> Result<Value> {
+    let source = if let Some(meta) = remote.local_meta().filter(|m| !m.files.is_empty()) {
+        let files: Vec<Value> = remote
+            .verify_local_files()
+            .into_iter()
+            .map(|(name, status)| {
+                json!({
+                    "name": name,
+                    "size": meta.files.get(&name).map(|f| f.size).unwrap_or_default(),
+                    "status": status_label(status),
+                })
+            })
+            .collect();
+        json!({
+            "type": "hosted",
+            "source_url": meta.source_url,
+            "channel": meta.channel,
+            "revision": meta.revision,
+            "generated_at": meta.generated_at,
+            "fetched_at": meta.fetched_at,
+            "files": files,
+        })
+    } else if let Some(manifest) = remote.get_local_manifest() {
+        json!({
+            "type": "hosted",
+            "generated_at": manifest.generated_at,
+            "version": manifest.version,
+        })
+    } else if remote.is_data_fresh() {
+        json!({ "type": "cached" })
+    } else {
+        json!({ "type": "none" })
+    };
+
+    let stats = cache.stats()?;
+    Ok(json!({
+        "version": env!("CARGO_PKG_VERSION"),
+        "channel": remote.channel(),
+        "source": source,
+        "data": summary.map(DataSummary::to_json),
+        "cache": {
+            "location": stats.location,
+            "files": stats.entry_count,
+            "size": stats.total_size,
+        },
+        "attribution": ATTRIBUTION,
+        "methodology": METHODOLOGY_URL,
+    }))
+}
+
 /// Run the info command.
-pub fn run() -> Result<()> {
+pub fn run(format: OutputFormat) -> Result<()> {
     let cache = Cache::new()?;
     let remote = RemoteDataClient::new(cache.base_dir().to_path_buf())?;
+    // A damaged cache is reported below rather than failing `info`
+    let summary = stats::summarize(cache.base_dir()).ok().flatten();
+
+    if format == OutputFormat::Json {
+        let json = info_json(&cache, &remote, summary.as_ref())?;
+        println!("{}", format_json(&json));
+        return Ok(());
+    }
 
-    println!("which-llm v{}", env!("CARGO_PKG_VERSION"));
+    println!("{} v{}", bin_name(), env!("CARGO_PKG_VERSION"));
     println!();
 
     // Show data source status
     println!("Data Source:");
-    if let Some(manifest) = remote.get_local_manifest() {
+    if let Some(meta) = remote.local_meta().filter(|m| !m.files.is_empty()) {
+        println!("  Type: Hosted (GitHub Releases)");
+        if let Some(url) = &meta.source_url {
+            println!("  Source: {}", url);
+        }
+        println!("  Channel: {}", meta.channel);
+        if meta.channel != remote.channel() {
+            println!(
+                "  Selected channel: {} (run '{}' to switch)",
+                remote.channel(),
+                invocation("refresh")
+            );
+        }
+        if let Some(revision) = &meta.revision {
+            println!("  Revision: {}", revision);
+        }
+        if let Some(generated_at) = &meta.generated_at {
+            println!("  Generated: {}", generated_at);
+        }
+        println!("  Fetched: {}", meta.fetched_at);
+        println!();
+        println!("  Files:");
+        let mut damaged = false;
+        for (name, status) in remote.verify_local_files() {
+            let size = meta.files.get(&name).map(|f| f.size).unwrap_or_default();
+            let label = status_label(status);
+            damaged |= status == FileStatus::Modified;
+            println!("    {} ({} bytes, {})", name, size, label);
+        }
+        if damaged {
+            println!();
+            println!("  Warning: cached files differ from the published checksums.");
+            println!("  Run '{}' to download them again.", invocation("refresh"));
+        }
+    } else if let Some(manifest) = remote.get_local_manifest() {
         println!("  Type: Hosted (GitHub Releases)");
         println!("  Generated: {}", manifest.generated_at);
         println!("  Version: {}", manifest.version);
@@ -33,7 +141,13 @@ pub fn run() -> Result<()> {
         println!("  Status: Fresh (within 24h TTL)");
     } else {
         println!("  Type: Not yet fetched");
-        println!("  Run 'which-llm refresh' to fetch data");
+        println!("  Channel: {}", remote.channel());
+        println!("  Run '{}' to fetch data", invocation("refresh"));
+    }
+
+    if let Some(summary) = &summary {
+        println!();
+        println!("Data: {}", summary);
     }
 
     println!();
@@ -50,8 +164,9 @@ pub fn run() -> Result<()> {
 
     // Show attribution
     println!("Attribution:");
-    println!("  {}", ATTRIBUTION);
-    println!("  {}", MODELS_DEV_ATTRIBUTION);
+    for line in ATTRIBUTION {
+        println!("  {}", line);
+    }
     println!();
     println!("Methodology: {}", METHODOLOGY_URL);
     println!();
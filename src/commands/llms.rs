@@ -0,0 +1,709 @@
+//! LLMs command - list models with their key metrics, filtered by flags.
+//!
+//! The common filters (score, price, speed and latency bounds, capabilities)
+//! are flags, so everyday lookups need no SQL. Every filter must hold. Bounds
+//! on a metric drop models without a value for it, and capabilities come
+//! from the models table (models.dev), as for `recommend`.
+//!
+//! The list is most intelligent first. `--sort` orders it by any numeric
+//! field, the release date or the context window, `--score-profile` by a
+//! composite score, and `--fields` picks the columns by the same keys as
+//! `compare --fields`. `--collapse-variants` and `--top-per` then thin it to
+//! one row per family or a few per group, as `compare` and `open` do.
+
+use super::compare::{collapse_variants, FieldColumns};
+use super::open::TopPer;
+use crate::error::{AppError, Result};
+use crate::models::{LlmModel, Percentile};
+use crate::output::dates;
+use crate::output::{format_output, note_hidden, Formattable, OutputFormat};
+use crate::query::{format_query_result, QueryResult};
+use crate::sources::models_dev::capabilities::{
+    capable_models, load_token_limits, validate_needs, TokenLimits,
+};
+use crate::utils::{
+    sort_field, sort_field_names, text_field, text_field_names, within, ScoreWeights, SortField,
+};
+use chrono::{NaiveDate, Utc};
+use serde::Serialize;
+use serde_json::{Map, Value};
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+use tabled::Tabled;
+
+/// Filters for the list; unset ones are not applied.
+#[derive(Debug, Clone, Default)]
+pub struct LlmFilters {
+    /// Minimum intelligence index.
+    pub min_intelligence: Option<f64>,
+    /// Maximum blended price in USD per million tokens.
+    pub max_price: Option<f64>,
+    /// Minimum output tokens per second.
+    pub min_tps: Option<f64>,
+    /// Maximum time to first token in seconds.
+    pub max_latency: Option<f64>,
+    /// Only models released on or after this date (`--new-only`).
+    pub released_after: Option<NaiveDate>,
+    /// Capabilities every model must have (columns of the `models` table).
+    pub needs: Vec<String>,
+}
+
+impl LlmFilters {
+    /// Whether `model` meets every bound.
+    fn matches(&self, model: &LlmModel) -> bool {
+        within(model.intelligence, self.min_intelligence, |v, min| v >= min)
+            && within(model.price, self.max_price, |v, max| v <= max)
+            && within(model.tps, self.min_tps, |v, min| v >= min)
+            && within(model.latency, self.max_latency, |v, max| v <= max)
+            && self
+                .released_after
+                .map_or(true, |after| model.release_date.is_some_and(|d| d >= after))
+    }
+
+    /// Keep the models that pass every filter, most intelligent first.
+    ///
+    /// `capable` holds the keys of models with every required capability;
+    /// `None` when none is required.
+    pub fn apply(&self, models: Vec<LlmModel>, capable: Option<&HashSet<String>>) -> Vec<LlmModel> {
+        let mut models: Vec<LlmModel> = models
+            .into_iter()
+            .filter(|m| self.matches(m))
+            .filter(|m| capable.map_or(true, |keys| keys.contains(&m.slug.to_lowercase())))
+            .collect();
+        models.sort_by(|a, b| {
+            b.intelligence
+                .unwrap_or(f64::NEG_INFINITY)
+                .total_cmp(&a.intelligence.unwrap_or(f64::NEG_INFINITY))
+                .then_with(|| a.slug.cmp(&b.slug))
+        });
+        models
+    }
+}
+
+/// How the filtered list is ordered and shown.
+#[derive(Debug, Clone, Default)]
+pub struct LlmView {
+    /// Field to sort by (a numeric `--where` field, `release_date` or
+    /// `context_window`), descending unless `ascending`; models without a
+    /// value sort last.
+    pub sort: Option<String>,
+    /// Sort ascending instead of descending.
+    pub ascending: bool,
+    /// Score every listed model, ranking by score unless `sort` is set.
+    pub score: Option<ScoreWeights>,
+    /// Columns to show instead of the default ones (`compare --fields` keys).
+    pub fields: Option<Vec<String>>,
+    /// Show one row per family: its best variant, with a variant summary.
+    pub collapse_variants: bool,
+    /// Keep the first models of each group (a text field), in list order.
+    pub top_per: Option<TopPer>,
+    /// Filter, sort and show speed and latency at this percentile instead of
+    /// the median.
+    pub percentile: Option<Percentile>,
+    /// Add an age column ("3 mo") computed from the release date.
+    pub age: bool,
+    /// Maximum number of models to show; `None` shows every match.
+    pub limit: Option<usize>,
+}
+
+/// Replace each model's median speed and latency with `percentile`, or
+/// nothing where it is not published, as `compare --percentile` shows them.
+fn at_percentile(models: &mut [LlmModel], percentile: Percentile) {
+    for model in models {
+        model.tps = model.tps_percentiles.and_then(|p| p.get(percentile));
+        model.latency = model.latency_percentiles.and_then(|p| p.get(percentile));
+    }
+}
+
+/// A model in the final list, with the summary of its family when variants
+/// are collapsed.
+struct Listed {
+    model: LlmModel,
+    /// Number of the family's variants that matched.
+    variants: Option<usize>,
+    /// The family summary, e.g. `3 (cheapest: GPT-5 mini $0.69)`.
+    variant_summary: Option<String>,
+    /// Slug of the family's cheapest variant.
+    cheapest_variant: Option<String>,
+}
+
+impl From<LlmModel> for Listed {
+    fn from(model: LlmModel) -> Self {
+        Self {
+            model,
+            variants: None,
+            variant_summary: None,
+            cheapest_variant: None,
+        }
+    }
+}
+
+/// Keep each family's best variant, summarizing the rest.
+fn collapse(models: Vec<LlmModel>) -> Vec<Listed> {
+    let refs: Vec<&LlmModel> = models.iter().collect();
+    collapse_variants(&refs)
+        .into_iter()
+        .map(|family| Listed {
+            model: family.best.clone(),
+            variants: Some(family.variants),
+            variant_summary: Some(family.summary()),
+            cheapest_variant: family.cheapest.map(|(m, _)| m.slug.clone()),
+        })
+        .collect()
+}
+
+/// Keep the first `top_per.count` models of each value of the
+/// `top_per.column` text field, in list order.
+fn keep_top_per(listed: &mut Vec<Listed>, group: fn(&LlmModel) -> Option<&str>, count: usize) {
+    let mut seen: HashMap<String, usize> = HashMap::new();
+    listed.retain(|l| {
+        let key = group(&l.model).unwrap_or_default().to_lowercase();
+        let kept = seen.entry(key).or_default();
+        *kept += 1;
+        *kept <= count
+    });
+}
+
+/// Order `models` by `field`, missing values last either way.
+///
+/// `limits` holds the context windows, read when sorting by them.
+fn sort_by_field(
+    models: &mut [LlmModel],
+    field: SortField,
+    limits: &HashMap<String, TokenLimits>,
+    ascending: bool,
+) {
+    match field {
+        SortField::Number(get) => sort_by_value(models, get, f64::total_cmp, ascending),
+        SortField::ReleaseDate => {
+            sort_by_value(models, |m| m.release_date, NaiveDate::cmp, ascending)
+        }
+        SortField::ContextWindow => sort_by_value(
+            models,
+            |m| {
+                limits
+                    .get(&m.slug.to_lowercase())
+                    .and_then(|l| l.context_window)
+            },
+            u64::cmp,
+            ascending,
+        ),
+    }
+}
+
+/// Order `models` by the value `get` reads, missing values last.
+fn sort_by_value<T>(
+    models: &mut [LlmModel],
+    get: impl Fn(&LlmModel) -> Option<T>,
+    compare: fn(&T, &T) -> Ordering,
+    ascending: bool,
+) {
+    models.sort_by(|a, b| match (get(a), get(b)) {
+        (Some(x), Some(y)) if ascending => compare(&x, &y),
+        (Some(x), Some(y)) => compare(&y, &x),
+        (Some(_), None) => Ordering::Less,
+        (None, Some(_)) => Ordering::Greater,
+        (None, None) => Ordering::Equal,
+    });
+}
+
+/// A listed model with the optional columns, for JSON output.
+#[derive(Debug, Serialize)]
+struct ListedModel<'a> {
+    #[serde(flatten)]
+    model: &'a LlmModel,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    variants: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    cheapest_variant: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    age_days: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    score: Option<f64>,
+}
+
+/// LLM row for table output.
+#[derive(Debug, Clone, Serialize, Tabled)]
+pub struct LlmRow {
+    #[tabled(rename = "Model")]
+    pub name: String,
+    #[tabled(rename = "Creator")]
+    pub creator: String,
+    #[tabled(rename = "Intelligence")]
+    pub intelligence: String,
+    #[tabled(rename = "Price $/M")]
+    pub price: String,
+    #[tabled(rename = "TPS")]
+    pub tps: String,
+    #[tabled(rename = "Latency (s)")]
+    pub latency: String,
+    #[tabled(rename = "Released")]
+    pub release_date: String,
+}
+
+impl Formattable for LlmRow {
+    fn headers() -> &'static [&'static str] {
+        &[
+            "Model",
+            "Creator",
+            "Intelligence",
+            "Price $/M",
+            "TPS",
+            "Latency (s)",
+            "Released",
+        ]
+    }
+
+    fn to_row(&self) -> Vec<String> {
+        vec![
+            self.name.clone(),
+            self.creator.clone(),
+            self.intelligence.clone(),
+            self.price.clone(),
+            self.tps.clone(),
+            self.latency.clone(),
+            self.release_date.clone(),
+        ]
+    }
+}
+
+impl From<&LlmModel> for LlmRow {
+    fn from(m: &LlmModel) -> Self {
+        let number = |v: Option<f64>, format: fn(f64) -> String| v.map_or("-".into(), format);
+        Self {
+            name: m.name.clone(),
+            creator: m.creator.clone(),
+            intelligence: number(m.intelligence, |v| format!("{:.1}", v)),
+            price: number(m.price, |v| format!("${:.2}", v)),
+            tps: number(m.tps, |v| format!("{:.0}", v)),
+            latency: number(m.latency, |v| format!("{:.2}", v)),
+            release_date: m.release_date.map_or("-".into(), dates::display),
+        }
+    }
+}
+
+/// Run the llms command, reading capabilities from the models table in
+/// `cache_dir` when any is required.
+///
+/// When `view.limit` hides rows, a note on stderr says how many there are,
+/// unless `quiet`. JSON output keeps numbers as numbers, also for `--fields`.
+///
+/// # Errors
+///
+/// Returns `AppError::Config` for an unknown capability, sort field,
+/// `--top-per` column or column, and `AppError::Empty` when nothing matches
+/// and `fail_on_empty` is set.
+pub fn run(
+    mut models: Vec<LlmModel>,
+    cache_dir: &Path,
+    filters: &LlmFilters,
+    view: &LlmView,
+    format: OutputFormat,
+    fail_on_empty: bool,
+    quiet: bool,
+) -> Result<()> {
+    validate_needs(&filters.needs)?;
+    let sort = view
+        .sort
+        .as_deref()
+        .map(|name| {
+            sort_field(name).ok_or_else(|| {
+                AppError::Config(format!(
+                    "Unknown sort field '{}'. Available fields: {}",
+                    name,
+                    sort_field_names().join(", ")
+                ))
+            })
+        })
+        .transpose()?;
+    let group = view
+        .top_per
+        .as_ref()
+        .map(|top_per| {
+            text_field(&top_per.column).ok_or_else(|| {
+                AppError::Config(format!(
+                    "Unknown --top-per column '{}'. Available columns: {}",
+                    top_per.column,
+                    text_field_names().join(", ")
+                ))
+            })
+        })
+        .transpose()?;
+    let columns = view
+        .fields
+        .as_deref()
+        .map(FieldColumns::select)
+        .transpose()?
+        .map(|columns| match view.percentile {
+            Some(percentile) => columns.at_percentile(percentile),
+            None => columns,
+        });
+
+    let capable = if filters.needs.is_empty() {
+        None
+    } else {
+        Some(capable_models(cache_dir, &filters.needs)?)
+    };
+    let limits = match sort {
+        Some(SortField::ContextWindow) => load_token_limits(cache_dir)?,
+        _ => HashMap::new(),
+    };
+    if let Some(percentile) = view.percentile {
+        at_percentile(&mut models, percentile);
+    }
+    let mut models = filters.apply(models, capable.as_ref());
+    let scorer = view.score.as_ref().map(|weights| weights.scorer(&models));
+    match (sort, &scorer) {
+        (Some(field), _) => sort_by_field(&mut models, field, &limits, view.ascending),
+        (None, Some(scorer)) => {
+            models.sort_by(|a, b| scorer.score(b).total_cmp(&scorer.score(a)));
+        }
+        (None, None) => {}
+    }
+    let mut listed: Vec<Listed> = if view.collapse_variants {
+        collapse(models)
+    } else {
+        models.into_iter().map(Listed::from).collect()
+    };
+    if let (Some(group), Some(top_per)) = (group, &view.top_per) {
+        keep_top_per(&mut listed, group, top_per.count);
+    }
+    let total = listed.len();
+    if let Some(limit) = view.limit {
+        listed.truncate(limit);
+    }
+
+    let today = Utc::now().date_naive();
+    // Release date when the age is shown
+    let aged = |m: &LlmModel| m.release_date.filter(|_| view.age);
+    let score = |m: &LlmModel| scorer.as_ref().map(|s| s.score(m));
+    let plain = columns.is_none()
+        && scorer.is_none()
+        && !view.collapse_variants
+        && !view.age
+        && view.percentile.is_none();
+    if format == OutputFormat::Json {
+        let json = match &columns {
+            None => {
+                let rows: Vec<ListedModel> = listed
+                    .iter()
+                    .map(|l| ListedModel {
+                        model: &l.model,
+                        variants: l.variants,
+                        cheapest_variant: l.cheapest_variant.as_deref(),
+                        age_days: aged(&l.model).map(|d| (today - d).num_days()),
+                        score: score(&l.model),
+                    })
+                    .collect();
+                crate::output::json::format_json(&rows)
+            }
+            Some(columns) => {
+                let rows: Vec<Map<String, Value>> = listed
+                    .iter()
+                    .map(|l| {
+                        let m = &l.model;
+                        let mut row = Map::new();
+                        row.insert("name".into(), m.name.clone().into());
+                        row.insert("slug".into(), m.slug.clone().into());
+                        row.extend(columns.json_values(m));
+                        if let Some(variants) = l.variants {
+                            row.insert("variants".into(), variants.into());
+                            row.insert(
+                                "cheapest_variant".into(),
+                                l.cheapest_variant.clone().into(),
+                            );
+                        }
+                        if let Some(date) = aged(m) {
+                            row.insert("age_days".into(), (today - date).num_days().into());
+                        }
+                        if let Some(score) = score(m) {
+                            row.insert("score".into(), score.into());
+                        }
+                        row
+                    })
+                    .collect();
+                crate::output::json::format_json(&rows)
+            }
+        };
+        println!("{}", json);
+    } else if plain {
+        let rows: Vec<LlmRow> = listed.iter().map(|l| LlmRow::from(&l.model)).collect();
+        println!("{}", format_output(&rows, format));
+    } else {
+        let mut header: Vec<String> = match &columns {
+            Some(columns) => std::iter::once("Model")
+                .chain(columns.headers())
+                .map(String::from)
+                .collect(),
+            None => <LlmRow as Formattable>::headers()
+                .iter()
+                .map(|h| match (*h, view.percentile) {
+                    ("TPS", Some(p)) => format!("TPS ({})", p),
+                    ("Latency (s)", Some(p)) => format!("Latency (s, {})", p),
+                    _ => h.to_string(),
+                })
+                .collect(),
+        };
+        if view.collapse_variants {
+            header.push("Variants".to_string());
+        }
+        if view.age {
+            header.push("Age".to_string());
+        }
+        header.extend(
+            view.score
+                .as_ref()
+                .map(|weights| format!("Score ({})", weights.label)),
+        );
+        let rows = listed
+            .iter()
+            .map(|l| {
+                let m = &l.model;
+                let mut row = match &columns {
+                    Some(columns) => {
+                        let mut row = vec![m.name.clone()];
+                        row.extend(columns.values(m));
+                        row
+                    }
+                    None => LlmRow::from(m).to_row(),
+                };
+                row.extend(l.variant_summary.clone());
+                if view.age {
+                    row.push(m.release_date.map_or("-".into(), |d| dates::age(d, today)));
+                }
+                row.extend(score(m).map(|s| format!("{:.1}", s)));
+                row
+            })
+            .collect();
+        let table = QueryResult {
+            columns: header,
+            rows,
+        };
+        println!("{}", format_query_result(&table, format));
+    }
+    if !quiet {
+        note_hidden(listed.len(), total, "--limit");
+    }
+
+    if fail_on_empty && listed.is_empty() {
+        return Err(AppError::Empty("no models match the filters".into()));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::Percentiles;
+
+    fn make_model(slug: &str, intelligence: f64, price: f64, tps: f64, latency: f64) -> LlmModel {
+        LlmModel {
+            tps: Some(tps),
+            latency: Some(latency),
+            ..LlmModel::fixture(slug, intelligence, price)
+        }
+    }
+
+    fn models() -> Vec<LlmModel> {
+        vec![
+            make_model("smart", 70.0, 5.0, 50.0, 2.0),
+            make_model("cheap", 50.0, 0.2, 100.0, 0.5),
+            make_model("fast", 55.0, 1.0, 300.0, 0.3),
+            LlmModel {
+                slug: "unscored".to_string(),
+                ..Default::default()
+            },
+        ]
+    }
+
+    fn slugs(models: &[LlmModel]) -> Vec<&str> {
+        models.iter().map(|m| m.slug.as_str()).collect()
+    }
+
+    #[test]
+    fn test_filters_compose() {
+        let all = LlmFilters::default().apply(models(), None);
+        assert_eq!(slugs(&all), vec!["smart", "fast", "cheap", "unscored"]);
+
+        let filters = LlmFilters {
+            min_intelligence: Some(50.0),
+            max_price: Some(2.0),
+            ..Default::default()
+        };
+        assert_eq!(slugs(&filters.apply(models(), None)), vec!["fast", "cheap"]);
+
+        let filters = LlmFilters {
+            min_tps: Some(80.0),
+            max_latency: Some(0.4),
+            ..Default::default()
+        };
+        assert_eq!(slugs(&filters.apply(models(), None)), vec!["fast"]);
+    }
+
+    #[test]
+    fn test_capability_filter() {
+        let capable: HashSet<String> = ["cheap", "smart"].map(String::from).into();
+        let filters = LlmFilters {
+            max_price: Some(2.0),
+            needs: vec!["tool_call".to_string()],
+            ..Default::default()
+        };
+        assert_eq!(
+            slugs(&filters.apply(models(), Some(&capable))),
+            vec!["cheap"]
+        );
+    }
+
+    #[test]
+    fn test_sort_by_field() {
+        let tps = sort_field("tps").unwrap();
+        let none = HashMap::new();
+        let mut list = models();
+        sort_by_field(&mut list, tps, &none, false);
+        assert_eq!(slugs(&list), vec!["fast", "cheap", "smart", "unscored"]);
+        sort_by_field(&mut list, tps, &none, true);
+        assert_eq!(slugs(&list), vec!["smart", "cheap", "fast", "unscored"]);
+    }
+
+    #[test]
+    fn test_sort_by_release_date_and_context_window() {
+        let mut list = models();
+        for (model, day) in list.iter_mut().zip([3, 1, 2]) {
+            model.release_date = NaiveDate::from_ymd_opt(2025, 1, day);
+        }
+        let none = HashMap::new();
+        let release_date = sort_field("release_date").unwrap();
+        sort_by_field(&mut list, release_date, &none, false);
+        assert_eq!(slugs(&list), vec!["smart", "fast", "cheap", "unscored"]);
+        sort_by_field(&mut list, release_date, &none, true);
+        assert_eq!(slugs(&list), vec!["cheap", "fast", "smart", "unscored"]);
+
+        let limits: HashMap<String, TokenLimits> = [("cheap", 1_000_000), ("smart", 200_000)]
+            .into_iter()
+            .map(|(slug, window)| {
+                let limits = TokenLimits {
+                    context_window: Some(window),
+                    max_output_tokens: None,
+                };
+                (slug.to_string(), limits)
+            })
+            .collect();
+        let context_window = sort_field("Context_Window").unwrap();
+        sort_by_field(&mut list, context_window, &limits, false);
+        assert_eq!(slugs(&list), vec!["cheap", "smart", "fast", "unscored"]);
+    }
+
+    #[test]
+    fn test_released_after() {
+        let mut list = models();
+        list[0].release_date = NaiveDate::from_ymd_opt(2025, 6, 1);
+        list[1].release_date = NaiveDate::from_ymd_opt(2024, 1, 1);
+        let filters = LlmFilters {
+            released_after: NaiveDate::from_ymd_opt(2025, 1, 1),
+            ..Default::default()
+        };
+        // Models without a release date are left out
+        assert_eq!(slugs(&filters.apply(list, None)), vec!["smart"]);
+    }
+
+    #[test]
+    fn test_collapse_and_top_per() {
+        let variant = |slug: &str, creator: &str, intelligence: f64, price: f64| LlmModel {
+            creator: creator.to_string(),
+            ..make_model(slug, intelligence, price, 100.0, 1.0)
+        };
+        let list = vec![
+            variant("gpt-5", "OpenAI", 68.0, 3.4),
+            variant("gpt-5-minimal", "OpenAI", 44.0, 1.0),
+            variant("o3", "OpenAI", 65.0, 3.5),
+            variant("gpt-4o", "OpenAI", 30.0, 4.4),
+            variant("claude-4-5-sonnet", "Anthropic", 63.0, 6.0),
+        ];
+
+        let mut listed = collapse(list.clone());
+        let shown: Vec<(&str, Option<usize>)> = listed
+            .iter()
+            .map(|l| (l.model.slug.as_str(), l.variants))
+            .collect();
+        assert_eq!(
+            shown,
+            vec![
+                ("gpt-5", Some(2)),
+                ("o3", Some(1)),
+                ("gpt-4o", Some(1)),
+                ("claude-4-5-sonnet", Some(1)),
+            ]
+        );
+        assert_eq!(listed[0].cheapest_variant.as_deref(), Some("gpt-5-minimal"));
+
+        let creator = text_field("creator").unwrap();
+        keep_top_per(&mut listed, creator, 2);
+        let kept: Vec<&str> = listed.iter().map(|l| l.model.slug.as_str()).collect();
+        assert_eq!(kept, vec!["gpt-5", "o3", "claude-4-5-sonnet"]);
+    }
+
+    #[test]
+    fn test_at_percentile() {
+        let mut list = models();
+        let mut tps = Percentiles::default();
+        tps.set(Percentile::P95, 20.0);
+        list[0].tps_percentiles = Some(tps);
+        at_percentile(&mut list, Percentile::P95);
+        assert_eq!(list[0].tps, Some(20.0));
+        // No fallback to the median when the percentile is not published
+        assert_eq!(list[1].tps, None);
+        assert_eq!(list[0].latency, None);
+    }
+
+    #[test]
+    fn test_field_columns_json_values_are_typed() {
+        let columns = FieldColumns::select(&["price".to_string(), "creator".to_string()]).unwrap();
+        let values = columns.json_values(&models()[0]);
+        assert_eq!(values["price"], serde_json::json!(5.0));
+        assert_eq!(values["creator"], serde_json::json!("Acme"));
+        let values = columns.json_values(&models()[3]);
+        assert_eq!(values["price"], Value::Null);
+    }
+
+    #[test]
+    fn test_run_rejects_unknown_names() {
+        let dir = tempfile::tempdir().unwrap();
+        let run = |filters: &LlmFilters, view: &LlmView| {
+            run(
+                models(),
+                dir.path(),
+                filters,
+                view,
+                OutputFormat::Json,
+                false,
+                true,
+            )
+        };
+        let needs = LlmFilters {
+            needs: vec!["telepathy".to_string()],
+            ..Default::default()
+        };
+        assert!(matches!(
+            run(&needs, &LlmView::default()),
+            Err(AppError::Config(_))
+        ));
+        for view in [
+            LlmView {
+                sort: Some("vibes".to_string()),
+                ..Default::default()
+            },
+            LlmView {
+                fields: Some(vec!["vibes".to_string()]),
+                ..Default::default()
+            },
+            LlmView {
+                top_per: Some("vibes=1".parse().unwrap()),
+                ..Default::default()
+            },
+        ] {
+            let err = run(&LlmFilters::default(), &view).unwrap_err();
+            assert!(err.to_string().contains("vibes"), "{}", err);
+        }
+    }
+}
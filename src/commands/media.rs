@@ -0,0 +1,771 @@
+//! Media command - leaderboards for image, video, and speech models.
+//!
+//! One command over all cached media tables, selected with `--kind`, with
+//! creator, ELO, and sort options shared across kinds. With a category, models
+//! are ranked by their ELO in that category from the `media_categories` table.
+//! `media show` prints one model with its full category breakdown, and
+//! `media compare` puts two models side by side with their expected
+//! head-to-head win rate.
+
+use crate::cache::Cache;
+use crate::cli::invocation;
+use crate::error::{AppError, Result};
+use crate::output::dates;
+use crate::output::json::format_json;
+use crate::output::porcelain::format_porcelain;
+use crate::output::{note_hidden, OutputFormat};
+use crate::query::{format_query_result, QueryExecutor, QueryResult};
+use crate::schema::{TableDef, MEDIA_CATEGORIES, MEDIA_TABLES};
+use serde_json::{json, Map, Value};
+use std::path::Path;
+
+/// Columns shown for media models, in display order.
+const MEDIA_VIEW_COLUMNS: &[&str] = &["rank", "name", "slug", "creator", "elo", "release_date"];
+
+/// Columns added when ranking within a category.
+const CATEGORY_COLUMNS: &[&str] = &["category", "appearances"];
+
+/// Columns added by `--confidence` on the overall leaderboard.
+const CONFIDENCE_COLUMNS: &[&str] = &["ci95", "appearances"];
+
+/// A category to rank media models in.
+#[derive(Debug, Clone)]
+pub struct CategoryFilter {
+    /// Category name, matched case-insensitively (e.g. "anime").
+    pub name: String,
+    /// Restrict to one category type (`style`, `subject_matter`, `format`).
+    pub category_type: Option<&'static str>,
+}
+
+/// Options for a media leaderboard.
+#[derive(Debug, Clone)]
+pub struct MediaOptions {
+    /// Media table to list.
+    pub kind: &'static TableDef,
+    /// Only models whose creator contains this text (case-insensitive).
+    pub creator: Option<String>,
+    /// Only models with at least this ELO (category ELO with `category`).
+    pub min_elo: Option<f64>,
+    /// Rank by ELO within this category instead of overall.
+    pub category: Option<CategoryFilter>,
+    /// Column to sort by; `None` sorts by ELO.
+    pub sort: Option<String>,
+    /// Sort ascending instead of descending.
+    pub ascending: bool,
+    /// Maximum number of rows; `None` shows every match.
+    pub top: Option<usize>,
+    /// Show the 95% confidence interval and arena appearances.
+    pub confidence: bool,
+}
+
+/// Resolve a `--kind` value (`text-to-image` or `text_to_image`) to its table.
+pub fn media_kind(kind: &str) -> Result<&'static TableDef> {
+    let name = kind.trim().to_lowercase().replace('-', "_");
+    MEDIA_TABLES
+        .iter()
+        .find(|t| t.name == name)
+        .copied()
+        .ok_or_else(|| {
+            let kinds: Vec<String> = MEDIA_TABLES
+                .iter()
+                .map(|t| t.name.replace('_', "-"))
+                .collect();
+            AppError::Config(format!(
+                "Unknown media kind '{}'. Available kinds: {}",
+                kind,
+                kinds.join(", ")
+            ))
+        })
+}
+
+/// Quote a value as a SQL string literal.
+fn sql_string(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "''"))
+}
+
+/// Columns of the leaderboard for the given options.
+///
+/// Category leaderboards already carry per-category appearances, and the
+/// arena publishes no per-category CI95, so `confidence` only widens the
+/// overall leaderboard.
+fn view_columns(options: &MediaOptions) -> Vec<&'static str> {
+    let mut columns = MEDIA_VIEW_COLUMNS.to_vec();
+    if options.category.is_some() {
+        columns.extend(CATEGORY_COLUMNS);
+    } else if options.confidence {
+        columns.extend(CONFIDENCE_COLUMNS);
+    }
+    columns
+}
+
+/// Build the ORDER BY clause, validating the sort column.
+fn media_order_by(options: &MediaOptions) -> Result<String> {
+    let Some(column) = options.sort.as_deref() else {
+        return Ok("elo DESC NULLS LAST, name".to_string());
+    };
+    let valid = view_columns(options);
+    let column = column.trim().to_lowercase();
+    let Some(column) = valid.iter().find(|c| **c == column) else {
+        return Err(AppError::Config(format!(
+            "Unknown sort key '{}'. Valid sort keys: {}",
+            column,
+            valid.join(", ")
+        )));
+    };
+    let direction = if options.ascending { "ASC" } else { "DESC" };
+    Ok(format!("{} {} NULLS LAST, name", column, direction))
+}
+
+/// Build the source rows for a category leaderboard.
+///
+/// `rank` is the model's position in the category across all models, so it
+/// stays meaningful when creator or ELO filters are applied on top.
+fn category_source_sql(table: &TableDef, category: &CategoryFilter) -> String {
+    let type_filter = match category.category_type {
+        Some(category_type) => format!(" AND c.category_type = {}", sql_string(category_type)),
+        None => String::new(),
+    };
+    format!(
+        "(
+    SELECT
+        CAST(rank() OVER (ORDER BY c.elo DESC NULLS LAST) AS INTEGER) AS rank,
+        m.name, m.slug, m.creator, c.elo, m.release_date, c.category, c.appearances
+    FROM {table} m
+    JOIN media_categories c ON c.model_id = m.id
+    WHERE c.media_table = {media_table} AND lower(c.category) = {category}{type_filter}
+) v",
+        table = table.name,
+        media_table = sql_string(table.name),
+        category = sql_string(&category.name.trim().to_lowercase()),
+        type_filter = type_filter
+    )
+}
+
+/// Build the `FROM` and `WHERE` clauses shared by the leaderboard and its
+/// row count.
+fn media_source_sql(options: &MediaOptions) -> String {
+    let mut conditions = Vec::new();
+    if let Some(creator) = &options.creator {
+        conditions.push(format!(
+            "lower(creator) LIKE {}",
+            sql_string(&format!("%{}%", creator.to_lowercase()))
+        ));
+    }
+    if let Some(min_elo) = options.min_elo {
+        conditions.push(format!("elo >= {}", min_elo));
+    }
+    let filter = if conditions.is_empty() {
+        String::new()
+    } else {
+        format!("\nWHERE {}", conditions.join(" AND "))
+    };
+
+    let source = match &options.category {
+        Some(category) => category_source_sql(options.kind, category),
+        None => options.kind.name.to_string(),
+    };
+
+    format!("FROM {}{}", source, filter)
+}
+
+/// Build the SQL for a media leaderboard.
+fn media_sql(options: &MediaOptions) -> Result<String> {
+    let limit = options
+        .top
+        .map(|top| format!("\nLIMIT {}", top))
+        .unwrap_or_default();
+    Ok(format!(
+        "SELECT {}\n{}\nORDER BY {}{}",
+        view_columns(options).join(", "),
+        media_source_sql(options),
+        media_order_by(options)?,
+        limit
+    ))
+}
+
+/// Count the models a leaderboard matches before `top` is applied.
+fn count_media(cache_dir: &Path, options: &MediaOptions) -> Result<usize> {
+    let executor = QueryExecutor::new(cache_dir.to_path_buf());
+    let result = executor.execute(&format!(
+        "SELECT count(*) AS total\n{}",
+        media_source_sql(options)
+    ))?;
+    Ok(result
+        .rows
+        .first()
+        .and_then(|row| row[0].parse().ok())
+        .unwrap_or_default())
+}
+
+/// Query a media leaderboard from the cache in `cache_dir`.
+///
+/// An unknown category is reported with the categories that do exist for
+/// the media kind.
+fn query_media(cache_dir: &Path, options: &MediaOptions) -> Result<QueryResult> {
+    let executor = QueryExecutor::new(cache_dir.to_path_buf());
+    let result = executor.execute(&media_sql(options)?)?;
+
+    if let (Some(category), true) = (&options.category, result.is_empty()) {
+        let known = executor.execute(&format!(
+            "SELECT DISTINCT category FROM media_categories WHERE media_table = {} ORDER BY category",
+            sql_string(options.kind.name)
+        ))?;
+        let exists = known
+            .rows
+            .iter()
+            .any(|row| row[0].eq_ignore_ascii_case(category.name.trim()));
+        if !exists {
+            let names: Vec<&str> = known.rows.iter().map(|row| row[0].as_str()).collect();
+            return Err(AppError::NotFound(format!(
+                "No '{}' category for {}. Available categories: {}",
+                category.name,
+                options.kind.name.replace('_', "-"),
+                names.join(", ")
+            )));
+        }
+    }
+
+    Ok(result)
+}
+
+/// Run the media command.
+///
+/// When `top` hides rows, a note on stderr says how many there are, unless
+/// `quiet`.
+pub fn run(
+    options: &MediaOptions,
+    format: OutputFormat,
+    fail_on_empty: bool,
+    quiet: bool,
+) -> Result<()> {
+    let cache = Cache::new()?;
+    let result = query_media(cache.base_dir(), options)?;
+    println!("{}", format_query_result(&result, format));
+    if !quiet && options.top.is_some_and(|top| result.len() >= top) {
+        let total = count_media(cache.base_dir(), options)?;
+        note_hidden(result.len(), total, "--top");
+    }
+    if fail_on_empty && result.is_empty() {
+        return Err(AppError::Empty(format!(
+            "no {} models matched",
+            options.kind.name.replace('_', "-")
+        )));
+    }
+    Ok(())
+}
+
+/// One media model with its category scores.
+struct MediaDetail {
+    kind: &'static TableDef,
+    /// Model row values keyed by column name.
+    fields: Map<String, Value>,
+    categories: QueryResult,
+}
+
+impl MediaDetail {
+    /// Value of a column, or "-" when missing or empty.
+    fn field(&self, name: &str) -> &str {
+        match self.fields.get(name) {
+            Some(Value::String(s)) if !s.is_empty() => s,
+            _ => "-",
+        }
+    }
+}
+
+/// Find a media model by slug or name across `kinds`, with its categories.
+///
+/// Only cached tables are searched; the first match wins.
+fn find_media_model(
+    cache_dir: &Path,
+    search: &str,
+    kinds: &[&'static TableDef],
+) -> Result<MediaDetail> {
+    let executor = QueryExecutor::new(cache_dir.to_path_buf());
+    let needle = sql_string(&search.trim().to_lowercase());
+
+    for &kind in kinds.iter().filter(|t| t.is_cached(cache_dir)) {
+        let result = executor.execute(&format!(
+            "SELECT * FROM {} WHERE lower(slug) = {} OR lower(name) = {} LIMIT 1",
+            kind.name, needle, needle
+        ))?;
+        let Some(row) = result.rows.first() else {
+            continue;
+        };
+
+        let fields: Map<String, Value> = result
+            .columns
+            .iter()
+            .zip(row)
+            .map(|(column, value)| (column.clone(), Value::String(value.clone())))
+            .collect();
+
+        let categories = if MEDIA_CATEGORIES.is_cached(cache_dir) {
+            let id = fields.get("id").and_then(Value::as_str).unwrap_or_default();
+            executor.execute(&format!(
+                "SELECT category_type, category, elo, appearances FROM media_categories
+WHERE media_table = {} AND model_id = {}
+ORDER BY category_type, elo DESC NULLS LAST",
+                sql_string(kind.name),
+                sql_string(id)
+            ))?
+        } else {
+            QueryResult {
+                columns: vec![],
+                rows: vec![],
+            }
+        };
+
+        return Ok(MediaDetail {
+            kind,
+            fields,
+            categories,
+        });
+    }
+
+    Err(AppError::NotFound(format!(
+        "No media model matching '{}'. Run '{}' to list models.",
+        search,
+        invocation("media --kind <kind>")
+    )))
+}
+
+/// Fields of a media model in porcelain output, in order.
+const DETAIL_FIELDS: &[(&str, &str)] = &[
+    ("Name", "name"),
+    ("Slug", "slug"),
+    ("Creator", "creator"),
+    ("Rank", "rank"),
+    ("ELO", "elo"),
+    ("CI95", "ci95"),
+    ("Appearances", "appearances"),
+    ("Released", "release_date"),
+];
+
+/// Render a media model as a human-readable detail view.
+///
+/// Porcelain output is one `Field`/`Value` line per entry of
+/// [`DETAIL_FIELDS`] after the kind; category scores are left out.
+fn format_detail(detail: &MediaDetail, format: OutputFormat) -> String {
+    if format == OutputFormat::Porcelain {
+        let kind = vec!["Kind".to_string(), detail.kind.name.to_string()];
+        let rows = std::iter::once(kind).chain(
+            DETAIL_FIELDS
+                .iter()
+                .map(|(label, column)| vec![label.to_string(), detail.field(column).to_string()]),
+        );
+        return format_porcelain(&["Field", "Value"], rows);
+    }
+
+    let mut lines = vec![
+        format!(
+            "Model:       {} ({})",
+            detail.field("name"),
+            detail.field("slug")
+        ),
+        format!("Kind:        {}", detail.kind.name.replace('_', "-")),
+        format!("Creator:     {}", detail.field("creator")),
+        format!("Rank:        {}", detail.field("rank")),
+        format!("ELO:         {}", detail.field("elo")),
+        format!("CI95:        {}", detail.field("ci95")),
+        format!("Appearances: {}", detail.field("appearances")),
+        format!(
+            "Released:    {}",
+            dates::display_text(detail.field("release_date"))
+        ),
+    ];
+
+    if !detail.categories.is_empty() {
+        lines.push(String::new());
+        lines.push("Categories:".to_string());
+        lines.push(format_query_result(&detail.categories, format));
+    }
+
+    lines.join("\n")
+}
+
+/// Render a media model as JSON, with categories as an array of objects.
+fn detail_json(detail: &MediaDetail) -> Value {
+    let categories: Vec<Value> = detail
+        .categories
+        .rows
+        .iter()
+        .map(|row| {
+            let object: Map<String, Value> = detail
+                .categories
+                .columns
+                .iter()
+                .zip(row)
+                .map(|(column, value)| (column.clone(), Value::String(value.clone())))
+                .collect();
+            Value::Object(object)
+        })
+        .collect();
+
+    json!({
+        "kind": detail.kind.name,
+        "model": detail.fields,
+        "categories": categories,
+    })
+}
+
+/// Run `media show`: one model's scores and category breakdown.
+///
+/// Searches every media kind unless `kind` is given.
+pub fn show(search: &str, kind: Option<&'static TableDef>, format: OutputFormat) -> Result<()> {
+    let cache = Cache::new()?;
+    let kinds: Vec<&'static TableDef> = match kind {
+        Some(kind) => vec![kind],
+        None => MEDIA_TABLES.to_vec(),
+    };
+    let detail = find_media_model(cache.base_dir(), search, &kinds)?;
+
+    if format == OutputFormat::Json {
+        println!("{}", format_json(&detail_json(&detail)));
+    } else {
+        println!("{}", format_detail(&detail, format));
+    }
+    Ok(())
+}
+
+/// Expected probability that a model rated `elo_a` wins a head-to-head
+/// vote against one rated `elo_b`, using the standard logistic ELO curve.
+pub fn win_probability(elo_a: f64, elo_b: f64) -> f64 {
+    1.0 / (1.0 + 10f64.powf((elo_b - elo_a) / 400.0))
+}
+
+/// Fields shown side by side in `media compare`.
+const COMPARE_FIELDS: &[(&str, &str)] = &[
+    ("Creator", "creator"),
+    ("Rank", "rank"),
+    ("ELO", "elo"),
+    ("CI95", "ci95"),
+    ("Appearances", "appearances"),
+    ("Released", "release_date"),
+];
+
+/// Build the side-by-side comparison of two media models.
+///
+/// The win probability is `None` when either model has no ELO.
+fn compare_details(a: &MediaDetail, b: &MediaDetail) -> Result<(QueryResult, Option<f64>)> {
+    if a.kind.name != b.kind.name {
+        return Err(AppError::Config(format!(
+            "Cannot compare a {} model with a {} model; ELO is only comparable within one kind.",
+            a.kind.name.replace('_', "-"),
+            b.kind.name.replace('_', "-")
+        )));
+    }
+
+    let table = QueryResult {
+        columns: vec![
+            "Field".to_string(),
+            a.field("name").to_string(),
+            b.field("name").to_string(),
+        ],
+        rows: COMPARE_FIELDS
+            .iter()
+            .map(|(label, column)| {
+                vec![
+                    label.to_string(),
+                    a.field(column).to_string(),
+                    b.field(column).to_string(),
+                ]
+            })
+            .collect(),
+    };
+
+    let elo = |d: &MediaDetail| d.field("elo").parse::<f64>().ok();
+    let probability = match (elo(a), elo(b)) {
+        (Some(elo_a), Some(elo_b)) => Some(win_probability(elo_a, elo_b)),
+        _ => None,
+    };
+
+    Ok((table, probability))
+}
+
+/// Run `media compare`: two models side by side with their expected win rate.
+pub fn compare(
+    first: &str,
+    second: &str,
+    kind: Option<&'static TableDef>,
+    format: OutputFormat,
+) -> Result<()> {
+    let cache = Cache::new()?;
+    let kinds: Vec<&'static TableDef> = match kind {
+        Some(kind) => vec![kind],
+        None => MEDIA_TABLES.to_vec(),
+    };
+    let a = find_media_model(cache.base_dir(), first, &kinds)?;
+    let b = find_media_model(cache.base_dir(), second, &kinds)?;
+    let (table, probability) = compare_details(&a, &b)?;
+
+    if format == OutputFormat::Json {
+        let json = json!({
+            "kind": a.kind.name,
+            "models": [a.fields, b.fields],
+            "win_probability": probability,
+        });
+        println!("{}", format_json(&json));
+        return Ok(());
+    }
+
+    println!("{}", format_query_result(&table, format));
+    if let (Some(p), false) = (probability, format == OutputFormat::Porcelain) {
+        println!();
+        println!(
+            "Expected head-to-head: {} wins {:.0}% of votes against {}",
+            a.field("name"),
+            p * 100.0,
+            b.field("name")
+        );
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::ParquetConfig;
+    use crate::models::MediaModel;
+    use crate::parquet::{write_media_categories_parquet, write_media_parquet, ParquetSession};
+    use tempfile::TempDir;
+
+    fn options() -> MediaOptions {
+        MediaOptions {
+            kind: media_kind("text-to-image").unwrap(),
+            creator: None,
+            min_elo: None,
+            category: None,
+            sort: None,
+            ascending: false,
+            top: Some(10),
+            confidence: false,
+        }
+    }
+
+    fn write_fixture(dir: &Path) {
+        let models: Vec<MediaModel> = serde_json::from_str(
+            r#"[
+                {"id": "1", "name": "Seedream 4", "slug": "seedream-4", "model_creator": {"id": "b", "name": "ByteDance"}, "elo": 1200.0, "rank": 1,
+                 "categories": [{"style_category": "Anime", "elo": 1100.0, "appearances": 50}]},
+                {"id": "2", "name": "Imagen 4", "slug": "imagen-4", "model_creator": {"id": "g", "name": "Google"}, "elo": 1150.0, "rank": 2,
+                 "categories": [{"style_category": "Anime", "elo": 1180.0, "appearances": 40},
+                                {"subject_matter_category": "People", "elo": 1120.0, "appearances": 30}]},
+                {"id": "3", "name": "Imagen 3", "slug": "imagen-3", "model_creator": {"id": "g", "name": "Google"}, "elo": 1050.0, "rank": 3}
+            ]"#,
+        )
+        .unwrap();
+        let session = ParquetSession::new(&ParquetConfig::default()).unwrap();
+        write_media_parquet(&models, &dir.join("text_to_image.parquet"), &session).unwrap();
+        write_media_categories_parquet(
+            "text_to_image",
+            &models,
+            &dir.join("text_to_image_categories.parquet"),
+            &session,
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_media_kind() {
+        assert_eq!(media_kind("Text-To-Image").unwrap().name, "text_to_image");
+        assert_eq!(media_kind("image_to_video").unwrap().name, "image_to_video");
+
+        let err = media_kind("music").unwrap_err().to_string();
+        assert!(err.contains("Unknown media kind 'music'"));
+        assert!(err.contains("text-to-speech"));
+    }
+
+    #[test]
+    fn test_media_filters() {
+        let temp_dir = TempDir::new().unwrap();
+        write_fixture(temp_dir.path());
+
+        let result = query_media(temp_dir.path(), &options()).unwrap();
+        assert_eq!(result.columns, MEDIA_VIEW_COLUMNS);
+        assert_eq!(result.len(), 3);
+        assert_eq!(result.rows[0][2], "seedream-4");
+
+        let filtered = MediaOptions {
+            creator: Some("google".into()),
+            min_elo: Some(1100.0),
+            ..options()
+        };
+        let result = query_media(temp_dir.path(), &filtered).unwrap();
+        assert_eq!(result.len(), 1);
+        assert_eq!(result.rows[0][2], "imagen-4");
+    }
+
+    #[test]
+    fn test_media_top_and_count() {
+        let temp_dir = TempDir::new().unwrap();
+        write_fixture(temp_dir.path());
+
+        let top = MediaOptions {
+            top: Some(2),
+            ..options()
+        };
+        assert_eq!(query_media(temp_dir.path(), &top).unwrap().len(), 2);
+        assert_eq!(count_media(temp_dir.path(), &top).unwrap(), 3);
+
+        let all = MediaOptions {
+            top: None,
+            ..options()
+        };
+        assert!(!media_sql(&all).unwrap().contains("LIMIT"));
+        assert_eq!(query_media(temp_dir.path(), &all).unwrap().len(), 3);
+
+        let google = MediaOptions {
+            creator: Some("google".into()),
+            ..top
+        };
+        assert_eq!(count_media(temp_dir.path(), &google).unwrap(), 2);
+    }
+
+    #[test]
+    fn test_media_confidence_columns() {
+        let temp_dir = TempDir::new().unwrap();
+        write_fixture(temp_dir.path());
+
+        let confidence = MediaOptions {
+            confidence: true,
+            sort: Some("appearances".into()),
+            ..options()
+        };
+        let result = query_media(temp_dir.path(), &confidence).unwrap();
+        assert_eq!(
+            result.columns,
+            vec![
+                "rank",
+                "name",
+                "slug",
+                "creator",
+                "elo",
+                "release_date",
+                "ci95",
+                "appearances"
+            ]
+        );
+        assert_eq!(result.len(), 3);
+
+        // Without the flag, appearances is not a sort key
+        let sorted = MediaOptions {
+            sort: Some("appearances".into()),
+            ..options()
+        };
+        assert!(media_sql(&sorted).is_err());
+    }
+
+    #[test]
+    fn test_media_sql_quotes_creator_and_validates_sort() {
+        let quoted = MediaOptions {
+            creator: Some("o'brien".into()),
+            ..options()
+        };
+        assert!(media_sql(&quoted).unwrap().contains("LIKE '%o''brien%'"));
+
+        let sorted = MediaOptions {
+            sort: Some("release_date".into()),
+            ascending: true,
+            ..options()
+        };
+        assert!(media_sql(&sorted)
+            .unwrap()
+            .contains("ORDER BY release_date ASC NULLS LAST, name"));
+
+        let bad = MediaOptions {
+            sort: Some("votes".into()),
+            ..options()
+        };
+        assert!(media_sql(&bad).is_err());
+    }
+
+    #[test]
+    fn test_media_category_leaderboard() {
+        let temp_dir = TempDir::new().unwrap();
+        write_fixture(temp_dir.path());
+
+        let anime = MediaOptions {
+            category: Some(CategoryFilter {
+                name: "anime".into(),
+                category_type: Some("style"),
+            }),
+            ..options()
+        };
+        let result = query_media(temp_dir.path(), &anime).unwrap();
+        assert_eq!(result.columns, view_columns(&anime));
+        let slugs: Vec<&str> = result.rows.iter().map(|r| r[2].as_str()).collect();
+        assert_eq!(slugs, vec!["imagen-4", "seedream-4"]);
+        assert_eq!(result.rows[0][0], "1");
+
+        // People is a subject category, not a style
+        let people = MediaOptions {
+            category: Some(CategoryFilter {
+                name: "People".into(),
+                category_type: Some("style"),
+            }),
+            ..options()
+        };
+        assert!(query_media(temp_dir.path(), &people).unwrap().is_empty());
+
+        let unknown = MediaOptions {
+            category: Some(CategoryFilter {
+                name: "watercolor".into(),
+                category_type: None,
+            }),
+            ..options()
+        };
+        let err = query_media(temp_dir.path(), &unknown).unwrap_err();
+        assert_eq!(err.exit_code(), 5);
+        assert!(err
+            .to_string()
+            .contains("Available categories: Anime, People"));
+    }
+
+    #[test]
+    fn test_find_media_model_with_categories() {
+        let temp_dir = TempDir::new().unwrap();
+        write_fixture(temp_dir.path());
+
+        let detail = find_media_model(temp_dir.path(), "IMAGEN-4", MEDIA_TABLES).unwrap();
+        assert_eq!(detail.kind.name, "text_to_image");
+        assert_eq!(detail.field("creator"), "Google");
+        assert_eq!(detail.field("ci95"), "-");
+        assert_eq!(detail.categories.len(), 2);
+        assert_eq!(detail.categories.rows[0][0], "style");
+
+        let text = format_detail(&detail, OutputFormat::Plain);
+        assert!(text.contains("Model:       Imagen 4 (imagen-4)"));
+        assert!(text.contains("Categories:"));
+
+        let json = detail_json(&detail);
+        assert_eq!(json["model"]["slug"], "imagen-4");
+        assert_eq!(json["categories"][1]["category"], "People");
+
+        let err = find_media_model(temp_dir.path(), "imagen", MEDIA_TABLES)
+            .err()
+            .unwrap();
+        assert_eq!(err.exit_code(), 5);
+    }
+
+    #[test]
+    fn test_win_probability() {
+        assert!((win_probability(1200.0, 1200.0) - 0.5).abs() < 1e-9);
+        assert!((win_probability(1400.0, 1000.0) - 10.0 / 11.0).abs() < 1e-9);
+        let p = win_probability(1150.0, 1050.0);
+        assert!((p + win_probability(1050.0, 1150.0) - 1.0).abs() < 1e-9);
+        assert!(p > 0.63 && p < 0.65);
+    }
+
+    #[test]
+    fn test_compare_details() {
+        let temp_dir = TempDir::new().unwrap();
+        write_fixture(temp_dir.path());
+
+        let a = find_media_model(temp_dir.path(), "imagen-4", MEDIA_TABLES).unwrap();
+        let b = find_media_model(temp_dir.path(), "imagen-3", MEDIA_TABLES).unwrap();
+        let (table, probability) = compare_details(&a, &b).unwrap();
+        assert_eq!(table.columns, vec!["Field", "Imagen 4", "Imagen 3"]);
+        assert_eq!(table.rows[2], vec!["ELO", "1150.00", "1050.00"]);
+        assert!((probability.unwrap() - win_probability(1150.0, 1050.0)).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,470 @@
+//! Migrate-cost command - what switching models would change for a workload.
+//!
+//! Usage is read from a CSV file with `input_tokens` and `output_tokens`
+//! columns (one row per request, or per group of `requests` identical
+//! requests when that column is present; other columns are ignored).
+//! Every row is priced on both models, long-context prices included, so the
+//! cost delta reflects the observed mix of prompt sizes. Capability gaps
+//! come from the models table and are left out when it is not cached.
+
+use crate::commands::cost::{calculate_cost, format_cost, Period};
+use crate::commands::get::resolve_model;
+use crate::error::{AppError, Result};
+use crate::models::LlmModel;
+use crate::output::{format_output, Formattable, OutputFormat};
+use crate::sources::models_dev::capabilities::{load_capabilities, Capabilities};
+use crate::sources::models_dev::pricing::PriceTier;
+use crate::utils::{format_tokens, parse_tokens};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::io::Read;
+use std::path::Path;
+use tabled::Tabled;
+
+/// Requests of one usage row.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct UsageRow {
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+    pub requests: u64,
+}
+
+/// Parse usage CSV.
+///
+/// # Errors
+///
+/// Returns `AppError::Config` when a required column is missing, a value is
+/// not a token count, or there are no rows.
+pub fn parse_usage(reader: impl Read) -> Result<Vec<UsageRow>> {
+    let invalid = |message: String| AppError::Config(format!("Invalid usage CSV: {}", message));
+    let mut rows_reader = csv::Reader::from_reader(reader);
+    let headers = rows_reader
+        .headers()
+        .map_err(|e| invalid(e.to_string()))?
+        .clone();
+    let column = |name: &str| {
+        headers
+            .iter()
+            .position(|h| h.trim().eq_ignore_ascii_case(name))
+    };
+    let input = column("input_tokens").ok_or_else(|| invalid("no input_tokens column".into()))?;
+    let output =
+        column("output_tokens").ok_or_else(|| invalid("no output_tokens column".into()))?;
+    let requests = column("requests");
+
+    let mut rows = Vec::new();
+    for (line, record) in rows_reader.records().enumerate() {
+        let record = record.map_err(|e| invalid(e.to_string()))?;
+        // Line numbers count the header
+        let value = |index: usize| {
+            parse_tokens(record.get(index).unwrap_or_default())
+                .map_err(|e| invalid(format!("line {}: {}", line + 2, e)))
+        };
+        rows.push(UsageRow {
+            input_tokens: value(input)?,
+            output_tokens: value(output)?,
+            requests: requests.map(value).transpose()?.unwrap_or(1),
+        });
+    }
+    if rows.is_empty() {
+        return Err(invalid("no usage rows".into()));
+    }
+    Ok(rows)
+}
+
+/// Load usage from a CSV file.
+pub fn load_usage(path: &Path) -> Result<Vec<UsageRow>> {
+    let file = std::fs::File::open(path).map_err(|e| {
+        AppError::Config(format!("Cannot read usage file {}: {}", path.display(), e))
+    })?;
+    parse_usage(file)
+}
+
+/// Cost of all usage on `model`, `None` when it has no published price.
+fn usage_cost(
+    model: &LlmModel,
+    usage: &[UsageRow],
+    tiers: &HashMap<String, PriceTier>,
+) -> Option<f64> {
+    let tier = tiers.get(&model.slug.to_lowercase());
+    usage
+        .iter()
+        .map(|row| {
+            calculate_cost(
+                model,
+                tier,
+                row.input_tokens,
+                row.output_tokens,
+                row.requests,
+                Period::Once,
+            )
+            .period_cost
+        })
+        .sum()
+}
+
+/// One model's side of the migration.
+#[derive(Debug, Clone, Serialize)]
+pub struct Side {
+    pub model: String,
+    pub name: String,
+    pub cost: Option<f64>,
+    pub intelligence: Option<f64>,
+    pub tps: Option<f64>,
+    pub latency: Option<f64>,
+    /// `None` when the models table is not cached or does not list the model.
+    pub capabilities: Option<Capabilities>,
+}
+
+/// The migration analysis.
+#[derive(Debug, Clone, Serialize)]
+pub struct Migration {
+    pub from: Side,
+    pub to: Side,
+    pub requests: u64,
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+    pub cost_delta: Option<f64>,
+    pub cost_delta_pct: Option<f64>,
+    /// Features or limits lost by switching.
+    pub gaps: Vec<String>,
+    /// Requests whose prompt does not fit the new model's context window.
+    pub requests_over_context: u64,
+}
+
+/// Features and limits `from` has that `to` lacks.
+fn capability_gaps(from: &Capabilities, to: &Capabilities) -> Vec<String> {
+    let mut gaps: Vec<String> = from
+        .flags()
+        .into_iter()
+        .zip(to.flags())
+        .filter(|((_, had), (_, has))| *had && !*has)
+        .map(|((name, _), _)| format!("loses {}", name))
+        .collect();
+    let limits = [
+        ("context window", from.context_window, to.context_window),
+        ("max output", from.max_output_tokens, to.max_output_tokens),
+    ];
+    for (label, had, has) in limits {
+        if let (Some(had), Some(has)) = (had, has) {
+            if has < had {
+                gaps.push(format!(
+                    "smaller {} ({} vs {} tokens)",
+                    label,
+                    format_tokens(has),
+                    format_tokens(had)
+                ));
+            }
+        }
+    }
+    gaps
+}
+
+/// Percentage change from `before` to `after`.
+fn pct_change(before: Option<f64>, after: Option<f64>) -> Option<f64> {
+    match (before, after) {
+        (Some(before), Some(after)) if before != 0.0 => Some((after - before) / before * 100.0),
+        _ => None,
+    }
+}
+
+/// Analyze switching from `from` to `to` for `usage`.
+pub fn analyze(
+    from: &LlmModel,
+    to: &LlmModel,
+    usage: &[UsageRow],
+    tiers: &HashMap<String, PriceTier>,
+    capabilities: (Option<Capabilities>, Option<Capabilities>),
+) -> Migration {
+    let side = |model: &LlmModel, capabilities: Option<Capabilities>| Side {
+        model: model.slug.clone(),
+        name: model.name.clone(),
+        cost: usage_cost(model, usage, tiers),
+        intelligence: model.intelligence,
+        tps: model.tps,
+        latency: model.latency,
+        capabilities,
+    };
+    let from = side(from, capabilities.0);
+    let to = side(to, capabilities.1);
+
+    let gaps = match (&from.capabilities, &to.capabilities) {
+        (Some(had), Some(has)) => capability_gaps(had, has),
+        _ => Vec::new(),
+    };
+    let context = to.capabilities.as_ref().and_then(|c| c.context_window);
+    let requests_over_context = usage
+        .iter()
+        .filter(|row| context.is_some_and(|limit| row.input_tokens > limit))
+        .map(|row| row.requests)
+        .sum();
+
+    Migration {
+        requests: usage.iter().map(|r| r.requests).sum(),
+        input_tokens: usage.iter().map(|r| r.input_tokens * r.requests).sum(),
+        output_tokens: usage.iter().map(|r| r.output_tokens * r.requests).sum(),
+        cost_delta: from.cost.zip(to.cost).map(|(before, after)| after - before),
+        cost_delta_pct: pct_change(from.cost, to.cost),
+        gaps,
+        requests_over_context,
+        from,
+        to,
+    }
+}
+
+/// Comparison row for table output.
+#[derive(Debug, Clone, Serialize, Tabled)]
+pub struct MigrationRow {
+    #[tabled(rename = "Metric")]
+    pub metric: String,
+    #[tabled(rename = "From")]
+    pub from: String,
+    #[tabled(rename = "To")]
+    pub to: String,
+    #[tabled(rename = "Change")]
+    pub change: String,
+}
+
+impl Formattable for MigrationRow {
+    fn headers() -> &'static [&'static str] {
+        &["Metric", "From", "To", "Change"]
+    }
+
+    fn to_row(&self) -> Vec<String> {
+        vec![
+            self.metric.clone(),
+            self.from.clone(),
+            self.to.clone(),
+            self.change.clone(),
+        ]
+    }
+}
+
+fn format_value(value: Option<f64>, unit: &str) -> String {
+    value.map_or("N/A".to_string(), |v| format!("{:.1}{}", v, unit))
+}
+
+fn format_change(before: Option<f64>, after: Option<f64>) -> String {
+    pct_change(before, after).map_or("-".to_string(), |pct| format!("{:+.1}%", pct))
+}
+
+/// Rows comparing both sides.
+fn rows(migration: &Migration) -> Vec<MigrationRow> {
+    let (from, to) = (&migration.from, &migration.to);
+    let context = |side: &Side| {
+        side.capabilities
+            .as_ref()
+            .and_then(|c| c.context_window)
+            .map_or("N/A".to_string(), format_tokens)
+    };
+    vec![
+        MigrationRow {
+            metric: "Model".into(),
+            from: from.name.clone(),
+            to: to.name.clone(),
+            change: String::new(),
+        },
+        MigrationRow {
+            metric: "Cost".into(),
+            from: format_cost(from.cost),
+            to: format_cost(to.cost),
+            change: format_change(from.cost, to.cost),
+        },
+        MigrationRow {
+            metric: "Intelligence".into(),
+            from: format_value(from.intelligence, ""),
+            to: format_value(to.intelligence, ""),
+            change: format_change(from.intelligence, to.intelligence),
+        },
+        MigrationRow {
+            metric: "Tokens/s".into(),
+            from: format_value(from.tps, ""),
+            to: format_value(to.tps, ""),
+            change: format_change(from.tps, to.tps),
+        },
+        MigrationRow {
+            metric: "Latency".into(),
+            from: format_value(from.latency, "s"),
+            to: format_value(to.latency, "s"),
+            change: format_change(from.latency, to.latency),
+        },
+        MigrationRow {
+            metric: "Context window".into(),
+            from: context(from),
+            to: context(to),
+            change: String::new(),
+        },
+    ]
+}
+
+/// Run the migrate-cost command.
+pub fn run(
+    models: &[LlmModel],
+    from: &str,
+    to: &str,
+    usage: &[UsageRow],
+    tiers: &HashMap<String, PriceTier>,
+    cache_dir: &Path,
+    format: OutputFormat,
+) -> Result<()> {
+    let from = resolve_model(models, from)?;
+    let to = resolve_model(models, to)?;
+    let capabilities = (
+        load_capabilities(cache_dir, &from.slug)?,
+        load_capabilities(cache_dir, &to.slug)?,
+    );
+    let migration = analyze(from, to, usage, tiers, capabilities);
+
+    if format == OutputFormat::Json {
+        println!("{}", crate::output::json::format_json(&migration));
+        return Ok(());
+    }
+
+    if matches!(format, OutputFormat::Markdown | OutputFormat::Table) {
+        println!(
+            "Switching {} to {} for {} requests ({} input / {} output tokens)",
+            migration.from.model,
+            migration.to.model,
+            migration.requests,
+            format_tokens(migration.input_tokens),
+            format_tokens(migration.output_tokens)
+        );
+        println!();
+    }
+    println!("{}", format_output(&rows(&migration), format));
+    if !matches!(format, OutputFormat::Markdown | OutputFormat::Table) {
+        return Ok(());
+    }
+
+    println!();
+    if let Some(delta) = migration.cost_delta {
+        let direction = if delta <= 0.0 { "saves" } else { "costs" };
+        println!(
+            "Switching {} {} for this usage.",
+            direction,
+            format_cost(Some(delta.abs()))
+        );
+    }
+    if migration.from.capabilities.is_none() || migration.to.capabilities.is_none() {
+        println!("Capability gaps unknown: the models table does not list both models.");
+    } else if migration.gaps.is_empty() {
+        println!("Capability gaps: none.");
+    } else {
+        println!("Capability gaps:");
+        for gap in &migration.gaps {
+            println!("  - {}", gap);
+        }
+    }
+    if migration.requests_over_context > 0 {
+        println!(
+            "{} request(s) have prompts larger than {}'s context window.",
+            migration.requests_over_context, migration.to.model
+        );
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn model(slug: &str, input: f64, output: f64, tps: f64) -> LlmModel {
+        LlmModel {
+            name: slug.into(),
+            slug: slug.into(),
+            input_price: Some(input),
+            output_price: Some(output),
+            tps: Some(tps),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_parse_usage() {
+        let csv = "timestamp,input_tokens,output_tokens,requests\n\
+                   2025-01-01,1000,200,10\n\
+                   2025-01-02,2k,1k,1\n";
+        let usage = parse_usage(csv.as_bytes()).unwrap();
+        assert_eq!(
+            usage,
+            vec![
+                UsageRow {
+                    input_tokens: 1000,
+                    output_tokens: 200,
+                    requests: 10
+                },
+                UsageRow {
+                    input_tokens: 2000,
+                    output_tokens: 1000,
+                    requests: 1
+                },
+            ]
+        );
+
+        // Without a requests column every row is one request
+        let usage = parse_usage("input_tokens,output_tokens\n10,5\n".as_bytes()).unwrap();
+        assert_eq!(usage[0].requests, 1);
+
+        assert!(parse_usage("input_tokens\n10\n".as_bytes()).is_err());
+        assert!(parse_usage("input_tokens,output_tokens\n".as_bytes()).is_err());
+        let err = parse_usage("input_tokens,output_tokens\n10,lots\n".as_bytes()).unwrap_err();
+        assert!(err.to_string().contains("line 2"));
+    }
+
+    #[test]
+    fn test_analyze() {
+        let from = model("gpt-4o", 2.5, 10.0, 100.0);
+        let to = model("claude-sonnet", 3.0, 15.0, 50.0);
+        let usage = vec![UsageRow {
+            input_tokens: 1_000_000,
+            output_tokens: 100_000,
+            requests: 2,
+        }];
+        let had = Capabilities {
+            tool_call: true,
+            structured_output: true,
+            context_window: Some(128_000),
+            ..Default::default()
+        };
+        let has = Capabilities {
+            tool_call: true,
+            reasoning: true,
+            context_window: Some(64_000),
+            ..Default::default()
+        };
+        let migration = analyze(&from, &to, &usage, &HashMap::new(), (Some(had), Some(has)));
+
+        assert_eq!(migration.requests, 2);
+        assert_eq!(migration.input_tokens, 2_000_000);
+        // (2.5 + 1.0) * 2 before, (3.0 + 1.5) * 2 after
+        assert!((migration.from.cost.unwrap() - 7.0).abs() < 1e-9);
+        assert!((migration.cost_delta.unwrap() - 2.0).abs() < 1e-9);
+        assert_eq!(
+            migration.gaps,
+            vec![
+                "loses structured_output",
+                "smaller context window (64K vs 128K tokens)"
+            ]
+        );
+        assert_eq!(migration.requests_over_context, 2);
+
+        let rows = rows(&migration);
+        let speed = rows.iter().find(|r| r.metric == "Tokens/s").unwrap();
+        assert_eq!(speed.change, "-50.0%");
+    }
+
+    #[test]
+    fn test_analyze_without_capabilities() {
+        let from = model("gpt-4o", 2.5, 10.0, 100.0);
+        let to = model("gpt-4o-mini", 0.15, 0.6, 80.0);
+        let usage = vec![UsageRow {
+            input_tokens: 1000,
+            output_tokens: 1000,
+            requests: 1,
+        }];
+        let migration = analyze(&from, &to, &usage, &HashMap::new(), (None, None));
+        assert!(migration.gaps.is_empty());
+        assert_eq!(migration.requests_over_context, 0);
+        assert!(migration.cost_delta.unwrap() < 0.0);
+    }
+}
@@ -1,11 +1,37 @@
 //! Command implementations.
 
+pub mod audit;
+pub mod batch;
 pub mod cache;
+pub mod changelog;
 pub mod compare;
 pub mod cost;
+pub mod doctor;
+pub mod examples;
+pub mod get;
 pub mod info;
+pub mod llms;
+pub mod media;
+pub mod migrate;
+pub mod notes;
+pub mod open;
+pub mod plan;
+pub mod policy;
 pub mod profile;
+#[cfg(feature = "sql")]
 pub mod query;
+pub mod quota;
+pub mod recommend;
 pub mod refresh;
+#[cfg(feature = "sql")]
+pub mod repl;
+pub mod report;
+pub mod route;
+pub mod schema;
+pub mod shortlist;
 pub mod skill;
+pub mod stats;
 pub mod tables;
+pub mod tokens;
+pub mod trend;
+pub mod unmatched;
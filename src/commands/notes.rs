@@ -0,0 +1,176 @@
+//! Note and tag commands - local annotations on models.
+//!
+//! Models are resolved like `get` (exact slug or name, or a search matching
+//! one model) and stored by slug. A slug that already has notes or tags is
+//! used as is, so models that have since left the data can still be cleaned
+//! up.
+
+use crate::commands::get::resolve_model;
+use crate::error::Result;
+use crate::models::LlmModel;
+use crate::notes::Notes;
+
+/// Slug to annotate for `search`.
+pub fn resolve_slug(notes: &Notes, models: &[LlmModel], search: &str) -> Result<String> {
+    if notes.get(search).is_some() {
+        return Ok(search.to_lowercase());
+    }
+    Ok(resolve_model(models, search)?.slug.to_lowercase())
+}
+
+/// Add a note to the model matching `search`.
+pub fn add_note(models: &[LlmModel], search: &str, text: &str, quiet: bool) -> Result<()> {
+    let mut notes = Notes::load()?;
+    let slug = resolve_slug(&notes, models, search)?;
+    notes.add_note(&slug, text);
+    notes.save()?;
+    if !quiet {
+        eprintln!("Added note to {}", slug);
+    }
+    Ok(())
+}
+
+/// Remove one note (1-based `index`) or all notes of the model matching
+/// `search`.
+pub fn remove_note(
+    models: &[LlmModel],
+    search: &str,
+    index: Option<usize>,
+    quiet: bool,
+) -> Result<()> {
+    let mut notes = Notes::load()?;
+    let slug = resolve_slug(&notes, models, search)?;
+    let removed = notes.remove_notes(&slug, index)?;
+    notes.save()?;
+    if !quiet {
+        eprintln!("Removed {} note(s) from {}", removed.len(), slug);
+    }
+    Ok(())
+}
+
+/// Render notes as numbered lines under each slug.
+fn render_notes(notes: &Notes, slug: Option<&str>) -> String {
+    let mut lines = Vec::new();
+    for (model, entry) in &notes.models {
+        if slug.is_some_and(|s| !s.eq_ignore_ascii_case(model)) || entry.notes.is_empty() {
+            continue;
+        }
+        lines.push(model.clone());
+        for (i, note) in entry.notes.iter().enumerate() {
+            lines.push(format!("  {}. {}", i + 1, note));
+        }
+    }
+    lines.join("\n")
+}
+
+/// List notes of every model, or of the model with `slug`.
+pub fn list_notes(slug: Option<&str>) -> Result<()> {
+    let text = render_notes(&Notes::load()?, slug);
+    if text.is_empty() {
+        eprintln!("No notes.");
+    } else {
+        println!("{}", text);
+    }
+    Ok(())
+}
+
+/// Add `tags` to the model matching `search`.
+pub fn add_tags(models: &[LlmModel], search: &str, tags: &[String], quiet: bool) -> Result<()> {
+    let mut notes = Notes::load()?;
+    let slug = resolve_slug(&notes, models, search)?;
+    notes.add_tags(&slug, tags)?;
+    notes.save()?;
+    if !quiet {
+        eprintln!("Tagged {}", slug);
+    }
+    Ok(())
+}
+
+/// Remove `tags` from the model matching `search`.
+pub fn remove_tags(models: &[LlmModel], search: &str, tags: &[String], quiet: bool) -> Result<()> {
+    let mut notes = Notes::load()?;
+    let slug = resolve_slug(&notes, models, search)?;
+    notes.remove_tags(&slug, tags)?;
+    notes.save()?;
+    if !quiet {
+        eprintln!("Untagged {}", slug);
+    }
+    Ok(())
+}
+
+/// Render `slug<TAB>tag,tag` lines, for every tagged model or those with `tag`.
+fn render_tags(notes: &Notes, tag: Option<&str>) -> String {
+    notes
+        .models
+        .iter()
+        .filter(|(_, entry)| !entry.tags.is_empty())
+        .filter(|(_, entry)| tag.map_or(true, |t| entry.tags.contains(&t.to_lowercase())))
+        .map(|(model, entry)| {
+            let tags: Vec<&str> = entry.tags.iter().map(String::as_str).collect();
+            format!("{}\t{}", model, tags.join(","))
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// List tagged models, optionally only those with `tag`.
+pub fn list_tags(tag: Option<&str>) -> Result<()> {
+    let text = render_tags(&Notes::load()?, tag);
+    if text.is_empty() {
+        eprintln!("No tagged models.");
+    } else {
+        println!("{}", text);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn models() -> Vec<LlmModel> {
+        ["gpt-4o", "claude-3-5-haiku", "claude-4-5-haiku"]
+            .into_iter()
+            .map(|slug| LlmModel {
+                name: slug.into(),
+                slug: slug.into(),
+                ..Default::default()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_resolve_slug() {
+        let mut notes = Notes::default();
+        assert_eq!(resolve_slug(&notes, &models(), "GPT-4o").unwrap(), "gpt-4o");
+        assert!(resolve_slug(&notes, &models(), "haiku").is_err());
+
+        // Annotated models resolve even when gone from the data
+        notes.add_note("old-model", "replaced by gpt-4o");
+        assert_eq!(
+            resolve_slug(&notes, &models(), "old-model").unwrap(),
+            "old-model"
+        );
+    }
+
+    #[test]
+    fn test_render() {
+        let mut notes = Notes::default();
+        notes.add_note("gpt-4o", "approved for prod");
+        notes
+            .add_tags("claude-3-5-haiku", &["cheap-tier".into()])
+            .unwrap();
+        notes.add_tags("gpt-4o", &["prod".into()]).unwrap();
+
+        assert_eq!(render_notes(&notes, None), "gpt-4o\n  1. approved for prod");
+        assert_eq!(render_notes(&notes, Some("claude-3-5-haiku")), "");
+        assert_eq!(
+            render_tags(&notes, None),
+            "claude-3-5-haiku\tcheap-tier\ngpt-4o\tprod"
+        );
+        assert_eq!(
+            render_tags(&notes, Some("Cheap-Tier")),
+            "claude-3-5-haiku\tcheap-tier"
+        );
+    }
+}
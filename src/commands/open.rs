@@ -0,0 +1,496 @@
+//! Open command - preset view of open-weight models.
+//!
+//! Lists models.dev models with `open_weights = true`, one row per model,
+//! with the cheapest hosted price across providers, the largest context
+//! window, and Artificial Analysis scores when the slug matches.
+
+use crate::cache::Cache;
+use crate::cli::invocation;
+use crate::error::{AppError, Result};
+use crate::output::{note_hidden, OutputFormat};
+use crate::query::{format_query_result, QueryExecutor, QueryResult};
+use crate::schema::BENCHMARKS;
+use chrono::{NaiveDate, Utc};
+use std::path::Path;
+use std::str::FromStr;
+
+/// Columns of the open-weights view that are always present.
+const VIEW_COLUMNS: &[&str] = &[
+    "model",
+    "name",
+    "family",
+    "providers",
+    "context_window",
+    "release_date",
+    "input_price",
+    "output_price",
+];
+
+/// Columns added when the benchmarks table is cached.
+const BENCHMARK_COLUMNS: &[&str] = &[
+    "creator",
+    "intelligence",
+    "coding",
+    "intelligence_per_dollar",
+    "coding_per_dollar",
+];
+
+/// Resolve a user-supplied column name against the view.
+///
+/// `what` names the option in the error ("sort key", "group").
+fn view_column(column: &str, with_benchmarks: bool, what: &str) -> Result<&'static str> {
+    let valid: Vec<&'static str> = if with_benchmarks {
+        VIEW_COLUMNS
+            .iter()
+            .chain(BENCHMARK_COLUMNS)
+            .copied()
+            .collect()
+    } else {
+        VIEW_COLUMNS.to_vec()
+    };
+    let column = column.trim().to_lowercase();
+    if let Some(found) = valid.iter().find(|c| **c == column) {
+        return Ok(found);
+    }
+
+    let mut message = format!(
+        "Unknown {} '{}'. Valid {}s: {}",
+        what,
+        column,
+        what,
+        valid.join(", ")
+    );
+    if !with_benchmarks && BENCHMARK_COLUMNS.contains(&column.as_str()) {
+        message.push_str(&format!(
+            ". Run '{}' to use benchmark columns.",
+            invocation("refresh")
+        ));
+    }
+    Err(AppError::Config(message))
+}
+
+/// Sort order for the open-weights view.
+#[derive(Debug, Clone, Default)]
+pub struct OpenSort {
+    /// Column to sort by; `None` keeps the default ranking.
+    pub column: Option<String>,
+    /// Sort ascending instead of descending.
+    pub ascending: bool,
+}
+
+impl OpenSort {
+    /// Build the ORDER BY clause, validating the column against the view.
+    fn order_by(&self, with_benchmarks: bool) -> Result<String> {
+        let Some(column) = &self.column else {
+            return Ok(if with_benchmarks {
+                "intelligence DESC NULLS LAST, context_window DESC NULLS LAST".to_string()
+            } else {
+                "context_window DESC NULLS LAST".to_string()
+            });
+        };
+
+        let column = view_column(column, with_benchmarks, "sort key")?;
+        let direction = if self.ascending { "ASC" } else { "DESC" };
+        Ok(format!("{} {} NULLS LAST, model", column, direction))
+    }
+}
+
+/// Keep only the best `count` rows per value of a view column.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TopPer {
+    pub column: String,
+    pub count: usize,
+}
+
+impl FromStr for TopPer {
+    type Err = AppError;
+
+    /// Parse `column=N`, e.g. `creator=2`.
+    fn from_str(s: &str) -> Result<Self> {
+        let invalid = || {
+            AppError::Config(format!(
+                "Invalid --top-per '{}'. Use <column>=<count>, e.g. creator=2.",
+                s
+            ))
+        };
+        let (column, count) = s.split_once('=').ok_or_else(invalid)?;
+        let count: usize = count.trim().parse().map_err(|_| invalid())?;
+        if column.trim().is_empty() || count == 0 {
+            return Err(invalid());
+        }
+        Ok(Self {
+            column: column.trim().to_string(),
+            count,
+        })
+    }
+}
+
+/// Options for the open-weights view.
+#[derive(Debug, Clone)]
+pub struct OpenOptions {
+    /// Maximum number of rows; `None` shows every match.
+    pub limit: Option<usize>,
+    pub sort: OpenSort,
+    pub top_per: Option<TopPer>,
+    /// Only keep models released on or after this date.
+    pub released_after: Option<NaiveDate>,
+    /// Add an `age` column ("3 mo") computed from `release_date`.
+    pub age: bool,
+}
+
+impl Default for OpenOptions {
+    fn default() -> Self {
+        Self {
+            limit: Some(20),
+            sort: OpenSort::default(),
+            top_per: None,
+            released_after: None,
+            age: false,
+        }
+    }
+}
+
+/// SQL expression rendering the time since `date` as a short age ("5 d", "3 mo").
+fn age_sql(date: &str, today: NaiveDate) -> String {
+    let days = format!("date_diff('day', {}, DATE '{}')", date, today);
+    format!(
+        "CASE
+        WHEN {date} IS NULL THEN NULL
+        WHEN {days} < 14 THEN CAST({days} AS VARCHAR) || ' d'
+        WHEN {days} < 60 THEN CAST({days} // 7 AS VARCHAR) || ' wk'
+        WHEN {days} < 730 THEN CAST({days} // 30 AS VARCHAR) || ' mo'
+        ELSE CAST({days} // 365 AS VARCHAR) || ' yr'
+    END",
+        date = date,
+        days = days
+    )
+}
+
+/// Build the SQL for the open-weights view.
+///
+/// Provider prefixes (`meta-llama/`) are stripped from models.dev ids so the
+/// same model served by several providers collapses into one row. Benchmark
+/// columns are only joined when the benchmarks table is cached. With
+/// `top_per`, rows are ranked within each group by the sort order and only
+/// the first `count` of each group are kept. `today` anchors the age column.
+fn open_models_sql(
+    options: &OpenOptions,
+    with_benchmarks: bool,
+    today: NaiveDate,
+) -> Result<String> {
+    let order = options.sort.order_by(with_benchmarks)?;
+    let (benchmark_columns, benchmark_join) = if with_benchmarks {
+        (
+            ",
+    max(b.creator) AS creator,
+    max(b.intelligence) AS intelligence,
+    max(b.coding) AS coding,
+    max(b.intelligence) / nullif(max(b.price), 0) AS intelligence_per_dollar,
+    max(b.coding) / nullif(max(b.price), 0) AS coding_per_dollar",
+            "\nLEFT JOIN benchmarks b ON lower(b.slug) = m.model_key",
+        )
+    } else {
+        ("", "")
+    };
+    let age_column = if options.age {
+        format!("\n    {} AS age,", age_sql("max(m.release_date)", today))
+    } else {
+        String::new()
+    };
+    let having = match options.released_after {
+        Some(cutoff) => format!("\nHAVING max(m.release_date) >= DATE '{}'", cutoff),
+        None => String::new(),
+    };
+
+    let view = format!(
+        "SELECT
+    m.model_key AS model,
+    max(m.model_name) AS name,
+    max(m.family) AS family,
+    count(DISTINCT m.provider_id) AS providers,
+    max(m.context_window) AS context_window,
+    max(m.release_date) AS release_date,{}
+    min(m.cost_input) AS input_price,
+    min(m.cost_output) AS output_price{}
+FROM (
+    SELECT *, lower(regexp_replace(model_id, '^.*/', '')) AS model_key
+    FROM models
+    WHERE open_weights = true
+) m{}
+GROUP BY m.model_key{}",
+        age_column, benchmark_columns, benchmark_join, having
+    );
+
+    let limit = options
+        .limit
+        .map(|limit| format!("\nLIMIT {}", limit))
+        .unwrap_or_default();
+    Ok(match &options.top_per {
+        None => format!("{}\nORDER BY {}{}", view, order, limit),
+        Some(top) => {
+            let group = view_column(&top.column, with_benchmarks, "group")?;
+            format!(
+                "SELECT * FROM (\n{}\n) v\nQUALIFY row_number() OVER (PARTITION BY {} ORDER BY {}) <= {}\nORDER BY {}{}",
+                view, group, order, top.count, order, limit
+            )
+        }
+    })
+}
+
+/// Query open-weight models from the cache in `cache_dir`.
+fn query_open_models(
+    cache_dir: &Path,
+    options: &OpenOptions,
+    today: NaiveDate,
+) -> Result<QueryResult> {
+    let executor = QueryExecutor::new(cache_dir.to_path_buf());
+    let with_benchmarks = BENCHMARKS.is_cached(cache_dir);
+    executor.execute(&open_models_sql(options, with_benchmarks, today)?)
+}
+
+/// Count the models the open-weights view lists before `limit` is applied.
+fn count_open_models(cache_dir: &Path, options: &OpenOptions, today: NaiveDate) -> Result<usize> {
+    let executor = QueryExecutor::new(cache_dir.to_path_buf());
+    let unlimited = OpenOptions {
+        limit: None,
+        ..options.clone()
+    };
+    executor.count(&open_models_sql(
+        &unlimited,
+        BENCHMARKS.is_cached(cache_dir),
+        today,
+    )?)
+}
+
+/// Run the open command.
+///
+/// When `limit` hides rows, a note on stderr says how many there are,
+/// unless `quiet`.
+pub fn run(
+    options: &OpenOptions,
+    format: OutputFormat,
+    fail_on_empty: bool,
+    quiet: bool,
+) -> Result<()> {
+    let cache = Cache::new()?;
+    let today = Utc::now().date_naive();
+    let result = query_open_models(cache.base_dir(), options, today)?;
+    println!("{}", format_query_result(&result, format));
+    if !quiet && options.limit.is_some_and(|limit| result.len() >= limit) {
+        let total = count_open_models(cache.base_dir(), options, today)?;
+        note_hidden(result.len(), total, "--limit");
+    }
+    if fail_on_empty && result.is_empty() {
+        return Err(AppError::Empty("no open-weight models matched".into()));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use duckdb::Connection;
+    use tempfile::TempDir;
+
+    fn write_parquet(path: &Path, select: &str) {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute(
+            &format!(
+                "COPY ({}) TO '{}' (FORMAT PARQUET)",
+                select,
+                path.to_string_lossy()
+            ),
+            [],
+        )
+        .unwrap();
+    }
+
+    fn options() -> OpenOptions {
+        OpenOptions {
+            limit: Some(10),
+            ..Default::default()
+        }
+    }
+
+    fn today() -> NaiveDate {
+        NaiveDate::from_ymd_opt(2025, 6, 1).unwrap()
+    }
+
+    fn write_fixtures(dir: &Path) {
+        write_parquet(
+            &dir.join("models.parquet"),
+            "SELECT * FROM (VALUES
+                ('groq', 'llama-3.3-70b', 'Llama 3.3 70B', 'llama', 128000::BIGINT, DATE '2024-12-06', 0.59::DOUBLE, 0.79::DOUBLE, true),
+                ('openrouter', 'meta-llama/llama-3.3-70b', 'Llama 3.3 70B', 'llama', 131072, DATE '2024-12-06', 0.12, 0.30, true),
+                ('groq', 'llama-3.1-8b', 'Llama 3.1 8B', 'llama', 131072, DATE '2024-07-23', 0.05, 0.08, true),
+                ('local', 'qwen3-8b', 'Qwen3 8B', 'qwen', 32768, DATE '2025-04-29', NULL, NULL, true),
+                ('openai', 'gpt-4o', 'GPT-4o', 'gpt', 128000, DATE '2024-05-13', 2.5, 10.0, false)
+            ) t(provider_id, model_id, model_name, family, context_window, release_date, cost_input, cost_output, open_weights)",
+        );
+        write_parquet(
+            &dir.join("benchmarks.parquet"),
+            "SELECT * FROM (VALUES
+                ('llama-3.3-70b', 'Meta', 41.0::DOUBLE, 30.0::DOUBLE, 0.5::DOUBLE),
+                ('llama-3.1-8b', 'Meta', 21.0, 12.0, 0.0)
+            ) t(slug, creator, intelligence, coding, price)",
+        );
+    }
+
+    #[test]
+    fn test_open_models_groups_providers() {
+        let temp_dir = TempDir::new().unwrap();
+        write_fixtures(temp_dir.path());
+
+        let result = query_open_models(temp_dir.path(), &options(), today()).unwrap();
+        assert_eq!(
+            result.columns,
+            vec![
+                "model",
+                "name",
+                "family",
+                "providers",
+                "context_window",
+                "release_date",
+                "input_price",
+                "output_price",
+                "creator",
+                "intelligence",
+                "coding",
+                "intelligence_per_dollar",
+                "coding_per_dollar"
+            ]
+        );
+        assert_eq!(result.len(), 3);
+        assert_eq!(result.rows[0][0], "llama-3.3-70b");
+        assert_eq!(result.rows[0][3], "2");
+        assert_eq!(result.rows[0][4], "131072");
+        assert_eq!(result.rows[0][6], "0.12");
+        assert_eq!(result.rows[0][11], "82.00");
+        assert_eq!(result.rows[1][11], "");
+        assert_eq!(result.rows[2][0], "qwen3-8b");
+
+        let options = OpenOptions {
+            sort: OpenSort {
+                column: Some("release_date".to_string()),
+                ascending: false,
+            },
+            ..options()
+        };
+        let result = query_open_models(temp_dir.path(), &options, today()).unwrap();
+        assert_eq!(result.rows[0][0], "qwen3-8b");
+        assert_eq!(result.rows[0][5], "2025-04-29");
+    }
+
+    #[test]
+    fn test_open_models_top_per_group() {
+        let temp_dir = TempDir::new().unwrap();
+        write_fixtures(temp_dir.path());
+
+        let per_family = OpenOptions {
+            top_per: Some("family=1".parse().unwrap()),
+            ..options()
+        };
+        let result = query_open_models(temp_dir.path(), &per_family, today()).unwrap();
+        let models: Vec<&str> = result.rows.iter().map(|r| r[0].as_str()).collect();
+        assert_eq!(models, vec!["llama-3.3-70b", "qwen3-8b"]);
+
+        let by_price = OpenOptions {
+            sort: OpenSort {
+                column: Some("input_price".to_string()),
+                ascending: true,
+            },
+            top_per: Some("creator=1".parse().unwrap()),
+            ..options()
+        };
+        let result = query_open_models(temp_dir.path(), &by_price, today()).unwrap();
+        let models: Vec<&str> = result.rows.iter().map(|r| r[0].as_str()).collect();
+        assert_eq!(models, vec!["llama-3.1-8b", "qwen3-8b"]);
+    }
+
+    #[test]
+    fn test_open_models_age_and_released_after() {
+        let temp_dir = TempDir::new().unwrap();
+        write_fixtures(temp_dir.path());
+
+        let options = OpenOptions {
+            age: true,
+            released_after: NaiveDate::from_ymd_opt(2024, 12, 1),
+            ..options()
+        };
+        let result = query_open_models(temp_dir.path(), &options, today()).unwrap();
+        assert_eq!(result.columns[6], "age");
+        let rows: Vec<(&str, &str)> = result
+            .rows
+            .iter()
+            .map(|r| (r[0].as_str(), r[6].as_str()))
+            .collect();
+        assert_eq!(rows, vec![("llama-3.3-70b", "5 mo"), ("qwen3-8b", "4 wk")]);
+    }
+
+    #[test]
+    fn test_parse_top_per() {
+        assert_eq!(
+            "creator=2".parse::<TopPer>().unwrap(),
+            TopPer {
+                column: "creator".to_string(),
+                count: 2
+            }
+        );
+        for bad in ["creator", "creator=0", "=2", "creator=two"] {
+            assert!(bad.parse::<TopPer>().is_err(), "{} should fail", bad);
+        }
+
+        let options = OpenOptions {
+            top_per: Some("provider=2".parse().unwrap()),
+            ..options()
+        };
+        let err = open_models_sql(&options, true, today())
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("Unknown group 'provider'"));
+    }
+
+    #[test]
+    fn test_open_models_without_benchmarks() {
+        let options = OpenOptions {
+            limit: Some(5),
+            ..options()
+        };
+        let sql = open_models_sql(&options, false, today()).unwrap();
+        assert!(!sql.contains("benchmarks"));
+        assert!(sql.ends_with("LIMIT 5"));
+
+        let all = OpenOptions {
+            limit: None,
+            ..options
+        };
+        assert!(!open_models_sql(&all, false, today())
+            .unwrap()
+            .contains("LIMIT"));
+    }
+
+    #[test]
+    fn test_sort_validation() {
+        let sort = |column: &str| OpenOptions {
+            sort: OpenSort {
+                column: Some(column.to_string()),
+                ascending: true,
+            },
+            ..options()
+        };
+
+        let sql = open_models_sql(&sort("Input_Price"), true, today()).unwrap();
+        assert!(sql.contains("ORDER BY input_price ASC NULLS LAST, model"));
+
+        let err = open_models_sql(&sort("gpqa"), true, today())
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("Unknown sort key 'gpqa'"));
+        assert!(err.contains("context_window, release_date"));
+
+        let err = open_models_sql(&sort("intelligence"), false, today())
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("which-llm refresh"));
+    }
+}
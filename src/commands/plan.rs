@@ -0,0 +1,578 @@
+//! Plan mode - what a data command would fetch, read and write.
+//!
+//! With `--plan`, nothing is contacted or written: the command is resolved
+//! to the data sources it would contact, the cache files it would use, and
+//! the files it would write, based on the current state of the cache.
+
+use crate::cli::{invocation, Commands, MediaCommands, PolicyCommands};
+use crate::error::{AppError, Result};
+use crate::output::{format_output, Formattable, OutputFormat};
+use crate::policy::Policy;
+#[cfg(feature = "sql")]
+use crate::query::referenced_tables;
+use crate::remote::{release_file_url, DataChannel};
+use crate::schema::{
+    TableDef, BENCHMARKS, BENCHMARK_RANKS, LLMS_EXTRA, LLMS_HISTORY, MEDIA_TABLES, TEXT_TO_SPEECH,
+};
+use crate::snapshot::Snapshot;
+use crate::sources::artificial_analysis::API_BASE;
+use crate::sources::models_dev::MODELS_DEV_API;
+use crate::sources::MODELS;
+use crate::utils::{sort_field, SortField};
+use chrono::NaiveDate;
+use serde::Serialize;
+use std::path::Path;
+use tabled::Tabled;
+
+/// Cache state the plan is resolved against.
+#[derive(Debug, Clone)]
+pub struct PlanContext<'a> {
+    pub cache_dir: &'a Path,
+    /// Whether hosted data was downloaded within its TTL.
+    pub data_fresh: bool,
+    /// Hosted data channel files are downloaded from.
+    pub channel: DataChannel,
+    /// `--use-api`: refresh from the APIs instead of hosted data.
+    pub use_api: bool,
+    /// `--as-of` snapshot that replaces the `benchmarks` table.
+    pub snapshot: Option<&'a Snapshot>,
+    /// Date `refresh --snapshot` would tag its snapshot with.
+    pub today: NaiveDate,
+}
+
+/// One planned data access.
+#[derive(Debug, Clone, PartialEq, Serialize, Tabled)]
+pub struct PlanStep {
+    /// `fetch` (network), `read` (cache) or `write` (cache).
+    #[tabled(rename = "Action")]
+    pub action: &'static str,
+    /// URL or file path.
+    #[tabled(rename = "Target")]
+    pub target: String,
+    #[tabled(rename = "Note")]
+    pub note: String,
+}
+
+impl Formattable for PlanStep {
+    fn headers() -> &'static [&'static str] {
+        &["Action", "Target", "Note"]
+    }
+
+    fn to_row(&self) -> Vec<String> {
+        vec![
+            self.action.to_string(),
+            self.target.clone(),
+            self.note.clone(),
+        ]
+    }
+}
+
+fn step(action: &'static str, target: impl Into<String>, note: impl Into<String>) -> PlanStep {
+    PlanStep {
+        action,
+        target: target.into(),
+        note: note.into(),
+    }
+}
+
+/// Read a cached file, noting when it is missing.
+fn read_cached(path: &Path, exists: bool, missing: &str) -> PlanStep {
+    let note = if exists {
+        "cached".to_string()
+    } else {
+        format!("not cached; {}", missing)
+    };
+    step("read", path.display().to_string(), note)
+}
+
+/// Read a table that is only ever read from the cache.
+fn read_table(ctx: &PlanContext, table: &TableDef) -> PlanStep {
+    read_cached(
+        &table.cache_path(ctx.cache_dir),
+        table.is_cached(ctx.cache_dir),
+        &format!("run '{}' first", table.command_line()),
+    )
+}
+
+/// Download a hosted file into the cache, or reuse the cached copy.
+fn hosted_file(ctx: &PlanContext, name: &str, force: bool) -> Vec<PlanStep> {
+    let filename = format!("{}.parquet", name);
+    let path = ctx.cache_dir.join(&filename);
+    if !force && path.exists() && ctx.data_fresh {
+        return vec![step(
+            "read",
+            path.display().to_string(),
+            "cached copy is fresh",
+        )];
+    }
+
+    let reason = if force {
+        "refresh"
+    } else if path.exists() {
+        "cached copy is stale"
+    } else {
+        "not cached"
+    };
+    vec![
+        step(
+            "fetch",
+            release_file_url(ctx.channel, "manifest.json"),
+            "expected checksums",
+        ),
+        step(
+            "fetch",
+            release_file_url(ctx.channel, &filename),
+            if path.exists() {
+                format!("{}; skipped if unchanged upstream", reason)
+            } else {
+                reason.to_string()
+            },
+        ),
+        step("write", path.display().to_string(), "verified download"),
+        step(
+            "write",
+            ctx.cache_dir.join("remote_meta.json").display().to_string(),
+            "download record",
+        ),
+    ]
+}
+
+/// Read the `--as-of` snapshot in place of `benchmarks`.
+fn read_snapshot(snapshot: &Snapshot) -> PlanStep {
+    step(
+        "read",
+        snapshot.path.display().to_string(),
+        format!("--as-of snapshot from {}", snapshot.date),
+    )
+}
+
+/// The `benchmarks` table as loaded by the model commands.
+fn benchmarks(ctx: &PlanContext) -> Vec<PlanStep> {
+    match ctx.snapshot {
+        Some(snapshot) => vec![read_snapshot(snapshot)],
+        None => hosted_file(ctx, "benchmarks", false),
+    }
+}
+
+/// The models.dev table, which the model commands that read it download
+/// alongside `benchmarks` when it is missing or stale.
+fn models_dev(ctx: &PlanContext) -> Vec<PlanStep> {
+    match ctx.snapshot {
+        Some(_) => vec![read_table(ctx, &MODELS)],
+        None => hosted_file(ctx, MODELS.name, false),
+    }
+}
+
+/// Media tables searched by `media show/compare`.
+fn media_lookup(ctx: &PlanContext, kind: Option<&str>) -> Result<Vec<PlanStep>> {
+    let tables: Vec<&TableDef> = match kind {
+        Some(kind) => vec![super::media::media_kind(kind)?],
+        None => MEDIA_TABLES.to_vec(),
+    };
+    Ok(tables.into_iter().map(|t| read_table(ctx, t)).collect())
+}
+
+/// Files written by `refresh`.
+fn refresh(ctx: &PlanContext, snapshot: bool) -> Vec<PlanStep> {
+    let mut steps = Vec::new();
+    if ctx.use_api {
+        steps.push(step(
+            "fetch",
+            API_BASE,
+            "LLM and media endpoints (API key required)",
+        ));
+        steps.push(step("fetch", MODELS_DEV_API, "capability metadata"));
+        let tables = ["benchmarks", "llms_extra", "models"]
+            .into_iter()
+            .map(String::from)
+            .chain(
+                MEDIA_TABLES
+                    .iter()
+                    .flat_map(|t| [t.name.to_string(), format!("{}_categories", t.name)]),
+            );
+        for name in tables {
+            let path = ctx.cache_dir.join(format!("{}.parquet", name));
+            steps.push(step(
+                "write",
+                path.display().to_string(),
+                "from API response",
+            ));
+        }
+    } else {
+        for name in ["benchmarks", "llms_extra", "models"] {
+            steps.extend(hosted_file(ctx, name, true));
+        }
+        for table in MEDIA_TABLES {
+            steps.extend(hosted_file(ctx, table.name, true));
+            steps.extend(hosted_file(
+                ctx,
+                &format!("{}_categories", table.name),
+                true,
+            ));
+        }
+    }
+
+    steps.push(step(
+        "write",
+        BENCHMARK_RANKS
+            .cache_path(ctx.cache_dir)
+            .display()
+            .to_string(),
+        "derived from benchmarks; skipped if unchanged",
+    ));
+    if snapshot {
+        let path = LLMS_HISTORY
+            .cache_path(ctx.cache_dir)
+            .join(format!("date={}", ctx.today))
+            .join("benchmarks.parquet");
+        steps.push(step("write", path.display().to_string(), "--snapshot"));
+    }
+    steps
+}
+
+/// Resolve the data accesses of `command`.
+///
+/// # Errors
+///
+/// Returns `AppError::Config` for commands that do not work with data
+/// (cache, profile, skill, doctor).
+pub fn plan(command: &Commands, ctx: &PlanContext) -> Result<Vec<PlanStep>> {
+    let steps = match command {
+        #[cfg(feature = "sql")]
+        Commands::Query { sql, fetch, .. } => {
+            let sql = sql.as_deref().unwrap_or_default();
+            let uncached = super::query::uncached_hosted_tables(sql, ctx.cache_dir);
+            referenced_tables(sql)
+                .into_iter()
+                .flat_map(|table| match ctx.snapshot {
+                    Some(snapshot) if table.name == BENCHMARKS.name => {
+                        vec![read_snapshot(snapshot)]
+                    }
+                    _ if *fetch && uncached.iter().any(|t| t.name == table.name) => {
+                        hosted_file(ctx, table.name, false)
+                    }
+                    _ => vec![read_table(ctx, table)],
+                })
+                .collect()
+        }
+        Commands::Refresh { snapshot } => refresh(ctx, *snapshot),
+        Commands::Tokens { model: Some(_), .. } => benchmarks(ctx),
+        Commands::Tables { .. }
+        | Commands::Schema { .. }
+        | Commands::Info { .. }
+        | Commands::Examples { .. }
+        | Commands::Tokens { .. } => Vec::new(),
+        Commands::Compare {
+            uncertainty,
+            percentile,
+            selection,
+            ..
+        } => {
+            let mut steps = benchmarks(ctx);
+            if selection.reads_models() {
+                steps.extend(models_dev(ctx));
+            }
+            if *uncertainty || percentile.is_some() {
+                steps.push(read_table(ctx, &LLMS_EXTRA));
+            }
+            steps
+        }
+        Commands::Route { selection, .. } => {
+            let mut steps = benchmarks(ctx);
+            if selection.reads_models() {
+                steps.extend(models_dev(ctx));
+            }
+            steps
+        }
+        Commands::Get { integration, .. } => {
+            let mut steps = benchmarks(ctx);
+            if *integration {
+                steps.extend(models_dev(ctx));
+            }
+            steps
+        }
+        Commands::Policy {
+            command: PolicyCommands::Check { policy, .. },
+        } => {
+            let path = match policy {
+                Some(path) => path.clone(),
+                None => Policy::default_path()?,
+            };
+            let mut steps = benchmarks(ctx);
+            if !Policy::load(&path)?.required_capabilities.is_empty() {
+                steps.push(read_table(ctx, &MODELS));
+            }
+            steps
+        }
+        Commands::Cost {
+            characters: Some(_),
+            ..
+        } => vec![read_table(ctx, &TEXT_TO_SPEECH)],
+        Commands::Cost {
+            media: Some(kind), ..
+        } => vec![read_table(ctx, super::cost::video_kind(kind)?)],
+        Commands::Report { selection, .. } if selection.reads_models() => {
+            let mut steps = benchmarks(ctx);
+            steps.extend(models_dev(ctx));
+            steps
+        }
+        Commands::Cost { .. } | Commands::Report { .. } | Commands::MigrateCost { .. } => {
+            let mut steps = benchmarks(ctx);
+            // Long-context price tiers are only read when already cached
+            if MODELS.is_cached(ctx.cache_dir) {
+                steps.push(read_table(ctx, &MODELS));
+            }
+            steps
+        }
+        Commands::Shortlist {
+            needs, selection, ..
+        } => {
+            let mut steps = benchmarks(ctx);
+            if !needs.is_empty() || selection.reads_models() {
+                steps.extend(models_dev(ctx));
+            }
+            steps
+        }
+        Commands::Recommend {
+            needs,
+            tool_call,
+            selection,
+            ..
+        } => {
+            let mut steps = benchmarks(ctx);
+            if !needs.is_empty() || *tool_call || selection.reads_models() {
+                steps.extend(models_dev(ctx));
+            }
+            steps
+        }
+        Commands::Llms {
+            tool_call,
+            reasoning,
+            open_weights,
+            sort,
+            percentile,
+            selection,
+            ..
+        } => {
+            let mut steps = benchmarks(ctx);
+            let sorts_models = sort
+                .as_deref()
+                .and_then(sort_field)
+                .is_some_and(SortField::reads_models);
+            if *tool_call || *reasoning || *open_weights || sorts_models || selection.reads_models()
+            {
+                steps.extend(models_dev(ctx));
+            }
+            if percentile.is_some() {
+                steps.push(read_table(ctx, &LLMS_EXTRA));
+            }
+            steps
+        }
+        Commands::Open { .. } | Commands::Unmatched { .. } | Commands::Audit { .. } => {
+            vec![read_table(ctx, &MODELS), read_table(ctx, &BENCHMARKS)]
+        }
+        Commands::Stats { .. } => vec![read_table(ctx, &BENCHMARKS), read_table(ctx, &MODELS)],
+        Commands::Trend { .. } => vec![read_table(ctx, &LLMS_HISTORY)],
+        Commands::Changelog { .. } => {
+            let mut steps = vec![read_table(ctx, &LLMS_HISTORY)];
+            steps.extend(benchmarks(ctx));
+            steps
+        }
+        Commands::Media {
+            command: Some(MediaCommands::Show { kind, .. }),
+            ..
+        }
+        | Commands::Media {
+            command: Some(MediaCommands::Compare { kind, .. }),
+            ..
+        } => media_lookup(ctx, kind.as_deref())?,
+        Commands::Media {
+            command: None,
+            kind,
+            category,
+            style,
+            ..
+        } => {
+            let table = super::media::media_kind(kind)?;
+            let mut steps = vec![read_table(ctx, table)];
+            if category.is_some() || style.is_some() {
+                let path = ctx
+                    .cache_dir
+                    .join(format!("{}_categories.parquet", table.name));
+                let exists = path.exists();
+                let note = format!("run '{}' first", invocation("refresh"));
+                steps.push(read_cached(&path, exists, &note));
+            }
+            steps
+        }
+        Commands::Batch { .. }
+        | Commands::Cache { .. }
+        | Commands::Note { .. }
+        | Commands::Tag { .. }
+        | Commands::Profile { .. }
+        | Commands::Quota { .. }
+        | Commands::Skill { .. }
+        | Commands::Doctor { .. } => {
+            return Err(AppError::Config(
+                "--plan only applies to data commands: query, refresh, tables, info, compare, cost, report, migrate-cost, route, shortlist, get, policy, open, stats, trend, changelog, media".into(),
+            ));
+        }
+    };
+
+    // A manifest is fetched once per run however many files it covers
+    let mut unique: Vec<PlanStep> = Vec::new();
+    for step in steps {
+        if !unique.contains(&step) {
+            unique.push(step);
+        }
+    }
+    Ok(unique)
+}
+
+/// Run plan mode for `command`.
+pub fn run(command: &Commands, ctx: &PlanContext) -> Result<()> {
+    let steps = plan(command, ctx)?;
+    if steps.is_empty() {
+        println!("No data sources would be contacted and no cache files read or written.");
+    } else {
+        println!("{}", format_output(&steps, OutputFormat::Markdown));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::Cli;
+    use clap::Parser;
+    use tempfile::TempDir;
+
+    fn command(args: &[&str]) -> Commands {
+        let mut argv = vec!["which-llm"];
+        argv.extend_from_slice(args);
+        Cli::parse_from(argv).command
+    }
+
+    fn context(cache_dir: &Path, data_fresh: bool) -> PlanContext<'_> {
+        PlanContext {
+            cache_dir,
+            data_fresh,
+            channel: DataChannel::Stable,
+            use_api: false,
+            snapshot: None,
+            today: NaiveDate::from_ymd_opt(2025, 3, 1).unwrap(),
+        }
+    }
+
+    fn actions(steps: &[PlanStep]) -> Vec<&str> {
+        steps.iter().map(|s| s.action).collect()
+    }
+
+    #[test]
+    fn test_plan_benchmarks_fetch_or_cache() {
+        let temp_dir = TempDir::new().unwrap();
+        let compare = command(&["compare", "gpt", "claude"]);
+
+        let steps = plan(&compare, &context(temp_dir.path(), false)).unwrap();
+        assert_eq!(actions(&steps), vec!["fetch", "fetch", "write", "write"]);
+        assert!(steps[1].target.ends_with("/benchmarks.parquet"));
+        assert_eq!(steps[1].note, "not cached");
+
+        std::fs::write(temp_dir.path().join("benchmarks.parquet"), b"data").unwrap();
+        let steps = plan(&compare, &context(temp_dir.path(), true)).unwrap();
+        assert_eq!(actions(&steps), vec!["read"]);
+        assert_eq!(steps[0].note, "cached copy is fresh");
+
+        let snapshot = Snapshot {
+            date: NaiveDate::from_ymd_opt(2025, 1, 1).unwrap(),
+            path: temp_dir.path().join("pinned.parquet"),
+            index_version: None,
+        };
+        let ctx = PlanContext {
+            snapshot: Some(&snapshot),
+            ..context(temp_dir.path(), false)
+        };
+        let steps = plan(&compare, &ctx).unwrap();
+        assert_eq!(actions(&steps), vec!["read"]);
+        assert!(steps[0].note.contains("2025-01-01"));
+    }
+
+    #[test]
+    #[cfg(feature = "sql")]
+    fn test_plan_query_reads_referenced_tables() {
+        let temp_dir = TempDir::new().unwrap();
+        let query = command(&[
+            "query",
+            "SELECT * FROM benchmarks b JOIN models m ON b.slug = m.model_id",
+        ]);
+        let steps = plan(&query, &context(temp_dir.path(), false)).unwrap();
+        assert_eq!(actions(&steps), vec!["read", "read"]);
+        assert!(steps[0].note.starts_with("not cached"));
+
+        // --fetch downloads the uncached hosted table first
+        std::fs::write(temp_dir.path().join("models.parquet"), b"data").unwrap();
+        let query = command(&[
+            "query",
+            "--fetch",
+            "SELECT * FROM benchmarks b JOIN models m ON b.slug = m.model_id",
+        ]);
+        let steps = plan(&query, &context(temp_dir.path(), false)).unwrap();
+        assert_eq!(
+            actions(&steps),
+            vec!["fetch", "fetch", "write", "write", "read"]
+        );
+        assert!(steps[1].target.ends_with("/benchmarks.parquet"));
+        assert!(steps[4].target.ends_with("models.parquet"));
+    }
+
+    #[test]
+    fn test_plan_refresh_fetches_manifest_once() {
+        let temp_dir = TempDir::new().unwrap();
+        let refresh = command(&["refresh", "--snapshot"]);
+        let steps = plan(&refresh, &context(temp_dir.path(), true)).unwrap();
+
+        let manifests = steps
+            .iter()
+            .filter(|s| s.target.ends_with("manifest.json"))
+            .count();
+        assert_eq!(manifests, 1);
+        assert!(steps
+            .iter()
+            .any(|s| s.target.ends_with("date=2025-03-01/benchmarks.parquet")));
+        assert!(steps.iter().all(|s| s.action != "read"));
+    }
+
+    #[test]
+    fn test_plan_fetches_models_dev_alongside_benchmarks() {
+        let temp_dir = TempDir::new().unwrap();
+        let llms = command(&["llms", "--tool-call"]);
+
+        // One manifest covers both downloads
+        let steps = plan(&llms, &context(temp_dir.path(), false)).unwrap();
+        assert_eq!(
+            actions(&steps),
+            vec!["fetch", "fetch", "write", "write", "fetch", "write"]
+        );
+        assert!(steps[4].target.ends_with("/models.parquet"));
+        let compare = command(&["compare", "a", "b", "--min-context", "100k"]);
+        let steps = plan(&compare, &context(temp_dir.path(), false)).unwrap();
+        assert!(steps[4].target.ends_with("/models.parquet"));
+
+        for name in ["benchmarks.parquet", "models.parquet"] {
+            std::fs::write(temp_dir.path().join(name), b"data").unwrap();
+        }
+        let steps = plan(&llms, &context(temp_dir.path(), true)).unwrap();
+        assert_eq!(actions(&steps), vec!["read", "read"]);
+    }
+
+    #[test]
+    fn test_plan_rejects_non_data_commands() {
+        let temp_dir = TempDir::new().unwrap();
+        let err = plan(
+            &command(&["cache", "clear"]),
+            &context(temp_dir.path(), false),
+        )
+        .unwrap_err();
+        assert_eq!(err.exit_code(), 2);
+    }
+}
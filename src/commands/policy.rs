@@ -0,0 +1,115 @@
+//! Policy command - check models against an organization policy file.
+//!
+//! `policy check` resolves one model like `get` and lists every rule it
+//! breaks, failing with a policy error so it can gate CI or scripts.
+
+use crate::commands::get::resolve_model;
+use crate::error::{AppError, Result};
+use crate::models::LlmModel;
+use crate::output::OutputFormat;
+use crate::policy::PolicyCheck;
+use serde::Serialize;
+use std::path::Path;
+
+/// Outcome of checking one model.
+#[derive(Debug, Clone, Serialize)]
+pub struct CheckResult {
+    pub model: String,
+    pub policy: String,
+    pub allowed: bool,
+    pub violations: Vec<String>,
+}
+
+/// Check the model matching `search` against `check`, loaded from `path`.
+pub fn check_model(
+    models: &[LlmModel],
+    search: &str,
+    check: &PolicyCheck,
+    path: &Path,
+) -> Result<CheckResult> {
+    let model = resolve_model(models, search)?;
+    let violations = check.violations(model);
+    Ok(CheckResult {
+        model: model.slug.clone(),
+        policy: path.display().to_string(),
+        allowed: violations.is_empty(),
+        violations,
+    })
+}
+
+/// Render a check result as text.
+fn render(result: &CheckResult) -> String {
+    if result.allowed {
+        return format!("{} is allowed by {}", result.model, result.policy);
+    }
+    let mut lines = vec![format!(
+        "{} is not allowed by {}:",
+        result.model, result.policy
+    )];
+    lines.extend(result.violations.iter().map(|v| format!("  - {}", v)));
+    lines.join("\n")
+}
+
+/// Run `policy check`. Returns `AppError::Policy` when the model is not
+/// allowed, after printing why.
+pub fn run_check(
+    models: &[LlmModel],
+    search: &str,
+    check: &PolicyCheck,
+    path: &Path,
+    format: OutputFormat,
+) -> Result<()> {
+    let result = check_model(models, search, check, path)?;
+    if format == OutputFormat::Json {
+        println!("{}", crate::output::json::format_json(&result));
+    } else {
+        println!("{}", render(&result));
+    }
+    if result.allowed {
+        Ok(())
+    } else {
+        Err(AppError::Policy(format!(
+            "{} breaks {} rule(s)",
+            result.model,
+            result.violations.len()
+        )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::policy::Policy;
+    use std::collections::BTreeMap;
+
+    #[test]
+    fn test_check_model() {
+        let models: Vec<LlmModel> = [("gpt-4o", "OpenAI"), ("grok-4", "xAI")]
+            .into_iter()
+            .map(|(slug, creator)| LlmModel {
+                name: slug.into(),
+                slug: slug.into(),
+                creator: creator.into(),
+                ..Default::default()
+            })
+            .collect();
+        let policy = Policy {
+            allowed_creators: vec!["OpenAI".into()],
+            ..Default::default()
+        };
+        let check = PolicyCheck::with_capabilities(policy, BTreeMap::new());
+        let path = Path::new("policy.toml");
+
+        let allowed = check_model(&models, "GPT-4o", &check, path).unwrap();
+        assert!(allowed.allowed);
+        assert_eq!(render(&allowed), "gpt-4o is allowed by policy.toml");
+
+        let denied = check_model(&models, "grok", &check, path).unwrap();
+        assert!(!denied.allowed);
+        assert_eq!(
+            render(&denied),
+            "grok-4 is not allowed by policy.toml:\n  - creator xAI is not allowed (allowed: OpenAI)"
+        );
+        assert!(check_model(&models, "claude", &check, path).is_err());
+    }
+}
@@ -1,11 +1,15 @@
 //! Profile management commands.
 
-use crate::config::{Config, Profile};
-use crate::error::Result;
+use crate::cli::invocation;
+use crate::config::{Config, Profile, API_KEY_ENV};
+use crate::error::{AppError, Result};
 use dialoguer::{Input, Password};
 
 /// Create a new profile.
-pub fn create(name: &str, api_key: Option<&str>) -> Result<()> {
+///
+/// Without `api_key`, the key is prompted for; when not `interactive`
+/// (agent mode) it must be given instead.
+pub fn create(name: &str, api_key: Option<&str>, interactive: bool) -> Result<()> {
     let mut config = Config::load()?;
 
     // Check if profile already exists
@@ -17,6 +21,11 @@ pub fn create(name: &str, api_key: Option<&str>) -> Result<()> {
     // Get API key
     let key = match api_key {
         Some(k) => k.to_string(),
+        None if !interactive => {
+            return Err(AppError::Config(
+                "--api-key is required in agent mode".into(),
+            ))
+        }
         None => Password::new()
             .with_prompt("Enter API key")
             .interact()
@@ -43,7 +52,10 @@ pub fn list() -> Result<()> {
     let config = Config::load()?;
 
     if config.profiles.is_empty() {
-        println!("No profiles configured. Run 'aa profile create <name>' to create one.");
+        println!(
+            "No profiles configured. Run '{}' to create one.",
+            invocation("profile create <name>")
+        );
         return Ok(());
     }
 
@@ -74,7 +86,10 @@ pub fn set_default(name: &str) -> Result<()> {
 }
 
 /// Delete a profile.
-pub fn delete(name: &str) -> Result<()> {
+///
+/// Deleting the default profile asks for confirmation; when not
+/// `interactive` (agent mode) it is refused instead.
+pub fn delete(name: &str, interactive: bool) -> Result<()> {
     let mut config = Config::load()?;
 
     if config.get_profile(name).is_none() {
@@ -84,6 +99,13 @@ pub fn delete(name: &str) -> Result<()> {
 
     // Warn if deleting default profile
     let is_default = config.default_profile.as_deref() == Some(name);
+    if is_default && !interactive {
+        return Err(AppError::Config(format!(
+            "'{}' is the default profile; set another default with '{}' before deleting it",
+            name,
+            invocation("profile default <name>")
+        )));
+    }
     if is_default {
         let confirm: String = Input::new()
             .with_prompt(format!(
@@ -128,15 +150,46 @@ pub fn show(name: Option<&str>) -> Result<()> {
     let default_marker = if is_default { " (default)" } else { "" };
 
     println!("Profile: {}{}", profile_name, default_marker);
+    println!("  API Key: {}", mask_key(&profile.api_key));
+
+    Ok(())
+}
+
+/// Show the API key requests would use and where it comes from, with
+/// `profile` as the selected profile.
+pub fn show_effective(profile: Option<&str>) -> Result<()> {
+    let config = Config::load()?;
+    let (key, source) = config.resolve_api_key(profile)?;
+
+    println!("Source: {}", source);
+    println!("  API Key: {}", mask_key(&key));
     println!(
-        "  API Key: {}...{}",
-        &profile.api_key[..8.min(profile.api_key.len())],
-        if profile.api_key.len() > 12 {
-            &profile.api_key[profile.api_key.len() - 4..]
-        } else {
-            ""
-        }
+        "  Precedence: --api-key, ${}, --profile, default profile",
+        API_KEY_ENV
     );
 
     Ok(())
 }
+
+/// The start and, for longer keys, the end of an API key.
+fn mask_key(key: &str) -> String {
+    let start: String = key.chars().take(8).collect();
+    let count = key.chars().count();
+    let end: String = if count > 12 {
+        key.chars().skip(count - 4).collect()
+    } else {
+        String::new()
+    };
+    format!("{}...{}", start, end)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mask_key() {
+        assert_eq!(mask_key("aa_live_0123456789abcd"), "aa_live_...abcd");
+        assert_eq!(mask_key("short"), "short...");
+    }
+}
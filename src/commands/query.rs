@@ -1,19 +1,56 @@
 //! Query command implementation.
 
 use crate::cache::Cache;
-use crate::error::Result;
+use crate::cli::invocation;
+use crate::error::{AppError, Result};
 use crate::output::OutputFormat;
-use crate::query::{format_query_result, format_tables_list, QueryExecutor};
+use crate::query::{format_query_batches, format_tables_list, referenced_tables, QueryExecutor};
+use crate::schema::{TableDef, BENCHMARKS, LLMS_HISTORY, MEDIA_TABLES};
+use crate::snapshot::{self, Snapshot};
+use crate::sources::MODELS;
+use crate::strict;
+use std::path::Path;
+
+/// Tables `sql` reads that every data release publishes as a file but
+/// that are not cached in `cache_dir`, for `query --fetch`.
+pub fn uncached_hosted_tables(sql: &str, cache_dir: &Path) -> Vec<&'static TableDef> {
+    referenced_tables(sql)
+        .into_iter()
+        .filter(|table| {
+            table.name == BENCHMARKS.name
+                || table.name == MODELS.name
+                || MEDIA_TABLES.iter().any(|t| t.name == table.name)
+        })
+        .filter(|table| !table.is_cached(cache_dir))
+        .collect()
+}
 
 /// Run the query command.
-pub fn run(sql: Option<&str>, tables: bool, format: OutputFormat) -> Result<()> {
+///
+/// With `strict`, fails if any table the query reads is older than the data TTL.
+/// With a `snapshot`, the `benchmarks` table is read from that snapshot.
+/// With `fail_on_empty`, a query that returns no rows is an error. Unless
+/// `quiet`, a query over `llms_history` warns when the stored snapshots span
+/// a re-basing of the intelligence index.
+pub fn run(
+    sql: Option<&str>,
+    tables: bool,
+    format: OutputFormat,
+    strict: bool,
+    fail_on_empty: bool,
+    quiet: bool,
+    snapshot: Option<&Snapshot>,
+) -> Result<()> {
     let cache = Cache::new()?;
-    let executor = QueryExecutor::new(cache.base_dir().to_path_buf());
+    let mut executor = QueryExecutor::new(cache.base_dir().to_path_buf());
+    if let Some(snapshot) = snapshot {
+        executor = executor.with_benchmarks_snapshot(snapshot.path.clone());
+    }
 
     if tables {
         // List available tables
         let table_info = executor.list_tables();
-        println!("{}", format_tables_list(&table_info));
+        println!("{}", format_tables_list(&table_info, false));
         return Ok(());
     }
 
@@ -24,16 +61,56 @@ pub fn run(sql: Option<&str>, tables: bool, format: OutputFormat) -> Result<()>
             eprintln!("Error: No SQL query provided.");
             eprintln!();
             eprintln!(
-                "Usage: which-llm query \"SELECT * FROM benchmarks WHERE intelligence > 40\""
+                "Usage: {}",
+                invocation("query \"SELECT * FROM benchmarks WHERE intelligence > 40\"")
             );
             eprintln!();
-            eprintln!("Use 'which-llm tables' to see available tables.");
+            eprintln!("Use '{}' to see available tables.", invocation("tables"));
             return Ok(());
         }
     };
 
-    let result = executor.execute(sql)?;
-    println!("{}", format_query_result(&result, format));
+    let mut tables = referenced_tables(sql);
+    if strict {
+        // A pinned snapshot is stale by design
+        if snapshot.is_some() {
+            tables.retain(|t| t.name != BENCHMARKS.name);
+        }
+        strict::ensure_fresh(&tables, cache.base_dir())?;
+    }
+    if !quiet && tables.iter().any(|t| t.name == LLMS_HISTORY.name) {
+        let versions = snapshot::index_versions(cache.base_dir());
+        if let Some(warning) = snapshot::rebasing_warning(&versions) {
+            eprintln!("{}", warning);
+        }
+    }
+
+    let result = executor.execute_batches(sql)?;
+    println!("{}", format_query_batches(&result, format));
 
+    if fail_on_empty && result.is_empty() {
+        return Err(AppError::Empty("query returned no rows".into()));
+    }
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_uncached_hosted_tables() {
+        let temp_dir = TempDir::new().unwrap();
+        std::fs::write(temp_dir.path().join("benchmarks.parquet"), b"data").unwrap();
+        let sql = "SELECT * FROM benchmarks b JOIN models m ON b.slug = m.model_id \
+                   JOIN llms_extra e ON e.model_id = b.id";
+
+        // llms_extra is not published by every release, so it is never fetched
+        let names: Vec<&str> = uncached_hosted_tables(sql, temp_dir.path())
+            .iter()
+            .map(|t| t.name)
+            .collect();
+        assert_eq!(names, vec!["models"]);
+    }
+}
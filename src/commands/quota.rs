@@ -0,0 +1,168 @@
+//! Quota command - API quota last reported for each API key.
+
+use crate::cache::Cache;
+use crate::cli::invocation;
+use crate::config::{ApiKeySource, Config};
+use crate::error::{AppError, Result};
+use crate::output::{format_output, Formattable, OutputFormat};
+use crate::quota::{get_quota, key_hash, QuotaRecord};
+use serde::Serialize;
+use std::path::Path;
+use tabled::Tabled;
+
+/// Name shown for the key from `ARTIFICIAL_ANALYSIS_API_KEY`.
+const ENV_KEY: &str = "$ARTIFICIAL_ANALYSIS_API_KEY";
+
+/// Quota of one profile's API key.
+#[derive(Debug, Clone, PartialEq, Serialize, Tabled)]
+pub struct QuotaRow {
+    #[tabled(rename = "Profile")]
+    pub profile: String,
+    /// Hash of the API key; profiles with the same hash share a quota.
+    #[tabled(rename = "Key")]
+    pub key: String,
+    #[tabled(rename = "Limit", display = "display_count")]
+    pub limit: Option<u64>,
+    #[tabled(rename = "Remaining", display = "display_count")]
+    pub remaining: Option<u64>,
+    #[tabled(rename = "Reset", display = "display_text")]
+    pub reset: Option<String>,
+    /// When the quota was last reported (RFC 3339).
+    #[tabled(rename = "Updated", display = "display_text")]
+    pub updated_at: Option<String>,
+}
+
+fn display_count(value: &Option<u64>) -> String {
+    value.map_or_else(|| "-".into(), |v| v.to_string())
+}
+
+fn display_text(value: &Option<String>) -> String {
+    value.clone().unwrap_or_else(|| "-".into())
+}
+
+impl Formattable for QuotaRow {
+    fn headers() -> &'static [&'static str] {
+        &["Profile", "Key", "Limit", "Remaining", "Reset", "Updated"]
+    }
+
+    fn to_row(&self) -> Vec<String> {
+        vec![
+            self.profile.clone(),
+            self.key.clone(),
+            display_count(&self.limit),
+            display_count(&self.remaining),
+            display_text(&self.reset),
+            display_text(&self.updated_at),
+        ]
+    }
+}
+
+impl QuotaRow {
+    fn new(profile: &str, api_key: &str, record: Option<QuotaRecord>) -> Self {
+        Self {
+            profile: profile.to_string(),
+            key: key_hash(api_key),
+            limit: record.as_ref().and_then(|r| r.limit),
+            remaining: record.as_ref().and_then(|r| r.remaining),
+            reset: record.as_ref().and_then(|r| r.reset.clone()),
+            updated_at: record.map(|r| r.updated_at.to_rfc3339()),
+        }
+    }
+}
+
+/// Quota rows for `profile` (or the key the API would use), or for every
+/// profile plus the environment key with `all_profiles`.
+fn quota_rows(
+    config: &Config,
+    cache_dir: &Path,
+    profile: Option<&str>,
+    all_profiles: bool,
+) -> Result<Vec<QuotaRow>> {
+    let row = |name: &str, key: &str| QuotaRow::new(name, key, get_quota(cache_dir, key));
+    if !all_profiles {
+        let (key, source) = config.resolve_api_key(profile)?;
+        let name = match source {
+            ApiKeySource::Flag => "--api-key".to_string(),
+            ApiKeySource::Env => ENV_KEY.to_string(),
+            ApiKeySource::Profile(name) | ApiKeySource::DefaultProfile(name) => name,
+        };
+        return Ok(vec![row(&name, &key)]);
+    }
+
+    let mut names: Vec<&String> = config.profiles.keys().collect();
+    names.sort();
+    let mut rows: Vec<QuotaRow> = names
+        .into_iter()
+        .map(|name| row(name, &config.profiles[name].api_key))
+        .collect();
+    if let Ok(key) = std::env::var("ARTIFICIAL_ANALYSIS_API_KEY") {
+        rows.push(row(ENV_KEY, &key));
+    }
+    if rows.is_empty() {
+        return Err(AppError::NoApiKey);
+    }
+    Ok(rows)
+}
+
+/// Run the quota command.
+pub fn run(profile: Option<&str>, all_profiles: bool, format: OutputFormat) -> Result<()> {
+    let config = Config::load()?;
+    let cache = Cache::new()?;
+    let rows = quota_rows(&config, cache.base_dir(), profile, all_profiles)?;
+    println!("{}", format_output(&rows, format));
+    if format == OutputFormat::Markdown && rows.iter().all(|r| r.updated_at.is_none()) {
+        eprintln!(
+            "No quota reported yet; it is recorded on each API request (e.g. '{}').",
+            invocation("refresh --use-api")
+        );
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Profile;
+    use crate::quota::set_quota;
+    use chrono::Utc;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_quota_rows_share_keys() {
+        if std::env::var("ARTIFICIAL_ANALYSIS_API_KEY").is_ok() {
+            return;
+        }
+        let temp_dir = TempDir::new().unwrap();
+        let mut config = Config::default();
+        for (name, key) in [("work", "shared"), ("ci", "shared"), ("home", "other")] {
+            config.set_profile(
+                name.into(),
+                Profile {
+                    api_key: key.into(),
+                },
+            );
+        }
+        config.set_default("home".into());
+        let record = QuotaRecord {
+            limit: Some(1000),
+            remaining: Some(7),
+            reset: None,
+            updated_at: Utc::now(),
+        };
+        set_quota(temp_dir.path(), "shared", &record).unwrap();
+
+        let rows = quota_rows(&config, temp_dir.path(), None, true).unwrap();
+        let names: Vec<&str> = rows.iter().map(|r| r.profile.as_str()).collect();
+        assert_eq!(names, vec!["ci", "home", "work"]);
+        assert_eq!(rows[0].key, rows[2].key);
+        assert_eq!(rows[0].remaining, Some(7));
+        assert_eq!(rows[2].remaining, Some(7));
+        assert_eq!(rows[1].remaining, None);
+
+        let rows = quota_rows(&config, temp_dir.path(), None, false).unwrap();
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].profile, "home");
+        let rows = quota_rows(&config, temp_dir.path(), Some("work"), false).unwrap();
+        assert_eq!(rows[0].remaining, Some(7));
+    }
+}
@@ -0,0 +1,474 @@
+//! Recommend command - rank the models that meet hard constraints.
+//!
+//! Candidates must be within the price caps, reach the minimum intelligence
+//! and have every required capability (from the models table); context and
+//! output bounds are shared selection options. The rest are ranked by a
+//! composite score whose weights come from `--optimize` or a score profile,
+//! normalized over the candidates.
+
+use crate::error::{AppError, Result};
+use crate::models::LlmModel;
+use crate::output::{format_output, note_hidden, Formattable, OutputFormat};
+use crate::query::{format_query_result, QueryResult};
+use crate::sources::models_dev::capabilities::{
+    capable_models, load_token_limits, validate_needs, TokenLimits,
+};
+use crate::sources::MODELS;
+use crate::utils::{within, ScoreWeights};
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+use std::str::FromStr;
+use tabled::Tabled;
+
+/// What the ranking favors.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Optimize {
+    /// Intelligence, with price and speed as secondary factors.
+    #[default]
+    Balanced,
+    /// Intelligence and coding only.
+    Quality,
+    /// Low blended price, then intelligence.
+    Cost,
+    /// High output speed and low latency, then intelligence.
+    Speed,
+}
+
+impl Optimize {
+    /// Score weights for this goal.
+    pub fn weights(self) -> ScoreWeights {
+        let (label, weights): (&str, &[(&str, f64)]) = match self {
+            Optimize::Balanced => (
+                "balanced",
+                &[("intelligence", 0.5), ("price", -0.25), ("tps", 0.25)],
+            ),
+            Optimize::Quality => ("quality", &[("intelligence", 0.8), ("coding", 0.2)]),
+            Optimize::Cost => ("cost", &[("price", -0.6), ("intelligence", 0.4)]),
+            Optimize::Speed => (
+                "speed",
+                &[("tps", 0.5), ("latency", -0.2), ("intelligence", 0.3)],
+            ),
+        };
+        ScoreWeights::new(
+            label,
+            weights.iter().map(|(metric, w)| (metric.to_string(), *w)),
+        )
+        .expect("built-in weights are valid")
+    }
+}
+
+impl FromStr for Optimize {
+    type Err = AppError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "balanced" => Ok(Optimize::Balanced),
+            "quality" => Ok(Optimize::Quality),
+            "cost" => Ok(Optimize::Cost),
+            "speed" => Ok(Optimize::Speed),
+            _ => Err(AppError::Config(format!(
+                "Unknown --optimize '{}'. Use balanced, quality, cost or speed.",
+                s
+            ))),
+        }
+    }
+}
+
+/// Hard constraints and ranking for a recommendation.
+#[derive(Debug, Clone, Default)]
+pub struct RecommendOptions {
+    /// Maximum input price in USD per million tokens.
+    pub max_input_price: Option<f64>,
+    /// Maximum output price in USD per million tokens.
+    pub max_output_price: Option<f64>,
+    /// Minimum intelligence index.
+    pub min_intelligence: Option<f64>,
+    /// Capabilities every model must have (columns of the `models` table).
+    pub needs: Vec<String>,
+    pub optimize: Optimize,
+    /// Score profile ranking instead of `optimize`.
+    pub score: Option<ScoreWeights>,
+    /// Break each score down into each weighted metric's points.
+    pub explain: bool,
+    /// Number of models to show; `None` shows every candidate.
+    pub count: Option<usize>,
+}
+
+impl RecommendOptions {
+    /// Weights the ranking uses: the score profile, else `optimize`'s.
+    pub fn weights(&self) -> ScoreWeights {
+        self.score
+            .clone()
+            .unwrap_or_else(|| self.optimize.weights())
+    }
+}
+
+/// Per-model data from the models table that constraints need.
+#[derive(Debug, Clone, Default)]
+pub struct ModelsData {
+    /// Keys of models with every required capability; `None` when no
+    /// capability is required.
+    pub capable: Option<HashSet<String>>,
+    /// Token limits by key; empty when the models table is not cached.
+    pub token_limits: HashMap<String, TokenLimits>,
+}
+
+/// One recommended model.
+#[derive(Debug, Clone, Serialize)]
+pub struct Recommendation {
+    pub rank: usize,
+    pub slug: String,
+    pub name: String,
+    pub creator: String,
+    pub score: f64,
+    pub intelligence: Option<f64>,
+    pub input_price: Option<f64>,
+    pub output_price: Option<f64>,
+    pub tps: Option<f64>,
+    pub context_window: Option<u64>,
+    /// Points each weighted metric adds to `score` (with `--explain`).
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub breakdown: Vec<ScorePart>,
+}
+
+/// One weighted metric's share of a score.
+#[derive(Debug, Clone, Serialize)]
+pub struct ScorePart {
+    pub metric: String,
+    pub weight: f64,
+    pub points: f64,
+}
+
+/// Recommendation row for table output.
+#[derive(Debug, Clone, Serialize, Tabled)]
+pub struct RecommendationRow {
+    #[tabled(rename = "#")]
+    pub rank: String,
+    #[tabled(rename = "Model")]
+    pub name: String,
+    #[tabled(rename = "Creator")]
+    pub creator: String,
+    #[tabled(rename = "Score")]
+    pub score: String,
+    #[tabled(rename = "Intelligence")]
+    pub intelligence: String,
+    #[tabled(rename = "Input $/M")]
+    pub input_price: String,
+    #[tabled(rename = "Output $/M")]
+    pub output_price: String,
+    #[tabled(rename = "TPS")]
+    pub tps: String,
+    #[tabled(rename = "Context")]
+    pub context_window: String,
+}
+
+impl Formattable for RecommendationRow {
+    fn headers() -> &'static [&'static str] {
+        &[
+            "#",
+            "Model",
+            "Creator",
+            "Score",
+            "Intelligence",
+            "Input $/M",
+            "Output $/M",
+            "TPS",
+            "Context",
+        ]
+    }
+
+    fn to_row(&self) -> Vec<String> {
+        vec![
+            self.rank.clone(),
+            self.name.clone(),
+            self.creator.clone(),
+            self.score.clone(),
+            self.intelligence.clone(),
+            self.input_price.clone(),
+            self.output_price.clone(),
+            self.tps.clone(),
+            self.context_window.clone(),
+        ]
+    }
+}
+
+impl From<&Recommendation> for RecommendationRow {
+    fn from(r: &Recommendation) -> Self {
+        let number = |v: Option<f64>, format: fn(f64) -> String| v.map_or("-".into(), format);
+        Self {
+            rank: r.rank.to_string(),
+            name: r.name.clone(),
+            creator: r.creator.clone(),
+            score: format!("{:.1}", r.score),
+            intelligence: number(r.intelligence, |v| format!("{:.1}", v)),
+            input_price: number(r.input_price, |v| format!("${:.2}", v)),
+            output_price: number(r.output_price, |v| format!("${:.2}", v)),
+            tps: number(r.tps, |v| format!("{:.0}", v)),
+            context_window: r
+                .context_window
+                .map_or("-".into(), |c| format!("{}k", c / 1000)),
+        }
+    }
+}
+
+/// Rank every model that meets the constraints, best first; empty when
+/// none does.
+///
+/// A bound on a metric excludes models without a value for it.
+pub fn recommend(
+    models: &[LlmModel],
+    data: &ModelsData,
+    options: &RecommendOptions,
+) -> Vec<Recommendation> {
+    let candidates: Vec<LlmModel> = models
+        .iter()
+        .filter(|m| within(m.input_price, options.max_input_price, |v, max| v <= max))
+        .filter(|m| within(m.output_price, options.max_output_price, |v, max| v <= max))
+        .filter(|m| within(m.intelligence, options.min_intelligence, |v, min| v >= min))
+        .filter(|m| {
+            data.capable
+                .as_ref()
+                .map_or(true, |keys| keys.contains(&m.slug.to_lowercase()))
+        })
+        .cloned()
+        .collect();
+
+    let scorer = options.weights().scorer(&candidates);
+    let mut scored: Vec<(f64, &LlmModel)> =
+        candidates.iter().map(|m| (scorer.score(m), m)).collect();
+    scored.sort_by(|a, b| b.0.total_cmp(&a.0).then_with(|| a.1.slug.cmp(&b.1.slug)));
+
+    scored
+        .into_iter()
+        .enumerate()
+        .map(|(i, (score, m))| Recommendation {
+            rank: i + 1,
+            slug: m.slug.clone(),
+            name: m.name.clone(),
+            creator: m.creator.clone(),
+            score,
+            intelligence: m.intelligence,
+            input_price: m.input_price,
+            output_price: m.output_price,
+            tps: m.tps,
+            context_window: data
+                .token_limits
+                .get(&m.slug.to_lowercase())
+                .and_then(|limits| limits.context_window),
+            breakdown: if options.explain {
+                scorer
+                    .contributions(m)
+                    .into_iter()
+                    .map(|(metric, weight, points)| ScorePart {
+                        metric: metric.to_string(),
+                        weight,
+                        points,
+                    })
+                    .collect()
+            } else {
+                Vec::new()
+            },
+        })
+        .collect()
+}
+
+/// Table of recommendations with one column per weighted metric's points.
+fn explained_table(recommendations: &[Recommendation]) -> QueryResult {
+    let mut columns: Vec<String> = <RecommendationRow as Formattable>::headers()
+        .iter()
+        .map(|h| h.to_string())
+        .collect();
+    if let Some(first) = recommendations.first() {
+        columns.extend(
+            first
+                .breakdown
+                .iter()
+                .map(|part| format!("{} ({:+})", part.metric, part.weight)),
+        );
+    }
+    let rows = recommendations
+        .iter()
+        .map(|r| {
+            let mut row = RecommendationRow::from(r).to_row();
+            row.extend(
+                r.breakdown
+                    .iter()
+                    .map(|part| format!("{:+.1}", part.points)),
+            );
+            row
+        })
+        .collect();
+    QueryResult { columns, rows }
+}
+
+/// Run the recommend command, reading capabilities and context windows
+/// from the models table in `cache_dir`.
+///
+/// When `count` hides candidates, a note on stderr says how many there are,
+/// unless `quiet`.
+///
+/// # Errors
+///
+/// Returns `AppError::Empty` when no model meets the constraints and
+/// `fail_on_empty` is set.
+pub fn run(
+    models: &[LlmModel],
+    cache_dir: &Path,
+    options: &RecommendOptions,
+    format: OutputFormat,
+    fail_on_empty: bool,
+    quiet: bool,
+) -> Result<()> {
+    let needs = validate_needs(&options.needs)?;
+    let data = ModelsData {
+        capable: if needs.is_empty() {
+            None
+        } else {
+            Some(capable_models(cache_dir, &needs)?)
+        },
+        token_limits: if MODELS.is_cached(cache_dir) {
+            load_token_limits(cache_dir)?
+        } else {
+            HashMap::new()
+        },
+    };
+    let mut recommendations = recommend(models, &data, options);
+    let total = recommendations.len();
+    if let Some(count) = options.count {
+        recommendations.truncate(count);
+    }
+
+    if format == OutputFormat::Json {
+        println!("{}", crate::output::json::format_json(&recommendations));
+    } else if options.explain {
+        println!(
+            "{}",
+            format_query_result(&explained_table(&recommendations), format)
+        );
+    } else {
+        let rows: Vec<RecommendationRow> = recommendations.iter().map(Into::into).collect();
+        println!("{}", format_output(&rows, format));
+    }
+    if !quiet {
+        note_hidden(recommendations.len(), total, "--count");
+    }
+
+    if fail_on_empty && recommendations.is_empty() {
+        return Err(AppError::Empty(
+            "no models meet every constraint; relax a price cap, --min-intelligence, \
+             --min-context or a required capability"
+                .into(),
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::ModelSelection;
+
+    fn make_model(slug: &str, intelligence: f64, price: f64, tps: f64) -> LlmModel {
+        LlmModel {
+            input_price: Some(price),
+            output_price: Some(price * 4.0),
+            tps: Some(tps),
+            ..LlmModel::fixture(slug, intelligence, price * 1.75)
+        }
+    }
+
+    fn models() -> Vec<LlmModel> {
+        vec![
+            make_model("smart", 70.0, 5.0, 50.0),
+            make_model("cheap", 50.0, 0.2, 100.0),
+            make_model("fast", 55.0, 1.0, 300.0),
+            make_model("weak", 30.0, 0.1, 150.0),
+        ]
+    }
+
+    fn options(optimize: Optimize) -> RecommendOptions {
+        RecommendOptions {
+            optimize,
+            count: Some(10),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_optimize_changes_ranking() {
+        let data = ModelsData::default();
+        let top = |optimize| {
+            recommend(&models(), &data, &options(optimize))[0]
+                .slug
+                .clone()
+        };
+        assert_eq!(top(Optimize::Quality), "smart");
+        assert_eq!(top(Optimize::Cost), "cheap");
+        assert_eq!(top(Optimize::Speed), "fast");
+        assert!("fastest".parse::<Optimize>().is_err());
+    }
+
+    #[test]
+    fn test_constraints() {
+        let limits = |context_window| TokenLimits {
+            context_window: Some(context_window),
+            max_output_tokens: None,
+        };
+        let token_limits: HashMap<String, TokenLimits> = [
+            ("fast".to_string(), limits(128_000)),
+            ("smart".to_string(), limits(64_000)),
+        ]
+        .into();
+        let data = ModelsData {
+            capable: Some(["cheap", "fast", "smart"].map(String::from).into()),
+            token_limits: token_limits.clone(),
+        };
+        let selection = ModelSelection {
+            min_context: Some(128_000),
+            token_limits,
+            ..Default::default()
+        };
+        let constrained = RecommendOptions {
+            max_input_price: Some(2.0),
+            min_intelligence: Some(50.0),
+            ..options(Optimize::Balanced)
+        };
+        let picks = recommend(&selection.apply(models()), &data, &constrained);
+        let slugs: Vec<&str> = picks.iter().map(|p| p.slug.as_str()).collect();
+        // cheap has no known context window, smart is too expensive, weak too weak
+        assert_eq!(slugs, vec!["fast"]);
+        assert_eq!(picks[0].context_window, Some(128_000));
+
+        let impossible = RecommendOptions {
+            min_intelligence: Some(90.0),
+            ..options(Optimize::Balanced)
+        };
+        assert!(recommend(&models(), &data, &impossible).is_empty());
+    }
+
+    #[test]
+    fn test_score_profile_and_explain() {
+        let data = ModelsData::default();
+        let cheapest = RecommendOptions {
+            score: Some(ScoreWeights::new("cheapest", [("price".to_string(), -1.0)]).unwrap()),
+            explain: true,
+            ..options(Optimize::Quality)
+        };
+        let picks = recommend(&models(), &data, &cheapest);
+        // The profile overrides --optimize quality
+        assert_eq!(picks[0].slug, "weak");
+
+        let parts = &picks[1].breakdown;
+        assert_eq!(parts.len(), 1);
+        assert_eq!(parts[0].metric, "price");
+        assert!((parts[0].points - picks[1].score).abs() < 1e-9);
+        assert!(recommend(&models(), &data, &options(Optimize::Quality))[0]
+            .breakdown
+            .is_empty());
+
+        let table = explained_table(&picks);
+        assert_eq!(table.columns.last().unwrap(), "price (-1)");
+        assert_eq!(table.rows[0].len(), table.columns.len());
+    }
+}
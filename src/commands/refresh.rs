@@ -1,16 +1,120 @@
 //! Refresh command - fetch fresh data for all tables.
+//!
+//! Tables are written to a staging directory and moved into the cache
+//! together once every required table has been fetched, so an interrupted or
+//! failed refresh leaves the previous tables in place.
 
+use crate::cache::{self, Cache, Staging};
+use crate::cli::invocation;
 use crate::client::{Client, HostedDataClient};
+use crate::commands::stats;
 use crate::config::Config;
-use crate::error::Result;
+use crate::error::{AppError, Result};
+use crate::logging;
+use crate::parquet::{self, ParquetSession};
+use crate::remote::sha256_hex;
+use crate::schema::{BENCHMARK_RANKS, LLMS_HISTORY};
+use crate::snapshot;
+use crate::sources::artificial_analysis::index_version;
+use crate::strict;
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 
 /// Run the refresh command using hosted data client with API fallback.
-pub async fn run(quiet: bool, use_api: bool, profile: Option<&str>) -> Result<()> {
+///
+/// When `snapshot` is set, the refreshed benchmarks are also appended to the
+/// `llms_history` table tagged with today's date, committed with the tables. When `strict` is set, a
+/// failure to refresh any optional table fails before anything is replaced.
+/// Otherwise the tables that refreshed are replaced, a table that failed
+/// keeps its cached copy, and the failures are reported as an error once the
+/// refresh is done.
+pub async fn run(
+    quiet: bool,
+    use_api: bool,
+    profile: Option<&str>,
+    snapshot: bool,
+    strict: bool,
+) -> Result<()> {
+    if cache::is_read_only() {
+        return Err(cache::read_only_error("refresh"));
+    }
+    let config = Config::load()?;
+    let session = ParquetSession::new(&config.parquet)?;
+    let cache = Cache::new()?;
+    let staging = Staging::begin(cache.base_dir())?;
+    let mut failures = refresh_tables(quiet, use_api, profile, &session, staging.dir()).await?;
+    note_failure(
+        &mut failures,
+        "benchmark_ranks",
+        write_ranks(staging.dir(), &session),
+    );
+    if strict {
+        strict::ensure_complete(&failures)?;
+    }
+    if snapshot {
+        write_snapshot(quiet, staging.dir(), &session)?;
+    }
+    staging.commit()?;
+
+    if !quiet {
+        // Informational only; a summary that cannot be computed is skipped
+        if let Ok(Some(summary)) = stats::summarize(cache.base_dir()) {
+            eprintln!("{}", summary);
+        }
+    }
+
+    if !failures.is_empty() {
+        return Err(AppError::Cache(format!(
+            "{} table(s) failed to refresh and kept their cached copy: {}",
+            failures.len(),
+            failures.join("; ")
+        )));
+    }
+    Ok(())
+}
+
+/// Download every table on the first run, before a command that reads data.
+///
+/// With an empty cache, commands would otherwise each fetch just the tables
+/// they need, so the first few runs behave differently. This runs a full
+/// refresh once (with progress) and says where the data lives. Failures are
+/// warnings: the command then fetches or reports missing data as usual.
+pub async fn bootstrap(quiet: bool, use_api: bool, profile: Option<&str>) {
+    if !quiet {
+        match Cache::new() {
+            Ok(cache) => eprintln!(
+                "First run: downloading all tables to {} (later runs use this cache; '{}' updates it)",
+                cache.base_dir().display(),
+                invocation("refresh")
+            ),
+            Err(_) => eprintln!("First run: downloading all tables"),
+        }
+    }
+    if let Err(e) = run(quiet, use_api, profile, false, false).await {
+        if !quiet {
+            eprintln!("Warning: Could not download data ({}).", e);
+        }
+    }
+}
+
+/// Refresh all tables into `dir` from hosted data, falling back to the API
+/// on failure.
+///
+/// Returns the optional refresh steps that failed.
+async fn refresh_tables(
+    quiet: bool,
+    use_api: bool,
+    profile: Option<&str>,
+    session: &ParquetSession,
+    dir: &Path,
+) -> Result<Vec<String>> {
     if use_api {
-        run_with_api(quiet, profile).await
+        run_with_api(quiet, profile, session, dir).await
     } else {
-        match run_with_hosted(quiet).await {
-            Ok(()) => Ok(()),
+        match run_with_hosted(quiet, dir).await {
+            Ok(failures) => Ok(failures),
             Err(e) => {
                 // Fallback to API if hosted data fails and API key is available
                 let config = Config::load()?;
@@ -21,7 +125,7 @@ pub async fn run(quiet: bool, use_api: bool, profile: Option<&str>) -> Result<()
                             e
                         );
                     }
-                    run_with_api(quiet, profile).await
+                    run_with_api(quiet, profile, session, dir).await
                 } else {
                     Err(e)
                 }
@@ -30,59 +134,175 @@ pub async fn run(quiet: bool, use_api: bool, profile: Option<&str>) -> Result<()
     }
 }
 
-/// Refresh using hosted data.
-async fn run_with_hosted(quiet: bool) -> Result<()> {
-    let client = HostedDataClient::new()?;
+/// Checksums of the inputs each derived table was last built from.
+///
+/// Stored as `derived_meta.json` next to the Parquet files.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct DerivedMeta {
+    /// Derived table name -> sha256 of its source file.
+    #[serde(default)]
+    sources: HashMap<String, String>,
+}
+
+impl DerivedMeta {
+    fn path(cache_dir: &Path) -> PathBuf {
+        cache_dir.join("derived_meta.json")
+    }
+
+    /// Load the record, treating a missing or unreadable file as empty.
+    fn load(cache_dir: &Path) -> Self {
+        std::fs::read_to_string(Self::path(cache_dir))
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    fn save(&self, cache_dir: &Path) -> Result<()> {
+        std::fs::write(Self::path(cache_dir), serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+}
+
+/// Derive the `benchmark_ranks` table from the refreshed benchmarks in
+/// `cache_dir`.
+///
+/// Skipped when the benchmarks file is byte-for-byte what the cached ranks
+/// were built from, so a refresh with no upstream changes does no rewrite.
+fn write_ranks(cache_dir: &Path, session: &ParquetSession) -> Result<()> {
+    let cache = Cache::at(cache_dir.to_path_buf());
+    let benchmarks = cache.parquet_path("benchmarks");
+    let ranks = BENCHMARK_RANKS.cache_path(cache.base_dir());
+    let source_hash = sha256_hex(&std::fs::read(&benchmarks)?);
+
+    let mut meta = DerivedMeta::load(cache.base_dir());
+    if ranks.exists() && meta.sources.get(BENCHMARK_RANKS.name) == Some(&source_hash) {
+        logging::debug(
+            "cache",
+            format_args!(
+                "{}: benchmarks unchanged, keeping cached table",
+                BENCHMARK_RANKS.name
+            ),
+        );
+        return Ok(());
+    }
+
+    parquet::write_benchmark_ranks(&benchmarks, &ranks, session)?;
+    meta.sources
+        .insert(BENCHMARK_RANKS.name.to_string(), source_hash);
+    meta.save(cache.base_dir())
+}
+
+/// Append the refreshed benchmarks in `cache_dir` to its `llms_history`
+/// table, recording the intelligence index version when AA reports one.
+fn write_snapshot(quiet: bool, cache_dir: &Path, session: &ParquetSession) -> Result<()> {
+    let cache = Cache::at(cache_dir.to_path_buf());
+    let today = Utc::now().date_naive();
 
-    // Refresh benchmarks
     if !quiet {
-        eprint!("Refreshing benchmarks... ");
+        eprint!("Writing llms_history snapshot... ");
+    }
+    parquet::append_benchmarks_snapshot(
+        &cache.parquet_path("benchmarks"),
+        &LLMS_HISTORY.cache_path(cache.base_dir()),
+        today,
+        session,
+    )?;
+    // The version is informational; a snapshot without one is still usable
+    let index_version = index_version::load_index_version(cache.base_dir())
+        .ok()
+        .flatten();
+    if let Some(version) = &index_version {
+        snapshot::write_index_version(cache.base_dir(), today, version)?;
     }
-    let models = client.get_llm_models(true).await?;
     if !quiet {
-        eprintln!("done ({} models)", models.len());
+        match &index_version {
+            Some(version) => eprintln!(
+                "done ({}, intelligence index {})",
+                today.format("%Y-%m-%d"),
+                version
+            ),
+            None => eprintln!("done ({})", today.format("%Y-%m-%d")),
+        }
     }
 
-    // Refresh models.dev data
+    Ok(())
+}
+
+/// Record the failure of an optional refresh step.
+fn note_failure<T>(failures: &mut Vec<String>, step: &str, result: Result<T>) {
+    if let Err(e) = result {
+        failures.push(format!("{}: {}", step, e));
+    }
+}
+
+/// Refresh into `dir` using hosted data.
+///
+/// The hosted files are independent, so each group is downloaded
+/// concurrently. Each file is verified and written without yielding, so
+/// concurrent downloads never interleave their cache writes.
+async fn run_with_hosted(quiet: bool, dir: &Path) -> Result<Vec<String>> {
+    let client = HostedDataClient::with_cache(Cache::at(dir.to_path_buf()))?;
+    let mut failures = Vec::new();
+
+    // Refresh benchmarks and models.dev data together
     if !quiet {
-        eprint!("Refreshing models... ");
+        eprint!("Refreshing benchmarks and models... ");
     }
-    client.refresh_models().await?;
+    let (benchmarks, extra, models) = tokio::join!(
+        client.get_llm_models(true),
+        client.refresh_llms_extra(),
+        client.refresh_models(),
+    );
+    let benchmarks = benchmarks?;
+    models?;
+    note_failure(&mut failures, "llms_extra", extra);
     if !quiet {
-        eprintln!("done");
+        eprintln!("done ({} models)", benchmarks.len());
     }
 
     // Refresh media tables
     if !quiet {
         eprint!("Refreshing media tables... ");
     }
-    let _ = client.get_text_to_image(true).await;
-    let _ = client.get_image_editing(true).await;
-    let _ = client.get_text_to_speech(true).await;
-    let _ = client.get_text_to_video(true).await;
-    let _ = client.get_image_to_video(true).await;
+    let (image, editing, speech, video, image_video, categories) = tokio::join!(
+        client.get_text_to_image(true),
+        client.get_image_editing(true),
+        client.get_text_to_speech(true),
+        client.get_text_to_video(true),
+        client.get_image_to_video(true),
+        client.refresh_media_categories(),
+    );
+    note_failure(&mut failures, "text_to_image", image);
+    note_failure(&mut failures, "image_editing", editing);
+    note_failure(&mut failures, "text_to_speech", speech);
+    note_failure(&mut failures, "text_to_video", video);
+    note_failure(&mut failures, "image_to_video", image_video);
+    note_failure(&mut failures, "media_categories", categories);
     if !quiet {
         eprintln!("done");
     }
 
     if !quiet {
         eprintln!();
-        eprintln!("All tables refreshed. Use 'which-llm tables' to see available data.");
+        eprintln!(
+            "All tables refreshed. Use '{}' to see available data.",
+            invocation("tables")
+        );
     }
 
-    Ok(())
+    Ok(failures)
 }
 
-/// Refresh using API client.
-async fn run_with_api(quiet: bool, profile: Option<&str>) -> Result<()> {
-    let config = Config::load()?;
-    let api_key = config.get_api_key(profile)?;
-    let profile_name = profile
-        .map(String::from)
-        .or(config.default_profile.clone())
-        .unwrap_or_else(|| "default".into());
-
-    let client = Client::new(api_key, profile_name)?;
+/// Refresh into `dir` using the API client.
+async fn run_with_api(
+    quiet: bool,
+    profile: Option<&str>,
+    session: &ParquetSession,
+    dir: &Path,
+) -> Result<Vec<String>> {
+    let client = Client::for_profile(&Config::load()?, profile, session.clone())?
+        .with_cache(Cache::at(dir.to_path_buf()));
+    let mut failures = Vec::new();
 
     // Refresh benchmarks
     if !quiet {
@@ -106,19 +326,42 @@ async fn run_with_api(quiet: bool, profile: Option<&str>) -> Result<()> {
     if !quiet {
         eprint!("Refreshing media tables... ");
     }
-    let _ = client.get_text_to_image(true).await;
-    let _ = client.get_image_editing(true).await;
-    let _ = client.get_text_to_speech(true).await;
-    let _ = client.get_text_to_video(true).await;
-    let _ = client.get_image_to_video(true).await;
+    note_failure(
+        &mut failures,
+        "text_to_image",
+        client.get_text_to_image(true).await,
+    );
+    note_failure(
+        &mut failures,
+        "image_editing",
+        client.get_image_editing(true).await,
+    );
+    note_failure(
+        &mut failures,
+        "text_to_speech",
+        client.get_text_to_speech(true).await,
+    );
+    note_failure(
+        &mut failures,
+        "text_to_video",
+        client.get_text_to_video(true).await,
+    );
+    note_failure(
+        &mut failures,
+        "image_to_video",
+        client.get_image_to_video(true).await,
+    );
     if !quiet {
         eprintln!("done");
     }
 
     if !quiet {
         eprintln!();
-        eprintln!("All tables refreshed. Use 'which-llm tables' to see available data.");
+        eprintln!(
+            "All tables refreshed. Use '{}' to see available data.",
+            invocation("tables")
+        );
     }
 
-    Ok(())
+    Ok(failures)
 }
@@ -0,0 +1,229 @@
+//! Interactive SQL shell for `query --interactive`.
+//!
+//! Statements may span lines and run once they end with `;`. Lines starting
+//! with `.` are meta commands (`.tables`, `.schema`, `.help`, `.quit`).
+//! History is kept next to the config file, and Tab completes table and
+//! column names from [`ALL_TABLES`].
+
+use crate::cache::Cache;
+use crate::config::Config;
+use crate::error::{AppError, Result};
+use crate::output::OutputFormat;
+use crate::query::{format_query_batches, QueryExecutor};
+use crate::schema::ALL_TABLES;
+use crate::snapshot::Snapshot;
+use rustyline::completion::{Completer, Pair};
+use rustyline::error::ReadlineError;
+use rustyline::highlight::Highlighter;
+use rustyline::hint::Hinter;
+use rustyline::history::FileHistory;
+use rustyline::validate::Validator;
+use rustyline::{Context, Editor, Helper};
+use std::path::PathBuf;
+
+/// Meta commands, completed at the start of a line.
+const META_COMMANDS: &[&str] = &[".exit", ".help", ".quit", ".schema", ".tables"];
+
+const HELP: &str = "\
+End a statement with ';' to run it; it may span several lines.
+  .tables          List tables and whether they are cached
+  .schema TABLE    Show a table's columns
+  .help            Show this help
+  .quit            Exit (or Ctrl-D)
+Ctrl-C discards the statement being typed.";
+
+/// Completion candidates for the word ending at `pos`, with the byte
+/// offset where that word starts.
+fn completions(line: &str, pos: usize) -> (usize, Vec<String>) {
+    let before = &line[..pos];
+    let start = before
+        .char_indices()
+        .rev()
+        .find(|(_, c)| !(c.is_alphanumeric() || *c == '_' || *c == '.'))
+        .map_or(0, |(i, c)| i + c.len_utf8());
+    let word = &before[start..];
+
+    if start == 0 && word.starts_with('.') {
+        let matches = META_COMMANDS
+            .iter()
+            .filter(|command| command.starts_with(word))
+            .map(|command| command.to_string())
+            .collect();
+        return (start, matches);
+    }
+
+    // Complete the column after a table alias, e.g. `b.intel`
+    let (start, word) = match word.rfind('.') {
+        Some(dot) => (start + dot + 1, &word[dot + 1..]),
+        None => (start, word),
+    };
+    if word.is_empty() {
+        return (start, Vec::new());
+    }
+    let prefix = word.to_lowercase();
+    let mut matches: Vec<String> = ALL_TABLES
+        .iter()
+        .flat_map(|table| {
+            std::iter::once(table.name).chain(table.columns.iter().map(|column| column.name))
+        })
+        .filter(|name| name.starts_with(&prefix))
+        .map(String::from)
+        .collect();
+    matches.sort();
+    matches.dedup();
+    (start, matches)
+}
+
+/// Tab completion for the shell.
+struct SqlHelper;
+
+impl Completer for SqlHelper {
+    type Candidate = Pair;
+
+    fn complete(
+        &self,
+        line: &str,
+        pos: usize,
+        _ctx: &Context<'_>,
+    ) -> rustyline::Result<(usize, Vec<Pair>)> {
+        let (start, matches) = completions(line, pos);
+        let candidates = matches
+            .into_iter()
+            .map(|name| Pair {
+                display: name.clone(),
+                replacement: name,
+            })
+            .collect();
+        Ok((start, candidates))
+    }
+}
+
+impl Hinter for SqlHelper {
+    type Hint = String;
+}
+
+impl Highlighter for SqlHelper {}
+
+impl Validator for SqlHelper {}
+
+impl Helper for SqlHelper {}
+
+fn readline_error(e: ReadlineError) -> AppError {
+    AppError::Io(std::io::Error::other(e.to_string()))
+}
+
+/// Where history is kept between sessions.
+fn history_path() -> Result<PathBuf> {
+    Ok(Config::config_path()?.with_file_name("query_history"))
+}
+
+/// Run a meta command. Returns `false` to leave the shell.
+fn meta_command(line: &str) -> Result<bool> {
+    let mut words = line.split_whitespace();
+    match (words.next().unwrap_or_default(), words.next()) {
+        (".quit" | ".exit", _) => return Ok(false),
+        (".tables", _) => super::tables::run(None, false)?,
+        (".schema", Some(table)) => super::tables::run(Some(table), true)?,
+        (".schema", None) => super::tables::run(None, true)?,
+        (".help", _) => println!("{}", HELP),
+        (other, _) => eprintln!("Unknown command '{}'. Type .help for help.", other),
+    }
+    Ok(true)
+}
+
+/// Run the interactive shell until `.quit` or end of input.
+///
+/// With a `snapshot`, the `benchmarks` table is read from that snapshot.
+/// A failing statement prints its error and the shell carries on.
+pub fn run(format: OutputFormat, snapshot: Option<&Snapshot>) -> Result<()> {
+    let cache = Cache::new()?;
+    let mut executor = QueryExecutor::new(cache.base_dir().to_path_buf());
+    if let Some(snapshot) = snapshot {
+        executor = executor.with_benchmarks_snapshot(snapshot.path.clone());
+    }
+
+    let mut editor: Editor<SqlHelper, FileHistory> = Editor::new().map_err(readline_error)?;
+    editor.set_helper(Some(SqlHelper));
+    let history = history_path()?;
+    // No history yet on the first run
+    let _ = editor.load_history(&history);
+
+    eprintln!("Type .help for help, .quit to exit.");
+    let mut statement = String::new();
+    loop {
+        let prompt = if statement.is_empty() {
+            "sql> "
+        } else {
+            " ..> "
+        };
+        let line = match editor.readline(prompt) {
+            Ok(line) => line,
+            Err(ReadlineError::Interrupted) => {
+                statement.clear();
+                continue;
+            }
+            Err(ReadlineError::Eof) => break,
+            Err(e) => return Err(readline_error(e)),
+        };
+
+        let trimmed = line.trim();
+        if statement.is_empty() && trimmed.starts_with('.') {
+            let _ = editor.add_history_entry(trimmed);
+            match meta_command(trimmed) {
+                Ok(true) => continue,
+                Ok(false) => break,
+                Err(e) => {
+                    eprintln!("Error: {}", e);
+                    continue;
+                }
+            }
+        }
+        if trimmed.is_empty() && statement.is_empty() {
+            continue;
+        }
+
+        if !statement.is_empty() {
+            statement.push('\n');
+        }
+        statement.push_str(&line);
+        if !trimmed.ends_with(';') {
+            continue;
+        }
+
+        let _ = editor.add_history_entry(statement.as_str());
+        match executor.execute_batches(&statement) {
+            Ok(result) => println!("{}", format_query_batches(&result, format)),
+            Err(e) => eprintln!("Error: {}", e),
+        }
+        statement.clear();
+    }
+
+    if let Some(dir) = history.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    editor.save_history(&history).map_err(readline_error)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_completions() {
+        let (start, matches) = completions("SELECT * FROM bench", 19);
+        assert_eq!(start, 14);
+        assert!(matches.contains(&"benchmarks".to_string()));
+        assert!(matches.contains(&"benchmark_ranks".to_string()));
+
+        // Columns after an alias, case-insensitively
+        let (start, matches) = completions("SELECT b.INTELL", 15);
+        assert_eq!(start, 9);
+        assert!(matches.contains(&"intelligence".to_string()));
+
+        let (start, matches) = completions(".sch", 4);
+        assert_eq!((start, matches), (0, vec![".schema".to_string()]));
+
+        assert!(completions("SELECT ", 7).1.is_empty());
+    }
+}
@@ -0,0 +1,464 @@
+//! Report command - monthly cost of a team's workloads.
+//!
+//! Workloads are read from a YAML file:
+//!
+//! ```yaml
+//! workloads:
+//!   - name: support-bot
+//!     team: support
+//!     model: gpt-4o
+//!     input_tokens: 2k        # per request
+//!     output_tokens: 500
+//!     requests_per_day: 20000
+//! ```
+//!
+//! Each workload is costed like `cost --period daily` (30-day months,
+//! long-context prices where they apply). The cheapest alternative is the
+//! model with at least the same intelligence index that would cost the
+//! least for the same workload.
+
+use crate::commands::cost::{calculate_cost, format_cost, Period};
+use crate::commands::get::resolve_model;
+use crate::error::{AppError, Result};
+use crate::models::LlmModel;
+use crate::output::{format_output, Formattable, OutputFormat};
+use crate::sources::models_dev::pricing::PriceTier;
+use crate::utils::parse_tokens;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+use tabled::Tabled;
+
+/// Team of workloads that do not name one.
+const NO_TEAM: &str = "unassigned";
+
+/// Token count written as a number or a short form such as `2k`.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+enum TokenCount {
+    Number(u64),
+    Text(String),
+}
+
+impl TokenCount {
+    fn value(&self) -> Result<u64> {
+        match self {
+            TokenCount::Number(n) => Ok(*n),
+            TokenCount::Text(s) => parse_tokens(s),
+        }
+    }
+}
+
+/// One workload as written in the workloads file.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct WorkloadSpec {
+    name: String,
+    #[serde(default)]
+    team: Option<String>,
+    model: String,
+    input_tokens: TokenCount,
+    output_tokens: TokenCount,
+    requests_per_day: u64,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct WorkloadsFile {
+    workloads: Vec<WorkloadSpec>,
+}
+
+/// A workload with its token counts parsed.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Workload {
+    pub name: String,
+    pub team: String,
+    pub model: String,
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+    pub requests_per_day: u64,
+}
+
+/// Parse a workloads file.
+///
+/// # Errors
+///
+/// Returns `AppError::Config` for invalid YAML, unknown keys, bad token
+/// counts, or a file with no workloads.
+pub fn parse_workloads(content: &str) -> Result<Vec<Workload>> {
+    let file: WorkloadsFile = serde_yaml::from_str(content)
+        .map_err(|e| AppError::Config(format!("Invalid workloads file: {}", e)))?;
+    if file.workloads.is_empty() {
+        return Err(AppError::Config(
+            "The workloads file lists no workloads".into(),
+        ));
+    }
+    file.workloads
+        .into_iter()
+        .map(|spec| {
+            let invalid =
+                |e: AppError| AppError::Config(format!("Workload '{}': {}", spec.name, e));
+            Ok(Workload {
+                team: spec.team.clone().unwrap_or_else(|| NO_TEAM.to_string()),
+                input_tokens: spec.input_tokens.value().map_err(invalid)?,
+                output_tokens: spec.output_tokens.value().map_err(invalid)?,
+                name: spec.name,
+                model: spec.model,
+                requests_per_day: spec.requests_per_day,
+            })
+        })
+        .collect()
+}
+
+/// Load and parse the workloads file at `path`.
+pub fn load_workloads(path: &Path) -> Result<Vec<Workload>> {
+    let content = std::fs::read_to_string(path).map_err(|e| {
+        AppError::Config(format!(
+            "Cannot read workloads file {}: {}",
+            path.display(),
+            e
+        ))
+    })?;
+    parse_workloads(&content)
+}
+
+/// A cheaper model for a workload.
+#[derive(Debug, Clone, Serialize)]
+pub struct Alternative {
+    pub model: String,
+    pub monthly_cost: f64,
+    pub monthly_savings: f64,
+}
+
+/// Monthly cost of one workload.
+#[derive(Debug, Clone, Serialize)]
+pub struct WorkloadCost {
+    pub team: String,
+    pub workload: String,
+    pub model: String,
+    pub requests_per_day: u64,
+    /// `None` when the model has no published price.
+    pub monthly_cost: Option<f64>,
+    pub alternative: Option<Alternative>,
+}
+
+/// Monthly total of one team.
+#[derive(Debug, Clone, Serialize)]
+pub struct TeamTotal {
+    pub team: String,
+    pub monthly_cost: f64,
+    pub potential_savings: f64,
+}
+
+/// The consolidated report.
+#[derive(Debug, Clone, Serialize)]
+pub struct Report {
+    pub workloads: Vec<WorkloadCost>,
+    pub teams: Vec<TeamTotal>,
+    pub total_monthly_cost: f64,
+    pub potential_savings: f64,
+    /// Workloads whose model has no published price, left out of totals.
+    pub unpriced: usize,
+}
+
+/// Monthly cost of `workload` on `model`.
+fn monthly_cost(
+    model: &LlmModel,
+    workload: &Workload,
+    tiers: &HashMap<String, PriceTier>,
+) -> Option<f64> {
+    let tier = tiers.get(&model.slug.to_lowercase());
+    calculate_cost(
+        model,
+        tier,
+        workload.input_tokens,
+        workload.output_tokens,
+        workload.requests_per_day,
+        Period::Daily,
+    )
+    .monthly_cost
+}
+
+/// Cheapest model among `candidates` at least as intelligent as `current`
+/// that costs less than `cost` for the workload.
+fn cheapest_alternative(
+    current: &LlmModel,
+    cost: f64,
+    workload: &Workload,
+    candidates: &[LlmModel],
+    tiers: &HashMap<String, PriceTier>,
+) -> Option<Alternative> {
+    let intelligence = current.intelligence?;
+    candidates
+        .iter()
+        .filter(|m| m.slug != current.slug)
+        .filter(|m| m.intelligence.is_some_and(|i| i >= intelligence))
+        .filter(|m| m.input_price.is_some() && m.output_price.is_some())
+        .filter_map(|m| Some((m, monthly_cost(m, workload, tiers)?)))
+        .filter(|(_, alt)| *alt < cost)
+        .min_by(|a, b| a.1.total_cmp(&b.1))
+        .map(|(m, alt)| Alternative {
+            model: m.slug.clone(),
+            monthly_cost: alt,
+            monthly_savings: cost - alt,
+        })
+}
+
+/// Build the report. Alternatives are drawn from `candidates`.
+pub fn build_report(
+    workloads: &[Workload],
+    models: &[LlmModel],
+    candidates: &[LlmModel],
+    tiers: &HashMap<String, PriceTier>,
+) -> Result<Report> {
+    let mut costs = Vec::new();
+    for workload in workloads {
+        let model = resolve_model(models, &workload.model)
+            .map_err(|e| AppError::Config(format!("Workload '{}': {}", workload.name, e)))?;
+        let cost = monthly_cost(model, workload, tiers);
+        costs.push(WorkloadCost {
+            team: workload.team.clone(),
+            workload: workload.name.clone(),
+            model: model.slug.clone(),
+            requests_per_day: workload.requests_per_day,
+            monthly_cost: cost,
+            alternative: cost
+                .and_then(|cost| cheapest_alternative(model, cost, workload, candidates, tiers)),
+        });
+    }
+    // Group by team, keeping the file order within a team
+    costs.sort_by(|a, b| a.team.cmp(&b.team));
+
+    let mut teams: BTreeMap<String, TeamTotal> = BTreeMap::new();
+    for cost in &costs {
+        let team = teams.entry(cost.team.clone()).or_insert(TeamTotal {
+            team: cost.team.clone(),
+            monthly_cost: 0.0,
+            potential_savings: 0.0,
+        });
+        team.monthly_cost += cost.monthly_cost.unwrap_or_default();
+        team.potential_savings += cost
+            .alternative
+            .as_ref()
+            .map_or(0.0, |alt| alt.monthly_savings);
+    }
+    let teams: Vec<TeamTotal> = teams.into_values().collect();
+
+    Ok(Report {
+        total_monthly_cost: teams.iter().map(|t| t.monthly_cost).sum(),
+        potential_savings: teams.iter().map(|t| t.potential_savings).sum(),
+        unpriced: costs.iter().filter(|c| c.monthly_cost.is_none()).count(),
+        workloads: costs,
+        teams,
+    })
+}
+
+/// Report row for table output.
+#[derive(Debug, Clone, Serialize, Tabled)]
+pub struct ReportRow {
+    #[tabled(rename = "Team")]
+    pub team: String,
+    #[tabled(rename = "Workload")]
+    pub workload: String,
+    #[tabled(rename = "Model")]
+    pub model: String,
+    #[tabled(rename = "Requests/Day")]
+    pub requests_per_day: String,
+    #[tabled(rename = "Monthly")]
+    pub monthly_cost: String,
+    #[tabled(rename = "Cheapest Alternative")]
+    pub alternative: String,
+    #[tabled(rename = "Savings")]
+    pub savings: String,
+}
+
+impl Formattable for ReportRow {
+    fn headers() -> &'static [&'static str] {
+        &[
+            "Team",
+            "Workload",
+            "Model",
+            "Requests/Day",
+            "Monthly",
+            "Cheapest Alternative",
+            "Savings",
+        ]
+    }
+
+    fn to_row(&self) -> Vec<String> {
+        vec![
+            self.team.clone(),
+            self.workload.clone(),
+            self.model.clone(),
+            self.requests_per_day.clone(),
+            self.monthly_cost.clone(),
+            self.alternative.clone(),
+            self.savings.clone(),
+        ]
+    }
+}
+
+impl From<&WorkloadCost> for ReportRow {
+    fn from(cost: &WorkloadCost) -> Self {
+        let alternative = cost.alternative.as_ref();
+        Self {
+            team: cost.team.clone(),
+            workload: cost.workload.clone(),
+            model: cost.model.clone(),
+            requests_per_day: cost.requests_per_day.to_string(),
+            monthly_cost: format_cost(cost.monthly_cost),
+            alternative: alternative.map_or("-".to_string(), |alt| {
+                format!("{} ({})", alt.model, format_cost(Some(alt.monthly_cost)))
+            }),
+            savings: alternative.map_or("-".to_string(), |alt| {
+                format_cost(Some(alt.monthly_savings))
+            }),
+        }
+    }
+}
+
+/// Run the report command.
+pub fn run(
+    workloads: &[Workload],
+    models: &[LlmModel],
+    candidates: &[LlmModel],
+    tiers: &HashMap<String, PriceTier>,
+    format: OutputFormat,
+) -> Result<()> {
+    let report = build_report(workloads, models, candidates, tiers)?;
+
+    if format == OutputFormat::Json {
+        println!("{}", crate::output::json::format_json(&report));
+        return Ok(());
+    }
+
+    let rows: Vec<ReportRow> = report.workloads.iter().map(ReportRow::from).collect();
+    println!("{}", format_output(&rows, format));
+    if matches!(format, OutputFormat::Markdown | OutputFormat::Table) {
+        println!();
+        println!("Monthly totals (30 days):");
+        for team in &report.teams {
+            println!(
+                "  {}: {} (could save {})",
+                team.team,
+                format_cost(Some(team.monthly_cost)),
+                format_cost(Some(team.potential_savings))
+            );
+        }
+        println!(
+            "  Total: {} (could save {})",
+            format_cost(Some(report.total_monthly_cost)),
+            format_cost(Some(report.potential_savings))
+        );
+        if report.unpriced > 0 {
+            println!();
+            println!(
+                "{} workload(s) use a model without published prices and are not in the totals.",
+                report.unpriced
+            );
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn model(slug: &str, intelligence: f64, input: f64, output: f64) -> LlmModel {
+        LlmModel {
+            name: slug.into(),
+            slug: slug.into(),
+            intelligence: Some(intelligence),
+            input_price: Some(input),
+            output_price: Some(output),
+            ..Default::default()
+        }
+    }
+
+    fn models() -> Vec<LlmModel> {
+        vec![
+            model("gpt-4o", 50.0, 2.5, 10.0),
+            model("cheap-smart", 55.0, 0.5, 2.0),
+            model("cheaper-dumb", 30.0, 0.1, 0.4),
+            LlmModel {
+                name: "unpriced".into(),
+                slug: "unpriced".into(),
+                ..Default::default()
+            },
+        ]
+    }
+
+    const WORKLOADS: &str = "
+workloads:
+  - name: support-bot
+    team: support
+    model: gpt-4o
+    input_tokens: 1M
+    output_tokens: 100000
+    requests_per_day: 1
+  - name: triage
+    model: cheap-smart
+    input_tokens: 1000000
+    output_tokens: 0
+    requests_per_day: 2
+  - name: experiments
+    team: support
+    model: unpriced
+    input_tokens: 1k
+    output_tokens: 1k
+    requests_per_day: 10
+";
+
+    #[test]
+    fn test_parse_workloads() {
+        let workloads = parse_workloads(WORKLOADS).unwrap();
+        assert_eq!(workloads.len(), 3);
+        assert_eq!(workloads[0].input_tokens, 1_000_000);
+        assert_eq!(workloads[0].output_tokens, 100_000);
+        assert_eq!(workloads[1].team, NO_TEAM);
+
+        assert!(parse_workloads("workloads: []").is_err());
+        assert!(parse_workloads("workloads:\n  - name: x\n    model: y\n").is_err());
+        let err = parse_workloads(
+            "workloads:\n  - {name: x, model: y, input_tokens: lots, output_tokens: 1, requests_per_day: 1}\n",
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("Workload 'x'"));
+    }
+
+    #[test]
+    fn test_build_report() {
+        let workloads = parse_workloads(WORKLOADS).unwrap();
+        let report = build_report(&workloads, &models(), &models(), &HashMap::new()).unwrap();
+
+        let support = &report.workloads[0];
+        assert_eq!(support.team, "support");
+        // (1M * $2.5 + 0.1M * $10) per day, over 30 days
+        assert!((support.monthly_cost.unwrap() - 105.0).abs() < 1e-9);
+        let alternative = support.alternative.as_ref().unwrap();
+        assert_eq!(alternative.model, "cheap-smart");
+        assert!((alternative.monthly_savings - 84.0).abs() < 1e-9);
+
+        // Nothing at least as intelligent is cheaper than cheap-smart
+        let triage = report.workloads.iter().find(|w| w.workload == "triage");
+        assert!(triage.unwrap().alternative.is_none());
+
+        assert_eq!(report.unpriced, 1);
+        assert_eq!(report.teams.len(), 2);
+        assert_eq!(report.teams[0].team, "support");
+        assert!((report.teams[0].monthly_cost - 105.0).abs() < 1e-9);
+        assert!((report.total_monthly_cost - 135.0).abs() < 1e-9);
+        assert!((report.potential_savings - 84.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_unknown_model() {
+        let workloads = parse_workloads(
+            "workloads:\n  - {name: x, model: gpt-9, input_tokens: 1, output_tokens: 1, requests_per_day: 1}\n",
+        )
+        .unwrap();
+        let err = build_report(&workloads, &models(), &models(), &HashMap::new()).unwrap_err();
+        assert!(err.to_string().contains("Workload 'x'"));
+    }
+}
@@ -0,0 +1,461 @@
+//! Route command - recommend a primary/fallback model pair for a task mix.
+//!
+//! Each task in the mix is scored with one benchmark on a 0-100 scale. For a
+//! pair of models, every task is routed to whichever model scores higher on
+//! it; the pair with the best weighted score wins, with the blended cost of
+//! the routing split breaking ties.
+
+use crate::error::{AppError, Result};
+use crate::models::LlmModel;
+use crate::output::{format_output, Formattable, OutputFormat};
+use serde::Serialize;
+use std::str::FromStr;
+use tabled::Tabled;
+
+/// Task names accepted in `--mix`, with the benchmark each is scored on.
+pub const TASKS: &[(&str, &str)] = &[
+    ("coding", "coding index"),
+    ("math", "math index"),
+    ("chat", "intelligence index"),
+    ("science", "GPQA Diamond"),
+    ("knowledge", "MMLU-Pro"),
+];
+
+/// Score a model on one task, on a 0-100 scale.
+fn task_score(model: &LlmModel, task: &str) -> Option<f64> {
+    match task {
+        "coding" => model.coding,
+        "math" => model.math,
+        "chat" => model.intelligence,
+        "science" => model.gpqa.map(|v| v * 100.0),
+        "knowledge" => model.mmlu_pro.map(|v| v * 100.0),
+        _ => None,
+    }
+}
+
+/// Task weights from `--mix`, normalized to sum to 1.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TaskMix {
+    pub weights: Vec<(String, f64)>,
+}
+
+impl FromStr for TaskMix {
+    type Err = AppError;
+
+    /// Parse `task=weight` pairs, e.g. `coding=0.5,chat=0.3,math=0.2`.
+    fn from_str(s: &str) -> Result<Self> {
+        let invalid = |reason: String| {
+            AppError::Config(format!(
+                "Invalid --mix '{}': {}. Use <task>=<weight> pairs, e.g. coding=0.5,chat=0.5.",
+                s, reason
+            ))
+        };
+
+        let mut weights: Vec<(String, f64)> = Vec::new();
+        for part in s.split(',').filter(|p| !p.trim().is_empty()) {
+            let (task, weight) = part
+                .split_once('=')
+                .ok_or_else(|| invalid(format!("'{}' has no weight", part.trim())))?;
+            let task = task.trim().to_lowercase();
+            if !TASKS.iter().any(|(name, _)| *name == task) {
+                let names: Vec<&str> = TASKS.iter().map(|(name, _)| *name).collect();
+                return Err(invalid(format!(
+                    "unknown task '{}' (tasks: {})",
+                    task,
+                    names.join(", ")
+                )));
+            }
+            let weight: f64 = weight
+                .trim()
+                .parse()
+                .ok()
+                .filter(|w: &f64| w.is_finite() && *w > 0.0)
+                .ok_or_else(|| invalid(format!("weight for '{}' must be positive", task)))?;
+            if weights.iter().any(|(name, _)| *name == task) {
+                return Err(invalid(format!("'{}' is listed twice", task)));
+            }
+            weights.push((task, weight));
+        }
+
+        if weights.is_empty() {
+            return Err(invalid("no tasks given".to_string()));
+        }
+        let total: f64 = weights.iter().map(|(_, w)| w).sum();
+        for (_, weight) in &mut weights {
+            *weight /= total;
+        }
+        Ok(Self { weights })
+    }
+}
+
+/// One model's role in a route.
+#[derive(Debug, Clone, Serialize)]
+pub struct RouteAssignment {
+    pub role: &'static str,
+    pub name: String,
+    pub slug: String,
+    /// Fraction of the mix routed to this model.
+    pub share: f64,
+    pub tasks: Vec<String>,
+    /// Blended price per million tokens.
+    pub price: f64,
+}
+
+/// A recommended primary/fallback pair.
+#[derive(Debug, Clone, Serialize)]
+pub struct Route {
+    pub primary: RouteAssignment,
+    pub fallback: Option<RouteAssignment>,
+    /// Weighted 0-100 score of the routed pair.
+    pub weighted_score: f64,
+    /// Best single model and its weighted score, for reference.
+    pub best_single: String,
+    pub best_single_score: f64,
+    /// Share-weighted blended price per million tokens.
+    pub blended_cost: f64,
+}
+
+/// Route row for table output.
+#[derive(Debug, Clone, Serialize, Tabled)]
+pub struct RouteRow {
+    #[tabled(rename = "Role")]
+    pub role: String,
+    #[tabled(rename = "Model")]
+    pub name: String,
+    #[tabled(rename = "Share")]
+    pub share: String,
+    #[tabled(rename = "Tasks")]
+    pub tasks: String,
+    #[tabled(rename = "Price")]
+    pub price: String,
+}
+
+impl Formattable for RouteRow {
+    fn headers() -> &'static [&'static str] {
+        &["Role", "Model", "Share", "Tasks", "Price"]
+    }
+
+    fn to_row(&self) -> Vec<String> {
+        vec![
+            self.role.clone(),
+            self.name.clone(),
+            self.share.clone(),
+            self.tasks.clone(),
+            self.price.clone(),
+        ]
+    }
+}
+
+impl From<&RouteAssignment> for RouteRow {
+    fn from(assignment: &RouteAssignment) -> Self {
+        Self {
+            role: assignment.role.to_string(),
+            name: assignment.name.clone(),
+            share: format!("{:.0}%", assignment.share * 100.0),
+            tasks: if assignment.tasks.is_empty() {
+                "failover only".to_string()
+            } else {
+                assignment.tasks.join(", ")
+            },
+            price: format!("${:.2}", assignment.price),
+        }
+    }
+}
+
+/// A model that can be routed to: priced and scored on every task.
+struct Candidate<'a> {
+    model: &'a LlmModel,
+    price: f64,
+    scores: Vec<f64>,
+    weighted: f64,
+}
+
+/// The best split found between two candidates.
+struct PairRoute {
+    score: f64,
+    cost: f64,
+    primary: usize,
+    fallback: usize,
+    primary_tasks: Vec<usize>,
+    fallback_tasks: Vec<usize>,
+}
+
+/// Build the assignment of `tasks` (indices into the mix) to a candidate.
+fn assignment(
+    role: &'static str,
+    candidate: &Candidate,
+    mix: &TaskMix,
+    tasks: &[usize],
+) -> RouteAssignment {
+    RouteAssignment {
+        role,
+        name: candidate.model.name.clone(),
+        slug: candidate.model.slug.clone(),
+        share: tasks.iter().map(|&t| mix.weights[t].1).sum(),
+        tasks: tasks.iter().map(|&t| mix.weights[t].0.clone()).collect(),
+        price: candidate.price,
+    }
+}
+
+/// Recommend a primary/fallback pair for a task mix.
+///
+/// Only models with a blended price (at most `max_price`) and a score for
+/// every task are considered. When no pair beats the best single model, the
+/// best model takes every task and the runner-up is kept for failover.
+pub fn recommend(models: &[LlmModel], mix: &TaskMix, max_price: Option<f64>) -> Result<Route> {
+    let mut candidates: Vec<Candidate> = models
+        .iter()
+        .filter_map(|model| {
+            let price = model.price?;
+            if max_price.is_some_and(|max| price > max) {
+                return None;
+            }
+            let scores = mix
+                .weights
+                .iter()
+                .map(|(task, _)| task_score(model, task))
+                .collect::<Option<Vec<f64>>>()?;
+            let weighted = scores
+                .iter()
+                .zip(&mix.weights)
+                .map(|(score, (_, w))| score * w)
+                .sum();
+            Some(Candidate {
+                model,
+                price,
+                scores,
+                weighted,
+            })
+        })
+        .collect();
+
+    if candidates.is_empty() {
+        let ceiling = max_price
+            .map(|max| format!(" at or under ${:.2}/M tokens", max))
+            .unwrap_or_default();
+        return Err(AppError::NotFound(format!(
+            "No priced models have scores for every task in the mix{}",
+            ceiling
+        )));
+    }
+
+    // Best single model first; cheaper wins ties
+    candidates.sort_by(|a, b| {
+        b.weighted
+            .total_cmp(&a.weighted)
+            .then(a.price.total_cmp(&b.price))
+    });
+    let all_tasks: Vec<usize> = (0..mix.weights.len()).collect();
+    let best = &candidates[0];
+
+    let mut best_pair: Option<PairRoute> = None;
+    for i in 0..candidates.len() {
+        for j in (i + 1)..candidates.len() {
+            let (a, b) = (&candidates[i], &candidates[j]);
+            let (mut to_a, mut to_b) = (Vec::new(), Vec::new());
+            for t in 0..mix.weights.len() {
+                let a_wins =
+                    a.scores[t] > b.scores[t] || (a.scores[t] == b.scores[t] && a.price <= b.price);
+                if a_wins {
+                    to_a.push(t);
+                } else {
+                    to_b.push(t);
+                }
+            }
+            if to_a.is_empty() || to_b.is_empty() {
+                continue;
+            }
+
+            let share = |tasks: &[usize]| tasks.iter().map(|&t| mix.weights[t].1).sum::<f64>();
+            let score: f64 = to_a
+                .iter()
+                .map(|&t| a.scores[t] * mix.weights[t].1)
+                .chain(to_b.iter().map(|&t| b.scores[t] * mix.weights[t].1))
+                .sum();
+            let cost = share(&to_a) * a.price + share(&to_b) * b.price;
+
+            let better = best_pair.as_ref().map_or(true, |best| {
+                score > best.score + 1e-9
+                    || ((score - best.score).abs() <= 1e-9 && cost < best.cost)
+            });
+            if better {
+                // The model taking the larger share is the primary
+                let (primary, fallback, primary_tasks, fallback_tasks) =
+                    if share(&to_a) >= share(&to_b) {
+                        (i, j, to_a, to_b)
+                    } else {
+                        (j, i, to_b, to_a)
+                    };
+                best_pair = Some(PairRoute {
+                    score,
+                    cost,
+                    primary,
+                    fallback,
+                    primary_tasks,
+                    fallback_tasks,
+                });
+            }
+        }
+    }
+
+    let route = match best_pair {
+        Some(pair) if pair.score > best.weighted + 1e-9 => Route {
+            primary: assignment(
+                "primary",
+                &candidates[pair.primary],
+                mix,
+                &pair.primary_tasks,
+            ),
+            fallback: Some(assignment(
+                "fallback",
+                &candidates[pair.fallback],
+                mix,
+                &pair.fallback_tasks,
+            )),
+            weighted_score: pair.score,
+            best_single: best.model.name.clone(),
+            best_single_score: best.weighted,
+            blended_cost: pair.cost,
+        },
+        _ => Route {
+            primary: assignment("primary", best, mix, &all_tasks),
+            fallback: candidates
+                .get(1)
+                .map(|runner_up| assignment("fallback", runner_up, mix, &[])),
+            weighted_score: best.weighted,
+            best_single: best.model.name.clone(),
+            best_single_score: best.weighted,
+            blended_cost: best.price,
+        },
+    };
+    Ok(route)
+}
+
+/// Run the route command.
+pub fn run(
+    models: &[LlmModel],
+    mix: &TaskMix,
+    max_price: Option<f64>,
+    format: OutputFormat,
+) -> Result<()> {
+    let route = recommend(models, mix, max_price)?;
+
+    if format == OutputFormat::Json {
+        println!("{}", crate::output::json::format_json(&route));
+        return Ok(());
+    }
+
+    let rows: Vec<RouteRow> = std::iter::once(&route.primary)
+        .chain(route.fallback.as_ref())
+        .map(RouteRow::from)
+        .collect();
+
+    match format {
+        OutputFormat::Json => unreachable!(), // Handled above
+        OutputFormat::Csv | OutputFormat::Plain | OutputFormat::Porcelain => {
+            println!("{}", format_output(&rows, format));
+        }
+        OutputFormat::Markdown | OutputFormat::Table => {
+            let mix_text: Vec<String> = mix
+                .weights
+                .iter()
+                .map(|(task, w)| format!("{} {:.0}%", task, w * 100.0))
+                .collect();
+            print!("Routing for {}", mix_text.join(", "));
+            match max_price {
+                Some(max) => println!(" (max ${:.2}/M tokens)", max),
+                None => println!(),
+            }
+            println!();
+            println!("{}", format_output(&rows, format));
+            println!();
+            println!(
+                "Weighted score: {:.1} (best single model: {}, {:.1})",
+                route.weighted_score, route.best_single, route.best_single_score
+            );
+            println!(
+                "Estimated blended cost: ${:.2}/M tokens",
+                route.blended_cost
+            );
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_model(slug: &str, coding: f64, intelligence: f64, price: f64) -> LlmModel {
+        LlmModel {
+            coding: Some(coding),
+            math: Some(50.0),
+            ..LlmModel::fixture(slug, intelligence, price)
+        }
+    }
+
+    #[test]
+    fn test_task_mix_parse() {
+        let mix: TaskMix = "coding=0.5, Chat=0.3,math=0.2".parse().unwrap();
+        assert_eq!(mix.weights.len(), 3);
+        assert_eq!(mix.weights[1].0, "chat");
+        assert!((mix.weights[0].1 - 0.5).abs() < 1e-9);
+
+        // Weights are normalized
+        let mix: TaskMix = "coding=3,chat=1".parse().unwrap();
+        assert!((mix.weights[0].1 - 0.75).abs() < 1e-9);
+
+        for bad in [
+            "",
+            "coding",
+            "coding=0",
+            "poetry=1",
+            "coding=1,coding=2",
+            "chat=x",
+        ] {
+            let err = bad.parse::<TaskMix>().unwrap_err();
+            assert_eq!(err.exit_code(), 2, "{}", bad);
+        }
+    }
+
+    #[test]
+    fn test_recommend_splits_tasks() {
+        let models = vec![
+            make_model("coder", 80.0, 40.0, 10.0),
+            make_model("chatter", 30.0, 70.0, 2.0),
+            make_model("middle", 55.0, 55.0, 5.0),
+        ];
+        let mix: TaskMix = "coding=0.6,chat=0.4".parse().unwrap();
+        let route = recommend(&models, &mix, None).unwrap();
+
+        assert_eq!(route.primary.slug, "coder");
+        assert_eq!(route.primary.tasks, vec!["coding"]);
+        let fallback = route.fallback.unwrap();
+        assert_eq!(fallback.slug, "chatter");
+        assert!((fallback.share - 0.4).abs() < 1e-9);
+        assert!((route.weighted_score - 76.0).abs() < 1e-9);
+        assert!((route.blended_cost - 6.8).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_recommend_price_ceiling_and_failover() {
+        let models = vec![
+            make_model("coder", 80.0, 40.0, 10.0),
+            make_model("chatter", 30.0, 70.0, 2.0),
+            make_model("middle", 55.0, 55.0, 5.0),
+        ];
+        let mix: TaskMix = "coding=1".parse().unwrap();
+
+        // A single task cannot be split; the runner-up is failover only
+        let route = recommend(&models, &mix, Some(6.0)).unwrap();
+        assert_eq!(route.primary.slug, "middle");
+        assert!((route.primary.share - 1.0).abs() < 1e-9);
+        let fallback = route.fallback.unwrap();
+        assert_eq!(fallback.slug, "chatter");
+        assert!(fallback.tasks.is_empty());
+
+        let err = recommend(&models, &mix, Some(1.0)).unwrap_err();
+        assert_eq!(err.exit_code(), 5);
+    }
+}
@@ -0,0 +1,319 @@
+//! Schema command - dump the table schemas and diff them across releases.
+//!
+//! `schema dump` prints every table in [`ALL_TABLES`] with its columns as
+//! JSON. Saving that file and running `schema diff <file>` after an upgrade
+//! lists the tables and columns that were added or removed, and the columns
+//! whose SQL type changed, so downstream pipelines know what to adjust.
+
+use crate::error::{AppError, Result};
+use crate::output::json::format_json;
+use crate::output::{format_output, Formattable, OutputFormat};
+use crate::schema::{TableDef, ALL_TABLES};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::Path;
+use tabled::Tabled;
+
+/// A column in a schema dump.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ColumnDump {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub sql_type: String,
+    pub nullable: bool,
+}
+
+/// A table in a schema dump.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TableDump {
+    pub name: String,
+    pub columns: Vec<ColumnDump>,
+}
+
+/// Every table's columns, tagged with the version that wrote them.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SchemaDump {
+    pub version: String,
+    pub tables: Vec<TableDump>,
+}
+
+impl SchemaDump {
+    /// The schema of this build.
+    pub fn current() -> Self {
+        Self::from_tables(env!("CARGO_PKG_VERSION"), ALL_TABLES)
+    }
+
+    fn from_tables(version: &str, tables: &[&TableDef]) -> Self {
+        Self {
+            version: version.to_string(),
+            tables: tables
+                .iter()
+                .map(|table| TableDump {
+                    name: table.name.to_string(),
+                    columns: table
+                        .columns
+                        .iter()
+                        .map(|column| ColumnDump {
+                            name: column.name.to_string(),
+                            sql_type: column.sql_type.to_string(),
+                            nullable: column.nullable,
+                        })
+                        .collect(),
+                })
+                .collect(),
+        }
+    }
+
+    /// Load a dump saved with `schema dump`.
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path).map_err(|e| {
+            AppError::Config(format!("Cannot read schema file {}: {}", path.display(), e))
+        })?;
+        serde_json::from_str(&content)
+            .map_err(|e| AppError::Config(format!("Invalid schema file {}: {}", path.display(), e)))
+    }
+}
+
+/// What changed about a table or column.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChangeKind {
+    TableRemoved,
+    TableAdded,
+    Removed,
+    Added,
+    Retyped,
+}
+
+impl ChangeKind {
+    fn label(self) -> &'static str {
+        match self {
+            ChangeKind::TableRemoved => "table removed",
+            ChangeKind::TableAdded => "table added",
+            ChangeKind::Removed => "removed",
+            ChangeKind::Added => "added",
+            ChangeKind::Retyped => "retyped",
+        }
+    }
+}
+
+/// One difference between two schemas. Table-level changes have no column.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SchemaChange {
+    pub table: String,
+    pub column: Option<String>,
+    pub change: ChangeKind,
+    pub old_type: Option<String>,
+    pub new_type: Option<String>,
+}
+
+/// Schema change row for table output.
+#[derive(Debug, Clone, Serialize, Tabled)]
+pub struct SchemaChangeRow {
+    #[tabled(rename = "Table")]
+    pub table: String,
+    #[tabled(rename = "Column")]
+    pub column: String,
+    #[tabled(rename = "Change")]
+    pub change: String,
+    #[tabled(rename = "Old Type")]
+    pub old_type: String,
+    #[tabled(rename = "New Type")]
+    pub new_type: String,
+}
+
+impl Formattable for SchemaChangeRow {
+    fn headers() -> &'static [&'static str] {
+        &["Table", "Column", "Change", "Old Type", "New Type"]
+    }
+
+    fn to_row(&self) -> Vec<String> {
+        vec![
+            self.table.clone(),
+            self.column.clone(),
+            self.change.clone(),
+            self.old_type.clone(),
+            self.new_type.clone(),
+        ]
+    }
+}
+
+impl From<&SchemaChange> for SchemaChangeRow {
+    fn from(c: &SchemaChange) -> Self {
+        Self {
+            table: c.table.clone(),
+            column: c.column.clone().unwrap_or_else(|| "-".into()),
+            change: c.change.label().to_string(),
+            old_type: c.old_type.clone().unwrap_or_else(|| "-".into()),
+            new_type: c.new_type.clone().unwrap_or_else(|| "-".into()),
+        }
+    }
+}
+
+/// Changes from `old` to `new`, ordered by table, then column.
+///
+/// A removed or added table is one change; its columns are not listed.
+pub fn diff(old: &SchemaDump, new: &SchemaDump) -> Vec<SchemaChange> {
+    let by_name = |dump: &SchemaDump| -> BTreeMap<String, Vec<ColumnDump>> {
+        dump.tables
+            .iter()
+            .map(|t| (t.name.clone(), t.columns.clone()))
+            .collect()
+    };
+    let (old_tables, new_tables) = (by_name(old), by_name(new));
+    let change = |table: &str, column: Option<&str>, change, old_type, new_type| SchemaChange {
+        table: table.to_string(),
+        column: column.map(String::from),
+        change,
+        old_type,
+        new_type,
+    };
+
+    let mut changes = Vec::new();
+    for (table, old_columns) in &old_tables {
+        let Some(new_columns) = new_tables.get(table) else {
+            changes.push(change(table, None, ChangeKind::TableRemoved, None, None));
+            continue;
+        };
+        for old_column in old_columns {
+            match new_columns.iter().find(|c| c.name == old_column.name) {
+                None => changes.push(change(
+                    table,
+                    Some(old_column.name.as_str()),
+                    ChangeKind::Removed,
+                    Some(old_column.sql_type.clone()),
+                    None,
+                )),
+                Some(new_column) if new_column.sql_type != old_column.sql_type => {
+                    changes.push(change(
+                        table,
+                        Some(old_column.name.as_str()),
+                        ChangeKind::Retyped,
+                        Some(old_column.sql_type.clone()),
+                        Some(new_column.sql_type.clone()),
+                    ))
+                }
+                Some(_) => {}
+            }
+        }
+        for new_column in new_columns {
+            if !old_columns.iter().any(|c| c.name == new_column.name) {
+                changes.push(change(
+                    table,
+                    Some(new_column.name.as_str()),
+                    ChangeKind::Added,
+                    None,
+                    Some(new_column.sql_type.clone()),
+                ));
+            }
+        }
+    }
+    for table in new_tables.keys() {
+        if !old_tables.contains_key(table) {
+            changes.push(change(table, None, ChangeKind::TableAdded, None, None));
+        }
+    }
+
+    changes.sort_by(|a, b| (&a.table, &a.column, a.change).cmp(&(&b.table, &b.column, b.change)));
+    changes
+}
+
+/// Run `schema dump`.
+pub fn run_dump() -> Result<()> {
+    println!("{}", format_json(&SchemaDump::current()));
+    Ok(())
+}
+
+/// Run `schema diff` against the dump saved at `path`.
+pub fn run_diff(path: &Path, format: OutputFormat, fail_on_empty: bool) -> Result<()> {
+    let old = SchemaDump::load(path)?;
+    let changes = diff(&old, &SchemaDump::current());
+
+    if format == OutputFormat::Json {
+        println!("{}", format_json(&changes));
+    } else if changes.is_empty() && matches!(format, OutputFormat::Table | OutputFormat::Markdown) {
+        println!(
+            "No schema changes since {} ({}).",
+            old.version,
+            path.display()
+        );
+    } else {
+        let rows: Vec<SchemaChangeRow> = changes.iter().map(Into::into).collect();
+        println!("{}", format_output(&rows, format));
+    }
+
+    if fail_on_empty && changes.is_empty() {
+        return Err(AppError::Empty("no schema changes".into()));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dump(tables: &[(&str, &[(&str, &str)])]) -> SchemaDump {
+        SchemaDump {
+            version: "0.0.0".into(),
+            tables: tables
+                .iter()
+                .map(|(name, columns)| TableDump {
+                    name: name.to_string(),
+                    columns: columns
+                        .iter()
+                        .map(|(name, sql_type)| ColumnDump {
+                            name: name.to_string(),
+                            sql_type: sql_type.to_string(),
+                            nullable: true,
+                        })
+                        .collect(),
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_diff() {
+        let old = dump(&[
+            (
+                "benchmarks",
+                &[("slug", "VARCHAR"), ("tps", "FLOAT"), ("mmlu", "DOUBLE")],
+            ),
+            ("legacy", &[("id", "VARCHAR")]),
+        ]);
+        let new = dump(&[
+            (
+                "benchmarks",
+                &[("slug", "VARCHAR"), ("tps", "DOUBLE"), ("hle", "DOUBLE")],
+            ),
+            ("models", &[("model_id", "VARCHAR")]),
+        ]);
+
+        let changes: Vec<(String, Option<String>, ChangeKind)> = diff(&old, &new)
+            .into_iter()
+            .map(|c| (c.table, c.column, c.change))
+            .collect();
+        let col = |name: &str| Some(name.to_string());
+        assert_eq!(
+            changes,
+            vec![
+                ("benchmarks".into(), col("hle"), ChangeKind::Added),
+                ("benchmarks".into(), col("mmlu"), ChangeKind::Removed),
+                ("benchmarks".into(), col("tps"), ChangeKind::Retyped),
+                ("legacy".into(), None, ChangeKind::TableRemoved),
+                ("models".into(), None, ChangeKind::TableAdded),
+            ]
+        );
+        assert!(diff(&new, &new).is_empty());
+    }
+
+    #[test]
+    fn test_dump_round_trips() {
+        let current = SchemaDump::current();
+        assert!(current.tables.iter().any(|t| t.name == "benchmarks"));
+        let json = serde_json::to_string(&current).unwrap();
+        assert!(json.contains(r#""type":"VARCHAR""#));
+        let parsed: SchemaDump = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed, current);
+    }
+}
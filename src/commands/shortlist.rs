@@ -0,0 +1,279 @@
+//! Shortlist command - a diversified set of candidates for an evaluation bake-off.
+//!
+//! Candidates are priced models within budget that have every required
+//! capability. Picks go by intelligence, preferring a creator and a price
+//! tier not already on the list, so the shortlist covers different vendors
+//! and price points instead of near-duplicates.
+
+use crate::error::{AppError, Result};
+use crate::models::LlmModel;
+use crate::output::{format_output, Formattable, OutputFormat};
+use crate::sources::models_dev::capabilities::{capable_models, validate_needs};
+use serde::Serialize;
+use std::collections::HashSet;
+use std::path::Path;
+use tabled::Tabled;
+
+/// Price tier names, cheapest first.
+const TIERS: [&str; 3] = ["budget", "mid", "premium"];
+
+/// Options for the shortlist.
+#[derive(Debug, Clone)]
+pub struct ShortlistOptions {
+    /// Maximum blended price in USD per thousand tokens.
+    pub budget_per_1k: Option<f64>,
+    /// Capabilities every pick must have (columns of the `models` table).
+    pub needs: Vec<String>,
+    /// Number of picks.
+    pub count: usize,
+}
+
+/// One shortlisted model with the reason it was picked.
+#[derive(Debug, Clone, Serialize)]
+pub struct Pick {
+    pub slug: String,
+    pub name: String,
+    pub creator: String,
+    pub tier: &'static str,
+    pub intelligence: f64,
+    pub coding: Option<f64>,
+    /// Blended price per million tokens.
+    pub price: f64,
+    pub price_per_1k: f64,
+    pub rationale: String,
+}
+
+/// Shortlist row for table output.
+#[derive(Debug, Clone, Serialize, Tabled)]
+pub struct PickRow {
+    #[tabled(rename = "Model")]
+    pub name: String,
+    #[tabled(rename = "Creator")]
+    pub creator: String,
+    #[tabled(rename = "Tier")]
+    pub tier: String,
+    #[tabled(rename = "Intelligence")]
+    pub intelligence: String,
+    #[tabled(rename = "Price")]
+    pub price: String,
+    #[tabled(rename = "Rationale")]
+    pub rationale: String,
+}
+
+impl Formattable for PickRow {
+    fn headers() -> &'static [&'static str] {
+        &[
+            "Model",
+            "Creator",
+            "Tier",
+            "Intelligence",
+            "Price",
+            "Rationale",
+        ]
+    }
+
+    fn to_row(&self) -> Vec<String> {
+        vec![
+            self.name.clone(),
+            self.creator.clone(),
+            self.tier.clone(),
+            self.intelligence.clone(),
+            self.price.clone(),
+            self.rationale.clone(),
+        ]
+    }
+}
+
+/// Tier of `price` among all candidate `prices`, by tercile.
+fn price_tier(price: f64, prices: &[f64]) -> &'static str {
+    let below = prices.iter().filter(|p| **p < price).count();
+    TIERS[(below * TIERS.len() / prices.len().max(1)).min(TIERS.len() - 1)]
+}
+
+/// Build a diversified shortlist.
+///
+/// Picks are made in up to three passes over candidates sorted by
+/// intelligence: first requiring a new creator and a new price tier, then
+/// only a new creator, then anything left.
+pub fn shortlist(
+    models: &[LlmModel],
+    capable: Option<&HashSet<String>>,
+    options: &ShortlistOptions,
+) -> Result<Vec<Pick>> {
+    let max_price = options.budget_per_1k.map(|budget| budget * 1000.0);
+    let mut candidates: Vec<(&LlmModel, f64, f64)> = models
+        .iter()
+        .filter_map(|m| Some((m, m.intelligence?, m.price?)))
+        .filter(|(_, _, price)| max_price.map_or(true, |max| *price <= max))
+        .filter(|(m, _, _)| capable.map_or(true, |keys| keys.contains(&m.slug.to_lowercase())))
+        .collect();
+
+    if candidates.is_empty() {
+        return Err(AppError::NotFound(
+            "No priced models with an intelligence score match the budget and capabilities"
+                .to_string(),
+        ));
+    }
+
+    candidates.sort_by(|a, b| b.1.total_cmp(&a.1).then(a.2.total_cmp(&b.2)));
+    let prices: Vec<f64> = candidates.iter().map(|(_, _, price)| *price).collect();
+    let needs_text = if options.needs.is_empty() {
+        String::new()
+    } else {
+        format!(", has {}", options.needs.join(" + "))
+    };
+
+    let mut picks: Vec<Pick> = Vec::new();
+    for pass in 0..3 {
+        for (model, intelligence, price) in &candidates {
+            if picks.len() >= options.count {
+                return Ok(picks);
+            }
+            if picks.iter().any(|p| p.slug == model.slug) {
+                continue;
+            }
+            let tier = price_tier(*price, &prices);
+            let new_creator = !picks.iter().any(|p| p.creator == model.creator);
+            let new_tier = !picks.iter().any(|p| p.tier == tier);
+            let eligible = match pass {
+                0 => new_creator && new_tier,
+                1 => new_creator,
+                _ => true,
+            };
+            if !eligible {
+                continue;
+            }
+
+            let reason = if picks.is_empty() {
+                "Top intelligence within budget".to_string()
+            } else if new_creator && new_tier {
+                format!("Best {} pick from a new creator", tier)
+            } else if new_creator {
+                format!("Best remaining model from a new creator ({} tier)", tier)
+            } else {
+                format!("Next-best remaining model ({} tier)", tier)
+            };
+            picks.push(Pick {
+                slug: model.slug.clone(),
+                name: model.name.clone(),
+                creator: model.creator.clone(),
+                tier,
+                intelligence: *intelligence,
+                coding: model.coding,
+                price: *price,
+                price_per_1k: price / 1000.0,
+                rationale: format!(
+                    "{}: intelligence {:.1} at ${:.2}/M tokens{}",
+                    reason, intelligence, price, needs_text
+                ),
+            });
+        }
+    }
+    Ok(picks)
+}
+
+/// Run the shortlist command.
+pub fn run(
+    models: &[LlmModel],
+    cache_dir: &Path,
+    options: &ShortlistOptions,
+    format: OutputFormat,
+) -> Result<()> {
+    let needs = validate_needs(&options.needs)?;
+    let capable = if needs.is_empty() {
+        None
+    } else {
+        Some(capable_models(cache_dir, &needs)?)
+    };
+    let options = ShortlistOptions {
+        needs,
+        ..options.clone()
+    };
+    let picks = shortlist(models, capable.as_ref(), &options)?;
+
+    if format == OutputFormat::Json {
+        println!("{}", crate::output::json::format_json(&picks));
+        return Ok(());
+    }
+
+    let rows: Vec<PickRow> = picks
+        .iter()
+        .map(|p| PickRow {
+            name: p.name.clone(),
+            creator: p.creator.clone(),
+            tier: p.tier.to_string(),
+            intelligence: format!("{:.1}", p.intelligence),
+            price: format!("${:.2}", p.price),
+            rationale: p.rationale.clone(),
+        })
+        .collect();
+    println!("{}", format_output(&rows, format));
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_model(slug: &str, creator: &str, intelligence: f64, price: f64) -> LlmModel {
+        LlmModel {
+            creator: creator.to_string(),
+            ..LlmModel::fixture(slug, intelligence, price)
+        }
+    }
+
+    fn models() -> Vec<LlmModel> {
+        vec![
+            make_model("a-large", "A", 70.0, 10.0),
+            make_model("a-medium", "A", 65.0, 3.0),
+            make_model("b-large", "B", 68.0, 12.0),
+            make_model("c-small", "C", 50.0, 0.2),
+            make_model("d-mini", "D", 40.0, 0.5),
+            make_model("e-medium", "E", 45.0, 2.0),
+        ]
+    }
+
+    fn options(count: usize) -> ShortlistOptions {
+        ShortlistOptions {
+            budget_per_1k: None,
+            needs: vec![],
+            count,
+        }
+    }
+
+    #[test]
+    fn test_shortlist_diversifies_creators_and_tiers() {
+        let picks = shortlist(&models(), None, &options(4)).unwrap();
+        let slugs: Vec<&str> = picks.iter().map(|p| p.slug.as_str()).collect();
+
+        // b-large shares a-large's premium tier, so the budget and mid tiers go first
+        assert_eq!(slugs, vec!["a-large", "c-small", "e-medium", "b-large"]);
+        let tiers: Vec<&str> = picks.iter().map(|p| p.tier).collect();
+        assert_eq!(tiers, vec!["premium", "budget", "mid", "premium"]);
+        assert!(picks[0].rationale.starts_with("Top intelligence"));
+        assert!(picks[1]
+            .rationale
+            .starts_with("Best budget pick from a new creator"));
+        assert!(picks[3].rationale.contains("(premium tier)"));
+    }
+
+    #[test]
+    fn test_shortlist_budget_and_capabilities() {
+        let budget = ShortlistOptions {
+            budget_per_1k: Some(0.005),
+            ..options(5)
+        };
+        let picks = shortlist(&models(), None, &budget).unwrap();
+        assert_eq!(picks.len(), 4);
+        assert!(picks.iter().all(|p| p.price <= 5.0));
+
+        let capable: HashSet<String> = ["d-mini".to_string()].into_iter().collect();
+        let picks = shortlist(&models(), Some(&capable), &options(3)).unwrap();
+        assert_eq!(picks.len(), 1);
+        assert_eq!(picks[0].slug, "d-mini");
+
+        let none: HashSet<String> = HashSet::new();
+        let err = shortlist(&models(), Some(&none), &options(3)).unwrap_err();
+        assert_eq!(err.exit_code(), 5);
+    }
+}
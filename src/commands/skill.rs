@@ -1,5 +1,6 @@
 //! Skill installation commands for AI coding tools.
 
+use crate::cli::invocation;
 use crate::error::{AppError, Result};
 use std::fs;
 use std::io::Cursor;
@@ -101,9 +102,7 @@ impl Tool {
 
 /// Get the cache directory for skills.
 fn cache_dir() -> Result<PathBuf> {
-    let cache = dirs::cache_dir()
-        .ok_or_else(|| AppError::Config("Could not determine cache directory".into()))?;
-    Ok(cache.join("which-llm/skills"))
+    Ok(crate::cache::Cache::new()?.base_dir().join("skills"))
 }
 
 /// Get the path to the cached skills.zip.
@@ -170,6 +169,9 @@ async fn get_skills_zip(force_refresh: bool) -> Result<Vec<u8>> {
     let data = download_skills_zip().await?;
 
     // Cache the downloaded zip
+    if crate::cache::is_read_only() {
+        return Ok(data);
+    }
     let cache_dir = cache_dir()?;
     fs::create_dir_all(&cache_dir)?;
     fs::write(&zip_path, &data)
@@ -258,8 +260,9 @@ fn extract_skill_to(zip_data: &[u8], target_dir: &Path, dry_run: bool) -> Result
 pub async fn install(tool_name: &str, global: bool, force: bool, dry_run: bool) -> Result<()> {
     let tool = Tool::parse(tool_name).ok_or_else(|| {
         AppError::Config(format!(
-            "Unknown tool '{}'. Run 'which-llm skill list' to see supported tools.",
-            tool_name
+            "Unknown tool '{}'. Run '{}' to see supported tools.",
+            tool_name,
+            invocation("skill list")
         ))
     })?;
 
@@ -320,8 +323,9 @@ pub async fn install(tool_name: &str, global: bool, force: bool, dry_run: bool)
 pub fn uninstall(tool_name: &str, global: bool) -> Result<()> {
     let tool = Tool::parse(tool_name).ok_or_else(|| {
         AppError::Config(format!(
-            "Unknown tool '{}'. Run 'which-llm skill list' to see supported tools.",
-            tool_name
+            "Unknown tool '{}'. Run '{}' to see supported tools.",
+            tool_name,
+            invocation("skill list")
         ))
     })?;
 
@@ -380,8 +384,14 @@ pub fn list() -> Result<()> {
     }
 
     println!("\nUsage:");
-    println!("  which-llm skill install <tool>          Install for current project");
-    println!("  which-llm skill install <tool> --global Install globally");
+    println!(
+        "  {:<39} Install for current project",
+        invocation("skill install <tool>")
+    );
+    println!(
+        "  {:<39} Install globally",
+        invocation("skill install <tool> --global")
+    );
 
     Ok(())
 }
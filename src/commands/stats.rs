@@ -0,0 +1,306 @@
+//! Stats command - dataset-wide aggregate statistics.
+//!
+//! Summarizes the cached benchmarks table: model counts and share per
+//! creator, median prices, intelligence percentiles, and how many models
+//! have a matching models.dev entry.
+
+use crate::cache::Cache;
+use crate::error::{AppError, Result};
+use crate::output::json::format_json;
+use crate::output::OutputFormat;
+use crate::query::{format_query_result, QueryExecutor, QueryResult};
+use crate::schema::BENCHMARKS;
+use crate::sources::MODELS;
+use serde_json::{json, Map, Value};
+use std::fmt;
+use std::path::Path;
+
+/// 1 for a benchmarks row with a models.dev entry, else 0.
+///
+/// Uses the same slug normalization as the open view (provider prefix
+/// stripped, lowercased).
+const MATCHED: &str = "CASE WHEN lower(slug) IN (
+        SELECT lower(regexp_replace(model_id, '^.*/', '')) FROM models
+    ) THEN 1 ELSE 0 END";
+
+/// Build the SQL for the single-row overview.
+///
+/// The models.dev match rate is only computed when the models table is
+/// cached.
+fn overview_sql(with_models: bool) -> String {
+    let match_rate = if with_models {
+        format!(
+            ",
+    CAST(100.0 * sum({}) / count(*) AS DOUBLE) AS models_dev_match_pct",
+            MATCHED
+        )
+    } else {
+        String::new()
+    };
+
+    format!(
+        "SELECT
+    count(*) AS model_count,
+    count(DISTINCT creator) AS creator_count,
+    median(input_price) AS median_input_price,
+    median(output_price) AS median_output_price,
+    median(price) AS median_price,
+    quantile_cont(intelligence, 0.25) AS intelligence_p25,
+    quantile_cont(intelligence, 0.5) AS intelligence_p50,
+    quantile_cont(intelligence, 0.75) AS intelligence_p75,
+    quantile_cont(intelligence, 0.9) AS intelligence_p90{}
+FROM benchmarks",
+        match_rate
+    )
+}
+
+/// SQL for per-creator counts, share of all models, and price/score summary.
+const CREATORS_SQL: &str = "SELECT
+    creator,
+    count(*) AS model_count,
+    CAST(100.0 * count(*) / sum(count(*)) OVER () AS DOUBLE) AS share_pct,
+    median(price) AS median_price,
+    max(intelligence) AS best_intelligence
+FROM benchmarks
+GROUP BY creator
+ORDER BY model_count DESC, creator";
+
+/// Headline counts for the cached data, shown after `refresh` and by `info`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DataSummary {
+    /// Models in the benchmarks table.
+    pub models: u64,
+    /// Models with a models.dev entry, when the models table is cached.
+    pub matched: Option<u64>,
+    /// Distinct model creators.
+    pub creators: u64,
+}
+
+impl DataSummary {
+    /// Share of models matched to models.dev, in percent.
+    pub fn match_pct(&self) -> Option<f64> {
+        let matched = self.matched?;
+        (self.models > 0).then(|| 100.0 * matched as f64 / self.models as f64)
+    }
+
+    /// The summary as a JSON object.
+    pub fn to_json(&self) -> Value {
+        json!({
+            "models": self.models,
+            "matched_to_models_dev": self.matched,
+            "match_pct": self.match_pct(),
+            "creators": self.creators,
+        })
+    }
+}
+
+impl fmt::Display for DataSummary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} AA models", self.models)?;
+        if let (Some(matched), Some(pct)) = (self.matched, self.match_pct()) {
+            write!(f, ", {} matched to models.dev ({:.0}%)", matched, pct)?;
+        }
+        write!(f, ", {} creators", self.creators)
+    }
+}
+
+/// Summarize the cache in `cache_dir`, or `None` if benchmarks are not cached.
+pub fn summarize(cache_dir: &Path) -> Result<Option<DataSummary>> {
+    if !BENCHMARKS.is_cached(cache_dir) {
+        return Ok(None);
+    }
+
+    let with_models = MODELS.is_cached(cache_dir);
+    let matched = if with_models {
+        format!("sum({})", MATCHED)
+    } else {
+        "NULL".to_string()
+    };
+    let executor = QueryExecutor::new(cache_dir.to_path_buf());
+    let result = executor.execute(&format!(
+        "SELECT count(*), count(DISTINCT creator), {} FROM benchmarks",
+        matched
+    ))?;
+    let Some(row) = result.rows.first() else {
+        return Ok(None);
+    };
+
+    Ok(Some(DataSummary {
+        models: row[0].parse().unwrap_or_default(),
+        creators: row[1].parse().unwrap_or_default(),
+        // An empty table sums to NULL
+        matched: with_models.then(|| row[2].parse().unwrap_or_default()),
+    }))
+}
+
+/// Overview and per-creator results.
+struct Stats {
+    overview: QueryResult,
+    creators: QueryResult,
+}
+
+/// Compute statistics from the cache in `cache_dir`.
+fn compute_stats(cache_dir: &Path) -> Result<Stats> {
+    if !BENCHMARKS.is_cached(cache_dir) {
+        return Err(AppError::Query(format!(
+            "Table '{}' not found. Run '{}' first to fetch and cache the data.",
+            BENCHMARKS.name,
+            BENCHMARKS.command_line()
+        )));
+    }
+
+    let executor = QueryExecutor::new(cache_dir.to_path_buf());
+    Ok(Stats {
+        overview: executor.execute(&overview_sql(MODELS.is_cached(cache_dir)))?,
+        creators: executor.execute(CREATORS_SQL)?,
+    })
+}
+
+/// Convert result rows into JSON objects keyed by column name.
+fn rows_to_json(result: &QueryResult) -> Vec<Value> {
+    result
+        .rows
+        .iter()
+        .map(|row| {
+            let object: Map<String, Value> = result
+                .columns
+                .iter()
+                .zip(row)
+                .map(|(column, value)| (column.clone(), Value::String(value.clone())))
+                .collect();
+            Value::Object(object)
+        })
+        .collect()
+}
+
+/// Render statistics as one JSON document.
+fn stats_json(stats: &Stats) -> Value {
+    json!({
+        "overview": rows_to_json(&stats.overview).into_iter().next().unwrap_or(Value::Null),
+        "creators": rows_to_json(&stats.creators),
+    })
+}
+
+/// Run the stats command.
+pub fn run(format: OutputFormat) -> Result<()> {
+    let cache = Cache::new()?;
+    let stats = compute_stats(cache.base_dir())?;
+
+    match format {
+        OutputFormat::Json => {
+            println!("{}", format_json(&stats_json(&stats)));
+        }
+        OutputFormat::Csv | OutputFormat::Plain | OutputFormat::Porcelain => {
+            // Scripted formats get the per-creator rows only; the overview
+            // is a different shape and would not parse as one table.
+            println!("{}", format_query_result(&stats.creators, format));
+        }
+        OutputFormat::Markdown | OutputFormat::Table => {
+            println!("Overview");
+            println!();
+            println!("{}", format_query_result(&stats.overview, format));
+            println!();
+            println!("By creator");
+            println!();
+            println!("{}", format_query_result(&stats.creators, format));
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use duckdb::Connection;
+    use tempfile::TempDir;
+
+    fn write_parquet(path: &Path, select: &str) {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute(
+            &format!(
+                "COPY ({}) TO '{}' (FORMAT PARQUET)",
+                select,
+                path.to_string_lossy()
+            ),
+            [],
+        )
+        .unwrap();
+    }
+
+    fn write_benchmarks(dir: &Path) {
+        write_parquet(
+            &dir.join("benchmarks.parquet"),
+            "SELECT * FROM (VALUES
+                ('gpt-4o', 'OpenAI', 40.0::DOUBLE, 2.5::DOUBLE, 10.0::DOUBLE, 4.38::DOUBLE),
+                ('gpt-5', 'OpenAI', 68.0, 1.25, 10.0, 3.44),
+                ('claude-4-5-sonnet', 'Anthropic', 63.0, 3.0, 15.0, 6.0),
+                ('llama-3-3-70b', 'Meta', 41.0, 0.6, 0.7, 0.63)
+            ) t(slug, creator, intelligence, input_price, output_price, price)",
+        );
+    }
+
+    #[test]
+    fn test_stats_overview_and_creators() {
+        let temp_dir = TempDir::new().unwrap();
+        write_benchmarks(temp_dir.path());
+        write_parquet(
+            &temp_dir.path().join("models.parquet"),
+            "SELECT * FROM (VALUES ('openai/gpt-5'), ('llama-3-3-70b')) t(model_id)",
+        );
+
+        let stats = compute_stats(temp_dir.path()).unwrap();
+        let overview = stats_json(&stats)["overview"].clone();
+        assert_eq!(overview["model_count"], "4");
+        assert_eq!(overview["creator_count"], "3");
+        assert_eq!(overview["median_input_price"], "1.88");
+        assert_eq!(overview["intelligence_p50"], "52.00");
+        assert_eq!(overview["models_dev_match_pct"], "50.00");
+
+        assert_eq!(stats.creators.rows[0][0], "OpenAI");
+        assert_eq!(stats.creators.rows[0][1], "2");
+        assert_eq!(stats.creators.rows[0][2], "50.00");
+        assert_eq!(stats.creators.rows[0][4], "68.00");
+    }
+
+    #[test]
+    fn test_summarize() {
+        let temp_dir = TempDir::new().unwrap();
+        assert_eq!(summarize(temp_dir.path()).unwrap(), None);
+
+        write_benchmarks(temp_dir.path());
+        let summary = summarize(temp_dir.path()).unwrap().unwrap();
+        assert_eq!(summary.matched, None);
+        assert_eq!(summary.to_string(), "4 AA models, 3 creators");
+
+        write_parquet(
+            &temp_dir.path().join("models.parquet"),
+            "SELECT * FROM (VALUES ('openai/gpt-5'), ('llama-3-3-70b')) t(model_id)",
+        );
+        let summary = summarize(temp_dir.path()).unwrap().unwrap();
+        assert_eq!(
+            summary.to_string(),
+            "4 AA models, 2 matched to models.dev (50%), 3 creators"
+        );
+        assert_eq!(summary.to_json()["match_pct"], 50.0);
+    }
+
+    #[test]
+    fn test_stats_without_models_table() {
+        let temp_dir = TempDir::new().unwrap();
+        write_benchmarks(temp_dir.path());
+
+        let stats = compute_stats(temp_dir.path()).unwrap();
+        assert!(!stats
+            .overview
+            .columns
+            .contains(&"models_dev_match_pct".to_string()));
+    }
+
+    #[test]
+    fn test_stats_requires_benchmarks() {
+        let temp_dir = TempDir::new().unwrap();
+        let err = compute_stats(temp_dir.path()).err().unwrap().to_string();
+        assert!(err.contains("which-llm refresh"));
+    }
+}
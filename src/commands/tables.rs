@@ -1,11 +1,15 @@
 //! Tables command - list available tables and their schemas.
 
 use crate::cache::Cache;
+use crate::cli::invocation;
 use crate::error::Result;
 use crate::query::{format_tables_list, QueryExecutor};
 
 /// Run the tables command.
-pub fn run(table_name: Option<&str>) -> Result<()> {
+///
+/// A single table's details always include column descriptions; the list of
+/// all tables includes them with `verbose`.
+pub fn run(table_name: Option<&str>, verbose: bool) -> Result<()> {
     let cache = Cache::new()?;
     let executor = QueryExecutor::new(cache.base_dir().to_path_buf());
 
@@ -29,11 +33,12 @@ pub fn run(table_name: Option<&str>) -> Result<()> {
                         col.data_type,
                         if col.nullable { "NULL" } else { "NOT NULL" }
                     );
+                    println!("      {}", col.description);
                 }
             }
             if !info.exists {
                 println!();
-                println!("Run 'which-llm refresh' to cache this table.");
+                println!("Run '{}' to cache this table.", invocation("refresh"));
             }
         } else {
             eprintln!("Table '{}' not found.", name);
@@ -45,7 +50,7 @@ pub fn run(table_name: Option<&str>) -> Result<()> {
         }
     } else {
         // List all tables
-        println!("{}", format_tables_list(&table_info));
+        println!("{}", format_tables_list(&table_info, verbose));
     }
 
     Ok(())
@@ -0,0 +1,172 @@
+//! Tokens command - token count conversions and estimates.
+//!
+//! Converts counts like `1.5M` to exact numbers and back, or estimates the
+//! token count of a file from its characters, using the model's tokenizer
+//! ratio when `--model` is given.
+
+use crate::error::{AppError, Result};
+use crate::models::LlmModel;
+use crate::output::{format_output, Formattable, OutputFormat};
+use crate::utils::{chars_per_token, estimate_tokens, format_tokens, parse_tokens};
+use serde::Serialize;
+use std::io::Read;
+use tabled::Tabled;
+
+/// One converted token count.
+#[derive(Debug, Clone, Serialize, Tabled)]
+pub struct TokenRow {
+    #[tabled(rename = "Input")]
+    pub input: String,
+    #[tabled(rename = "Tokens")]
+    pub tokens: u64,
+    #[tabled(rename = "Short")]
+    pub short: String,
+}
+
+impl Formattable for TokenRow {
+    fn headers() -> &'static [&'static str] {
+        &["Input", "Tokens", "Short"]
+    }
+
+    fn to_row(&self) -> Vec<String> {
+        vec![
+            self.input.clone(),
+            self.tokens.to_string(),
+            self.short.clone(),
+        ]
+    }
+}
+
+/// Token estimate for a file.
+#[derive(Debug, Clone, Serialize, Tabled)]
+pub struct EstimateRow {
+    #[tabled(rename = "Source")]
+    pub source: String,
+    #[tabled(rename = "Characters")]
+    pub characters: u64,
+    /// Model whose tokenizer ratio was used, or `default`.
+    #[tabled(rename = "Basis")]
+    pub basis: String,
+    #[tabled(rename = "Chars/Token")]
+    pub chars_per_token: f64,
+    #[tabled(rename = "Tokens (est.)")]
+    pub tokens: u64,
+    #[tabled(rename = "Short")]
+    pub short: String,
+}
+
+impl Formattable for EstimateRow {
+    fn headers() -> &'static [&'static str] {
+        &[
+            "Source",
+            "Characters",
+            "Basis",
+            "Chars/Token",
+            "Tokens (est.)",
+            "Short",
+        ]
+    }
+
+    fn to_row(&self) -> Vec<String> {
+        vec![
+            self.source.clone(),
+            self.characters.to_string(),
+            self.basis.clone(),
+            format!("{:.1}", self.chars_per_token),
+            self.tokens.to_string(),
+            self.short.clone(),
+        ]
+    }
+}
+
+/// Convert each count to its exact and short forms.
+pub fn convert(counts: &[String]) -> Result<Vec<TokenRow>> {
+    counts
+        .iter()
+        .map(|count| {
+            let tokens = parse_tokens(count)?;
+            Ok(TokenRow {
+                input: count.trim().to_string(),
+                tokens,
+                short: format_tokens(tokens),
+            })
+        })
+        .collect()
+}
+
+/// Estimate the tokens in `text`, with `model`'s ratio when given.
+pub fn estimate(source: &str, text: &str, model: Option<&LlmModel>) -> EstimateRow {
+    let characters = text.chars().count() as u64;
+    let ratio = chars_per_token(model.and_then(|m| m.creator_slug.as_deref()));
+    let tokens = estimate_tokens(characters, ratio);
+    EstimateRow {
+        source: source.to_string(),
+        characters,
+        basis: model.map_or("default".to_string(), |m| m.slug.clone()),
+        chars_per_token: ratio,
+        tokens,
+        short: format_tokens(tokens),
+    }
+}
+
+/// Read a file, or stdin for `-`, as text (invalid UTF-8 is replaced).
+fn read_text(path: &str) -> Result<String> {
+    let bytes = if path == "-" {
+        let mut bytes = Vec::new();
+        std::io::stdin().read_to_end(&mut bytes)?;
+        bytes
+    } else {
+        std::fs::read(path)
+            .map_err(|e| AppError::Config(format!("Cannot read '{}': {}", path, e)))?
+    };
+    Ok(String::from_utf8_lossy(&bytes).into_owned())
+}
+
+/// Run the tokens command for counts.
+pub fn run(counts: &[String], format: OutputFormat) -> Result<()> {
+    println!("{}", format_output(&convert(counts)?, format));
+    Ok(())
+}
+
+/// Run the tokens command for a file's characters.
+pub fn run_estimate(path: &str, model: Option<&LlmModel>, format: OutputFormat) -> Result<()> {
+    let text = read_text(path)?;
+    let source = if path == "-" { "stdin" } else { path };
+    println!(
+        "{}",
+        format_output(&[estimate(source, &text, model)], format)
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_convert() {
+        let rows = convert(&["1.5M".to_string(), " 2500 ".to_string()]).unwrap();
+        assert_eq!(rows[0].tokens, 1_500_000);
+        assert_eq!(rows[0].short, "1.5M");
+        assert_eq!(rows[1].input, "2500");
+        assert_eq!(rows[1].short, "2.5K");
+        assert!(convert(&["lots".to_string()]).is_err());
+    }
+
+    #[test]
+    fn test_estimate() {
+        let row = estimate("notes.txt", "héllo world!", None);
+        assert_eq!(row.characters, 12);
+        assert_eq!(row.basis, "default");
+        assert_eq!(row.tokens, 3);
+
+        let model = LlmModel {
+            slug: "claude-4-5-sonnet".into(),
+            creator_slug: Some("anthropic".into()),
+            ..Default::default()
+        };
+        let row = estimate("notes.txt", &"a".repeat(700), Some(&model));
+        assert_eq!(row.basis, "claude-4-5-sonnet");
+        assert_eq!(row.tokens, 200);
+    }
+}
@@ -0,0 +1,299 @@
+//! Trend command - how a metric moved per model across stored snapshots.
+//!
+//! Fits a least-squares line through each model's values in the
+//! `llms_history` snapshots within the window and lists the models improving
+//! fastest: prices and latency falling, scores and speed rising. Models are
+//! ranked by their change per 30 days relative to their average value, so
+//! a cheap model halving its price ranks above an expensive one shaving a
+//! few cents.
+
+use crate::cache::Cache;
+use crate::cli::invocation;
+use crate::error::{AppError, Result};
+use crate::output::{note_hidden, OutputFormat};
+use crate::query::{format_query_result, QueryExecutor, QueryResult};
+use crate::snapshot;
+use chrono::NaiveDate;
+use std::path::Path;
+
+/// Metrics a trend can be fitted for, with whether lower values are better.
+pub const TREND_METRICS: &[(&str, bool)] = &[
+    ("intelligence", false),
+    ("coding", false),
+    ("math", false),
+    ("mmlu_pro", false),
+    ("gpqa", false),
+    ("hle", false),
+    ("livecodebench", false),
+    ("scicode", false),
+    ("math_500", false),
+    ("aime", false),
+    ("input_price", true),
+    ("output_price", true),
+    ("price", true),
+    ("tps", false),
+    ("latency", true),
+];
+
+/// Index metrics whose scores change meaning when AA re-bases the index.
+const INDEX_METRICS: &[&str] = &["intelligence", "coding", "math"];
+
+/// Look up a trend metric, returning its name and whether lower is better.
+fn trend_metric(name: &str) -> Result<(&'static str, bool)> {
+    let lower = name.trim().to_lowercase();
+    TREND_METRICS
+        .iter()
+        .find(|(metric, _)| *metric == lower)
+        .copied()
+        .ok_or_else(|| {
+            let names: Vec<&str> = TREND_METRICS.iter().map(|(m, _)| *m).collect();
+            AppError::Config(format!(
+                "Unknown trend metric '{}'. Available metrics: {}",
+                name,
+                names.join(", ")
+            ))
+        })
+}
+
+/// Build the SQL fitting `metric` per model over snapshots since `since`.
+///
+/// Only models with values on at least two snapshot dates that moved in the
+/// improving direction are listed, fastest first.
+fn trend_sql(
+    metric: &str,
+    lower_is_better: bool,
+    since: NaiveDate,
+    limit: Option<usize>,
+) -> String {
+    let (improving, order) = if lower_is_better {
+        ("< 0", "ASC")
+    } else {
+        ("> 0", "DESC")
+    };
+    let limit = limit
+        .map(|limit| format!("\nLIMIT {}", limit))
+        .unwrap_or_default();
+    format!(
+        "WITH points AS (
+    SELECT slug, name, creator, snapshot_date,
+        date_diff('day', DATE '{since}', snapshot_date) AS day,
+        CAST({metric} AS DOUBLE) AS value
+    FROM llms_history
+    WHERE snapshot_date >= DATE '{since}' AND {metric} IS NOT NULL
+),
+fits AS (
+    SELECT
+        slug,
+        arg_max(name, snapshot_date) AS name,
+        arg_max(creator, snapshot_date) AS creator,
+        count(DISTINCT snapshot_date) AS snapshots,
+        arg_min(value, snapshot_date) AS start_value,
+        arg_max(value, snapshot_date) AS end_value,
+        regr_slope(value, day) * 30 AS slope_per_30d,
+        avg(value) AS mean_value
+    FROM points
+    GROUP BY slug
+)
+SELECT
+    slug AS model,
+    name,
+    creator,
+    snapshots,
+    start_value,
+    end_value,
+    slope_per_30d,
+    CASE WHEN mean_value > 0 THEN 100 * slope_per_30d / mean_value END AS change_pct_per_30d
+FROM fits
+WHERE snapshots >= 2 AND slope_per_30d {improving}
+ORDER BY change_pct_per_30d {order} NULLS LAST, slope_per_30d {order}, model{limit}"
+    )
+}
+
+/// Fit trends for `metric` over the snapshots in `cache_dir` taken on or
+/// after `since`.
+///
+/// # Errors
+///
+/// Returns `AppError::Config` for an unknown metric and `AppError::NotFound`
+/// when fewer than two snapshots fall within the window.
+fn query_trends(
+    cache_dir: &Path,
+    metric: &str,
+    since: NaiveDate,
+    limit: Option<usize>,
+) -> Result<QueryResult> {
+    let (metric, lower_is_better) = trend_metric(metric)?;
+    let dates = snapshot::snapshot_dates(cache_dir);
+    let in_window = dates.iter().filter(|d| **d >= since).count();
+    if in_window < 2 {
+        return Err(AppError::NotFound(format!(
+            "A trend needs at least two snapshots since {}, but {} stored ({} in the window). \
+            Snapshots are saved by '{}'.",
+            since,
+            match dates.len() {
+                1 => "1 is".to_string(),
+                n => format!("{} are", n),
+            },
+            in_window,
+            invocation("refresh --snapshot")
+        )));
+    }
+    let executor = QueryExecutor::new(cache_dir.to_path_buf());
+    executor.execute(&trend_sql(metric, lower_is_better, since, limit))
+}
+
+/// Run the trend command.
+///
+/// Unless `quiet`, warns when an index metric's snapshots span a re-basing
+/// of the intelligence index, and notes how many rows `limit` hid.
+pub fn run(
+    metric: &str,
+    since: NaiveDate,
+    limit: Option<usize>,
+    format: OutputFormat,
+    quiet: bool,
+    fail_on_empty: bool,
+) -> Result<()> {
+    let cache = Cache::new()?;
+    let result = query_trends(cache.base_dir(), metric, since, limit)?;
+    if !quiet && INDEX_METRICS.contains(&metric.trim().to_lowercase().as_str()) {
+        let versions: Vec<_> = snapshot::index_versions(cache.base_dir())
+            .into_iter()
+            .filter(|(date, _)| *date >= since)
+            .collect();
+        if let Some(warning) = snapshot::rebasing_warning(&versions) {
+            eprintln!("{}", warning);
+        }
+    }
+    println!("{}", format_query_result(&result, format));
+    if !quiet && limit.is_some_and(|limit| result.len() >= limit) {
+        let (column, lower_is_better) = trend_metric(metric)?;
+        let executor = QueryExecutor::new(cache.base_dir().to_path_buf());
+        let total = executor.count(&trend_sql(column, lower_is_better, since, None))?;
+        note_hidden(result.len(), total, "--limit");
+    }
+    if fail_on_empty && result.is_empty() {
+        return Err(AppError::Empty(format!(
+            "no model's {} improved since {}",
+            metric, since
+        )));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::schema::LLMS_HISTORY;
+    use duckdb::Connection;
+    use tempfile::TempDir;
+
+    /// Write a snapshot of `(slug, input_price, intelligence)` rows.
+    fn write_snapshot(cache_dir: &Path, date: &str, rows: &str) {
+        let dir = LLMS_HISTORY
+            .cache_path(cache_dir)
+            .join(format!("date={}", date));
+        std::fs::create_dir_all(&dir).unwrap();
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute(
+            &format!(
+                "COPY (SELECT slug, slug AS name, 'Acme' AS creator, input_price, intelligence,
+                    DATE '{}' AS snapshot_date
+                FROM (VALUES {}) t(slug, input_price, intelligence))
+                TO '{}' (FORMAT PARQUET)",
+                date,
+                rows,
+                dir.join("benchmarks.parquet").to_string_lossy()
+            ),
+            [],
+        )
+        .unwrap();
+    }
+
+    fn date(s: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap()
+    }
+
+    #[test]
+    fn test_trend_metric() {
+        assert_eq!(trend_metric("Input_Price").unwrap(), ("input_price", true));
+        assert_eq!(
+            trend_metric("intelligence").unwrap(),
+            ("intelligence", false)
+        );
+        let err = trend_metric("vibes").unwrap_err().to_string();
+        assert!(err.contains("Available metrics: intelligence"));
+    }
+
+    #[test]
+    fn test_trends_rank_fastest_improvers() {
+        let temp_dir = TempDir::new().unwrap();
+        write_snapshot(
+            temp_dir.path(),
+            "2025-01-01",
+            "('cheap', 1.0::DOUBLE, 40.0::DOUBLE), ('pricey', 10.0, 60.0), ('flat', 2.0, 50.0), ('rising', 3.0, 45.0)",
+        );
+        write_snapshot(
+            temp_dir.path(),
+            "2025-01-31",
+            "('cheap', 0.5::DOUBLE, 42.0::DOUBLE), ('pricey', 9.0, 60.0), ('flat', 2.0, 50.0), ('rising', 4.0, 44.0)",
+        );
+        // Before the window; ignored
+        write_snapshot(
+            temp_dir.path(),
+            "2024-06-01",
+            "('pricey', 100.0::DOUBLE, 10.0::DOUBLE)",
+        );
+
+        let result =
+            query_trends(temp_dir.path(), "input_price", date("2025-01-01"), Some(10)).unwrap();
+        let models: Vec<&str> = result.rows.iter().map(|r| r[0].as_str()).collect();
+        // Halving a cheap price beats 10% off an expensive one; flat and
+        // rising prices are not listed
+        assert_eq!(models, vec!["cheap", "pricey"]);
+        assert_eq!(result.rows[0][3], "2");
+        assert_eq!(result.rows[0][6], "-0.50");
+        assert_eq!(result.rows[1][6], "-1.00");
+
+        let result = query_trends(
+            temp_dir.path(),
+            "intelligence",
+            date("2025-01-01"),
+            Some(10),
+        )
+        .unwrap();
+        let models: Vec<&str> = result.rows.iter().map(|r| r[0].as_str()).collect();
+        assert_eq!(models, vec!["cheap"]);
+
+        let result =
+            query_trends(temp_dir.path(), "input_price", date("2025-01-01"), Some(1)).unwrap();
+        assert_eq!(result.len(), 1);
+        let all = trend_sql("input_price", true, date("2025-01-01"), None);
+        assert!(!all.contains("LIMIT"));
+        let executor = QueryExecutor::new(temp_dir.path().to_path_buf());
+        assert_eq!(executor.count(&all).unwrap(), 2);
+    }
+
+    #[test]
+    fn test_trends_need_two_snapshots() {
+        let temp_dir = TempDir::new().unwrap();
+        let err = query_trends(temp_dir.path(), "price", date("2025-01-01"), Some(10)).unwrap_err();
+        assert_eq!(err.exit_code(), 5);
+        assert!(err.to_string().contains("refresh --snapshot"));
+
+        write_snapshot(
+            temp_dir.path(),
+            "2024-12-01",
+            "('a', 1.0::DOUBLE, 1.0::DOUBLE)",
+        );
+        write_snapshot(
+            temp_dir.path(),
+            "2025-01-15",
+            "('a', 1.0::DOUBLE, 1.0::DOUBLE)",
+        );
+        let err = query_trends(temp_dir.path(), "price", date("2025-01-01"), Some(10))
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("2 are stored (1 in the window)"), "{}", err);
+    }
+}
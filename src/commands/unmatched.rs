@@ -0,0 +1,148 @@
+//! Unmatched command - models.dev models that AA does not benchmark.
+//!
+//! Lists models.dev models whose slug has no row in the benchmarks table,
+//! one row per model, newest first, with the cheapest hosted price across
+//! providers and the capabilities models.dev reports. Slugs are matched as
+//! in the open view (provider prefix stripped, lowercased), so this is the
+//! complement of the benchmark columns `open` fills in.
+
+use crate::cache::Cache;
+use crate::cli::invocation;
+use crate::error::{AppError, Result};
+use crate::output::{note_hidden, OutputFormat};
+use crate::query::{format_query_result, QueryExecutor, QueryResult};
+use crate::schema::BENCHMARKS;
+use std::path::Path;
+
+/// Build the SQL for models.dev models missing from the benchmarks table.
+///
+/// A capability is reported when any provider lists it.
+fn unmatched_models_sql(limit: Option<usize>) -> String {
+    let limit = limit
+        .map(|limit| format!("\nLIMIT {}", limit))
+        .unwrap_or_default();
+    format!(
+        "SELECT
+    m.model_key AS model,
+    max(m.model_name) AS name,
+    max(m.family) AS family,
+    count(DISTINCT m.provider_id) AS providers,
+    max(m.release_date) AS release_date,
+    max(m.context_window) AS context_window,
+    min(m.cost_input) AS input_price,
+    min(m.cost_output) AS output_price,
+    bool_or(m.reasoning) AS reasoning,
+    bool_or(m.tool_call) AS tool_call,
+    bool_or(m.structured_output) AS structured_output,
+    bool_or(m.open_weights) AS open_weights
+FROM (
+    SELECT *, lower(regexp_replace(model_id, '^.*/', '')) AS model_key
+    FROM models
+) m
+WHERE m.model_key NOT IN (SELECT lower(slug) FROM benchmarks)
+GROUP BY m.model_key
+ORDER BY release_date DESC NULLS LAST, model{}",
+        limit
+    )
+}
+
+/// Query unmatched models from the cache in `cache_dir`.
+///
+/// Without a cached benchmarks table every model would be unmatched, so
+/// that is an error instead.
+fn query_unmatched_models(cache_dir: &Path, limit: Option<usize>) -> Result<QueryResult> {
+    if !BENCHMARKS.is_cached(cache_dir) {
+        return Err(AppError::Config(format!(
+            "The benchmarks table is not cached. Run '{}' first.",
+            invocation("refresh")
+        )));
+    }
+    let executor = QueryExecutor::new(cache_dir.to_path_buf());
+    executor.execute(&unmatched_models_sql(limit))
+}
+
+/// Run the unmatched command.
+///
+/// When `limit` hides rows, a note on stderr says how many there are,
+/// unless `quiet`.
+pub fn run(
+    limit: Option<usize>,
+    format: OutputFormat,
+    fail_on_empty: bool,
+    quiet: bool,
+) -> Result<()> {
+    let cache = Cache::new()?;
+    let result = query_unmatched_models(cache.base_dir(), limit)?;
+    println!("{}", format_query_result(&result, format));
+    if !quiet && limit.is_some_and(|limit| result.len() >= limit) {
+        let executor = QueryExecutor::new(cache.base_dir().to_path_buf());
+        let total = executor.count(&unmatched_models_sql(None))?;
+        note_hidden(result.len(), total, "--limit");
+    }
+    if fail_on_empty && result.is_empty() {
+        return Err(AppError::Empty(
+            "every models.dev model is benchmarked".into(),
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use duckdb::Connection;
+    use tempfile::TempDir;
+
+    fn write_parquet(path: &Path, select: &str) {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute(
+            &format!(
+                "COPY ({}) TO '{}' (FORMAT PARQUET)",
+                select,
+                path.to_string_lossy()
+            ),
+            [],
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_unmatched_models() {
+        let temp_dir = TempDir::new().unwrap();
+        write_parquet(
+            &temp_dir.path().join("models.parquet"),
+            "SELECT * FROM (VALUES
+                ('openai', 'gpt-4o', 'GPT-4o', 'gpt', DATE '2024-05-13', 128000::BIGINT, 2.5::DOUBLE, 10.0::DOUBLE, false, true, true, false),
+                ('groq', 'Niche-7B', 'Niche 7B', 'niche', DATE '2025-05-01', 32768, 0.20, 0.20, false, false, false, true),
+                ('openrouter', 'acme/niche-7b', 'Niche 7B', 'niche', DATE '2025-05-01', 65536, 0.10, 0.30, true, false, false, true),
+                ('fresh', 'fresh-1', 'Fresh 1', NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL)
+            ) t(provider_id, model_id, model_name, family, release_date, context_window, cost_input, cost_output, reasoning, tool_call, structured_output, open_weights)",
+        );
+
+        let err = query_unmatched_models(temp_dir.path(), Some(10))
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("which-llm refresh"));
+
+        write_parquet(
+            &temp_dir.path().join("benchmarks.parquet"),
+            "SELECT * FROM (VALUES ('GPT-4o')) t(slug)",
+        );
+        let result = query_unmatched_models(temp_dir.path(), Some(10)).unwrap();
+        let models: Vec<&str> = result.rows.iter().map(|r| r[0].as_str()).collect();
+        assert_eq!(models, vec!["niche-7b", "fresh-1"]);
+
+        // Providers collapse into one row: cheapest price, any capability
+        let niche = &result.rows[0];
+        assert_eq!(niche[3], "2");
+        assert_eq!(niche[5], "65536");
+        assert_eq!(niche[6], "0.10");
+        assert_eq!(niche[8], "true");
+
+        let result = query_unmatched_models(temp_dir.path(), Some(1)).unwrap();
+        assert_eq!(result.len(), 1);
+        let executor = QueryExecutor::new(temp_dir.path().to_path_buf());
+        assert_eq!(executor.count(&unmatched_models_sql(None)).unwrap(), 2);
+        assert!(!unmatched_models_sql(None).contains("LIMIT"));
+    }
+}
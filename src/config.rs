@@ -1,9 +1,13 @@
 //! Configuration management with profile support.
 
 use crate::error::{AppError, Result};
+use crate::remote::DataChannel;
+use crate::schema::{get_table_def, TableDef};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::path::PathBuf;
+use std::sync::OnceLock;
+use std::time::Duration;
 
 /// Profile configuration.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -11,6 +15,123 @@ pub struct Profile {
     pub api_key: String,
 }
 
+/// Environment variable holding an API key.
+pub const API_KEY_ENV: &str = "ARTIFICIAL_ANALYSIS_API_KEY";
+
+/// Key passed with `--api-key`.
+static API_KEY: OnceLock<String> = OnceLock::new();
+
+/// Use `key` for every API request of this run (`--api-key`). Only the
+/// first call has an effect.
+pub fn set_api_key(key: String) {
+    let _ = API_KEY.set(key);
+}
+
+/// Where the API key in use comes from, highest precedence first.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ApiKeySource {
+    /// The `--api-key` flag.
+    Flag,
+    /// The `ARTIFICIAL_ANALYSIS_API_KEY` environment variable.
+    Env,
+    /// The profile selected with `--profile`.
+    Profile(String),
+    /// The default profile.
+    DefaultProfile(String),
+}
+
+impl std::fmt::Display for ApiKeySource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Flag => write!(f, "--api-key"),
+            Self::Env => write!(f, "${}", API_KEY_ENV),
+            Self::Profile(name) => write!(f, "profile '{}' (--profile)", name),
+            Self::DefaultProfile(name) => write!(f, "profile '{}' (default)", name),
+        }
+    }
+}
+
+/// Compression codecs supported by DuckDB's Parquet writer.
+const PARQUET_COMPRESSIONS: &[&str] = &["uncompressed", "snappy", "gzip", "zstd", "lz4", "brotli"];
+
+/// Parquet writer settings.
+///
+/// Configured under `[parquet]` in `config.toml`:
+///
+/// ```toml
+/// [parquet]
+/// compression = "zstd"
+/// row_group_size = 100000
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ParquetConfig {
+    /// Compression codec (uncompressed, snappy, gzip, zstd, lz4, brotli).
+    #[serde(default = "default_parquet_compression")]
+    pub compression: String,
+    /// Rows per row group. DuckDB writes min/max statistics per row group, so
+    /// smaller groups let predicate pushdown skip more data on large datasets.
+    #[serde(default)]
+    pub row_group_size: Option<u64>,
+}
+
+fn default_parquet_compression() -> String {
+    "zstd".into()
+}
+
+impl Default for ParquetConfig {
+    fn default() -> Self {
+        Self {
+            compression: default_parquet_compression(),
+            row_group_size: None,
+        }
+    }
+}
+
+impl ParquetConfig {
+    /// Build the option list for a DuckDB `COPY ... TO` statement.
+    pub fn copy_options(&self) -> Result<String> {
+        let compression = self.compression.to_lowercase();
+        if !PARQUET_COMPRESSIONS.contains(&compression.as_str()) {
+            return Err(AppError::Config(format!(
+                "Invalid parquet compression '{}'. Use one of: {}.",
+                self.compression,
+                PARQUET_COMPRESSIONS.join(", ")
+            )));
+        }
+
+        let mut options = format!("FORMAT PARQUET, COMPRESSION {}", compression.to_uppercase());
+        if let Some(size) = self.row_group_size {
+            if size == 0 {
+                return Err(AppError::Config(
+                    "Invalid parquet row_group_size: must be greater than 0.".into(),
+                ));
+            }
+            options.push_str(&format!(", ROW_GROUP_SIZE {}", size));
+        }
+        Ok(options)
+    }
+}
+
+/// Directory passed with `--home` (or `WHICH_LLM_HOME`).
+static HOME: OnceLock<PathBuf> = OnceLock::new();
+
+/// Keep all state of this run under `home` (`--home`). Only the first call
+/// has an effect.
+pub fn set_home(home: PathBuf) {
+    let _ = HOME.set(home);
+}
+
+/// Single directory holding all state (`config/` and `cache/`), from
+/// `--home` or `WHICH_LLM_HOME`.
+///
+/// `WHICH_LLM_CONFIG_DIR` and `WHICH_LLM_CACHE_DIR` still override their
+/// part of it.
+pub fn home_dir() -> Option<PathBuf> {
+    HOME.get()
+        .filter(|home| !home.as_os_str().is_empty())
+        .cloned()
+}
+
 /// Application configuration.
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct Config {
@@ -18,6 +139,45 @@ pub struct Config {
     pub default_profile: Option<String>,
     #[serde(default)]
     pub profiles: HashMap<String, Profile>,
+    #[serde(default)]
+    pub parquet: ParquetConfig,
+    /// Debug trace log written on every run (same as `--log-file`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub log_file: Option<PathBuf>,
+    /// Hosted data channel (same as `--channel`): `stable` or `nightly`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub channel: Option<DataChannel>,
+    /// Named metric weights for `--score-profile`, keyed by profile name.
+    ///
+    /// ```toml
+    /// [score_profiles]
+    /// coding_heavy = { coding = 0.6, intelligence = 0.3, price = -0.1 }
+    /// ```
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub score_profiles: BTreeMap<String, BTreeMap<String, f64>>,
+    /// How long cached tables stay fresh, overriding each table's default.
+    /// Values are minutes (`m`), hours (`h`) or days (`d`).
+    ///
+    /// ```toml
+    /// [cache_ttl]
+    /// benchmarks = "6h"
+    /// text_to_image = "14d"
+    /// ```
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub cache_ttl: BTreeMap<String, String>,
+}
+
+/// Parse a TTL such as `30m`, `6h` or `7d`.
+fn parse_ttl(value: &str) -> Option<Duration> {
+    let value = value.trim();
+    let unit = match value.chars().last()? {
+        'm' => 60,
+        'h' => 60 * 60,
+        'd' => 24 * 60 * 60,
+        _ => return None,
+    };
+    let count: u64 = value[..value.len() - 1].parse().ok()?;
+    Some(Duration::from_secs(count.checked_mul(unit)?))
 }
 
 impl Config {
@@ -54,18 +214,50 @@ impl Config {
     }
 
     /// Get the configuration file path.
-    /// Respects WHICH_LLM_CONFIG_DIR environment variable for testing/portability.
+    /// Respects WHICH_LLM_CONFIG_DIR, then `--home` (WHICH_LLM_HOME), for
+    /// testing/portability.
     pub fn config_path() -> Result<PathBuf> {
         // Allow override via environment variable (useful for testing)
         if let Ok(config_dir) = std::env::var("WHICH_LLM_CONFIG_DIR") {
             return Ok(PathBuf::from(config_dir).join("config.toml"));
         }
+        if let Some(home) = home_dir() {
+            return Ok(home.join("config").join("config.toml"));
+        }
 
         dirs::config_dir()
             .map(|p| p.join("which-llm").join("config.toml"))
             .ok_or_else(|| AppError::Config("Could not determine config directory".into()))
     }
 
+    /// How long `table` stays fresh: its `cache_ttl` entry, else its default.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AppError::Config` if `cache_ttl` names an unknown table or
+    /// has a value that is not a TTL.
+    pub fn table_ttl(&self, table: &TableDef) -> Result<Duration> {
+        if let Some(name) = self
+            .cache_ttl
+            .keys()
+            .find(|name| get_table_def(name).is_none())
+        {
+            return Err(AppError::Config(format!(
+                "Unknown table '{}' in cache_ttl. Run 'which-llm tables' to list tables.",
+                name
+            )));
+        }
+        match self.cache_ttl.get(table.name) {
+            Some(value) => parse_ttl(value).ok_or_else(|| {
+                AppError::Config(format!(
+                    "Invalid cache_ttl for '{}': '{}'. Use minutes, hours or days, e.g. 30m, 6h or 7d.",
+                    table.name, value
+                ))
+            }),
+            None => Ok(table.default_ttl),
+        }
+    }
+
     /// Get a profile by name.
     pub fn get_profile(&self, name: &str) -> Option<&Profile> {
         self.profiles.get(name)
@@ -87,26 +279,45 @@ impl Config {
     }
 
     /// Get the API key for the given profile, or the default profile.
-    /// Environment variable ARTIFICIAL_ANALYSIS_API_KEY takes precedence.
+    /// `--api-key` and then `ARTIFICIAL_ANALYSIS_API_KEY` take precedence.
     pub fn get_api_key(&self, profile_name: Option<&str>) -> Result<String> {
-        // Check environment variable first
-        if let Ok(key) = std::env::var("ARTIFICIAL_ANALYSIS_API_KEY") {
-            return Ok(key);
-        }
+        self.resolve_api_key(profile_name).map(|(key, _)| key)
+    }
+
+    /// The API key to use and where it comes from: `--api-key`, then
+    /// `ARTIFICIAL_ANALYSIS_API_KEY`, then the selected profile, then the
+    /// default profile.
+    pub fn resolve_api_key(&self, profile_name: Option<&str>) -> Result<(String, ApiKeySource)> {
+        self.resolve_api_key_from(
+            API_KEY.get().map(String::as_str),
+            std::env::var(API_KEY_ENV).ok(),
+            profile_name,
+        )
+    }
 
-        // Determine which profile to use
-        let profile_name = profile_name
-            .map(String::from)
-            .or_else(|| self.default_profile.clone())
-            .ok_or(AppError::NoApiKey)?;
+    fn resolve_api_key_from(
+        &self,
+        flag: Option<&str>,
+        env: Option<String>,
+        profile_name: Option<&str>,
+    ) -> Result<(String, ApiKeySource)> {
+        if let Some(key) = flag {
+            return Ok((key.to_string(), ApiKeySource::Flag));
+        }
+        if let Some(key) = env {
+            return Ok((key, ApiKeySource::Env));
+        }
 
-        // Get the profile
+        let (name, source) = match (profile_name, &self.default_profile) {
+            (Some(name), _) => (name, ApiKeySource::Profile(name.to_string())),
+            (None, Some(name)) => (name.as_str(), ApiKeySource::DefaultProfile(name.clone())),
+            (None, None) => return Err(AppError::NoApiKey),
+        };
         let profile = self
             .profiles
-            .get(&profile_name)
-            .ok_or_else(|| AppError::ProfileNotFound(profile_name))?;
-
-        Ok(profile.api_key.clone())
+            .get(name)
+            .ok_or_else(|| AppError::ProfileNotFound(name.to_string()))?;
+        Ok((profile.api_key.clone(), source))
     }
 }
 
@@ -114,6 +325,107 @@ impl Config {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_config_score_profiles() {
+        let config: Config = toml::from_str(
+            r#"
+[score_profiles]
+coding_heavy = { coding = 0.6, intelligence = 0.3, price = -0.1 }
+
+[score_profiles.cheap]
+price = -1.0
+"#,
+        )
+        .unwrap();
+        assert_eq!(config.score_profiles.len(), 2);
+        assert_eq!(config.score_profiles["coding_heavy"]["price"], -0.1);
+        assert_eq!(config.score_profiles["cheap"]["price"], -1.0);
+    }
+
+    #[test]
+    fn test_config_cache_ttl() {
+        use crate::schema::{BENCHMARKS, MEDIA_DATA_TTL, TEXT_TO_IMAGE, TEXT_TO_SPEECH};
+
+        let config: Config = toml::from_str(
+            r#"
+[cache_ttl]
+benchmarks = "6h"
+text_to_image = "30m"
+"#,
+        )
+        .unwrap();
+        let hours = |h: u64| Duration::from_secs(h * 60 * 60);
+        assert_eq!(config.table_ttl(&BENCHMARKS).unwrap(), hours(6));
+        assert_eq!(
+            config.table_ttl(&TEXT_TO_IMAGE).unwrap(),
+            Duration::from_secs(30 * 60)
+        );
+        assert_eq!(config.table_ttl(&TEXT_TO_SPEECH).unwrap(), MEDIA_DATA_TTL);
+        assert_eq!(parse_ttl("14d"), Some(hours(14 * 24)));
+        assert_eq!(parse_ttl("6"), None);
+        assert_eq!(parse_ttl("h"), None);
+
+        let invalid: Config = toml::from_str("[cache_ttl]\nbenchmarks = \"soon\"").unwrap();
+        assert!(invalid.table_ttl(&BENCHMARKS).is_err());
+        let unknown: Config = toml::from_str("[cache_ttl]\nbenchmark = \"6h\"").unwrap();
+        assert!(unknown.table_ttl(&BENCHMARKS).is_err());
+    }
+
+    #[test]
+    fn test_api_key_precedence() {
+        let mut config = Config::default();
+        for (name, key) in [("home", "home-key"), ("work", "work-key")] {
+            config.set_profile(
+                name.into(),
+                Profile {
+                    api_key: key.into(),
+                },
+            );
+        }
+        config.set_default("home".into());
+        let env = || Some("env-key".to_string());
+
+        let resolve = |flag: Option<&str>, env: Option<String>, profile: Option<&str>| {
+            config.resolve_api_key_from(flag, env, profile).unwrap()
+        };
+        assert_eq!(
+            resolve(Some("flag-key"), env(), Some("work")),
+            ("flag-key".into(), ApiKeySource::Flag)
+        );
+        assert_eq!(
+            resolve(None, env(), Some("work")),
+            ("env-key".into(), ApiKeySource::Env)
+        );
+        assert_eq!(
+            resolve(None, None, Some("work")),
+            ("work-key".into(), ApiKeySource::Profile("work".into()))
+        );
+        assert_eq!(
+            resolve(None, None, None),
+            (
+                "home-key".into(),
+                ApiKeySource::DefaultProfile("home".into())
+            )
+        );
+
+        let err = config
+            .resolve_api_key_from(None, None, Some("missing"))
+            .unwrap_err();
+        assert!(matches!(err, AppError::ProfileNotFound(name) if name == "missing"));
+        assert!(matches!(
+            Config::default().resolve_api_key_from(None, None, None),
+            Err(AppError::NoApiKey)
+        ));
+    }
+
+    #[test]
+    fn test_config_channel() {
+        let config: Config = toml::from_str("channel = \"nightly\"").unwrap();
+        assert_eq!(config.channel, Some(DataChannel::Nightly));
+        assert!(toml::from_str::<Config>("channel = \"beta\"").is_err());
+        assert_eq!(Config::default().channel, None);
+    }
+
     #[test]
     fn test_config_default() {
         let config = Config::default();
@@ -141,4 +453,32 @@ mod tests {
         config.remove_profile("test");
         assert!(config.get_profile("test").is_none());
     }
+
+    #[test]
+    fn test_parquet_copy_options() {
+        let default = ParquetConfig::default();
+        assert_eq!(
+            default.copy_options().unwrap(),
+            "FORMAT PARQUET, COMPRESSION ZSTD"
+        );
+
+        let config: Config = toml::from_str(
+            r#"
+            [parquet]
+            compression = "snappy"
+            row_group_size = 50000
+            "#,
+        )
+        .unwrap();
+        assert_eq!(
+            config.parquet.copy_options().unwrap(),
+            "FORMAT PARQUET, COMPRESSION SNAPPY, ROW_GROUP_SIZE 50000"
+        );
+
+        let invalid = ParquetConfig {
+            compression: "zip".into(),
+            row_group_size: None,
+        };
+        assert!(invalid.copy_options().is_err());
+    }
 }
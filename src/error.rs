@@ -1,5 +1,6 @@
 //! Error types for the CLI.
 
+use crate::cli::invocation;
 use thiserror::Error;
 
 /// Application-level errors.
@@ -8,7 +9,10 @@ pub enum AppError {
     #[error("Configuration error: {0}")]
     Config(String),
 
-    #[error("Invalid API key. Run 'which-llm profile create' to configure.")]
+    #[error(
+        "Invalid API key. Run '{}' to configure.",
+        invocation("profile create")
+    )]
     InvalidApiKey,
 
     #[error("Rate limit exceeded. Free tier allows 1,000 requests/day. Reset: {0}")]
@@ -27,7 +31,8 @@ pub enum AppError {
     ProfileNotFound(String),
 
     #[error(
-        "No API key configured. Set ARTIFICIAL_ANALYSIS_API_KEY environment variable or run 'which-llm profile create'."
+        "No API key configured. Pass --api-key, set ARTIFICIAL_ANALYSIS_API_KEY environment variable or run '{}'.",
+        invocation("profile create")
     )]
     NoApiKey,
 
@@ -40,6 +45,22 @@ pub enum AppError {
     #[error("{0}")]
     Query(String),
 
+    #[error("Strict mode: {0}")]
+    Strict(String),
+
+    #[error("Policy violation: {0}")]
+    Policy(String),
+
+    #[error("No results: {0}")]
+    Empty(String),
+
+    #[error("{failed} batch line(s) failed")]
+    Batch {
+        failed: usize,
+        /// Exit code of the first failing line.
+        code: i32,
+    },
+
     #[error("IO error: {0}")]
     Io(#[from] std::io::Error),
 
@@ -56,4 +77,124 @@ pub enum AppError {
     Http(#[from] reqwest::Error),
 }
 
+impl AppError {
+    /// Stable, machine-readable error code.
+    pub fn code(&self) -> &'static str {
+        match self {
+            AppError::Config(_) => "config",
+            AppError::InvalidApiKey => "invalid_api_key",
+            AppError::RateLimited(_) => "rate_limited",
+            AppError::Network(_) => "network",
+            AppError::ServerError => "server_error",
+            AppError::Api { .. } => "api_error",
+            AppError::ProfileNotFound(_) => "profile_not_found",
+            AppError::NoApiKey => "no_api_key",
+            AppError::Cache(_) => "cache",
+            AppError::NotFound(_) => "not_found",
+            AppError::Query(_) => "query",
+            AppError::Strict(_) => "strict",
+            AppError::Policy(_) => "policy",
+            AppError::Empty(_) => "empty",
+            AppError::Batch { .. } => "batch",
+            AppError::Io(_) => "io",
+            AppError::Json(_) => "json",
+            AppError::TomlParse(_) => "toml_parse",
+            AppError::TomlSerialize(_) => "toml_serialize",
+            AppError::Http(_) => "http",
+        }
+    }
+
+    /// Process exit code for this error's category.
+    ///
+    /// config = 2, network = 3, rate limit = 4, not found = 5,
+    /// no results with `--fail-on-empty` = 6, anything else = 1. A batch
+    /// run with `--keep-going` exits with its first failing line's code.
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            AppError::Config(_)
+            | AppError::InvalidApiKey
+            | AppError::NoApiKey
+            | AppError::TomlParse(_)
+            | AppError::TomlSerialize(_) => 2,
+            AppError::Network(_)
+            | AppError::ServerError
+            | AppError::Api { .. }
+            | AppError::Http(_) => 3,
+            AppError::RateLimited(_) => 4,
+            AppError::NotFound(_) | AppError::ProfileNotFound(_) => 5,
+            AppError::Empty(_) => 6,
+            AppError::Batch { code, .. } => *code,
+            AppError::Cache(_)
+            | AppError::Query(_)
+            | AppError::Strict(_)
+            | AppError::Policy(_)
+            | AppError::Io(_)
+            | AppError::Json(_) => 1,
+        }
+    }
+
+    /// Suggested next step for the user, if there is an obvious one.
+    pub fn hint(&self) -> Option<String> {
+        let hint = match self {
+            AppError::InvalidApiKey | AppError::NoApiKey => format!(
+                "Run '{}' or set ARTIFICIAL_ANALYSIS_API_KEY",
+                invocation("profile create")
+            ),
+            AppError::ProfileNotFound(_) => {
+                format!("Run '{}' to see profiles", invocation("profile list"))
+            }
+            AppError::RateLimited(_) => "Wait for the limit to reset or use hosted data".into(),
+            AppError::Network(_) | AppError::ServerError | AppError::Http(_) => {
+                "Check your connection and try again".into()
+            }
+            AppError::NotFound(_) | AppError::Cache(_) => {
+                format!("Run '{}' to fetch data", invocation("refresh"))
+            }
+            AppError::TomlParse(_) => "Check the syntax of your config file".into(),
+            AppError::Strict(_) => "Run without --strict to accept stale or partial data".into(),
+            AppError::Policy(_) => "Pick an allowed model or update the policy file".into(),
+            _ => return None,
+        };
+        Some(hint)
+    }
+
+    /// Machine-readable representation: `{code, message, hint}`.
+    pub fn to_json(&self) -> serde_json::Value {
+        serde_json::json!({
+            "code": self.code(),
+            "message": self.to_string(),
+            "hint": self.hint(),
+        })
+    }
+}
+
 pub type Result<T> = std::result::Result<T, AppError>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_exit_codes() {
+        assert_eq!(AppError::Config("bad".into()).exit_code(), 2);
+        assert_eq!(AppError::NoApiKey.exit_code(), 2);
+        assert_eq!(AppError::Network("down".into()).exit_code(), 3);
+        assert_eq!(AppError::RateLimited("tomorrow".into()).exit_code(), 4);
+        assert_eq!(AppError::NotFound("gpt-9".into()).exit_code(), 5);
+        assert_eq!(AppError::Empty("0 rows".into()).exit_code(), 6);
+        assert_eq!(AppError::Query("syntax".into()).exit_code(), 1);
+        assert_eq!(AppError::Policy("gpt-4o is banned".into()).exit_code(), 1);
+        assert_eq!(AppError::Batch { failed: 2, code: 5 }.exit_code(), 5);
+    }
+
+    #[test]
+    fn test_to_json() {
+        let json = AppError::ProfileNotFound("work".into()).to_json();
+        assert_eq!(json["code"], "profile_not_found");
+        assert_eq!(json["message"], "Profile 'work' not found");
+        assert!(json["hint"].as_str().unwrap().contains("profile list"));
+
+        let json = AppError::Query("syntax".into()).to_json();
+        assert!(json["hint"].is_null());
+    }
+}
@@ -1,20 +1,28 @@
 //! Artificial Analysis CLI library.
 
+mod app;
 pub mod cache;
 pub mod cli;
 pub mod client;
 pub mod commands;
 pub mod config;
 pub mod error;
+pub mod logging;
 pub mod models;
+pub mod notes;
 pub mod output;
 pub mod parquet;
+pub mod policy;
 pub mod query;
+pub mod quota;
 pub mod remote;
 pub mod schema;
+pub mod snapshot;
 pub mod sources;
+pub mod strict;
 pub mod utils;
 
+pub use app::run;
 pub use cli::{Cli, Commands};
 pub use config::Config;
 pub use error::{AppError, Result};
@@ -0,0 +1,128 @@
+//! Debug trace log for support reports.
+//!
+//! Enabled with `--log-file PATH` or `log_file` in `config.toml`. Records HTTP
+//! calls, cache decisions, row counts and executed SQL at debug level. API
+//! keys are redacted before anything reaches the file.
+
+use crate::error::Result;
+use chrono::Utc;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use std::fmt;
+use std::fs::{File, OpenOptions};
+use std::io::Write;
+use std::path::Path;
+use std::sync::Mutex;
+
+/// Open log file, if tracing is enabled.
+static LOG_FILE: Lazy<Mutex<Option<File>>> = Lazy::new(|| Mutex::new(None));
+
+/// Secret values to mask wherever they appear.
+static SECRETS: Lazy<Mutex<Vec<String>>> = Lazy::new(|| Mutex::new(Vec::new()));
+
+/// `key=value` / `key: value` pairs whose value is a credential.
+static CREDENTIAL_PATTERN: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(concat!(
+        r"(?i)\b(x-api-key|api[_-]?key|authorization|token)(\s*[=:]\s*)(?:bearer\s+)?[^\s,&;]+",
+        r"|\b(bearer)(\s+)[^\s,&;]+"
+    ))
+    .unwrap()
+});
+
+/// Start appending debug traces to `path`.
+pub fn init(path: &Path) -> Result<()> {
+    if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        std::fs::create_dir_all(parent)?;
+    }
+    let file = OpenOptions::new().create(true).append(true).open(path)?;
+    *LOG_FILE.lock().unwrap_or_else(|e| e.into_inner()) = Some(file);
+    debug(
+        "which-llm",
+        format_args!(
+            "version {} args: {}",
+            env!("CARGO_PKG_VERSION"),
+            std::env::args().skip(1).collect::<Vec<_>>().join(" ")
+        ),
+    );
+    Ok(())
+}
+
+/// Whether debug traces are being written.
+pub fn enabled() -> bool {
+    LOG_FILE.lock().map(|file| file.is_some()).unwrap_or(false)
+}
+
+/// Mask `secret` in all subsequent log lines.
+pub fn register_secret(secret: &str) {
+    if secret.is_empty() {
+        return;
+    }
+    let mut secrets = SECRETS.lock().unwrap_or_else(|e| e.into_inner());
+    if !secrets.iter().any(|s| s == secret) {
+        secrets.push(secret.to_string());
+    }
+}
+
+/// Replace registered secrets and credential-looking values with `[REDACTED]`.
+pub fn redact(text: &str) -> String {
+    let mut text = CREDENTIAL_PATTERN
+        .replace_all(text, "${1}${2}${3}${4}[REDACTED]")
+        .into_owned();
+    for secret in SECRETS.lock().unwrap_or_else(|e| e.into_inner()).iter() {
+        text = text.replace(secret.as_str(), "[REDACTED]");
+    }
+    text
+}
+
+/// Write a debug trace line for `target` (e.g. `http`, `cache`, `sql`).
+///
+/// Takes `format_args!` so nothing is formatted when tracing is off.
+pub fn debug(target: &str, message: fmt::Arguments) {
+    let mut guard = LOG_FILE.lock().unwrap_or_else(|e| e.into_inner());
+    let Some(file) = guard.as_mut() else {
+        return;
+    };
+    let line = format!(
+        "{} DEBUG {}: {}",
+        Utc::now().format("%Y-%m-%dT%H:%M:%S%.3fZ"),
+        target,
+        message
+    );
+    // Tracing must never fail the command itself
+    let _ = writeln!(file, "{}", redact(&line));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_redact_credentials() {
+        assert_eq!(
+            redact("GET /v2/data x-api-key: aa_live_123 status=200"),
+            "GET /v2/data x-api-key: [REDACTED] status=200"
+        );
+        assert_eq!(
+            redact("url?api_key=abc123&page=2"),
+            "url?api_key=[REDACTED]&page=2"
+        );
+        assert_eq!(
+            redact("Authorization: Bearer sk-xyz"),
+            "Authorization: [REDACTED]"
+        );
+        assert_eq!(redact("bearer abc.def"), "bearer [REDACTED]");
+        assert_eq!(
+            redact("SELECT * FROM benchmarks"),
+            "SELECT * FROM benchmarks"
+        );
+    }
+
+    #[test]
+    fn test_redact_registered_secret() {
+        register_secret("secret-value-42");
+        assert_eq!(
+            redact("cache key built from secret-value-42"),
+            "cache key built from [REDACTED]"
+        );
+    }
+}
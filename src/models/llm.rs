@@ -4,7 +4,11 @@
 //! For capability data (tool_call, reasoning, context_window, etc.),
 //! query the `providers` table from models.dev.
 
+use crate::error::{AppError, Result};
+use chrono::NaiveDate;
 use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
 
 /// LLM Model from Artificial Analysis.
 ///
@@ -16,15 +20,23 @@ pub struct LlmModel {
     pub id: String,
     pub name: String,
     pub slug: String,
-    #[serde(default)]
-    pub release_date: Option<String>,
+    #[serde(default, deserialize_with = "crate::utils::deserialize_date")]
+    pub release_date: Option<NaiveDate>,
     pub creator: String,
     #[serde(default)]
     pub creator_slug: Option<String>,
+    /// `deprecated` or `retired` once the model is on its way out; `None`
+    /// while it is active. Derived from models.dev and snapshots, not AA.
+    #[serde(default)]
+    pub status: Option<String>,
 
     // === Benchmarks ===
     #[serde(default)]
     pub intelligence: Option<f64>,
+    /// 95% confidence half-width of `intelligence`, when AA publishes one.
+    /// Only loaded on request (`compare --uncertainty`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub intelligence_error: Option<f64>,
     #[serde(default)]
     pub coding: Option<f64>,
     #[serde(default)]
@@ -57,6 +69,22 @@ pub struct LlmModel {
     pub tps: Option<f64>,
     #[serde(default)]
     pub latency: Option<f64>,
+    /// Speed percentiles besides the median `tps`, when AA publishes them.
+    /// Only loaded on request (`compare --percentile`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tps_percentiles: Option<Percentiles>,
+    /// Latency percentiles besides the median `latency`, as for
+    /// `tps_percentiles`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub latency_percentiles: Option<Percentiles>,
+
+    // === Integration ===
+    /// AI SDK npm package of the suggested provider, from models.dev.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub npm_package: Option<String>,
+    /// Env vars for the suggested provider's API key, comma-separated.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub env_vars: Option<String>,
 }
 
 impl LlmModel {
@@ -94,6 +122,209 @@ impl LlmModel {
     pub fn get_tps(&self) -> Option<f64> {
         self.tps
     }
+
+    /// Intelligence index points per blended dollar (per million tokens).
+    ///
+    /// `None` when either value is missing or the model is free.
+    pub fn intelligence_per_dollar(&self) -> Option<f64> {
+        per_dollar(self.intelligence, self.price)
+    }
+
+    /// Coding index points per blended dollar (per million tokens).
+    pub fn coding_per_dollar(&self) -> Option<f64> {
+        per_dollar(self.coding, self.price)
+    }
+
+    /// Number of [`BENCHMARK_COLUMNS`] the model has a score for.
+    pub fn coverage(&self) -> usize {
+        [
+            self.intelligence,
+            self.coding,
+            self.math,
+            self.mmlu_pro,
+            self.gpqa,
+            self.hle,
+            self.livecodebench,
+            self.scicode,
+            self.math_500,
+            self.aime,
+        ]
+        .iter()
+        .filter(|score| score.is_some())
+        .count()
+    }
+
+    /// Family the model is a variant of: its slug without reasoning-effort
+    /// and thinking-mode suffixes, e.g. `gpt-5` for `gpt-5-low` and
+    /// `claude-4-5-sonnet` for `claude-4-5-sonnet-thinking`.
+    pub fn family(&self) -> &str {
+        let mut family = self.slug.as_str();
+        while let Some(base) = VARIANT_SUFFIXES
+            .iter()
+            .find_map(|suffix| family.strip_suffix(suffix))
+            .filter(|base| !base.is_empty())
+        {
+            family = base;
+        }
+        family
+    }
+
+    /// Reasoning mode of this variant, from its slug suffixes.
+    pub fn reasoning_mode(&self) -> ReasoningMode {
+        let variant = &self.slug[self.family().len()..];
+        if variant.is_empty() {
+            ReasoningMode::Default
+        } else if variant.contains("-non-reasoning") || variant.contains("-minimal") {
+            ReasoningMode::NonReasoning
+        } else {
+            ReasoningMode::Reasoning
+        }
+    }
+
+    /// Benchmark coverage as `scored/total`, e.g. `8/10`.
+    pub fn coverage_label(&self) -> String {
+        format!("{}/{}", self.coverage(), BENCHMARK_COLUMNS.len())
+    }
+}
+
+/// Reasoning mode of a model variant.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReasoningMode {
+    /// A thinking or reasoning-effort variant (`-thinking`, `-high`, ...).
+    Reasoning,
+    /// A variant with reasoning off or minimal (`-non-reasoning`, `-minimal`).
+    NonReasoning,
+    /// The family's plain slug, whose mode depends on the vendor default.
+    Default,
+}
+
+/// A performance percentile AA may publish besides the median.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Percentile {
+    P5,
+    P25,
+    P75,
+    P95,
+}
+
+impl Percentile {
+    /// The percentile as a number, e.g. 95.
+    pub fn value(self) -> u8 {
+        match self {
+            Percentile::P5 => 5,
+            Percentile::P25 => 25,
+            Percentile::P75 => 75,
+            Percentile::P95 => 95,
+        }
+    }
+
+    /// The percentile for a number, e.g. 95.
+    pub fn from_value(value: u8) -> Option<Self> {
+        match value {
+            5 => Some(Percentile::P5),
+            25 => Some(Percentile::P25),
+            75 => Some(Percentile::P75),
+            95 => Some(Percentile::P95),
+            _ => None,
+        }
+    }
+}
+
+impl FromStr for Percentile {
+    type Err = AppError;
+
+    /// Parse `p95`, `P95` or `95` (`p05` and `p5` are the same).
+    fn from_str(s: &str) -> Result<Self> {
+        let trimmed = s.trim();
+        let number = trimmed
+            .strip_prefix(['p', 'P'])
+            .unwrap_or(trimmed)
+            .parse()
+            .ok()
+            .and_then(Percentile::from_value);
+        number.ok_or_else(|| {
+            AppError::Config(format!(
+                "Invalid percentile '{}'. Use p5, p25, p75 or p95.",
+                s
+            ))
+        })
+    }
+}
+
+impl fmt::Display for Percentile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "p{}", self.value())
+    }
+}
+
+/// Percentiles of one performance metric.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct Percentiles {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub p5: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub p25: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub p75: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub p95: Option<f64>,
+}
+
+impl Percentiles {
+    pub fn get(&self, percentile: Percentile) -> Option<f64> {
+        match percentile {
+            Percentile::P5 => self.p5,
+            Percentile::P25 => self.p25,
+            Percentile::P75 => self.p75,
+            Percentile::P95 => self.p95,
+        }
+    }
+
+    pub fn set(&mut self, percentile: Percentile, value: f64) {
+        let slot = match percentile {
+            Percentile::P5 => &mut self.p5,
+            Percentile::P25 => &mut self.p25,
+            Percentile::P75 => &mut self.p75,
+            Percentile::P95 => &mut self.p95,
+        };
+        *slot = Some(value);
+    }
+}
+
+/// Slug suffixes of variants within a family, stripped by [`LlmModel::family`].
+///
+/// `-non-reasoning` comes before `-reasoning` so the longer suffix wins.
+const VARIANT_SUFFIXES: &[&str] = &[
+    "-non-reasoning",
+    "-reasoning",
+    "-thinking",
+    "-minimal",
+    "-low",
+    "-medium",
+    "-high",
+    "-xhigh",
+];
+
+/// Benchmark score columns counted by [`LlmModel::coverage`].
+pub const BENCHMARK_COLUMNS: &[&str] = &[
+    "intelligence",
+    "coding",
+    "math",
+    "mmlu_pro",
+    "gpqa",
+    "hle",
+    "livecodebench",
+    "scicode",
+    "math_500",
+    "aime",
+];
+
+/// Divide a score by a price, skipping missing and zero prices.
+fn per_dollar(score: Option<f64>, price: Option<f64>) -> Option<f64> {
+    match (score, price) {
+        (Some(score), Some(price)) if price > 0.0 => Some(score / price),
+        _ => None,
+    }
 }
 
 // Backward compatibility: implement the old accessor methods
@@ -124,6 +355,24 @@ impl LlmModel {
     }
 }
 
+#[cfg(test)]
+impl LlmModel {
+    /// Model for command tests: by "Acme", named after `slug`, with the
+    /// given intelligence and blended price. Tests set other fields with
+    /// struct update syntax.
+    pub(crate) fn fixture(slug: &str, intelligence: f64, price: f64) -> Self {
+        Self {
+            id: slug.to_string(),
+            name: slug.to_uppercase(),
+            slug: slug.to_string(),
+            creator: "Acme".to_string(),
+            intelligence: Some(intelligence),
+            price: Some(price),
+            ..Default::default()
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -133,10 +382,12 @@ mod tests {
             id: "test-id".to_string(),
             name: "GPT-4o".to_string(),
             slug: "gpt-4o".to_string(),
-            release_date: Some("2024-05-13".to_string()),
+            release_date: NaiveDate::from_ymd_opt(2024, 5, 13),
             creator: "OpenAI".to_string(),
             creator_slug: Some("openai".to_string()),
+            status: None,
             intelligence: Some(55.0),
+            intelligence_error: None,
             coding: Some(50.0),
             math: Some(60.0),
             mmlu_pro: Some(0.75),
@@ -151,9 +402,23 @@ mod tests {
             price: Some(5.0),
             tps: Some(150.0),
             latency: Some(0.5),
+            tps_percentiles: None,
+            latency_percentiles: None,
+            npm_package: None,
+            env_vars: None,
         }
     }
 
+    #[test]
+    fn test_parse_percentile() {
+        assert_eq!("p95".parse::<Percentile>().unwrap(), Percentile::P95);
+        assert_eq!("P05".parse::<Percentile>().unwrap(), Percentile::P5);
+        assert_eq!("25".parse::<Percentile>().unwrap(), Percentile::P25);
+        assert_eq!(Percentile::P75.to_string(), "p75");
+        let err = "p50".parse::<Percentile>().unwrap_err();
+        assert_eq!(err.exit_code(), 2);
+    }
+
     #[test]
     fn test_basic_accessors() {
         let model = make_test_model();
@@ -164,6 +429,67 @@ mod tests {
         assert_eq!(model.tps(), Some(150.0));
     }
 
+    #[test]
+    fn test_coverage() {
+        let mut model = make_test_model();
+        assert_eq!(model.coverage(), 5);
+        assert_eq!(model.coverage_label(), "5/10");
+
+        model.intelligence = None;
+        model.aime = Some(0.9);
+        assert_eq!(model.coverage(), 5);
+    }
+
+    #[test]
+    fn test_family() {
+        let family = |slug: &str| {
+            LlmModel {
+                slug: slug.to_string(),
+                ..Default::default()
+            }
+            .family()
+            .to_string()
+        };
+        assert_eq!(family("gpt-5"), "gpt-5");
+        assert_eq!(family("gpt-5-minimal"), "gpt-5");
+        assert_eq!(family("o3-mini-high"), "o3-mini");
+        assert_eq!(family("claude-4-5-sonnet-thinking"), "claude-4-5-sonnet");
+        assert_eq!(family("gemini-2-5-flash-non-reasoning"), "gemini-2-5-flash");
+        assert_eq!(family("gemini-2-5-flash-reasoning-low"), "gemini-2-5-flash");
+        assert_eq!(family("high"), "high");
+    }
+
+    #[test]
+    fn test_reasoning_mode() {
+        let mode = |slug: &str| {
+            LlmModel {
+                slug: slug.to_string(),
+                ..Default::default()
+            }
+            .reasoning_mode()
+        };
+        assert_eq!(mode("gpt-5"), ReasoningMode::Default);
+        assert_eq!(mode("gpt-5-minimal"), ReasoningMode::NonReasoning);
+        assert_eq!(mode("gpt-5-low"), ReasoningMode::Reasoning);
+        assert_eq!(mode("claude-4-5-sonnet-thinking"), ReasoningMode::Reasoning);
+        assert_eq!(
+            mode("gemini-2-5-flash-non-reasoning"),
+            ReasoningMode::NonReasoning
+        );
+    }
+
+    #[test]
+    fn test_per_dollar() {
+        let mut model = make_test_model();
+        assert_eq!(model.intelligence_per_dollar(), Some(11.0));
+        assert_eq!(model.coding_per_dollar(), Some(10.0));
+
+        model.price = Some(0.0);
+        assert_eq!(model.intelligence_per_dollar(), None);
+        model.price = None;
+        assert_eq!(model.coding_per_dollar(), None);
+    }
+
     #[test]
     fn test_serialization() {
         let model = make_test_model();
@@ -1,5 +1,6 @@
 //! Data models for media model responses.
 
+use chrono::NaiveDate;
 use serde::{Deserialize, Serialize};
 
 /// Media model from the API (text-to-image, video, speech, etc.).
@@ -17,8 +18,18 @@ pub struct MediaModel {
     pub ci95: Option<String>,
     #[serde(default)]
     pub appearances: Option<u64>,
+    /// Text-to-speech price in USD per million characters, when published.
     #[serde(default)]
-    pub release_date: Option<String>,
+    pub price_per_1m_characters: Option<f64>,
+    /// Text-to-speech price in USD per minute of audio, when published.
+    #[serde(default)]
+    pub price_per_minute: Option<f64>,
+    /// Video generation price in USD per second of output, when published.
+    #[serde(default)]
+    pub price_per_second: Option<f64>,
+    /// Release date; month-only values ("2025-12") map to the first of the month.
+    #[serde(default, deserialize_with = "crate::utils::deserialize_date")]
+    pub release_date: Option<NaiveDate>,
     #[serde(default)]
     pub categories: Option<Vec<CategoryScore>>,
     #[serde(flatten)]
@@ -99,6 +110,24 @@ mod tests {
         assert_eq!(model.elo_score(), 1246.0);
         assert_eq!(model.ranking(), 1);
         assert_eq!(model.creator_name(), "OpenAI");
+        assert_eq!(model.release_date, NaiveDate::from_ymd_opt(2025, 12, 1));
+        assert_eq!(model.price_per_1m_characters, None);
+    }
+
+    #[test]
+    fn test_speech_model_pricing() {
+        let json = r#"{
+            "id": "tts-id",
+            "name": "Voice Pro",
+            "slug": "voice-pro",
+            "model_creator": { "id": "c-id", "name": "Voices" },
+            "price_per_1m_characters": 15.0,
+            "price_per_minute": 0.015
+        }"#;
+
+        let model: MediaModel = serde_json::from_str(json).unwrap();
+        assert_eq!(model.price_per_1m_characters, Some(15.0));
+        assert_eq!(model.price_per_minute, Some(0.015));
     }
 
     #[test]
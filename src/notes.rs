@@ -0,0 +1,247 @@
+//! Local notes and tags attached to models.
+//!
+//! Stored as `notes.toml` next to `config.toml`, keyed by model slug:
+//!
+//! ```toml
+//! [models.gpt-4o]
+//! notes = ["approved for prod"]
+//! tags = ["prod"]
+//! ```
+//!
+//! Notes and tags are the user's own and never leave the machine; they are
+//! shown by `get` and matched by `--tag`.
+
+use crate::config::Config;
+use crate::error::{AppError, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+use std::path::{Path, PathBuf};
+
+/// Notes and tags of one model.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ModelNotes {
+    /// Free-text notes, oldest first.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub notes: Vec<String>,
+    /// Tags, lowercased.
+    #[serde(default, skip_serializing_if = "BTreeSet::is_empty")]
+    pub tags: BTreeSet<String>,
+}
+
+impl ModelNotes {
+    fn is_empty(&self) -> bool {
+        self.notes.is_empty() && self.tags.is_empty()
+    }
+}
+
+/// All local notes and tags, keyed by lowercased model slug.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Notes {
+    #[serde(default)]
+    pub models: BTreeMap<String, ModelNotes>,
+}
+
+/// Normalize a tag: trimmed and lowercased.
+///
+/// # Errors
+///
+/// Returns `AppError::Config` for an empty tag or one with whitespace or
+/// commas, which could not be passed back to `--tag`.
+pub fn normalize_tag(tag: &str) -> Result<String> {
+    let tag = tag.trim().to_lowercase();
+    if tag.is_empty() || tag.chars().any(|c| c.is_whitespace() || c == ',') {
+        return Err(AppError::Config(format!(
+            "Invalid tag '{}': tags cannot be empty or contain spaces or commas",
+            tag
+        )));
+    }
+    Ok(tag)
+}
+
+impl Notes {
+    /// Path of the notes file, next to the config file.
+    pub fn path() -> Result<PathBuf> {
+        Ok(Config::config_path()?.with_file_name("notes.toml"))
+    }
+
+    /// Load the notes file, or empty notes when there is none.
+    pub fn load() -> Result<Self> {
+        Self::load_from(&Self::path()?)
+    }
+
+    fn load_from(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        Ok(toml::from_str(&std::fs::read_to_string(path)?)?)
+    }
+
+    /// Save the notes file.
+    pub fn save(&self) -> Result<()> {
+        self.save_to(&Self::path()?)
+    }
+
+    fn save_to(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(path, toml::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    /// Notes and tags of `slug`, if any.
+    pub fn get(&self, slug: &str) -> Option<&ModelNotes> {
+        self.models.get(&slug.to_lowercase())
+    }
+
+    fn entry(&mut self, slug: &str) -> &mut ModelNotes {
+        self.models.entry(slug.to_lowercase()).or_default()
+    }
+
+    /// Drop `slug` once it has neither notes nor tags.
+    fn prune(&mut self, slug: &str) {
+        let key = slug.to_lowercase();
+        if self.models.get(&key).is_some_and(ModelNotes::is_empty) {
+            self.models.remove(&key);
+        }
+    }
+
+    /// Append a note to `slug`.
+    pub fn add_note(&mut self, slug: &str, note: &str) {
+        self.entry(slug).notes.push(note.trim().to_string());
+    }
+
+    /// Remove the `index`th note (1-based) of `slug`, or all of them.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AppError::NotFound` when there is no such note.
+    pub fn remove_notes(&mut self, slug: &str, index: Option<usize>) -> Result<Vec<String>> {
+        let count = self.get(slug).map_or(0, |m| m.notes.len());
+        let removed = match index {
+            None if count > 0 => std::mem::take(&mut self.entry(slug).notes),
+            Some(i) if (1..=count).contains(&i) => vec![self.entry(slug).notes.remove(i - 1)],
+            _ => {
+                return Err(AppError::NotFound(match index {
+                    Some(i) => format!("{} has no note #{} ({} notes)", slug, i, count),
+                    None => format!("{} has no notes", slug),
+                }))
+            }
+        };
+        self.prune(slug);
+        Ok(removed)
+    }
+
+    /// Add `tags` to `slug`.
+    pub fn add_tags(&mut self, slug: &str, tags: &[String]) -> Result<()> {
+        let tags = tags
+            .iter()
+            .map(|t| normalize_tag(t))
+            .collect::<Result<Vec<_>>>()?;
+        self.entry(slug).tags.extend(tags);
+        Ok(())
+    }
+
+    /// Remove `tags` from `slug`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AppError::NotFound` when the model has none of them.
+    pub fn remove_tags(&mut self, slug: &str, tags: &[String]) -> Result<()> {
+        let tags = tags
+            .iter()
+            .map(|t| normalize_tag(t))
+            .collect::<Result<Vec<_>>>()?;
+        let mut removed = false;
+        if let Some(entry) = self.models.get_mut(&slug.to_lowercase()) {
+            for tag in &tags {
+                removed |= entry.tags.remove(tag);
+            }
+        }
+        if !removed {
+            return Err(AppError::NotFound(format!(
+                "{} is not tagged {}",
+                slug,
+                tags.join(", ")
+            )));
+        }
+        self.prune(slug);
+        Ok(())
+    }
+
+    /// Lowercased slugs tagged with every one of `tags`.
+    pub fn tagged(&self, tags: &[String]) -> Result<HashSet<String>> {
+        let tags = tags
+            .iter()
+            .map(|t| normalize_tag(t))
+            .collect::<Result<Vec<_>>>()?;
+        Ok(self
+            .models
+            .iter()
+            .filter(|(_, m)| tags.iter().all(|t| m.tags.contains(t)))
+            .map(|(slug, _)| slug.clone())
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_notes_and_tags() {
+        let mut notes = Notes::default();
+        notes.add_note("GPT-4o", "approved for prod");
+        notes.add_note("gpt-4o", "  watch the rate limits ");
+        notes
+            .add_tags("gpt-4o", &["Prod".into(), "cheap-tier".into()])
+            .unwrap();
+        notes
+            .add_tags("claude-3-5-haiku", &["cheap-tier".into()])
+            .unwrap();
+        assert!(notes.add_tags("gpt-4o", &["two words".into()]).is_err());
+
+        let entry = notes.get("gpt-4o").unwrap();
+        assert_eq!(
+            entry.notes,
+            vec!["approved for prod", "watch the rate limits"]
+        );
+        assert!(entry.tags.contains("prod"));
+
+        let tagged = notes.tagged(&["cheap-tier".into()]).unwrap();
+        assert_eq!(tagged.len(), 2);
+        let tagged = notes.tagged(&["cheap-tier".into(), "PROD".into()]).unwrap();
+        assert_eq!(tagged, HashSet::from(["gpt-4o".to_string()]));
+
+        assert_eq!(
+            notes.remove_notes("gpt-4o", Some(1)).unwrap(),
+            vec!["approved for prod"]
+        );
+        assert!(notes.remove_notes("gpt-4o", Some(5)).is_err());
+        notes
+            .remove_tags("claude-3-5-haiku", &["cheap-tier".into()])
+            .unwrap();
+        // Models left with nothing are dropped
+        assert!(notes.get("claude-3-5-haiku").is_none());
+        assert!(notes
+            .remove_tags("claude-3-5-haiku", &["cheap-tier".into()])
+            .is_err());
+    }
+
+    #[test]
+    fn test_notes_round_trip() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("config").join("notes.toml");
+        assert_eq!(Notes::load_from(&path).unwrap(), Notes::default());
+
+        let mut notes = Notes::default();
+        notes.add_note("gpt-4o", "approved for prod");
+        notes.add_tags("gpt-4o", &["prod".into()]).unwrap();
+        notes.save_to(&path).unwrap();
+        assert_eq!(Notes::load_from(&path).unwrap(), notes);
+        assert!(std::fs::read_to_string(&path)
+            .unwrap()
+            .contains("[models.gpt-4o]"));
+    }
+}
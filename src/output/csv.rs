@@ -1,6 +1,6 @@
 //! CSV formatter.
 
-/// Format data as CSV.
+/// Format data as CSV, after the `--attribution` comment header if enabled.
 pub fn format_csv(headers: &[&str], rows: &[Vec<String>]) -> String {
     let mut wtr = csv::Writer::from_writer(vec![]);
 
@@ -13,6 +13,6 @@ pub fn format_csv(headers: &[&str], rows: &[Vec<String>]) -> String {
     }
 
     wtr.into_inner()
-        .map(|v| String::from_utf8_lossy(&v).to_string())
+        .map(|v| super::export::csv_header() + &String::from_utf8_lossy(&v))
         .unwrap_or_else(|_| "Error formatting CSV".into())
 }
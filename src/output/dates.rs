@@ -0,0 +1,183 @@
+//! Date display for human-readable output.
+//!
+//! Dates are stored and exchanged as ISO-8601 (`2024-05-13`): Parquet
+//! files, JSON, CSV, plain and porcelain output, and `get` never change.
+//! Table and Markdown output, and prose such as `changelog`, render dates
+//! with [`display`], in the format chosen with `--date-format`.
+
+use crate::error::{AppError, Result};
+use chrono::format::{Item, StrftimeItems};
+use chrono::NaiveDate;
+use std::borrow::Cow;
+use std::str::FromStr;
+use std::sync::OnceLock;
+
+/// How dates are shown.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum DateFormat {
+    /// `2024-05-13`
+    #[default]
+    Iso,
+    /// `13 May 2024`
+    DayMonthYear,
+    /// `May 13, 2024`
+    MonthDayYear,
+    /// A strftime pattern, e.g. `%d.%m.%Y`.
+    Custom(String),
+}
+
+impl DateFormat {
+    /// The format used by the locale named by the first set of `LC_ALL`,
+    /// `LC_TIME` and `LANG`: ISO for `C`/`POSIX`, no locale, and locales
+    /// that write year first; month first for the US; day first otherwise.
+    fn from_locale(var: impl Fn(&str) -> Option<String>) -> Self {
+        let Some(locale) = ["LC_ALL", "LC_TIME", "LANG"]
+            .into_iter()
+            .find_map(|name| var(name).filter(|v| !v.is_empty()))
+        else {
+            return Self::Iso;
+        };
+        // Drop the encoding and modifier: en_US.UTF-8@euro -> en_US
+        let locale = locale.split(['.', '@']).next().unwrap_or_default();
+        let (language, region) = locale.split_once('_').unwrap_or((locale, ""));
+        match (language, region) {
+            ("C" | "POSIX" | "", _) => Self::Iso,
+            (_, "US") => Self::MonthDayYear,
+            ("ja" | "zh" | "ko" | "hu" | "lt" | "sv", _) => Self::Iso,
+            _ => Self::DayMonthYear,
+        }
+    }
+
+    fn pattern(&self) -> &str {
+        match self {
+            Self::Iso => "%Y-%m-%d",
+            Self::DayMonthYear => "%-d %b %Y",
+            Self::MonthDayYear => "%b %-d, %Y",
+            Self::Custom(pattern) => pattern,
+        }
+    }
+}
+
+impl FromStr for DateFormat {
+    type Err = AppError;
+
+    /// Parse `iso`, `dmy`, `mdy`, `auto` (from the locale) or a strftime
+    /// pattern containing `%`.
+    fn from_str(s: &str) -> Result<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "iso" => Ok(Self::Iso),
+            "dmy" => Ok(Self::DayMonthYear),
+            "mdy" => Ok(Self::MonthDayYear),
+            "auto" => Ok(Self::from_locale(|name| std::env::var(name).ok())),
+            _ if s.contains('%')
+                && !StrftimeItems::new(s).any(|item| matches!(item, Item::Error)) =>
+            {
+                Ok(Self::Custom(s.to_string()))
+            }
+            _ => Err(AppError::Config(format!(
+                "Invalid --date-format '{}'. Use iso, dmy, mdy, auto, or a strftime pattern such as %d.%m.%Y.",
+                s
+            ))),
+        }
+    }
+}
+
+/// The format chosen with `--date-format`.
+static FORMAT: OnceLock<DateFormat> = OnceLock::new();
+
+/// Use `format` for all later output. Only the first call has an effect.
+pub fn set_format(format: DateFormat) {
+    let _ = FORMAT.set(format);
+}
+
+/// Display a date in the chosen format (ISO unless set).
+pub fn display(date: NaiveDate) -> String {
+    let format = FORMAT.get().cloned().unwrap_or_default();
+    date.format(format.pattern()).to_string()
+}
+
+/// Time from `date` to `today` as a short age ("5 d", "3 wk", "3 mo",
+/// "2 yr"), as `open --age` shows it.
+pub fn age(date: NaiveDate, today: NaiveDate) -> String {
+    let days = (today - date).num_days();
+    if days < 14 {
+        format!("{} d", days)
+    } else if days < 60 {
+        format!("{} wk", days / 7)
+    } else if days < 730 {
+        format!("{} mo", days / 30)
+    } else {
+        format!("{} yr", days / 365)
+    }
+}
+
+/// Display a cell holding an ISO date; other text is kept.
+pub fn display_text(cell: &str) -> Cow<'_, str> {
+    if cell.len() != 10 {
+        return Cow::Borrowed(cell);
+    }
+    match NaiveDate::parse_from_str(cell, "%Y-%m-%d") {
+        Ok(date) => Cow::Owned(display(date)),
+        Err(_) => Cow::Borrowed(cell),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date() -> NaiveDate {
+        NaiveDate::from_ymd_opt(2024, 5, 3).unwrap()
+    }
+
+    #[test]
+    fn test_date_format_patterns() {
+        let show = |format: &str| {
+            date()
+                .format(format.parse::<DateFormat>().unwrap().pattern())
+                .to_string()
+        };
+        assert_eq!(show("iso"), "2024-05-03");
+        assert_eq!(show("DMY"), "3 May 2024");
+        assert_eq!(show("mdy"), "May 3, 2024");
+        assert_eq!(show("%d.%m.%Y"), "03.05.2024");
+        assert!("long".parse::<DateFormat>().is_err());
+        assert!("%Q".parse::<DateFormat>().is_err());
+    }
+
+    #[test]
+    fn test_date_format_from_locale() {
+        let locale = |vars: &'static [(&'static str, &'static str)]| {
+            DateFormat::from_locale(|name| {
+                vars.iter()
+                    .find(|(n, _)| *n == name)
+                    .map(|(_, v)| v.to_string())
+            })
+        };
+        assert_eq!(locale(&[]), DateFormat::Iso);
+        assert_eq!(locale(&[("LANG", "C.UTF-8")]), DateFormat::Iso);
+        assert_eq!(locale(&[("LANG", "en_US.UTF-8")]), DateFormat::MonthDayYear);
+        assert_eq!(locale(&[("LANG", "en_GB.UTF-8")]), DateFormat::DayMonthYear);
+        assert_eq!(locale(&[("LANG", "ja_JP.UTF-8")]), DateFormat::Iso);
+        // LC_TIME overrides LANG
+        assert_eq!(
+            locale(&[("LC_TIME", "de_DE@euro"), ("LANG", "en_US.UTF-8")]),
+            DateFormat::DayMonthYear
+        );
+    }
+
+    #[test]
+    fn test_age() {
+        let age = |days| age(date() - chrono::Duration::days(days), date());
+        assert_eq!(age(5), "5 d");
+        assert_eq!(age(21), "3 wk");
+        assert_eq!(age(95), "3 mo");
+        assert_eq!(age(800), "2 yr");
+    }
+
+    #[test]
+    fn test_display_text_keeps_other_cells() {
+        assert_eq!(display_text("gpt-4o"), "gpt-4o");
+        assert_eq!(display_text("2024-13-01"), "2024-13-01");
+    }
+}
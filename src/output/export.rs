@@ -0,0 +1,92 @@
+//! Attribution metadata embedded in exported output (`--attribution`).
+//!
+//! JSON output is wrapped as `{"_meta": ..., "data": ...}` and CSV output
+//! starts with `#` comment lines, so extracts shared outside the terminal
+//! keep the data attribution and the timestamps of the data they came from.
+
+use serde::Serialize;
+use std::sync::OnceLock;
+
+/// Export metadata, set once per run.
+static EXPORT_META: OnceLock<ExportMeta> = OnceLock::new();
+
+/// Attribution and source timestamps for exported output.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ExportMeta {
+    pub attribution: &'static [&'static str],
+    /// Tool and version that produced the output.
+    pub generator: String,
+    /// When the hosted data was generated (RFC 3339), if known.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub data_generated_at: Option<String>,
+    /// When the cached data was fetched (RFC 3339), if known.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fetched_at: Option<String>,
+}
+
+impl ExportMeta {
+    /// Metadata for data generated and fetched at the given times.
+    pub fn new(data_generated_at: Option<String>, fetched_at: Option<String>) -> Self {
+        Self {
+            attribution: super::ATTRIBUTION,
+            generator: format!("which-llm {}", env!("CARGO_PKG_VERSION")),
+            data_generated_at,
+            fetched_at,
+        }
+    }
+
+    /// `# `-prefixed lines to put before a CSV header.
+    pub fn comment_header(&self) -> String {
+        let mut lines: Vec<String> = self.attribution.iter().map(|a| a.to_string()).collect();
+        lines.push(format!("Generated by {}", self.generator));
+        if let Some(generated_at) = &self.data_generated_at {
+            lines.push(format!("Data generated at {}", generated_at));
+        }
+        if let Some(fetched_at) = &self.fetched_at {
+            lines.push(format!("Data fetched at {}", fetched_at));
+        }
+        lines.iter().map(|line| format!("# {}\n", line)).collect()
+    }
+}
+
+/// Embed `meta` in all later JSON and CSV output.
+pub fn enable(meta: ExportMeta) {
+    let _ = EXPORT_META.set(meta);
+}
+
+/// The export metadata, when `--attribution` is on.
+pub fn meta() -> Option<&'static ExportMeta> {
+    EXPORT_META.get()
+}
+
+/// CSV comment header for the current run; empty unless enabled.
+pub fn csv_header() -> String {
+    meta().map(ExportMeta::comment_header).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_comment_header() {
+        let meta = ExportMeta::new(None, Some("2025-06-01T08:00:00Z".into()));
+        let header = meta.comment_header();
+        assert!(header.starts_with("# Data provided by Artificial Analysis"));
+        assert!(header.contains("# Generated by which-llm "));
+        assert!(header.contains("# Data fetched at 2025-06-01T08:00:00Z\n"));
+        assert!(!header.contains("generated at"));
+        assert!(header.lines().all(|line| line.starts_with("# ")));
+    }
+
+    #[test]
+    fn test_serialize_skips_unknown_timestamps() {
+        let json = serde_json::to_value(ExportMeta::new(Some("2025-06-01".into()), None)).unwrap();
+        assert_eq!(json["data_generated_at"], "2025-06-01");
+        assert!(json.get("fetched_at").is_none());
+        assert!(json["attribution"][1]
+            .as_str()
+            .unwrap()
+            .contains("models.dev"));
+    }
+}
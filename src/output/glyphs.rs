@@ -0,0 +1,95 @@
+//! Glyphs for booleans in table output.
+//!
+//! Table and Markdown output show boolean cells (capabilities such as
+//! `tool_call`) as `✓`/`✗`. Terminals whose locale is not UTF-8, and
+//! `--ascii`, get `yes`/`no` instead. JSON, CSV, plain and porcelain output
+//! keep the raw `true`/`false`.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::OnceLock;
+
+/// Whether `--ascii` was passed.
+static ASCII: AtomicBool = AtomicBool::new(false);
+
+/// Use `yes`/`no` for all later output, whatever the locale (`--ascii`).
+pub fn force_ascii() {
+    ASCII.store(true, Ordering::Relaxed);
+}
+
+/// Whether the locale named by the first set of `LC_ALL`, `LC_CTYPE` and
+/// `LANG` uses UTF-8. The Windows console always gets UTF-16, so it counts.
+fn locale_is_utf8(var: impl Fn(&str) -> Option<String>) -> bool {
+    if cfg!(windows) {
+        return true;
+    }
+    ["LC_ALL", "LC_CTYPE", "LANG"]
+        .into_iter()
+        .filter_map(|name| var(name).filter(|v| !v.is_empty()))
+        .next()
+        .map(|locale| {
+            let locale = locale.to_lowercase();
+            locale.contains("utf-8") || locale.contains("utf8")
+        })
+        .unwrap_or(false)
+}
+
+/// Whether glyphs can be used, from `--ascii` and the locale.
+fn unicode() -> bool {
+    static UTF8: OnceLock<bool> = OnceLock::new();
+    !ASCII.load(Ordering::Relaxed)
+        && *UTF8.get_or_init(|| locale_is_utf8(|name| std::env::var(name).ok()))
+}
+
+/// Display a boolean cell.
+pub fn boolean(value: bool) -> &'static str {
+    match (value, unicode()) {
+        (true, true) => "✓",
+        (false, true) => "✗",
+        (true, false) => "yes",
+        (false, false) => "no",
+    }
+}
+
+/// Display a cell already rendered as `true`/`false`; other text is kept.
+pub fn boolean_text(cell: &str) -> &str {
+    match cell {
+        "true" => boolean(true),
+        "false" => boolean(false),
+        other => other,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_locale_is_utf8() {
+        let env = |vars: &'static [(&'static str, &'static str)]| {
+            move |name: &str| {
+                vars.iter()
+                    .find(|(n, _)| *n == name)
+                    .map(|(_, v)| v.to_string())
+            }
+        };
+        if cfg!(windows) {
+            return;
+        }
+        assert!(locale_is_utf8(env(&[("LANG", "en_US.UTF-8")])));
+        assert!(locale_is_utf8(env(&[("LC_CTYPE", "de_DE.utf8")])));
+        // LC_ALL overrides LANG; an empty value is skipped
+        assert!(!locale_is_utf8(env(&[
+            ("LC_ALL", "C"),
+            ("LANG", "en_US.UTF-8")
+        ])));
+        assert!(locale_is_utf8(env(&[("LC_ALL", ""), ("LANG", "C.UTF-8")])));
+        assert!(!locale_is_utf8(env(&[])));
+    }
+
+    #[test]
+    fn test_boolean_text_keeps_other_cells() {
+        assert_eq!(boolean_text("gpt-4o"), "gpt-4o");
+        assert_eq!(boolean_text(""), "");
+        assert!(["✓", "yes"].contains(&boolean_text("true")));
+    }
+}
@@ -1,8 +1,58 @@
 //! JSON formatter.
 
 use serde::Serialize;
+use serde_json::json;
+use std::sync::atomic::{AtomicBool, Ordering};
 
-/// Format data as pretty-printed JSON.
+/// Version of the agent-mode envelope; bumped only on breaking changes.
+pub const ENVELOPE_VERSION: u32 = 1;
+
+/// Whether JSON output is wrapped in the agent-mode envelope.
+static ENVELOPE: AtomicBool = AtomicBool::new(false);
+
+/// Wrap all later JSON output as `{"data": ..., "metadata": ...}`, with the
+/// tool version and data attribution in `metadata` (agent mode).
+pub fn enable_envelope() {
+    ENVELOPE.store(true, Ordering::Relaxed);
+}
+
+/// Metadata attached to enveloped output.
+fn metadata() -> serde_json::Value {
+    json!({
+        "tool": "which-llm",
+        "version": env!("CARGO_PKG_VERSION"),
+        "envelope_version": ENVELOPE_VERSION,
+        "attribution": super::ATTRIBUTION,
+    })
+}
+
+/// Format data as pretty-printed JSON, wrapped with agent-mode or
+/// `--attribution` metadata when enabled.
 pub fn format_json<T: Serialize + ?Sized>(data: &T) -> String {
-    serde_json::to_string_pretty(data).unwrap_or_else(|_| "Error formatting JSON".into())
+    let formatted = if ENVELOPE.load(Ordering::Relaxed) {
+        serde_json::to_value(data).and_then(|data| {
+            serde_json::to_string_pretty(&json!({ "data": data, "metadata": metadata() }))
+        })
+    } else if let Some(meta) = super::export::meta() {
+        serde_json::to_value(data)
+            .and_then(|data| serde_json::to_string_pretty(&json!({ "_meta": meta, "data": data })))
+    } else {
+        serde_json::to_string_pretty(data)
+    };
+    formatted.unwrap_or_else(|_| "Error formatting JSON".into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_metadata() {
+        let metadata = metadata();
+        assert_eq!(metadata["envelope_version"], ENVELOPE_VERSION);
+        assert!(metadata["attribution"][0]
+            .as_str()
+            .unwrap()
+            .contains("Artificial Analysis"));
+    }
 }
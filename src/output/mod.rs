@@ -1,14 +1,25 @@
 //! Output formatting module.
 
 pub mod csv;
+pub mod dates;
+pub mod export;
+pub mod glyphs;
 pub mod json;
 pub mod markdown;
 pub mod plain;
+pub mod porcelain;
 pub mod table;
 
 use serde::Serialize;
 use tabled::Tabled;
 
+/// Attribution required when using the data; shown by `info` and included
+/// in agent-mode JSON metadata, `--attribution` exports and Parquet files.
+pub const ATTRIBUTION: &[&str] = &[
+    "Data provided by Artificial Analysis (https://artificialanalysis.ai)",
+    "Capability data from models.dev (https://models.dev)",
+];
+
 /// Output format selection.
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
 pub enum OutputFormat {
@@ -18,6 +29,8 @@ pub enum OutputFormat {
     Csv,
     Table,
     Plain,
+    /// Stable tab-separated output with a header line (`--porcelain`).
+    Porcelain,
 }
 
 /// Trait for types that can be formatted for output.
@@ -29,6 +42,17 @@ pub trait Formattable: Serialize + Tabled {
     fn to_row(&self) -> Vec<String>;
 }
 
+/// Note on stderr that a row limit set by `flag` hid some of `total` rows,
+/// when fewer than `total` are shown.
+pub fn note_hidden(shown: usize, total: usize, flag: &str) {
+    if total > shown {
+        eprintln!(
+            "showing {} of {} models (use --all or {} to adjust)",
+            shown, total, flag
+        );
+    }
+}
+
 /// Format a collection of items.
 pub fn format_output<T: Formattable>(data: &[T], format: OutputFormat) -> String {
     match format {
@@ -46,5 +70,9 @@ pub fn format_output<T: Formattable>(data: &[T], format: OutputFormat) -> String
             let rows: Vec<Vec<String>> = data.iter().map(|item| item.to_row()).collect();
             plain::format_plain(&rows)
         }
+        OutputFormat::Porcelain => {
+            let rows = data.iter().map(|item| item.to_row());
+            porcelain::format_porcelain(<T as Formattable>::headers(), rows)
+        }
     }
 }
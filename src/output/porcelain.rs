@@ -0,0 +1,60 @@
+//! Porcelain formatter: stable, tab-separated output for scripts.
+//!
+//! The first line holds the column names; columns keep the order of the
+//! command's fixed header list. Tabs, newlines and backslashes inside values
+//! are escaped as `\t`, `\n`, `\r` and `\\`, so every record is one line.
+
+/// Escape a value so it cannot break the record structure.
+fn escape(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for ch in value.chars() {
+        match ch {
+            '\\' => escaped.push_str("\\\\"),
+            '\t' => escaped.push_str("\\t"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            _ => escaped.push(ch),
+        }
+    }
+    escaped
+}
+
+fn format_record<S: AsRef<str>>(values: &[S]) -> String {
+    values
+        .iter()
+        .map(|value| escape(value.as_ref()))
+        .collect::<Vec<_>>()
+        .join("\t")
+}
+
+/// Format a header line followed by one line per row.
+///
+/// An empty result is just the header line.
+pub fn format_porcelain<H, R>(headers: &[H], rows: impl IntoIterator<Item = R>) -> String
+where
+    H: AsRef<str>,
+    R: AsRef<[String]>,
+{
+    let mut lines = vec![format_record(headers)];
+    lines.extend(rows.into_iter().map(|row| format_record(row.as_ref())));
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_porcelain() {
+        let rows = vec![
+            vec!["GPT-5".to_string(), "1.25".to_string()],
+            vec!["a\tb\nc\\d".to_string(), String::new()],
+        ];
+        assert_eq!(
+            format_porcelain(&["name", "price"], &rows),
+            "name\tprice\nGPT-5\t1.25\na\\tb\\nc\\\\d\t"
+        );
+        let empty: Vec<Vec<String>> = Vec::new();
+        assert_eq!(format_porcelain(&["name"], empty), "name");
+    }
+}
@@ -1,15 +1,24 @@
 //! Parquet serialization for cached data.
 //!
-//! Writes data to Parquet files using DuckDB.
+//! Writes data to Parquet files using DuckDB. Compression and row group size
+//! come from the `[parquet]` section of the config file. All writes go
+//! through a [`ParquetSession`], so one invocation starts DuckDB only once.
 
+use crate::config::ParquetConfig;
 use crate::error::{AppError, Result};
 use crate::models::MediaModel;
-use crate::sources::artificial_analysis::models::AaLlmRow;
+use crate::output::ATTRIBUTION;
+use crate::schema::{TableDef, LLMS_EXTRA, MEDIA_CATEGORIES, RANKED_METRICS, TEXT_TO_IMAGE};
+use crate::sources::artificial_analysis::models::{AaExtraRow, AaLlmRow};
 use crate::sources::artificial_analysis::schema::BENCHMARKS;
 use crate::sources::models_dev::models::ModelsDevRow;
 use crate::sources::models_dev::schema::MODELS;
-use duckdb::{params, Connection};
-use std::path::Path;
+use chrono::NaiveDate;
+use duckdb::types::ToSql;
+use duckdb::{params_from_iter, Connection};
+use std::marker::PhantomData;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, MutexGuard};
 
 /// Flattened media model row for Parquet storage.
 #[derive(Debug)]
@@ -20,7 +29,12 @@ pub struct MediaRow {
     pub creator: String,
     pub elo: Option<f64>,
     pub rank: Option<i32>,
-    pub release_date: Option<String>,
+    pub release_date: Option<NaiveDate>,
+    pub ci95: Option<String>,
+    pub appearances: Option<i64>,
+    pub price_per_1m_characters: Option<f64>,
+    pub price_per_minute: Option<f64>,
+    pub price_per_second: Option<f64>,
 }
 
 impl From<&MediaModel> for MediaRow {
@@ -32,183 +46,470 @@ impl From<&MediaModel> for MediaRow {
             creator: model.model_creator.name.clone(),
             elo: model.elo,
             rank: model.rank.map(|r| r as i32),
-            release_date: model.release_date.clone(),
+            release_date: model.release_date,
+            ci95: model.ci95.clone(),
+            appearances: model.appearances.map(|a| a as i64),
+            price_per_1m_characters: model.price_per_1m_characters,
+            price_per_minute: model.price_per_minute,
+            price_per_second: model.price_per_second,
         }
     }
 }
 
-/// Write benchmark data (from AA) to Parquet.
+/// Flattened media category score for Parquet storage.
 ///
-/// This writes the `benchmarks` table which contains Artificial Analysis data.
-pub fn write_benchmarks_parquet(rows: &[AaLlmRow], path: &Path) -> Result<()> {
-    let conn = Connection::open_in_memory()
-        .map_err(|e| AppError::Cache(format!("DuckDB error: {}", e)))?;
+/// One row per category a model was scored in; `category_type` is one of
+/// `style`, `subject_matter`, or `format`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MediaCategoryRow {
+    pub media_table: String,
+    pub model_id: String,
+    pub category_type: String,
+    pub category: String,
+    pub elo: Option<f64>,
+    pub appearances: Option<i64>,
+}
 
-    conn.execute(&BENCHMARKS.create_table_sql(), [])
-        .map_err(|e| AppError::Cache(format!("DuckDB error: {}", e)))?;
+impl MediaCategoryRow {
+    /// Flatten a media model's category scores into rows.
+    pub fn from_model(media_table: &str, model: &MediaModel) -> Vec<Self> {
+        let Some(categories) = &model.categories else {
+            return Vec::new();
+        };
+
+        categories
+            .iter()
+            .filter_map(|score| {
+                let (category_type, category) = if let Some(c) = &score.style_category {
+                    ("style", c)
+                } else if let Some(c) = &score.subject_matter_category {
+                    ("subject_matter", c)
+                } else if let Some(c) = &score.format_category {
+                    ("format", c)
+                } else {
+                    return None;
+                };
+
+                Some(Self {
+                    media_table: media_table.to_string(),
+                    model_id: model.id.clone(),
+                    category_type: category_type.to_string(),
+                    category: category.clone(),
+                    elo: score.elo,
+                    appearances: score.appearances.map(|a| a as i64),
+                })
+            })
+            .collect()
+    }
+}
 
-    {
-        let mut appender = conn
-            .appender(BENCHMARKS.name)
-            .map_err(|e| AppError::Cache(format!("DuckDB appender error: {}", e)))?;
-
-        for row in rows {
-            appender
-                .append_row(params![
-                    row.id,
-                    row.name,
-                    row.slug,
-                    row.creator,
-                    row.creator_slug,
-                    row.release_date,
-                    row.intelligence,
-                    row.coding,
-                    row.math,
-                    row.mmlu_pro,
-                    row.gpqa,
-                    row.hle,
-                    row.livecodebench,
-                    row.scicode,
-                    row.math_500,
-                    row.aime,
-                    row.input_price,
-                    row.output_price,
-                    row.price,
-                    row.tps,
-                    row.latency,
-                ])
-                .map_err(|e| AppError::Cache(format!("DuckDB append error: {}", e)))?;
-        }
+/// A row that can be appended to a table described by a [`TableDef`].
+///
+/// `to_row` must return one value per column of `TABLE`, in column order.
+/// `WIDTH` is checked against the table at compile time, and every row is
+/// checked against `WIDTH` before it is appended.
+pub trait ToRow {
+    /// Table whose columns this row fills.
+    const TABLE: &'static TableDef;
+    /// Number of values returned by `to_row`.
+    const WIDTH: usize;
+
+    /// Values for each column, in the same order as `TABLE.columns`.
+    fn to_row(&self) -> Vec<Box<dyn ToSql + '_>>;
+}
+
+/// Compile-time check that a row type's width matches its table.
+struct AssertWidth<T>(PhantomData<T>);
+
+impl<T: ToRow> AssertWidth<T> {
+    const OK: () = assert!(
+        T::WIDTH == T::TABLE.columns.len(),
+        "ToRow::WIDTH does not match the number of table columns"
+    );
+}
+
+/// An in-memory DuckDB connection shared by the Parquet writes of one run.
+///
+/// Opening DuckDB costs more than writing a small table, so a refresh opens
+/// one session and passes it to every write. Clones share the connection.
+#[derive(Clone)]
+pub struct ParquetSession {
+    conn: Arc<Mutex<Connection>>,
+    options: ParquetConfig,
+}
+
+impl ParquetSession {
+    /// Open a session writing with the given Parquet options.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AppError::Config` for invalid options, so a bad config fails
+    /// before anything is fetched.
+    pub fn new(options: &ParquetConfig) -> Result<Self> {
+        options.copy_options()?;
+        let conn = Connection::open_in_memory()
+            .map_err(|e| AppError::Cache(format!("DuckDB error: {}", e)))?;
+        Ok(Self {
+            conn: Arc::new(Mutex::new(conn)),
+            options: options.clone(),
+        })
     }
 
-    let path_str = path.to_string_lossy();
-    conn.execute(
-        &format!(
-            "COPY {} TO '{}' (FORMAT PARQUET)",
-            BENCHMARKS.name, path_str
-        ),
-        [],
-    )
-    .map_err(|e| AppError::Cache(format!("DuckDB error: {}", e)))?;
+    /// Lock the shared connection.
+    fn connection(&self) -> MutexGuard<'_, Connection> {
+        self.conn.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// `COPY` options: the configured Parquet options plus key-value
+    /// metadata with the data attribution and the writer, so files copied
+    /// out of the cache still carry them. Nothing time-dependent is
+    /// embedded: the same rows always write the same bytes.
+    fn copy_options(&self) -> Result<String> {
+        Ok(format!(
+            "{}, KV_METADATA {{attribution: '{}', generator: 'which-llm {}''}}",
+            self.options.copy_options()?,
+            ATTRIBUTION.join("; ").replace('\'', "''"),
+            env!("CARGO_PKG_VERSION")
+        ))
+    }
+
+    /// Run a `COPY (<select>) TO <path>` with the session's Parquet options.
+    fn copy_to(&self, select: &str, path: &Path) -> Result<()> {
+        let sql = format!(
+            "COPY ({}) TO '{}' ({})",
+            select,
+            path.to_string_lossy(),
+            self.copy_options()?
+        );
+        self.connection()
+            .execute(&sql, [])
+            .map_err(|e| AppError::Cache(format!("DuckDB error: {}", e)))?;
+        Ok(())
+    }
+}
 
-    Ok(())
+/// Writes rows to a Parquet file using the table's schema definition.
+pub struct ParquetWriter<'a, T> {
+    session: &'a ParquetSession,
+    _row: PhantomData<T>,
 }
 
-/// Write models data (from models.dev) to Parquet.
-pub fn write_models_parquet(rows: &[ModelsDevRow], path: &Path) -> Result<()> {
-    let conn = Connection::open_in_memory()
-        .map_err(|e| AppError::Cache(format!("DuckDB error: {}", e)))?;
+impl<'a, T: ToRow> ParquetWriter<'a, T> {
+    /// Create a writer on the given session.
+    pub fn new(session: &'a ParquetSession) -> Self {
+        // Referencing the constant forces the width check at compile time.
+        let () = AssertWidth::<T>::OK;
+        Self {
+            session,
+            _row: PhantomData,
+        }
+    }
 
-    conn.execute(&MODELS.create_table_sql(), [])
+    /// Write all rows to `path`, replacing any existing file.
+    ///
+    /// The staging table is dropped afterwards, so the session can write the
+    /// same table (or another table sharing its definition) again.
+    pub fn write(&self, rows: &[T], path: &Path) -> Result<()> {
+        let copy_options = self.session.copy_options()?;
+        let table = T::TABLE;
+        let conn = self.session.connection();
+
+        conn.execute(&format!("DROP TABLE IF EXISTS {}", table.name), [])
+            .map_err(|e| AppError::Cache(format!("DuckDB error: {}", e)))?;
+        conn.execute(&table.create_table_sql(), [])
+            .map_err(|e| AppError::Cache(format!("DuckDB error: {}", e)))?;
+
+        {
+            let mut appender = conn
+                .appender(table.name)
+                .map_err(|e| AppError::Cache(format!("DuckDB appender error: {}", e)))?;
+
+            for row in rows {
+                let values = row.to_row();
+                if values.len() != T::WIDTH {
+                    return Err(AppError::Cache(format!(
+                        "Row for table '{}' has {} values, expected {}",
+                        table.name,
+                        values.len(),
+                        T::WIDTH
+                    )));
+                }
+                appender
+                    .append_row(params_from_iter(values.iter()))
+                    .map_err(|e| AppError::Cache(format!("DuckDB append error: {}", e)))?;
+            }
+        }
+
+        let path_str = path.to_string_lossy();
+        conn.execute(
+            &format!("COPY {} TO '{}' ({})", table.name, path_str, copy_options),
+            [],
+        )
         .map_err(|e| AppError::Cache(format!("DuckDB error: {}", e)))?;
+        conn.execute(&format!("DROP TABLE {}", table.name), [])
+            .map_err(|e| AppError::Cache(format!("DuckDB error: {}", e)))?;
 
-    {
-        let mut appender = conn
-            .appender(MODELS.name)
-            .map_err(|e| AppError::Cache(format!("DuckDB appender error: {}", e)))?;
-
-        for row in rows {
-            appender
-                .append_row(params![
-                    // Provider identity
-                    row.provider_id,
-                    row.provider_name,
-                    // Provider metadata
-                    row.provider_env,
-                    row.provider_npm,
-                    row.provider_api,
-                    row.provider_doc,
-                    // Model identity
-                    row.model_id,
-                    row.model_name,
-                    row.family,
-                    // Capabilities
-                    row.attachment,
-                    row.reasoning,
-                    row.tool_call,
-                    row.structured_output,
-                    row.temperature,
-                    // Metadata
-                    row.knowledge,
-                    row.release_date,
-                    row.last_updated,
-                    row.open_weights,
-                    row.status,
-                    // Limits
-                    row.context_window.map(|v| v as i64),
-                    row.max_input_tokens.map(|v| v as i64),
-                    row.max_output_tokens.map(|v| v as i64),
-                    // Costs
-                    row.cost_input,
-                    row.cost_output,
-                    row.cost_cache_read,
-                    row.cost_cache_write,
-                    // Modalities
-                    row.input_modalities,
-                    row.output_modalities,
-                ])
-                .map_err(|e| AppError::Cache(format!("DuckDB append error: {}", e)))?;
-        }
+        Ok(())
     }
+}
 
-    let path_str = path.to_string_lossy();
-    conn.execute(
-        &format!("COPY {} TO '{}' (FORMAT PARQUET)", MODELS.name, path_str),
-        [],
-    )
-    .map_err(|e| AppError::Cache(format!("DuckDB error: {}", e)))?;
+impl ToRow for AaLlmRow {
+    const TABLE: &'static TableDef = &BENCHMARKS;
+    const WIDTH: usize = 21;
+
+    fn to_row(&self) -> Vec<Box<dyn ToSql + '_>> {
+        vec![
+            Box::new(&self.id),
+            Box::new(&self.name),
+            Box::new(&self.slug),
+            Box::new(&self.creator),
+            Box::new(&self.creator_slug),
+            Box::new(self.release_date),
+            Box::new(self.intelligence),
+            Box::new(self.coding),
+            Box::new(self.math),
+            Box::new(self.mmlu_pro),
+            Box::new(self.gpqa),
+            Box::new(self.hle),
+            Box::new(self.livecodebench),
+            Box::new(self.scicode),
+            Box::new(self.math_500),
+            Box::new(self.aime),
+            Box::new(self.input_price),
+            Box::new(self.output_price),
+            Box::new(self.price),
+            Box::new(self.tps),
+            Box::new(self.latency),
+        ]
+    }
+}
+
+impl ToRow for AaExtraRow {
+    const TABLE: &'static TableDef = &LLMS_EXTRA;
+    const WIDTH: usize = 3;
 
-    Ok(())
+    fn to_row(&self) -> Vec<Box<dyn ToSql + '_>> {
+        vec![
+            Box::new(&self.model_id),
+            Box::new(&self.json_path),
+            Box::new(&self.value),
+        ]
+    }
 }
 
-/// Write media models to a Parquet file.
-pub fn write_media_parquet(models: &[MediaModel], path: &Path) -> Result<()> {
-    let conn = Connection::open_in_memory()
-        .map_err(|e| AppError::Cache(format!("DuckDB error: {}", e)))?;
+impl ToRow for ModelsDevRow {
+    const TABLE: &'static TableDef = &MODELS;
+    const WIDTH: usize = 31;
+
+    fn to_row(&self) -> Vec<Box<dyn ToSql + '_>> {
+        vec![
+            // Provider identity
+            Box::new(&self.provider_id),
+            Box::new(&self.provider_name),
+            // Provider metadata
+            Box::new(&self.provider_env),
+            Box::new(&self.provider_npm),
+            Box::new(&self.provider_api),
+            Box::new(&self.provider_doc),
+            // Model identity
+            Box::new(&self.model_id),
+            Box::new(&self.model_name),
+            Box::new(&self.family),
+            // Capabilities
+            Box::new(self.attachment),
+            Box::new(self.reasoning),
+            Box::new(self.tool_call),
+            Box::new(self.structured_output),
+            Box::new(self.temperature),
+            // Metadata
+            Box::new(self.knowledge),
+            Box::new(self.release_date),
+            Box::new(self.last_updated),
+            Box::new(self.open_weights),
+            Box::new(&self.status),
+            // Limits
+            Box::new(self.context_window.map(|v| v as i64)),
+            Box::new(self.max_input_tokens.map(|v| v as i64)),
+            Box::new(self.max_output_tokens.map(|v| v as i64)),
+            // Costs
+            Box::new(self.cost_input),
+            Box::new(self.cost_output),
+            Box::new(self.cost_cache_read),
+            Box::new(self.cost_cache_write),
+            // Long-context tier
+            Box::new(self.long_context_threshold.map(|v| v as i64)),
+            Box::new(self.cost_input_long_context),
+            Box::new(self.cost_output_long_context),
+            // Modalities
+            Box::new(&self.input_modalities),
+            Box::new(&self.output_modalities),
+        ]
+    }
+}
 
-    conn.execute(
-        "CREATE TABLE media (
-            id VARCHAR NOT NULL,
-            name VARCHAR NOT NULL,
-            slug VARCHAR NOT NULL,
-            creator VARCHAR NOT NULL,
-            elo DOUBLE,
-            rank INTEGER,
-            release_date VARCHAR
-        )",
-        [],
-    )
-    .map_err(|e| AppError::Cache(format!("DuckDB error: {}", e)))?;
-
-    {
-        let mut appender = conn
-            .appender("media")
-            .map_err(|e| AppError::Cache(format!("DuckDB appender error: {}", e)))?;
-
-        for model in models {
-            let row = MediaRow::from(model);
-            appender
-                .append_row(params![
-                    row.id,
-                    row.name,
-                    row.slug,
-                    row.creator,
-                    row.elo,
-                    row.rank,
-                    row.release_date,
-                ])
-                .map_err(|e| AppError::Cache(format!("DuckDB append error: {}", e)))?;
+impl ToRow for MediaRow {
+    // All media tables share the same columns.
+    const TABLE: &'static TableDef = &TEXT_TO_IMAGE;
+    const WIDTH: usize = 12;
+
+    fn to_row(&self) -> Vec<Box<dyn ToSql + '_>> {
+        vec![
+            Box::new(&self.id),
+            Box::new(&self.name),
+            Box::new(&self.slug),
+            Box::new(&self.creator),
+            Box::new(self.elo),
+            Box::new(self.rank),
+            Box::new(self.release_date),
+            Box::new(&self.ci95),
+            Box::new(self.appearances),
+            Box::new(self.price_per_1m_characters),
+            Box::new(self.price_per_minute),
+            Box::new(self.price_per_second),
+        ]
+    }
+}
+
+impl ToRow for MediaCategoryRow {
+    const TABLE: &'static TableDef = &MEDIA_CATEGORIES;
+    const WIDTH: usize = 6;
+
+    fn to_row(&self) -> Vec<Box<dyn ToSql + '_>> {
+        vec![
+            Box::new(&self.media_table),
+            Box::new(&self.model_id),
+            Box::new(&self.category_type),
+            Box::new(&self.category),
+            Box::new(self.elo),
+            Box::new(self.appearances),
+        ]
+    }
+}
+
+/// Write benchmark data (from AA) to Parquet.
+///
+/// This writes the `benchmarks` table which contains Artificial Analysis data.
+pub fn write_benchmarks_parquet(
+    rows: &[AaLlmRow],
+    path: &Path,
+    session: &ParquetSession,
+) -> Result<()> {
+    ParquetWriter::new(session).write(rows, path)
+}
+
+/// Write untyped AA fields to the `llms_extra` key/value Parquet table.
+pub fn write_llms_extra_parquet(
+    rows: &[AaExtraRow],
+    path: &Path,
+    session: &ParquetSession,
+) -> Result<()> {
+    ParquetWriter::new(session).write(rows, path)
+}
+
+/// Append a dated snapshot of the benchmarks table to the history dataset.
+///
+/// Rows are tagged with `snapshot_date` and written to
+/// `<history_dir>/date=YYYY-MM-DD/benchmarks.parquet`. Writing the same date
+/// twice replaces that day's partition, so repeated refreshes stay idempotent.
+pub fn append_benchmarks_snapshot(
+    benchmarks_path: &Path,
+    history_dir: &Path,
+    snapshot_date: NaiveDate,
+    session: &ParquetSession,
+) -> Result<PathBuf> {
+    let date = snapshot_date.format("%Y-%m-%d").to_string();
+    let partition_dir = history_dir.join(format!("date={}", date));
+    std::fs::create_dir_all(&partition_dir)?;
+    let snapshot_path = partition_dir.join("benchmarks.parquet");
+
+    session.copy_to(
+        &format!(
+            "SELECT *, DATE '{}' AS snapshot_date FROM read_parquet('{}')",
+            date,
+            benchmarks_path.to_string_lossy()
+        ),
+        &snapshot_path,
+    )?;
+
+    Ok(snapshot_path)
+}
+
+/// Build the SELECT that derives `benchmark_ranks` from the benchmarks table.
+///
+/// 0–1 accuracies are scaled to 0–100 (values already above 1 are kept).
+/// Percentile ranks are computed among models with a score for the metric,
+/// so missing scores neither rank nor drag others down.
+fn benchmark_ranks_sql(benchmarks_path: &Path) -> String {
+    let mut columns = vec![
+        "slug".to_string(),
+        "name".to_string(),
+        "creator".to_string(),
+    ];
+    for (metric, is_fraction) in RANKED_METRICS {
+        if *is_fraction {
+            columns.push(format!(
+                "CASE WHEN {m} <= 1 THEN {m} * 100 ELSE {m} END AS {m}",
+                m = metric
+            ));
+        } else {
+            columns.push(metric.to_string());
         }
+        columns.push(format!(
+            "CASE WHEN {m} IS NULL THEN NULL ELSE CAST(round(100 * percent_rank() OVER (PARTITION BY {m} IS NULL ORDER BY {m})) AS INTEGER) END AS {m}_pct",
+            m = metric
+        ));
     }
 
-    let path_str = path.to_string_lossy();
-    conn.execute(
-        &format!("COPY media TO '{}' (FORMAT PARQUET)", path_str),
-        [],
+    format!(
+        "SELECT {} FROM read_parquet('{}')",
+        columns.join(", "),
+        benchmarks_path.to_string_lossy()
     )
-    .map_err(|e| AppError::Cache(format!("DuckDB error: {}", e)))?;
+}
+
+/// Derive the `benchmark_ranks` table from the cached benchmarks Parquet file.
+pub fn write_benchmark_ranks(
+    benchmarks_path: &Path,
+    ranks_path: &Path,
+    session: &ParquetSession,
+) -> Result<()> {
+    session.copy_to(&benchmark_ranks_sql(benchmarks_path), ranks_path)
+}
 
-    Ok(())
+/// Write models data (from models.dev) to Parquet.
+pub fn write_models_parquet(
+    rows: &[ModelsDevRow],
+    path: &Path,
+    session: &ParquetSession,
+) -> Result<()> {
+    ParquetWriter::new(session).write(rows, path)
+}
+
+/// Write media models to a Parquet file.
+pub fn write_media_parquet(
+    models: &[MediaModel],
+    path: &Path,
+    session: &ParquetSession,
+) -> Result<()> {
+    let rows: Vec<MediaRow> = models.iter().map(MediaRow::from).collect();
+    ParquetWriter::new(session).write(&rows, path)
+}
+
+/// Write the category scores of a media table to its `_categories` Parquet file.
+///
+/// Rows are tagged with `media_table` so all category files can be read
+/// together as the `media_categories` table.
+pub fn write_media_categories_parquet(
+    media_table: &str,
+    models: &[MediaModel],
+    path: &Path,
+    session: &ParquetSession,
+) -> Result<()> {
+    let rows: Vec<MediaCategoryRow> = models
+        .iter()
+        .flat_map(|model| MediaCategoryRow::from_model(media_table, model))
+        .collect();
+    ParquetWriter::new(session).write(&rows, path)
 }
 
 #[cfg(test)]
@@ -216,6 +517,10 @@ mod tests {
     use super::*;
     use tempfile::TempDir;
 
+    fn session() -> ParquetSession {
+        ParquetSession::new(&ParquetConfig::default()).unwrap()
+    }
+
     fn make_test_aa_row() -> AaLlmRow {
         AaLlmRow {
             id: "test-id".to_string(),
@@ -223,7 +528,7 @@ mod tests {
             slug: "gpt-4o".to_string(),
             creator: "OpenAI".to_string(),
             creator_slug: Some("openai".to_string()),
-            release_date: Some("2024-05-13".to_string()),
+            release_date: NaiveDate::from_ymd_opt(2024, 5, 13),
             intelligence: Some(55.0),
             coding: Some(50.0),
             math: Some(60.0),
@@ -248,7 +553,7 @@ mod tests {
         let parquet_path = temp_dir.path().join("benchmarks.parquet");
 
         let rows = vec![make_test_aa_row()];
-        write_benchmarks_parquet(&rows, &parquet_path).unwrap();
+        write_benchmarks_parquet(&rows, &parquet_path, &session()).unwrap();
 
         assert!(parquet_path.exists());
 
@@ -280,6 +585,204 @@ mod tests {
         assert_eq!(intelligence, Some(55.0));
     }
 
+    #[test]
+    fn test_write_llms_extra_parquet() {
+        let temp_dir = TempDir::new().unwrap();
+        let parquet_path = temp_dir.path().join("llms_extra.parquet");
+
+        let rows = vec![
+            AaExtraRow {
+                model_id: "test-id".to_string(),
+                json_path: "evaluations.new_benchmark".to_string(),
+                value: Some("0.42".to_string()),
+            },
+            AaExtraRow {
+                model_id: "test-id".to_string(),
+                json_path: "evaluations.retired_benchmark".to_string(),
+                value: None,
+            },
+        ];
+        write_llms_extra_parquet(&rows, &parquet_path, &session()).unwrap();
+
+        let conn = Connection::open_in_memory().unwrap();
+        let value: f64 = conn
+            .query_row(
+                &format!(
+                    "SELECT CAST(value AS DOUBLE) FROM read_parquet('{}') WHERE json_path = 'evaluations.new_benchmark'",
+                    parquet_path.to_string_lossy()
+                ),
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(value, 0.42);
+    }
+
+    #[test]
+    fn test_write_benchmark_ranks() {
+        let temp_dir = TempDir::new().unwrap();
+        let parquet_path = temp_dir.path().join("benchmarks.parquet");
+        let ranks_path = temp_dir.path().join("benchmark_ranks.parquet");
+
+        let mut strong = make_test_aa_row();
+        strong.slug = "strong".into();
+        strong.intelligence = Some(70.0);
+        strong.gpqa = Some(0.9);
+        let mut missing = make_test_aa_row();
+        missing.slug = "missing".into();
+        missing.gpqa = None;
+        let rows = vec![make_test_aa_row(), strong, missing];
+        write_benchmarks_parquet(&rows, &parquet_path, &session()).unwrap();
+
+        write_benchmark_ranks(&parquet_path, &ranks_path, &session()).unwrap();
+
+        let conn = Connection::open_in_memory().unwrap();
+        let query = |slug: &str| -> (Option<f64>, Option<i32>) {
+            conn.query_row(
+                &format!(
+                    "SELECT gpqa, gpqa_pct FROM read_parquet('{}') WHERE slug = '{}'",
+                    ranks_path.to_string_lossy(),
+                    slug
+                ),
+                [],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .unwrap()
+        };
+
+        let (gpqa, pct) = query("strong");
+        assert!((gpqa.unwrap() - 90.0).abs() < 1e-9);
+        assert_eq!(pct, Some(100));
+        assert_eq!(query("missing"), (None, None));
+    }
+
+    #[test]
+    fn test_writes_embed_attribution() {
+        let temp_dir = TempDir::new().unwrap();
+        let parquet_path = temp_dir.path().join("benchmarks.parquet");
+        let ranks_path = temp_dir.path().join("benchmark_ranks.parquet");
+        write_benchmarks_parquet(&[make_test_aa_row()], &parquet_path, &session()).unwrap();
+        write_benchmark_ranks(&parquet_path, &ranks_path, &session()).unwrap();
+
+        let conn = Connection::open_in_memory().unwrap();
+        for path in [&parquet_path, &ranks_path] {
+            let metadata = |key: &str| -> String {
+                conn.query_row(
+                    &format!(
+                        "SELECT decode(value) FROM parquet_kv_metadata('{}') WHERE decode(key) = '{}'",
+                        path.to_string_lossy(),
+                        key
+                    ),
+                    [],
+                    |row| row.get(0),
+                )
+                .unwrap()
+            };
+            assert!(metadata("attribution").contains("Artificial Analysis"));
+            assert!(metadata("attribution").contains("models.dev"));
+            assert!(metadata("generator").starts_with("which-llm "));
+        }
+
+        // Rewriting the same rows gives the same file
+        let rewritten = temp_dir.path().join("rewritten.parquet");
+        write_benchmarks_parquet(&[make_test_aa_row()], &rewritten, &session()).unwrap();
+        assert_eq!(
+            std::fs::read(&parquet_path).unwrap(),
+            std::fs::read(&rewritten).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_append_benchmarks_snapshot() {
+        let temp_dir = TempDir::new().unwrap();
+        let parquet_path = temp_dir.path().join("benchmarks.parquet");
+        let history_dir = temp_dir.path().join("llms_history");
+
+        write_benchmarks_parquet(&[make_test_aa_row()], &parquet_path, &session()).unwrap();
+
+        let date = NaiveDate::from_ymd_opt(2025, 1, 15).unwrap();
+        let snapshot_path =
+            append_benchmarks_snapshot(&parquet_path, &history_dir, date, &session()).unwrap();
+        assert!(snapshot_path.ends_with("date=2025-01-15/benchmarks.parquet"));
+
+        // Same-day snapshots replace the partition rather than duplicating rows
+        append_benchmarks_snapshot(&parquet_path, &history_dir, date, &session()).unwrap();
+
+        let conn = Connection::open_in_memory().unwrap();
+        let (count, snapshot_date): (i64, String) = conn
+            .query_row(
+                &format!(
+                    "SELECT COUNT(*), CAST(MAX(snapshot_date) AS VARCHAR) FROM read_parquet('{}/*/*.parquet')",
+                    history_dir.to_string_lossy()
+                ),
+                [],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .unwrap();
+        assert_eq!(count, 1);
+        assert_eq!(snapshot_date, "2025-01-15");
+    }
+
+    fn make_test_media_model() -> MediaModel {
+        serde_json::from_str(
+            r#"{
+                "id": "media-id",
+                "name": "Image Model",
+                "slug": "image-model",
+                "model_creator": { "id": "creator-id", "name": "Creator" },
+                "elo": 1200,
+                "categories": [
+                    { "style_category": "Photorealistic", "elo": 1210, "appearances": 300 },
+                    { "subject_matter_category": "People", "elo": 1190 },
+                    { "elo": 1000 }
+                ]
+            }"#,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_media_category_rows() {
+        let rows = MediaCategoryRow::from_model("text_to_image", &make_test_media_model());
+
+        // Scores without a category name are skipped
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0].media_table, "text_to_image");
+        assert_eq!(rows[0].model_id, "media-id");
+        assert_eq!(rows[0].category_type, "style");
+        assert_eq!(rows[0].category, "Photorealistic");
+        assert_eq!(rows[0].appearances, Some(300));
+        assert_eq!(rows[1].category_type, "subject_matter");
+        assert_eq!(rows[1].appearances, None);
+    }
+
+    #[test]
+    fn test_write_media_categories_parquet() {
+        let temp_dir = TempDir::new().unwrap();
+        let parquet_path = temp_dir.path().join("text_to_image_categories.parquet");
+
+        write_media_categories_parquet(
+            "text_to_image",
+            &[make_test_media_model()],
+            &parquet_path,
+            &session(),
+        )
+        .unwrap();
+
+        let conn = Connection::open_in_memory().unwrap();
+        let elo: Option<f64> = conn
+            .query_row(
+                &format!(
+                    "SELECT elo FROM read_parquet('{}') WHERE category = 'Photorealistic'",
+                    parquet_path.to_string_lossy()
+                ),
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(elo, Some(1210.0));
+    }
+
     #[test]
     fn test_write_models_parquet() {
         let temp_dir = TempDir::new().unwrap();
@@ -300,9 +803,9 @@ mod tests {
             tool_call: Some(true),
             structured_output: Some(true),
             temperature: Some(true),
-            knowledge: Some("2024-04".to_string()),
-            release_date: Some("2024-05-13".to_string()),
-            last_updated: Some("2024-11-20".to_string()),
+            knowledge: NaiveDate::from_ymd_opt(2024, 4, 1),
+            release_date: NaiveDate::from_ymd_opt(2024, 5, 13),
+            last_updated: NaiveDate::from_ymd_opt(2024, 11, 20),
             open_weights: Some(false),
             status: None,
             context_window: Some(128000),
@@ -312,11 +815,14 @@ mod tests {
             cost_output: Some(10.0),
             cost_cache_read: Some(1.25),
             cost_cache_write: Some(3.75),
+            long_context_threshold: None,
+            cost_input_long_context: None,
+            cost_output_long_context: None,
             input_modalities: Some("text,image".to_string()),
             output_modalities: Some("text".to_string()),
         }];
 
-        write_models_parquet(&rows, &parquet_path).unwrap();
+        write_models_parquet(&rows, &parquet_path, &session()).unwrap();
         assert!(parquet_path.exists());
 
         let conn = Connection::open_in_memory().unwrap();
@@ -345,4 +851,106 @@ mod tests {
             .unwrap();
         assert_eq!(tool_call, Some(true));
     }
+
+    #[test]
+    fn test_row_widths_match_tables() {
+        let media = make_test_media_model();
+        let extra = AaExtraRow {
+            model_id: "test-id".to_string(),
+            json_path: "evaluations.new_index".to_string(),
+            value: Some("42".to_string()),
+        };
+
+        assert_eq!(
+            make_test_aa_row().to_row().len(),
+            AaLlmRow::TABLE.columns.len()
+        );
+        assert_eq!(extra.to_row().len(), AaExtraRow::TABLE.columns.len());
+        assert_eq!(
+            MediaRow::from(&media).to_row().len(),
+            MediaRow::TABLE.columns.len()
+        );
+        for row in MediaCategoryRow::from_model("text_to_image", &media) {
+            assert_eq!(row.to_row().len(), MediaCategoryRow::TABLE.columns.len());
+        }
+    }
+
+    #[test]
+    fn test_writer_rejects_short_rows() {
+        struct ShortRow;
+
+        impl ToRow for ShortRow {
+            const TABLE: &'static TableDef = &LLMS_EXTRA;
+            const WIDTH: usize = 3;
+
+            fn to_row(&self) -> Vec<Box<dyn ToSql + '_>> {
+                vec![Box::new("only-one")]
+            }
+        }
+
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("short.parquet");
+        let result = ParquetWriter::new(&session()).write(&[ShortRow], &path);
+
+        assert!(matches!(result, Err(AppError::Cache(msg)) if msg.contains("expected 3")));
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn test_write_media_parquet() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("text_to_image.parquet");
+        let mut model = make_test_media_model();
+        model.release_date = NaiveDate::from_ymd_opt(2025, 3, 1);
+
+        write_media_parquet(&[model], &path, &session()).unwrap();
+
+        let conn = Connection::open_in_memory().unwrap();
+        let release_date: String = conn
+            .query_row(
+                &format!(
+                    "SELECT CAST(release_date AS VARCHAR) FROM read_parquet('{}')",
+                    path.to_string_lossy()
+                ),
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(release_date, "2025-03-01");
+    }
+
+    #[test]
+    fn test_session_reuses_connection_across_writes() {
+        let temp_dir = TempDir::new().unwrap();
+        let session = session();
+        let model = make_test_media_model();
+
+        // Media tables share one definition, so the staging table must not linger
+        for table in ["text_to_image", "text_to_video"] {
+            let path = temp_dir.path().join(format!("{}.parquet", table));
+            write_media_parquet(&[model.clone(), model.clone()], &path, &session).unwrap();
+        }
+        let rows = vec![make_test_aa_row()];
+        let benchmarks_path = temp_dir.path().join("benchmarks.parquet");
+        write_benchmarks_parquet(&rows, &benchmarks_path, &session).unwrap();
+        let ranks_path = temp_dir.path().join("benchmark_ranks.parquet");
+        write_benchmark_ranks(&benchmarks_path, &ranks_path, &session).unwrap();
+
+        let conn = Connection::open_in_memory().unwrap();
+        let count: i64 = conn
+            .query_row(
+                &format!(
+                    "SELECT COUNT(*) FROM read_parquet('{}')",
+                    temp_dir
+                        .path()
+                        .join("text_to_video.parquet")
+                        .to_string_lossy()
+                ),
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(count, 2);
+        assert!(ranks_path.exists());
+    }
 }
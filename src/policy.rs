@@ -0,0 +1,252 @@
+//! Organization policy - governance rules that models must satisfy.
+//!
+//! A policy is a TOML file, by default `policy.toml` next to `config.toml`:
+//!
+//! ```toml
+//! allowed_creators = ["OpenAI", "Anthropic"]
+//! max_price = 10.0                     # blended USD per million tokens
+//! required_capabilities = ["tool_call"]
+//! banned_models = ["gpt-4o-mini"]
+//! ```
+//!
+//! Every rule is optional. `policy check` explains why one model is or is
+//! not allowed, and `--policy` drops disallowed models from listings.
+
+use crate::config::Config;
+use crate::error::{AppError, Result};
+use crate::models::LlmModel;
+use crate::sources::models_dev::capabilities::{capable_models, validate_needs};
+use serde::Deserialize;
+use std::collections::{BTreeMap, HashSet};
+use std::path::{Path, PathBuf};
+
+/// Governance rules, as read from a policy file.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Policy {
+    /// Creators models may come from, by name or slug (case-insensitive).
+    /// Empty allows every creator.
+    #[serde(default)]
+    pub allowed_creators: Vec<String>,
+    /// Maximum blended price per million tokens. Models without a price
+    /// are not allowed when set.
+    pub max_price: Option<f64>,
+    /// Capabilities from the `models` table every model must have (see
+    /// `shortlist --needs`).
+    #[serde(default)]
+    pub required_capabilities: Vec<String>,
+    /// Banned models, by exact slug or name (case-insensitive).
+    #[serde(default)]
+    pub banned_models: Vec<String>,
+}
+
+impl Policy {
+    /// Default policy file, next to the config file.
+    pub fn default_path() -> Result<PathBuf> {
+        Ok(Config::config_path()?.with_file_name("policy.toml"))
+    }
+
+    /// Load and validate the policy file at `path`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AppError::Config` when the file is missing, has unknown keys,
+    /// or names an unknown capability or a negative price.
+    pub fn load(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Err(AppError::Config(format!(
+                "Policy file {} not found",
+                path.display()
+            )));
+        }
+        let content = std::fs::read_to_string(path)?;
+        let policy: Policy = toml::from_str(&content).map_err(|e| {
+            AppError::Config(format!("Invalid policy file {}: {}", path.display(), e))
+        })?;
+        policy.validated()
+    }
+
+    /// Normalize capabilities and reject impossible limits.
+    fn validated(self) -> Result<Self> {
+        if self.max_price.is_some_and(|max| max < 0.0) {
+            return Err(AppError::Config(
+                "Policy max_price cannot be negative".to_string(),
+            ));
+        }
+        Ok(Self {
+            required_capabilities: validate_needs(&self.required_capabilities)?,
+            ..self
+        })
+    }
+}
+
+/// A policy with its capability lookups done, ready to check models.
+#[derive(Debug, Clone)]
+pub struct PolicyCheck {
+    policy: Policy,
+    /// Model keys having each required capability.
+    capable: BTreeMap<String, HashSet<String>>,
+}
+
+impl PolicyCheck {
+    /// Look up the models having each required capability in the cached
+    /// `models` table. No lookup is done when none are required.
+    pub fn new(policy: Policy, cache_dir: &Path) -> Result<Self> {
+        let capable = policy
+            .required_capabilities
+            .iter()
+            .map(|need| {
+                let keys = capable_models(cache_dir, std::slice::from_ref(need))?;
+                Ok((need.clone(), keys))
+            })
+            .collect::<Result<_>>()?;
+        Ok(Self::with_capabilities(policy, capable))
+    }
+
+    /// Build a check from already known capable model keys (lowercased
+    /// model ids without provider prefix).
+    pub fn with_capabilities(policy: Policy, capable: BTreeMap<String, HashSet<String>>) -> Self {
+        Self { policy, capable }
+    }
+
+    /// The rules being checked.
+    pub fn policy(&self) -> &Policy {
+        &self.policy
+    }
+
+    /// Why `model` is not allowed; empty when it is.
+    pub fn violations(&self, model: &LlmModel) -> Vec<String> {
+        let policy = &self.policy;
+        let mut violations = Vec::new();
+
+        if policy.banned_models.iter().any(|banned| {
+            banned.eq_ignore_ascii_case(&model.slug) || banned.eq_ignore_ascii_case(&model.name)
+        }) {
+            violations.push(format!("{} is banned", model.slug));
+        }
+
+        let creator_allowed = policy.allowed_creators.is_empty()
+            || policy.allowed_creators.iter().any(|allowed| {
+                allowed.eq_ignore_ascii_case(&model.creator)
+                    || model
+                        .creator_slug
+                        .as_deref()
+                        .is_some_and(|slug| allowed.eq_ignore_ascii_case(slug))
+            });
+        if !creator_allowed {
+            violations.push(format!(
+                "creator {} is not allowed (allowed: {})",
+                model.creator,
+                policy.allowed_creators.join(", ")
+            ));
+        }
+
+        if let Some(max) = policy.max_price {
+            match model.price {
+                Some(price) if price > max => violations.push(format!(
+                    "blended price ${:.2}/M exceeds the ${:.2}/M limit",
+                    price, max
+                )),
+                Some(_) => {}
+                None => violations.push(format!(
+                    "has no published price to check against the ${:.2}/M limit",
+                    max
+                )),
+            }
+        }
+
+        let key = model.slug.to_lowercase();
+        for (need, keys) in &self.capable {
+            if !keys.contains(&key) {
+                violations.push(format!("lacks required capability {}", need));
+            }
+        }
+
+        violations
+    }
+
+    /// Whether `model` satisfies every rule.
+    pub fn allows(&self, model: &LlmModel) -> bool {
+        self.violations(model).is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn model(slug: &str, creator: &str, price: Option<f64>) -> LlmModel {
+        LlmModel {
+            name: slug.into(),
+            slug: slug.into(),
+            creator: creator.into(),
+            creator_slug: Some(creator.to_lowercase()),
+            price,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_policy_load() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("policy.toml");
+        assert!(Policy::load(&path).is_err());
+
+        std::fs::write(
+            &path,
+            "allowed_creators = [\"OpenAI\"]\nmax_price = 10.0\nrequired_capabilities = [\"Tool_Call\"]\n",
+        )
+        .unwrap();
+        let policy = Policy::load(&path).unwrap();
+        assert_eq!(policy.allowed_creators, vec!["OpenAI"]);
+        assert_eq!(policy.max_price, Some(10.0));
+        assert_eq!(policy.required_capabilities, vec!["tool_call"]);
+        assert!(policy.banned_models.is_empty());
+
+        for invalid in [
+            "max_price = -1.0",
+            "required_capabilities = [\"telepathy\"]",
+            "banned = [\"gpt-4o\"]",
+        ] {
+            std::fs::write(&path, invalid).unwrap();
+            assert!(Policy::load(&path).is_err(), "{}", invalid);
+        }
+    }
+
+    #[test]
+    fn test_policy_violations() {
+        let policy = Policy {
+            allowed_creators: vec!["openai".into(), "Anthropic".into()],
+            max_price: Some(10.0),
+            required_capabilities: vec!["tool_call".into()],
+            banned_models: vec!["GPT-4o-mini".into()],
+        };
+        let capable = BTreeMap::from([(
+            "tool_call".to_string(),
+            HashSet::from(["gpt-4o".to_string(), "gpt-4o-mini".to_string()]),
+        )]);
+        let check = PolicyCheck::with_capabilities(policy, capable);
+
+        assert!(check.allows(&model("gpt-4o", "OpenAI", Some(4.4))));
+        assert_eq!(
+            check.violations(&model("gpt-4o-mini", "OpenAI", Some(0.3))),
+            vec!["gpt-4o-mini is banned"]
+        );
+        assert_eq!(
+            check.violations(&model("o1-pro", "OpenAI", None)),
+            vec![
+                "has no published price to check against the $10.00/M limit",
+                "lacks required capability tool_call",
+            ]
+        );
+        let violations = check.violations(&model("grok-4", "xAI", Some(30.0)));
+        assert_eq!(violations.len(), 3);
+        assert!(violations[0].contains("creator xAI is not allowed"));
+        assert!(violations[1].contains("exceeds the $10.00/M limit"));
+
+        // An empty policy allows everything
+        let open = PolicyCheck::with_capabilities(Policy::default(), BTreeMap::new());
+        assert!(open.allows(&model("grok-4", "xAI", None)));
+    }
+}
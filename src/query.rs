@@ -1,18 +1,29 @@
 //! SQL query execution via DuckDB.
 //!
-//! Provides table name alias substitution and SQL execution against Parquet files.
+//! Registers the cached Parquet files a query reads as DuckDB views named
+//! after their tables, then runs the query unchanged. With the `sql` feature,
+//! queries are parsed with `sqlparser` to validate them and find the tables
+//! they read; without it, tables are matched as whole words.
 
+use crate::cli::invocation;
 use crate::error::{AppError, Result};
-use crate::output::OutputFormat;
-use crate::schema::{self, Column, ALL_TABLES};
+use crate::logging;
+use crate::output::porcelain::format_porcelain;
+use crate::output::{dates, export, glyphs, json, OutputFormat};
+use crate::schema::{self, Column, TableDef, ALL_TABLES, BENCHMARKS};
 use comfy_table::{presets::ASCII_BORDERS_ONLY_CONDENSED, Table};
 use duckdb::arrow::array::Array;
 use duckdb::arrow::record_batch::RecordBatch;
 use duckdb::Connection;
+use serde::ser::{SerializeMap, SerializeSeq};
+use serde::{Serialize, Serializer};
+#[cfg(feature = "sql")]
 use sqlparser::ast::{ObjectName, Visit, Visitor};
+#[cfg(feature = "sql")]
 use sqlparser::dialect::GenericDialect;
+#[cfg(feature = "sql")]
 use sqlparser::parser::Parser;
-use std::collections::HashMap;
+#[cfg(feature = "sql")]
 use std::ops::ControlFlow;
 use std::path::PathBuf;
 
@@ -23,6 +34,7 @@ pub struct TableSchema {
 }
 
 /// Result of a SQL query.
+#[derive(Debug)]
 pub struct QueryResult {
     /// Column names.
     pub columns: Vec<String>,
@@ -30,6 +42,54 @@ pub struct QueryResult {
     pub rows: Vec<Vec<String>>,
 }
 
+/// Result of a SQL query, kept as the Arrow batches DuckDB returned.
+///
+/// Cells are formatted only when written to an output sink: machine formats
+/// (JSON, CSV) keep full precision and JSON keeps value types, while the
+/// human formats round floats for display.
+pub struct QueryBatches {
+    /// Column names.
+    pub columns: Vec<String>,
+    batches: Vec<RecordBatch>,
+}
+
+impl QueryBatches {
+    /// Check if the result is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Get the number of rows.
+    pub fn len(&self) -> usize {
+        self.batches.iter().map(RecordBatch::num_rows).sum()
+    }
+
+    /// Format every cell for display, as used by the built-in commands.
+    pub fn into_result(self) -> QueryResult {
+        let rows = self.rows(arrow_value_to_string).collect();
+        QueryResult {
+            columns: self.columns,
+            rows,
+        }
+    }
+
+    /// Rows with each cell rendered by `cell`, produced one row at a time.
+    fn rows(
+        &self,
+        cell: fn(&dyn Array, usize) -> String,
+    ) -> impl Iterator<Item = Vec<String>> + '_ {
+        self.batches.iter().flat_map(move |batch| {
+            (0..batch.num_rows()).map(move |row| {
+                batch
+                    .columns()
+                    .iter()
+                    .map(|col| cell(col.as_ref(), row))
+                    .collect()
+            })
+        })
+    }
+}
+
 impl QueryResult {
     /// Check if the result is empty.
     pub fn is_empty(&self) -> bool {
@@ -45,212 +105,110 @@ impl QueryResult {
 /// SQL query executor.
 pub struct QueryExecutor {
     cache_dir: PathBuf,
+    /// Parquet file read as `benchmarks` instead of the latest data (`--as-of`).
+    benchmarks_snapshot: Option<PathBuf>,
 }
 
 impl QueryExecutor {
     /// Create a new query executor.
     pub fn new(cache_dir: PathBuf) -> Self {
-        Self { cache_dir }
-    }
-
-    /// Substitute table aliases with read_parquet() calls using AST parsing.
-    /// Falls back to string replacement if parsing fails.
-    pub fn substitute_aliases(&self, sql: &str) -> Result<String> {
-        // Try AST-based substitution first
-        match self.substitute_aliases_ast(sql) {
-            Ok(result) => Ok(result),
-            Err(_) => {
-                // Fall back to string-based substitution
-                self.substitute_aliases_string(sql)
-            }
+        Self {
+            cache_dir,
+            benchmarks_snapshot: None,
         }
     }
 
-    /// AST-based table substitution - identifies table positions via AST, then does targeted replacement.
-    fn substitute_aliases_ast(&self, sql: &str) -> Result<String> {
-        let dialect = GenericDialect {};
-        let ast = Parser::parse_sql(&dialect, sql)
-            .map_err(|e| AppError::Query(format!("SQL parse error: {}", e)))?;
-
-        // Collect table names that need substitution
-        let mut table_names: Vec<String> = Vec::new();
-        for statement in &ast {
-            let mut visitor = TableNameCollector { tables: Vec::new() };
-            let _ = statement.visit(&mut visitor);
-            table_names.extend(visitor.tables);
-        }
-
-        // Check for missing tables and build replacement map
-        let mut missing_tables = Vec::new();
-        let mut replacements: HashMap<String, String> = HashMap::new();
+    /// Read the `benchmarks` table from a stored snapshot file.
+    pub fn with_benchmarks_snapshot(mut self, path: PathBuf) -> Self {
+        self.benchmarks_snapshot = Some(path);
+        self
+    }
 
-        for table_name in &table_names {
-            let lower = table_name.to_lowercase();
-            if let Some(table_def) = schema::get_table_def(&lower) {
-                let parquet_path = self.cache_dir.join(table_def.parquet_file);
-                if !parquet_path.exists() {
-                    missing_tables.push(table_def);
-                } else {
-                    let path_str = parquet_path.to_string_lossy().replace('\\', "/");
-                    replacements.insert(lower.clone(), format!("read_parquet('{}')", path_str));
-                }
+    /// The `read_parquet()` source for a table, or `None` if it is not cached.
+    fn table_source(&self, table_def: &TableDef) -> Option<String> {
+        match &self.benchmarks_snapshot {
+            Some(path) if table_def.name == BENCHMARKS.name => {
+                return Some(format!(
+                    "read_parquet('{}')",
+                    path.to_string_lossy().replace('\\', "/")
+                ));
             }
+            _ => {}
         }
-
-        if !missing_tables.is_empty() {
-            let table = missing_tables[0];
-            return Err(AppError::Query(format!(
-                "Table '{}' not found. Run '{}' first to fetch and cache the data.",
-                table.name, table.command
-            )));
-        }
-
-        // Now do targeted replacement using the AST to find exact positions
-        // We regenerate SQL from AST with substitutions
-        let mut result = sql.to_string();
-
-        // Sort by length descending to avoid replacing substrings
-        let mut sorted_replacements: Vec<_> = replacements.iter().collect();
-        sorted_replacements.sort_by(|a, b| b.0.len().cmp(&a.0.len()));
-
-        for (table_name, replacement) in sorted_replacements {
-            // Use case-insensitive word boundary replacement
-            result = replace_table_name_safe(&result, table_name, replacement);
-        }
-
-        Ok(result)
+        table_def
+            .is_cached(&self.cache_dir)
+            .then(|| table_def.read_parquet_sql(&self.cache_dir))
     }
 
-    /// String-based table substitution (fallback).
-    fn substitute_aliases_string(&self, sql: &str) -> Result<String> {
-        let mut result = sql.to_string();
-        let mut missing_tables = Vec::new();
-
-        for table_def in ALL_TABLES {
-            let alias = table_def.name;
-
-            // Match table name as a whole word (case insensitive)
-            let patterns = [
-                format!(" FROM {} ", alias),
-                format!(" FROM {}\n", alias),
-                format!(" FROM {}\r", alias),
-                format!(" FROM {}", alias),
-                format!(" JOIN {} ", alias),
-                format!(" JOIN {}\n", alias),
-                format!(" JOIN {}\r", alias),
-                format!(" JOIN {}", alias),
-                format!(",{} ", alias),
-                format!(",{},", alias),
-            ];
-
-            let alias_upper = alias.to_uppercase();
-            let patterns_upper = [
-                format!(" FROM {} ", alias_upper),
-                format!(" FROM {}\n", alias_upper),
-                format!(" FROM {}\r", alias_upper),
-                format!(" FROM {}", alias_upper),
-                format!(" JOIN {} ", alias_upper),
-                format!(" JOIN {}\n", alias_upper),
-                format!(" JOIN {}\r", alias_upper),
-                format!(" JOIN {}", alias_upper),
-                format!(",{} ", alias_upper),
-                format!(",{},", alias_upper),
-            ];
-
-            let parquet_path = self.cache_dir.join(table_def.parquet_file);
-            let replacement = format!(
-                "read_parquet('{}')",
-                parquet_path.to_string_lossy().replace('\\', "/")
-            );
-
-            // Check if this table is referenced
-            let is_referenced = patterns.iter().any(|p| result.contains(p))
-                || patterns_upper.iter().any(|p| result.contains(p))
-                || result.to_lowercase().ends_with(&format!(" from {}", alias))
-                || result.to_lowercase().ends_with(&format!(" join {}", alias));
-
-            if is_referenced && !parquet_path.exists() {
-                missing_tables.push(table_def);
-            }
-
-            // Perform substitutions (case-insensitive, whole word)
-            for (pattern, pattern_upper) in patterns.iter().zip(patterns_upper.iter()) {
-                if result.contains(pattern) {
-                    let new_pattern = pattern.replace(alias, &replacement);
-                    result = result.replace(pattern, &new_pattern);
-                }
-                if result.contains(pattern_upper) {
-                    let new_pattern = pattern_upper.replace(&alias_upper, &replacement);
-                    result = result.replace(pattern_upper, &new_pattern);
-                }
-            }
-
-            // Handle end-of-string case
-            let end_pattern = format!(" from {}", alias);
-            let end_pattern_upper = format!(" FROM {}", alias_upper);
-            if result.to_lowercase().ends_with(&end_pattern) {
-                let len = result.len();
-                let prefix = &result[..len - end_pattern.len()];
-                result = format!("{} FROM {}", prefix, replacement);
-            } else if result.ends_with(&end_pattern_upper) {
-                let len = result.len();
-                let prefix = &result[..len - end_pattern_upper.len()];
-                result = format!("{} FROM {}", prefix, replacement);
-            }
-        }
+    /// `CREATE VIEW` statements registering each known table `sql` reads
+    /// under its own name.
+    ///
+    /// DuckDB then resolves the names itself, in subqueries, CTEs and quoted
+    /// identifiers alike, and the query text is never rewritten. Tables
+    /// that are not cached get no view; reading one fails with a hint to
+    /// fetch it.
+    pub fn view_statements(&self, sql: &str) -> Vec<String> {
+        referenced_tables(sql)
+            .into_iter()
+            .filter_map(|table_def| {
+                self.table_source(table_def).map(|source| {
+                    format!(
+                        "CREATE VIEW \"{}\" AS SELECT * FROM {}",
+                        table_def.name, source
+                    )
+                })
+            })
+            .collect()
+    }
 
-        if !missing_tables.is_empty() {
-            let table = missing_tables[0];
-            return Err(AppError::Query(format!(
-                "Table '{}' not found. Run '{}' first to fetch and cache the data.",
-                table.name, table.command
-            )));
-        }
+    /// Execute a SQL query and return the results as display strings.
+    pub fn execute(&self, sql: &str) -> Result<QueryResult> {
+        Ok(self.execute_batches(sql)?.into_result())
+    }
 
-        Ok(result)
+    /// Number of rows a SQL query returns.
+    pub fn count(&self, sql: &str) -> Result<usize> {
+        let result = self.execute(&format!("SELECT count(*) AS total FROM (\n{}\n) t", sql))?;
+        Ok(result
+            .rows
+            .first()
+            .and_then(|row| row[0].parse().ok())
+            .unwrap_or_default())
     }
 
-    /// Execute a SQL query and return the results.
-    pub fn execute(&self, sql: &str) -> Result<QueryResult> {
+    /// Execute a SQL query and return the results as Arrow batches.
+    pub fn execute_batches(&self, sql: &str) -> Result<QueryBatches> {
         // Validate SQL syntax first using sqlparser to catch syntax errors
         // before they reach DuckDB (which can throw uncatchable C++ exceptions)
-        let dialect = GenericDialect {};
-        Parser::parse_sql(&dialect, sql)
-            .map_err(|e| AppError::Query(format!("SQL syntax error: {}", e)))?;
+        #[cfg(feature = "sql")]
+        {
+            let dialect = GenericDialect {};
+            Parser::parse_sql(&dialect, sql)
+                .map_err(|e| AppError::Query(format!("SQL syntax error: {}", e)))?;
+        }
 
-        // Substitute table aliases
-        let transformed_sql = self.substitute_aliases(sql)?;
+        // Register the tables the query reads as views
+        let views = self.view_statements(sql);
+        for view in &views {
+            logging::debug("sql", format_args!("{}", view));
+        }
+        logging::debug("sql", format_args!("{}", sql));
 
         // Execute the query
-        let batches = execute_duckdb_query(&transformed_sql)?;
-
-        if batches.is_empty() {
-            return Ok(QueryResult {
-                columns: vec![],
-                rows: vec![],
-            });
-        }
+        let batches = execute_duckdb_query(&views, sql)?;
 
         // Get column names from the first batch's schema
-        let schema = batches[0].schema();
-        let columns: Vec<String> = schema.fields().iter().map(|f| f.name().clone()).collect();
-
-        // Convert Arrow arrays to strings
-        let mut rows = Vec::new();
-        for batch in &batches {
-            let num_rows = batch.num_rows();
-            for row_idx in 0..num_rows {
-                let mut row_values = Vec::new();
-                for col_idx in 0..batch.num_columns() {
-                    let col = batch.column(col_idx);
-                    let string_value = arrow_value_to_string(col.as_ref(), row_idx);
-                    row_values.push(string_value);
-                }
-                rows.push(row_values);
-            }
-        }
-
-        Ok(QueryResult { columns, rows })
+        let columns: Vec<String> = batches
+            .first()
+            .map(|batch| {
+                let schema = batch.schema();
+                schema.fields().iter().map(|f| f.name().clone()).collect()
+            })
+            .unwrap_or_default();
+
+        let result = QueryBatches { columns, batches };
+        logging::debug("sql", format_args!("{} rows", result.len()));
+        Ok(result)
     }
 
     /// List available tables with their schemas.
@@ -258,8 +216,7 @@ impl QueryExecutor {
         let mut tables = Vec::new();
 
         for table_def in ALL_TABLES {
-            let parquet_path = self.cache_dir.join(table_def.parquet_file);
-            let exists = parquet_path.exists();
+            let exists = table_def.is_cached(&self.cache_dir);
 
             tables.push(TableInfo {
                 name: table_def.name.to_string(),
@@ -272,6 +229,7 @@ impl QueryExecutor {
                             name: col.name.to_string(),
                             data_type: col.sql_type.to_string(),
                             nullable: col.nullable,
+                            description: col.description.to_string(),
                         })
                         .collect(),
                 ),
@@ -282,21 +240,49 @@ impl QueryExecutor {
     }
 }
 
-/// Execute a DuckDB query and return the results as Arrow RecordBatches.
-fn execute_duckdb_query(sql: &str) -> Result<Vec<RecordBatch>> {
+/// Extract the table name from a DuckDB "Table with name X does not exist" error.
+fn missing_table_name(msg: &str) -> Option<&str> {
+    let rest = msg.split("Table with name ").nth(1)?;
+    rest.split(" does not exist").next().map(str::trim)
+}
+
+/// Execute a DuckDB query after creating `views`, and return the results
+/// as Arrow RecordBatches.
+///
+/// A known table without a view is not cached, and the error says how to
+/// fetch it.
+fn execute_duckdb_query(views: &[String], sql: &str) -> Result<Vec<RecordBatch>> {
     // Open in-memory connection
     let conn = Connection::open_in_memory()
         .map_err(|e| AppError::Query(format!("DuckDB error: {}", e)))?;
+    for view in views {
+        conn.execute_batch(view)
+            .map_err(|e| AppError::Query(format!("Cannot read cached table: {}", e)))?;
+    }
 
     // Execute the query and get results as Arrow RecordBatches
     let mut stmt = conn.prepare(sql).map_err(|e| {
         // Provide helpful error messages for common mistakes
         let msg = e.to_string();
-        if msg.contains("syntax error") {
+        let uncached = missing_table_name(&msg)
+            .and_then(|name| schema::get_table_def(&name.to_lowercase()));
+        if let Some(table) = uncached {
+            AppError::Query(format!(
+                "Table '{}' not found. Run '{}' first to fetch and cache the data.",
+                table.name,
+                table.command_line()
+            ))
+        } else if msg.contains("syntax error") {
             AppError::Query(format!("SQL syntax error: {}", msg))
         } else if msg.contains("does not exist") || msg.contains("not found") {
+            let suggestion = missing_table_name(&msg)
+                .and_then(schema::suggest_table)
+                .map(|table| format!("\nDid you mean '{}'?", table.name))
+                .unwrap_or_default();
             AppError::Query(format!(
-                "Table or column not found. Use 'aa query --tables' to see available tables and columns.\nError: {}",
+                "Table or column not found. Use '{}' to see available tables and columns.{}\nError: {}",
+                invocation("tables"),
+                suggestion,
                 msg
             ))
         } else {
@@ -313,6 +299,33 @@ fn execute_duckdb_query(sql: &str) -> Result<Vec<RecordBatch>> {
     Ok(batches)
 }
 
+/// Convert an Arrow array value for a table cell: as for display, with
+/// booleans shown as glyphs and dates in the `--date-format`.
+fn arrow_value_to_cell(array: &dyn Array, row: usize) -> String {
+    use duckdb::arrow::array::{BooleanArray, Date32Array};
+
+    if array.is_null(row) {
+        return String::new();
+    }
+    if let Some(arr) = array.as_any().downcast_ref::<BooleanArray>() {
+        return glyphs::boolean(arr.value(row)).to_string();
+    }
+    match array.as_any().downcast_ref::<Date32Array>() {
+        Some(arr) => match arr.value_as_date(row) {
+            Some(date) => dates::display(date),
+            None => arrow_value_to_string(array, row),
+        },
+        None => arrow_value_to_string(array, row),
+    }
+}
+
+/// Render a cell already formatted for display as a table cell, as
+/// [`arrow_value_to_cell`] does: `true`/`false` become glyphs and ISO dates
+/// use the `--date-format`.
+fn table_cell(cell: &str) -> String {
+    dates::display_text(glyphs::boolean_text(cell)).into_owned()
+}
+
 /// Convert an Arrow array value at a given row index to a string.
 /// Uses Arrow's built-in display formatting to handle all types safely.
 fn arrow_value_to_string(array: &dyn Array, row: usize) -> String {
@@ -388,13 +401,17 @@ fn arrow_value_to_string(array: &dyn Array, row: usize) -> String {
         DataType::Date32 => {
             let arr = array.as_any().downcast_ref::<Date32Array>().unwrap();
             // Date32 is days since epoch
-            let days = arr.value(row);
-            format!("{}", days)
+            match arr.value_as_date(row) {
+                Some(date) => date.format("%Y-%m-%d").to_string(),
+                None => arr.value(row).to_string(),
+            }
         }
         DataType::Date64 => {
             let arr = array.as_any().downcast_ref::<Date64Array>().unwrap();
-            let ms = arr.value(row);
-            format!("{}", ms)
+            match arr.value_as_date(row) {
+                Some(date) => date.format("%Y-%m-%d").to_string(),
+                None => arr.value(row).to_string(),
+            }
         }
         DataType::Timestamp(unit, _) => match unit {
             TimeUnit::Second => {
@@ -457,72 +474,165 @@ fn arrow_value_to_string(array: &dyn Array, row: usize) -> String {
     }
 }
 
-/// Replace table name in SQL, being careful not to replace inside string literals.
-fn replace_table_name_safe(sql: &str, table_name: &str, replacement: &str) -> String {
-    let mut result = String::new();
-    let chars = sql.chars();
-    let mut in_string = false;
-    let mut string_char = '"';
-    let mut current_word = String::new();
+/// Convert an Arrow value to a string without display rounding (CSV output).
+fn arrow_value_to_raw(array: &dyn Array, row: usize) -> String {
+    use duckdb::arrow::array::{Float32Array, Float64Array};
+    use duckdb::arrow::datatypes::DataType;
 
-    for ch in chars {
-        // Track string literals
-        if !in_string && (ch == '\'' || ch == '"') {
-            in_string = true;
-            string_char = ch;
-            // Flush any accumulated word
-            if !current_word.is_empty() {
-                if current_word.to_lowercase() == table_name {
-                    result.push_str(replacement);
-                } else {
-                    result.push_str(&current_word);
-                }
-                current_word.clear();
-            }
-            result.push(ch);
-        } else if in_string && ch == string_char {
-            in_string = false;
-            result.push(ch);
-        } else if in_string {
-            result.push(ch);
-        } else if ch.is_alphanumeric() || ch == '_' {
-            current_word.push(ch);
-        } else {
-            // End of word, check if it's a table name
-            if !current_word.is_empty() {
-                if current_word.to_lowercase() == table_name {
-                    result.push_str(replacement);
-                } else {
-                    result.push_str(&current_word);
-                }
-                current_word.clear();
+    if array.is_null(row) {
+        return String::new();
+    }
+    match array.data_type() {
+        DataType::Float32 => {
+            let arr = array.as_any().downcast_ref::<Float32Array>().unwrap();
+            arr.value(row).to_string()
+        }
+        DataType::Float64 => {
+            let arr = array.as_any().downcast_ref::<Float64Array>().unwrap();
+            arr.value(row).to_string()
+        }
+        _ => arrow_value_to_string(array, row),
+    }
+}
+
+/// Convert an Arrow value to a typed JSON value (numbers stay numbers).
+fn arrow_value_to_json(array: &dyn Array, row: usize) -> serde_json::Value {
+    use duckdb::arrow::array::*;
+    use duckdb::arrow::datatypes::DataType;
+    use serde_json::Value;
+
+    macro_rules! number {
+        ($array_type:ty) => {
+            Value::from(
+                array
+                    .as_any()
+                    .downcast_ref::<$array_type>()
+                    .unwrap()
+                    .value(row),
+            )
+        };
+    }
+
+    if array.is_null(row) {
+        return Value::Null;
+    }
+    match array.data_type() {
+        DataType::Null => Value::Null,
+        DataType::Boolean => {
+            let arr = array.as_any().downcast_ref::<BooleanArray>().unwrap();
+            Value::Bool(arr.value(row))
+        }
+        DataType::Int8 => number!(Int8Array),
+        DataType::Int16 => number!(Int16Array),
+        DataType::Int32 => number!(Int32Array),
+        DataType::Int64 => number!(Int64Array),
+        DataType::UInt8 => number!(UInt8Array),
+        DataType::UInt16 => number!(UInt16Array),
+        DataType::UInt32 => number!(UInt32Array),
+        DataType::UInt64 => number!(UInt64Array),
+        // Non-finite floats become null
+        DataType::Float32 => number!(Float32Array),
+        DataType::Float64 => number!(Float64Array),
+        DataType::Decimal128(_, _) => {
+            let text = arrow_value_to_string(array, row);
+            text.parse::<f64>()
+                .map(Value::from)
+                .unwrap_or(Value::String(text))
+        }
+        _ => Value::String(arrow_value_to_string(array, row)),
+    }
+}
+
+/// JSON array of row objects, serialized straight from the batches with keys
+/// in column order.
+struct JsonRows<'a>(&'a QueryBatches);
+
+/// One row of [`JsonRows`].
+struct JsonRow<'a> {
+    columns: &'a [String],
+    batch: &'a RecordBatch,
+    row: usize,
+}
+
+impl Serialize for JsonRows<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        let mut seq = serializer.serialize_seq(Some(self.0.len()))?;
+        for batch in &self.0.batches {
+            for row in 0..batch.num_rows() {
+                seq.serialize_element(&JsonRow {
+                    columns: &self.0.columns,
+                    batch,
+                    row,
+                })?;
             }
-            result.push(ch);
         }
+        seq.end()
     }
+}
 
-    // Handle any remaining word
-    if !current_word.is_empty() {
-        if current_word.to_lowercase() == table_name {
-            result.push_str(replacement);
-        } else {
-            result.push_str(&current_word);
+impl Serialize for JsonRow<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(self.columns.len()))?;
+        for (name, column) in self.columns.iter().zip(self.batch.columns()) {
+            map.serialize_entry(name, &arrow_value_to_json(column.as_ref(), self.row))?;
         }
+        map.end()
+    }
+}
+
+/// Known tables referenced by a SQL query.
+///
+/// Returns an empty list if the query cannot be parsed.
+#[cfg(feature = "sql")]
+pub fn referenced_tables(sql: &str) -> Vec<&'static TableDef> {
+    let dialect = GenericDialect {};
+    let Ok(ast) = Parser::parse_sql(&dialect, sql) else {
+        return Vec::new();
+    };
+
+    let mut visitor = TableNameCollector { tables: Vec::new() };
+    for statement in &ast {
+        let _ = statement.visit(&mut visitor);
     }
 
-    result
+    let mut tables: Vec<&'static TableDef> = Vec::new();
+    for name in &visitor.tables {
+        if let Some(table_def) = schema::get_table_def(&name.to_lowercase()) {
+            if !tables.iter().any(|t| t.name == table_def.name) {
+                tables.push(table_def);
+            }
+        }
+    }
+    tables
+}
+
+/// Known tables referenced by a SQL query, matched as whole words.
+#[cfg(not(feature = "sql"))]
+pub fn referenced_tables(sql: &str) -> Vec<&'static TableDef> {
+    let sql = sql.to_lowercase();
+    let words: Vec<&str> = sql
+        .split(|c: char| !(c.is_alphanumeric() || c == '_'))
+        .collect();
+    ALL_TABLES
+        .iter()
+        .copied()
+        .filter(|table| words.contains(&table.name))
+        .collect()
 }
 
 /// Visitor to collect table names from SQL AST.
+#[cfg(feature = "sql")]
 struct TableNameCollector {
     tables: Vec<String>,
 }
 
+#[cfg(feature = "sql")]
 impl Visitor for TableNameCollector {
     type Break = ();
 
     fn pre_visit_relation(&mut self, relation: &ObjectName) -> ControlFlow<Self::Break> {
-        if let Some(ident) = relation.0.first() {
+        // The table name is last in a qualified name such as `main.models`
+        if let Some(ident) = relation.0.last() {
             self.tables.push(ident.value.clone());
         }
         ControlFlow::Continue(())
@@ -541,92 +651,160 @@ pub struct ColumnInfo {
     pub name: String,
     pub data_type: String,
     pub nullable: bool,
+    pub description: String,
 }
 
 /// Format query results according to the output format.
 pub fn format_query_result(result: &QueryResult, format: OutputFormat) -> String {
+    if format == OutputFormat::Porcelain {
+        return format_porcelain(&result.columns, &result.rows);
+    }
     if result.is_empty() {
         return "No results.".to_string();
     }
 
+    let columns = &result.columns;
+    let rows = result.rows.iter();
+    let cells = || {
+        result
+            .rows
+            .iter()
+            .map(|row| row.iter().map(|cell| table_cell(cell)).collect::<Vec<_>>())
+    };
     match format {
         OutputFormat::Json => format_json(result),
-        OutputFormat::Csv => format_csv(result),
-        OutputFormat::Table => format_ascii_table(result),
-        OutputFormat::Plain => format_plain(result),
-        OutputFormat::Markdown => format_markdown(result),
+        OutputFormat::Csv => format_csv(columns, rows),
+        OutputFormat::Table => format_ascii_table(columns, cells()),
+        OutputFormat::Plain => format_plain(rows),
+        OutputFormat::Markdown => format_markdown(columns, cells()),
+        OutputFormat::Porcelain => unreachable!(), // Handled above
+    }
+}
+
+/// Format Arrow query results, rendering each cell for the output sink.
+pub fn format_query_batches(result: &QueryBatches, format: OutputFormat) -> String {
+    if format == OutputFormat::Porcelain {
+        return format_porcelain(&result.columns, result.rows(arrow_value_to_raw));
+    }
+    if result.is_empty() {
+        return "No results.".to_string();
+    }
+
+    let columns = &result.columns;
+    let display = || result.rows(arrow_value_to_string);
+    let cells = || result.rows(arrow_value_to_cell);
+    match format {
+        OutputFormat::Json => json::format_json(&JsonRows(result)),
+        OutputFormat::Csv => format_csv(columns, result.rows(arrow_value_to_raw)),
+        OutputFormat::Table => format_ascii_table(columns, cells()),
+        OutputFormat::Plain => format_plain(display()),
+        OutputFormat::Markdown => format_markdown(columns, cells()),
+        OutputFormat::Porcelain => unreachable!(), // Handled above
     }
 }
 
 fn format_json(result: &QueryResult) -> String {
-    let mut rows_json = Vec::new();
-    for row in &result.rows {
-        let mut obj = HashMap::new();
-        for (i, col) in result.columns.iter().enumerate() {
-            obj.insert(col.clone(), row.get(i).cloned().unwrap_or_default());
+    json::format_json(&ResultRows(result))
+}
+
+/// Serializes a [`QueryResult`] as an array of objects, keys in column order.
+struct ResultRows<'a>(&'a QueryResult);
+
+/// One row of [`ResultRows`].
+struct ResultRow<'a> {
+    columns: &'a [String],
+    row: &'a [String],
+}
+
+impl Serialize for ResultRows<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        let mut seq = serializer.serialize_seq(Some(self.0.rows.len()))?;
+        for row in &self.0.rows {
+            seq.serialize_element(&ResultRow {
+                columns: &self.0.columns,
+                row,
+            })?;
+        }
+        seq.end()
+    }
+}
+
+impl Serialize for ResultRow<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(self.columns.len()))?;
+        for (i, name) in self.columns.iter().enumerate() {
+            map.serialize_entry(
+                name,
+                self.row.get(i).map(String::as_str).unwrap_or_default(),
+            )?;
         }
-        rows_json.push(obj);
+        map.end()
     }
-    serde_json::to_string_pretty(&rows_json).unwrap_or_else(|_| "Error formatting JSON".to_string())
 }
 
-fn format_csv(result: &QueryResult) -> String {
+fn format_csv<R: AsRef<[String]>>(columns: &[String], rows: impl Iterator<Item = R>) -> String {
     let mut wtr = csv::Writer::from_writer(vec![]);
-    let _ = wtr.write_record(&result.columns);
-    for row in &result.rows {
-        let _ = wtr.write_record(row);
+    let _ = wtr.write_record(columns);
+    for row in rows {
+        let _ = wtr.write_record(row.as_ref());
     }
     wtr.into_inner()
-        .map(|v| String::from_utf8_lossy(&v).to_string())
+        .map(|v| export::csv_header() + &String::from_utf8_lossy(&v))
         .unwrap_or_else(|_| "Error formatting CSV".to_string())
 }
 
-fn format_ascii_table(result: &QueryResult) -> String {
+fn format_ascii_table<R: AsRef<[String]>>(
+    columns: &[String],
+    rows: impl Iterator<Item = R>,
+) -> String {
     let mut table = Table::new();
     table.load_preset(ASCII_BORDERS_ONLY_CONDENSED);
-    table.set_header(&result.columns);
+    table.set_header(columns);
 
-    for row in &result.rows {
-        table.add_row(row);
+    for row in rows {
+        table.add_row(row.as_ref());
     }
 
     table.to_string()
 }
 
-fn format_plain(result: &QueryResult) -> String {
+fn format_plain<R: AsRef<[String]>>(rows: impl Iterator<Item = R>) -> String {
     let mut output = String::new();
-    for row in &result.rows {
-        output.push_str(&row.join("\t"));
+    for row in rows {
+        output.push_str(&row.as_ref().join("\t"));
         output.push('\n');
     }
     output
 }
 
-fn format_markdown(result: &QueryResult) -> String {
+fn format_markdown<R: AsRef<[String]>>(
+    columns: &[String],
+    rows: impl Iterator<Item = R>,
+) -> String {
     use std::fmt::Write;
 
     let mut output = String::new();
 
     // Header row
-    write!(output, "| {} |", result.columns.join(" | ")).unwrap();
+    write!(output, "| {} |", columns.join(" | ")).unwrap();
     writeln!(output).unwrap();
 
     // Separator row
-    let separators: Vec<&str> = result.columns.iter().map(|_| "---").collect();
+    let separators: Vec<&str> = columns.iter().map(|_| "---").collect();
     write!(output, "| {} |", separators.join(" | ")).unwrap();
     writeln!(output).unwrap();
 
     // Data rows
-    for row in &result.rows {
-        write!(output, "| {} |", row.join(" | ")).unwrap();
+    for row in rows {
+        write!(output, "| {} |", row.as_ref().join(" | ")).unwrap();
         writeln!(output).unwrap();
     }
 
     output
 }
 
-/// Format table list for display.
-pub fn format_tables_list(tables: &[TableInfo]) -> String {
+/// Format table list for display, with column descriptions when `verbose`.
+pub fn format_tables_list(tables: &[TableInfo], verbose: bool) -> String {
     use std::fmt::Write;
 
     let mut output = String::new();
@@ -651,6 +829,9 @@ pub fn format_tables_list(tables: &[TableInfo]) -> String {
                     col.name, col.data_type, nullable
                 )
                 .unwrap();
+                if verbose {
+                    writeln!(output, "          {}", col.description).unwrap();
+                }
             }
         }
         writeln!(output).unwrap();
@@ -658,7 +839,13 @@ pub fn format_tables_list(tables: &[TableInfo]) -> String {
 
     writeln!(output, "To cache a table, run the corresponding command:").unwrap();
     for table_def in ALL_TABLES {
-        writeln!(output, "  {} -> {}", table_def.command, table_def.name).unwrap();
+        writeln!(
+            output,
+            "  {} -> {}",
+            table_def.command_line(),
+            table_def.name
+        )
+        .unwrap();
     }
 
     output
@@ -669,64 +856,137 @@ mod tests {
     use super::*;
     use tempfile::TempDir;
 
+    /// Write a small benchmarks table to `dir`.
+    fn write_benchmarks(dir: &std::path::Path) {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute(
+            &format!(
+                "COPY (SELECT * FROM (VALUES ('gpt-4o', 'GPT-4o', 50.0::DOUBLE), ('llama-4', 'Llama 4', 30.0))
+                    t(slug, name, intelligence)) TO '{}' (FORMAT PARQUET)",
+                dir.join("benchmarks.parquet").to_string_lossy()
+            ),
+            [],
+        )
+        .unwrap();
+    }
+
     #[test]
-    fn test_substitute_aliases_basic() {
+    fn test_view_statements() {
         let temp_dir = TempDir::new().unwrap();
         let executor = QueryExecutor::new(temp_dir.path().to_path_buf());
-
-        // Create a dummy parquet file
         std::fs::write(temp_dir.path().join("benchmarks.parquet"), b"dummy").unwrap();
 
-        let sql = "SELECT * FROM benchmarks WHERE intelligence > 40";
-        let result = executor.substitute_aliases(sql).unwrap();
+        let views = executor.view_statements("SELECT * FROM benchmarks WHERE intelligence > 40");
+        assert_eq!(views.len(), 1);
+        assert!(views[0].starts_with("CREATE VIEW \"benchmarks\" AS SELECT * FROM read_parquet("));
+        assert!(views[0].contains("benchmarks.parquet"));
 
-        assert!(result.contains("read_parquet("));
-        assert!(result.contains("benchmarks.parquet"));
+        // Uncached tables get no view
+        assert!(executor.view_statements("SELECT * FROM models").is_empty());
     }
 
     #[test]
-    fn test_substitute_aliases_missing_table() {
+    fn test_missing_table_error() {
         let temp_dir = TempDir::new().unwrap();
         let executor = QueryExecutor::new(temp_dir.path().to_path_buf());
 
-        let sql = "SELECT * FROM benchmarks";
-        let result = executor.substitute_aliases(sql);
-
-        assert!(result.is_err());
-        let err = result.unwrap_err().to_string();
-        assert!(err.contains("not found"));
+        let err = executor
+            .execute("SELECT * FROM benchmarks")
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("Table 'benchmarks' not found"), "{}", err);
         assert!(err.contains("which-llm refresh"));
     }
 
     #[test]
-    fn test_substitute_aliases_string_literal_not_replaced() {
+    fn test_tables_resolved_by_duckdb() {
         let temp_dir = TempDir::new().unwrap();
+        write_benchmarks(temp_dir.path());
         let executor = QueryExecutor::new(temp_dir.path().to_path_buf());
+        let first = |sql: &str| executor.execute(sql).unwrap().rows[0][0].clone();
+
+        // Subqueries, CTEs and quoted identifiers all read the view
+        assert_eq!(
+            first("SELECT name FROM benchmarks WHERE intelligence = (SELECT max(intelligence) FROM benchmarks)"),
+            "GPT-4o"
+        );
+        assert_eq!(
+            first("WITH top AS (SELECT * FROM \"Benchmarks\" ORDER BY intelligence LIMIT 1) SELECT name FROM top"),
+            "Llama 4"
+        );
+        // Text that looks like a table reference is left alone
+        assert_eq!(
+            first("SELECT 'select * from benchmarks' AS q FROM benchmarks LIMIT 1"),
+            "select * from benchmarks"
+        );
+    }
 
-        // Create a dummy parquet file
-        std::fs::write(temp_dir.path().join("benchmarks.parquet"), b"dummy").unwrap();
+    #[test]
+    fn test_unknown_table_suggestion() {
+        let err = execute_duckdb_query(&[], "SELECT * FROM benchmark")
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("Did you mean 'benchmarks'?"), "{}", err);
+    }
 
-        // String literal should NOT be replaced
-        let sql = "SELECT 'benchmarks' AS table_name FROM benchmarks";
-        let result = executor.substitute_aliases(sql).unwrap();
+    #[test]
+    fn test_missing_table_name() {
+        let msg = "Catalog Error: Table with name modles does not exist!";
+        assert_eq!(missing_table_name(msg), Some("modles"));
+        assert_eq!(missing_table_name("Binder Error: column x"), None);
+    }
 
-        // The string literal 'benchmarks' should be preserved
-        assert!(result.contains("'benchmarks'"));
-        // But the table reference should be replaced
-        assert!(result.contains("read_parquet("));
+    #[test]
+    fn test_view_statements_partitioned_table() {
+        let temp_dir = TempDir::new().unwrap();
+        let executor = QueryExecutor::new(temp_dir.path().to_path_buf());
+
+        let partition_dir = temp_dir.path().join("llms_history").join("date=2025-01-01");
+        std::fs::create_dir_all(&partition_dir).unwrap();
+        std::fs::write(partition_dir.join("benchmarks.parquet"), b"dummy").unwrap();
+
+        let sql = "SELECT * FROM llms_history WHERE snapshot_date = '2025-01-01'";
+        let result = executor.view_statements(sql).join("\n");
+
+        assert!(result.contains("llms_history/*/*.parquet"));
+        assert!(result.contains("union_by_name = true"));
     }
 
     #[test]
-    fn test_replace_table_name_safe() {
-        // Test that string literals are preserved
-        let sql = "SELECT 'benchmarks' FROM benchmarks";
-        let result = replace_table_name_safe(sql, "benchmarks", "REPLACED");
-        assert_eq!(result, "SELECT 'benchmarks' FROM REPLACED");
+    fn test_view_statements_benchmarks_snapshot() {
+        let temp_dir = TempDir::new().unwrap();
+        let snapshot = temp_dir
+            .path()
+            .join("llms_history")
+            .join("date=2025-01-01")
+            .join("benchmarks.parquet");
+        let executor =
+            QueryExecutor::new(temp_dir.path().to_path_buf()).with_benchmarks_snapshot(snapshot);
+
+        // The latest benchmarks file is not needed when pinned to a snapshot
+        let result = executor
+            .view_statements("SELECT name FROM benchmarks")
+            .join("\n");
+        assert!(result.contains("date=2025-01-01/benchmarks.parquet"));
+
+        // Other tables still read the latest cache
+        assert!(executor.view_statements("SELECT * FROM models").is_empty());
+    }
 
-        // Test case insensitivity
-        let sql = "SELECT * FROM BENCHMARKS";
-        let result = replace_table_name_safe(sql, "benchmarks", "REPLACED");
-        assert_eq!(result, "SELECT * FROM REPLACED");
+    #[test]
+    fn test_referenced_tables() {
+        let tables = referenced_tables(
+            "SELECT * FROM benchmarks b JOIN models m ON b.slug = m.model_id JOIN benchmarks x ON true",
+        );
+        let names: Vec<&str> = tables.iter().map(|t| t.name).collect();
+        assert_eq!(names, vec!["benchmarks", "models"]);
+
+        assert_eq!(
+            referenced_tables("SELECT * FROM main.models")[0].name,
+            "models"
+        );
+        assert!(referenced_tables("SELECT * FROM unknown_table").is_empty());
+        assert!(referenced_tables("NOT SQL").is_empty());
     }
 
     #[test]
@@ -739,7 +999,7 @@ mod tests {
         let executor = QueryExecutor::new(temp_dir.path().to_path_buf());
         let tables = executor.list_tables();
 
-        assert_eq!(tables.len(), 7);
+        assert_eq!(tables.len(), 11);
 
         let benchmarks = tables.iter().find(|t| t.name == "benchmarks").unwrap();
         assert!(benchmarks.exists);
@@ -788,4 +1048,58 @@ mod tests {
         assert!(output.contains("Model A"));
         assert!(output.contains("100"));
     }
+
+    #[test]
+    fn test_format_query_batches_keeps_types() {
+        let temp_dir = TempDir::new().unwrap();
+        let executor = QueryExecutor::new(temp_dir.path().to_path_buf());
+        let result = executor
+            .execute_batches("SELECT 'a' AS name, 3 AS n, 1.23456::DOUBLE AS score, NULL AS z")
+            .unwrap();
+        assert_eq!(result.len(), 1);
+
+        let json: serde_json::Value =
+            serde_json::from_str(&format_query_batches(&result, OutputFormat::Json)).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!([{"name": "a", "n": 3, "score": 1.23456, "z": null}])
+        );
+
+        let csv = format_query_batches(&result, OutputFormat::Csv);
+        assert_eq!(csv, "name,n,score,z\na,3,1.23456,\n");
+
+        let markdown = format_query_batches(&result, OutputFormat::Markdown);
+        assert!(markdown.contains("| a | 3 | 1.23 |  |"));
+    }
+
+    #[test]
+    fn test_format_query_batches_booleans() {
+        let temp_dir = TempDir::new().unwrap();
+        let executor = QueryExecutor::new(temp_dir.path().to_path_buf());
+        let result = executor
+            .execute_batches("SELECT 'a' AS name, true AS tool_call, false AS reasoning")
+            .unwrap();
+        // Makes the table cells independent of the test runner's locale
+        glyphs::force_ascii();
+
+        let markdown = format_query_batches(&result, OutputFormat::Markdown);
+        assert!(markdown.contains("| a | yes | no |"));
+        let csv = format_query_batches(&result, OutputFormat::Csv);
+        assert_eq!(csv, "name,tool_call,reasoning\na,true,false\n");
+        let json = format_query_batches(&result, OutputFormat::Json);
+        assert!(json.contains("\"tool_call\": true"));
+
+        let markdown = format_query_result(&result.into_result(), OutputFormat::Markdown);
+        assert!(markdown.contains("| a | yes | no |"));
+    }
+
+    #[test]
+    fn test_format_query_batches_json_column_order() {
+        let temp_dir = TempDir::new().unwrap();
+        let executor = QueryExecutor::new(temp_dir.path().to_path_buf());
+        let result = executor.execute_batches("SELECT 2 AS b, 1 AS a").unwrap();
+
+        let json = format_query_batches(&result, OutputFormat::Json);
+        assert!(json.find("\"b\"").unwrap() < json.find("\"a\"").unwrap());
+    }
 }
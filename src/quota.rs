@@ -0,0 +1,234 @@
+//! API quota last reported by Artificial Analysis, per API key.
+//!
+//! Every API response carries rate-limit headers; the latest values are
+//! stored under `quota/<key hash>.json` in the cache. Keying by a hash of
+//! the API key (not the profile name) means profiles sharing a key share
+//! its quota, and the key itself is never written. Each key has its own
+//! file, written to a temporary file and renamed into place, so concurrent
+//! processes never see a partial record. Writers of the same key take a
+//! lock on it and keep whichever record came from the latest response, so
+//! a slow process never overwrites newer quota with older.
+
+use crate::cache::{self, Cache};
+use crate::error::Result;
+use crate::remote::sha256_hex;
+use chrono::{DateTime, Utc};
+use reqwest::header::HeaderMap;
+use serde::{Deserialize, Serialize};
+use std::fs::OpenOptions;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+/// Header names tried for each value, most specific first.
+const LIMIT_HEADERS: &[&str] = &["x-ratelimit-limit", "ratelimit-limit"];
+const REMAINING_HEADERS: &[&str] = &["x-ratelimit-remaining", "ratelimit-remaining"];
+const RESET_HEADERS: &[&str] = &["x-ratelimit-reset", "ratelimit-reset", "retry-after"];
+
+/// Quota as of the latest API response for one key.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct QuotaRecord {
+    /// Requests allowed per window, if reported.
+    #[serde(default)]
+    pub limit: Option<u64>,
+    /// Requests left in the window, if reported.
+    #[serde(default)]
+    pub remaining: Option<u64>,
+    /// When the window resets, as reported (timestamp or seconds).
+    #[serde(default)]
+    pub reset: Option<String>,
+    /// When the response was received.
+    pub updated_at: DateTime<Utc>,
+}
+
+impl QuotaRecord {
+    /// Read the rate-limit headers of a response; `None` when it has none.
+    pub fn from_headers(headers: &HeaderMap, now: DateTime<Utc>) -> Option<Self> {
+        let header = |names: &[&str]| {
+            names.iter().find_map(|name| {
+                headers
+                    .get(*name)
+                    .and_then(|value| value.to_str().ok())
+                    .map(|value| value.trim().to_string())
+            })
+        };
+        let limit = header(LIMIT_HEADERS).and_then(|v| v.parse().ok());
+        let remaining = header(REMAINING_HEADERS).and_then(|v| v.parse().ok());
+        let reset = header(RESET_HEADERS);
+        if limit.is_none() && remaining.is_none() && reset.is_none() {
+            return None;
+        }
+        Some(Self {
+            limit,
+            remaining,
+            reset,
+            updated_at: now,
+        })
+    }
+}
+
+/// Short, stable identifier of an API key that does not reveal it.
+pub fn key_hash(api_key: &str) -> String {
+    sha256_hex(api_key.as_bytes())[..16].to_string()
+}
+
+fn quota_dir(cache_dir: &Path) -> PathBuf {
+    cache_dir.join("quota")
+}
+
+fn record_path(cache_dir: &Path, api_key: &str) -> PathBuf {
+    quota_dir(cache_dir).join(format!("{}.json", key_hash(api_key)))
+}
+
+/// The quota last recorded for `api_key`, if any.
+pub fn get_quota(cache_dir: &Path, api_key: &str) -> Option<QuotaRecord> {
+    let content = std::fs::read_to_string(record_path(cache_dir, api_key)).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+/// How long a writer waits for another writer's lock on the same key
+/// before giving up on its record.
+const LOCK_WAIT: Duration = Duration::from_secs(2);
+
+/// Age after which a lock is taken to be left by a killed process.
+const STALE_LOCK: Duration = Duration::from_secs(30);
+
+/// Exclusive lock on one key's record: a lock file created exclusively and
+/// removed when dropped.
+struct KeyLock(PathBuf);
+
+impl KeyLock {
+    /// Take the lock at `path`, waiting up to [`LOCK_WAIT`] for another
+    /// writer. `None` when it is still held after that.
+    fn acquire(path: PathBuf) -> Result<Option<Self>> {
+        let start = Instant::now();
+        loop {
+            match OpenOptions::new().write(true).create_new(true).open(&path) {
+                Ok(_) => return Ok(Some(Self(path))),
+                Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => {}
+                Err(e) => return Err(e.into()),
+            }
+            let stale = std::fs::metadata(&path)
+                .and_then(|meta| meta.modified())
+                .ok()
+                .and_then(|modified| modified.elapsed().ok())
+                .is_some_and(|age| age > STALE_LOCK);
+            if stale {
+                let _ = std::fs::remove_file(&path);
+            } else if start.elapsed() > LOCK_WAIT {
+                return Ok(None);
+            } else {
+                std::thread::sleep(Duration::from_millis(10));
+            }
+        }
+    }
+}
+
+impl Drop for KeyLock {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.0);
+    }
+}
+
+/// Record the quota for `api_key` atomically, unless the record already
+/// stored is from a later response. A no-op when the cache is read-only.
+pub fn set_quota(cache_dir: &Path, api_key: &str, record: &QuotaRecord) -> Result<()> {
+    if cache::is_read_only() {
+        return Ok(());
+    }
+    let path = record_path(cache_dir, api_key);
+    std::fs::create_dir_all(quota_dir(cache_dir))?;
+    // Held until the record is replaced, so the check below stays true
+    let Some(_lock) = KeyLock::acquire(path.with_extension("lock"))? else {
+        return Ok(());
+    };
+    if get_quota(cache_dir, api_key).is_some_and(|stored| stored.updated_at > record.updated_at) {
+        return Ok(());
+    }
+    // Unique per process, so concurrent writers never share a temporary file
+    let tmp_path = path.with_extension(format!("{}.tmp", std::process::id()));
+    std::fs::write(&tmp_path, serde_json::to_string_pretty(record)?)?;
+    std::fs::rename(&tmp_path, &path)?;
+    Ok(())
+}
+
+/// Record the quota reported by an API response for `api_key`, ignoring
+/// failures: quota tracking is informational.
+pub fn record_response(api_key: &str, headers: &HeaderMap) {
+    let Some(record) = QuotaRecord::from_headers(headers, Utc::now()) else {
+        return;
+    };
+    if let Ok(cache) = Cache::new() {
+        let _ = set_quota(cache.base_dir(), api_key, &record);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::header::HeaderValue;
+    use tempfile::TempDir;
+
+    fn record(remaining: u64) -> QuotaRecord {
+        QuotaRecord {
+            limit: Some(1000),
+            remaining: Some(remaining),
+            reset: None,
+            updated_at: DateTime::parse_from_rfc3339("2025-06-01T08:00:00Z")
+                .unwrap()
+                .with_timezone(&Utc),
+        }
+    }
+
+    #[test]
+    fn test_from_headers() {
+        let now = Utc::now();
+        let mut headers = HeaderMap::new();
+        assert_eq!(QuotaRecord::from_headers(&headers, now), None);
+
+        headers.insert("x-ratelimit-limit", HeaderValue::from_static("1000"));
+        headers.insert("x-ratelimit-remaining", HeaderValue::from_static(" 42 "));
+        headers.insert("retry-after", HeaderValue::from_static("3600"));
+        let quota = QuotaRecord::from_headers(&headers, now).unwrap();
+        assert_eq!(quota.limit, Some(1000));
+        assert_eq!(quota.remaining, Some(42));
+        assert_eq!(quota.reset.as_deref(), Some("3600"));
+    }
+
+    #[test]
+    fn test_quota_keyed_by_api_key() {
+        let temp_dir = TempDir::new().unwrap();
+        assert_eq!(get_quota(temp_dir.path(), "key-a"), None);
+
+        set_quota(temp_dir.path(), "key-a", &record(10)).unwrap();
+        set_quota(temp_dir.path(), "key-b", &record(20)).unwrap();
+        set_quota(temp_dir.path(), "key-a", &record(9)).unwrap();
+        assert_eq!(get_quota(temp_dir.path(), "key-a"), Some(record(9)));
+        assert_eq!(get_quota(temp_dir.path(), "key-b"), Some(record(20)));
+
+        // One file per key, no temporary files or locks left, and no key in
+        // the name
+        let mut files: Vec<String> = std::fs::read_dir(quota_dir(temp_dir.path()))
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().to_string_lossy().into_owned())
+            .collect();
+        files.sort();
+        assert_eq!(files.len(), 2);
+        assert!(files
+            .iter()
+            .all(|f| f.ends_with(".json") && !f.contains("key")));
+        assert_eq!(key_hash("key-a").len(), 16);
+    }
+
+    #[test]
+    fn test_older_record_does_not_overwrite_newer() {
+        let temp_dir = TempDir::new().unwrap();
+        let later = QuotaRecord {
+            updated_at: record(5).updated_at + chrono::Duration::minutes(1),
+            ..record(5)
+        };
+        set_quota(temp_dir.path(), "key-a", &later).unwrap();
+        // A slower process recording an earlier response
+        set_quota(temp_dir.path(), "key-a", &record(9)).unwrap();
+        assert_eq!(get_quota(temp_dir.path(), "key-a"), Some(later));
+    }
+}
@@ -3,12 +3,19 @@
 //! This module provides functionality to fetch pre-built Parquet data files
 //! from GitHub Releases, eliminating the need for users to have API keys.
 
+use crate::cache::{is_read_only, read_only_error};
+use crate::cli::invocation;
+use crate::config::Config;
 use crate::error::{AppError, Result};
+use crate::logging;
+use crate::schema::{get_table_def, LLM_DATA_TTL};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
 use std::collections::HashMap;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tokio::sync::OnceCell;
 
 /// GitHub repository for data releases.
 const GITHUB_REPO: &str = "richard-gyiko/which-llm";
@@ -16,8 +23,93 @@ const GITHUB_REPO: &str = "richard-gyiko/which-llm";
 /// Release tag for latest data.
 const DATA_RELEASE_TAG: &str = "data/latest";
 
+/// Release tag for data republished every six hours by the update-data
+/// workflow, between the daily updates of `DATA_RELEASE_TAG`.
+const NIGHTLY_RELEASE_TAG: &str = "data/nightly";
+
 /// TTL for remote data cache (24 hours).
-const REMOTE_DATA_TTL_HOURS: i64 = 24;
+pub const REMOTE_DATA_TTL_HOURS: i64 = 24;
+
+/// Channel passed with `--channel` (or `WHICH_LLM_CHANNEL`).
+static CHANNEL: std::sync::OnceLock<String> = std::sync::OnceLock::new();
+
+/// Manifest of each channel, fetched at most once per run however many
+/// tables are downloaded.
+static STABLE_MANIFEST: OnceCell<DataManifest> = OnceCell::const_new();
+static NIGHTLY_MANIFEST: OnceCell<DataManifest> = OnceCell::const_new();
+
+/// Published data channel to download from.
+///
+/// Selected with `--channel` (`WHICH_LLM_CHANNEL`) or `channel` in
+/// `config.toml`; stable by default.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DataChannel {
+    /// Daily releases, also kept as dated snapshots.
+    #[default]
+    Stable,
+    /// Fresher data, republished every six hours.
+    Nightly,
+}
+
+impl DataChannel {
+    /// Channel names accepted by `--channel` and the config file.
+    pub const NAMES: [&'static str; 2] = ["stable", "nightly"];
+
+    /// Parse a channel name.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AppError::Config` for an unknown channel.
+    pub fn parse(name: &str) -> Result<Self> {
+        match name.trim().to_lowercase().as_str() {
+            "stable" => Ok(Self::Stable),
+            "nightly" => Ok(Self::Nightly),
+            _ => Err(AppError::Config(format!(
+                "Unknown data channel '{}'. Use one of: {}.",
+                name,
+                Self::NAMES.join(", ")
+            ))),
+        }
+    }
+
+    /// Download from the channel named `name` for this run (`--channel`),
+    /// checked when data is first fetched. Only the first call has an
+    /// effect.
+    pub fn select(name: String) {
+        let _ = CHANNEL.set(name);
+    }
+
+    /// The channel for this run: `--channel` (`WHICH_LLM_CHANNEL`), then
+    /// `channel` in the config file, then stable.
+    pub fn current() -> Result<Self> {
+        match CHANNEL.get() {
+            Some(name) if !name.is_empty() => Self::parse(name),
+            _ => Ok(Config::load()?.channel.unwrap_or_default()),
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Stable => "stable",
+            Self::Nightly => "nightly",
+        }
+    }
+
+    /// Release tag the channel is published under.
+    fn release_tag(self) -> &'static str {
+        match self {
+            Self::Stable => DATA_RELEASE_TAG,
+            Self::Nightly => NIGHTLY_RELEASE_TAG,
+        }
+    }
+}
+
+impl std::fmt::Display for DataChannel {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
 
 /// Manifest file describing the data release.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -26,6 +118,9 @@ pub struct DataManifest {
     pub generated_at: String,
     /// Manifest format version.
     pub version: String,
+    /// Git revision the data was published from.
+    #[serde(default)]
+    pub revision: Option<String>,
     /// Source information.
     pub source: ManifestSource,
     /// Files in the release.
@@ -55,15 +150,60 @@ pub struct Attribution {
     pub url: String,
 }
 
+/// Local record of the hosted data that was downloaded.
+///
+/// Stored as `remote_meta.json` next to the Parquet files.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RemoteMeta {
+    /// When data was last fetched (RFC 3339).
+    pub fetched_at: String,
+    /// Release URL the files were downloaded from.
+    #[serde(default)]
+    pub source_url: Option<String>,
+    /// Channel the files were downloaded from (stable for older records).
+    #[serde(default)]
+    pub channel: DataChannel,
+    /// Git revision of the published data.
+    #[serde(default)]
+    pub revision: Option<String>,
+    /// When the published data was generated.
+    #[serde(default)]
+    pub generated_at: Option<String>,
+    /// Verified size and checksum of each downloaded file.
+    #[serde(default)]
+    pub files: HashMap<String, FileInfo>,
+    /// When each file was last checked against a manifest (RFC 3339). Older
+    /// records only have `fetched_at`.
+    #[serde(default)]
+    pub file_fetched_at: HashMap<String, String>,
+}
+
+/// Integrity of a downloaded file compared to its recorded checksum.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FileStatus {
+    /// File matches the recorded checksum.
+    Ok,
+    /// File contents changed since download.
+    Modified,
+    /// File no longer exists.
+    Missing,
+}
+
 /// Client for fetching data from GitHub Releases.
 pub struct RemoteDataClient {
     http_client: reqwest::Client,
     cache_dir: PathBuf,
+    channel: DataChannel,
 }
 
 impl RemoteDataClient {
-    /// Create a new remote data client.
+    /// Create a new remote data client for the current channel.
     pub fn new(cache_dir: PathBuf) -> Result<Self> {
+        Self::with_channel(cache_dir, DataChannel::current()?)
+    }
+
+    /// Create a new remote data client for `channel`.
+    pub fn with_channel(cache_dir: PathBuf, channel: DataChannel) -> Result<Self> {
         let http_client = reqwest::Client::builder()
             .user_agent(format!("which-llm/{}", env!("CARGO_PKG_VERSION")))
             .build()
@@ -72,10 +212,17 @@ impl RemoteDataClient {
         Ok(Self {
             http_client,
             cache_dir,
+            channel,
         })
     }
 
-    /// Check if local data is fresh (within TTL).
+    /// Channel this client downloads from.
+    pub fn channel(&self) -> DataChannel {
+        self.channel
+    }
+
+    /// Check if local data is fresh (within TTL) and from this client's
+    /// channel; data from another channel is replaced on the next fetch.
     pub fn is_data_fresh(&self) -> bool {
         let meta_path = self.cache_dir.join("remote_meta.json");
         if !meta_path.exists() {
@@ -84,6 +231,9 @@ impl RemoteDataClient {
 
         if let Ok(content) = std::fs::read_to_string(&meta_path) {
             if let Ok(meta) = serde_json::from_str::<RemoteMeta>(&content) {
+                if meta.channel != self.channel {
+                    return false;
+                }
                 if let Ok(fetched_at) = DateTime::parse_from_rfc3339(&meta.fetched_at) {
                     let age = Utc::now().signed_duration_since(fetched_at.with_timezone(&Utc));
                     return age.num_hours() < REMOTE_DATA_TTL_HOURS;
@@ -93,12 +243,51 @@ impl RemoteDataClient {
         false
     }
 
+    /// Check if `filename` was fetched from this client's channel less than
+    /// `ttl` ago.
+    ///
+    /// After a channel switch, files not yet downloaded from the new channel
+    /// are stale even though the record is fresh.
+    pub fn is_file_fresh(&self, filename: &str, ttl: Duration) -> bool {
+        let Some(meta) = self.local_meta() else {
+            return false;
+        };
+        if meta.channel != self.channel
+            || !(meta.files.is_empty() || meta.files.contains_key(filename))
+        {
+            return false;
+        }
+        let fetched_at = meta
+            .file_fetched_at
+            .get(filename)
+            .unwrap_or(&meta.fetched_at);
+        match DateTime::parse_from_rfc3339(fetched_at) {
+            Ok(fetched_at) => Utc::now()
+                .signed_duration_since(fetched_at.with_timezone(&Utc))
+                .to_std()
+                .map_or(true, |age| age < ttl),
+            Err(_) => false,
+        }
+    }
+
     /// Fetch the manifest from GitHub Releases.
+    ///
+    /// The manifest is fetched once per run and channel; later calls, from
+    /// any client, reuse it. A failed fetch is retried by the next call.
     pub async fn fetch_manifest(&self) -> Result<DataManifest> {
-        let url = format!(
-            "https://github.com/{}/releases/download/{}/manifest.json",
-            GITHUB_REPO, DATA_RELEASE_TAG
-        );
+        let cell = match self.channel {
+            DataChannel::Stable => &STABLE_MANIFEST,
+            DataChannel::Nightly => &NIGHTLY_MANIFEST,
+        };
+        cell.get_or_try_init(|| self.download_manifest())
+            .await
+            .cloned()
+    }
+
+    /// Download the manifest, bypassing the per-run copy.
+    async fn download_manifest(&self) -> Result<DataManifest> {
+        let url = release_file_url(self.channel, "manifest.json");
+        logging::debug("http", format_args!("GET {}", url));
 
         let response = self
             .http_client
@@ -106,6 +295,7 @@ impl RemoteDataClient {
             .send()
             .await
             .map_err(|e| AppError::Network(format!("Failed to fetch manifest: {}", e)))?;
+        logging::debug("http", format_args!("GET {} -> {}", url, response.status()));
 
         if !response.status().is_success() {
             return Err(AppError::Network(format!(
@@ -124,10 +314,8 @@ impl RemoteDataClient {
 
     /// Fetch a parquet file from GitHub Releases.
     pub async fn fetch_parquet(&self, filename: &str) -> Result<Vec<u8>> {
-        let url = format!(
-            "https://github.com/{}/releases/download/{}/{}",
-            GITHUB_REPO, DATA_RELEASE_TAG, filename
-        );
+        let url = release_file_url(self.channel, filename);
+        logging::debug("http", format_args!("GET {}", url));
 
         let response = self
             .http_client
@@ -135,6 +323,7 @@ impl RemoteDataClient {
             .send()
             .await
             .map_err(|e| AppError::Network(format!("Failed to fetch {}: {}", filename, e)))?;
+        logging::debug("http", format_args!("GET {} -> {}", url, response.status()));
 
         if !response.status().is_success() {
             return Err(AppError::Network(format!(
@@ -148,46 +337,86 @@ impl RemoteDataClient {
             .bytes()
             .await
             .map_err(|e| AppError::Network(format!("Failed to read {}: {}", filename, e)))?;
+        logging::debug("http", format_args!("{}: {} bytes", filename, bytes.len()));
 
         Ok(bytes.to_vec())
     }
 
     /// Fetch and cache a parquet file if needed, with SHA256 verification.
+    ///
+    /// Files not listed in the manifest, or whose size or checksum differ from
+    /// it, are rejected and the cached copy is left untouched.
     pub async fn ensure_parquet(&self, name: &str, force_refresh: bool) -> Result<PathBuf> {
         let filename = format!("{}.parquet", name);
         let local_path = self.cache_dir.join(&filename);
 
-        // Check if we need to fetch
-        if !force_refresh && local_path.exists() && self.is_data_fresh() {
+        // A read-only cache is used as is, however old
+        if is_read_only() {
+            if force_refresh {
+                return Err(read_only_error(&format!("refresh {}", filename)));
+            }
+            if local_path.exists() {
+                logging::debug("cache", format_args!("{}: read-only cache", filename));
+                return Ok(local_path);
+            }
+            return Err(AppError::Cache(format!(
+                "{} is not cached and the cache is read-only (WHICH_LLM_READONLY)",
+                filename
+            )));
+        }
+
+        // Check if we need to fetch, against the table's own TTL
+        let ttl = match get_table_def(name) {
+            Some(table) => Config::load()?.table_ttl(table)?,
+            None => LLM_DATA_TTL,
+        };
+        let fresh = self.is_file_fresh(&filename, ttl);
+        if !force_refresh && local_path.exists() && fresh {
+            logging::debug("cache", format_args!("{}: using cached copy", filename));
             return Ok(local_path);
         }
+        logging::debug(
+            "cache",
+            format_args!(
+                "{}: fetching (force_refresh={}, cached={}, fresh={})",
+                filename,
+                force_refresh,
+                local_path.exists(),
+                fresh
+            ),
+        );
 
         // Fetch manifest to get expected checksum
         let manifest = self.fetch_manifest().await?;
-        let expected_hash = manifest.files.get(&filename).map(|f| f.sha256.clone());
+        let expected = manifest.files.get(&filename).cloned().ok_or_else(|| {
+            AppError::Cache(format!(
+                "{} is not listed in the data manifest; refusing unverified download",
+                filename
+            ))
+        })?;
+
+        // Unchanged upstream: keep the cached copy instead of downloading it again
+        if matches_manifest(&local_path, &expected) {
+            logging::debug(
+                "cache",
+                format_args!("{}: unchanged upstream, skipping download", filename),
+            );
+            self.record_fetch(&manifest, &filename, expected)?;
+            return Ok(local_path);
+        }
 
-        // Fetch the file
+        // Fetch the file and verify it before touching the cache
         let data = self.fetch_parquet(&filename).await?;
-
-        // Verify SHA256 checksum if available
-        if let Some(expected) = expected_hash {
-            let mut hasher = Sha256::new();
-            hasher.update(&data);
-            let actual = format!("{:x}", hasher.finalize());
-            if actual != expected {
-                return Err(AppError::Cache(format!(
-                    "SHA256 mismatch for {}: expected {}, got {}",
-                    filename, expected, actual
-                )));
-            }
-        }
+        verify_file(&filename, &data, &expected)?;
 
         // Write to cache
         std::fs::create_dir_all(&self.cache_dir)?;
-        std::fs::write(&local_path, data)?;
+        let tmp_path = self.cache_dir.join(format!("{}.tmp", filename));
+        std::fs::write(&tmp_path, data)?;
+        std::fs::rename(&tmp_path, &local_path)?;
 
-        // Update metadata
-        self.update_meta()?;
+        // Record where the file came from
+        self.record_fetch(&manifest, &filename, expected)?;
 
         Ok(local_path)
     }
@@ -224,17 +453,58 @@ impl RemoteDataClient {
         Ok(())
     }
 
-    /// Update the remote metadata file.
-    fn update_meta(&self) -> Result<()> {
-        let meta = RemoteMeta {
-            fetched_at: Utc::now().to_rfc3339(),
-        };
+    /// Record a verified download in the local metadata and manifest files.
+    fn record_fetch(&self, manifest: &DataManifest, filename: &str, info: FileInfo) -> Result<()> {
+        let mut meta = self
+            .local_meta()
+            // Files from another channel no longer describe the cache
+            .filter(|m| m.channel == self.channel)
+            .unwrap_or_default();
+        meta.fetched_at = Utc::now().to_rfc3339();
+        meta.file_fetched_at
+            .insert(filename.to_string(), meta.fetched_at.clone());
+        meta.source_url = Some(release_url(self.channel));
+        meta.channel = self.channel;
+        meta.revision = manifest.revision.clone();
+        meta.generated_at = Some(manifest.generated_at.clone());
+        meta.files.insert(filename.to_string(), info);
+
         let meta_path = self.cache_dir.join("remote_meta.json");
-        let content = serde_json::to_string_pretty(&meta)?;
-        std::fs::write(meta_path, content)?;
+        std::fs::write(meta_path, serde_json::to_string_pretty(&meta)?)?;
+
+        let manifest_path = self.cache_dir.join("manifest.json");
+        std::fs::write(manifest_path, serde_json::to_string_pretty(manifest)?)?;
         Ok(())
     }
 
+    /// Get the local download record if available.
+    pub fn local_meta(&self) -> Option<RemoteMeta> {
+        let content = std::fs::read_to_string(self.cache_dir.join("remote_meta.json")).ok()?;
+        serde_json::from_str(&content).ok()
+    }
+
+    /// Check downloaded files against the checksums recorded at download time.
+    pub fn verify_local_files(&self) -> Vec<(String, FileStatus)> {
+        let Some(meta) = self.local_meta() else {
+            return Vec::new();
+        };
+
+        let mut statuses: Vec<(String, FileStatus)> = meta
+            .files
+            .iter()
+            .map(|(filename, info)| {
+                let status = match std::fs::read(self.cache_dir.join(filename)) {
+                    Ok(data) if verify_file(filename, &data, info).is_ok() => FileStatus::Ok,
+                    Ok(_) => FileStatus::Modified,
+                    Err(_) => FileStatus::Missing,
+                };
+                (filename.clone(), status)
+            })
+            .collect();
+        statuses.sort_by(|a, b| a.0.cmp(&b.0));
+        statuses
+    }
+
     /// Get the local manifest if available.
     pub fn get_local_manifest(&self) -> Option<DataManifest> {
         let manifest_path = self.cache_dir.join("manifest.json");
@@ -258,8 +528,216 @@ impl RemoteDataClient {
     }
 }
 
-/// Metadata about when remote data was fetched.
-#[derive(Debug, Serialize, Deserialize)]
-struct RemoteMeta {
-    fetched_at: String,
+/// URL of the release that `channel` is downloaded from.
+fn release_url(channel: DataChannel) -> String {
+    format!(
+        "https://github.com/{}/releases/tag/{}",
+        GITHUB_REPO,
+        channel.release_tag()
+    )
+}
+
+/// Download URL of a file in the hosted data release of `channel`.
+pub fn release_file_url(channel: DataChannel, filename: &str) -> String {
+    format!(
+        "https://github.com/{}/releases/download/{}/{}",
+        GITHUB_REPO,
+        channel.release_tag(),
+        filename
+    )
+}
+
+/// Compute the hex-encoded SHA256 of `data`.
+pub(crate) fn sha256_hex(data: &[u8]) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(data);
+    format!("{:x}", hasher.finalize())
+}
+
+/// Verify downloaded data against its manifest entry.
+fn verify_file(filename: &str, data: &[u8], expected: &FileInfo) -> Result<()> {
+    let actual = sha256_hex(data);
+    if data.len() as u64 != expected.size || !actual.eq_ignore_ascii_case(&expected.sha256) {
+        return Err(AppError::Cache(format!(
+            "Checksum mismatch for {}: expected sha256 {} ({} bytes), got {} ({} bytes). \
+             The file may be corrupted or tampered with; run '{}' to retry.",
+            filename,
+            expected.sha256,
+            expected.size,
+            actual,
+            data.len(),
+            invocation("refresh")
+        )));
+    }
+    Ok(())
+}
+
+/// Whether the file at `path` already has the size and checksum in `expected`.
+fn matches_manifest(path: &Path, expected: &FileInfo) -> bool {
+    let same_size = std::fs::metadata(path)
+        .map(|meta| meta.len() == expected.size)
+        .unwrap_or(false);
+    same_size
+        && std::fs::read(path)
+            .map(|data| sha256_hex(&data).eq_ignore_ascii_case(&expected.sha256))
+            .unwrap_or(false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn file_info(data: &[u8]) -> FileInfo {
+        FileInfo {
+            size: data.len() as u64,
+            sha256: sha256_hex(data),
+        }
+    }
+
+    #[test]
+    fn test_verify_file() {
+        let data = b"parquet bytes";
+        assert!(verify_file("benchmarks.parquet", data, &file_info(data)).is_ok());
+
+        let err = verify_file("benchmarks.parquet", b"tampered bytes", &file_info(data));
+        assert!(matches!(err, Err(AppError::Cache(msg)) if msg.contains("Checksum mismatch")));
+    }
+
+    #[test]
+    fn test_matches_manifest() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("benchmarks.parquet");
+        let data = b"parquet bytes";
+        assert!(!matches_manifest(&path, &file_info(data)));
+
+        std::fs::write(&path, data).unwrap();
+        assert!(matches_manifest(&path, &file_info(data)));
+        assert!(!matches_manifest(&path, &file_info(b"newer parquet")));
+        assert!(!matches_manifest(&path, &file_info(b"same size!!!!")));
+    }
+
+    fn test_manifest() -> DataManifest {
+        DataManifest {
+            generated_at: "2025-01-01T06:00:00Z".to_string(),
+            version: "1".to_string(),
+            revision: Some("abc123".to_string()),
+            source: ManifestSource {
+                artificial_analysis: "https://artificialanalysis.ai".to_string(),
+                models_dev: "https://models.dev".to_string(),
+            },
+            files: HashMap::new(),
+            attribution: Attribution {
+                text: "Data provided by Artificial Analysis".to_string(),
+                url: "https://artificialanalysis.ai".to_string(),
+            },
+        }
+    }
+
+    #[test]
+    fn test_verify_local_files() {
+        let temp_dir = TempDir::new().unwrap();
+        let client =
+            RemoteDataClient::with_channel(temp_dir.path().to_path_buf(), DataChannel::Stable)
+                .unwrap();
+        let manifest = test_manifest();
+
+        for (name, data) in [
+            ("benchmarks.parquet", &b"benchmarks"[..]),
+            ("models.parquet", &b"models"[..]),
+            ("text_to_image.parquet", &b"media"[..]),
+        ] {
+            std::fs::write(temp_dir.path().join(name), data).unwrap();
+            client
+                .record_fetch(&manifest, name, file_info(data))
+                .unwrap();
+        }
+        std::fs::write(temp_dir.path().join("models.parquet"), b"changed").unwrap();
+        std::fs::remove_file(temp_dir.path().join("text_to_image.parquet")).unwrap();
+
+        let meta = client.local_meta().unwrap();
+        assert_eq!(meta.revision.as_deref(), Some("abc123"));
+        assert!(meta.source_url.unwrap().contains(DATA_RELEASE_TAG));
+
+        assert_eq!(
+            client.verify_local_files(),
+            vec![
+                ("benchmarks.parquet".to_string(), FileStatus::Ok),
+                ("models.parquet".to_string(), FileStatus::Modified),
+                ("text_to_image.parquet".to_string(), FileStatus::Missing),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_channel_parse() {
+        assert_eq!(DataChannel::parse("Nightly").unwrap(), DataChannel::Nightly);
+        assert_eq!(DataChannel::parse("stable").unwrap(), DataChannel::Stable);
+        assert!(DataChannel::parse("beta").is_err());
+        assert!(
+            release_file_url(DataChannel::Nightly, "manifest.json").contains(NIGHTLY_RELEASE_TAG)
+        );
+    }
+
+    #[test]
+    fn test_channel_switch_makes_data_stale() {
+        let temp_dir = TempDir::new().unwrap();
+        let dir = temp_dir.path().to_path_buf();
+        let stable = RemoteDataClient::with_channel(dir.clone(), DataChannel::Stable).unwrap();
+        let nightly = RemoteDataClient::with_channel(dir, DataChannel::Nightly).unwrap();
+        let manifest = test_manifest();
+
+        stable
+            .record_fetch(&manifest, "benchmarks.parquet", file_info(b"stable"))
+            .unwrap();
+        stable
+            .record_fetch(&manifest, "models.parquet", file_info(b"models"))
+            .unwrap();
+        assert!(stable.is_data_fresh());
+        assert!(!nightly.is_data_fresh());
+
+        // The first nightly download starts a new record for that channel
+        nightly
+            .record_fetch(&manifest, "benchmarks.parquet", file_info(b"nightly"))
+            .unwrap();
+        let meta = nightly.local_meta().unwrap();
+        assert_eq!(meta.channel, DataChannel::Nightly);
+        assert!(meta.source_url.unwrap().contains(NIGHTLY_RELEASE_TAG));
+        assert_eq!(meta.files.len(), 1);
+        assert!(nightly.is_data_fresh());
+        assert!(!stable.is_data_fresh());
+    }
+
+    #[test]
+    fn test_file_freshness_uses_table_ttl() {
+        let temp_dir = TempDir::new().unwrap();
+        let client =
+            RemoteDataClient::with_channel(temp_dir.path().to_path_buf(), DataChannel::Stable)
+                .unwrap();
+        let manifest = test_manifest();
+        client
+            .record_fetch(&manifest, "benchmarks.parquet", file_info(b"llms"))
+            .unwrap();
+        client
+            .record_fetch(&manifest, "text_to_image.parquet", file_info(b"media"))
+            .unwrap();
+
+        // Both files were checked two days ago
+        let mut meta = client.local_meta().unwrap();
+        let two_days_ago = (Utc::now() - chrono::Duration::days(2)).to_rfc3339();
+        for fetched_at in meta.file_fetched_at.values_mut() {
+            *fetched_at = two_days_ago.clone();
+        }
+        std::fs::write(
+            temp_dir.path().join("remote_meta.json"),
+            serde_json::to_string(&meta).unwrap(),
+        )
+        .unwrap();
+
+        let hours = |h: u64| Duration::from_secs(h * 60 * 60);
+        assert!(!client.is_file_fresh("benchmarks.parquet", hours(24)));
+        assert!(client.is_file_fresh("text_to_image.parquet", hours(7 * 24)));
+        // Not downloaded yet
+        assert!(!client.is_file_fresh("models.parquet", hours(7 * 24)));
+    }
 }
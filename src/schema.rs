@@ -4,6 +4,16 @@
 //! and SQL query display.
 
 use crate::sources::MODELS;
+use crate::utils::levenshtein;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// How long LLM tables stay fresh before they are refetched. Pricing and
+/// speed change often.
+pub const LLM_DATA_TTL: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// How long media tables stay fresh. Arena rankings move slowly.
+pub const MEDIA_DATA_TTL: Duration = Duration::from_secs(7 * 24 * 60 * 60);
 
 /// Column definition.
 #[derive(Debug, Clone, Copy)]
@@ -11,14 +21,34 @@ pub struct Column {
     pub name: &'static str,
     pub sql_type: &'static str,
     pub nullable: bool,
+    /// What the column holds: units, source, and whether higher or lower is
+    /// better.
+    pub description: &'static str,
+}
+
+/// How a table's Parquet data is laid out in the cache directory.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TableLayout {
+    /// A single Parquet file.
+    File,
+    /// Hive-style partitioned directory (`<dir>/key=value/*.parquet`).
+    Partitioned,
+    /// Several Parquet files matched by a single `*` wildcard.
+    Glob,
 }
 
 /// Table definition.
 #[derive(Debug, Clone, Copy)]
 pub struct TableDef {
     pub name: &'static str,
+    /// Subcommand that caches the table, e.g. `refresh`.
     pub command: &'static str,
+    /// Parquet file name, directory name, or glob pattern depending on `layout`.
     pub parquet_file: &'static str,
+    pub layout: TableLayout,
+    /// How long a cached copy stays fresh. `cache_ttl` in the config file
+    /// overrides it per table.
+    pub default_ttl: Duration,
     pub columns: &'static [Column],
 }
 
@@ -39,6 +69,63 @@ impl TableDef {
             columns.join(",\n    ")
         )
     }
+
+    /// Command line that caches the table, e.g. `which-llm refresh`.
+    pub fn command_line(&self) -> String {
+        crate::cli::invocation(self.command)
+    }
+
+    /// Get the path to the table's data in the cache directory.
+    pub fn cache_path(&self, cache_dir: &Path) -> PathBuf {
+        cache_dir.join(self.parquet_file)
+    }
+
+    /// Check whether the table has cached data.
+    pub fn is_cached(&self, cache_dir: &Path) -> bool {
+        match self.layout {
+            TableLayout::File => self.cache_path(cache_dir).exists(),
+            TableLayout::Partitioned => std::fs::read_dir(self.cache_path(cache_dir))
+                .map(|mut entries| entries.next().is_some())
+                .unwrap_or(false),
+            TableLayout::Glob => !self.glob_files(cache_dir).is_empty(),
+        }
+    }
+
+    /// Files in `cache_dir` matched by a `Glob` table's pattern.
+    pub fn glob_files(&self, cache_dir: &Path) -> Vec<PathBuf> {
+        let (prefix, suffix) = self
+            .parquet_file
+            .split_once('*')
+            .unwrap_or((self.parquet_file, ""));
+        std::fs::read_dir(cache_dir)
+            .map(|entries| {
+                entries
+                    .filter_map(|e| e.ok())
+                    .filter(|e| {
+                        let name = e.file_name().to_string_lossy().to_string();
+                        name.starts_with(prefix) && name.ends_with(suffix)
+                    })
+                    .map(|e| e.path())
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Generate the `read_parquet()` expression that reads this table.
+    pub fn read_parquet_sql(&self, cache_dir: &Path) -> String {
+        let path_str = self
+            .cache_path(cache_dir)
+            .to_string_lossy()
+            .replace('\\', "/");
+        match self.layout {
+            TableLayout::File => format!("read_parquet('{}')", path_str),
+            TableLayout::Partitioned => format!(
+                "read_parquet('{}/*/*.parquet', union_by_name = true)",
+                path_str
+            ),
+            TableLayout::Glob => format!("read_parquet('{}', union_by_name = true)", path_str),
+        }
+    }
 }
 
 /// Benchmarks table schema - contains Artificial Analysis data.
@@ -47,117 +134,494 @@ impl TableDef {
 /// query the `models` table from models.dev.
 pub const BENCHMARKS: TableDef = TableDef {
     name: "benchmarks",
-    command: "which-llm refresh",
+    command: "refresh",
     parquet_file: "benchmarks.parquet",
+    layout: TableLayout::File,
+    default_ttl: LLM_DATA_TTL,
     columns: &[
         // Core identity
         Column {
             name: "id",
             sql_type: "VARCHAR",
             nullable: false,
+            description: "AA model id (UUID)",
         },
         Column {
             name: "name",
             sql_type: "VARCHAR",
             nullable: false,
+            description: "Model display name",
         },
         Column {
             name: "slug",
             sql_type: "VARCHAR",
             nullable: false,
+            description: "AA model slug, e.g. gpt-4o; matches a models.dev model_id without its provider prefix",
         },
         Column {
             name: "creator",
             sql_type: "VARCHAR",
             nullable: false,
+            description: "Model creator, e.g. OpenAI",
         },
         Column {
             name: "creator_slug",
             sql_type: "VARCHAR",
             nullable: true,
+            description: "Model creator slug, e.g. openai",
         },
         Column {
             name: "release_date",
+            sql_type: "DATE",
+            nullable: true,
+            description: "Release date",
+        },
+        // Benchmarks
+        Column {
+            name: "intelligence",
+            sql_type: "DOUBLE",
+            nullable: true,
+            description: "AA Intelligence Index, a composite of the benchmarks (0-100); higher is better",
+        },
+        Column {
+            name: "coding",
+            sql_type: "DOUBLE",
+            nullable: true,
+            description: "AA Coding Index (0-100); higher is better",
+        },
+        Column {
+            name: "math",
+            sql_type: "DOUBLE",
+            nullable: true,
+            description: "AA Math Index (0-100); higher is better",
+        },
+        Column {
+            name: "mmlu_pro",
+            sql_type: "DOUBLE",
+            nullable: true,
+            description: "MMLU-Pro accuracy (0-1); higher is better",
+        },
+        Column {
+            name: "gpqa",
+            sql_type: "DOUBLE",
+            nullable: true,
+            description: "GPQA Diamond accuracy (0-1); higher is better",
+        },
+        Column {
+            name: "hle",
+            sql_type: "DOUBLE",
+            nullable: true,
+            description: "Humanity's Last Exam accuracy (0-1); higher is better",
+        },
+        Column {
+            name: "livecodebench",
+            sql_type: "DOUBLE",
+            nullable: true,
+            description: "LiveCodeBench accuracy (0-1); higher is better",
+        },
+        Column {
+            name: "scicode",
+            sql_type: "DOUBLE",
+            nullable: true,
+            description: "SciCode accuracy (0-1); higher is better",
+        },
+        Column {
+            name: "math_500",
+            sql_type: "DOUBLE",
+            nullable: true,
+            description: "MATH-500 accuracy (0-1); higher is better",
+        },
+        Column {
+            name: "aime",
+            sql_type: "DOUBLE",
+            nullable: true,
+            description: "AIME accuracy (0-1); higher is better",
+        },
+        // Pricing
+        Column {
+            name: "input_price",
+            sql_type: "DOUBLE",
+            nullable: true,
+            description: "USD per 1M input tokens; lower is better",
+        },
+        Column {
+            name: "output_price",
+            sql_type: "DOUBLE",
+            nullable: true,
+            description: "USD per 1M output tokens; lower is better",
+        },
+        Column {
+            name: "price",
+            sql_type: "DOUBLE",
+            nullable: true,
+            description: "Blended USD per 1M tokens at 3 input : 1 output; lower is better",
+        },
+        // Performance
+        Column {
+            name: "tps",
+            sql_type: "DOUBLE",
+            nullable: true,
+            description: "Median output speed in tokens per second; higher is better",
+        },
+        Column {
+            name: "latency",
+            sql_type: "DOUBLE",
+            nullable: true,
+            description: "Median time to first token in seconds; lower is better",
+        },
+    ],
+};
+
+/// LLM history table schema - dated snapshots of the benchmarks table.
+///
+/// Written by `which-llm refresh --snapshot` as a partitioned dataset
+/// (`llms_history/date=YYYY-MM-DD/benchmarks.parquet`).
+pub const LLMS_HISTORY: TableDef = TableDef {
+    name: "llms_history",
+    command: "refresh --snapshot",
+    parquet_file: "llms_history",
+    layout: TableLayout::Partitioned,
+    default_ttl: LLM_DATA_TTL,
+    columns: &[
+        // Core identity
+        Column {
+            name: "id",
+            sql_type: "VARCHAR",
+            nullable: false,
+            description: "AA model id (UUID)",
+        },
+        Column {
+            name: "name",
+            sql_type: "VARCHAR",
+            nullable: false,
+            description: "Model display name",
+        },
+        Column {
+            name: "slug",
+            sql_type: "VARCHAR",
+            nullable: false,
+            description: "AA model slug, e.g. gpt-4o; matches a models.dev model_id without its provider prefix",
+        },
+        Column {
+            name: "creator",
+            sql_type: "VARCHAR",
+            nullable: false,
+            description: "Model creator, e.g. OpenAI",
+        },
+        Column {
+            name: "creator_slug",
             sql_type: "VARCHAR",
             nullable: true,
+            description: "Model creator slug, e.g. openai",
+        },
+        Column {
+            name: "release_date",
+            sql_type: "DATE",
+            nullable: true,
+            description: "Release date",
         },
         // Benchmarks
         Column {
             name: "intelligence",
             sql_type: "DOUBLE",
             nullable: true,
+            description: "AA Intelligence Index, a composite of the benchmarks (0-100); higher is better",
         },
         Column {
             name: "coding",
             sql_type: "DOUBLE",
             nullable: true,
+            description: "AA Coding Index (0-100); higher is better",
         },
         Column {
             name: "math",
             sql_type: "DOUBLE",
             nullable: true,
+            description: "AA Math Index (0-100); higher is better",
         },
         Column {
             name: "mmlu_pro",
             sql_type: "DOUBLE",
             nullable: true,
+            description: "MMLU-Pro accuracy (0-1); higher is better",
         },
         Column {
             name: "gpqa",
             sql_type: "DOUBLE",
             nullable: true,
+            description: "GPQA Diamond accuracy (0-1); higher is better",
         },
         Column {
             name: "hle",
             sql_type: "DOUBLE",
             nullable: true,
+            description: "Humanity's Last Exam accuracy (0-1); higher is better",
         },
         Column {
             name: "livecodebench",
             sql_type: "DOUBLE",
             nullable: true,
+            description: "LiveCodeBench accuracy (0-1); higher is better",
         },
         Column {
             name: "scicode",
             sql_type: "DOUBLE",
             nullable: true,
+            description: "SciCode accuracy (0-1); higher is better",
         },
         Column {
             name: "math_500",
             sql_type: "DOUBLE",
             nullable: true,
+            description: "MATH-500 accuracy (0-1); higher is better",
         },
         Column {
             name: "aime",
             sql_type: "DOUBLE",
             nullable: true,
+            description: "AIME accuracy (0-1); higher is better",
         },
         // Pricing
         Column {
             name: "input_price",
             sql_type: "DOUBLE",
             nullable: true,
+            description: "USD per 1M input tokens; lower is better",
         },
         Column {
             name: "output_price",
             sql_type: "DOUBLE",
             nullable: true,
+            description: "USD per 1M output tokens; lower is better",
         },
         Column {
             name: "price",
             sql_type: "DOUBLE",
             nullable: true,
+            description: "Blended USD per 1M tokens at 3 input : 1 output; lower is better",
         },
         // Performance
         Column {
             name: "tps",
             sql_type: "DOUBLE",
             nullable: true,
+            description: "Median output speed in tokens per second; higher is better",
         },
         Column {
             name: "latency",
             sql_type: "DOUBLE",
             nullable: true,
+            description: "Median time to first token in seconds; lower is better",
+        },
+        // Snapshot
+        Column {
+            name: "snapshot_date",
+            sql_type: "DATE",
+            nullable: false,
+            description: "Date of the `refresh --snapshot` that recorded the row",
+        },
+    ],
+};
+
+/// LLM extra fields table schema - AA API fields without typed support yet.
+///
+/// Stored as key/value rows so newly added AA metrics are queryable
+/// immediately, e.g. `SELECT * FROM llms_extra WHERE json_path LIKE 'evaluations.%'`.
+pub const LLMS_EXTRA: TableDef = TableDef {
+    name: "llms_extra",
+    command: "refresh",
+    parquet_file: "llms_extra.parquet",
+    layout: TableLayout::File,
+    default_ttl: LLM_DATA_TTL,
+    columns: &[
+        Column {
+            name: "model_id",
+            sql_type: "VARCHAR",
+            nullable: false,
+            description: "AA model id; joins to benchmarks.id",
+        },
+        Column {
+            name: "json_path",
+            sql_type: "VARCHAR",
+            nullable: false,
+            description:
+                "Dotted path of the field in the AA API response, e.g. evaluations.new_metric",
+        },
+        Column {
+            name: "value",
+            sql_type: "VARCHAR",
+            nullable: true,
+            description: "Field value as text; NULL for JSON null",
+        },
+    ],
+};
+
+/// Benchmark metrics ranked in `benchmark_ranks`.
+///
+/// The flag marks metrics Artificial Analysis reports as 0–1 accuracies;
+/// those are scaled to 0–100 so every metric shares the indices' scale.
+pub const RANKED_METRICS: &[(&str, bool)] = &[
+    ("intelligence", false),
+    ("coding", false),
+    ("math", false),
+    ("mmlu_pro", true),
+    ("gpqa", true),
+    ("hle", true),
+    ("livecodebench", true),
+    ("scicode", true),
+    ("math_500", true),
+    ("aime", true),
+];
+
+/// Benchmark ranks table schema - derived from `benchmarks` on refresh.
+///
+/// Every metric in [`RANKED_METRICS`] on a 0–100 scale, plus a `<metric>_pct`
+/// percentile rank (0–100) among models that have a score for it, e.g.
+/// `SELECT name, gpqa, gpqa_pct FROM benchmark_ranks ORDER BY gpqa_pct DESC`.
+pub const BENCHMARK_RANKS: TableDef = TableDef {
+    name: "benchmark_ranks",
+    command: "refresh",
+    parquet_file: "benchmark_ranks.parquet",
+    layout: TableLayout::File,
+    default_ttl: LLM_DATA_TTL,
+    columns: &[
+        Column {
+            name: "slug",
+            sql_type: "VARCHAR",
+            nullable: false,
+            description: "AA model slug, e.g. gpt-4o; matches a models.dev model_id without its provider prefix",
+        },
+        Column {
+            name: "name",
+            sql_type: "VARCHAR",
+            nullable: false,
+            description: "Model display name",
+        },
+        Column {
+            name: "creator",
+            sql_type: "VARCHAR",
+            nullable: false,
+            description: "Model creator, e.g. OpenAI",
+        },
+        Column {
+            name: "intelligence",
+            sql_type: "DOUBLE",
+            nullable: true,
+            description: "AA Intelligence Index, a composite of the benchmarks (0-100); higher is better",
+        },
+        Column {
+            name: "intelligence_pct",
+            sql_type: "INTEGER",
+            nullable: true,
+            description: "Percentile of intelligence among models with a score (0-100); higher is better",
+        },
+        Column {
+            name: "coding",
+            sql_type: "DOUBLE",
+            nullable: true,
+            description: "AA Coding Index (0-100); higher is better",
+        },
+        Column {
+            name: "coding_pct",
+            sql_type: "INTEGER",
+            nullable: true,
+            description: "Percentile of coding among models with a score (0-100); higher is better",
+        },
+        Column {
+            name: "math",
+            sql_type: "DOUBLE",
+            nullable: true,
+            description: "AA Math Index (0-100); higher is better",
+        },
+        Column {
+            name: "math_pct",
+            sql_type: "INTEGER",
+            nullable: true,
+            description: "Percentile of math among models with a score (0-100); higher is better",
+        },
+        Column {
+            name: "mmlu_pro",
+            sql_type: "DOUBLE",
+            nullable: true,
+            description: "MMLU-Pro accuracy (0-1); higher is better",
+        },
+        Column {
+            name: "mmlu_pro_pct",
+            sql_type: "INTEGER",
+            nullable: true,
+            description: "Percentile of mmlu_pro among models with a score (0-100); higher is better",
+        },
+        Column {
+            name: "gpqa",
+            sql_type: "DOUBLE",
+            nullable: true,
+            description: "GPQA Diamond accuracy (0-1); higher is better",
+        },
+        Column {
+            name: "gpqa_pct",
+            sql_type: "INTEGER",
+            nullable: true,
+            description: "Percentile of gpqa among models with a score (0-100); higher is better",
+        },
+        Column {
+            name: "hle",
+            sql_type: "DOUBLE",
+            nullable: true,
+            description: "Humanity's Last Exam accuracy (0-1); higher is better",
+        },
+        Column {
+            name: "hle_pct",
+            sql_type: "INTEGER",
+            nullable: true,
+            description: "Percentile of hle among models with a score (0-100); higher is better",
+        },
+        Column {
+            name: "livecodebench",
+            sql_type: "DOUBLE",
+            nullable: true,
+            description: "LiveCodeBench accuracy (0-1); higher is better",
+        },
+        Column {
+            name: "livecodebench_pct",
+            sql_type: "INTEGER",
+            nullable: true,
+            description: "Percentile of livecodebench among models with a score (0-100); higher is better",
+        },
+        Column {
+            name: "scicode",
+            sql_type: "DOUBLE",
+            nullable: true,
+            description: "SciCode accuracy (0-1); higher is better",
+        },
+        Column {
+            name: "scicode_pct",
+            sql_type: "INTEGER",
+            nullable: true,
+            description: "Percentile of scicode among models with a score (0-100); higher is better",
+        },
+        Column {
+            name: "math_500",
+            sql_type: "DOUBLE",
+            nullable: true,
+            description: "MATH-500 accuracy (0-1); higher is better",
+        },
+        Column {
+            name: "math_500_pct",
+            sql_type: "INTEGER",
+            nullable: true,
+            description: "Percentile of math_500 among models with a score (0-100); higher is better",
+        },
+        Column {
+            name: "aime",
+            sql_type: "DOUBLE",
+            nullable: true,
+            description: "AIME accuracy (0-1); higher is better",
+        },
+        Column {
+            name: "aime_pct",
+            sql_type: "INTEGER",
+            nullable: true,
+            description: "Percentile of aime among models with a score (0-100); higher is better",
         },
     ],
 };
@@ -168,100 +632,232 @@ const MEDIA_COLUMNS: &[Column] = &[
         name: "id",
         sql_type: "VARCHAR",
         nullable: false,
+        description: "AA model id (UUID)",
     },
     Column {
         name: "name",
         sql_type: "VARCHAR",
         nullable: false,
+        description: "Model display name",
     },
     Column {
         name: "slug",
         sql_type: "VARCHAR",
         nullable: false,
+        description: "AA model slug",
     },
     Column {
         name: "creator",
         sql_type: "VARCHAR",
         nullable: false,
+        description: "Model creator",
     },
     Column {
         name: "elo",
         sql_type: "DOUBLE",
         nullable: true,
+        description: "Arena ELO rating; higher is better",
     },
     Column {
         name: "rank",
         sql_type: "INTEGER",
         nullable: true,
+        description: "Arena rank; 1 is best",
     },
     Column {
         name: "release_date",
+        sql_type: "DATE",
+        nullable: true,
+        description: "Release date",
+    },
+    Column {
+        name: "ci95",
         sql_type: "VARCHAR",
         nullable: true,
+        description: "95% confidence interval of the ELO rating, as reported by AA",
+    },
+    Column {
+        name: "appearances",
+        sql_type: "BIGINT",
+        nullable: true,
+        description: "Number of arena matchups the rating is based on",
+    },
+    Column {
+        name: "price_per_1m_characters",
+        sql_type: "DOUBLE",
+        nullable: true,
+        description: "USD per 1M input characters (text-to-speech); lower is better",
+    },
+    Column {
+        name: "price_per_minute",
+        sql_type: "DOUBLE",
+        nullable: true,
+        description: "USD per minute of generated output; lower is better",
+    },
+    Column {
+        name: "price_per_second",
+        sql_type: "DOUBLE",
+        nullable: true,
+        description: "USD per second of generated output; lower is better",
     },
 ];
 
 // Individual media table definitions
 pub const TEXT_TO_IMAGE: TableDef = TableDef {
     name: "text_to_image",
-    command: "which-llm refresh",
+    command: "refresh",
     parquet_file: "text_to_image.parquet",
+    layout: TableLayout::File,
+    default_ttl: MEDIA_DATA_TTL,
     columns: MEDIA_COLUMNS,
 };
 
 pub const IMAGE_EDITING: TableDef = TableDef {
     name: "image_editing",
-    command: "which-llm refresh",
+    command: "refresh",
     parquet_file: "image_editing.parquet",
+    layout: TableLayout::File,
+    default_ttl: MEDIA_DATA_TTL,
     columns: MEDIA_COLUMNS,
 };
 
 pub const TEXT_TO_SPEECH: TableDef = TableDef {
     name: "text_to_speech",
-    command: "which-llm refresh",
+    command: "refresh",
     parquet_file: "text_to_speech.parquet",
+    layout: TableLayout::File,
+    default_ttl: MEDIA_DATA_TTL,
     columns: MEDIA_COLUMNS,
 };
 
 pub const TEXT_TO_VIDEO: TableDef = TableDef {
     name: "text_to_video",
-    command: "which-llm refresh",
+    command: "refresh",
     parquet_file: "text_to_video.parquet",
+    layout: TableLayout::File,
+    default_ttl: MEDIA_DATA_TTL,
     columns: MEDIA_COLUMNS,
 };
 
 pub const IMAGE_TO_VIDEO: TableDef = TableDef {
     name: "image_to_video",
-    command: "which-llm refresh",
+    command: "refresh",
     parquet_file: "image_to_video.parquet",
+    layout: TableLayout::File,
+    default_ttl: MEDIA_DATA_TTL,
     columns: MEDIA_COLUMNS,
 };
 
+/// Media categories table schema - per-category ELO breakdowns for media models.
+///
+/// Each media table writes a `<table>_categories.parquet` child file alongside
+/// its own Parquet file; this table reads all of them.
+pub const MEDIA_CATEGORIES: TableDef = TableDef {
+    name: "media_categories",
+    command: "refresh",
+    parquet_file: "*_categories.parquet",
+    layout: TableLayout::Glob,
+    default_ttl: MEDIA_DATA_TTL,
+    columns: &[
+        Column {
+            name: "media_table",
+            sql_type: "VARCHAR",
+            nullable: false,
+            description: "Media table the rating belongs to, e.g. text_to_image",
+        },
+        Column {
+            name: "model_id",
+            sql_type: "VARCHAR",
+            nullable: false,
+            description: "AA model id; joins to the media table's id",
+        },
+        Column {
+            name: "category_type",
+            sql_type: "VARCHAR",
+            nullable: false,
+            description: "Kind of category, e.g. style or subject",
+        },
+        Column {
+            name: "category",
+            sql_type: "VARCHAR",
+            nullable: false,
+            description: "Category value, e.g. photorealistic",
+        },
+        Column {
+            name: "elo",
+            sql_type: "DOUBLE",
+            nullable: true,
+            description: "Arena ELO rating within the category; higher is better",
+        },
+        Column {
+            name: "appearances",
+            sql_type: "BIGINT",
+            nullable: true,
+            description: "Number of arena matchups in the category",
+        },
+    ],
+};
+
 /// All available tables (user-facing).
 pub const ALL_TABLES: &[&TableDef] = &[
     &BENCHMARKS,
+    &LLMS_HISTORY,
+    &LLMS_EXTRA,
+    &BENCHMARK_RANKS,
     &MODELS,
     &TEXT_TO_IMAGE,
     &IMAGE_EDITING,
     &TEXT_TO_SPEECH,
     &TEXT_TO_VIDEO,
     &IMAGE_TO_VIDEO,
+    &MEDIA_CATEGORIES,
+];
+
+/// Media tables (each has a matching `<name>_categories.parquet` child file).
+pub const MEDIA_TABLES: &[&TableDef] = &[
+    &TEXT_TO_IMAGE,
+    &IMAGE_EDITING,
+    &TEXT_TO_SPEECH,
+    &TEXT_TO_VIDEO,
+    &IMAGE_TO_VIDEO,
 ];
 
 /// Get table definition by name.
 pub fn get_table_def(name: &str) -> Option<&'static TableDef> {
     match name {
         "benchmarks" => Some(&BENCHMARKS),
+        "llms_history" => Some(&LLMS_HISTORY),
+        "llms_extra" => Some(&LLMS_EXTRA),
+        "benchmark_ranks" => Some(&BENCHMARK_RANKS),
         "models" => Some(&MODELS),
         "text_to_image" => Some(&TEXT_TO_IMAGE),
         "image_editing" => Some(&IMAGE_EDITING),
         "text_to_speech" => Some(&TEXT_TO_SPEECH),
         "text_to_video" => Some(&TEXT_TO_VIDEO),
         "image_to_video" => Some(&IMAGE_TO_VIDEO),
+        "media_categories" => Some(&MEDIA_CATEGORIES),
         _ => None,
     }
 }
 
+/// Whether no table is cached yet, as on the first run.
+pub fn cache_is_empty(cache_dir: &Path) -> bool {
+    !ALL_TABLES.iter().any(|table| table.is_cached(cache_dir))
+}
+
+/// Find the table whose name is closest to a misspelled one.
+///
+/// Returns `None` when no table is within three edits.
+pub fn suggest_table(name: &str) -> Option<&'static TableDef> {
+    let name = name.trim_matches('"').to_lowercase();
+    ALL_TABLES
+        .iter()
+        .map(|table| (levenshtein(&name, table.name), *table))
+        .filter(|(distance, _)| *distance <= 3)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, table)| table)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -279,6 +875,26 @@ mod tests {
         assert!(!sql.contains("models_dev_matched"));
     }
 
+    #[test]
+    fn test_columns_have_descriptions() {
+        for table in ALL_TABLES {
+            for col in table.columns {
+                assert!(
+                    !col.description.is_empty(),
+                    "{}.{} has no description",
+                    table.name,
+                    col.name
+                );
+            }
+        }
+        let price = BENCHMARKS
+            .columns
+            .iter()
+            .find(|c| c.name == "price")
+            .unwrap();
+        assert!(price.description.contains("3 input : 1 output"));
+    }
+
     #[test]
     fn test_media_tables_share_schema() {
         assert_eq!(TEXT_TO_IMAGE.columns.len(), IMAGE_EDITING.columns.len());
@@ -293,9 +909,74 @@ mod tests {
         assert!(get_table_def("unknown").is_none());
     }
 
+    #[test]
+    fn test_suggest_table() {
+        assert_eq!(suggest_table("benchmark").unwrap().name, "benchmarks");
+        assert_eq!(suggest_table("modles").unwrap().name, "models");
+        assert_eq!(suggest_table("TEXT_TO_IMG").unwrap().name, "text_to_image");
+        assert!(suggest_table("completely_unrelated").is_none());
+    }
+
     #[test]
     fn test_all_tables_count() {
-        assert_eq!(ALL_TABLES.len(), 7);
+        assert_eq!(ALL_TABLES.len(), 11);
+    }
+
+    #[test]
+    fn test_benchmark_ranks_columns_cover_ranked_metrics() {
+        for (metric, _) in RANKED_METRICS {
+            let pct = format!("{}_pct", metric);
+            let names: Vec<&str> = BENCHMARK_RANKS.columns.iter().map(|c| c.name).collect();
+            assert!(names.contains(metric), "missing {}", metric);
+            assert!(names.contains(&pct.as_str()), "missing {}", pct);
+            assert!(BENCHMARKS.columns.iter().any(|c| c.name == *metric));
+        }
+        assert_eq!(BENCHMARK_RANKS.columns.len(), 3 + 2 * RANKED_METRICS.len());
+    }
+
+    #[test]
+    fn test_partitioned_table_paths() {
+        let cache_dir = Path::new("/tmp/which-llm");
+        assert_eq!(
+            BENCHMARKS.read_parquet_sql(cache_dir),
+            "read_parquet('/tmp/which-llm/benchmarks.parquet')"
+        );
+        assert_eq!(
+            LLMS_HISTORY.read_parquet_sql(cache_dir),
+            "read_parquet('/tmp/which-llm/llms_history/*/*.parquet', union_by_name = true)"
+        );
+        assert_eq!(
+            MEDIA_CATEGORIES.read_parquet_sql(cache_dir),
+            "read_parquet('/tmp/which-llm/*_categories.parquet', union_by_name = true)"
+        );
+    }
+
+    #[test]
+    fn test_glob_table_is_cached() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        assert!(!MEDIA_CATEGORIES.is_cached(temp_dir.path()));
+
+        std::fs::write(temp_dir.path().join("text_to_image.parquet"), b"dummy").unwrap();
+        assert!(!MEDIA_CATEGORIES.is_cached(temp_dir.path()));
+
+        std::fs::write(
+            temp_dir.path().join("text_to_image_categories.parquet"),
+            b"dummy",
+        )
+        .unwrap();
+        assert!(MEDIA_CATEGORIES.is_cached(temp_dir.path()));
+    }
+
+    #[test]
+    fn test_cache_is_empty() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        assert!(cache_is_empty(temp_dir.path()));
+
+        std::fs::write(temp_dir.path().join("manifest.json"), b"{}").unwrap();
+        assert!(cache_is_empty(temp_dir.path()));
+
+        std::fs::write(temp_dir.path().join("models.parquet"), b"dummy").unwrap();
+        assert!(!cache_is_empty(temp_dir.path()));
     }
 
     #[test]
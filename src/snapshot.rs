@@ -0,0 +1,238 @@
+//! Pinned benchmark snapshots for reproducible runs.
+//!
+//! With `--as-of`, commands read the `benchmarks` table from the newest
+//! `llms_history` snapshot taken on or before the given date instead of the
+//! latest data. Snapshots are written by `which-llm refresh --snapshot`.
+//!
+//! Each snapshot also records the intelligence index version it was taken
+//! under, when AA reports one, so readers can warn before comparing scores
+//! across a re-basing of the index.
+
+use crate::cli::invocation;
+use crate::error::{AppError, Result};
+use crate::schema::LLMS_HISTORY;
+use chrono::NaiveDate;
+use std::path::{Path, PathBuf};
+
+/// A stored benchmarks snapshot.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Snapshot {
+    pub date: NaiveDate,
+    /// The snapshot's `benchmarks.parquet` file.
+    pub path: PathBuf,
+    /// Intelligence index version the snapshot was taken under, if known.
+    pub index_version: Option<String>,
+}
+
+/// File next to a snapshot's `benchmarks.parquet` naming its index version.
+const INDEX_VERSION_FILE: &str = "index_version";
+
+/// Directory of the snapshot taken on `date`.
+fn partition_dir(cache_dir: &Path, date: NaiveDate) -> PathBuf {
+    LLMS_HISTORY
+        .cache_path(cache_dir)
+        .join(format!("date={}", date))
+}
+
+/// Record the intelligence index version of the snapshot taken on `date`.
+pub fn write_index_version(cache_dir: &Path, date: NaiveDate, version: &str) -> Result<()> {
+    std::fs::write(
+        partition_dir(cache_dir, date).join(INDEX_VERSION_FILE),
+        version,
+    )?;
+    Ok(())
+}
+
+/// Intelligence index version of the snapshot taken on `date`, if recorded.
+pub fn index_version(cache_dir: &Path, date: NaiveDate) -> Option<String> {
+    let version =
+        std::fs::read_to_string(partition_dir(cache_dir, date).join(INDEX_VERSION_FILE)).ok()?;
+    let version = version.trim();
+    (!version.is_empty()).then(|| version.to_string())
+}
+
+/// Index versions of all stored snapshots, oldest first.
+pub fn index_versions(cache_dir: &Path) -> Vec<(NaiveDate, Option<String>)> {
+    snapshot_dates(cache_dir)
+        .into_iter()
+        .map(|date| (date, index_version(cache_dir, date)))
+        .collect()
+}
+
+/// Warning for snapshots that span a re-basing of the intelligence index.
+///
+/// `versions` must be oldest first. Snapshots without a recorded version
+/// are skipped, so `None` means no re-basing is known.
+pub fn rebasing_warning(versions: &[(NaiveDate, Option<String>)]) -> Option<String> {
+    // Each version with the first and last snapshot date taken under it
+    let mut spans: Vec<(&str, NaiveDate, NaiveDate)> = Vec::new();
+    for (date, version) in versions {
+        let Some(version) = version.as_deref() else {
+            continue;
+        };
+        match spans.last_mut() {
+            Some((last, _, until)) if *last == version => *until = *date,
+            _ => spans.push((version, *date, *date)),
+        }
+    }
+    if spans.len() < 2 {
+        return None;
+    }
+
+    let spans: Vec<String> = spans
+        .iter()
+        .map(|(version, from, until)| {
+            if from == until {
+                format!("{} on {}", version, from)
+            } else {
+                format!("{} from {} to {}", version, from, until)
+            }
+        })
+        .collect();
+    Some(format!(
+        "Warning: snapshots span a re-basing of the intelligence index ({}). \
+        Intelligence scores are not comparable across versions; compare changes within one version.",
+        spans.join(", ")
+    ))
+}
+
+/// Warning when a snapshot and the current data use different index versions.
+pub fn version_mismatch_warning(snapshot: &Snapshot, current: Option<&str>) -> Option<String> {
+    let (Some(pinned), Some(current)) = (snapshot.index_version.as_deref(), current) else {
+        return None;
+    };
+    (pinned != current).then(|| {
+        format!(
+            "Warning: the {} snapshot uses intelligence index {}, but current data uses {}. \
+            Its intelligence scores are not comparable with current ones.",
+            snapshot.date, pinned, current
+        )
+    })
+}
+
+/// Dates of all stored benchmarks snapshots, oldest first.
+pub fn snapshot_dates(cache_dir: &Path) -> Vec<NaiveDate> {
+    let Ok(entries) = std::fs::read_dir(LLMS_HISTORY.cache_path(cache_dir)) else {
+        return Vec::new();
+    };
+    let mut dates: Vec<NaiveDate> = entries
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.path().join("benchmarks.parquet").exists())
+        .filter_map(|entry| {
+            let name = entry.file_name();
+            let date = name.to_str()?.strip_prefix("date=")?;
+            NaiveDate::parse_from_str(date, "%Y-%m-%d").ok()
+        })
+        .collect();
+    dates.sort();
+    dates
+}
+
+/// Find the newest snapshot taken on or before `as_of`.
+pub fn resolve(cache_dir: &Path, as_of: NaiveDate) -> Result<Snapshot> {
+    let dates = snapshot_dates(cache_dir);
+    let Some(date) = dates.iter().rev().find(|d| **d <= as_of).copied() else {
+        let available = if dates.is_empty() {
+            "No snapshots are stored".to_string()
+        } else {
+            let dates: Vec<String> = dates.iter().map(|d| d.to_string()).collect();
+            format!("Available snapshots: {}", dates.join(", "))
+        };
+        return Err(AppError::NotFound(format!(
+            "No benchmarks snapshot on or before {}. {}. Snapshots are saved by '{}'.",
+            as_of,
+            available,
+            invocation("refresh --snapshot")
+        )));
+    };
+
+    Ok(Snapshot {
+        date,
+        path: partition_dir(cache_dir, date).join("benchmarks.parquet"),
+        index_version: index_version(cache_dir, date),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn write_snapshot(cache_dir: &Path, date: &str) {
+        let dir = LLMS_HISTORY
+            .cache_path(cache_dir)
+            .join(format!("date={}", date));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("benchmarks.parquet"), b"data").unwrap();
+    }
+
+    #[test]
+    fn test_resolve_picks_latest_on_or_before() {
+        let temp_dir = TempDir::new().unwrap();
+        write_snapshot(temp_dir.path(), "2025-01-01");
+        write_snapshot(temp_dir.path(), "2025-02-15");
+        // Partitions without a benchmarks file are ignored
+        std::fs::create_dir_all(
+            LLMS_HISTORY
+                .cache_path(temp_dir.path())
+                .join("date=2025-01-20"),
+        )
+        .unwrap();
+
+        let date = |s: &str| NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap();
+        let snapshot = resolve(temp_dir.path(), date("2025-02-01")).unwrap();
+        assert_eq!(snapshot.date, date("2025-01-01"));
+        assert!(snapshot
+            .path
+            .ends_with("date=2025-01-01/benchmarks.parquet"));
+
+        let snapshot = resolve(temp_dir.path(), date("2025-02-15")).unwrap();
+        assert_eq!(snapshot.date, date("2025-02-15"));
+
+        let err = resolve(temp_dir.path(), date("2024-12-31")).unwrap_err();
+        assert_eq!(err.exit_code(), 5);
+        assert!(err
+            .to_string()
+            .contains("Available snapshots: 2025-01-01, 2025-02-15"));
+    }
+
+    #[test]
+    fn test_index_versions() {
+        let temp_dir = TempDir::new().unwrap();
+        let date = |s: &str| NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap();
+        for day in ["2025-01-01", "2025-02-01", "2025-03-01", "2025-04-01"] {
+            write_snapshot(temp_dir.path(), day);
+        }
+        write_index_version(temp_dir.path(), date("2025-01-01"), "v2").unwrap();
+        write_index_version(temp_dir.path(), date("2025-02-01"), "v2").unwrap();
+        assert_eq!(rebasing_warning(&index_versions(temp_dir.path())), None);
+
+        // The 2025-03-01 snapshot has no version and is skipped
+        write_index_version(temp_dir.path(), date("2025-04-01"), "v3").unwrap();
+        let versions = index_versions(temp_dir.path());
+        assert_eq!(versions[2], (date("2025-03-01"), None));
+        let warning = rebasing_warning(&versions).unwrap();
+        assert!(warning.contains("v2 from 2025-01-01 to 2025-02-01, v3 on 2025-04-01"));
+
+        let snapshot = resolve(temp_dir.path(), date("2025-02-15")).unwrap();
+        assert_eq!(snapshot.index_version.as_deref(), Some("v2"));
+        assert!(version_mismatch_warning(&snapshot, Some("v3"))
+            .unwrap()
+            .contains("2025-02-01 snapshot uses intelligence index v2"));
+        assert_eq!(version_mismatch_warning(&snapshot, Some("v2")), None);
+        assert_eq!(version_mismatch_warning(&snapshot, None), None);
+    }
+
+    #[test]
+    fn test_resolve_without_snapshots() {
+        let temp_dir = TempDir::new().unwrap();
+        let err = resolve(
+            temp_dir.path(),
+            NaiveDate::from_ymd_opt(2025, 1, 1).unwrap(),
+        )
+        .unwrap_err()
+        .to_string();
+        assert!(err.contains("No snapshots are stored"));
+        assert!(err.contains("refresh --snapshot"));
+    }
+}
@@ -3,7 +3,10 @@
 use super::models::AaLlmModel;
 use crate::cache::Cache;
 use crate::error::{AppError, Result};
+use crate::logging;
 use crate::models::{ApiResponse, MediaModel};
+use crate::quota::{self, QuotaRecord};
+use chrono::Utc;
 use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
 use serde::de::DeserializeOwned;
 use std::time::Duration;
@@ -28,7 +31,6 @@ const CONNECT_TIMEOUT_SECS: u64 = 10;
 /// API client for Artificial Analysis.
 pub struct AaClient {
     http: reqwest::Client,
-    #[allow(dead_code)]
     api_key: String,
     cache: Cache,
 }
@@ -36,6 +38,7 @@ pub struct AaClient {
 impl AaClient {
     /// Create a new API client.
     pub fn new(api_key: String, _profile_name: String) -> Result<Self> {
+        logging::register_secret(&api_key);
         let mut headers = HeaderMap::new();
         headers.insert(
             HeaderName::from_static("x-api-key"),
@@ -70,21 +73,32 @@ impl AaClient {
         // Check cache unless refresh requested
         if !refresh {
             if let Some(cached) = self.cache.get::<T>(&cache_key) {
+                logging::debug("cache", format_args!("{}: hit ({})", endpoint, cache_key));
                 return Ok(cached);
             }
         }
+        logging::debug(
+            "cache",
+            format_args!("{}: miss (refresh={})", endpoint, refresh),
+        );
 
         // Make the request
         let url = format!("{}{}", API_BASE, endpoint);
+        logging::debug("http", format_args!("GET {} {:?}", url, params));
         let response = self.http.get(&url).query(params).send().await?;
 
         // Handle response status
         let status = response.status();
+        logging::debug("http", format_args!("GET {} -> {}", url, status));
+        quota::record_response(&self.api_key, response.headers());
         if status == 401 {
             return Err(AppError::InvalidApiKey);
         }
         if status == 429 {
-            return Err(AppError::RateLimited("unknown".into()));
+            let reset = QuotaRecord::from_headers(response.headers(), Utc::now())
+                .and_then(|quota| quota.reset)
+                .unwrap_or_else(|| "unknown".into());
+            return Err(AppError::RateLimited(reset));
         }
         if status.is_server_error() {
             return Err(AppError::ServerError);
@@ -112,13 +126,15 @@ impl AaClient {
         Ok(response.data)
     }
 
-    /// Fetch media models for a given endpoint.
+    /// Fetch media models for a given endpoint, including per-category scores.
     pub async fn fetch_media_models(
         &self,
         endpoint: &str,
         refresh: bool,
     ) -> Result<Vec<MediaModel>> {
-        let response: ApiResponse<Vec<MediaModel>> = self.request(endpoint, &[], refresh).await?;
+        let response: ApiResponse<Vec<MediaModel>> = self
+            .request(endpoint, &[("include_categories", "true")], refresh)
+            .await?;
 
         Ok(response.data)
     }
@@ -0,0 +1,109 @@
+//! Intelligence index version from untyped AA fields.
+//!
+//! AA periodically re-bases the intelligence index (v2, v3, ...), after which
+//! scores are not comparable with earlier ones. Responses that name the
+//! version carry it in `evaluations` next to the index; those fields end up in
+//! the `llms_extra` table, and this module reads the version back.
+
+use crate::error::{AppError, Result};
+use crate::schema::LLMS_EXTRA;
+use duckdb::Connection;
+use std::collections::HashMap;
+use std::path::Path;
+
+/// `llms_extra` path suffix of the intelligence index version field.
+const VERSION_SUFFIX: &str = "intelligence_index_version";
+
+/// Normalize a raw version to `v<N>`: `3`, `3.0`, `v3` and `V3` are all `v3`.
+pub fn normalize_version(raw: &str) -> Option<String> {
+    let version = raw.trim().trim_matches('"');
+    let version = version
+        .strip_prefix(['v', 'V'])
+        .unwrap_or(version)
+        .trim_end_matches(".0");
+    (!version.is_empty()).then(|| format!("v{}", version))
+}
+
+/// Read the intelligence index version of the cached `llms_extra` table.
+///
+/// When models disagree, the version most of them report wins. Returns
+/// `None` when the table is not cached or carries no version.
+pub fn load_index_version(cache_dir: &Path) -> Result<Option<String>> {
+    if !LLMS_EXTRA.is_cached(cache_dir) {
+        return Ok(None);
+    }
+
+    let conn = Connection::open_in_memory()
+        .map_err(|e| AppError::Cache(format!("DuckDB error: {}", e)))?;
+    let sql = format!(
+        "SELECT value, count(*) FROM read_parquet('{}')
+        WHERE ends_with(json_path, '{}') AND value IS NOT NULL
+        GROUP BY value",
+        LLMS_EXTRA.cache_path(cache_dir).to_string_lossy(),
+        VERSION_SUFFIX
+    );
+    let mut stmt = conn
+        .prepare(&sql)
+        .map_err(|e| AppError::Cache(format!("DuckDB error: {}", e)))?;
+    let rows = stmt
+        .query_map([], |row| {
+            Ok((row.get::<_, String>(0)?, row.get::<_, i64>(1)?))
+        })
+        .map_err(|e| AppError::Cache(format!("DuckDB error: {}", e)))?
+        .collect::<std::result::Result<Vec<_>, _>>()
+        .map_err(|e| AppError::Cache(format!("DuckDB error: {}", e)))?;
+
+    let mut counts: HashMap<String, i64> = HashMap::new();
+    for (value, count) in rows {
+        if let Some(version) = normalize_version(&value) {
+            *counts.entry(version).or_default() += count;
+        }
+    }
+    // Ties go to the later version
+    Ok(counts
+        .into_iter()
+        .max_by(|a, b| a.1.cmp(&b.1).then_with(|| a.0.cmp(&b.0)))
+        .map(|(version, _)| version))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_normalize_version() {
+        assert_eq!(normalize_version("3"), Some("v3".to_string()));
+        assert_eq!(normalize_version("3.0"), Some("v3".to_string()));
+        assert_eq!(normalize_version(" V3 "), Some("v3".to_string()));
+        assert_eq!(normalize_version("\"v2.1\""), Some("v2.1".to_string()));
+        assert_eq!(normalize_version("v"), None);
+    }
+
+    #[test]
+    fn test_load_index_version() {
+        let temp_dir = TempDir::new().unwrap();
+        assert_eq!(load_index_version(temp_dir.path()).unwrap(), None);
+
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute(
+            &format!(
+                "COPY (SELECT * FROM (VALUES
+                    ('a', 'evaluations.artificial_analysis_intelligence_index_version', '3'),
+                    ('b', 'evaluations.artificial_analysis_intelligence_index_version', 'v3'),
+                    ('c', 'evaluations.artificial_analysis_intelligence_index_version', '2'),
+                    ('c', 'evaluations.artificial_analysis_intelligence_index', '61.0')
+                ) t(model_id, json_path, value)) TO '{}' (FORMAT PARQUET)",
+                temp_dir.path().join("llms_extra.parquet").to_string_lossy()
+            ),
+            [],
+        )
+        .unwrap();
+
+        // '3' and 'v3' count toward the same version
+        assert_eq!(
+            load_index_version(temp_dir.path()).unwrap(),
+            Some("v3".to_string())
+        );
+    }
+}
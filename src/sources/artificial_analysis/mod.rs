@@ -1,9 +1,15 @@
 //! Artificial Analysis data source.
 //!
-//! Contains the AA API client, raw data models, and Parquet schema for AA data.
+//! Contains the AA API client, raw data models, Parquet schema, schema
+//! drift validation, intelligence index uncertainty and version, and speed
+//! and latency percentiles for AA data.
 
 mod client;
+pub mod index_version;
 pub mod models;
+pub mod percentiles;
 pub mod schema;
+pub mod uncertainty;
+pub mod validation;
 
-pub use client::AaClient;
+pub use client::{AaClient, API_BASE};
@@ -2,6 +2,7 @@
 //!
 //! These are the unprocessed structures directly from the AA API.
 
+use chrono::NaiveDate;
 use serde::{Deserialize, Serialize};
 
 /// Raw LLM Model from the AA API.
@@ -10,8 +11,8 @@ pub struct AaLlmModel {
     pub id: String,
     pub name: String,
     pub slug: String,
-    #[serde(default)]
-    pub release_date: Option<String>,
+    #[serde(default, deserialize_with = "crate::utils::deserialize_date")]
+    pub release_date: Option<NaiveDate>,
     pub model_creator: AaModelCreator,
     #[serde(default)]
     pub evaluations: Option<AaEvaluations>,
@@ -92,7 +93,7 @@ pub struct AaLlmRow {
     pub slug: String,
     pub creator: String,
     pub creator_slug: Option<String>,
-    pub release_date: Option<String>,
+    pub release_date: Option<NaiveDate>,
     pub intelligence: Option<f64>,
     pub coding: Option<f64>,
     pub math: Option<f64>,
@@ -121,7 +122,7 @@ impl From<&AaLlmModel> for AaLlmRow {
             slug: model.slug.clone(),
             creator: model.model_creator.name.clone(),
             creator_slug: model.model_creator.slug.clone(),
-            release_date: model.release_date.clone(),
+            release_date: model.release_date,
             intelligence: evaluations.and_then(|e| e.artificial_analysis_intelligence_index),
             coding: evaluations.and_then(|e| e.artificial_analysis_coding_index),
             math: evaluations.and_then(|e| e.artificial_analysis_math_index),
@@ -141,6 +142,80 @@ impl From<&AaLlmModel> for AaLlmRow {
     }
 }
 
+/// Untyped AA field captured from `extra` for Parquet storage.
+///
+/// `json_path` is the dotted path of the field in the API response
+/// (e.g. `evaluations.new_metric`, `pricing.tiers[0].price`). Scalar values
+/// are stored as text; JSON `null` is stored as NULL.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AaExtraRow {
+    pub model_id: String,
+    pub json_path: String,
+    pub value: Option<String>,
+}
+
+impl AaExtraRow {
+    /// Collect all unknown fields of a model, including nested `extra` maps.
+    pub fn from_model(model: &AaLlmModel) -> Vec<Self> {
+        let mut rows = Vec::new();
+        collect_extra(&model.id, "", &model.extra, &mut rows);
+        collect_extra(
+            &model.id,
+            "model_creator",
+            &model.model_creator.extra,
+            &mut rows,
+        );
+        if let Some(evaluations) = &model.evaluations {
+            collect_extra(&model.id, "evaluations", &evaluations.extra, &mut rows);
+        }
+        if let Some(pricing) = &model.pricing {
+            collect_extra(&model.id, "pricing", &pricing.extra, &mut rows);
+        }
+        rows
+    }
+}
+
+/// Recursively flatten a JSON value into leaf rows.
+fn collect_extra(
+    model_id: &str,
+    path: &str,
+    value: &serde_json::Value,
+    rows: &mut Vec<AaExtraRow>,
+) {
+    use serde_json::Value;
+
+    let value = match value {
+        Value::Object(map) => {
+            for (key, child) in map {
+                let child_path = if path.is_empty() {
+                    key.clone()
+                } else {
+                    format!("{}.{}", path, key)
+                };
+                collect_extra(model_id, &child_path, child, rows);
+            }
+            return;
+        }
+        Value::Array(items) => {
+            for (i, child) in items.iter().enumerate() {
+                collect_extra(model_id, &format!("{}[{}]", path, i), child, rows);
+            }
+            return;
+        }
+        // A bare null at the root means no extra fields were captured
+        Value::Null if path.is_empty() => return,
+        Value::Null => None,
+        Value::String(s) => Some(s.clone()),
+        other => Some(other.to_string()),
+    };
+
+    rows.push(AaExtraRow {
+        model_id: model_id.to_string(),
+        json_path: path.to_string(),
+        value,
+    });
+}
+
 impl AaLlmModel {
     /// Get intelligence index.
     pub fn intelligence(&self) -> Option<f64> {
@@ -242,4 +317,44 @@ mod tests {
         assert_eq!(row.intelligence, Some(50.0));
         assert_eq!(row.input_price, Some(1.0));
     }
+
+    #[test]
+    fn test_aa_extra_rows() {
+        let json = r#"{
+            "id": "test-uuid",
+            "name": "Test Model",
+            "slug": "test-model",
+            "new_top_level": "beta",
+            "model_creator": {
+                "id": "creator-uuid",
+                "name": "Test Corp"
+            },
+            "evaluations": {
+                "artificial_analysis_intelligence_index": 50.0,
+                "new_benchmark": 0.42,
+                "retired_benchmark": null
+            },
+            "pricing": {
+                "tiers": [{ "price": 1.5 }]
+            }
+        }"#;
+
+        let model: AaLlmModel = serde_json::from_str(json).unwrap();
+        let rows = AaExtraRow::from_model(&model);
+
+        let find = |path: &str| rows.iter().find(|r| r.json_path == path).cloned();
+        assert_eq!(find("new_top_level").unwrap().value, Some("beta".into()));
+        assert_eq!(
+            find("evaluations.new_benchmark").unwrap().value,
+            Some("0.42".into())
+        );
+        assert_eq!(find("evaluations.retired_benchmark").unwrap().value, None);
+        assert_eq!(
+            find("pricing.tiers[0].price").unwrap().value,
+            Some("1.5".into())
+        );
+        // Typed fields are not duplicated
+        assert!(find("evaluations.artificial_analysis_intelligence_index").is_none());
+        assert!(rows.iter().all(|r| r.model_id == "test-uuid"));
+    }
 }
@@ -0,0 +1,177 @@
+//! Speed and latency percentiles from untyped AA fields.
+//!
+//! The typed benchmarks columns only hold medians, but some AA payloads also
+//! carry p5/p25/p75/p95 output speed and time to first token. Those fields
+//! end up in the `llms_extra` table; this module recognizes them by path
+//! (e.g. `percentile_95_output_tokens_per_second` or
+//! `time_to_first_token_seconds_p5`) and reads them back as typed values.
+
+use crate::error::{AppError, Result};
+use crate::models::{LlmModel, Percentile, Percentiles};
+use crate::schema::LLMS_EXTRA;
+use duckdb::Connection;
+use std::collections::HashMap;
+use std::path::Path;
+
+/// Performance metric a percentile field belongs to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Metric {
+    /// Output tokens per second (`tps`).
+    Speed,
+    /// Time to first token in seconds (`latency`).
+    Latency,
+}
+
+/// Recognize a percentile field by its `llms_extra` path.
+///
+/// Time to first *answer* token is a different measure and is not matched.
+pub fn parse_field(json_path: &str) -> Option<(Metric, Percentile)> {
+    let path = json_path.to_lowercase();
+    let metric = if path.contains("output_tokens_per_second") || path.contains("output_speed") {
+        Metric::Speed
+    } else if path.contains("time_to_first_token") || path.contains("ttft") {
+        Metric::Latency
+    } else {
+        return None;
+    };
+
+    let tokens: Vec<&str> = path.split(['_', '.']).collect();
+    let percentile = tokens.iter().enumerate().find_map(|(i, token)| {
+        let number = if *token == "percentile" {
+            *tokens.get(i + 1)?
+        } else {
+            token.strip_prefix('p')?
+        };
+        Percentile::from_value(number.parse().ok()?)
+    })?;
+    Some((metric, percentile))
+}
+
+/// Read speed and latency percentiles by model id from the cached
+/// `llms_extra` table.
+///
+/// Returns an empty map when the table is not cached or has no percentile
+/// fields.
+pub fn load_percentiles(cache_dir: &Path) -> Result<HashMap<String, (Percentiles, Percentiles)>> {
+    if !LLMS_EXTRA.is_cached(cache_dir) {
+        return Ok(HashMap::new());
+    }
+
+    let conn = Connection::open_in_memory()
+        .map_err(|e| AppError::Cache(format!("DuckDB error: {}", e)))?;
+    let sql = format!(
+        "SELECT model_id, json_path, TRY_CAST(value AS DOUBLE)
+        FROM read_parquet('{}')
+        WHERE (json_path ILIKE '%output_tokens_per_second%' OR json_path ILIKE '%output_speed%'
+            OR json_path ILIKE '%time_to_first_token%' OR json_path ILIKE '%ttft%')
+            AND TRY_CAST(value AS DOUBLE) IS NOT NULL",
+        LLMS_EXTRA.cache_path(cache_dir).to_string_lossy()
+    );
+    let mut stmt = conn
+        .prepare(&sql)
+        .map_err(|e| AppError::Cache(format!("DuckDB error: {}", e)))?;
+    let rows = stmt
+        .query_map([], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, f64>(2)?,
+            ))
+        })
+        .map_err(|e| AppError::Cache(format!("DuckDB error: {}", e)))?
+        .collect::<std::result::Result<Vec<_>, _>>()
+        .map_err(|e| AppError::Cache(format!("DuckDB error: {}", e)))?;
+
+    let mut by_model: HashMap<String, (Percentiles, Percentiles)> = HashMap::new();
+    for (model_id, json_path, value) in rows {
+        let Some((metric, percentile)) = parse_field(&json_path) else {
+            continue;
+        };
+        if !value.is_finite() || value < 0.0 {
+            continue;
+        }
+        let (speed, latency) = by_model.entry(model_id).or_default();
+        match metric {
+            Metric::Speed => speed.set(percentile, value),
+            Metric::Latency => latency.set(percentile, value),
+        }
+    }
+    Ok(by_model)
+}
+
+/// Set `tps_percentiles` and `latency_percentiles` on every model with
+/// percentiles in `llms_extra`.
+pub fn attach_percentiles(models: &mut [LlmModel], cache_dir: &Path) -> Result<()> {
+    let percentiles = load_percentiles(cache_dir)?;
+    for model in models {
+        let found = percentiles.get(&model.id);
+        model.tps_percentiles = found.map(|p| p.0).filter(|p| *p != Percentiles::default());
+        model.latency_percentiles = found.map(|p| p.1).filter(|p| *p != Percentiles::default());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_parse_field() {
+        assert_eq!(
+            parse_field("percentile_95_output_tokens_per_second"),
+            Some((Metric::Speed, Percentile::P95))
+        );
+        assert_eq!(
+            parse_field("performance.p05_output_tokens_per_second"),
+            Some((Metric::Speed, Percentile::P5))
+        );
+        assert_eq!(
+            parse_field("time_to_first_token_seconds_p75"),
+            Some((Metric::Latency, Percentile::P75))
+        );
+        assert_eq!(parse_field("median_output_tokens_per_second"), None);
+        assert_eq!(parse_field("p50_output_tokens_per_second"), None);
+        assert_eq!(parse_field("evaluations.p95_score"), None);
+    }
+
+    #[test]
+    fn test_load_percentiles() {
+        let temp_dir = TempDir::new().unwrap();
+        assert!(load_percentiles(temp_dir.path()).unwrap().is_empty());
+
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute(
+            &format!(
+                "COPY (SELECT * FROM (VALUES
+                    ('a', 'percentile_05_output_tokens_per_second', '40.5'),
+                    ('a', 'percentile_95_output_tokens_per_second', '180'),
+                    ('a', 'percentile_95_time_to_first_token_seconds', '2.5'),
+                    ('b', 'p95_output_tokens_per_second', 'n/a'),
+                    ('b', 'evaluations.new_metric', '3.0')
+                ) t(model_id, json_path, value)) TO '{}' (FORMAT PARQUET)",
+                temp_dir.path().join("llms_extra.parquet").to_string_lossy()
+            ),
+            [],
+        )
+        .unwrap();
+
+        let mut models = vec![
+            LlmModel {
+                id: "a".into(),
+                ..Default::default()
+            },
+            LlmModel {
+                id: "b".into(),
+                ..Default::default()
+            },
+        ];
+        attach_percentiles(&mut models, temp_dir.path()).unwrap();
+        let speed = models[0].tps_percentiles.unwrap();
+        assert_eq!(speed.p5, Some(40.5));
+        assert_eq!(speed.p95, Some(180.0));
+        assert_eq!(speed.p25, None);
+        assert_eq!(models[0].latency_percentiles.unwrap().p95, Some(2.5));
+        assert_eq!(models[1].tps_percentiles, None);
+    }
+}
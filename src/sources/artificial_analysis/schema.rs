@@ -1,117 +1,140 @@
 //! Parquet schema for Artificial Analysis benchmark data.
 
-use crate::schema::{Column, TableDef};
+use crate::schema::{Column, TableDef, TableLayout, LLM_DATA_TTL};
 
 /// Benchmarks table schema (from Artificial Analysis).
 pub const BENCHMARKS: TableDef = TableDef {
     name: "benchmarks",
-    command: "which-llm refresh",
+    command: "refresh",
     parquet_file: "benchmarks.parquet",
+    layout: TableLayout::File,
+    default_ttl: LLM_DATA_TTL,
     columns: &[
         Column {
             name: "id",
             sql_type: "VARCHAR",
             nullable: false,
+            description: "AA model id (UUID)",
         },
         Column {
             name: "name",
             sql_type: "VARCHAR",
             nullable: false,
+            description: "Model display name",
         },
         Column {
             name: "slug",
             sql_type: "VARCHAR",
             nullable: false,
+            description: "AA model slug, e.g. gpt-4o; matches a models.dev model_id without its provider prefix",
         },
         Column {
             name: "creator",
             sql_type: "VARCHAR",
             nullable: false,
+            description: "Model creator, e.g. OpenAI",
         },
         Column {
             name: "creator_slug",
             sql_type: "VARCHAR",
             nullable: true,
+            description: "Model creator slug, e.g. openai",
         },
         Column {
             name: "release_date",
-            sql_type: "VARCHAR",
+            sql_type: "DATE",
             nullable: true,
+            description: "Release date",
         },
         Column {
             name: "intelligence",
             sql_type: "DOUBLE",
             nullable: true,
+            description: "AA Intelligence Index, a composite of the benchmarks (0-100); higher is better",
         },
         Column {
             name: "coding",
             sql_type: "DOUBLE",
             nullable: true,
+            description: "AA Coding Index (0-100); higher is better",
         },
         Column {
             name: "math",
             sql_type: "DOUBLE",
             nullable: true,
+            description: "AA Math Index (0-100); higher is better",
         },
         Column {
             name: "mmlu_pro",
             sql_type: "DOUBLE",
             nullable: true,
+            description: "MMLU-Pro accuracy (0-1); higher is better",
         },
         Column {
             name: "gpqa",
             sql_type: "DOUBLE",
             nullable: true,
+            description: "GPQA Diamond accuracy (0-1); higher is better",
         },
         Column {
             name: "hle",
             sql_type: "DOUBLE",
             nullable: true,
+            description: "Humanity's Last Exam accuracy (0-1); higher is better",
         },
         Column {
             name: "livecodebench",
             sql_type: "DOUBLE",
             nullable: true,
+            description: "LiveCodeBench accuracy (0-1); higher is better",
         },
         Column {
             name: "scicode",
             sql_type: "DOUBLE",
             nullable: true,
+            description: "SciCode accuracy (0-1); higher is better",
         },
         Column {
             name: "math_500",
             sql_type: "DOUBLE",
             nullable: true,
+            description: "MATH-500 accuracy (0-1); higher is better",
         },
         Column {
             name: "aime",
             sql_type: "DOUBLE",
             nullable: true,
+            description: "AIME accuracy (0-1); higher is better",
         },
         Column {
             name: "input_price",
             sql_type: "DOUBLE",
             nullable: true,
+            description: "USD per 1M input tokens; lower is better",
         },
         Column {
             name: "output_price",
             sql_type: "DOUBLE",
             nullable: true,
+            description: "USD per 1M output tokens; lower is better",
         },
         Column {
             name: "price",
             sql_type: "DOUBLE",
             nullable: true,
+            description: "Blended USD per 1M tokens at 3 input : 1 output; lower is better",
         },
         Column {
             name: "tps",
             sql_type: "DOUBLE",
             nullable: true,
+            description: "Median output speed in tokens per second; higher is better",
         },
         Column {
             name: "latency",
             sql_type: "DOUBLE",
             nullable: true,
+            description: "Median time to first token in seconds; lower is better",
         },
     ],
 };
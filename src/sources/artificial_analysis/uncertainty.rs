@@ -0,0 +1,153 @@
+//! Intelligence index uncertainty from untyped AA fields.
+//!
+//! AA does not publish a typed error for the intelligence index, but some
+//! responses carry one in `evaluations` next to the index. Those fields end up
+//! in the `llms_extra` table; this module reads them back and normalizes them
+//! to the half-width of a 95% confidence interval, so every error means the
+//! same thing wherever it is shown.
+
+use crate::error::{AppError, Result};
+use crate::models::LlmModel;
+use crate::schema::LLMS_EXTRA;
+use duckdb::Connection;
+use std::collections::HashMap;
+use std::path::Path;
+
+/// `llms_extra` path prefix of the intelligence index fields.
+const INDEX_PATH: &str = "evaluations.artificial_analysis_intelligence_index";
+
+/// Suffixes that already hold a 95% confidence interval half-width.
+const HALF_WIDTH_SUFFIXES: &[&str] = &["_ci", "_ci_95", "_confidence_interval", "_error"];
+
+/// Suffixes that hold a standard error.
+const STANDARD_ERROR_SUFFIXES: &[&str] = &["_se", "_stderr", "_standard_error"];
+
+/// Normal quantile for a two-sided 95% interval.
+const Z_95: f64 = 1.96;
+
+/// Compute the 95% half-width from one model's index fields, keyed by suffix.
+///
+/// A lower/upper interval pair is used as-is; a standard error is scaled to
+/// 95%. Negative or non-finite values are ignored.
+pub fn intelligence_error(fields: &HashMap<&str, f64>) -> Option<f64> {
+    let valid = |v: f64| (v.is_finite() && v >= 0.0).then_some(v);
+
+    let half_width = HALF_WIDTH_SUFFIXES
+        .iter()
+        .find_map(|suffix| fields.get(suffix).copied().and_then(valid));
+    let interval = match (fields.get("_ci_lower"), fields.get("_ci_upper")) {
+        (Some(lower), Some(upper)) => valid((upper - lower) / 2.0),
+        _ => None,
+    };
+    let standard_error = STANDARD_ERROR_SUFFIXES
+        .iter()
+        .find_map(|suffix| fields.get(suffix).copied().and_then(valid))
+        .map(|se| se * Z_95);
+
+    half_width.or(interval).or(standard_error)
+}
+
+/// Read intelligence errors by model id from the cached `llms_extra` table.
+///
+/// Returns an empty map when the table is not cached or has no error fields.
+pub fn load_intelligence_errors(cache_dir: &Path) -> Result<HashMap<String, f64>> {
+    if !LLMS_EXTRA.is_cached(cache_dir) {
+        return Ok(HashMap::new());
+    }
+
+    let conn = Connection::open_in_memory()
+        .map_err(|e| AppError::Cache(format!("DuckDB error: {}", e)))?;
+    let sql = format!(
+        "SELECT model_id, substr(json_path, {}), TRY_CAST(value AS DOUBLE)
+        FROM read_parquet('{}')
+        WHERE starts_with(json_path, '{}_') AND TRY_CAST(value AS DOUBLE) IS NOT NULL",
+        INDEX_PATH.len() + 1,
+        LLMS_EXTRA.cache_path(cache_dir).to_string_lossy(),
+        INDEX_PATH
+    );
+    let mut stmt = conn
+        .prepare(&sql)
+        .map_err(|e| AppError::Cache(format!("DuckDB error: {}", e)))?;
+    let rows = stmt
+        .query_map([], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, f64>(2)?,
+            ))
+        })
+        .map_err(|e| AppError::Cache(format!("DuckDB error: {}", e)))?
+        .collect::<std::result::Result<Vec<_>, _>>()
+        .map_err(|e| AppError::Cache(format!("DuckDB error: {}", e)))?;
+
+    let mut by_model: HashMap<&str, HashMap<&str, f64>> = HashMap::new();
+    for (model_id, suffix, value) in &rows {
+        by_model
+            .entry(model_id.as_str())
+            .or_default()
+            .insert(suffix.as_str(), *value);
+    }
+
+    Ok(by_model
+        .into_iter()
+        .filter_map(|(id, fields)| Some((id.to_string(), intelligence_error(&fields)?)))
+        .collect())
+}
+
+/// Set `intelligence_error` on every model with an error in `llms_extra`.
+pub fn attach_intelligence_errors(models: &mut [LlmModel], cache_dir: &Path) -> Result<()> {
+    let errors = load_intelligence_errors(cache_dir)?;
+    for model in models {
+        model.intelligence_error = errors.get(&model.id).copied();
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_intelligence_error() {
+        let fields = HashMap::from([("_ci", 1.5)]);
+        assert_eq!(intelligence_error(&fields), Some(1.5));
+
+        let fields = HashMap::from([("_ci_lower", 60.0), ("_ci_upper", 64.0)]);
+        assert_eq!(intelligence_error(&fields), Some(2.0));
+
+        let fields = HashMap::from([("_stderr", 1.0)]);
+        assert_eq!(intelligence_error(&fields), Some(Z_95));
+
+        let fields = HashMap::from([("_rank", 3.0), ("_error", -1.0)]);
+        assert_eq!(intelligence_error(&fields), None);
+    }
+
+    #[test]
+    fn test_load_intelligence_errors() {
+        let temp_dir = TempDir::new().unwrap();
+        assert!(load_intelligence_errors(temp_dir.path())
+            .unwrap()
+            .is_empty());
+
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute(
+            &format!(
+                "COPY (SELECT * FROM (VALUES
+                    ('a', 'evaluations.artificial_analysis_intelligence_index_ci', '1.2'),
+                    ('b', 'evaluations.artificial_analysis_intelligence_index_stderr', '0.5'),
+                    ('c', 'evaluations.artificial_analysis_intelligence_index_ci', 'n/a'),
+                    ('c', 'evaluations.new_metric', '3.0')
+                ) t(model_id, json_path, value)) TO '{}' (FORMAT PARQUET)",
+                temp_dir.path().join("llms_extra.parquet").to_string_lossy()
+            ),
+            [],
+        )
+        .unwrap();
+
+        let errors = load_intelligence_errors(temp_dir.path()).unwrap();
+        assert_eq!(errors.len(), 2);
+        assert_eq!(errors["a"], 1.2);
+        assert!((errors["b"] - 0.98).abs() < 1e-9);
+    }
+}
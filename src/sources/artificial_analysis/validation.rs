@@ -0,0 +1,151 @@
+//! Schema drift detection for AA API responses.
+//!
+//! Fields the API renames or removes deserialize to `None` and new fields land
+//! in `extra`, so neither fails loudly. This pass inspects a fetched response
+//! and reports both so users know their build may be outdated.
+
+use super::models::AaLlmModel;
+use std::collections::BTreeSet;
+
+/// Whether a model has a value for a key field.
+type HasField = fn(&AaLlmModel) -> bool;
+
+/// Key fields checked for nulls, with an accessor for each.
+const KEY_FIELDS: &[(&str, HasField)] = &[
+    ("evaluations", |m| m.evaluations.is_some()),
+    ("evaluations.artificial_analysis_intelligence_index", |m| {
+        m.evaluations
+            .as_ref()
+            .and_then(|e| e.artificial_analysis_intelligence_index)
+            .is_some()
+    }),
+    ("pricing", |m| m.pricing.is_some()),
+    ("pricing.price_1m_blended_3_to_1", |m| {
+        m.pricing
+            .as_ref()
+            .and_then(|p| p.price_1m_blended_3_to_1)
+            .is_some()
+    }),
+    ("median_output_tokens_per_second", |m| {
+        m.median_output_tokens_per_second.is_some()
+    }),
+    ("release_date", |m| m.release_date.is_some()),
+];
+
+/// Result of validating an AA LLM response against the expected schema.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct DriftReport {
+    /// Key fields that were null for every model.
+    pub null_fields: Vec<String>,
+    /// Top-level fields with no typed support.
+    pub unknown_fields: Vec<String>,
+}
+
+impl DriftReport {
+    /// True when no drift was detected.
+    pub fn is_clean(&self) -> bool {
+        self.null_fields.is_empty() && self.unknown_fields.is_empty()
+    }
+
+    /// Human-readable warnings, empty when the response looks as expected.
+    pub fn warnings(&self) -> Vec<String> {
+        let mut warnings = Vec::new();
+        if !self.null_fields.is_empty() {
+            warnings.push(format!(
+                "AA API returned no values for: {}",
+                self.null_fields.join(", ")
+            ));
+        }
+        if !self.unknown_fields.is_empty() {
+            warnings.push(format!(
+                "AA API returned unknown fields: {} (available in the llms_extra table)",
+                self.unknown_fields.join(", ")
+            ));
+        }
+        if !warnings.is_empty() {
+            warnings.push(
+                "The AA API schema may have changed; your which-llm build may be outdated.".into(),
+            );
+        }
+        warnings
+    }
+}
+
+/// Check a fetched LLM response for schema drift.
+///
+/// A key field is reported only when it is null for every model, since
+/// individual models routinely lack benchmarks or pricing.
+pub fn validate_llm_models(models: &[AaLlmModel]) -> DriftReport {
+    if models.is_empty() {
+        return DriftReport::default();
+    }
+
+    let null_fields = KEY_FIELDS
+        .iter()
+        .filter(|(_, present)| !models.iter().any(present))
+        .map(|(name, _)| name.to_string())
+        .collect();
+
+    let unknown_fields: BTreeSet<String> = models
+        .iter()
+        .filter_map(|m| m.extra.as_object())
+        .flat_map(|extra| extra.keys().cloned())
+        .collect();
+
+    DriftReport {
+        null_fields,
+        unknown_fields: unknown_fields.into_iter().collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(json: &str) -> Vec<AaLlmModel> {
+        serde_json::from_str(json).unwrap()
+    }
+
+    #[test]
+    fn test_clean_response() {
+        let models = parse(
+            r#"[{
+                "id": "1", "name": "GPT-4o", "slug": "gpt-4o", "release_date": "2024-05-13",
+                "model_creator": { "id": "c", "name": "OpenAI" },
+                "evaluations": { "artificial_analysis_intelligence_index": 55.0 },
+                "pricing": { "price_1m_blended_3_to_1": 4.38 },
+                "median_output_tokens_per_second": 100.0
+            }, {
+                "id": "2", "name": "New Model", "slug": "new-model",
+                "model_creator": { "id": "c", "name": "OpenAI" }
+            }]"#,
+        );
+
+        let report = validate_llm_models(&models);
+        assert!(report.is_clean());
+        assert!(report.warnings().is_empty());
+    }
+
+    #[test]
+    fn test_renamed_field_is_reported() {
+        let models = parse(
+            r#"[{
+                "id": "1", "name": "GPT-4o", "slug": "gpt-4o", "release_date": "2024-05-13",
+                "model_creator": { "id": "c", "name": "OpenAI" },
+                "evaluations": { "artificial_analysis_intelligence_index": 55.0 },
+                "pricing": { "price_1m_blended_3_to_1": 4.38 },
+                "median_output_tps": 100.0
+            }]"#,
+        );
+
+        let report = validate_llm_models(&models);
+        assert_eq!(report.null_fields, vec!["median_output_tokens_per_second"]);
+        assert_eq!(report.unknown_fields, vec!["median_output_tps"]);
+        assert_eq!(report.warnings().len(), 3);
+    }
+
+    #[test]
+    fn test_empty_response() {
+        assert!(validate_llm_models(&[]).is_clean());
+    }
+}
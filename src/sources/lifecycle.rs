@@ -0,0 +1,158 @@
+//! Model lifecycle status across data sources.
+//!
+//! A model is deprecated when every models.dev listing of it carries the
+//! `deprecated` status, and retired when it is in a pinned benchmarks
+//! snapshot but no longer in the current benchmarks table. AA models are
+//! matched to models.dev by slug, with the provider prefix of the models.dev
+//! id stripped (as in the open view and `shortlist --needs`).
+
+use crate::error::Result;
+use crate::models::LlmModel;
+use crate::query::QueryExecutor;
+use crate::schema::BENCHMARKS;
+use crate::snapshot::Snapshot;
+use crate::sources::MODELS;
+use std::collections::HashSet;
+use std::path::Path;
+
+/// Status of a model that models.dev lists as deprecated everywhere.
+pub const DEPRECATED: &str = "deprecated";
+
+/// Status of a model that AA no longer lists.
+pub const RETIRED: &str = "retired";
+
+/// Lowercased keys of models deprecated at every models.dev provider.
+///
+/// Empty when the models table is not cached.
+pub fn deprecated_models(cache_dir: &Path) -> Result<HashSet<String>> {
+    if !MODELS.is_cached(cache_dir) {
+        return Ok(HashSet::new());
+    }
+    let executor = QueryExecutor::new(cache_dir.to_path_buf());
+    let result = executor.execute(&format!(
+        "SELECT lower(regexp_replace(model_id, '^.*/', '')) AS key FROM models
+        GROUP BY key HAVING bool_and(coalesce(lower(status), '') = '{}')",
+        DEPRECATED
+    ))?;
+    Ok(result
+        .rows
+        .into_iter()
+        .map(|mut row| row.remove(0))
+        .collect())
+}
+
+/// Lowercased slugs in `snapshot` that the current benchmarks table lacks.
+///
+/// Empty when the current benchmarks table is not cached.
+pub fn retired_models(cache_dir: &Path, snapshot: &Snapshot) -> Result<HashSet<String>> {
+    if !BENCHMARKS.is_cached(cache_dir) {
+        return Ok(HashSet::new());
+    }
+    let executor = QueryExecutor::new(cache_dir.to_path_buf());
+    let result = executor.execute(&format!(
+        "SELECT DISTINCT lower(slug) FROM read_parquet('{}')
+        WHERE lower(slug) NOT IN (SELECT lower(slug) FROM read_parquet('{}'))",
+        snapshot.path.to_string_lossy(),
+        BENCHMARKS.cache_path(cache_dir).to_string_lossy()
+    ))?;
+    Ok(result
+        .rows
+        .into_iter()
+        .map(|mut row| row.remove(0))
+        .collect())
+}
+
+/// Set `status` on deprecated and retired models.
+///
+/// `snapshot` is the pinned `--as-of` snapshot the models were read from,
+/// if any; retirement is only known relative to one.
+pub fn attach_status(
+    models: &mut [LlmModel],
+    cache_dir: &Path,
+    snapshot: Option<&Snapshot>,
+) -> Result<()> {
+    let deprecated = deprecated_models(cache_dir)?;
+    let retired = match snapshot {
+        Some(snapshot) => retired_models(cache_dir, snapshot)?,
+        None => HashSet::new(),
+    };
+    for model in models {
+        let slug = model.slug.to_lowercase();
+        model.status = if retired.contains(&slug) {
+            Some(RETIRED.to_string())
+        } else if deprecated.contains(&slug) {
+            Some(DEPRECATED.to_string())
+        } else {
+            None
+        };
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+    use duckdb::Connection;
+    use tempfile::TempDir;
+
+    fn write_parquet(path: &Path, select: &str) {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute(
+            &format!(
+                "COPY ({}) TO '{}' (FORMAT PARQUET)",
+                select,
+                path.to_string_lossy()
+            ),
+            [],
+        )
+        .unwrap();
+    }
+
+    fn model(slug: &str) -> LlmModel {
+        LlmModel {
+            slug: slug.to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_attach_status() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut models = vec![model("gpt-4"), model("GPT-4o"), model("gpt-3-5-turbo")];
+        attach_status(&mut models, temp_dir.path(), None).unwrap();
+        assert!(models.iter().all(|m| m.status.is_none()));
+
+        write_parquet(
+            &temp_dir.path().join("models.parquet"),
+            "SELECT * FROM (VALUES
+                ('openai/gpt-4', 'deprecated'),
+                ('gpt-4', 'deprecated'),
+                ('openai/gpt-4o', 'deprecated'),
+                ('azure/gpt-4o', NULL),
+                ('gpt-3-5-turbo', 'deprecated')
+            ) t(model_id, status)",
+        );
+        write_parquet(
+            &temp_dir.path().join("benchmarks.parquet"),
+            "SELECT * FROM (VALUES ('gpt-4'), ('gpt-4o')) t(slug)",
+        );
+        let snapshot = Snapshot {
+            date: NaiveDate::from_ymd_opt(2025, 1, 1).unwrap(),
+            path: temp_dir.path().join("snapshot.parquet"),
+            index_version: None,
+        };
+        write_parquet(
+            &snapshot.path,
+            "SELECT * FROM (VALUES ('gpt-4'), ('gpt-4o'), ('gpt-3-5-turbo')) t(slug)",
+        );
+
+        attach_status(&mut models, temp_dir.path(), None).unwrap();
+        let statuses: Vec<Option<&str>> = models.iter().map(|m| m.status.as_deref()).collect();
+        assert_eq!(statuses, vec![Some(DEPRECATED), None, Some(DEPRECATED)]);
+
+        attach_status(&mut models, temp_dir.path(), Some(&snapshot)).unwrap();
+        let statuses: Vec<Option<&str>> = models.iter().map(|m| m.status.as_deref()).collect();
+        assert_eq!(statuses, vec![Some(DEPRECATED), None, Some(RETIRED)]);
+    }
+}
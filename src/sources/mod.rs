@@ -3,8 +3,10 @@
 //! This module provides the structure for multiple data sources:
 //! - `artificial_analysis`: Primary source for benchmarks and performance metrics
 //! - `models_dev`: Secondary source for capability metadata
+//! - `lifecycle`: Deprecated and retired status derived from both
 
 pub mod artificial_analysis;
+pub mod lifecycle;
 pub mod models_dev;
 
 pub use artificial_analysis::AaClient;
@@ -0,0 +1,356 @@
+//! Capabilities, limits and modalities of models from the models table.
+//!
+//! A model is usually served by several providers; a capability or modality
+//! counts when any provider offers it, and limits are the largest any
+//! provider allows. Keys match AA slugs as in [`super::pricing`].
+
+use crate::cli::invocation;
+use crate::error::{AppError, Result};
+use crate::query::QueryExecutor;
+use crate::sources::MODELS;
+use duckdb::Connection;
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+
+/// What a model supports, across its providers.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct Capabilities {
+    pub tool_call: bool,
+    pub reasoning: bool,
+    pub structured_output: bool,
+    pub attachment: bool,
+    pub temperature: bool,
+    pub open_weights: bool,
+    pub context_window: Option<u64>,
+    pub max_output_tokens: Option<u64>,
+}
+
+impl Capabilities {
+    /// Boolean capabilities by column name.
+    pub fn flags(&self) -> [(&'static str, bool); 6] {
+        [
+            ("tool_call", self.tool_call),
+            ("reasoning", self.reasoning),
+            ("structured_output", self.structured_output),
+            ("attachment", self.attachment),
+            ("temperature", self.temperature),
+            ("open_weights", self.open_weights),
+        ]
+    }
+}
+
+/// Capabilities of the model with `slug`, or `None` when the models table
+/// is not cached or does not list it.
+pub fn load_capabilities(cache_dir: &Path, slug: &str) -> Result<Option<Capabilities>> {
+    if !MODELS.is_cached(cache_dir) {
+        return Ok(None);
+    }
+
+    let conn = Connection::open_in_memory()
+        .map_err(|e| AppError::Cache(format!("DuckDB error: {}", e)))?;
+    let sql = format!(
+        "SELECT coalesce(bool_or(tool_call), false),
+            coalesce(bool_or(reasoning), false),
+            coalesce(bool_or(structured_output), false),
+            coalesce(bool_or(attachment), false),
+            coalesce(bool_or(temperature), false),
+            coalesce(bool_or(open_weights), false),
+            max(context_window),
+            max(max_output_tokens)
+        FROM read_parquet('{}')
+        WHERE lower(regexp_replace(model_id, '^.*/', '')) = ?
+        HAVING count(*) > 0",
+        MODELS.cache_path(cache_dir).to_string_lossy()
+    );
+    let mut stmt = conn
+        .prepare(&sql)
+        .map_err(|e| AppError::Cache(format!("DuckDB error: {}", e)))?;
+    let mut rows = stmt
+        .query_map([slug.to_lowercase()], |row| {
+            Ok(Capabilities {
+                tool_call: row.get(0)?,
+                reasoning: row.get(1)?,
+                structured_output: row.get(2)?,
+                attachment: row.get(3)?,
+                temperature: row.get(4)?,
+                open_weights: row.get(5)?,
+                context_window: row.get::<_, Option<i64>>(6)?.map(|v| v.max(0) as u64),
+                max_output_tokens: row.get::<_, Option<i64>>(7)?.map(|v| v.max(0) as u64),
+            })
+        })
+        .map_err(|e| AppError::Cache(format!("DuckDB error: {}", e)))?;
+    rows.next()
+        .transpose()
+        .map_err(|e| AppError::Cache(format!("DuckDB error: {}", e)))
+}
+
+/// Fail unless the models table is cached, for lookups where a missing
+/// table would silently filter out every model.
+fn require_models(cache_dir: &Path) -> Result<()> {
+    if MODELS.is_cached(cache_dir) {
+        return Ok(());
+    }
+    Err(AppError::Config(format!(
+        "The {} table is not cached. Run '{}' first.",
+        MODELS.name,
+        invocation("refresh")
+    )))
+}
+
+/// Boolean capability columns of the `models` table usable with `--needs`.
+pub const CAPABILITIES: &[&str] = &[
+    "tool_call",
+    "reasoning",
+    "structured_output",
+    "attachment",
+    "temperature",
+    "open_weights",
+];
+
+/// Validate `--needs` values against [`CAPABILITIES`].
+pub fn validate_needs(needs: &[String]) -> Result<Vec<String>> {
+    needs
+        .iter()
+        .map(|need| {
+            let need = need.trim().to_lowercase();
+            if CAPABILITIES.contains(&need.as_str()) {
+                Ok(need)
+            } else {
+                Err(AppError::Config(format!(
+                    "Unknown capability '{}'. Available capabilities: {}",
+                    need,
+                    CAPABILITIES.join(", ")
+                )))
+            }
+        })
+        .collect()
+}
+
+/// Lowercased model keys (model id without provider prefix) of models.dev
+/// models that have every capability in `needs` at some provider.
+pub fn capable_models(cache_dir: &Path, needs: &[String]) -> Result<HashSet<String>> {
+    let executor = QueryExecutor::new(cache_dir.to_path_buf());
+    let conditions: Vec<String> = needs
+        .iter()
+        .map(|need| format!("{} = true", need))
+        .collect();
+    let result = executor.execute(&format!(
+        "SELECT DISTINCT lower(regexp_replace(model_id, '^.*/', '')) FROM models WHERE {}",
+        conditions.join(" AND ")
+    ))?;
+    Ok(result
+        .rows
+        .into_iter()
+        .map(|mut row| row.remove(0))
+        .collect())
+}
+
+/// Modalities models.dev lists in `input_modalities` and `output_modalities`.
+pub const MODALITIES: &[&str] = &["text", "image", "audio", "video", "pdf"];
+
+/// Validate `--input-modality` and `--output-modality` values against
+/// [`MODALITIES`].
+pub fn validate_modalities(modalities: &[String]) -> Result<Vec<String>> {
+    modalities
+        .iter()
+        .map(|modality| {
+            let modality = modality.trim().to_lowercase();
+            if MODALITIES.contains(&modality.as_str()) {
+                Ok(modality)
+            } else {
+                Err(AppError::Config(format!(
+                    "Unknown modality '{}'. Available modalities: {}",
+                    modality,
+                    MODALITIES.join(", ")
+                )))
+            }
+        })
+        .collect()
+}
+
+/// Keys of models that accept every modality in `inputs` and produce every
+/// one in `outputs` at some provider. Both must be validated with
+/// [`validate_modalities`].
+///
+/// Without a cached models table no model could match, so that is an
+/// error instead.
+pub fn modal_models(
+    cache_dir: &Path,
+    inputs: &[String],
+    outputs: &[String],
+) -> Result<HashSet<String>> {
+    require_models(cache_dir)?;
+
+    let conditions: Vec<String> = inputs
+        .iter()
+        .map(|m| ("input_modalities", m))
+        .chain(outputs.iter().map(|m| ("output_modalities", m)))
+        .map(|(column, modality)| {
+            format!(
+                "list_contains(string_split({}, ','), '{}')",
+                column, modality
+            )
+        })
+        .collect();
+    let conn = Connection::open_in_memory()
+        .map_err(|e| AppError::Cache(format!("DuckDB error: {}", e)))?;
+    let sql = format!(
+        "SELECT DISTINCT lower(regexp_replace(model_id, '^.*/', ''))
+        FROM read_parquet('{}')
+        WHERE {}",
+        MODELS.cache_path(cache_dir).to_string_lossy(),
+        conditions.join(" AND ")
+    );
+    let mut stmt = conn
+        .prepare(&sql)
+        .map_err(|e| AppError::Cache(format!("DuckDB error: {}", e)))?;
+    let keys = stmt
+        .query_map([], |row| row.get::<_, String>(0))
+        .map_err(|e| AppError::Cache(format!("DuckDB error: {}", e)))?
+        .collect::<std::result::Result<HashSet<_>, _>>()
+        .map_err(|e| AppError::Cache(format!("DuckDB error: {}", e)))?;
+    Ok(keys)
+}
+
+/// Largest context window and output limit any provider allows.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TokenLimits {
+    pub context_window: Option<u64>,
+    pub max_output_tokens: Option<u64>,
+}
+
+/// Token limits by lowercased model key.
+///
+/// Without a cached models table every model would be dropped by a token
+/// bound, so that is an error instead.
+pub fn load_token_limits(cache_dir: &Path) -> Result<HashMap<String, TokenLimits>> {
+    require_models(cache_dir)?;
+
+    let conn = Connection::open_in_memory()
+        .map_err(|e| AppError::Cache(format!("DuckDB error: {}", e)))?;
+    let sql = format!(
+        "SELECT lower(regexp_replace(model_id, '^.*/', '')) AS key,
+            max(context_window),
+            max(max_output_tokens)
+        FROM read_parquet('{}')
+        GROUP BY key",
+        MODELS.cache_path(cache_dir).to_string_lossy()
+    );
+    let mut stmt = conn
+        .prepare(&sql)
+        .map_err(|e| AppError::Cache(format!("DuckDB error: {}", e)))?;
+    let rows = stmt
+        .query_map([], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                TokenLimits {
+                    context_window: row.get::<_, Option<i64>>(1)?.map(|v| v.max(0) as u64),
+                    max_output_tokens: row.get::<_, Option<i64>>(2)?.map(|v| v.max(0) as u64),
+                },
+            ))
+        })
+        .map_err(|e| AppError::Cache(format!("DuckDB error: {}", e)))?
+        .collect::<std::result::Result<HashMap<_, _>, _>>()
+        .map_err(|e| AppError::Cache(format!("DuckDB error: {}", e)))?;
+    Ok(rows)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_load_capabilities() {
+        let temp_dir = TempDir::new().unwrap();
+        assert_eq!(load_capabilities(temp_dir.path(), "gpt-4o").unwrap(), None);
+        let err = load_token_limits(temp_dir.path()).unwrap_err();
+        assert_eq!(err.exit_code(), 2);
+        assert!(err.to_string().contains("models table is not cached"));
+
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute(
+            &format!(
+                "COPY (SELECT * FROM (VALUES
+                    ('openai/gpt-4o', true, false, true, true, true, false, 128000::BIGINT, 16384::BIGINT),
+                    ('gpt-4o', false, NULL, false, true, true, false, 64000, NULL),
+                    ('claude-sonnet-4', true, true, false, true, true, false, 200000, 64000)
+                ) t(model_id, tool_call, reasoning, structured_output, attachment, temperature,
+                    open_weights, context_window, max_output_tokens)) TO '{}' (FORMAT PARQUET)",
+                temp_dir.path().join("models.parquet").to_string_lossy()
+            ),
+            [],
+        )
+        .unwrap();
+
+        let gpt = load_capabilities(temp_dir.path(), "GPT-4o")
+            .unwrap()
+            .unwrap();
+        assert!(gpt.tool_call && gpt.structured_output);
+        assert!(!gpt.reasoning);
+        assert_eq!(gpt.context_window, Some(128_000));
+        assert_eq!(gpt.max_output_tokens, Some(16_384));
+        assert_eq!(load_capabilities(temp_dir.path(), "gpt-9").unwrap(), None);
+
+        let limits = load_token_limits(temp_dir.path()).unwrap();
+        assert_eq!(
+            limits["gpt-4o"],
+            TokenLimits {
+                context_window: Some(128_000),
+                max_output_tokens: Some(16_384),
+            }
+        );
+        assert_eq!(limits["claude-sonnet-4"].context_window, Some(200_000));
+        assert_eq!(limits["claude-sonnet-4"].max_output_tokens, Some(64_000));
+    }
+
+    #[test]
+    fn test_validate_needs() {
+        assert_eq!(
+            validate_needs(&["Tool_Call".to_string()]).unwrap(),
+            vec!["tool_call"]
+        );
+        let err = validate_needs(&["vision".to_string()]).unwrap_err();
+        assert_eq!(err.exit_code(), 2);
+        assert!(err.to_string().contains("tool_call"));
+    }
+
+    #[test]
+    fn test_validate_modalities() {
+        assert_eq!(
+            validate_modalities(&[" Audio".to_string(), "text".to_string()]).unwrap(),
+            vec!["audio", "text"]
+        );
+        let err = validate_modalities(&["vision".to_string()]).unwrap_err();
+        assert_eq!(err.exit_code(), 2);
+        assert!(err.to_string().contains("image"));
+    }
+
+    #[test]
+    fn test_modal_models() {
+        let temp_dir = TempDir::new().unwrap();
+        let audio = ["audio".to_string()];
+        let err = modal_models(temp_dir.path(), &audio, &[]).unwrap_err();
+        assert!(err.to_string().contains("models table"));
+
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute(
+            &format!(
+                "COPY (SELECT * FROM (VALUES
+                    ('openai/gpt-4o', 'text,image,audio', 'text'),
+                    ('gpt-4o', 'text', 'text'),
+                    ('claude-sonnet-4', 'text,image,pdf', 'text')
+                ) t(model_id, input_modalities, output_modalities)) TO '{}' (FORMAT PARQUET)",
+                temp_dir.path().join("models.parquet").to_string_lossy()
+            ),
+            [],
+        )
+        .unwrap();
+
+        let keys = modal_models(temp_dir.path(), &audio, &["text".to_string()]).unwrap();
+        assert_eq!(keys, HashSet::from(["gpt-4o".to_string()]));
+        let keys = modal_models(temp_dir.path(), &["image".to_string()], &[]).unwrap();
+        assert_eq!(keys.len(), 2);
+    }
+}
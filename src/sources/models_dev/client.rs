@@ -2,6 +2,7 @@
 
 use super::models::{flatten_response, ModelsDevResponse, ModelsDevRow};
 use crate::error::{AppError, Result};
+use crate::logging;
 use std::time::Duration;
 
 /// Endpoint for models.dev API.
@@ -32,9 +33,11 @@ impl ModelsDevClient {
 
     /// Fetch all model data from models.dev.
     pub async fn fetch(&self) -> Result<ModelsDevResponse> {
+        logging::debug("http", format_args!("GET {}", MODELS_DEV_API));
         let response = self.http.get(MODELS_DEV_API).send().await?;
 
         let status = response.status();
+        logging::debug("http", format_args!("GET {} -> {}", MODELS_DEV_API, status));
         if !status.is_success() {
             let body = response.text().await.unwrap_or_default();
             return Err(AppError::Api {
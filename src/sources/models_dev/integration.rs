@@ -0,0 +1,188 @@
+//! Provider SDK integration hints from the models table.
+//!
+//! models.dev lists, per provider, the AI SDK npm package and the
+//! environment variables holding the API key. This module collects them for
+//! each model so they can be shown next to AA data. Keys match AA slugs as
+//! in [`super::pricing`].
+
+use crate::error::{AppError, Result};
+use crate::models::LlmModel;
+use crate::sources::MODELS;
+use duckdb::Connection;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::path::Path;
+
+/// How to call a model through one provider.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Integration {
+    pub provider_id: String,
+    pub provider_name: String,
+    pub npm_package: Option<String>,
+    pub env_vars: Vec<String>,
+}
+
+impl Integration {
+    /// Ready-to-copy shell lines: one `export` per env var, then the
+    /// install command for the SDK package.
+    pub fn setup_commands(&self) -> Vec<String> {
+        let mut lines: Vec<String> = self
+            .env_vars
+            .iter()
+            .map(|var| format!("export {}=...", var))
+            .collect();
+        if let Some(package) = &self.npm_package {
+            lines.push(format!("npm install ai {}", package));
+        }
+        lines
+    }
+}
+
+/// Providers with integration hints by lowercased model key, ordered by
+/// provider id. Providers with neither a package nor env vars are left out.
+/// Empty when the models table is not cached.
+pub fn load_integrations(cache_dir: &Path) -> Result<HashMap<String, Vec<Integration>>> {
+    if !MODELS.is_cached(cache_dir) {
+        return Ok(HashMap::new());
+    }
+
+    let conn = Connection::open_in_memory()
+        .map_err(|e| AppError::Cache(format!("DuckDB error: {}", e)))?;
+    let sql = format!(
+        "SELECT DISTINCT lower(regexp_replace(model_id, '^.*/', '')) AS key,
+            provider_id, provider_name, provider_npm, provider_env
+        FROM read_parquet('{}')
+        WHERE provider_npm IS NOT NULL OR provider_env IS NOT NULL
+        ORDER BY key, provider_id",
+        MODELS.cache_path(cache_dir).to_string_lossy()
+    );
+    let mut stmt = conn
+        .prepare(&sql)
+        .map_err(|e| AppError::Cache(format!("DuckDB error: {}", e)))?;
+    let rows = stmt
+        .query_map([], |row| {
+            let env: Option<String> = row.get(4)?;
+            Ok((
+                row.get::<_, String>(0)?,
+                Integration {
+                    provider_id: row.get(1)?,
+                    provider_name: row.get(2)?,
+                    npm_package: row.get(3)?,
+                    env_vars: env
+                        .iter()
+                        .flat_map(|e| e.split(','))
+                        .map(str::trim)
+                        .filter(|v| !v.is_empty())
+                        .map(String::from)
+                        .collect(),
+                },
+            ))
+        })
+        .map_err(|e| AppError::Cache(format!("DuckDB error: {}", e)))?;
+
+    let mut integrations: HashMap<String, Vec<Integration>> = HashMap::new();
+    for row in rows {
+        let (key, integration) =
+            row.map_err(|e| AppError::Cache(format!("DuckDB error: {}", e)))?;
+        let providers = integrations.entry(key).or_default();
+        // A provider lists a model once per id prefix; keep one entry
+        if !providers
+            .iter()
+            .any(|p| p.provider_id == integration.provider_id)
+        {
+            providers.push(integration);
+        }
+    }
+    Ok(integrations)
+}
+
+/// The provider to suggest for `model`: the creator's own when it serves
+/// the model, otherwise the first by provider id.
+pub fn preferred<'a>(model: &LlmModel, providers: &'a [Integration]) -> Option<&'a Integration> {
+    let creator = model
+        .creator_slug
+        .clone()
+        .unwrap_or_else(|| model.creator.to_lowercase());
+    providers
+        .iter()
+        .find(|p| p.provider_id.eq_ignore_ascii_case(&creator))
+        .or_else(|| providers.first())
+}
+
+/// Set `npm_package` and `env_vars` on every model from its preferred
+/// provider.
+pub fn attach_integrations(models: &mut [LlmModel], cache_dir: &Path) -> Result<()> {
+    let integrations = load_integrations(cache_dir)?;
+    for model in models {
+        let providers = integrations
+            .get(&model.slug.to_lowercase())
+            .map(Vec::as_slice)
+            .unwrap_or_default();
+        let provider = preferred(model, providers);
+        model.npm_package = provider.and_then(|p| p.npm_package.clone());
+        model.env_vars = provider
+            .filter(|p| !p.env_vars.is_empty())
+            .map(|p| p.env_vars.join(","));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_attach_integrations() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut models = vec![
+            LlmModel {
+                slug: "gpt-4o".into(),
+                creator: "OpenAI".into(),
+                creator_slug: Some("openai".into()),
+                ..Default::default()
+            },
+            LlmModel {
+                slug: "llama-4".into(),
+                creator: "Meta".into(),
+                ..Default::default()
+            },
+        ];
+        attach_integrations(&mut models, temp_dir.path()).unwrap();
+        assert!(models.iter().all(|m| m.npm_package.is_none()));
+
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute(
+            &format!(
+                "COPY (SELECT * FROM (VALUES
+                    ('azure', 'Azure', 'gpt-4o', '@ai-sdk/azure', 'AZURE_API_KEY,AZURE_RESOURCE_NAME'),
+                    ('openai', 'OpenAI', 'gpt-4o', '@ai-sdk/openai', 'OPENAI_API_KEY'),
+                    ('groq', 'Groq', 'meta/llama-4', NULL, 'GROQ_API_KEY'),
+                    ('local', 'Local', 'llama-4', NULL, NULL)
+                ) t(provider_id, provider_name, model_id, provider_npm, provider_env))
+                TO '{}' (FORMAT PARQUET)",
+                temp_dir.path().join("models.parquet").to_string_lossy()
+            ),
+            [],
+        )
+        .unwrap();
+
+        let integrations = load_integrations(temp_dir.path()).unwrap();
+        assert_eq!(integrations["gpt-4o"].len(), 2);
+        assert_eq!(
+            integrations["gpt-4o"][0].env_vars,
+            ["AZURE_API_KEY", "AZURE_RESOURCE_NAME"]
+        );
+        assert_eq!(
+            integrations["gpt-4o"][1].setup_commands(),
+            ["export OPENAI_API_KEY=...", "npm install ai @ai-sdk/openai"]
+        );
+
+        attach_integrations(&mut models, temp_dir.path()).unwrap();
+        // The creator's own provider wins over the first by id
+        assert_eq!(models[0].npm_package.as_deref(), Some("@ai-sdk/openai"));
+        assert_eq!(models[0].env_vars.as_deref(), Some("OPENAI_API_KEY"));
+        assert_eq!(models[1].npm_package, None);
+        assert_eq!(models[1].env_vars.as_deref(), Some("GROQ_API_KEY"));
+    }
+}
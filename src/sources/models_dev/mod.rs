@@ -1,10 +1,15 @@
 //! models.dev data source.
 //!
-//! Contains the models.dev API client, raw data models, and Parquet schema.
+//! Contains the models.dev API client, raw data models, Parquet schema,
+//! long-context pricing tiers, per-model capabilities, and provider SDK
+//! integration hints.
 
+pub mod capabilities;
 mod client;
+pub mod integration;
 pub mod models;
+pub mod pricing;
 pub mod schema;
 
-pub use client::ModelsDevClient;
+pub use client::{ModelsDevClient, MODELS_DEV_API};
 pub use schema::MODELS;
@@ -1,6 +1,8 @@
 //! Data models for models.dev API responses.
 
+use chrono::NaiveDate;
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
 use std::collections::HashMap;
 
 /// Top-level response from models.dev API.
@@ -42,13 +44,13 @@ pub struct ModelsDevModel {
     pub structured_output: Option<bool>,
     #[serde(default)]
     pub temperature: Option<bool>,
-    // Metadata
-    #[serde(default)]
-    pub knowledge: Option<String>,
-    #[serde(default)]
-    pub release_date: Option<String>,
-    #[serde(default)]
-    pub last_updated: Option<String>,
+    // Metadata (month-only knowledge cutoffs map to the first of the month)
+    #[serde(default, deserialize_with = "crate::utils::deserialize_date")]
+    pub knowledge: Option<NaiveDate>,
+    #[serde(default, deserialize_with = "crate::utils::deserialize_date")]
+    pub release_date: Option<NaiveDate>,
+    #[serde(default, deserialize_with = "crate::utils::deserialize_date")]
+    pub last_updated: Option<NaiveDate>,
     #[serde(default)]
     pub open_weights: Option<bool>,
     #[serde(default)]
@@ -76,6 +78,9 @@ pub struct ModelsDevLimits {
 }
 
 /// Cost information from models.dev (per million tokens).
+///
+/// Long-context tiers arrive as `context_over_<N>k` objects holding the
+/// prices that apply once the prompt exceeds N thousand tokens.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ModelsDevCost {
     #[serde(default)]
@@ -86,6 +91,42 @@ pub struct ModelsDevCost {
     pub cache_read: Option<f64>,
     #[serde(default)]
     pub cache_write: Option<f64>,
+    /// Other cost keys, including long-context tiers.
+    #[serde(flatten)]
+    pub extra: HashMap<String, Value>,
+}
+
+/// Prices that apply to a whole request once its prompt exceeds `threshold`
+/// tokens.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ModelsDevCostTier {
+    pub threshold: u64,
+    pub input: Option<f64>,
+    pub output: Option<f64>,
+}
+
+impl ModelsDevCost {
+    /// Long-context tiers, lowest threshold first.
+    pub fn tiers(&self) -> Vec<ModelsDevCostTier> {
+        let mut tiers: Vec<ModelsDevCostTier> = self
+            .extra
+            .iter()
+            .filter_map(|(key, value)| {
+                let thousands: u64 = key
+                    .strip_prefix("context_over_")?
+                    .strip_suffix('k')?
+                    .parse()
+                    .ok()?;
+                Some(ModelsDevCostTier {
+                    threshold: thousands * 1000,
+                    input: value.get("input").and_then(Value::as_f64),
+                    output: value.get("output").and_then(Value::as_f64),
+                })
+            })
+            .collect();
+        tiers.sort_by_key(|tier| tier.threshold);
+        tiers
+    }
 }
 
 /// Modality information from models.dev.
@@ -120,9 +161,9 @@ pub struct ModelsDevRow {
     pub structured_output: Option<bool>,
     pub temperature: Option<bool>,
     // Metadata
-    pub knowledge: Option<String>,
-    pub release_date: Option<String>,
-    pub last_updated: Option<String>,
+    pub knowledge: Option<NaiveDate>,
+    pub release_date: Option<NaiveDate>,
+    pub last_updated: Option<NaiveDate>,
     pub open_weights: Option<bool>,
     pub status: Option<String>,
     // Limits
@@ -134,6 +175,10 @@ pub struct ModelsDevRow {
     pub cost_output: Option<f64>,
     pub cost_cache_read: Option<f64>,
     pub cost_cache_write: Option<f64>,
+    // Lowest long-context tier
+    pub long_context_threshold: Option<u64>,
+    pub cost_input_long_context: Option<f64>,
+    pub cost_output_long_context: Option<f64>,
     // Modalities (stored as comma-separated strings for Parquet)
     pub input_modalities: Option<String>,
     pub output_modalities: Option<String>,
@@ -142,6 +187,10 @@ pub struct ModelsDevRow {
 impl ModelsDevRow {
     /// Create a flattened row from provider and model.
     pub fn from_provider_model(provider: &ModelsDevProvider, model: &ModelsDevModel) -> Self {
+        let tier = model
+            .cost
+            .as_ref()
+            .and_then(|c| c.tiers().into_iter().next());
         Self {
             provider_id: provider.id.clone(),
             provider_name: provider.name.clone(),
@@ -165,9 +214,9 @@ impl ModelsDevRow {
             structured_output: model.structured_output,
             temperature: model.temperature,
             // Metadata
-            knowledge: model.knowledge.clone(),
-            release_date: model.release_date.clone(),
-            last_updated: model.last_updated.clone(),
+            knowledge: model.knowledge,
+            release_date: model.release_date,
+            last_updated: model.last_updated,
             open_weights: model.open_weights,
             status: model.status.clone(),
             // Limits
@@ -179,6 +228,9 @@ impl ModelsDevRow {
             cost_output: model.cost.as_ref().and_then(|c| c.output),
             cost_cache_read: model.cost.as_ref().and_then(|c| c.cache_read),
             cost_cache_write: model.cost.as_ref().and_then(|c| c.cache_write),
+            long_context_threshold: tier.as_ref().map(|t| t.threshold),
+            cost_input_long_context: tier.as_ref().and_then(|t| t.input),
+            cost_output_long_context: tier.as_ref().and_then(|t| t.output),
             // Modalities
             input_modalities: model.modalities.as_ref().map(|m| m.input.join(",")),
             output_modalities: model.modalities.as_ref().map(|m| m.output.join(",")),
@@ -187,12 +239,22 @@ impl ModelsDevRow {
 }
 
 /// Flatten the entire API response into rows for storage.
+///
+/// Rows are ordered by provider id, then model id, so the written table (and
+/// snapshots of it) does not churn with `HashMap` iteration order.
 pub fn flatten_response(response: &ModelsDevResponse) -> Vec<ModelsDevRow> {
+    let mut providers: Vec<&ModelsDevProvider> = response.values().collect();
+    providers.sort_by(|a, b| a.id.cmp(&b.id));
+
     let mut rows = Vec::new();
-    for provider in response.values() {
-        for model in provider.models.values() {
-            rows.push(ModelsDevRow::from_provider_model(provider, model));
-        }
+    for provider in providers {
+        let mut models: Vec<&ModelsDevModel> = provider.models.values().collect();
+        models.sort_by(|a, b| a.id.cmp(&b.id));
+        rows.extend(
+            models
+                .into_iter()
+                .map(|model| ModelsDevRow::from_provider_model(provider, model)),
+        );
     }
     rows
 }
@@ -237,6 +299,8 @@ mod tests {
         assert_eq!(model.reasoning, Some(false));
         assert_eq!(model.tool_call, Some(true));
         assert_eq!(model.structured_output, Some(true));
+        assert_eq!(model.knowledge, NaiveDate::from_ymd_opt(2024, 4, 1));
+        assert_eq!(model.release_date, NaiveDate::from_ymd_opt(2024, 5, 13));
         assert_eq!(model.limit.as_ref().unwrap().context, Some(128000));
         assert_eq!(
             model.modalities.as_ref().unwrap().input,
@@ -244,6 +308,35 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_cost_tiers() {
+        let cost: ModelsDevCost = serde_json::from_str(
+            r#"{
+                "input": 1.25,
+                "output": 10.0,
+                "reasoning": 10.0,
+                "context_over_200k": { "input": 2.5, "output": 15.0, "cache_read": 0.625 },
+                "context_over_128k": { "input": 2.0 }
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(
+            cost.tiers(),
+            vec![
+                ModelsDevCostTier {
+                    threshold: 128_000,
+                    input: Some(2.0),
+                    output: None,
+                },
+                ModelsDevCostTier {
+                    threshold: 200_000,
+                    input: Some(2.5),
+                    output: Some(15.0),
+                },
+            ]
+        );
+    }
+
     #[test]
     fn test_deserialize_provider() {
         let json = r#"{
@@ -289,9 +382,9 @@ mod tests {
             tool_call: Some(true),
             structured_output: Some(true),
             temperature: Some(true),
-            knowledge: Some("2024-04".into()),
-            release_date: Some("2024-05-13".into()),
-            last_updated: Some("2024-11-20".into()),
+            knowledge: NaiveDate::from_ymd_opt(2024, 4, 1),
+            release_date: NaiveDate::from_ymd_opt(2024, 5, 13),
+            last_updated: NaiveDate::from_ymd_opt(2024, 11, 20),
             open_weights: Some(false),
             status: None,
             limit: Some(ModelsDevLimits {
@@ -304,6 +397,7 @@ mod tests {
                 output: Some(10.0),
                 cache_read: Some(1.25),
                 cache_write: Some(3.75),
+                extra: HashMap::new(),
             }),
             modalities: Some(ModelsDevModalities {
                 input: vec!["text".into(), "image".into()],
@@ -420,4 +514,35 @@ mod tests {
             Some("AZURE_OPENAI_API_KEY,AZURE_OPENAI_ENDPOINT".into())
         );
     }
+
+    #[test]
+    fn test_flatten_response_sorted() {
+        let response: ModelsDevResponse = serde_json::from_str(
+            r#"{
+                "openai": {"id": "openai", "name": "OpenAI", "models": {
+                    "gpt-4o-mini": {"id": "gpt-4o-mini", "name": "GPT-4o mini"},
+                    "gpt-4o": {"id": "gpt-4o", "name": "GPT-4o"}
+                }},
+                "anthropic": {"id": "anthropic", "name": "Anthropic", "models": {
+                    "claude-sonnet-4": {"id": "claude-sonnet-4", "name": "Claude Sonnet 4"},
+                    "claude-haiku-4": {"id": "claude-haiku-4", "name": "Claude Haiku 4"}
+                }}
+            }"#,
+        )
+        .unwrap();
+
+        let ids: Vec<(String, String)> = flatten_response(&response)
+            .into_iter()
+            .map(|row| (row.provider_id, row.model_id))
+            .collect();
+        assert_eq!(
+            ids,
+            vec![
+                ("anthropic".into(), "claude-haiku-4".into()),
+                ("anthropic".into(), "claude-sonnet-4".into()),
+                ("openai".into(), "gpt-4o".into()),
+                ("openai".into(), "gpt-4o-mini".into()),
+            ]
+        );
+    }
 }
@@ -0,0 +1,108 @@
+//! Long-context pricing tiers from the models table.
+//!
+//! Some providers charge more for the whole request once the prompt exceeds
+//! a threshold (e.g. 200K tokens). models.dev publishes those prices per
+//! provider; this module turns them into multipliers on the base price, so
+//! they can be applied to AA prices, which carry no tiers. Keys are
+//! models.dev ids with the provider prefix stripped and lowercased, matching
+//! AA slugs as in the open view.
+
+use crate::error::{AppError, Result};
+use crate::sources::MODELS;
+use duckdb::Connection;
+use std::collections::HashMap;
+use std::path::Path;
+
+/// Price multipliers that apply once the prompt exceeds `threshold` tokens.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PriceTier {
+    pub threshold: u64,
+    pub input_multiplier: f64,
+    pub output_multiplier: f64,
+}
+
+impl PriceTier {
+    /// Whether a request with `input_tokens` of prompt is billed at this tier.
+    pub fn applies(&self, input_tokens: u64) -> bool {
+        input_tokens > self.threshold
+    }
+}
+
+/// Long-context tiers by lowercased model key.
+///
+/// When providers disagree, the lowest threshold and the highest
+/// multipliers win, so estimates err on the expensive side. A price
+/// missing from a tier keeps its base price. Empty when the models table is
+/// not cached.
+pub fn load_price_tiers(cache_dir: &Path) -> Result<HashMap<String, PriceTier>> {
+    if !MODELS.is_cached(cache_dir) {
+        return Ok(HashMap::new());
+    }
+
+    let conn = Connection::open_in_memory()
+        .map_err(|e| AppError::Cache(format!("DuckDB error: {}", e)))?;
+    let sql = format!(
+        "SELECT lower(regexp_replace(model_id, '^.*/', '')) AS key,
+            min(long_context_threshold),
+            max(coalesce(cost_input_long_context / nullif(cost_input, 0), 1)),
+            max(coalesce(cost_output_long_context / nullif(cost_output, 0), 1))
+        FROM read_parquet('{}')
+        WHERE long_context_threshold IS NOT NULL
+        GROUP BY key",
+        MODELS.cache_path(cache_dir).to_string_lossy()
+    );
+    let mut stmt = conn
+        .prepare(&sql)
+        .map_err(|e| AppError::Cache(format!("DuckDB error: {}", e)))?;
+    let rows = stmt
+        .query_map([], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                PriceTier {
+                    threshold: row.get::<_, i64>(1)?.max(0) as u64,
+                    input_multiplier: row.get(2)?,
+                    output_multiplier: row.get(3)?,
+                },
+            ))
+        })
+        .map_err(|e| AppError::Cache(format!("DuckDB error: {}", e)))?
+        .collect::<std::result::Result<HashMap<_, _>, _>>()
+        .map_err(|e| AppError::Cache(format!("DuckDB error: {}", e)))?;
+    Ok(rows)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_load_price_tiers() {
+        let temp_dir = TempDir::new().unwrap();
+        assert!(load_price_tiers(temp_dir.path()).unwrap().is_empty());
+
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute(
+            &format!(
+                "COPY (SELECT * FROM (VALUES
+                    ('google/gemini-2.5-pro', 1.25::DOUBLE, 10.0::DOUBLE, 200000::BIGINT, 2.5::DOUBLE, 15.0::DOUBLE),
+                    ('gemini-2.5-pro', 1.25, 10.0, 200000, 2.5, NULL),
+                    ('gpt-4o', 2.5, 10.0, NULL, NULL, NULL)
+                ) t(model_id, cost_input, cost_output, long_context_threshold,
+                    cost_input_long_context, cost_output_long_context)) TO '{}' (FORMAT PARQUET)",
+                temp_dir.path().join("models.parquet").to_string_lossy()
+            ),
+            [],
+        )
+        .unwrap();
+
+        let tiers = load_price_tiers(temp_dir.path()).unwrap();
+        assert_eq!(tiers.len(), 1);
+        let tier = tiers["gemini-2.5-pro"];
+        assert_eq!(tier.threshold, 200_000);
+        assert!((tier.input_multiplier - 2.0).abs() < 1e-9);
+        assert!((tier.output_multiplier - 1.5).abs() < 1e-9);
+        assert!(!tier.applies(200_000));
+        assert!(tier.applies(300_000));
+    }
+}
@@ -1,162 +1,211 @@
 //! Parquet schema for models data (from models.dev).
 
-use crate::schema::{Column, TableDef};
+use crate::schema::{Column, TableDef, TableLayout, LLM_DATA_TTL};
 
 /// Models table schema - models with capabilities from models.dev.
 /// Each row represents a model available from a specific provider with
 /// capabilities, context limits, and provider-specific pricing.
 pub const MODELS: TableDef = TableDef {
     name: "models",
-    command: "which-llm refresh",
+    command: "refresh",
     parquet_file: "models.parquet",
+    layout: TableLayout::File,
+    default_ttl: LLM_DATA_TTL,
     columns: &[
         // Provider identity
         Column {
             name: "provider_id",
             sql_type: "VARCHAR",
             nullable: false,
+            description: "models.dev provider id, e.g. openai",
         },
         Column {
             name: "provider_name",
             sql_type: "VARCHAR",
             nullable: false,
+            description: "Provider display name",
         },
         // Provider metadata
         Column {
             name: "provider_env",
             sql_type: "VARCHAR",
             nullable: true,
+            description: "Environment variables for the provider's API key, comma-separated",
         },
         Column {
             name: "provider_npm",
             sql_type: "VARCHAR",
             nullable: true,
+            description: "AI SDK npm package for the provider",
         },
         Column {
             name: "provider_api",
             sql_type: "VARCHAR",
             nullable: true,
+            description: "Provider API base URL",
         },
         Column {
             name: "provider_doc",
             sql_type: "VARCHAR",
             nullable: true,
+            description: "Provider documentation URL",
         },
         // Model identity
         Column {
             name: "model_id",
             sql_type: "VARCHAR",
             nullable: false,
+            description: "Model id at the provider; may carry a provider prefix (openai/gpt-4o)",
         },
         Column {
             name: "model_name",
             sql_type: "VARCHAR",
             nullable: false,
+            description: "Model display name at the provider",
         },
         Column {
             name: "family",
             sql_type: "VARCHAR",
             nullable: true,
+            description: "models.dev model family, e.g. gpt",
         },
         // Capabilities
         Column {
             name: "attachment",
             sql_type: "BOOLEAN",
             nullable: true,
+            description: "Accepts file attachments",
         },
         Column {
             name: "reasoning",
             sql_type: "BOOLEAN",
             nullable: true,
+            description: "Supports reasoning (thinking) output",
         },
         Column {
             name: "tool_call",
             sql_type: "BOOLEAN",
             nullable: true,
+            description: "Supports tool calling",
         },
         Column {
             name: "structured_output",
             sql_type: "BOOLEAN",
             nullable: true,
+            description: "Supports structured (JSON schema) output",
         },
         Column {
             name: "temperature",
             sql_type: "BOOLEAN",
             nullable: true,
+            description: "Accepts a temperature setting",
         },
         // Metadata
         Column {
             name: "knowledge",
-            sql_type: "VARCHAR",
+            sql_type: "DATE",
             nullable: true,
+            description: "Knowledge cutoff; month-only cutoffs map to the first of the month",
         },
         Column {
             name: "release_date",
-            sql_type: "VARCHAR",
+            sql_type: "DATE",
             nullable: true,
+            description: "Release date",
         },
         Column {
             name: "last_updated",
-            sql_type: "VARCHAR",
+            sql_type: "DATE",
             nullable: true,
+            description: "Date models.dev last updated the entry",
         },
         Column {
             name: "open_weights",
             sql_type: "BOOLEAN",
             nullable: true,
+            description: "Weights are openly available",
         },
         Column {
             name: "status",
             sql_type: "VARCHAR",
             nullable: true,
+            description: "Lifecycle status, e.g. deprecated; NULL while active",
         },
         // Limits
         Column {
             name: "context_window",
             sql_type: "BIGINT",
             nullable: true,
+            description: "Context window in tokens; higher is better",
         },
         Column {
             name: "max_input_tokens",
             sql_type: "BIGINT",
             nullable: true,
+            description: "Maximum input tokens per request",
         },
         Column {
             name: "max_output_tokens",
             sql_type: "BIGINT",
             nullable: true,
+            description: "Maximum output tokens per request",
         },
         // Cost (per million tokens)
         Column {
             name: "cost_input",
             sql_type: "DOUBLE",
             nullable: true,
+            description: "Provider USD per 1M input tokens; lower is better",
         },
         Column {
             name: "cost_output",
             sql_type: "DOUBLE",
             nullable: true,
+            description: "Provider USD per 1M output tokens; lower is better",
         },
         Column {
             name: "cost_cache_read",
             sql_type: "DOUBLE",
             nullable: true,
+            description: "Provider USD per 1M cached input tokens read; lower is better",
         },
         Column {
             name: "cost_cache_write",
             sql_type: "DOUBLE",
             nullable: true,
+            description: "Provider USD per 1M tokens written to the prompt cache; lower is better",
+        },
+        // Lowest long-context tier
+        Column {
+            name: "long_context_threshold",
+            sql_type: "BIGINT",
+            nullable: true,
+            description: "Prompt tokens above which the long-context prices apply to the whole request",
+        },
+        Column {
+            name: "cost_input_long_context",
+            sql_type: "DOUBLE",
+            nullable: true,
+            description: "Provider USD per 1M input tokens once the prompt exceeds long_context_threshold; lower is better",
+        },
+        Column {
+            name: "cost_output_long_context",
+            sql_type: "DOUBLE",
+            nullable: true,
+            description: "Provider USD per 1M output tokens once the prompt exceeds long_context_threshold; lower is better",
         },
         // Modalities (comma-separated)
         Column {
             name: "input_modalities",
             sql_type: "VARCHAR",
             nullable: true,
+            description: "Accepted input modalities, comma-separated (text, image, audio, ...)",
         },
         Column {
             name: "output_modalities",
             sql_type: "VARCHAR",
             nullable: true,
+            description: "Produced output modalities, comma-separated",
         },
     ],
 };
@@ -0,0 +1,87 @@
+//! Strict mode checks.
+//!
+//! With `--strict`, commands fail instead of acting on stale or partial data,
+//! so CI pipelines never make decisions from an incomplete cache.
+
+use crate::config::Config;
+use crate::error::{AppError, Result};
+use crate::schema::{TableDef, TableLayout};
+use chrono::{DateTime, Utc};
+use std::path::Path;
+
+/// Fail if any of the given single-file tables is older than its TTL
+/// (see [`Config::table_ttl`]).
+///
+/// Partitioned and glob tables accumulate over time and are not checked.
+pub fn ensure_fresh(tables: &[&TableDef], cache_dir: &Path) -> Result<()> {
+    let config = Config::load()?;
+    for table in tables {
+        if table.layout != TableLayout::File {
+            continue;
+        }
+
+        let path = table.cache_path(cache_dir);
+        let modified = std::fs::metadata(&path)
+            .and_then(|m| m.modified())
+            .map_err(|_| {
+                AppError::Strict(format!(
+                    "table '{}' is not cached. Run '{}' first.",
+                    table.name,
+                    table.command_line()
+                ))
+            })?;
+
+        let ttl = config.table_ttl(table)?;
+        let age = Utc::now().signed_duration_since(DateTime::<Utc>::from(modified));
+        if age.to_std().is_ok_and(|age| age >= ttl) {
+            return Err(AppError::Strict(format!(
+                "table '{}' is {} hours old (limit {}). Run '{}' to refresh it.",
+                table.name,
+                age.num_hours(),
+                ttl.as_secs() / 3600,
+                table.command_line()
+            )));
+        }
+    }
+
+    Ok(())
+}
+
+/// Fail if any refresh step failed.
+pub fn ensure_complete(failures: &[String]) -> Result<()> {
+    if failures.is_empty() {
+        return Ok(());
+    }
+
+    Err(AppError::Strict(format!(
+        "{} source(s) failed to refresh: {}",
+        failures.len(),
+        failures.join("; ")
+    )))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::schema::{BENCHMARKS, LLMS_HISTORY};
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_ensure_fresh() {
+        let temp_dir = TempDir::new().unwrap();
+
+        let err = ensure_fresh(&[&BENCHMARKS], temp_dir.path()).unwrap_err();
+        assert!(err.to_string().contains("not cached"));
+
+        std::fs::write(BENCHMARKS.cache_path(temp_dir.path()), b"data").unwrap();
+        assert!(ensure_fresh(&[&BENCHMARKS, &LLMS_HISTORY], temp_dir.path()).is_ok());
+    }
+
+    #[test]
+    fn test_ensure_complete() {
+        assert!(ensure_complete(&[]).is_ok());
+
+        let err = ensure_complete(&["media tables: timeout".to_string()]).unwrap_err();
+        assert!(matches!(err, AppError::Strict(msg) if msg.contains("media tables: timeout")));
+    }
+}
@@ -0,0 +1,201 @@
+//! Date parsing utilities.
+//!
+//! Upstream sources mix full dates ("2024-05-13"), year-months ("2024-04")
+//! and occasionally timestamps. Everything is normalized to `NaiveDate`;
+//! year-month values map to the first day of the month.
+//!
+//! User-supplied cutoffs may also be relative ("90d") or quarters ("2024-Q3").
+
+use chrono::{DateTime, Days, Months, NaiveDate};
+use serde::{Deserialize, Deserializer};
+
+/// Parse a date leniently, returning `None` for malformed values.
+///
+/// Supported formats:
+/// - Full dates: "2024-05-13"
+/// - Timestamps: "2024-05-13T10:00:00Z" (date part is kept)
+/// - Year-months: "2024-04" -> 2024-04-01
+/// - Years: "2024" -> 2024-01-01
+///
+/// # Examples
+///
+/// ```
+/// use chrono::NaiveDate;
+/// use which_llm::utils::parse_date;
+///
+/// assert_eq!(parse_date("2024-05-13"), NaiveDate::from_ymd_opt(2024, 5, 13));
+/// assert_eq!(parse_date("2024-04"), NaiveDate::from_ymd_opt(2024, 4, 1));
+/// assert_eq!(parse_date("soon"), None);
+/// ```
+pub fn parse_date(s: &str) -> Option<NaiveDate> {
+    let s = s.trim();
+
+    if let Ok(date) = NaiveDate::parse_from_str(s, "%Y-%m-%d") {
+        return Some(date);
+    }
+
+    if let Ok(datetime) = DateTime::parse_from_rfc3339(s) {
+        return Some(datetime.date_naive());
+    }
+
+    // Timestamps without a timezone: keep the leading date
+    if s.len() > 10 {
+        if let Some(date) = s
+            .get(..10)
+            .and_then(|d| NaiveDate::parse_from_str(d, "%Y-%m-%d").ok())
+        {
+            return Some(date);
+        }
+    }
+
+    // Year-month
+    if let Some((year, month)) = s.split_once('-') {
+        if year.len() == 4 {
+            if let (Ok(year), Ok(month)) = (year.parse::<i32>(), month.parse::<u32>()) {
+                return NaiveDate::from_ymd_opt(year, month, 1);
+            }
+        }
+        return None;
+    }
+
+    // Year only
+    if s.len() == 4 {
+        if let Ok(year) = s.parse::<i32>() {
+            return NaiveDate::from_ymd_opt(year, 1, 1);
+        }
+    }
+
+    None
+}
+
+/// Parse a user-supplied date cutoff relative to `today`.
+///
+/// Accepts everything [`parse_date`] does, plus:
+/// - Relative ages: "90d", "6w", "3m", "1y" (days, weeks, months, years ago)
+/// - Quarters: "2024-Q3" or "2024Q3" -> first day of the quarter
+///
+/// # Examples
+///
+/// ```
+/// use chrono::NaiveDate;
+/// use which_llm::utils::parse_relative_date;
+///
+/// let today = NaiveDate::from_ymd_opt(2025, 4, 10).unwrap();
+/// assert_eq!(parse_relative_date("10d", today), NaiveDate::from_ymd_opt(2025, 3, 31));
+/// assert_eq!(parse_relative_date("2024-Q3", today), NaiveDate::from_ymd_opt(2024, 7, 1));
+/// ```
+pub fn parse_relative_date(s: &str, today: NaiveDate) -> Option<NaiveDate> {
+    let s = s.trim();
+
+    if let Some((year, quarter)) = s
+        .split_once("-Q")
+        .or_else(|| s.split_once("-q"))
+        .or_else(|| s.split_once(['Q', 'q']))
+    {
+        if year.len() == 4 {
+            let year = year.parse::<i32>().ok()?;
+            let quarter = quarter
+                .parse::<u32>()
+                .ok()
+                .filter(|q| (1..=4).contains(q))?;
+            return NaiveDate::from_ymd_opt(year, (quarter - 1) * 3 + 1, 1);
+        }
+    }
+
+    if let Some(unit) = s.chars().last().filter(|c| c.is_ascii_alphabetic()) {
+        if let Ok(n) = s[..s.len() - 1].parse::<u32>() {
+            return match unit.to_ascii_lowercase() {
+                'd' => today.checked_sub_days(Days::new(n.into())),
+                'w' => today.checked_sub_days(Days::new(u64::from(n) * 7)),
+                'm' => today.checked_sub_months(Months::new(n)),
+                'y' => today.checked_sub_months(Months::new(n.checked_mul(12)?)),
+                _ => None,
+            };
+        }
+    }
+
+    parse_date(s)
+}
+
+/// Deserialize an optional date leniently.
+///
+/// Malformed or non-string values become `None` instead of failing the whole
+/// response. Use with `#[serde(default, deserialize_with = "...")]`.
+pub fn deserialize_date<'de, D>(deserializer: D) -> Result<Option<NaiveDate>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let value = Option::<serde_json::Value>::deserialize(deserializer)?;
+    Ok(value.as_ref().and_then(|v| v.as_str()).and_then(parse_date))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(y: i32, m: u32, d: u32) -> Option<NaiveDate> {
+        NaiveDate::from_ymd_opt(y, m, d)
+    }
+
+    #[test]
+    fn test_full_dates() {
+        assert_eq!(parse_date("2024-05-13"), date(2024, 5, 13));
+        assert_eq!(parse_date(" 2024-05-13 "), date(2024, 5, 13));
+    }
+
+    #[test]
+    fn test_timestamps() {
+        assert_eq!(parse_date("2024-05-13T10:00:00Z"), date(2024, 5, 13));
+        assert_eq!(parse_date("2024-05-13T10:00:00"), date(2024, 5, 13));
+    }
+
+    #[test]
+    fn test_partial_dates() {
+        assert_eq!(parse_date("2024-04"), date(2024, 4, 1));
+        assert_eq!(parse_date("2025"), date(2025, 1, 1));
+    }
+
+    #[test]
+    fn test_malformed() {
+        assert_eq!(parse_date(""), None);
+        assert_eq!(parse_date("unknown"), None);
+        assert_eq!(parse_date("2024-13"), None);
+        assert_eq!(parse_date("24-04"), None);
+    }
+
+    #[test]
+    fn test_relative_dates() {
+        let today = NaiveDate::from_ymd_opt(2025, 3, 31).unwrap();
+        assert_eq!(parse_relative_date("90d", today), date(2024, 12, 31));
+        assert_eq!(parse_relative_date("2w", today), date(2025, 3, 17));
+        assert_eq!(parse_relative_date("1m", today), date(2025, 2, 28));
+        assert_eq!(parse_relative_date("1y", today), date(2024, 3, 31));
+        assert_eq!(parse_relative_date("2024-Q3", today), date(2024, 7, 1));
+        assert_eq!(parse_relative_date("2024q4", today), date(2024, 10, 1));
+        assert_eq!(parse_relative_date("2024-06-15", today), date(2024, 6, 15));
+        assert_eq!(parse_relative_date("2024-Q5", today), None);
+        assert_eq!(parse_relative_date("90x", today), None);
+        assert_eq!(parse_relative_date("soon", today), None);
+    }
+
+    #[test]
+    fn test_deserialize_lenient() {
+        #[derive(Deserialize)]
+        struct Row {
+            #[serde(default, deserialize_with = "deserialize_date")]
+            date: Option<NaiveDate>,
+        }
+
+        let row: Row = serde_json::from_str(r#"{"date": "2025-12"}"#).unwrap();
+        assert_eq!(row.date, date(2025, 12, 1));
+
+        let row: Row = serde_json::from_str(r#"{"date": "not a date"}"#).unwrap();
+        assert_eq!(row.date, None);
+
+        let row: Row = serde_json::from_str(r#"{"date": 2024}"#).unwrap();
+        assert_eq!(row.date, None);
+
+        let row: Row = serde_json::from_str("{}").unwrap();
+        assert_eq!(row.date, None);
+    }
+}
@@ -0,0 +1,996 @@
+//! Filter expression mini-language over `LlmModel` fields.
+//!
+//! A lightweight alternative to SQL for the common case:
+//!
+//! ```text
+//! intelligence>50 && price<3 && (creator==OpenAI || creator==Anthropic)
+//! ```
+//!
+//! - Comparisons: `>`, `>=`, `<`, `<=`, `==` (or `=`), `!=`, and `~`
+//!   (case-insensitive substring match on text fields)
+//! - Logic: `&&`, `||`, `!`, and parentheses
+//! - A bare field name is true when the field has a value
+//! - A bare capability (`tool_call`, `reasoning`, `open_weights`, ...) is
+//!   true when the model has it at some provider in the `models` table
+//! - Comparisons against a missing value are false
+//! - `release_date` accepts relative cutoffs such as `90d` or `2024-Q3`
+
+use crate::error::{AppError, Result};
+use crate::models::{LlmModel, BENCHMARK_COLUMNS};
+use crate::policy::PolicyCheck;
+use crate::sources::models_dev::capabilities::{capable_models, TokenLimits, CAPABILITIES};
+use crate::utils::parse_relative_date;
+use chrono::{NaiveDate, Utc};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::Path;
+use std::str::FromStr;
+
+/// Accessor for a numeric field.
+type NumberField = fn(&LlmModel) -> Option<f64>;
+
+/// Accessor for a text field.
+type TextField = fn(&LlmModel) -> Option<&str>;
+
+/// Numeric fields available in filters.
+const NUMBER_FIELDS: &[(&str, NumberField)] = &[
+    ("intelligence", |m| m.intelligence),
+    ("coding", |m| m.coding),
+    ("math", |m| m.math),
+    ("mmlu_pro", |m| m.mmlu_pro),
+    ("gpqa", |m| m.gpqa),
+    ("hle", |m| m.hle),
+    ("livecodebench", |m| m.livecodebench),
+    ("scicode", |m| m.scicode),
+    ("math_500", |m| m.math_500),
+    ("aime", |m| m.aime),
+    ("input_price", |m| m.input_price),
+    ("output_price", |m| m.output_price),
+    ("price", |m| m.price),
+    ("tps", |m| m.tps),
+    ("latency", |m| m.latency),
+    ("intelligence_per_dollar", |m| m.intelligence_per_dollar()),
+    ("coding_per_dollar", |m| m.coding_per_dollar()),
+    ("coverage", |m| Some(m.coverage() as f64)),
+];
+
+/// Text fields available in filters.
+const TEXT_FIELDS: &[(&str, TextField)] = &[
+    ("name", |m| Some(m.name.as_str())),
+    ("slug", |m| Some(m.slug.as_str())),
+    ("creator", |m| Some(m.creator.as_str())),
+    ("creator_slug", |m| m.creator_slug.as_deref()),
+    ("family", |m| Some(m.family())),
+];
+
+/// Comparison operator.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Op {
+    Gt,
+    Ge,
+    Lt,
+    Le,
+    Eq,
+    Ne,
+    Contains,
+}
+
+impl Op {
+    fn symbol(self) -> &'static str {
+        match self {
+            Op::Gt => ">",
+            Op::Ge => ">=",
+            Op::Lt => "<",
+            Op::Le => "<=",
+            Op::Eq => "==",
+            Op::Ne => "!=",
+            Op::Contains => "~",
+        }
+    }
+}
+
+/// A field reference resolved against `LlmModel`.
+#[derive(Clone, Copy)]
+enum Field {
+    Number(NumberField),
+    Text(TextField),
+    ReleaseDate,
+    /// A capability column of the `models` table.
+    Capability(&'static str),
+}
+
+/// Parsed filter expression.
+#[derive(Clone)]
+enum Expr {
+    And(Box<Expr>, Box<Expr>),
+    Or(Box<Expr>, Box<Expr>),
+    Not(Box<Expr>),
+    Present(Field),
+    Number(NumberField, Op, f64),
+    Text(TextField, Op, String),
+    Date(Op, NaiveDate),
+}
+
+/// A compiled filter expression.
+#[derive(Clone)]
+pub struct Filter {
+    expr: Expr,
+    /// Model keys having each capability the expression names.
+    capable: BTreeMap<&'static str, HashSet<String>>,
+}
+
+impl Filter {
+    /// Parse a filter expression.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use which_llm::models::LlmModel;
+    /// use which_llm::utils::Filter;
+    ///
+    /// let filter = Filter::parse("intelligence>50 && price<3").unwrap();
+    /// let model = LlmModel {
+    ///     intelligence: Some(55.0),
+    ///     price: Some(2.0),
+    ///     ..Default::default()
+    /// };
+    /// assert!(filter.matches(&model));
+    /// ```
+    pub fn parse(input: &str) -> Result<Self> {
+        let tokens = tokenize(input)?;
+        let mut parser = FilterParser { tokens, pos: 0 };
+        let expr = parser.parse_or()?;
+        if let Some(token) = parser.peek() {
+            return Err(invalid(format!("unexpected '{}'", token)));
+        }
+        Ok(Self {
+            expr,
+            capable: BTreeMap::new(),
+        })
+    }
+
+    /// Capabilities the expression names, in order of first use.
+    pub fn capabilities(&self) -> Vec<&'static str> {
+        let mut names = Vec::new();
+        collect_capabilities(&self.expr, &mut names);
+        names
+    }
+
+    /// Look up the models having each capability the expression names in
+    /// the cached `models` table. No lookup is done when it names none.
+    pub fn load_capabilities(self, cache_dir: &Path) -> Result<Self> {
+        let capable = self
+            .capabilities()
+            .into_iter()
+            .map(|name| {
+                let keys = capable_models(cache_dir, &[name.to_string()])?;
+                Ok((name, keys))
+            })
+            .collect::<Result<_>>()?;
+        Ok(self.with_capabilities(capable))
+    }
+
+    /// Use already known capable model keys (lowercased model ids without
+    /// provider prefix). A capability without keys matches no model.
+    pub fn with_capabilities(self, capable: BTreeMap<&'static str, HashSet<String>>) -> Self {
+        Self { capable, ..self }
+    }
+
+    /// Check whether a model satisfies the filter.
+    pub fn matches(&self, model: &LlmModel) -> bool {
+        eval(&self.expr, model, &self.capable)
+    }
+}
+
+/// Model selection options shared by commands that work on LLM listings.
+#[derive(Clone, Default)]
+pub struct ModelSelection {
+    /// Only keep models with these lowercased slugs (from `--input-modality`
+    /// and `--output-modality`).
+    pub modal: Option<HashSet<String>>,
+    /// Filter expression models must satisfy.
+    pub filter: Option<Filter>,
+    /// Only keep models released on or after this date.
+    pub released_after: Option<NaiveDate>,
+    /// Drop models whose slug or name contains any of these (case-insensitive).
+    pub exclude: Vec<String>,
+    /// Only keep models with a score for at least this many benchmarks.
+    pub min_coverage: Option<usize>,
+    /// Keep deprecated and retired models (dropped by default).
+    pub include_retired: bool,
+    /// Only keep models with these lowercased slugs (from `--tag`).
+    pub tagged: Option<HashSet<String>>,
+    /// Only keep models the policy allows (from `--policy`).
+    pub policy: Option<PolicyCheck>,
+    /// Only keep models whose context window is at least this many tokens.
+    pub min_context: Option<u64>,
+    /// Only keep models that can output at least this many tokens.
+    pub min_output: Option<u64>,
+    /// Token limits by lowercased slug, from the models table; a model
+    /// missing here fails either bound.
+    pub token_limits: HashMap<String, TokenLimits>,
+}
+
+impl ModelSelection {
+    /// Keep only the models that satisfy every selection option.
+    pub fn apply(&self, models: Vec<LlmModel>) -> Vec<LlmModel> {
+        models
+            .into_iter()
+            .filter(|m| {
+                self.modal
+                    .as_ref()
+                    .map_or(true, |slugs| slugs.contains(&m.slug.to_lowercase()))
+            })
+            .filter(|m| self.filter.as_ref().map_or(true, |f| f.matches(m)))
+            .filter(|m| {
+                self.released_after
+                    .map_or(true, |cutoff| m.release_date.is_some_and(|d| d >= cutoff))
+            })
+            .filter(|m| !self.is_excluded(m))
+            .filter(|m| self.min_coverage.map_or(true, |min| m.coverage() >= min))
+            .filter(|m| self.include_retired || m.status.is_none())
+            .filter(|m| {
+                self.tagged
+                    .as_ref()
+                    .map_or(true, |slugs| slugs.contains(&m.slug.to_lowercase()))
+            })
+            .filter(|m| self.policy.as_ref().map_or(true, |p| p.allows(m)))
+            .filter(|m| self.fits_tokens(m))
+            .collect()
+    }
+
+    /// Whether the model's known token limits meet `min_context` and
+    /// `min_output`.
+    fn fits_tokens(&self, model: &LlmModel) -> bool {
+        if self.min_context.is_none() && self.min_output.is_none() {
+            return true;
+        }
+        let limits = self
+            .token_limits
+            .get(&model.slug.to_lowercase())
+            .copied()
+            .unwrap_or_default();
+        let fits = |limit: Option<u64>, min: Option<u64>| {
+            min.map_or(true, |min| limit.is_some_and(|limit| limit >= min))
+        };
+        fits(limits.context_window, self.min_context)
+            && fits(limits.max_output_tokens, self.min_output)
+    }
+
+    /// Benchmark columns to read for a command that displays `columns`.
+    ///
+    /// Returns `None` (read everything) when a filter expression is set,
+    /// since it may reference any field. `exclude` only needs the identity
+    /// columns, which are always read; `min_coverage` needs every benchmark
+    /// and a policy price limit needs `price`.
+    pub fn projection(&self, columns: &[&'static str]) -> Option<Vec<&'static str>> {
+        if self.filter.is_some() {
+            return None;
+        }
+        let mut columns = columns.to_vec();
+        if self.released_after.is_some() {
+            columns.push("release_date");
+        }
+        if self
+            .policy
+            .as_ref()
+            .is_some_and(|p| p.policy().max_price.is_some())
+            && !columns.contains(&"price")
+        {
+            columns.push("price");
+        }
+        if self.min_coverage.is_some() {
+            for column in BENCHMARK_COLUMNS {
+                if !columns.contains(column) {
+                    columns.push(column);
+                }
+            }
+        }
+        Some(columns)
+    }
+
+    /// Check whether a model matches one of the `exclude` patterns.
+    fn is_excluded(&self, model: &LlmModel) -> bool {
+        if self.exclude.is_empty() {
+            return false;
+        }
+        let slug = model.slug.to_lowercase();
+        let name = model.name.to_lowercase();
+        self.exclude.iter().any(|pattern| {
+            let pattern = pattern.to_lowercase();
+            slug.contains(&pattern) || name.contains(&pattern)
+        })
+    }
+}
+
+impl FromStr for Filter {
+    type Err = AppError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Self::parse(s)
+    }
+}
+
+fn invalid(message: String) -> AppError {
+    AppError::Query(format!("Invalid filter: {}", message))
+}
+
+fn eval(expr: &Expr, model: &LlmModel, capable: &BTreeMap<&'static str, HashSet<String>>) -> bool {
+    match expr {
+        Expr::And(a, b) => eval(a, model, capable) && eval(b, model, capable),
+        Expr::Or(a, b) => eval(a, model, capable) || eval(b, model, capable),
+        Expr::Not(e) => !eval(e, model, capable),
+        Expr::Present(Field::Number(get)) => get(model).is_some(),
+        Expr::Present(Field::Text(get)) => get(model).is_some_and(|v| !v.is_empty()),
+        Expr::Present(Field::ReleaseDate) => model.release_date.is_some(),
+        Expr::Present(Field::Capability(name)) => capable
+            .get(name)
+            .is_some_and(|keys| keys.contains(&model.slug.to_lowercase())),
+        Expr::Number(get, op, value) => get(model).is_some_and(|v| compare(op, &v, value)),
+        Expr::Text(get, op, value) => get(model).is_some_and(|v| match op {
+            Op::Contains => v.to_lowercase().contains(&value.to_lowercase()),
+            Op::Eq => v.eq_ignore_ascii_case(value),
+            Op::Ne => !v.eq_ignore_ascii_case(value),
+            _ => compare(op, &v.to_lowercase(), &value.to_lowercase()),
+        }),
+        Expr::Date(op, value) => model.release_date.is_some_and(|d| compare(op, &d, value)),
+    }
+}
+
+fn collect_capabilities(expr: &Expr, names: &mut Vec<&'static str>) {
+    match expr {
+        Expr::And(a, b) | Expr::Or(a, b) => {
+            collect_capabilities(a, names);
+            collect_capabilities(b, names);
+        }
+        Expr::Not(e) => collect_capabilities(e, names),
+        Expr::Present(Field::Capability(name)) if !names.contains(name) => names.push(*name),
+        _ => {}
+    }
+}
+
+fn compare<T: PartialOrd>(op: &Op, a: &T, b: &T) -> bool {
+    match op {
+        Op::Gt => a > b,
+        Op::Ge => a >= b,
+        Op::Lt => a < b,
+        Op::Le => a <= b,
+        Op::Eq => a == b,
+        Op::Ne => a != b,
+        Op::Contains => false,
+    }
+}
+
+/// Whether `value` is known and satisfies `bound`; no bound always passes.
+///
+/// Shared by the flag filters of `llms` and `recommend`, where a bound on a
+/// metric drops models without a value for it.
+pub fn within(value: Option<f64>, bound: Option<f64>, ok: fn(f64, f64) -> bool) -> bool {
+    match bound {
+        Some(bound) => value.is_some_and(|v| ok(v, bound)),
+        None => true,
+    }
+}
+
+/// Look up a numeric field by name (case-insensitive).
+pub(crate) fn number_field(name: &str) -> Option<NumberField> {
+    let lower = name.to_lowercase();
+    NUMBER_FIELDS
+        .iter()
+        .find(|(n, _)| *n == lower)
+        .map(|(_, get)| *get)
+}
+
+/// Names of the numeric fields, in declaration order.
+pub(crate) fn number_field_names() -> Vec<&'static str> {
+    NUMBER_FIELDS.iter().map(|(n, _)| *n).collect()
+}
+
+/// Look up a text field by name (case-insensitive).
+pub(crate) fn text_field(name: &str) -> Option<TextField> {
+    let lower = name.to_lowercase();
+    TEXT_FIELDS
+        .iter()
+        .find(|(n, _)| *n == lower)
+        .map(|(_, get)| *get)
+}
+
+/// Names of the text fields, in declaration order.
+pub(crate) fn text_field_names() -> Vec<&'static str> {
+    TEXT_FIELDS.iter().map(|(n, _)| *n).collect()
+}
+
+/// A field a model list can be sorted by.
+#[derive(Clone, Copy)]
+pub(crate) enum SortField {
+    Number(NumberField),
+    ReleaseDate,
+    /// Read from the `models` table, which the caller loads.
+    ContextWindow,
+}
+
+impl SortField {
+    /// Whether sorting needs the `models` table.
+    pub(crate) fn reads_models(self) -> bool {
+        matches!(self, SortField::ContextWindow)
+    }
+}
+
+/// Look up a sort field by name (case-insensitive): any numeric field,
+/// `release_date` or `context_window`.
+pub(crate) fn sort_field(name: &str) -> Option<SortField> {
+    match name.to_lowercase().as_str() {
+        "release_date" => Some(SortField::ReleaseDate),
+        "context_window" => Some(SortField::ContextWindow),
+        other => number_field(other).map(SortField::Number),
+    }
+}
+
+/// Names of the sort fields.
+pub(crate) fn sort_field_names() -> Vec<&'static str> {
+    let mut names = number_field_names();
+    names.extend(["release_date", "context_window"]);
+    names
+}
+
+fn resolve_field(name: &str) -> Result<Field> {
+    let lower = name.to_lowercase();
+    if let Some(get) = number_field(&lower) {
+        return Ok(Field::Number(get));
+    }
+    if let Some(get) = text_field(&lower) {
+        return Ok(Field::Text(get));
+    }
+    if lower == "release_date" {
+        return Ok(Field::ReleaseDate);
+    }
+    if let Some(capability) = CAPABILITIES.iter().find(|c| **c == lower) {
+        return Ok(Field::Capability(capability));
+    }
+
+    let known: Vec<&str> = NUMBER_FIELDS
+        .iter()
+        .map(|(n, _)| *n)
+        .chain(TEXT_FIELDS.iter().map(|(n, _)| *n))
+        .chain(std::iter::once("release_date"))
+        .chain(CAPABILITIES.iter().copied())
+        .collect();
+    Err(invalid(format!(
+        "unknown field '{}'. Available fields: {}",
+        name,
+        known.join(", ")
+    )))
+}
+
+/// Lexical token.
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Word(String),
+    Quoted(String),
+    Op(Op),
+    And,
+    Or,
+    Not,
+    LParen,
+    RParen,
+}
+
+impl std::fmt::Display for Token {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Token::Word(w) => write!(f, "{}", w),
+            Token::Quoted(q) => write!(f, "\"{}\"", q),
+            Token::Op(op) => write!(f, "{}", op.symbol()),
+            Token::And => write!(f, "&&"),
+            Token::Or => write!(f, "||"),
+            Token::Not => write!(f, "!"),
+            Token::LParen => write!(f, "("),
+            Token::RParen => write!(f, ")"),
+        }
+    }
+}
+
+fn tokenize(input: &str) -> Result<Vec<Token>> {
+    let chars: Vec<char> = input.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        let next = chars.get(i + 1).copied();
+        match c {
+            c if c.is_whitespace() => i += 1,
+            '(' => {
+                tokens.push(Token::LParen);
+                i += 1;
+            }
+            ')' => {
+                tokens.push(Token::RParen);
+                i += 1;
+            }
+            '&' if next == Some('&') => {
+                tokens.push(Token::And);
+                i += 2;
+            }
+            '|' if next == Some('|') => {
+                tokens.push(Token::Or);
+                i += 2;
+            }
+            '!' if next == Some('=') => {
+                tokens.push(Token::Op(Op::Ne));
+                i += 2;
+            }
+            '!' => {
+                tokens.push(Token::Not);
+                i += 1;
+            }
+            '>' | '<' | '=' => {
+                let op = match (c, next) {
+                    ('>', Some('=')) => Op::Ge,
+                    ('<', Some('=')) => Op::Le,
+                    ('=', Some('=')) => Op::Eq,
+                    ('>', _) => Op::Gt,
+                    ('<', _) => Op::Lt,
+                    _ => Op::Eq,
+                };
+                tokens.push(Token::Op(op));
+                i += if next == Some('=') { 2 } else { 1 };
+            }
+            '~' => {
+                tokens.push(Token::Op(Op::Contains));
+                i += 1;
+            }
+            '"' | '\'' => {
+                let end = chars[i + 1..]
+                    .iter()
+                    .position(|&ch| ch == c)
+                    .ok_or_else(|| invalid("unterminated string".into()))?;
+                tokens.push(Token::Quoted(chars[i + 1..i + 1 + end].iter().collect()));
+                i += end + 2;
+            }
+            _ => {
+                let start = i;
+                while i < chars.len()
+                    && !chars[i].is_whitespace()
+                    && !"()&|!<>=~\"'".contains(chars[i])
+                {
+                    i += 1;
+                }
+                if start == i {
+                    return Err(invalid(format!("unexpected '{}'", c)));
+                }
+                tokens.push(Token::Word(chars[start..i].iter().collect()));
+            }
+        }
+    }
+
+    Ok(tokens)
+}
+
+/// Recursive descent parser over filter tokens.
+struct FilterParser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl FilterParser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn advance(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        token
+    }
+
+    fn parse_or(&mut self) -> Result<Expr> {
+        let mut left = self.parse_and()?;
+        while self.peek() == Some(&Token::Or) {
+            self.pos += 1;
+            left = Expr::Or(Box::new(left), Box::new(self.parse_and()?));
+        }
+        Ok(left)
+    }
+
+    fn parse_and(&mut self) -> Result<Expr> {
+        let mut left = self.parse_unary()?;
+        while self.peek() == Some(&Token::And) {
+            self.pos += 1;
+            left = Expr::And(Box::new(left), Box::new(self.parse_unary()?));
+        }
+        Ok(left)
+    }
+
+    fn parse_unary(&mut self) -> Result<Expr> {
+        match self.advance() {
+            Some(Token::Not) => Ok(Expr::Not(Box::new(self.parse_unary()?))),
+            Some(Token::LParen) => {
+                let expr = self.parse_or()?;
+                match self.advance() {
+                    Some(Token::RParen) => Ok(expr),
+                    _ => Err(invalid("missing ')'".into())),
+                }
+            }
+            Some(Token::Word(name)) => self.parse_comparison(&name),
+            Some(token) => Err(invalid(format!("unexpected '{}'", token))),
+            None => Err(invalid("unexpected end of expression".into())),
+        }
+    }
+
+    fn parse_comparison(&mut self, name: &str) -> Result<Expr> {
+        let field = resolve_field(name)?;
+        let op = match self.peek() {
+            Some(Token::Op(op)) => *op,
+            _ => return Ok(Expr::Present(field)),
+        };
+        self.pos += 1;
+
+        let value = match self.advance() {
+            Some(Token::Word(v)) | Some(Token::Quoted(v)) => v,
+            _ => return Err(invalid(format!("missing value after '{}'", name))),
+        };
+
+        match field {
+            Field::Number(get) => {
+                if op == Op::Contains {
+                    return Err(invalid(format!(
+                        "'~' only applies to text fields, not '{}'",
+                        name
+                    )));
+                }
+                let number = value
+                    .parse::<f64>()
+                    .map_err(|_| invalid(format!("'{}' is not a number", value)))?;
+                Ok(Expr::Number(get, op, number))
+            }
+            Field::Text(get) => Ok(Expr::Text(get, op, value)),
+            Field::Capability(_) => Err(invalid(format!(
+                "'{}' is a capability; use it bare ({}) or negated (!{})",
+                name, name, name
+            ))),
+            Field::ReleaseDate => {
+                if op == Op::Contains {
+                    return Err(invalid(
+                        "'~' only applies to text fields, not 'release_date'".into(),
+                    ));
+                }
+                let date = parse_relative_date(&value, Utc::now().date_naive())
+                    .ok_or_else(|| invalid(format!("'{}' is not a date", value)))?;
+                Ok(Expr::Date(op, date))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::policy::Policy;
+
+    fn model(name: &str, creator: &str, intelligence: Option<f64>, price: Option<f64>) -> LlmModel {
+        LlmModel {
+            name: name.to_string(),
+            slug: name.to_lowercase().replace(' ', "-"),
+            creator: creator.to_string(),
+            intelligence,
+            price,
+            release_date: NaiveDate::from_ymd_opt(2025, 6, 1),
+            ..Default::default()
+        }
+    }
+
+    fn matching(filter: &str, models: &[LlmModel]) -> Vec<String> {
+        let filter = Filter::parse(filter).unwrap();
+        models
+            .iter()
+            .filter(|m| filter.matches(m))
+            .map(|m| m.name.clone())
+            .collect()
+    }
+
+    fn models() -> Vec<LlmModel> {
+        vec![
+            model("GPT-5", "OpenAI", Some(68.0), Some(3.4)),
+            model("Claude 4.5 Sonnet", "Anthropic", Some(63.0), Some(6.0)),
+            model("Small Model", "Acme", Some(20.0), Some(0.1)),
+            model("Unscored", "Acme", None, None),
+        ]
+    }
+
+    #[test]
+    fn test_model_selection_modalities() {
+        // Keys of audio-in models, as modal_models returns them
+        let audio: HashSet<String> = ["gpt-5", "small-model"].map(String::from).into();
+        let selection = ModelSelection {
+            modal: Some(audio),
+            filter: Some(Filter::parse("intelligence>30").unwrap()),
+            ..Default::default()
+        };
+        let names: Vec<String> = selection
+            .apply(models())
+            .into_iter()
+            .map(|m| m.name)
+            .collect();
+        assert_eq!(names, vec!["GPT-5"]);
+    }
+
+    #[test]
+    fn test_numeric_comparisons() {
+        assert_eq!(
+            matching("intelligence>60", &models()),
+            vec!["GPT-5", "Claude 4.5 Sonnet"]
+        );
+        assert_eq!(matching("price <= 0.1", &models()), vec!["Small Model"]);
+        assert_eq!(
+            matching("intelligence != 20", &models()),
+            vec!["GPT-5", "Claude 4.5 Sonnet"]
+        );
+    }
+
+    #[test]
+    fn test_logic_and_grouping() {
+        assert_eq!(
+            matching("intelligence>50 && price<5", &models()),
+            vec!["GPT-5"]
+        );
+        assert_eq!(
+            matching(
+                "(creator==openai || creator=Acme) && intelligence",
+                &models()
+            ),
+            vec!["GPT-5", "Small Model"]
+        );
+        assert_eq!(matching("!intelligence", &models()), vec!["Unscored"]);
+    }
+
+    #[test]
+    fn test_text_and_date() {
+        assert_eq!(
+            matching("name ~ \"4.5 son\"", &models()),
+            vec!["Claude 4.5 Sonnet"]
+        );
+        assert_eq!(matching("release_date >= 2025-06", &models()).len(), 4);
+        assert!(matching("release_date < 2025-01-01", &models()).is_empty());
+    }
+
+    #[test]
+    fn test_model_selection() {
+        let mut old = model("Old Model", "Acme", Some(70.0), Some(1.0));
+        old.release_date = NaiveDate::from_ymd_opt(2023, 1, 1);
+
+        let selection = ModelSelection {
+            modal: None,
+            filter: Some(Filter::parse("intelligence>60").unwrap()),
+            released_after: NaiveDate::from_ymd_opt(2024, 1, 1),
+            exclude: Vec::new(),
+            min_coverage: None,
+            include_retired: false,
+            tagged: None,
+            policy: None,
+            min_context: None,
+            min_output: None,
+            token_limits: HashMap::new(),
+        };
+        let mut all = models();
+        all.push(old);
+
+        let names: Vec<String> = selection.apply(all).into_iter().map(|m| m.name).collect();
+        assert_eq!(names, vec!["GPT-5", "Claude 4.5 Sonnet"]);
+    }
+
+    #[test]
+    fn test_model_selection_exclude() {
+        let selection = ModelSelection {
+            exclude: vec!["SONNET".to_string(), "small".to_string()],
+            ..Default::default()
+        };
+
+        let names: Vec<String> = selection
+            .apply(models())
+            .into_iter()
+            .map(|m| m.name)
+            .collect();
+        assert_eq!(names, vec!["GPT-5", "Unscored"]);
+    }
+
+    #[test]
+    fn test_model_selection_min_coverage() {
+        let selection = ModelSelection {
+            min_coverage: Some(1),
+            ..Default::default()
+        };
+        let names: Vec<String> = selection
+            .apply(models())
+            .into_iter()
+            .map(|m| m.name)
+            .collect();
+        assert!(!names.contains(&"Unscored".to_string()));
+        assert!(names.contains(&"GPT-5".to_string()));
+
+        let projection = selection.projection(&["price"]).unwrap();
+        assert_eq!(projection.len(), 1 + BENCHMARK_COLUMNS.len());
+        assert!(matching("coverage >= 1", &models()).len() < models().len());
+    }
+
+    #[test]
+    fn test_model_selection_hides_retired() {
+        let mut retired = model("GPT-4", "OpenAI", Some(30.0), Some(37.5));
+        retired.status = Some("deprecated".to_string());
+        let all = vec![retired, model("GPT-5", "OpenAI", Some(68.0), Some(3.4))];
+
+        let names = |selection: ModelSelection| -> Vec<String> {
+            selection
+                .apply(all.clone())
+                .into_iter()
+                .map(|m| m.name)
+                .collect()
+        };
+        assert_eq!(names(ModelSelection::default()), vec!["GPT-5"]);
+        let include = ModelSelection {
+            include_retired: true,
+            ..Default::default()
+        };
+        assert_eq!(names(include), vec!["GPT-4", "GPT-5"]);
+    }
+
+    #[test]
+    fn test_model_selection_tagged() {
+        let all = models();
+        let tagged = ModelSelection {
+            tagged: Some(HashSet::from([all[0].slug.to_lowercase()])),
+            ..Default::default()
+        };
+        let names: Vec<String> = tagged
+            .apply(all.clone())
+            .into_iter()
+            .map(|m| m.name)
+            .collect();
+        assert_eq!(names, vec![all[0].name.clone()]);
+
+        let none = ModelSelection {
+            tagged: Some(HashSet::new()),
+            ..Default::default()
+        };
+        assert!(none.apply(all).is_empty());
+    }
+
+    #[test]
+    fn test_model_selection_token_limits() {
+        let all = models();
+        let limits = |context_window, max_output_tokens| TokenLimits {
+            context_window,
+            max_output_tokens,
+        };
+        let token_limits = HashMap::from([
+            ("gpt-5".to_string(), limits(Some(400_000), Some(128_000))),
+            (
+                "claude-4.5-sonnet".to_string(),
+                limits(Some(200_000), Some(64_000)),
+            ),
+            // Context known, output limit unknown
+            ("small-model".to_string(), limits(Some(1_000_000), None)),
+        ]);
+        let names = |min_context, min_output| -> Vec<String> {
+            ModelSelection {
+                min_context,
+                min_output,
+                token_limits: token_limits.clone(),
+                ..Default::default()
+            }
+            .apply(all.clone())
+            .into_iter()
+            .map(|m| m.name)
+            .collect()
+        };
+
+        // "Unscored" has no known window, so any bound drops it
+        assert_eq!(names(None, None).len(), 4);
+        assert_eq!(
+            names(Some(200_000), None),
+            vec!["GPT-5", "Claude 4.5 Sonnet", "Small Model"]
+        );
+        assert_eq!(names(Some(400_000), None), vec!["GPT-5", "Small Model"]);
+        assert_eq!(
+            names(None, Some(64_000)),
+            vec!["GPT-5", "Claude 4.5 Sonnet"]
+        );
+        assert_eq!(names(Some(300_000), Some(100_000)), vec!["GPT-5"]);
+        assert!(names(Some(2_000_000), None).is_empty());
+    }
+
+    #[test]
+    fn test_model_selection_policy() {
+        let policy = Policy {
+            allowed_creators: vec!["Acme".into()],
+            max_price: Some(1.0),
+            ..Default::default()
+        };
+        let selection = ModelSelection {
+            policy: Some(PolicyCheck::with_capabilities(policy, BTreeMap::new())),
+            ..Default::default()
+        };
+        let names: Vec<String> = selection
+            .apply(models())
+            .into_iter()
+            .map(|m| m.name)
+            .collect();
+        // Unscored has no price, so the price limit cannot be checked
+        assert_eq!(names, vec!["Small Model"]);
+        assert_eq!(
+            selection.projection(&["intelligence"]),
+            Some(vec!["intelligence", "price"])
+        );
+        assert_eq!(selection.projection(&["price"]), Some(vec!["price"]));
+    }
+
+    #[test]
+    fn test_model_selection_projection() {
+        let columns = &["input_price", "output_price"];
+        assert_eq!(
+            ModelSelection::default().projection(columns),
+            Some(vec!["input_price", "output_price"])
+        );
+
+        let recent = ModelSelection {
+            released_after: NaiveDate::from_ymd_opt(2024, 1, 1),
+            ..Default::default()
+        };
+        assert_eq!(
+            recent.projection(columns),
+            Some(vec!["input_price", "output_price", "release_date"])
+        );
+
+        let filtered = ModelSelection {
+            filter: Some(Filter::parse("coding>40").unwrap()),
+            ..Default::default()
+        };
+        assert_eq!(filtered.projection(columns), None);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        for bad in [
+            "",
+            "intelligence >",
+            "unknown_field > 3",
+            "(price < 3",
+            "price < cheap",
+            "price ~ 3",
+            "name == 'open",
+            "price < 3 price",
+            "tool_call == true",
+            "vision",
+        ] {
+            assert!(Filter::parse(bad).is_err(), "expected error for {:?}", bad);
+        }
+    }
+
+    #[test]
+    fn test_capabilities() {
+        let filter = Filter::parse("intelligence>50 && price<3 && tool_call").unwrap();
+        assert_eq!(filter.capabilities(), vec!["tool_call"]);
+
+        let mut all = models();
+        all.push(model("Cheap Agent", "Acme", Some(55.0), Some(1.0)));
+        all.push(model("Cheap Chat", "Acme", Some(52.0), Some(0.5)));
+        // Without a capability lookup, no model is known to have tool calling
+        assert!(all.iter().all(|m| !filter.matches(m)));
+
+        let filter = filter.with_capabilities(BTreeMap::from([(
+            "tool_call",
+            HashSet::from(["cheap-agent".to_string(), "gpt-5".to_string()]),
+        )]));
+        let names: Vec<&str> = all
+            .iter()
+            .filter(|m| filter.matches(m))
+            .map(|m| m.name.as_str())
+            .collect();
+        assert_eq!(names, vec!["Cheap Agent"]);
+
+        let negated = Filter::parse("!open_weights && (reasoning || tool_call)").unwrap();
+        assert_eq!(
+            negated.capabilities(),
+            vec!["open_weights", "reasoning", "tool_call"]
+        );
+    }
+}
@@ -1,35 +1,54 @@
 //! Model matching utilities.
 //!
-//! Substring matching for finding models by name or slug.
+//! Substring or regex matching for finding models by name or slug, plus
+//! edit-distance suggestions when nothing matches.
 
+use crate::error::{AppError, Result};
 use crate::models::LlmModel;
+use regex::{Regex, RegexBuilder};
+
+/// Maximum number of "did you mean" suggestions.
+const MAX_SUGGESTIONS: usize = 5;
 
 /// Filter models by a search term using substring matching.
 ///
-/// Matches against:
-/// - Model slug (case-sensitive substring match)
-/// - Model name (case-insensitive substring match)
+/// Matches against the model slug and name (case-insensitive substring match).
 ///
 /// Returns all models that match the search term.
 pub fn filter_models_by_name<'a>(models: &'a [LlmModel], search: &str) -> Vec<&'a LlmModel> {
     let search_lower = search.to_lowercase();
     models
         .iter()
-        .filter(|m| m.slug.contains(search) || m.name.to_lowercase().contains(&search_lower))
+        .filter(|m| {
+            m.slug.to_lowercase().contains(&search_lower)
+                || m.name.to_lowercase().contains(&search_lower)
+        })
+        .collect()
+}
+
+/// Filter models by a regular expression.
+///
+/// A model matches when the pattern matches its slug or its name.
+pub fn filter_models_by_regex<'a>(models: &'a [LlmModel], pattern: &Regex) -> Vec<&'a LlmModel> {
+    models
+        .iter()
+        .filter(|m| pattern.is_match(&m.slug) || pattern.is_match(&m.name))
         .collect()
 }
 
 /// Filter models by creator using substring matching.
 ///
-/// Matches against:
-/// - Creator slug (case-sensitive substring match)
-/// - Creator name (case-insensitive substring match)
+/// Matches against the creator slug and name (case-insensitive substring match).
 pub fn filter_models_by_creator<'a>(models: &'a [LlmModel], creator: &str) -> Vec<&'a LlmModel> {
     let creator_lower = creator.to_lowercase();
     models
         .iter()
         .filter(|m| {
-            m.creator_slug.as_deref().unwrap_or("").contains(creator)
+            m.creator_slug
+                .as_deref()
+                .unwrap_or("")
+                .to_lowercase()
+                .contains(&creator_lower)
                 || m.creator.to_lowercase().contains(&creator_lower)
         })
         .collect()
@@ -54,6 +73,166 @@ pub fn find_models_by_names<'a>(models: &'a [LlmModel], searches: &[String]) ->
     result
 }
 
+/// Model search terms from the command line, matched as substrings or regexes.
+#[derive(Debug, Clone)]
+pub struct ModelSearch {
+    terms: Vec<String>,
+    patterns: Option<Vec<Regex>>,
+}
+
+impl ModelSearch {
+    /// Build a search from terms, compiling them as case-insensitive regexes
+    /// when `regex` is set.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AppError::Query` if a term is not a valid regex.
+    pub fn new(terms: Vec<String>, regex: bool) -> Result<Self> {
+        let patterns = if regex {
+            let compiled = terms
+                .iter()
+                .map(|term| {
+                    RegexBuilder::new(term)
+                        .case_insensitive(true)
+                        .build()
+                        .map_err(|e| AppError::Query(format!("Invalid regex '{}': {}", term, e)))
+                })
+                .collect::<Result<Vec<_>>>()?;
+            Some(compiled)
+        } else {
+            None
+        };
+
+        Ok(Self { terms, patterns })
+    }
+
+    /// The search terms as given.
+    pub fn terms(&self) -> &[String] {
+        &self.terms
+    }
+
+    /// Find all models matching any term, deduplicated, in term order.
+    pub fn find<'a>(&self, models: &'a [LlmModel]) -> Vec<&'a LlmModel> {
+        let Some(patterns) = &self.patterns else {
+            return find_models_by_names(models, &self.terms);
+        };
+
+        let mut result: Vec<&'a LlmModel> = Vec::new();
+        for pattern in patterns {
+            for model in filter_models_by_regex(models, pattern) {
+                if !result.iter().any(|m| m.id == model.id) {
+                    result.push(model);
+                }
+            }
+        }
+        result
+    }
+
+    /// Whether a slug or name matches any term.
+    ///
+    /// Used for rows that are not `LlmModel`s, such as media models.
+    pub fn matches(&self, slug: &str, name: &str) -> bool {
+        match &self.patterns {
+            Some(patterns) => patterns
+                .iter()
+                .any(|p| p.is_match(slug) || p.is_match(name)),
+            None => {
+                let (slug, name) = (slug.to_lowercase(), name.to_lowercase());
+                self.terms.iter().any(|term| {
+                    let term = term.to_lowercase();
+                    slug.contains(&term) || name.contains(&term)
+                })
+            }
+        }
+    }
+
+    /// Build the error for a search that matched nothing.
+    ///
+    /// Suggestions are only offered for substring searches; edit distance
+    /// to a regex is not meaningful.
+    pub fn not_found_error(&self, models: &[LlmModel]) -> AppError {
+        if self.patterns.is_some() {
+            AppError::NotFound(format!(
+                "No models found matching pattern: {}",
+                self.terms.join(", ")
+            ))
+        } else {
+            no_models_found_error(models, &self.terms)
+        }
+    }
+}
+
+/// Levenshtein edit distance between two strings, by character.
+pub(crate) fn levenshtein(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    let mut curr = vec![0; b.len() + 1];
+
+    for (i, ca) in a.chars().enumerate() {
+        curr[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let cost = usize::from(ca != *cb);
+            curr[j + 1] = (prev[j] + cost).min(prev[j + 1] + 1).min(curr[j] + 1);
+        }
+        std::mem::swap(&mut prev, &mut curr);
+    }
+
+    prev[b.len()]
+}
+
+/// Find the models whose slug or name is closest to a search term.
+///
+/// Returns up to five models, nearest first. Candidates more than half the
+/// length of the search term away (minimum 3 edits) are not suggested.
+pub fn suggest_models<'a>(models: &'a [LlmModel], search: &str) -> Vec<&'a LlmModel> {
+    let search = search.trim().to_lowercase();
+    let search_slug = search.replace([' ', '_'], "-");
+    let max_distance = (search.chars().count() / 2).max(3);
+
+    let mut scored: Vec<(usize, &LlmModel)> = models
+        .iter()
+        .map(|m| {
+            let distance = levenshtein(&search_slug, &m.slug.to_lowercase())
+                .min(levenshtein(&search, &m.name.to_lowercase()));
+            (distance, m)
+        })
+        .filter(|(distance, _)| *distance <= max_distance)
+        .collect();
+    scored.sort_by(|a, b| a.0.cmp(&b.0).then_with(|| a.1.slug.cmp(&b.1.slug)));
+
+    scored
+        .into_iter()
+        .map(|(_, m)| m)
+        .take(MAX_SUGGESTIONS)
+        .collect()
+}
+
+/// Build a "not found" error for searches that matched no models.
+///
+/// Includes up to five "did you mean" suggestions across all search terms.
+pub fn no_models_found_error(models: &[LlmModel], searches: &[String]) -> AppError {
+    let mut message = format!("No models found matching: {}", searches.join(", "));
+
+    let mut suggestions: Vec<&LlmModel> = Vec::new();
+    for search in searches {
+        for model in suggest_models(models, search) {
+            if !suggestions.iter().any(|s| s.id == model.id) {
+                suggestions.push(model);
+            }
+        }
+    }
+    suggestions.truncate(MAX_SUGGESTIONS);
+
+    if !suggestions.is_empty() {
+        message.push_str("\n\nDid you mean:");
+        for model in suggestions {
+            message.push_str(&format!("\n  {} ({})", model.name, model.slug));
+        }
+    }
+
+    AppError::NotFound(message)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -85,6 +264,59 @@ mod tests {
         assert_eq!(matches[0].name, "Claude 4.5 Sonnet");
     }
 
+    #[test]
+    fn test_filter_by_name_ignores_slug_case() {
+        let models = vec![make_model("1", "Llama 3.3", "Meta-Llama-3.3", "Meta")];
+        assert_eq!(filter_models_by_name(&models, "meta-llama").len(), 1);
+        assert_eq!(filter_models_by_name(&models, "META-LLAMA").len(), 1);
+    }
+
+    #[test]
+    fn test_model_search_regex() {
+        let models = vec![
+            make_model("1", "GPT-5 (high)", "gpt-5-high", "OpenAI"),
+            make_model("2", "GPT-5 (medium)", "gpt-5-medium", "OpenAI"),
+            make_model("3", "GPT-5 (low)", "gpt-5-low", "OpenAI"),
+            make_model("4", "GPT-5 mini (high)", "gpt-5-mini-high", "OpenAI"),
+        ];
+
+        let search = ModelSearch::new(vec!["^GPT-5-(high|medium)$".to_string()], true).unwrap();
+        let slugs: Vec<&str> = search
+            .find(&models)
+            .iter()
+            .map(|m| m.slug.as_str())
+            .collect();
+        assert_eq!(slugs, vec!["gpt-5-high", "gpt-5-medium"]);
+
+        let search = ModelSearch::new(vec!["^claude".to_string()], true).unwrap();
+        assert!(search.find(&models).is_empty());
+        assert!(search
+            .not_found_error(&models)
+            .to_string()
+            .contains("matching pattern: ^claude"));
+    }
+
+    #[test]
+    fn test_model_search_matches() {
+        let search = ModelSearch::new(vec!["ELEVEN".to_string()], false).unwrap();
+        assert!(search.matches("elevenlabs-v3", "Eleven v3"));
+        assert!(!search.matches("voice-pro", "Voice Pro"));
+
+        let search = ModelSearch::new(vec!["^voice".to_string()], true).unwrap();
+        assert!(search.matches("voice-pro", "Voice Pro"));
+        assert!(!search.matches("pro-voice", "Pro Voice"));
+    }
+
+    #[test]
+    fn test_model_search_invalid_regex() {
+        let err = ModelSearch::new(vec!["gpt-(5".to_string()], true).unwrap_err();
+        assert!(matches!(err, AppError::Query(_)));
+        assert!(err.to_string().contains("Invalid regex 'gpt-(5'"));
+
+        // Without --regex the same term is a plain substring
+        assert!(ModelSearch::new(vec!["gpt-(5".to_string()], false).is_ok());
+    }
+
     #[test]
     fn test_filter_by_creator() {
         let models = vec![
@@ -110,4 +342,55 @@ mod tests {
         let matches = find_models_by_names(&models, &searches);
         assert_eq!(matches.len(), 2);
     }
+
+    #[test]
+    fn test_levenshtein() {
+        assert_eq!(levenshtein("gpt-5", "gpt-5"), 0);
+        assert_eq!(levenshtein("gtp-5", "gpt-5"), 2);
+        assert_eq!(levenshtein("", "abc"), 3);
+        assert_eq!(levenshtein("claude", "claud"), 1);
+    }
+
+    #[test]
+    fn test_suggest_models() {
+        let models = vec![
+            make_model("1", "GPT-5", "gpt-5", "OpenAI"),
+            make_model("2", "GPT-4o", "gpt-4o", "OpenAI"),
+            make_model("3", "Claude 4.5", "claude-4.5", "Anthropic"),
+        ];
+
+        let suggestions = suggest_models(&models, "gtp5");
+        assert_eq!(suggestions[0].slug, "gpt-5");
+        assert!(suggestions.iter().all(|m| m.slug != "claude-4.5"));
+
+        let suggestions = suggest_models(&models, "cluade 4.5");
+        assert_eq!(suggestions[0].slug, "claude-4.5");
+
+        assert!(suggest_models(&models, "zzzzzzzzzzzzzzzzzzzzzzzz").is_empty());
+    }
+
+    #[test]
+    fn test_no_models_found_error() {
+        let models: Vec<LlmModel> = (0..8)
+            .map(|i| {
+                make_model(
+                    &i.to_string(),
+                    &format!("GPT-{}", i),
+                    &format!("gpt-{}", i),
+                    "OpenAI",
+                )
+            })
+            .collect();
+
+        let err = no_models_found_error(&models, &["gpt-".to_string()]);
+        let AppError::NotFound(message) = err else {
+            panic!("expected NotFound");
+        };
+        assert!(message.starts_with("No models found matching: gpt-"));
+        assert!(message.contains("Did you mean:"));
+        assert_eq!(message.lines().filter(|l| l.starts_with("  ")).count(), 5);
+
+        let err = no_models_found_error(&models, &["completely unrelated search".to_string()]);
+        assert!(!err.to_string().contains("Did you mean"));
+    }
 }
@@ -1,7 +1,20 @@
 //! Utility functions and helpers.
 
+mod dates;
+mod filter;
 mod matching;
+mod score;
 mod tokens;
 
-pub use matching::{filter_models_by_creator, filter_models_by_name, find_models_by_names};
-pub use tokens::parse_tokens;
+pub use dates::{deserialize_date, parse_date, parse_relative_date};
+pub(crate) use filter::{sort_field, sort_field_names, text_field, text_field_names, SortField};
+pub use filter::{within, Filter, ModelSelection};
+pub(crate) use matching::levenshtein;
+pub use matching::{
+    filter_models_by_creator, filter_models_by_name, filter_models_by_regex, find_models_by_names,
+    no_models_found_error, suggest_models, ModelSearch,
+};
+pub use score::{ScoreWeights, Scorer};
+pub use tokens::{
+    chars_per_token, estimate_tokens, format_tokens, parse_tokens, DEFAULT_CHARS_PER_TOKEN,
+};
@@ -0,0 +1,251 @@
+//! Weighted composite scores over `LlmModel` metrics.
+//!
+//! Each metric is min-max normalized across a population of models, so a
+//! weight means the same thing for a 0-100 index as for a price in dollars.
+//! Negative weights penalize a metric (e.g. `price = -0.1`). Named weight
+//! sets are defined in `config.toml`:
+//!
+//! ```toml
+//! [score_profiles.coding_heavy]
+//! coding = 0.6
+//! intelligence = 0.3
+//! price = -0.1
+//! ```
+
+use super::filter::{number_field, number_field_names};
+use crate::config::Config;
+use crate::error::{AppError, Result};
+use crate::models::LlmModel;
+
+/// Metric getter, as used by the filter language.
+type Metric = fn(&LlmModel) -> Option<f64>;
+
+/// Named metric weights for a composite score.
+#[derive(Debug, Clone)]
+pub struct ScoreWeights {
+    /// Name shown next to the score (the profile name).
+    pub label: String,
+    weights: Vec<(String, Metric, f64)>,
+}
+
+impl ScoreWeights {
+    /// Build weights from `metric = weight` pairs.
+    ///
+    /// # Errors
+    ///
+    /// Returns `AppError::Config` for unknown metrics, non-finite weights, or
+    /// an empty or all-zero weight set.
+    pub fn new(label: &str, weights: impl IntoIterator<Item = (String, f64)>) -> Result<Self> {
+        let mut resolved = Vec::new();
+        for (name, weight) in weights {
+            let metric = number_field(&name).ok_or_else(|| {
+                AppError::Config(format!(
+                    "Unknown score metric '{}' in '{}'. Available metrics: {}",
+                    name,
+                    label,
+                    number_field_names().join(", ")
+                ))
+            })?;
+            if !weight.is_finite() {
+                return Err(AppError::Config(format!(
+                    "Invalid weight for '{}' in '{}': must be a finite number",
+                    name, label
+                )));
+            }
+            resolved.push((name.to_lowercase(), metric, weight));
+        }
+        if resolved.iter().all(|(_, _, w)| *w == 0.0) {
+            return Err(AppError::Config(format!(
+                "Score '{}' needs at least one non-zero weight",
+                label
+            )));
+        }
+        Ok(Self {
+            label: label.to_string(),
+            weights: resolved,
+        })
+    }
+
+    /// Load a named profile from `[score_profiles]` in the config.
+    pub fn from_profile(config: &Config, name: &str) -> Result<Self> {
+        let Some(weights) = config.score_profiles.get(name) else {
+            let defined: Vec<&str> = config.score_profiles.keys().map(String::as_str).collect();
+            let hint = if defined.is_empty() {
+                "No profiles are defined; add [score_profiles.<name>] to config.toml".to_string()
+            } else {
+                format!("Defined profiles: {}", defined.join(", "))
+            };
+            return Err(AppError::Config(format!(
+                "Unknown score profile '{}'. {}",
+                name, hint
+            )));
+        };
+        Self::new(name, weights.clone())
+    }
+
+    /// Metric names and weights, in definition order.
+    pub fn weights(&self) -> impl Iterator<Item = (&str, f64)> {
+        self.weights.iter().map(|(name, _, w)| (name.as_str(), *w))
+    }
+
+    /// Prepare a scorer normalized over `population`.
+    pub fn scorer(&self, population: &[LlmModel]) -> Scorer {
+        let metrics = self
+            .weights
+            .iter()
+            .map(|(name, metric, weight)| {
+                let (min, max) = population
+                    .iter()
+                    .filter_map(metric)
+                    .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), v| {
+                        (min.min(v), max.max(v))
+                    });
+                ScoredMetric {
+                    name: name.clone(),
+                    metric: *metric,
+                    weight: *weight,
+                    min,
+                    max,
+                }
+            })
+            .collect();
+        Scorer { metrics }
+    }
+}
+
+/// One weighted metric with its range over the population.
+#[derive(Debug, Clone)]
+struct ScoredMetric {
+    name: String,
+    metric: Metric,
+    weight: f64,
+    min: f64,
+    max: f64,
+}
+
+impl ScoredMetric {
+    /// Points this metric adds to a model's score.
+    ///
+    /// A missing value counts as the worst value for the weight's sign.
+    fn contribution(&self, model: &LlmModel) -> f64 {
+        let normalized = match (self.metric)(model) {
+            Some(v) if self.max > self.min => (v - self.min) / (self.max - self.min),
+            Some(_) => 1.0,
+            None if self.weight < 0.0 => 1.0,
+            None => 0.0,
+        };
+        self.weight * normalized * 100.0
+    }
+}
+
+/// Scores models against weights normalized over a fixed population.
+#[derive(Debug, Clone)]
+pub struct Scorer {
+    metrics: Vec<ScoredMetric>,
+}
+
+impl Scorer {
+    /// Composite score: the weighted sum of normalized metrics, times 100.
+    ///
+    /// With positive weights summing to 1 this is a 0-100 scale.
+    pub fn score(&self, model: &LlmModel) -> f64 {
+        self.metrics.iter().map(|m| m.contribution(model)).sum()
+    }
+
+    /// Per-metric contributions to the score, as `(metric, weight, points)`.
+    ///
+    /// The points sum to [`Scorer::score`].
+    pub fn contributions(&self, model: &LlmModel) -> Vec<(&str, f64, f64)> {
+        self.metrics
+            .iter()
+            .map(|m| (m.name.as_str(), m.weight, m.contribution(model)))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeMap;
+
+    fn make_model(coding: Option<f64>, price: Option<f64>) -> LlmModel {
+        LlmModel {
+            coding,
+            price,
+            ..Default::default()
+        }
+    }
+
+    fn weights() -> ScoreWeights {
+        ScoreWeights::new(
+            "test",
+            [("coding".to_string(), 0.8), ("price".to_string(), -0.2)],
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_score_normalizes_and_penalizes() {
+        let models = vec![
+            make_model(Some(80.0), Some(10.0)),
+            make_model(Some(40.0), Some(0.0)),
+            make_model(Some(60.0), Some(5.0)),
+        ];
+        let scorer = weights().scorer(&models);
+
+        assert!((scorer.score(&models[0]) - 60.0).abs() < 1e-9);
+        assert!((scorer.score(&models[1]) - 0.0).abs() < 1e-9);
+        assert!((scorer.score(&models[2]) - 30.0).abs() < 1e-9);
+
+        // Missing values count as the worst value
+        assert!((scorer.score(&make_model(None, None)) + 20.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_score_contributions_sum_to_score() {
+        let models = vec![
+            make_model(Some(80.0), Some(10.0)),
+            make_model(Some(60.0), Some(5.0)),
+        ];
+        let scorer = weights().scorer(&models);
+        let parts = scorer.contributions(&models[1]);
+
+        assert_eq!(parts.len(), 2);
+        assert_eq!((parts[0].0, parts[0].1), ("coding", 0.8));
+        assert!((parts[0].2 - 0.0).abs() < 1e-9);
+        assert!((parts[1].2 - 0.0).abs() < 1e-9);
+        let parts = scorer.contributions(&models[0]);
+        assert!((parts[0].2 - 80.0).abs() < 1e-9);
+        assert!((parts[1].2 + 20.0).abs() < 1e-9);
+        let total: f64 = parts.iter().map(|p| p.2).sum();
+        assert!((total - scorer.score(&models[0])).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_score_weights_validation() {
+        let err = ScoreWeights::new("p", [("vibes".to_string(), 1.0)]).unwrap_err();
+        assert_eq!(err.exit_code(), 2);
+        assert!(err.to_string().contains("Unknown score metric 'vibes'"));
+
+        assert!(ScoreWeights::new("p", [("coding".to_string(), 0.0)]).is_err());
+        assert!(ScoreWeights::new("p", [("coding".to_string(), f64::NAN)]).is_err());
+    }
+
+    #[test]
+    fn test_score_profile_from_config() {
+        let mut config = Config::default();
+        let err = ScoreWeights::from_profile(&config, "coding_heavy").unwrap_err();
+        assert!(err.to_string().contains("No profiles are defined"));
+
+        let profile: BTreeMap<String, f64> = [("coding".to_string(), 0.6)].into_iter().collect();
+        config
+            .score_profiles
+            .insert("coding_heavy".to_string(), profile);
+        let weights = ScoreWeights::from_profile(&config, "coding_heavy").unwrap();
+        assert_eq!(weights.label, "coding_heavy");
+        assert_eq!(weights.weights().collect::<Vec<_>>(), vec![("coding", 0.6)]);
+
+        let err = ScoreWeights::from_profile(&config, "other").unwrap_err();
+        assert!(err.to_string().contains("Defined profiles: coding_heavy"));
+    }
+}
@@ -1,6 +1,7 @@
-//! Token count parsing utilities.
+//! Token count utilities.
 //!
-//! Parses human-friendly token counts like "10k", "1.5M", "10000".
+//! Parses human-friendly token counts like "10k", "1.5M", "10000", formats
+//! counts back into that form, and estimates token counts from characters.
 
 use crate::error::{AppError, Result};
 
@@ -76,6 +77,57 @@ pub fn parse_tokens(s: &str) -> Result<u64> {
     Ok(result)
 }
 
+/// Format a token count for display, the inverse of [`parse_tokens`].
+///
+/// Counts of a million or more are rounded to one decimal (`1.5M`);
+/// thousands keep one decimal unless exact (`10K`, `1.5K`).
+pub fn format_tokens(tokens: u64) -> String {
+    if tokens >= 1_000_000 {
+        format!("{:.1}M", tokens as f64 / 1_000_000.0)
+    } else if tokens >= 1_000 {
+        if tokens % 1_000 == 0 {
+            format!("{}K", tokens / 1_000)
+        } else {
+            format!("{:.1}K", tokens as f64 / 1_000.0)
+        }
+    } else {
+        tokens.to_string()
+    }
+}
+
+/// Characters per token assumed when the tokenizer is unknown.
+pub const DEFAULT_CHARS_PER_TOKEN: f64 = 4.0;
+
+/// Approximate characters per token for English text, by creator slug.
+///
+/// Rough averages of each vendor's tokenizer; good for budgeting, not billing.
+const CHARS_PER_TOKEN: &[(&str, f64)] = &[
+    ("openai", 4.0),
+    ("anthropic", 3.5),
+    ("google", 4.0),
+    ("meta", 3.8),
+    ("mistral", 3.6),
+    ("deepseek", 3.8),
+    ("alibaba", 3.6),
+    ("xai", 3.8),
+];
+
+/// Characters per token for a model creator, falling back to the default.
+pub fn chars_per_token(creator_slug: Option<&str>) -> f64 {
+    creator_slug
+        .and_then(|slug| {
+            CHARS_PER_TOKEN
+                .iter()
+                .find(|(creator, _)| slug.eq_ignore_ascii_case(creator))
+        })
+        .map_or(DEFAULT_CHARS_PER_TOKEN, |(_, ratio)| *ratio)
+}
+
+/// Estimate the token count of `chars` characters, rounded up.
+pub fn estimate_tokens(chars: u64, chars_per_token: f64) -> u64 {
+    (chars as f64 / chars_per_token).ceil() as u64
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -112,6 +164,29 @@ mod tests {
         assert_eq!(parse_tokens("1B").unwrap(), 1000000000);
     }
 
+    #[test]
+    fn test_format_tokens() {
+        assert_eq!(format_tokens(950), "950");
+        assert_eq!(format_tokens(10_000), "10K");
+        assert_eq!(format_tokens(1_500), "1.5K");
+        assert_eq!(format_tokens(1_500_000), "1.5M");
+        for count in ["950", "10K", "1.5K", "1.5M"] {
+            assert_eq!(format_tokens(parse_tokens(count).unwrap()), count);
+        }
+    }
+
+    #[test]
+    fn test_estimate_tokens() {
+        assert_eq!(chars_per_token(Some("Anthropic")), 3.5);
+        assert_eq!(
+            chars_per_token(Some("unknown-lab")),
+            DEFAULT_CHARS_PER_TOKEN
+        );
+        assert_eq!(chars_per_token(None), DEFAULT_CHARS_PER_TOKEN);
+        assert_eq!(estimate_tokens(10, 4.0), 3);
+        assert_eq!(estimate_tokens(0, 4.0), 0);
+    }
+
     #[test]
     fn test_whitespace() {
         assert_eq!(parse_tokens(" 10k ").unwrap(), 10000);
@@ -65,6 +65,35 @@ fn test_cache_status() {
         .stdout(predicate::str::contains("Cache Status"));
 }
 
+#[test]
+fn test_cache_clear_table() {
+    let temp = tempfile::tempdir().unwrap();
+    let cache_dir = temp.path().join("cache").join("which-llm");
+    std::fs::create_dir_all(&cache_dir).unwrap();
+    for name in ["benchmarks.parquet", "text_to_image.parquet"] {
+        std::fs::write(cache_dir.join(name), "data").unwrap();
+    }
+
+    cmd_with_temp_config(&temp)
+        .args(["cache", "clear", "--table", "benchmarks"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Cleared table 'benchmarks'"));
+    assert!(!cache_dir.join("benchmarks.parquet").exists());
+    assert!(cache_dir.join("text_to_image.parquet").exists());
+
+    cmd_with_temp_config(&temp)
+        .args(["cache", "clear", "--table", "benchmark"])
+        .assert()
+        .code(2)
+        .stderr(predicate::str::contains("Did you mean 'benchmarks'?"));
+
+    cmd_with_temp_config(&temp)
+        .args(["cache", "clear", "--table", "models", "--json-only"])
+        .assert()
+        .failure();
+}
+
 #[test]
 fn test_refresh_api_mode_requires_api_key() {
     let temp = tempfile::tempdir().unwrap();
@@ -76,6 +105,256 @@ fn test_refresh_api_mode_requires_api_key() {
         .stderr(predicate::str::contains("No API key configured"));
 }
 
+#[test]
+fn test_format_flags_on_every_table_command() {
+    for command in [
+        "query",
+        "compare",
+        "cost",
+        "report",
+        "migrate-cost",
+        "route",
+        "shortlist",
+        "recommend",
+        "llms",
+        "open",
+        "unmatched",
+        "media",
+        "stats",
+        "trend",
+        "doctor",
+        "quota",
+    ] {
+        let assert = cmd().args([command, "--help"]).assert().success();
+        let help = String::from_utf8_lossy(&assert.get_output().stdout).into_owned();
+        for flag in ["--json", "--csv", "--table", "--plain", "--porcelain"] {
+            assert!(
+                help.contains(flag),
+                "{} --help is missing {}",
+                command,
+                flag
+            );
+        }
+        cmd()
+            .args([command, "--json", "--csv"])
+            .assert()
+            .failure()
+            .stderr(predicate::str::contains("cannot be used with"));
+    }
+}
+
+#[test]
+fn test_cost_media_modes_reject_model_selection() {
+    for args in [
+        vec![
+            "cost",
+            "tts-1",
+            "--characters",
+            "1k",
+            "--filter",
+            "tool_call",
+        ],
+        vec![
+            "cost",
+            "veo",
+            "--media",
+            "text-to-video",
+            "--seconds",
+            "8",
+            "--min-context",
+            "128k",
+        ],
+    ] {
+        cmd()
+            .args(&args)
+            .assert()
+            .failure()
+            .stderr(predicate::str::contains("cannot be used with"));
+    }
+}
+
+#[test]
+fn test_use_api_and_profile_rejected_where_unused() {
+    let temp = tempfile::tempdir().unwrap();
+    for args in [
+        ["tables", "--use-api"],
+        ["stats", "--use-api"],
+        ["open", "--use-api"],
+        ["info", "--use-api"],
+        ["query", "--use-api"],
+        ["stats", "--profile=work"],
+    ] {
+        cmd_with_temp_config(&temp)
+            .args(args)
+            .assert()
+            .code(2)
+            .stderr(predicate::str::contains(
+                "--use-api and --profile only apply to commands that fetch benchmarks",
+            ));
+    }
+}
+
+#[test]
+fn test_api_key_flag_precedence() {
+    let temp = tempfile::tempdir().unwrap();
+    cmd_with_temp_config(&temp)
+        .args(["profile", "create", "work", "--api-key", "profile-key-0001"])
+        .assert()
+        .success();
+
+    cmd_with_temp_config(&temp)
+        .args(["profile", "show", "--effective"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Source: profile 'work' (default)"));
+
+    cmd_with_temp_config(&temp)
+        .args(["--profile", "missing", "profile", "show", "--effective"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Profile 'missing' not found"));
+
+    cmd_with_temp_config(&temp)
+        .env("ARTIFICIAL_ANALYSIS_API_KEY", "env-key-000000002")
+        .args(["profile", "show", "--effective"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Source: $ARTIFICIAL_ANALYSIS_API_KEY",
+        ));
+
+    cmd_with_temp_config(&temp)
+        .env("ARTIFICIAL_ANALYSIS_API_KEY", "env-key-000000002")
+        .args([
+            "profile",
+            "show",
+            "--effective",
+            "--api-key",
+            "flag-key-00000003",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Source: --api-key"))
+        .stdout(predicate::str::contains("flag-key...0003"));
+
+    cmd_with_temp_config(&temp)
+        .args(["stats", "--api-key", "flag-key"])
+        .assert()
+        .code(2)
+        .stderr(predicate::str::contains("--api-key only applies"));
+}
+
+#[test]
+fn test_get_use_api_requires_api_key() {
+    let temp = tempfile::tempdir().unwrap();
+    cmd_with_temp_config(&temp)
+        .args(["get", "gpt-4o", "--use-api"])
+        .assert()
+        .code(2)
+        .stderr(predicate::str::contains("No API key configured"));
+}
+
+#[test]
+fn test_compare_stdin_requires_names() {
+    let temp = tempfile::tempdir().unwrap();
+    cmd_with_temp_config(&temp)
+        .args(["compare", "-"])
+        .write_stdin("\n\n")
+        .assert()
+        .code(2)
+        .stderr(predicate::str::contains("No model names on stdin"));
+}
+
+#[test]
+fn test_agent_mode() {
+    let temp = tempfile::tempdir().unwrap();
+    let assert = cmd_with_temp_config(&temp)
+        .env("WHICH_LLM_AGENT", "1")
+        .args(["query", "SELECT 1 AS x"])
+        .assert()
+        .success();
+    let json: serde_json::Value = serde_json::from_slice(&assert.get_output().stdout).unwrap();
+    assert_eq!(json["data"], serde_json::json!([{ "x": 1 }]));
+    assert_eq!(json["metadata"]["envelope_version"], 1);
+    assert!(json["metadata"]["attribution"].is_array());
+
+    // Errors are JSON, and prompts are refused
+    cmd_with_temp_config(&temp)
+        .env("WHICH_LLM_AGENT", "1")
+        .args(["profile", "create", "work"])
+        .assert()
+        .code(2)
+        .stderr(predicate::str::contains(r#""code":"config""#));
+
+    // A falsey value leaves agent mode off
+    cmd_with_temp_config(&temp)
+        .env("WHICH_LLM_AGENT", "0")
+        .args(["query", "SELECT 1 AS x"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("| x |"));
+}
+
+#[test]
+fn test_attribution_in_exports() {
+    let temp = tempfile::tempdir().unwrap();
+    let assert = cmd_with_temp_config(&temp)
+        .args(["query", "SELECT 1 AS x", "--json", "--attribution"])
+        .assert()
+        .success();
+    let json: serde_json::Value = serde_json::from_slice(&assert.get_output().stdout).unwrap();
+    assert_eq!(json["data"], serde_json::json!([{ "x": 1 }]));
+    assert!(json["_meta"]["attribution"][0]
+        .as_str()
+        .unwrap()
+        .contains("Artificial Analysis"));
+    assert!(json["_meta"]["generator"]
+        .as_str()
+        .unwrap()
+        .starts_with("which-llm "));
+
+    cmd_with_temp_config(&temp)
+        .args(["query", "SELECT 1 AS x", "--csv", "--attribution"])
+        .assert()
+        .success()
+        .stdout(predicate::str::starts_with(
+            "# Data provided by Artificial Analysis",
+        ))
+        .stdout(predicate::str::contains("\nx\n1\n"));
+
+    // Without the flag, exports are unchanged
+    cmd_with_temp_config(&temp)
+        .args(["query", "SELECT 1 AS x", "--csv"])
+        .assert()
+        .success()
+        .stdout(predicate::str::starts_with("x\n1\n"));
+}
+
+#[test]
+fn test_errors_json_output_and_exit_code() {
+    let temp = tempfile::tempdir().unwrap();
+    cmd_with_temp_config(&temp)
+        .arg("refresh")
+        .arg("--use-api")
+        .arg("--errors-json")
+        .assert()
+        .code(2)
+        .stderr(predicate::str::contains(r#""code":"no_api_key""#))
+        .stderr(predicate::str::contains(r#""hint":"#));
+}
+
+#[test]
+fn test_profile_not_found_exit_code() {
+    let temp = tempfile::tempdir().unwrap();
+    cmd_with_temp_config(&temp)
+        .arg("profile")
+        .arg("show")
+        .arg("missing")
+        .assert()
+        .code(5)
+        .stderr(predicate::str::contains("Profile 'missing' not found"));
+}
+
 #[test]
 fn test_query_help() {
     cmd()
@@ -99,6 +378,21 @@ fn test_tables_command() {
         .stdout(predicate::str::contains("not cached"));
 }
 
+#[test]
+fn test_tables_descriptions() {
+    let temp = tempfile::tempdir().unwrap();
+    cmd_with_temp_config(&temp)
+        .args(["tables", "--verbose"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("3 input : 1 output"));
+    cmd_with_temp_config(&temp)
+        .args(["tables", "benchmarks"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("higher is better"));
+}
+
 #[test]
 fn test_query_no_sql() {
     let temp = tempfile::tempdir().unwrap();
@@ -109,10 +403,52 @@ fn test_query_no_sql() {
         .stderr(predicate::str::contains("No SQL query provided"));
 }
 
+#[test]
+fn test_query_interactive() {
+    let temp = tempfile::tempdir().unwrap();
+    cmd_with_temp_config(&temp)
+        .args(["query", "--interactive", "--no-bootstrap", "--csv"])
+        .write_stdin("SELECT 40 +\n 2 AS answer;\n.nope\nSELECT * FROM nowhere;\n.quit\n")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("answer\n42"))
+        .stderr(predicate::str::contains("Unknown command '.nope'"))
+        .stderr(predicate::str::contains("Error:"));
+}
+
+#[test]
+fn test_schema_dump_and_diff() {
+    let temp = tempfile::tempdir().unwrap();
+    let assert = cmd_with_temp_config(&temp)
+        .args(["schema", "dump"])
+        .assert()
+        .success();
+    let dump = String::from_utf8_lossy(&assert.get_output().stdout).into_owned();
+    let saved = temp.path().join("schema.json");
+    std::fs::write(&saved, &dump).unwrap();
+    cmd_with_temp_config(&temp)
+        .args(["schema", "diff", "--table"])
+        .arg(&saved)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("No schema changes"));
+
+    let old = dump.replacen("\"benchmarks\"", "\"benchmarks_v0\"", 1);
+    std::fs::write(&saved, old).unwrap();
+    cmd_with_temp_config(&temp)
+        .args(["schema", "diff", "--csv"])
+        .arg(&saved)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("benchmarks,-,table added"))
+        .stdout(predicate::str::contains("benchmarks_v0,-,table removed"));
+}
+
 #[test]
 fn test_query_missing_table() {
     let temp = tempfile::tempdir().unwrap();
     cmd_with_temp_config(&temp)
+        .env("WHICH_LLM_NO_BOOTSTRAP", "1")
         .arg("query")
         .arg("SELECT * FROM benchmarks")
         .assert()
@@ -120,3 +456,649 @@ fn test_query_missing_table() {
         .stderr(predicate::str::contains("not found"))
         .stderr(predicate::str::contains("which-llm refresh"));
 }
+
+#[test]
+fn test_bootstrap_only_for_commands_reading_tables() {
+    let temp = tempfile::tempdir().unwrap();
+    cmd_with_temp_config(&temp)
+        .args(["query", "SELECT 1 AS x"])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("First run").not());
+    cmd_with_temp_config(&temp)
+        .args(["tokens", "1.5M"])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("First run").not());
+}
+
+#[test]
+fn test_query_suggests_table_name() {
+    let temp = tempfile::tempdir().unwrap();
+    cmd_with_temp_config(&temp)
+        .arg("query")
+        .arg("SELECT * FROM benchmark")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Did you mean 'benchmarks'?"));
+}
+
+#[test]
+fn test_subcommand_typo_suggestion() {
+    cmd()
+        .arg("comapre")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("compare"));
+}
+
+#[test]
+fn test_fail_on_empty_exit_code() {
+    let temp = tempfile::tempdir().unwrap();
+    let sql = "SELECT 1 AS x WHERE 1 = 0";
+    cmd_with_temp_config(&temp)
+        .args(["query", sql])
+        .assert()
+        .success();
+    cmd_with_temp_config(&temp)
+        .args(["query", sql, "--fail-on-empty"])
+        .assert()
+        .code(6)
+        .stderr(predicate::str::contains("No results"));
+    cmd_with_temp_config(&temp)
+        .args(["query", "SELECT 1 AS x", "--fail-on-empty"])
+        .assert()
+        .success();
+}
+
+#[test]
+fn test_query_porcelain() {
+    let temp = tempfile::tempdir().unwrap();
+    cmd_with_temp_config(&temp)
+        .args([
+            "query",
+            "SELECT 1 AS a, 'x' || chr(9) || 'y' AS b",
+            "--porcelain",
+            "--json",
+        ])
+        .assert()
+        .success()
+        .stdout("a\tb\n1\tx\\ty\n");
+    cmd_with_temp_config(&temp)
+        .args(["query", "SELECT 1 AS a WHERE 1 = 0", "--porcelain"])
+        .assert()
+        .success()
+        .stdout("a\n");
+}
+
+#[test]
+fn test_examples() {
+    cmd()
+        .args(["cost", "--examples"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "which-llm cost \"gpt-5 (high)\" --input 10k",
+        ));
+    cmd()
+        .args(["cost", "--help"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Examples:"));
+    cmd()
+        .arg("examples")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("compare:"))
+        .stdout(predicate::str::contains("route:"));
+    cmd().args(["examples", "nope"]).assert().code(5);
+}
+
+#[test]
+fn test_tokens() {
+    cmd()
+        .args(["tokens", "1.5M", "250k", "--csv"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("1.5M,1500000,1.5M"))
+        .stdout(predicate::str::contains("250k,250000,250K"));
+
+    let temp = tempfile::tempdir().unwrap();
+    let path = temp.path().join("prompt.txt");
+    std::fs::write(&path, "a".repeat(4000)).unwrap();
+    cmd()
+        .args(["tokens", "--csv", "--chars"])
+        .arg(&path)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("4000,default,4.0,1000,1K"));
+
+    cmd().args(["tokens", "lots"]).assert().code(2);
+}
+
+#[test]
+fn test_batch() {
+    let temp = tempfile::tempdir().unwrap();
+    let path = temp.path().join("batch.txt");
+    std::fs::write(
+        &path,
+        "# conversions\ntokens 1.5M --csv\n\nwhich-llm query 'SELECT 42 AS answer' --csv\n",
+    )
+    .unwrap();
+    cmd_with_temp_config(&temp)
+        .arg("batch")
+        .arg(&path)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("1.5M,1500000,1.5M"))
+        .stdout(predicate::str::contains("answer\n42"))
+        .stderr(predicate::str::contains("==> tokens 1.5M --csv"));
+
+    // A line that does not parse stops the batch before anything runs
+    std::fs::write(&path, "tokens 1k --csv\ntokens lots --csv --bogus\n").unwrap();
+    cmd_with_temp_config(&temp)
+        .arg("batch")
+        .arg(&path)
+        .assert()
+        .code(2)
+        .stdout(predicate::str::is_empty())
+        .stderr(predicate::str::contains("Line 2"));
+
+    // A failing command stops the batch, unless --keep-going
+    std::fs::write(&path, "tokens lots\ntokens 1k --csv\n").unwrap();
+    cmd_with_temp_config(&temp)
+        .arg("batch")
+        .arg(&path)
+        .assert()
+        .code(2)
+        .stdout(predicate::str::is_empty())
+        .stderr(predicate::str::contains("Line 1 failed: tokens lots"));
+    cmd_with_temp_config(&temp)
+        .args(["batch", "--keep-going"])
+        .arg(&path)
+        .assert()
+        .code(2)
+        .stdout(predicate::str::contains("1k,1000,1K"))
+        .stderr(predicate::str::contains("1 batch line(s) failed"));
+}
+
+#[test]
+fn test_batch_inherits_process_settings() {
+    let temp = tempfile::tempdir().unwrap();
+    let path = temp.path().join("batch.txt");
+    std::fs::write(&path, "tokens 1k --csv\n").unwrap();
+
+    // Settings given to the batch itself, by flag or environment, apply to
+    // every line rather than being rejected as set on the line
+    cmd_with_temp_config(&temp)
+        .env("WHICH_LLM_HOME", temp.path().join("home"))
+        .env("WHICH_LLM_CHANNEL", "stable")
+        .env("WHICH_LLM_DATE_FORMAT", "iso")
+        .arg("--ascii")
+        .arg("batch")
+        .arg(&path)
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("1k,1000,1K"));
+    cmd_with_temp_config(&temp)
+        .arg("--home")
+        .arg(temp.path().join("home"))
+        .arg("batch")
+        .arg(&path)
+        .assert()
+        .success();
+}
+
+#[test]
+fn test_read_only_cache() {
+    let temp = tempfile::tempdir().unwrap();
+    let cache_dir = temp.path().join("cache").join("which-llm");
+
+    // Reads work and nothing is created
+    cmd_with_temp_config(&temp)
+        .env("WHICH_LLM_READONLY", "1")
+        .args(["query", "SELECT 1 AS x"])
+        .assert()
+        .success();
+    cmd_with_temp_config(&temp)
+        .env("WHICH_LLM_READONLY", "1")
+        .args(["cache", "status"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("read-only"));
+    assert!(!cache_dir.exists());
+
+    // Only explicit writes fail
+    for args in [&["refresh"][..], &["cache", "clear"]] {
+        cmd_with_temp_config(&temp)
+            .env("WHICH_LLM_READONLY", "1")
+            .args(args)
+            .assert()
+            .code(2)
+            .stderr(predicate::str::contains("the cache is read-only"));
+    }
+}
+
+#[test]
+fn test_home_holds_config_and_cache() {
+    let temp = tempfile::tempdir().unwrap();
+    let home = temp.path().join("state");
+    cmd()
+        .env_remove("WHICH_LLM_CONFIG_DIR")
+        .env_remove("WHICH_LLM_CACHE_DIR")
+        .arg("--home")
+        .arg(&home)
+        .args(["profile", "create", "work", "--api-key", "test-key"])
+        .assert()
+        .success();
+    assert!(home.join("config").join("config.toml").exists());
+
+    cmd()
+        .env_remove("WHICH_LLM_CONFIG_DIR")
+        .env_remove("WHICH_LLM_CACHE_DIR")
+        .env("WHICH_LLM_HOME", &home)
+        .args(["cache", "status"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            home.join("cache").display().to_string(),
+        ));
+
+    // The specific directory variables take precedence
+    let cache_dir = temp.path().join("elsewhere");
+    cmd()
+        .env("WHICH_LLM_CACHE_DIR", &cache_dir)
+        .arg("--home")
+        .arg(&home)
+        .args(["cache", "status"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(cache_dir.display().to_string()));
+}
+
+#[test]
+fn test_channel_echoed_in_info() {
+    let temp = tempfile::tempdir().unwrap();
+    cmd_with_temp_config(&temp)
+        .env_remove("WHICH_LLM_CHANNEL")
+        .args(["info", "--json"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(r#""channel": "stable""#));
+
+    cmd_with_temp_config(&temp)
+        .args(["--channel", "nightly", "info"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Channel: nightly"));
+
+    let config_dir = temp.path().join("config").join("which-llm");
+    std::fs::create_dir_all(&config_dir).unwrap();
+    std::fs::write(config_dir.join("config.toml"), "channel = \"nightly\"\n").unwrap();
+    cmd_with_temp_config(&temp)
+        .env_remove("WHICH_LLM_CHANNEL")
+        .args(["info", "--json"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(r#""channel": "nightly""#));
+
+    cmd_with_temp_config(&temp)
+        .args(["--channel", "beta", "info"])
+        .assert()
+        .code(2)
+        .stderr(predicate::str::contains("Unknown data channel 'beta'"));
+}
+
+#[test]
+fn test_trend_needs_snapshots() {
+    let temp = tempfile::tempdir().unwrap();
+    cmd_with_temp_config(&temp)
+        .args(["trend", "--metric", "input_price", "--window", "90d"])
+        .assert()
+        .code(5)
+        .stderr(predicate::str::contains("refresh --snapshot"));
+
+    cmd_with_temp_config(&temp)
+        .args(["trend", "--metric", "vibes"])
+        .assert()
+        .code(2)
+        .stderr(predicate::str::contains("Unknown trend metric 'vibes'"));
+
+    cmd_with_temp_config(&temp)
+        .args(["trend", "--metric", "price", "--window", "soon"])
+        .assert()
+        .code(2)
+        .stderr(predicate::str::contains("Invalid --window 'soon'"));
+}
+
+#[test]
+fn test_changelog_needs_snapshot() {
+    let temp = tempfile::tempdir().unwrap();
+    cmd_with_temp_config(&temp)
+        .args(["changelog", "--since", "14d"])
+        .assert()
+        .code(5)
+        .stderr(predicate::str::contains("No snapshots are stored"));
+}
+
+#[test]
+fn test_notes_and_tags_lists() {
+    let temp = tempfile::tempdir().unwrap();
+    cmd_with_temp_config(&temp)
+        .args(["tag", "list"])
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("No tagged models."));
+
+    let notes = temp
+        .path()
+        .join("config")
+        .join("which-llm")
+        .join("notes.toml");
+    std::fs::create_dir_all(notes.parent().unwrap()).unwrap();
+    std::fs::write(
+        &notes,
+        "[models.gpt-4o]\nnotes = [\"approved for prod\"]\ntags = [\"prod\"]\n",
+    )
+    .unwrap();
+    cmd_with_temp_config(&temp)
+        .args(["note", "list", "gpt-4o"])
+        .assert()
+        .success()
+        .stdout("gpt-4o\n  1. approved for prod\n");
+    cmd_with_temp_config(&temp)
+        .args(["tag", "list", "--tag", "prod"])
+        .assert()
+        .success()
+        .stdout("gpt-4o\tprod\n");
+}
+
+#[test]
+fn test_policy_file_errors() {
+    let temp = tempfile::tempdir().unwrap();
+    cmd_with_temp_config(&temp)
+        .args(["policy", "check", "gpt-4o", "--no-bootstrap"])
+        .assert()
+        .code(2)
+        .stderr(predicate::str::contains("policy.toml not found"));
+
+    let policy = temp.path().join("policy.toml");
+    std::fs::write(
+        &policy,
+        "max_price = 10.0\nrequired_capabilities = [\"telepathy\"]\n",
+    )
+    .unwrap();
+    cmd_with_temp_config(&temp)
+        .args(["policy", "check", "gpt-4o", "--no-bootstrap", "--policy"])
+        .arg(&policy)
+        .assert()
+        .code(2)
+        .stderr(predicate::str::contains("Unknown capability 'telepathy'"));
+}
+
+#[test]
+fn test_report_workloads_errors() {
+    let temp = tempfile::tempdir().unwrap();
+    let workloads = temp.path().join("workloads.yaml");
+    cmd_with_temp_config(&temp)
+        .args(["report", "--no-bootstrap", "--workloads"])
+        .arg(&workloads)
+        .assert()
+        .code(2)
+        .stderr(predicate::str::contains("Cannot read workloads file"));
+
+    std::fs::write(
+        &workloads,
+        "workloads:\n  - name: support-bot\n    model: gpt-4o\n",
+    )
+    .unwrap();
+    cmd_with_temp_config(&temp)
+        .args(["report", "--no-bootstrap", "--workloads"])
+        .arg(&workloads)
+        .assert()
+        .code(2)
+        .stderr(predicate::str::contains("Invalid workloads file"));
+}
+
+#[test]
+fn test_invalid_date_format() {
+    let temp = tempfile::tempdir().unwrap();
+    cmd_with_temp_config(&temp)
+        .args(["tables", "--date-format", "long"])
+        .assert()
+        .code(2)
+        .stderr(predicate::str::contains("Invalid --date-format 'long'"));
+}
+
+#[test]
+fn test_audit_pricing_errors() {
+    let temp = tempfile::tempdir().unwrap();
+    cmd_with_temp_config(&temp)
+        .args(["audit", "pricing", "--no-bootstrap", "--threshold", "nan"])
+        .assert()
+        .code(2)
+        .stderr(predicate::str::contains("Invalid --threshold"));
+    cmd_with_temp_config(&temp)
+        .args(["audit", "pricing", "--no-bootstrap"])
+        .assert()
+        .code(2)
+        .stderr(predicate::str::contains("benchmarks table is not cached"));
+}
+
+#[test]
+fn test_recommend_rejects_unknown_optimize() {
+    let temp = tempfile::tempdir().unwrap();
+    cmd_with_temp_config(&temp)
+        .args(["recommend", "--no-bootstrap", "--optimize", "fastest"])
+        .assert()
+        .code(2)
+        .stderr(predicate::str::contains("Unknown --optimize 'fastest'"));
+}
+
+#[test]
+fn test_llms_plan_reads_models_for_capabilities() {
+    let temp = tempfile::tempdir().unwrap();
+    cmd_with_temp_config(&temp)
+        .args(["llms", "--min-intelligence", "50", "--plan"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("benchmarks.parquet"))
+        .stdout(predicate::str::contains("models.parquet").not());
+
+    cmd_with_temp_config(&temp)
+        .args(["llms", "--open-weights", "--reasoning", "--plan"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("models.parquet"));
+}
+
+#[test]
+fn test_migrate_cost_usage_errors() {
+    let temp = tempfile::tempdir().unwrap();
+    let usage = temp.path().join("usage.csv");
+    let migrate = |temp: &tempfile::TempDir| {
+        let mut cmd = cmd_with_temp_config(temp);
+        cmd.args([
+            "migrate-cost",
+            "--no-bootstrap",
+            "--from",
+            "gpt-4o",
+            "--to",
+            "claude-sonnet",
+            "--usage",
+        ])
+        .arg(&usage);
+        cmd
+    };
+    migrate(&temp)
+        .assert()
+        .code(2)
+        .stderr(predicate::str::contains("Cannot read usage file"));
+
+    std::fs::write(&usage, "timestamp,tokens\n2025-01-01,100\n").unwrap();
+    migrate(&temp)
+        .assert()
+        .code(2)
+        .stderr(predicate::str::contains("no input_tokens column"));
+}
+
+#[test]
+fn test_quota_without_requests() {
+    let temp = tempfile::tempdir().unwrap();
+    cmd_with_temp_config(&temp)
+        .args(["profile", "create", "work", "--api-key", "test-key"])
+        .assert()
+        .success();
+    cmd_with_temp_config(&temp)
+        .args(["quota", "--all-profiles", "--csv"])
+        .assert()
+        .success()
+        .stdout(predicate::str::starts_with(
+            "Profile,Key,Limit,Remaining,Reset,Updated\nwork,",
+        ))
+        .stdout(predicate::str::contains("test-key").not());
+    cmd_with_temp_config(&temp)
+        .args(["quota", "--profile", "missing"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Profile 'missing' not found"));
+}
+
+#[test]
+fn test_query_strict_requires_cached_data() {
+    let temp = tempfile::tempdir().unwrap();
+    cmd_with_temp_config(&temp)
+        .arg("query")
+        .arg("--strict")
+        .arg("SELECT * FROM benchmarks")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Strict mode"))
+        .stderr(predicate::str::contains("not cached"));
+}
+
+#[test]
+fn test_invalid_filter_fails_before_fetching() {
+    let temp = tempfile::tempdir().unwrap();
+    cmd_with_temp_config(&temp)
+        .arg("compare")
+        .arg("gpt")
+        .arg("claude")
+        .arg("--filter")
+        .arg("tool_call && price <")
+        .assert()
+        .code(1)
+        .stderr(predicate::str::contains("Invalid filter"));
+}
+
+#[test]
+fn test_invalid_regex_fails_before_fetching() {
+    let temp = tempfile::tempdir().unwrap();
+    cmd_with_temp_config(&temp)
+        .arg("compare")
+        .arg("-e")
+        .arg("^gpt-(5")
+        .arg("claude")
+        .assert()
+        .code(1)
+        .stderr(predicate::str::contains("Invalid regex"));
+}
+
+#[test]
+fn test_invalid_released_after() {
+    let temp = tempfile::tempdir().unwrap();
+    cmd_with_temp_config(&temp)
+        .arg("cost")
+        .arg("gpt")
+        .arg("--input")
+        .arg("1k")
+        .arg("--output")
+        .arg("1k")
+        .arg("--released-after")
+        .arg("last spring")
+        .assert()
+        .code(2)
+        .stderr(predicate::str::contains("Invalid --released-after"));
+}
+
+#[test]
+fn test_invalid_min_coverage() {
+    let temp = tempfile::tempdir().unwrap();
+    cmd_with_temp_config(&temp)
+        .args(["compare", "gpt", "claude", "--min-coverage", "11"])
+        .assert()
+        .code(2)
+        .stderr(predicate::str::contains("Invalid --min-coverage 11"));
+}
+
+#[test]
+fn test_log_file_records_sql() {
+    let temp = tempfile::tempdir().unwrap();
+    let log_path = temp.path().join("logs").join("which-llm.log");
+    cmd_with_temp_config(&temp)
+        .arg("query")
+        .arg("SELECT 42 AS answer")
+        .arg("--log-file")
+        .arg(&log_path)
+        .assert()
+        .success();
+
+    let log = std::fs::read_to_string(&log_path).unwrap();
+    assert!(log.contains("DEBUG sql: SELECT 42 AS answer"));
+    assert!(log.contains("DEBUG sql: 1 rows"));
+}
+
+#[test]
+fn test_plan_does_not_fetch() {
+    let temp = tempfile::tempdir().unwrap();
+    cmd_with_temp_config(&temp)
+        .arg("compare")
+        .arg("gpt")
+        .arg("claude")
+        .arg("--plan")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("manifest.json"))
+        .stdout(predicate::str::contains("benchmarks.parquet"));
+
+    let cache_dir = temp.path().join("cache").join("which-llm");
+    assert!(!cache_dir.join("benchmarks.parquet").exists());
+}
+
+#[test]
+fn test_info_json() {
+    let temp = tempfile::tempdir().unwrap();
+    let output = cmd_with_temp_config(&temp)
+        .arg("info")
+        .arg("--json")
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+
+    let info: serde_json::Value = serde_json::from_slice(&output).unwrap();
+    assert_eq!(info["version"], env!("CARGO_PKG_VERSION"));
+    assert_eq!(info["source"]["type"], "none");
+    assert!(info["data"].is_null());
+}
+
+#[test]
+#[allow(deprecated)]
+fn test_aa_alias_names_itself() {
+    let temp = tempfile::tempdir().unwrap();
+    Command::cargo_bin("aa")
+        .unwrap()
+        .arg("--help")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Usage: aa"));
+
+    Command::cargo_bin("aa")
+        .unwrap()
+        .env("WHICH_LLM_CONFIG_DIR", temp.path().join("config"))
+        .env("WHICH_LLM_CACHE_DIR", temp.path().join("cache"))
+        .arg("query")
+        .assert()
+        .stderr(predicate::str::contains("Use 'aa tables'"));
+}